@@ -0,0 +1,3 @@
+//! Bibliothèques dynamique et statique de l'interface C de keeprust (voir `include/keeprust.h`)
+
+pub use keeprust::ffi::*;
\ No newline at end of file
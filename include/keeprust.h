@@ -0,0 +1,41 @@
+/* Interface C de KeepRust (voir src/ffi.rs), compilée par cargo build -p keeprust-ffi (libkeeprust_ffi) */
+#ifndef KEEPRUST_H
+#define KEEPRUST_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct KrVault KrVault;
+
+/* Ouvre une base de données; NULL en cas d'erreur (voir kr_last_error) */
+KrVault *kr_vault_open(const char *path, const char *password);
+
+/* Sauvegarde la base de données comme la ligne de commande (scripts, signature, index de recherche); 0 en cas de succès, -1 sinon */
+int32_t kr_vault_save(KrVault *vault);
+
+/* Libère une base de données ouverte */
+void kr_vault_free(KrVault *vault);
+
+/* Liste toutes les entrées (JSON, sans mot de passe) */
+char *kr_vault_list(const KrVault *vault);
+
+/* Recherche des entrées hors corbeille avec la syntaxe de `keeprust list --query` (JSON) */
+char *kr_vault_search(const KrVault *vault, const char *query);
+
+/* Mot de passe d'une entrée hors corbeille */
+char *kr_vault_get_password(const KrVault *vault, const char *entry_id);
+
+/* Dernière erreur du thread courant, ou NULL */
+char *kr_last_error(void);
+
+/* Libère et efface une chaîne retournée par la bibliothèque */
+void kr_string_free(char *value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEEPRUST_H */
@@ -0,0 +1,709 @@
+use base64::{Engine as _, engine::general_purpose};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::models::repository::FileKey;
+use crate::utils::symbols;
+
+use super::profiles::Profiles;
+use super::session::Session;
+
+/// Délai de verrouillage par défaut d'une base confiée à l'agent, en minutes
+pub const DEFAULT_LOCK_AFTER: u64 = 15;
+
+/// Variable d'environnement désignant le socket de l'agent
+pub const SOCKET_ENV: &str = "KEEPRUST_AGENT_SOCK";
+
+/// Requête adressée à l'agent (une ligne JSON par connexion)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "kebab-case")]
+enum Request {
+    /// Confie à l'agent la clé dérivée du fichier actuel d'une base (jamais son mot de passe maître)
+    Unlock { path: PathBuf, key: WireKey, lock_after: Option<u64> },
+    
+    /// Clé dérivée d'une base déverrouillée; son délai de verrouillage repart de zéro
+    Fetch { path: PathBuf },
+    
+    /// Clé dérivée pour le nouveau sel d'une base déverrouillée (après un enregistrement)
+    Key { path: PathBuf, key: WireKey },
+    
+    /// Oublie la clé d'une base, ou de toutes
+    Lock { path: Option<PathBuf> },
+    
+    /// Bases déverrouillées
+    List,
+}
+
+/// Réponse de l'agent
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum Response {
+    Done { count: usize },
+    Key { key: WireKey },
+    Vaults { vaults: Vec<VaultStatus> },
+    Error { message: String },
+}
+
+/// Clé dérivée échangée avec l'agent : sel de l'en-tête et clé (base64, effacée de la mémoire à
+/// la libération)
+#[derive(Debug, Serialize, Deserialize)]
+struct WireKey {
+    salt: String,
+    key: String,
+}
+
+impl Drop for WireKey {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl WireKey {
+    fn new(file_key: &FileKey) -> Self {
+        Self::from_bytes(&file_key.salt, &*file_key.key)
+    }
+    
+    fn from_bytes(salt: &str, key: &[u8]) -> Self {
+        Self { salt: salt.to_string(), key: general_purpose::STANDARD.encode(key) }
+    }
+    
+    fn file_key(&self) -> Option<FileKey> {
+        let bytes = Zeroizing::new(general_purpose::STANDARD.decode(&self.key).ok()?);
+        let key = <[u8; 32]>::try_from(bytes.as_slice()).ok()?;
+        Some(FileKey { salt: self.salt.clone(), key: Zeroizing::new(key) })
+    }
+}
+
+/// Base déverrouillée dans l'agent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VaultStatus {
+    pub path: PathBuf,
+    
+    /// Secondes avant verrouillage (None : jamais)
+    pub remaining: Option<u64>,
+}
+
+/// Emplacement du socket : KEEPRUST_AGENT_SOCK, sinon le répertoire d'exécution de la session
+/// (XDG_RUNTIME_DIR), sinon le répertoire de configuration
+pub fn socket_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(SOCKET_ENV).filter(|path| !path.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    let directory = std::env::var_os("XDG_RUNTIME_DIR").map(|runtime| PathBuf::from(runtime).join("keeprust"))
+        .or_else(crate::events::config_dir)?;
+    Some(directory.join("agent.sock"))
+}
+
+/// Clé dérivée d'une base déverrouillée dans l'agent : elle ouvre le fichier tant qu'il n'a pas
+/// été réenregistré par une autre application; None si l'agent ne tourne pas ou ne la connaît pas
+pub fn cached(path: &Path) -> Option<FileKey> {
+    match client::request(&Request::Fetch { path: super::profiles::absolute(path) }) {
+        Ok(Response::Key { key }) => key.file_key(),
+        _ => None,
+    }
+}
+
+/// Confie à l'agent la clé dérivée du fichier actuel d'une base
+pub fn unlock(path: &Path, key: &FileKey, lock_after: Option<u64>) -> Result<(), String> {
+    let request = Request::Unlock {
+        path: super::profiles::absolute(path),
+        key: WireKey::new(key),
+        lock_after,
+    };
+    match client::request(&request)? {
+        Response::Done { .. } => Ok(()),
+        response => Err(unexpected(response)),
+    }
+}
+
+/// Transmet la clé dérivée du fichier tel qu'il vient d'être enregistré ou lu; sans effet si
+/// l'agent ne tourne pas ou si la base n'y est pas déverrouillée
+pub fn remember_key(path: &Path, key: &FileKey) {
+    let _ = client::request(&Request::Key { path: super::profiles::absolute(path), key: WireKey::new(key) });
+}
+
+/// Fait oublier à l'agent la clé d'une base (toutes si None); retourne le nombre de bases verrouillées
+pub fn lock(path: Option<&Path>) -> Result<usize, String> {
+    match client::request(&Request::Lock { path: path.map(super::profiles::absolute) })? {
+        Response::Done { count } => Ok(count),
+        response => Err(unexpected(response)),
+    }
+}
+
+/// Bases déverrouillées dans l'agent
+pub fn vaults() -> Result<Vec<VaultStatus>, String> {
+    match client::request(&Request::List)? {
+        Response::Vaults { vaults } => Ok(vaults),
+        response => Err(unexpected(response)),
+    }
+}
+
+/// Base désignée par un profil, sinon la base courante, avec le délai de verrouillage de son profil
+fn target(path: &Path, profile: Option<&str>) -> Result<(PathBuf, Option<u64>), String> {
+    let profiles = Profiles::load()?;
+    Ok(match profile {
+        Some(name) => {
+            let profile = profiles.get(name)?;
+            (profile.path.clone(), profile.lock_after)
+        }
+        None => (path.to_path_buf(), profiles.for_path(path).and_then(|(_, profile)| profile.lock_after)),
+    })
+}
+
+/// État d'une base dans l'agent
+fn describe(status: Option<&VaultStatus>) -> String {
+    match status {
+        None => "verrouillée".to_string(),
+        Some(VaultStatus { remaining: None, .. }) => "déverrouillée (sans délai)".to_string(),
+        Some(VaultStatus { remaining: Some(seconds), .. }) => format!("déverrouillée (verrouillage dans {} min)", seconds.div_ceil(60)),
+    }
+}
+
+/// Liste les profils et les bases déverrouillées dans l'agent
+pub fn cmd_vaults_list() -> Result<(), String> {
+    let profiles = Profiles::load()?;
+    let unlocked = match vaults() {
+        Ok(unlocked) => unlocked,
+        Err(e) => {
+            println!("{}", e);
+            Vec::new()
+        }
+    };
+    if profiles.profiles.is_empty() && unlocked.is_empty() {
+        println!("Aucun profil: définissez vos bases dans {}.", super::profiles::profiles_path().map(|path| path.display().to_string()).unwrap_or_default());
+        return Ok(());
+    }
+    
+    let width = profiles.profiles.keys().map(|name| name.chars().count()).max().unwrap_or(0).max(1);
+    let print = |name: &str, path: &Path, status: Option<&VaultStatus>| {
+        if symbols::is_plain() {
+            println!("Profil: {}; base: {}; état: {}", name, path.display(), describe(status));
+        } else {
+            println!("{:width$}  {}  {}", name, path.display(), describe(status), width = width);
+        }
+    };
+    for (name, profile) in &profiles.profiles {
+        let path = super::profiles::absolute(&profile.path);
+        print(name, &profile.path, unlocked.iter().find(|status| status.path == path));
+    }
+    // Bases déverrouillées sans profil (keeprust -d ... vaults unlock)
+    for status in unlocked.iter().filter(|status| profiles.for_path(&status.path).is_none()) {
+        print("aucun", &status.path, Some(status));
+    }
+    Ok(())
+}
+
+/// Déverrouille une base dans l'agent après avoir vérifié son mot de passe
+pub fn cmd_vaults_unlock(path: &Path, profile: Option<&str>, lock_after: Option<u64>) -> Result<(), String> {
+    let (path, profile_lock_after) = target(path, profile)?;
+    // Vérifier que l'agent tourne avant de demander le mot de passe
+    vaults()?;
+    
+    let session = Session::open(&path)?;
+    let lock_after = lock_after.or(profile_lock_after);
+    session.share_with_agent(lock_after)?;
+    match lock_after {
+        Some(0) => println!("{} déverrouillée dans l'agent (sans délai).", path.display()),
+        Some(minutes) => println!("{} déverrouillée dans l'agent ({} minute(s) sans utilisation).", path.display(), minutes),
+        None => println!("{} déverrouillée dans l'agent.", path.display()),
+    }
+    session.close()
+}
+
+/// Verrouille une base dans l'agent, ou toutes
+pub fn cmd_vaults_lock(path: &Path, profile: Option<&str>, all: bool) -> Result<(), String> {
+    if all {
+        println!("{} base(s) verrouillée(s).", lock(None)?);
+        return Ok(());
+    }
+    let (path, _) = target(path, profile)?;
+    if lock(Some(&path))? > 0 {
+        println!("{} verrouillée.", path.display());
+    } else {
+        println!("{} n'était pas déverrouillée dans l'agent.", path.display());
+    }
+    Ok(())
+}
+
+/// Verrouille toutes les bases déverrouillées dans l'agent (keeprust lock)
+pub fn cmd_lock() -> Result<(), String> {
+    match lock(None)? {
+        0 => println!("Aucune base n'était déverrouillée dans l'agent."),
+        count => println!("{} base(s) verrouillée(s): le mot de passe maître sera redemandé.", count),
+    }
+    Ok(())
+}
+
+fn unexpected(response: Response) -> String {
+    match response {
+        Response::Error { message } => message,
+        _ => "Réponse inattendue de l'agent.".to_string(),
+    }
+}
+
+#[cfg(not(unix))]
+mod client {
+    use super::{Request, Response};
+    
+    pub(super) fn request(_request: &Request) -> Result<Response, String> {
+        Err("L'agent keeprust nécessite les sockets Unix (Linux, macOS, BSD).".to_string())
+    }
+}
+
+#[cfg(unix)]
+mod client {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+    use std::time::Duration;
+    use zeroize::Zeroizing;
+    
+    use super::{Request, Response, socket_path};
+    
+    /// Envoie une requête à l'agent et lit sa réponse
+    pub(super) fn request(request: &Request) -> Result<Response, String> {
+        let path = socket_path().ok_or("Emplacement du socket de l'agent introuvable.")?;
+        let mut stream = UnixStream::connect(&path).map_err(|_| "L'agent keeprust n'est pas lancé ('keeprust agent').".to_string())?;
+        stream.set_read_timeout(Some(Duration::from_secs(10))).map_err(|e| e.to_string())?;
+        
+        let mut line = Zeroizing::new(serde_json::to_string(request).map_err(|e| e.to_string())?);
+        line.push('\n');
+        stream.write_all(line.as_bytes()).map_err(|e| format!("Agent injoignable: {}", e))?;
+        
+        let mut reply = Zeroizing::new(String::new());
+        BufReader::new(stream).read_line(&mut reply).map_err(|e| format!("Agent injoignable: {}", e))?;
+        serde_json::from_str(&reply).map_err(|e| format!("Réponse de l'agent illisible: {}", e))
+    }
+}
+
+/// Garde en mémoire les clés dérivées des bases déverrouillées jusqu'à Ctrl+C; les commandes
+/// ouvrant l'une d'elles ne demandent plus le mot de passe
+#[cfg(not(unix))]
+pub fn cmd_agent(_lock_after: u64, _keychain: bool) -> Result<(), String> {
+    Err("L'agent keeprust nécessite les sockets Unix (Linux, macOS, BSD).".to_string())
+}
+
+#[cfg(unix)]
+pub use server::cmd_agent;
+
+#[cfg(unix)]
+mod server {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::io::{self, BufRead, BufReader, Write};
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+    use std::thread;
+    use std::time::{Duration, Instant, SystemTime};
+    use zeroize::Zeroizing;
+    
+    use crate::cli::login_item;
+    use crate::crypto::cipher::{self, EncryptedData};
+    use crate::utils::symbols::Symbol;
+    use super::memory::{self, Locked};
+    use super::{Request, Response, VaultStatus, WireKey, socket_path};
+    
+    /// Écart entre l'horloge murale et l'horloge monotone au-delà duquel une mise en veille est
+    /// supposée : l'horloge monotone s'arrête pendant la veille (Linux, macOS)
+    const SUSPEND_GAP: Duration = Duration::from_secs(30);
+    
+    /// Clé d'enveloppe des secrets gardés en mémoire, lue dans le trousseau macOS (agent lancé
+    /// à l'ouverture de session par 'agent enable-login')
+    static WRAPPING_KEY: OnceLock<Locked> = OnceLock::new();
+    
+    fn wrapping_key() -> Option<&'static [u8; 32]> {
+        WRAPPING_KEY.get().and_then(|key| key.bytes().try_into().ok())
+    }
+    
+    /// Garde des octets en mémoire verrouillée, chiffrés si l'agent a une clé d'enveloppe
+    fn seal(bytes: &[u8], key: Option<&[u8; 32]>) -> Result<Locked, String> {
+        let Some(key) = key else {
+            return Ok(Locked::new(bytes));
+        };
+        let sealed = cipher::encrypt(bytes, key).map_err(|e| e.to_string())?;
+        let sealed = Zeroizing::new(serde_json::to_vec(&sealed).map_err(|e| e.to_string())?);
+        Ok(Locked::new(&sealed))
+    }
+    
+    /// Octets gardés par `seal`, déchiffrés le temps de la réponse
+    fn unseal(locked: &Locked, key: Option<&[u8; 32]>) -> Result<Zeroizing<Vec<u8>>, String> {
+        let Some(key) = key else {
+            return Ok(Zeroizing::new(locked.bytes().to_vec()));
+        };
+        let sealed: EncryptedData = serde_json::from_slice(locked.bytes()).map_err(|e| e.to_string())?;
+        cipher::decrypt(&sealed, key).map(Zeroizing::new).map_err(|e| format!("Secret illisible dans l'agent: {}", e))
+    }
+    
+    /// Base déverrouillée : sa clé dérivée et son délai de verrouillage
+    struct Unlocked {
+        /// Sel du fichier et clé dérivée correspondante
+        key: (String, Locked),
+        
+        lock_after: Option<Duration>,
+        last_used: Instant,
+    }
+    
+    impl Unlocked {
+        fn remaining(&self) -> Option<Duration> {
+            self.lock_after.map(|lock_after| lock_after.saturating_sub(self.last_used.elapsed()))
+        }
+        
+        /// Clé dérivée, déchiffrée pour la réponse au client
+        fn reveal(&self) -> Result<Response, String> {
+            let (salt, key) = &self.key;
+            let key = unseal(key, wrapping_key())?;
+            Ok(Response::Key { key: WireKey::from_bytes(salt, &key) })
+        }
+    }
+    
+    /// Clé reçue d'un client, gardée en mémoire verrouillée
+    fn lock_key(key: &WireKey) -> Result<(String, Locked), String> {
+        let file_key = key.file_key().ok_or("Clé dérivée invalide.")?;
+        Ok((file_key.salt, seal(&*file_key.key, wrapping_key())?))
+    }
+    
+    /// Bases déverrouillées, par chemin absolu
+    type Vaults = Arc<Mutex<HashMap<PathBuf, Unlocked>>>;
+    
+    fn vaults(shared: &Vaults) -> MutexGuard<'_, HashMap<PathBuf, Unlocked>> {
+        shared.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+    
+    /// Traite une requête; `default_lock_after` s'applique aux bases sans délai propre
+    fn handle(shared: &Vaults, request: Request, default_lock_after: u64) -> Response {
+        let mut vaults = vaults(shared);
+        match request {
+            Request::Unlock { path, key, lock_after } => {
+                let minutes = lock_after.unwrap_or(default_lock_after);
+                let lock_after = (minutes > 0).then(|| Duration::from_secs(minutes * 60));
+                let key = match lock_key(&key) {
+                    Ok(key) => key,
+                    Err(message) => return Response::Error { message },
+                };
+                let unlocked = Unlocked {
+                    key,
+                    lock_after,
+                    last_used: Instant::now(),
+                };
+                vaults.insert(path.clone(), unlocked);
+                eprintln!("Déverrouillée: {}", path.display());
+                Response::Done { count: 1 }
+            }
+            Request::Fetch { path } => match vaults.get_mut(&path) {
+                Some(unlocked) => {
+                    unlocked.last_used = Instant::now();
+                    unlocked.reveal().unwrap_or_else(|message| Response::Error { message })
+                }
+                None => Response::Error { message: format!("{} n'est pas déverrouillée dans l'agent.", path.display()) },
+            },
+            Request::Key { path, key } => match vaults.get_mut(&path) {
+                Some(unlocked) => match lock_key(&key) {
+                    Ok(key) => {
+                        unlocked.key = key;
+                        Response::Done { count: 1 }
+                    }
+                    Err(message) => Response::Error { message },
+                },
+                // Une base verrouillée ne doit pas laisser de clé dans l'agent
+                None => Response::Done { count: 0 },
+            },
+            Request::Lock { path: Some(path) } => {
+                let count = usize::from(vaults.remove(&path).is_some());
+                if count > 0 {
+                    eprintln!("Verrouillée: {}", path.display());
+                }
+                Response::Done { count }
+            }
+            Request::Lock { path: None } => {
+                let count = vaults.len();
+                vaults.clear();
+                eprintln!("{} base(s) verrouillée(s).", count);
+                Response::Done { count }
+            }
+            Request::List => {
+                let mut list: Vec<VaultStatus> = vaults.iter()
+                    .map(|(path, unlocked)| VaultStatus { path: path.clone(), remaining: unlocked.remaining().map(|remaining| remaining.as_secs()) })
+                    .collect();
+                list.sort_by(|a, b| a.path.cmp(&b.path));
+                Response::Vaults { vaults: list }
+            }
+        }
+    }
+    
+    fn serve(stream: UnixStream, shared: &Vaults, default_lock_after: u64) -> io::Result<()> {
+        // Seul l'utilisateur qui a lancé l'agent peut l'interroger, même si les droits du socket changent
+        let uid = memory::peer_uid(&stream)?;
+        if uid != memory::current_uid() {
+            return Err(io::Error::new(io::ErrorKind::PermissionDenied, format!("client d'un autre utilisateur refusé (uid {})", uid)));
+        }
+        
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+        let mut line = Zeroizing::new(String::new());
+        BufReader::new(&stream).read_line(&mut line)?;
+        let response = match serde_json::from_str(&line) {
+            Ok(request) => handle(shared, request, default_lock_after),
+            Err(e) => Response::Error { message: format!("Requête invalide: {}", e) },
+        };
+        
+        let mut reply = Zeroizing::new(serde_json::to_string(&response).map_err(io::Error::other)?);
+        reply.push('\n');
+        (&stream).write_all(reply.as_bytes())
+    }
+    
+    /// Verrouille les bases inutilisées depuis leur délai
+    fn expire(shared: &Vaults) {
+        vaults(shared).retain(|path, unlocked| {
+            let keep = unlocked.remaining().is_none_or(|remaining| !remaining.is_zero());
+            if !keep {
+                eprintln!("Verrouillée (délai écoulé): {}", path.display());
+            }
+            keep
+        });
+    }
+    
+    /// Indique si l'horloge murale a avancé nettement plus que l'horloge monotone, c'est-à-dire
+    /// si le système a été mis en veille (un réglage de l'heure vers l'avant verrouille aussi)
+    fn suspended(wall: Duration, monotonic: Duration) -> bool {
+        wall.saturating_sub(monotonic) > SUSPEND_GAP
+    }
+    
+    /// Verrouille les bases arrivées à leur délai, et toutes les bases au retour de veille
+    fn watch(shared: &Vaults) {
+        let (mut wall, mut monotonic) = (SystemTime::now(), Instant::now());
+        loop {
+            thread::sleep(Duration::from_secs(1));
+            let (now_wall, now_monotonic) = (SystemTime::now(), Instant::now());
+            let wall_elapsed = now_wall.duration_since(wall).unwrap_or_default();
+            if suspended(wall_elapsed, now_monotonic - monotonic) {
+                let mut vaults = vaults(shared);
+                if !vaults.is_empty() {
+                    eprintln!("Mise en veille détectée: {} base(s) verrouillée(s).", vaults.len());
+                    vaults.clear();
+                }
+            }
+            (wall, monotonic) = (now_wall, now_monotonic);
+            expire(shared);
+        }
+    }
+    
+    /// Garde en mémoire les clés dérivées des bases déverrouillées (keeprust vaults unlock)
+    /// jusqu'à Ctrl+C; les commandes ouvrant l'une d'elles ne demandent plus le mot de passe et
+    /// ne refont pas la dérivation de clé. Le mot de passe maître n'est jamais confié à l'agent :
+    /// une clé ouvre et enregistre le fichier pour lequel elle a été dérivée, sans permettre d'en
+    /// changer le mot de passe ni d'ouvrir les autres bases protégées par le même mot de passe.
+    ///
+    /// Le socket n'est accessible qu'à l'utilisateur (répertoire 0700, socket 0600, identité du
+    /// client vérifiée); les secrets restent en mémoire verrouillée, hors du swap et des vidages
+    /// mémoire, et toutes les bases se verrouillent à la mise en veille. Avec `keychain`, ils y
+    /// sont en outre chiffrés avec la clé d'enveloppe du trousseau macOS.
+    pub fn cmd_agent(lock_after: u64, keychain: bool) -> Result<(), String> {
+        let path = socket_path().ok_or("Emplacement du socket de l'agent introuvable (définissez KEEPRUST_AGENT_SOCK).")?;
+        if UnixStream::connect(&path).is_ok() {
+            return Err(format!("Un agent keeprust est déjà lancé ({}).", path.display()));
+        }
+        if let Some(directory) = path.parent() {
+            fs::DirBuilder::new().recursive(true).mode(0o700).create(directory)
+                .map_err(|e| format!("Impossible de créer {}: {}", directory.display(), e))?;
+        }
+        memory::harden_process();
+        if keychain {
+            let key = login_item::wrapping_key()?;
+            let _ = WRAPPING_KEY.set(Locked::new(&*key));
+        }
+        
+        // Socket d'un agent arrêté brutalement
+        let _ = fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).map_err(|e| format!("Impossible d'écouter sur {}: {}", path.display(), e))?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).map_err(|e| e.to_string())?;
+        
+        let shared: Vaults = Arc::new(Mutex::new(HashMap::new()));
+        let watched = shared.clone();
+        thread::spawn(move || watch(&watched));
+        
+        println!("Agent keeprust à l'écoute sur {}.", path.display());
+        if lock_after > 0 {
+            println!("Les bases se verrouillent après {} minute(s) sans utilisation (sauf délai de leur profil) et à la mise en veille.", lock_after);
+        } else {
+            println!("Les bases se verrouillent à la mise en veille.");
+        }
+        if keychain {
+            println!("Les secrets gardés en mémoire sont chiffrés avec la clé du trousseau.");
+        }
+        println!("Déverrouillez une base avec 'keeprust vaults unlock'. Ctrl+C pour arrêter.");
+        
+        for stream in listener.incoming() {
+            let result = stream.and_then(|stream| serve(stream, &shared, lock_after));
+            if let Err(e) = result {
+                eprintln!("{}Requête ignorée: {}", Symbol::Warning, e);
+            }
+        }
+        Ok(())
+    }
+    
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::models::repository::FileKey;
+        
+        #[test]
+        fn test_unlock_key_and_expiry() {
+            let shared: Vaults = Arc::new(Mutex::new(HashMap::new()));
+            let travail = PathBuf::from("/srv/travail.krs");
+            let perso = PathBuf::from("/srv/perso.krs");
+            let key = |byte: u8| WireKey::new(&FileKey { salt: "c2Vs".to_string(), key: Zeroizing::new([byte; 32]) });
+            handle(&shared, Request::Unlock { path: travail.clone(), key: key(1), lock_after: Some(0) }, 15);
+            handle(&shared, Request::Unlock { path: perso.clone(), key: key(2), lock_after: None }, 15);
+            
+            match handle(&shared, Request::Fetch { path: perso.clone() }, 15) {
+                Response::Key { key } => assert_eq!(*key.file_key().unwrap().key, [2u8; 32]),
+                response => panic!("{:?}", response),
+            }
+            match handle(&shared, Request::List, 15) {
+                Response::Vaults { vaults } => {
+                    assert_eq!(vaults.len(), 2);
+                    assert_eq!(vaults[0].path, perso);
+                    assert!(vaults[0].remaining.is_some_and(|remaining| remaining > 14 * 60));
+                    assert_eq!(vaults[1].remaining, None);
+                }
+                response => panic!("{:?}", response),
+            }
+            
+            // Délai écoulé : seule la base sans délai reste déverrouillée
+            vaults(&shared).get_mut(&perso).unwrap().lock_after = Some(Duration::ZERO);
+            expire(&shared);
+            assert!(matches!(handle(&shared, Request::Fetch { path: perso }, 15), Response::Error { .. }));
+            assert!(matches!(handle(&shared, Request::Lock { path: None }, 15), Response::Done { count: 1 }));
+            assert!(matches!(handle(&shared, Request::Fetch { path: travail }, 15), Response::Error { .. }));
+        }
+        
+        #[test]
+        fn test_derived_key_update_and_suspend() {
+            let shared: Vaults = Arc::new(Mutex::new(HashMap::new()));
+            let path = PathBuf::from("/srv/travail.krs");
+            let key = FileKey { salt: "c2Vs".to_string(), key: Zeroizing::new([7u8; 32]) };
+            let saved = FileKey { salt: "bm91dmVhdQ==".to_string(), key: Zeroizing::new([8u8; 32]) };
+            
+            // Une clé n'est retenue que pour une base déverrouillée
+            let request = Request::Key { path: path.clone(), key: WireKey::new(&saved) };
+            assert!(matches!(handle(&shared, request, 15), Response::Done { count: 0 }));
+            handle(&shared, Request::Unlock { path: path.clone(), key: WireKey::new(&key), lock_after: None }, 15);
+            handle(&shared, Request::Key { path: path.clone(), key: WireKey::new(&saved) }, 15);
+            
+            match handle(&shared, Request::Fetch { path }, 15) {
+                Response::Key { key } => {
+                    let cached = key.file_key().unwrap();
+                    assert_eq!((cached.salt, *cached.key), (saved.salt.clone(), *saved.key));
+                }
+                response => panic!("{:?}", response),
+            }
+            
+            // Aucun mot de passe dans le protocole
+            let request = serde_json::to_string(&Request::Unlock { path: PathBuf::from("/srv/a.krs"), key: WireKey::new(&key), lock_after: None }).unwrap();
+            assert_eq!(serde_json::from_str::<serde_json::Value>(&request).unwrap().as_object().unwrap().keys().collect::<Vec<_>>(), ["key", "lock_after", "op", "path"]);
+            
+            // Secrets chiffrés avec la clé d'enveloppe (agent lancé à l'ouverture de session)
+            let wrapping = [3u8; 32];
+            let sealed = seal(b"s1", Some(&wrapping)).unwrap();
+            assert_ne!(sealed.bytes(), b"s1");
+            assert_eq!(unseal(&sealed, Some(&wrapping)).unwrap().as_slice(), b"s1");
+            assert!(unseal(&sealed, Some(&[4u8; 32])).is_err());
+            
+            assert!(suspended(Duration::from_secs(3600), Duration::from_secs(1)));
+            assert!(!suspended(Duration::from_secs(2), Duration::from_secs(1)));
+        }
+    }
+}
+
+/// Mémoire verrouillée et identité des clients de l'agent
+#[cfg(unix)]
+mod memory {
+    use std::io;
+    use std::os::fd::AsRawFd;
+    use std::os::unix::net::UnixStream;
+    use std::sync::Once;
+    use zeroize::Zeroize;
+    
+    use crate::utils::symbols::Symbol;
+    
+    /// Octets gardés en mémoire vive (jamais écrits dans le swap), effacés à la libération
+    pub(super) struct Locked(Box<[u8]>);
+    
+    impl Locked {
+        pub(super) fn new(bytes: &[u8]) -> Self {
+            let bytes: Box<[u8]> = bytes.into();
+            // SAFETY : la zone désignée appartient à `bytes`, qui vit aussi longtemps que `Locked`
+            if !bytes.is_empty() && unsafe { libc::mlock(bytes.as_ptr().cast(), bytes.len()) } != 0 {
+                static WARNING: Once = Once::new();
+                let error = io::Error::last_os_error();
+                WARNING.call_once(|| eprintln!("{}Verrouillage en mémoire impossible ({}): les secrets pourraient être écrits dans le swap.", Symbol::Warning, error));
+            }
+            Self(bytes)
+        }
+        
+        pub(super) fn bytes(&self) -> &[u8] {
+            &self.0
+        }
+    }
+    
+    impl Drop for Locked {
+        fn drop(&mut self) {
+            self.0.zeroize();
+            if !self.0.is_empty() {
+                // SAFETY : même zone que lors du mlock; un échec (zone non verrouillée) est sans conséquence
+                unsafe { libc::munlock(self.0.as_ptr().cast(), self.0.len()) };
+            }
+        }
+    }
+    
+    /// Interdit les vidages mémoire (core dumps) et, sous Linux, l'inspection du processus par
+    /// les autres processus de l'utilisateur (ptrace)
+    pub(super) fn harden_process() {
+        let limit = libc::rlimit { rlim_cur: 0, rlim_max: 0 };
+        // SAFETY : appels système sans pointeur conservé
+        unsafe {
+            libc::setrlimit(libc::RLIMIT_CORE, &limit);
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            libc::prctl(libc::PR_SET_DUMPABLE, 0);
+        }
+    }
+    
+    /// Utilisateur effectif de l'agent
+    pub(super) fn current_uid() -> u32 {
+        // SAFETY : geteuid ne peut pas échouer
+        unsafe { libc::geteuid() }
+    }
+    
+    /// Utilisateur du processus connecté au socket (SO_PEERCRED)
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(super) fn peer_uid(stream: &UnixStream) -> io::Result<u32> {
+        let mut credentials = libc::ucred { pid: 0, uid: 0, gid: 0 };
+        let mut len = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+        // SAFETY : `credentials` et `len` décrivent un tampon valide de la taille attendue
+        let result = unsafe {
+            libc::getsockopt(stream.as_raw_fd(), libc::SOL_SOCKET, libc::SO_PEERCRED, (&raw mut credentials).cast(), &mut len)
+        };
+        if result != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(credentials.uid)
+    }
+    
+    /// Utilisateur du processus connecté au socket (getpeereid)
+    #[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd", target_os = "openbsd", target_os = "netbsd", target_os = "dragonfly"))]
+    pub(super) fn peer_uid(stream: &UnixStream) -> io::Result<u32> {
+        let (mut uid, mut gid) = (0, 0);
+        // SAFETY : `uid` et `gid` sont des emplacements valides
+        if unsafe { libc::getpeereid(stream.as_raw_fd(), &mut uid, &mut gid) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(uid)
+    }
+    
+    /// Identité du client non vérifiable : les connexions sont refusées
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "freebsd", target_os = "openbsd", target_os = "netbsd", target_os = "dragonfly")))]
+    pub(super) fn peer_uid(_stream: &UnixStream) -> io::Result<u32> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "identité du client non vérifiable sur ce système"))
+    }
+}
@@ -1,14 +1,87 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+use crate::crypto::key_derivation::KdfParams;
+use crate::models::entry::ColorLabel;
+use crate::models::export::ExportFormat;
+use crate::models::import::ImportFormat;
+use crate::models::id::GroupId;
+use crate::models::lookup::EntryRef;
+use crate::models::replace::TextField;
+use crate::models::share::{DEFAULT_SHARE_TTL_MINUTES, DEFAULT_SHARE_USES};
+use crate::models::repository::PayloadFormat;
+use crate::utils::password_generator::DEFAULT_PASSPHRASE_WORDS;
+use crate::utils::redact::Redactor;
+
+use super::agent::DEFAULT_LOCK_AFTER;
+use super::completions::CompletionShell;
+use super::output::OutputFormat;
 
 #[derive(Parser, Debug)]
 #[command(name = "keeprust")]
 #[command(author = "Mostralim <mostralimcode@pm.me>")]
 #[command(version = "0.1.0")]
 #[command(about = "Un gestionnaire de mots de passe sécurisé similaire à KeePass", long_about = None)]
+#[command(disable_help_subcommand = true)]
 pub struct Cli {
-    /// Chemin vers le fichier de base de données (par défaut: keeprust.krs)
-    #[arg(short, long, default_value = "keeprust.krs")]
-    pub database: String,
+    /// Chemin vers le fichier de base de données (par défaut: keeprust.krs, sinon une base trouvée
+    /// dans le répertoire des données, ~/Documents ou les profils), ou URL d'une base WebDAV
+    /// (webdavs://utilisateur@hôte/chemin/coffre.krs, mot de passe dans KEEPRUST_WEBDAV_PASSWORD
+    /// ou ~/.netrc)
+    #[arg(short, long)]
+    pub database: Option<String>,
+    
+    /// Ouvre la base d'un profil défini dans profiles.toml (à la place de --database)
+    #[arg(long, value_name = "NOM", conflicts_with = "database")]
+    pub profile: Option<String>,
+    
+    /// Affiche la durée de chaque étape de chargement et d'enregistrement (sérialisation, KDF, chiffrement, E/S)
+    #[arg(long, global = true)]
+    pub timings: bool,
+    
+    /// Lit le mot de passe maître (puis chaque mot de passe demandé) sur l'entrée standard, une ligne chacun
+    #[arg(long, global = true)]
+    pub password_stdin: bool,
+    
+    /// Lit le mot de passe maître dans la première ligne d'un fichier (lisible par vous seul);
+    /// la variable KEEPRUST_PASSWORD peut aussi le fournir
+    #[arg(long, global = true, value_name = "FICHIER", conflicts_with = "password_stdin")]
+    pub password_file: Option<PathBuf>,
+    
+    /// Répond oui à toutes les confirmations (utile dans les scripts)
+    #[arg(short = 'y', long, global = true)]
+    pub yes: bool,
+    
+    /// Ouvre en lecture seule la dernière version (fichier ou sauvegarde) enregistrée au plus tard à cette date
+    /// (AAAA-MM-JJ, JJ-MM-AAAA ou relative: 2w, hier, "il y a 3 jours", "last month")
+    #[arg(long, global = true, value_name = "DATE")]
+    pub as_of: Option<String>,
+    
+    /// Nombre minimal d'itérations PBKDF2 attendu à l'ouverture; en dessous, un avertissement est affiché
+    /// (par défaut: variable KEEPRUST_MIN_ITERATIONS, sinon le nombre d'itérations recommandé)
+    #[arg(long, global = true, value_name = "N")]
+    pub min_iterations: Option<u32>,
+    
+    /// Fichier clé combiné au mot de passe maître (second facteur); nécessaire à chaque ouverture
+    /// d'une base créée avec lui
+    #[arg(long, global = true, value_name = "FICHIER")]
+    pub keyfile: Option<PathBuf>,
+    
+    /// Inclut les entrées de la corbeille dans les recherches et les requêtes, et accepte leurs IDs
+    /// (show, copy, edit...) au lieu de les refuser
+    #[arg(long, global = true)]
+    pub include_trash: bool,
+    
+    /// Format de sortie de list, show, search, audit, generate et history : text, ou json (un
+    /// document par commande, erreurs comprises, pour les scripts)
+    #[arg(long, global = true, value_name = "FORMAT", default_value_t)]
+    pub output: OutputFormat,
+    
+    /// Sortie accessible (lecteurs d'écran, terminaux simples) : libellés explicites à la place
+    /// des emojis, ni couleur ni animation, colonnes remplacées par des champs nommés
+    #[arg(long, global = true, conflicts_with = "output")]
+    pub plain: bool,
     
     /// Commande à exécuter
     #[command(subcommand)]
@@ -26,6 +99,14 @@ pub enum Commands {
         /// Description de la base de données
         #[arg(short, long)]
         description: Option<String>,
+        
+        /// Format de sérialisation interne (json, cbor ou msgpack)
+        #[arg(short, long, default_value = "cbor")]
+        format: PayloadFormat,
+        
+        /// Algorithme de dérivation de clé (argon2id ou scrypt), avec ses paramètres recommandés
+        #[arg(long, default_value = "argon2id", value_name = "ALGORITHME")]
+        kdf: KdfParams,
     },
     
     /// Ouvre une base de données existante
@@ -33,30 +114,78 @@ pub enum Commands {
     
     /// Affiche une entrée spécifique
     Show {
-        /// ID de l'entrée à afficher
-        id: String,
+        /// ID, titre ou motif de l'entrée à afficher
+        id: EntryRef,
         
         /// Affiche également le mot de passe
         #[arg(short, long)]
         show_password: bool,
+        
+        /// Affichage du mot de passe : mask, last4 (4 derniers caractères) ou reveal
+        #[arg(long, value_name = "MODE", conflicts_with = "show_password")]
+        redact: Option<Redactor>,
     },
     
     /// Liste toutes les entrées ou toutes les entrées d'un groupe
     List {
         /// ID du groupe (liste toutes les entrées si non spécifié)
         #[arg(short, long)]
-        group: Option<String>,
+        group: Option<GroupId>,
+        
+        /// Requête de filtrage (ex: 'tag:banque AND (strength:<medium OR age:>1y)')
+        #[arg(short, long)]
+        query: Option<String>,
+        
+        /// N'affiche que les entrées modifiées depuis cette date (ex: 2024-01-31, 2w, "last month", hier)
+        #[arg(long, value_name = "DATE")]
+        updated_since: Option<String>,
+        
+        /// N'affiche que les entrées non modifiées depuis plus longtemps que cette durée (ex: 90d, 6m, 2y)
+        #[arg(long, value_name = "DURÉE")]
+        older_than: Option<String>,
+        
+        /// Inclut les entrées archivées
+        #[arg(short, long)]
+        all: bool,
+    },
+    
+    /// Recherche un texte dans les titres, utilisateurs, URL et notes
+    Search {
+        /// Texte recherché
+        query: String,
+        
+        /// Ignore la casse
+        #[arg(short, long)]
+        ignore_case: bool,
+        
+        /// Inclut les entrées archivées
+        #[arg(short, long)]
+        all: bool,
+        
+        /// Affiche les identifiants complets
+        #[arg(short, long)]
+        long: bool,
     },
     
     /// Ajoute une nouvelle entrée
     Add {
         /// Titre de l'entrée
-        #[arg(short, long)]
-        title: String,
+        #[arg(short, long, required_unless_present_any = ["from_url", "from_otpauth"])]
+        title: Option<String>,
         
         /// Nom d'utilisateur ou email
-        #[arg(short, long)]
-        username: String,
+        #[arg(short, long, required_unless_present_any = ["from_url", "from_otpauth"])]
+        username: Option<String>,
+        
+        /// Pré-remplit l'entrée depuis l'URL d'un site : titre (nom du site), URL, et nom
+        /// d'utilisateur proposé d'après defaults.toml
+        #[arg(long, value_name = "URL", conflicts_with = "url")]
+        from_url: Option<String>,
+        
+        /// Crée l'entrée depuis une URI otpauth:// : titre (émetteur), nom d'utilisateur (compte)
+        /// et générateur de codes
+        #[arg(long, value_name = "URI")]
+        from_otpauth: Option<String>,
         
         /// Mot de passe (sera demandé si non spécifié)
         #[arg(short, long)]
@@ -70,9 +199,9 @@ pub enum Commands {
         #[arg(short, long)]
         notes: Option<String>,
         
-        /// ID du groupe parent (utilise le groupe racine si non spécifié)
+        /// ID du groupe parent (sans cette option : choisi dans une liste dans un terminal, sinon le groupe racine)
         #[arg(short, long)]
-        group: Option<String>,
+        group: Option<GroupId>,
         
         /// Génère un mot de passe aléatoire
         #[arg(short = 'G', long)]  // Changer de -g à -G
@@ -81,6 +210,39 @@ pub enum Commands {
         /// Longueur du mot de passe généré
         #[arg(short, long, default_value = "16")]
         length: usize,
+        
+        /// Date d'expiration du mot de passe (JJ-MM-AAAA, AAAA-MM-JJ ou relative: 90d, 6m, "dans 1 an")
+        #[arg(short, long)]
+        expires: Option<String>,
+        
+        /// Étiquette à associer à l'entrée (peut être répété)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        
+        /// Couleur de l'entrée (rouge, orange, vert ou bleu)
+        #[arg(short, long)]
+        color: Option<ColorLabel>,
+        
+        #[command(flatten)]
+        fields: FieldArgs,
+    },
+    
+    /// Ajout rapide (ex: quick Netflix bob@mail.com -G) : groupe, nom d'utilisateur et réglages
+    /// du générateur d'après defaults.toml
+    Quick {
+        /// Titre de l'entrée
+        title: String,
+        
+        /// Nom d'utilisateur ou email (celui de defaults.toml si non spécifié)
+        username: Option<String>,
+        
+        /// Génère le mot de passe (sinon il est demandé)
+        #[arg(short = 'G', long)]
+        generate: bool,
+        
+        /// Copie le mot de passe généré dans le presse-papiers au lieu de l'afficher
+        #[arg(short, long, requires = "generate")]
+        copy: bool,
     },
     
     /// Génère un mot de passe aléatoire (sans le sauvegarder)
@@ -113,8 +275,28 @@ pub enum Commands {
         /// Exclut les caractères ambigus ({}[]()/\'"`~,;:.<>)
         #[arg(short = 'a', long)]  // Changé de 'e' à 'a'
         exclude_ambiguous: bool,
+        
+        /// Génère une phrase de passe (mots anglais courants) au lieu d'une suite de caractères
+        #[arg(short, long)]
+        passphrase: bool,
+        
+        /// Nombre de mots de la phrase de passe (environ 12,9 bits d'entropie par mot)
+        #[arg(short, long, default_value_t = DEFAULT_PASSPHRASE_WORDS, requires = "passphrase")]
+        words: usize,
+        
+        /// Séparateur des mots de la phrase de passe
+        #[arg(long, default_value = "-", requires = "passphrase")]
+        separator: String,
+        
+        /// Met une majuscule au début de chaque mot
+        #[arg(short, long, requires = "passphrase")]
+        capitalize: bool,
+        
+        /// Ajoute un chiffre à l'un des mots
+        #[arg(long, requires = "passphrase")]
+        digit: bool,
     },
-
+    
      /// Crée un nouveau groupe
     AddGroup {
         /// Nom du groupe
@@ -123,39 +305,900 @@ pub enum Commands {
         
         /// ID du groupe parent (utilise le groupe racine si non spécifié)
         #[arg(short, long)]
-        parent: Option<String>,
+        parent: Option<GroupId>,
     },
-
-    /// Copie le mot de passe d'une entrée dans le presse-papiers
+    
+    /// Renomme, déplace ou supprime un groupe
+    Group {
+        #[command(subcommand)]
+        action: GroupAction,
+    },
+    
+    /// Modifie les entrées d'un groupe dans l'éditeur de texte ($EDITOR)
+    BulkEdit {
+        /// ID du groupe à modifier (utilise le groupe racine si non spécifié)
+        #[arg(short, long)]
+        group: Option<GroupId>,
+    },
+    
+    /// Copie le mot de passe d'une entrée (ou l'un de ses champs) dans le presse-papiers
     Copy {
-        /// ID de l'entrée dont copier le mot de passe
-        id: String,
+        /// ID, titre ou motif de l'entrée dont copier le mot de passe
+        id: EntryRef,
+        
+        /// Copie la valeur de ce champ supplémentaire au lieu du mot de passe
+        #[arg(short, long, value_name = "NOM", conflicts_with = "both")]
+        field: Option<String>,
         
-        /// Temps en secondes avant effacement automatique (0 = pas d'effacement)
+        /// Temps en secondes avant de retirer le mot de passe et de rétablir le contenu précédent (0 = jamais)
         #[arg(short, long, default_value = "30")]
         timeout: u64,
+        
+        /// Copie le nom d'utilisateur, puis le mot de passe une fois le premier collé (touche Entrée)
+        #[arg(short, long)]
+        both: bool,
+        
+        /// Avec --both, copie le mot de passe après ce délai au lieu d'attendre la touche Entrée
+        #[arg(long, value_name = "SECONDES", requires = "both")]
+        delay: Option<u64>,
     },
-
+    
+    /// Transmet un identifiant à un téléphone du réseau local (QR code, lien HTTPS à usage unique)
+    Beam {
+        /// ID, titre ou motif de l'entrée à transmettre
+        id: EntryRef,
+        
+        /// Durée de validité du lien, en secondes (1 à 3600)
+        #[arg(short, long, default_value_t = 120, value_parser = clap::value_parser!(u64).range(1..=3600))]
+        timeout: u64,
+        
+        /// Adresse d'écoute (adresse de la machine sur le réseau local si non spécifiée)
+        #[arg(long, value_name = "IP")]
+        bind: Option<IpAddr>,
+    },
+    
+    /// Partage une entrée en lecture seule avec un collègue du réseau local (lien HTTPS de courte durée, sans fichier exporté)
+    Share {
+        /// ID, titre ou motif de l'entrée à partager
+        id: EntryRef,
+        
+        /// Durée de validité du lien, en minutes (1 à 1440)
+        #[arg(short, long, default_value_t = DEFAULT_SHARE_TTL_MINUTES, value_parser = clap::value_parser!(u64).range(1..=1440))]
+        minutes: u64,
+        
+        /// Nombre de consultations permises (1 à 20)
+        #[arg(short, long, default_value_t = DEFAULT_SHARE_USES, value_parser = clap::value_parser!(u32).range(1..=20))]
+        uses: u32,
+        
+        /// Adresse d'écoute (adresse de la machine sur le réseau local si non spécifiée)
+        #[arg(long, value_name = "IP")]
+        bind: Option<IpAddr>,
+    },
+    
+    /// Tape le nom d'utilisateur et le mot de passe d'une entrée dans la fenêtre active (séquence configurable)
+    Autotype {
+        /// ID, titre ou motif de l'entrée à saisir
+        id: EntryRef,
+        
+        /// Secondes d'attente avant la saisie, pour placer le curseur dans le premier champ
+        #[arg(short, long, default_value_t = 3, value_name = "SECONDES")]
+        delay: u64,
+        
+        /// Enregistre cette séquence pour l'entrée au lieu de la taper
+        /// (ex: "{USERNAME}{TAB}{DELAY 500}{PASSWORD}{ENTER}")
+        #[arg(long, value_name = "SEQUENCE")]
+        set_sequence: Option<String>,
+        
+        /// Rétablit la séquence par défaut de l'entrée
+        #[arg(long, conflicts_with = "set_sequence")]
+        reset_sequence: bool,
+    },
+    
+    /// Affiche le code TOTP actuel d'une entrée (raccourci de 'otp code')
+    Totp {
+        /// ID, titre ou motif de l'entrée
+        id: EntryRef,
+        
+        /// Copie le code dans le presse-papiers (effacé à la fin de sa validité)
+        #[arg(short, long)]
+        copy: bool,
+    },
+    
+    /// Codes à usage unique (TOTP, HOTP, Steam Guard) associés aux entrées
+    Otp {
+        #[command(subcommand)]
+        action: OtpAction,
+    },
+    
+    /// Joint un fichier à une entrée (compressé, et chiffré avec le reste de la base)
+    Attach {
+        /// ID, titre ou motif de l'entrée
+        id: EntryRef,
+        
+        /// Fichier à joindre (10 Mio au plus)
+        file: PathBuf,
+        
+        /// Nom de la pièce jointe (nom du fichier si non spécifié)
+        #[arg(short, long)]
+        name: Option<String>,
+    },
+    
+    /// Liste les pièces jointes d'une entrée
+    Attachments {
+        /// ID, titre ou motif de l'entrée
+        id: EntryRef,
+    },
+    
+    /// Extrait une pièce jointe d'une entrée
+    Extract {
+        /// ID, titre ou motif de l'entrée
+        id: EntryRef,
+        
+        /// Nom de la pièce jointe
+        name: String,
+        
+        /// Fichier à créer (nom de la pièce jointe dans le répertoire courant si non spécifié, '-' pour
+        /// la sortie standard); un fichier existant n'est pas écrasé
+        #[arg(short = 'o', long, value_name = "FICHIER")]
+        file: Option<PathBuf>,
+        
+        /// Affiche la pièce jointe dans le terminal (texte ou image) sans l'écrire sur le disque
+        #[arg(short, long, conflicts_with = "file")]
+        preview: bool,
+    },
+    
+    /// Modifie une entrée existante (questions interactives si aucune option n'est fournie)
+    Edit {
+        /// ID, titre ou motif de l'entrée à modifier
+        id: EntryRef,
+        
+        /// Nouveau titre
+        #[arg(short, long)]
+        title: Option<String>,
+        
+        /// Nouveau nom d'utilisateur ou email
+        #[arg(short, long)]
+        username: Option<String>,
+        
+        /// Nouveau mot de passe (l'ancien est conservé dans l'historique)
+        #[arg(short, long)]
+        password: Option<String>,
+        
+        /// Nouvelle URL
+        #[arg(short = 'r', long)]
+        url: Option<String>,
+        
+        /// Nouvelles notes
+        #[arg(short, long)]
+        notes: Option<String>,
+        
+        /// Génère un nouveau mot de passe aléatoire
+        #[arg(short = 'G', long, conflicts_with = "password")]
+        generate: bool,
+        
+        /// Longueur du mot de passe généré
+        #[arg(short, long, default_value = "16")]
+        length: usize,
+        
+        #[command(flatten)]
+        fields: FieldArgs,
+        
+        /// Retire un champ supplémentaire (peut être répété)
+        #[arg(long = "remove-field", value_name = "NOM")]
+        remove_fields: Vec<String>,
+        
+        /// Confirme la modification d'une entrée verrouillée (lock-entry)
+        #[arg(long)]
+        unlock_entry: bool,
+    },
+    
     /// Copie le nom d'utilisateur d'une entrée dans le presse-papiers
     CopyUser {
-        /// ID de l'entrée dont copier le nom d'utilisateur
-        id: String,
+        /// ID, titre ou motif de l'entrée dont copier le nom d'utilisateur
+        id: EntryRef,
     },
-
+    
+    /// Archive une entrée inactive (masquée par défaut dans list et audit)
+    Archive {
+        /// ID, titre ou motif de l'entrée à archiver
+        id: EntryRef,
+    },
+    
+    /// Restaure une entrée archivée
+    Unarchive {
+        /// ID, titre ou motif de l'entrée à restaurer
+        id: EntryRef,
+    },
+    
+    /// Verrouille une entrée critique (ex: identifiants de secours) : edit et delete la refusent
+    /// sans --unlock-entry
+    LockEntry {
+        /// ID, titre ou motif de l'entrée à verrouiller
+        id: EntryRef,
+    },
+    
+    /// Déverrouille une entrée verrouillée avec lock-entry
+    UnlockEntry {
+        /// ID, titre ou motif de l'entrée à déverrouiller
+        id: EntryRef,
+    },
+    
+    /// Place une entrée dans la corbeille
+    Delete {
+        /// ID, titre ou motif de l'entrée à supprimer
+        id: EntryRef,
+        
+        /// Supprime définitivement l'entrée au lieu de la placer dans la corbeille
+        #[arg(long)]
+        permanent: bool,
+        
+        /// Confirme la suppression d'une entrée verrouillée (lock-entry)
+        #[arg(long)]
+        unlock_entry: bool,
+    },
+    
+    /// Restaure une entrée de la corbeille dans son groupe d'origine
+    Restore {
+        /// ID, titre ou motif de l'entrée à restaurer
+        id: EntryRef,
+    },
+    
+    /// Supprime définitivement toutes les entrées de la corbeille
+    EmptyTrash,
+    
+    /// Attribue une couleur à une entrée (filtrable avec 'list --query couleur:rouge')
+    Label {
+        /// ID, titre ou motif de l'entrée
+        id: EntryRef,
+        
+        /// Couleur (rouge, orange, vert ou bleu); retire la couleur si non spécifiée
+        color: Option<ColorLabel>,
+    },
+    
     /// Analyse la force d'un mot de passe
     Analyze {
         /// Mot de passe à analyser (sera demandé si non spécifié)
         #[arg(short, long)]
         password: Option<String>,
     },
-
+    
     /// Affiche l'historique des mots de passe d'une entrée
     History {
-        /// ID de l'entrée
-        id: String,
+        /// ID, titre ou motif de l'entrée
+        id: EntryRef,
+        
+        /// Journal chronologique de toutes les modifications de l'entrée, avec les champs modifiés
+        /// en même temps que chaque mot de passe
+        #[arg(long)]
+        diff: bool,
+    },
+    
+    /// Remplace un domaine par un autre dans les URL des entrées (ex: changement de nom d'un service)
+    RewriteUrls {
+        /// Domaine à remplacer (ses sous-domaines sont aussi concernés)
+        #[arg(long)]
+        from: String,
+        
+        /// Nouveau domaine
+        #[arg(long)]
+        to: String,
+        
+        /// Affiche les modifications sans les appliquer
+        #[arg(long)]
+        dry_run: bool,
     },
+    
+    /// Remplace un texte dans un champ de toutes les entrées (ex: nettoyage après un import)
+    Replace {
+        /// Champ concerné (title, username, url ou notes)
+        #[arg(long)]
+        field: TextField,
+        
+        /// Texte recherché
+        #[arg(long)]
+        find: String,
+        
+        /// Texte de remplacement (vide pour supprimer le texte recherché)
+        #[arg(long, default_value = "")]
+        replace: String,
+        
+        /// Interprète --find comme une expression régulière (groupes $1, ${nom} dans le remplacement)
+        #[arg(long)]
+        regex: bool,
+        
+        /// Affiche les modifications sans les appliquer
+        #[arg(long)]
+        dry_run: bool,
+    },
+    
+    /// Rapports sur le contenu de la base de données
+    Report {
+        #[command(subcommand)]
+        kind: ReportKind,
+    },
+    
+    /// Vérifie si des entrées utilisent des mots de passe faibles (seuils réglables dans strength.toml)
+    Audit {
+        /// Inclut les entrées archivées
+        #[arg(short, long)]
+        all: bool,
+        
+        /// Échoue (code de sortie non nul) si un mot de passe a un score inférieur (0 à 100)
+        #[arg(long, value_name = "SCORE", value_parser = clap::value_parser!(u8).range(0..=100))]
+        min_score: Option<u8>,
+        
+        /// Vérifie aussi les mots de passe auprès de Have I Been Pwned (accès réseau, k-anonymat)
+        #[arg(long)]
+        hibp: bool,
+    },
+    
+    /// Vérifie le mot de passe d'une entrée auprès de Have I Been Pwned (accès réseau; seuls les
+    /// 5 premiers caractères de son empreinte SHA-1 sont envoyés)
+    CheckBreach {
+        /// ID, titre ou motif de l'entrée à vérifier
+        id: EntryRef,
+    },
+    
+    /// Politique commune (policy.toml) : dérivation de clé, entropie, expiration, exports
+    Policy {
+        #[command(subcommand)]
+        action: PolicyAction,
+    },
+    
+    /// Signature Ed25519 détachée de la base (coffre.krs.sig), vérifiée avant chaque ouverture
+    Signing {
+        #[command(subcommand)]
+        action: SigningAction,
+    },
+    
+    /// Index de recherche chiffré (coffre.krs.idx) : 'search' répond sans déchiffrer toute la base
+    Index {
+        #[command(subcommand)]
+        action: IndexAction,
+    },
+    
+    /// Ouvre un shell interactif : le mot de passe maître n'est demandé qu'une fois pour toutes les commandes
+    Shell {
+        /// Verrouille la base après ce nombre de minutes d'inactivité (0 = jamais)
+        #[arg(long, default_value_t = 5, value_name = "MINUTES")]
+        lock_after: u64,
+    },
+    
+    /// Lance l'agent : il garde les bases déverrouillées avec 'vaults unlock' pour les commandes suivantes
+    Agent {
+        #[command(subcommand)]
+        action: Option<AgentAction>,
+        
+        /// Délai de verrouillage des bases sans délai propre, en minutes sans utilisation (0 = jamais)
+        #[arg(long, default_value_t = DEFAULT_LOCK_AFTER, value_name = "MINUTES")]
+        lock_after: u64,
+        
+        /// Chiffre les secrets gardés en mémoire avec la clé du trousseau macOS (agent lancé par 'agent enable-login')
+        #[arg(long, hide = true)]
+        keychain: bool,
+    },
+    
+    /// Bases déverrouillées dans l'agent : liste (par défaut), déverrouillage, verrouillage
+    Vaults {
+        #[command(subcommand)]
+        action: Option<VaultsAction>,
+    },
+    
+    /// Verrouille toutes les bases déverrouillées dans l'agent avant de quitter son poste
+    /// (dans 'keeprust shell' : verrouille la base du shell; dans 'keeprust tui' : touche v)
+    Lock,
+    
+    /// Ouvre l'interface plein écran : groupes, entrées, recherche incrémentale, copie et modification
+    Tui {
+        /// Temps en secondes avant effacement automatique des mots de passe copiés (0 = pas d'effacement)
+        #[arg(short, long, default_value = "30")]
+        timeout: u64,
+    },
+    
+    /// Hôte de messagerie native pour l'extension keepassxc-browser (lancé par le navigateur)
+    Proxy {
+        /// Autorise pendant 2 minutes l'association d'une extension sous ce nom
+        #[arg(long, value_name = "NOM", conflicts_with_all = ["revoke", "list", "manifest"])]
+        pair: Option<String>,
+        
+        /// Limite l'extension appairée à ce groupe et à ses sous-groupes (répétable; tous par défaut)
+        #[arg(long, value_name = "GROUPE", requires = "pair")]
+        scope: Vec<String>,
+        
+        /// Retire l'association d'une extension
+        #[arg(long, value_name = "NOM", conflicts_with_all = ["list", "manifest"])]
+        revoke: Option<String>,
+        
+        /// Liste les extensions associées
+        #[arg(long, conflicts_with = "manifest")]
+        list: bool,
+        
+        /// Affiche le manifeste de messagerie native pour ce navigateur (firefox ou chromium)
+        #[arg(long, value_name = "NAVIGATEUR")]
+        manifest: Option<String>,
+        
+        /// Arguments ajoutés par le navigateur (manifeste, origine de l'extension), ignorés
+        #[arg(hide = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        browser_args: Vec<String>,
+    },
+    
+    /// Fournit la base aux applications utilisant libsecret (Secret Service sur D-Bus) jusqu'à Ctrl+C
+    Service {
+        /// Verrouille la base après ce nombre de minutes sans requête (0 = jamais)
+        #[arg(long, default_value_t = 15)]
+        lock_after: u64,
+        
+        /// N'expose que ce groupe et ses sous-groupes (répétable; tous par défaut)
+        #[arg(long, value_name = "GROUPE")]
+        scope: Vec<String>,
+    },
+    
+    /// Surveille la base : entrées expirées et, avec --hibp, mots de passe présents dans des fuites de données
+    Monitor {
+        /// Intervalle entre deux vérifications, en minutes
+        #[arg(short, long, default_value_t = 60)]
+        interval: u64,
+        
+        /// Vérifie aussi les mots de passe auprès de Have I Been Pwned (accès réseau, k-anonymat)
+        #[arg(long)]
+        hibp: bool,
+        
+        /// Effectue une seule vérification puis s'arrête (pour cron ou un minuteur systemd)
+        #[arg(long)]
+        once: bool,
+    },
+    
+    /// Affiche l'identité de cet appareil, marquée sur les modifications, ou la renomme
+    Device {
+        /// Nouveau nom de l'appareil (ex: portable-x)
+        #[arg(long, value_name = "NOM")]
+        rename: Option<String>,
+    },
+    
+    /// Fusionne une autre version de la base (copie synchronisée, autre appareil) dans celle-ci
+    Merge {
+        /// Fichier à fusionner
+        file: PathBuf,
+    },
+    
+    /// Synchronise la base avec un dépôt git : récupère et fusionne les versions des autres appareils, puis envoie la sienne
+    Sync {
+        #[command(subcommand)]
+        action: Option<SyncAction>,
+    },
+    
+    /// Copie (ou déplace) un groupe et toute sa descendance dans une autre base de données
+    CopyGroup {
+        /// ID du groupe à copier
+        id: GroupId,
+        
+        /// Base de données de destination
+        #[arg(long, value_name = "FICHIER")]
+        to_db: PathBuf,
+        
+        /// ID du groupe parent dans la base de destination (groupe racine si non spécifié)
+        #[arg(long, value_name = "ID")]
+        to_group: Option<GroupId>,
+        
+        /// Supprime le groupe de cette base une fois la copie enregistrée
+        #[arg(long = "move")]
+        move_group: bool,
+        
+        /// Conserve les IDs des groupes et des entrées (échoue en cas de collision) au lieu d'en créer de nouveaux
+        #[arg(long)]
+        keep_ids: bool,
+    },
+    
+    /// Liste les copies de conflit créées lors des fusions (entrées modifiées sur deux appareils)
+    Conflicts {
+        /// Résout chaque conflit champ par champ (versions locale et distante, résultat)
+        #[arg(long)]
+        resolve: bool,
+    },
+    
+    /// Exporte les entrées en clair (CSV compatible avec l'import de KeePassXC, ou JSON)
+    Export {
+        /// Format d'export (csv ou json)
+        #[arg(short, long)]
+        format: ExportFormat,
+        
+        /// N'exporte que ce groupe et sa descendance
+        #[arg(short, long)]
+        group: Option<GroupId>,
+        
+        /// Fichier à créer (sortie standard si non spécifié); --output désigne le format de sortie
+        /// commun à toutes les commandes
+        #[arg(short = 'o', long, value_name = "FICHIER")]
+        file: Option<PathBuf>,
+        
+        /// N'exporte que les métadonnées, sans mots de passe ni secrets des codes à usage unique
+        #[arg(long)]
+        no_passwords: bool,
+    },
+    
+    /// Importe le fichier exporté par un autre gestionnaire de mots de passe
+    Import {
+        /// Format du fichier (bitwarden : export JSON non chiffré)
+        #[arg(short, long)]
+        format: ImportFormat,
+        
+        /// Fichier à importer
+        file: PathBuf,
+        
+        /// ID du groupe sous lequel importer (groupe racine si non spécifié)
+        #[arg(short, long)]
+        group: Option<GroupId>,
+    },
+    
+    /// Exporte l'arborescence des groupes sans aucune entrée (JSON), pour la reproduire dans d'autres bases
+    ExportStructure {
+        /// N'exporte que les sous-groupes de ce groupe
+        #[arg(short, long)]
+        group: Option<GroupId>,
+        
+        /// Fichier à créer (sortie standard si non spécifié)
+        #[arg(short = 'o', long, value_name = "FICHIER")]
+        file: Option<PathBuf>,
+    },
+    
+    /// Crée les groupes d'une structure exportée qui n'existent pas encore dans la base
+    ImportStructure {
+        /// Fichier produit par export-structure
+        file: PathBuf,
+        
+        /// ID du groupe sous lequel recréer l'arborescence (groupe racine si non spécifié)
+        #[arg(short, long)]
+        group: Option<GroupId>,
+    },
+    
+    /// Change le mot de passe maître de la base de données
+    ChangePassword,
+    
+    /// Génère un fichier clé aléatoire (32 octets) à utiliser avec --keyfile
+    KeygenKeyfile {
+        /// Fichier à créer (un fichier existant n'est pas écrasé)
+        file: PathBuf,
+    },
+    
+    /// Réenregistre la base avec les paramètres de dérivation de clé recommandés (Argon2id) ou ceux indiqués
+    UpgradeKdf {
+        /// Mémoire utilisée par Argon2id, en Mio
+        #[arg(short, long, value_name = "MIO")]
+        memory: Option<u32>,
+        
+        /// Nombre de passes Argon2id
+        #[arg(short = 't', long)]
+        passes: Option<u32>,
+        
+        /// Nombre de voies Argon2id
+        #[arg(short, long)]
+        parallelism: Option<u32>,
+    },
+    
+    /// Mesure le temps de dérivation de clé de chaque algorithme disponible sur cette machine
+    KdfBenchmark {
+        /// Algorithme à mesurer (tous si non spécifié)
+        #[arg(long, value_name = "ALGORITHME")]
+        kdf: Option<KdfParams>,
+    },
+    
+    /// Vérifie le bon fonctionnement de la cryptographie et du format de fichier sur cette machine
+    Selftest,
+    
+    /// Tente de récupérer une base de données endommagée (sauvegardes, lecture partielle)
+    Recover,
+    
+    /// Décrit un fichier keeprust ou KeePass (KDBX) d'origine incertaine sans le déchiffrer :
+    /// en-tête, dérivation de clé et blocs, avec des limites strictes de taille
+    Inspect {
+        /// Fichier à inspecter
+        file: PathBuf,
+    },
+    
+    /// Génère une base de test reproductible remplie de données fictives
+    GenFixture {
+        /// Nombre d'entrées à générer
+        #[arg(long, default_value_t = 1000)]
+        entries: usize,
+        
+        /// Profondeur maximale des groupes
+        #[arg(long, default_value_t = 3)]
+        depth: usize,
+        
+        /// Graine du générateur (même graine = même contenu)
+        #[arg(long, default_value_t = 42)]
+        seed: u64,
+        
+        /// Mot de passe maître de la base générée
+        #[arg(short, long, default_value = "fixture")]
+        password: String,
+        
+        /// Format de sérialisation interne (json, cbor ou msgpack)
+        #[arg(short, long, default_value = "cbor")]
+        format: PayloadFormat,
+        
+        /// Écrase le fichier s'il existe déjà
+        #[arg(long)]
+        force: bool,
+    },
+    
+    
+    /// Affiche l'aide d'une commande ou un guide (security, format, sync, hooks, scripts, plugins)
+    Help {
+        /// Sujet ou nom de commande
+        topic: Option<String>,
+    },
+    
+    /// Génère les pages de manuel (une par commande et une par sujet d'aide)
+    Manpages {
+        /// Répertoire de destination
+        #[arg(short, long, default_value = "man")]
+        out: PathBuf,
+    },
+    
+    /// Affiche le script de complétion d'un shell, à charger à son démarrage (ex: `keeprust completions
+    /// bash > ~/.local/share/bash-completion/completions/keeprust`); les IDs d'entrées et de groupes
+    /// sont proposés avec leur titre tant que la base est déverrouillée dans l'agent (vaults unlock)
+    Completions {
+        /// Shell cible
+        #[arg(value_enum)]
+        shell: CompletionShell,
+    },
+    
+    /// Valeurs proposées par les scripts de complétion (usage interne)
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        /// Shell appelant (format des valeurs)
+        #[arg(long, value_enum)]
+        shell: CompletionShell,
+        
+        /// Position du mot à compléter dans la ligne
+        #[arg(long)]
+        index: usize,
+        
+        /// Mots de la ligne de commande, programme compris
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        words: Vec<String>,
+    },
+    
+    /// Exécute des scripts Rhai sur la base de données déverrouillée
+    Script {
+        #[command(subcommand)]
+        action: ScriptAction,
+    },
+    
+    /// Sous-commande externe : `keeprust foo` exécute `keeprust-foo` trouvé dans le PATH
+    #[command(external_subcommand)]
+    External(Vec<String>),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ScriptAction {
+    /// Exécute un script (fonctions disponibles: entries, search, generate, update; arguments dans ARGS)
+    Run {
+        /// Fichier du script (.rhai)
+        file: PathBuf,
+        
+        /// Arguments transmis au script
+        #[arg(trailing_var_arg = true)]
+        args: Vec<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PolicyAction {
+    /// Vérifie la base; échoue (code de sortie non nul) si elle ne respecte pas la politique
+    Check {
+        /// Fichier de politique (policy.toml du répertoire de configuration si non spécifié)
+        #[arg(short, long, value_name = "FICHIER")]
+        policy: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SigningAction {
+    /// Exige la signature de la base (crée la clé de cet appareil si besoin) et la signe
+    Init,
+    
+    /// Remplace la clé de cet appareil et signe de nouveau les bases
+    Rotate,
+    
+    /// Accepte les signatures d'un autre appareil
+    Trust {
+        /// Clé publique de l'autre appareil (affichée par 'signing init' sur celui-ci)
+        key: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AgentAction {
+    /// Lance l'agent à chaque ouverture de session (macOS), ses secrets chiffrés par une clé du trousseau
+    EnableLogin {
+        /// Délai de verrouillage des bases sans délai propre, en minutes sans utilisation (0 = jamais)
+        #[arg(long, default_value_t = DEFAULT_LOCK_AFTER, value_name = "MINUTES")]
+        lock_after: u64,
+    },
+    
+    /// Ne lance plus l'agent à l'ouverture de session et retire sa clé du trousseau
+    DisableLogin,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum VaultsAction {
+    /// Liste les profils et les bases déverrouillées dans l'agent
+    List,
+    
+    /// Déverrouille une base dans l'agent (la base courante, ou celle du profil indiqué)
+    Unlock {
+        /// Profil de profiles.toml
+        profile: Option<String>,
+        
+        /// Délai de verrouillage en minutes sans utilisation (par défaut : celui du profil, sinon de l'agent)
+        #[arg(long, value_name = "MINUTES")]
+        lock_after: Option<u64>,
+    },
+    
+    /// Verrouille une base dans l'agent (la base courante, ou celle du profil indiqué)
+    Lock {
+        /// Profil de profiles.toml
+        #[arg(conflicts_with = "all")]
+        profile: Option<String>,
+        
+        /// Verrouille toutes les bases
+        #[arg(short, long)]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SyncAction {
+    /// Active la synchronisation : chaque enregistrement est commité et envoyé, chaque ouverture récupère les autres versions
+    Init {
+        /// Dépôt git distant (URL ou chemin); une base absente y est récupérée
+        remote: String,
+    },
+    
+    /// Désactive la synchronisation (le dépôt git est conservé)
+    Disable,
+}
 
-    /// Vérifie si des entrées utilisent des mots de passe faibles
-    Audit,
+#[derive(Subcommand, Debug)]
+pub enum GroupAction {
+    /// Renomme un groupe
+    Rename {
+        /// ID du groupe
+        id: GroupId,
+        
+        /// Nouveau nom
+        name: String,
+    },
+    
+    /// Déplace un groupe et toute sa descendance sous un autre groupe
+    Move {
+        /// ID du groupe
+        id: GroupId,
+        
+        /// ID du nouveau groupe parent (le groupe racine si non spécifié)
+        #[arg(long)]
+        to: Option<GroupId>,
+    },
+    
+    /// Supprime un groupe et ses sous-groupes après confirmation; leurs entrées sont placées dans la corbeille
+    Delete {
+        /// ID du groupe
+        id: GroupId,
+        
+        /// Confie les entrées et les sous-groupes au groupe parent au lieu de les supprimer
+        #[arg(long)]
+        keep_contents: bool,
+        
+        /// Confirme la mise à la corbeille des entrées verrouillées du groupe (lock-entry)
+        #[arg(long, conflicts_with = "keep_contents")]
+        unlock_entry: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum IndexAction {
+    /// Crée l'index de la base (titres, identifiants, URL, notes et étiquettes), ou le reconstruit entièrement
+    Enable,
+    
+    /// Supprime l'index de la base
+    Disable,
+    
+    /// Indique si l'index correspond au fichier actuel de la base
+    Status,
+}
 
+#[derive(Subcommand, Debug)]
+pub enum ReportKind {
+    /// Regroupe les entrées par adresse email ou nom d'utilisateur
+    Emails {
+        /// N'affiche que les comptes utilisant cet identifiant (ex: avant de changer d'adresse)
+        #[arg(short = 'A', long)]
+        address: Option<String>,
+        
+        /// Inclut les entrées archivées
+        #[arg(short, long)]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum OtpAction {
+    /// Associe un générateur de codes à une entrée à partir d'une URI otpauth://
+    Set {
+        /// ID, titre ou motif de l'entrée
+        id: EntryRef,
+        
+        /// URI otpauth:// (sera demandée sans écho si non spécifiée)
+        uri: Option<String>,
+    },
+    
+    /// Lit l'URI otpauth:// dans l'image d'un QR code (nécessite zbarimg)
+    ImportQr {
+        /// Image contenant le QR code (capture d'écran PNG, JPEG...)
+        image: PathBuf,
+        
+        /// ID, titre ou motif de l'entrée
+        id: EntryRef,
+    },
+    
+    /// Affiche le code actuel (pour HOTP, le compteur est incrémenté et enregistré)
+    Code {
+        /// ID, titre ou motif de l'entrée
+        id: EntryRef,
+        
+        /// Copie le code dans le presse-papiers au lieu de l'afficher
+        #[arg(short, long)]
+        copy: bool,
+    },
+    
+    /// Affiche l'URI otpauth:// de l'entrée (secret compris)
+    Uri {
+        /// ID, titre ou motif de l'entrée
+        id: EntryRef,
+    },
+    
+    /// Retire le générateur de codes d'une entrée
+    Remove {
+        /// ID, titre ou motif de l'entrée
+        id: EntryRef,
+    },
+}
+
+/// Champs supplémentaires d'une entrée (add, edit)
+#[derive(Args, Debug, Default)]
+pub struct FieldArgs {
+    /// Champ supplémentaire (question de sécurité, numéro de compte...); peut être répété
+    #[arg(long = "field", value_name = "NOM=VALEUR")]
+    pub fields: Vec<String>,
+    
+    /// Champ protégé, masqué à l'affichage comme le mot de passe; sans '=VALEUR', la valeur est
+    /// demandée sans être affichée. Peut être répété
+    #[arg(long = "protected-field", value_name = "NOM[=VALEUR]")]
+    pub protected_fields: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+    
+    #[test]
+    fn test_cli_definition() {
+        // Détecte les options en double, notamment entre options globales et options des commandes
+        Cli::command().debug_assert();
+    }
+    
+    #[test]
+    fn test_beam_and_share_durations_are_bounded() {
+        assert!(Cli::try_parse_from(["keeprust", "beam", "Gmail", "--timeout", "3600"]).is_ok());
+        assert!(Cli::try_parse_from(["keeprust", "beam", "Gmail", "--timeout", "0"]).is_err());
+        assert!(Cli::try_parse_from(["keeprust", "beam", "Gmail", "--timeout", "18446744073709551615"]).is_err());
+        
+        assert!(Cli::try_parse_from(["keeprust", "share", "Gmail", "--minutes", "1440", "--uses", "20"]).is_ok());
+        assert!(Cli::try_parse_from(["keeprust", "share", "Gmail", "--minutes", "0"]).is_err());
+        assert!(Cli::try_parse_from(["keeprust", "share", "Gmail", "--uses", "0"]).is_err());
+    }
 }
\ No newline at end of file
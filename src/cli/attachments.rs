@@ -0,0 +1,117 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+use crate::models::attachment::{Attachment, AttachmentError, MAX_ATTACHMENT_SIZE};
+use crate::models::lookup::EntryRef;
+use crate::utils::preview::{self, ImageProtocol};
+use super::output::{self, AttachmentDetail};
+use super::session::{self, Session};
+
+/// Joint un fichier à une entrée, sous son nom ou sous `name`
+pub fn cmd_attach(path: &Path, id: &EntryRef, file: &Path, name: Option<&str>) -> Result<(), String> {
+    // Lire et compresser le fichier avant de demander le mot de passe maître
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => file.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .ok_or_else(|| format!("{} n'est pas un fichier.", file.display()))?,
+    };
+    let size = fs::metadata(file).map_err(|e| format!("Impossible de lire {}: {}", file.display(), e))?.len();
+    if size > MAX_ATTACHMENT_SIZE as u64 {
+        return Err(AttachmentError::TooLarge { name, size: size as usize, max: MAX_ATTACHMENT_SIZE }.to_string());
+    }
+    let content = Zeroizing::new(fs::read(file).map_err(|e| format!("Impossible de lire {}: {}", file.display(), e))?);
+    let attachment = Attachment::new(&name, &content).map_err(|e| e.to_string())?;
+    
+    let mut session = Session::open(path)?;
+    
+    let id = &session.resolve(id)?;
+    let entry = session.entry(id)?;
+    let title = entry.title.clone();
+    
+    if let Some(existing) = entry.attachment(&attachment.name) {
+        let question = format!("L'entrée '{}' a déjà une pièce jointe '{}'. La remplacer?", title, existing.name);
+        if !session::confirm(&question, false)? {
+            println!("Opération annulée.");
+            return session.close();
+        }
+    }
+    
+    let summary = format!("'{}' ({} octets, {} une fois compressé)", attachment.name, attachment.size, attachment.stored_size());
+    session.db.update_entry(id, |entry| entry.attach(attachment))
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    session.save()?;
+    
+    println!("Fichier {} joint à l'entrée '{}'.", summary, title);
+    session.close()
+}
+
+/// Liste les pièces jointes d'une entrée, sans les déchiffrer
+pub fn cmd_attachments(path: &Path, id: &EntryRef) -> Result<(), String> {
+    let session = Session::open(path)?;
+    let id = &session.resolve(id)?;
+    let entry = session.entry(id)?;
+    
+    if output::is_json() {
+        let attachments: Vec<_> = entry.attachments.iter().map(AttachmentDetail::new).collect();
+        output::print_json(&serde_json::json!({ "attachments": attachments }))?;
+        return session.close();
+    }
+    
+    if entry.attachments.is_empty() {
+        println!("L'entrée '{}' n'a aucune pièce jointe.", entry.title);
+        return session.close();
+    }
+    
+    println!("\n{} pièce(s) jointe(s) de '{}':", entry.attachments.len(), entry.title);
+    for attachment in &entry.attachments {
+        println!("  - {} ({} octets, ajoutée le {})", attachment.name, attachment.size, attachment.added_at.format("%d-%m-%Y %H:%M:%S"));
+    }
+    session.close()
+}
+
+/// Crée le fichier extrait, lisible par son seul propriétaire; un fichier existant n'est pas écrasé
+fn create_extracted_file(path: &Path) -> Result<fs::File, String> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path).map_err(|e| match e.kind() {
+        io::ErrorKind::AlreadyExists => format!("{} existe déjà; choisissez un autre fichier avec --file.", path.display()),
+        _ => format!("Impossible de créer {}: {}", path.display(), e),
+    })
+}
+
+/// Extrait une pièce jointe dans un fichier (son nom, dans le répertoire courant, par défaut), sur
+/// la sortie standard avec "-", ou l'affiche dans le terminal sans l'écrire sur le disque avec `preview`
+pub fn cmd_extract(path: &Path, id: &EntryRef, name: &str, file: Option<&Path>, preview: bool) -> Result<(), String> {
+    let session = Session::open(path)?;
+    let id = &session.resolve(id)?;
+    let entry = session.entry(id)?;
+    let attachment = entry.attachment(name)
+        .ok_or_else(|| format!("L'entrée '{}' n'a pas de pièce jointe '{}'.", entry.title, name.trim()))?;
+    let content = attachment.content().map_err(|e| e.to_string())?;
+    
+    if preview {
+        let mut stdout = io::stdout().lock();
+        preview::render(&attachment.name, &content, ImageProtocol::detect(), &mut stdout)
+            .map_err(|e| format!("Pièce jointe '{}': {}", attachment.name, e))?;
+        return session.close();
+    }
+    
+    let file = file.map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from(&attachment.name));
+    if file == Path::new("-") {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(&content).and_then(|_| stdout.flush()).map_err(|e| e.to_string())?;
+    } else {
+        create_extracted_file(&file)?.write_all(&content).map_err(|e| e.to_string())?;
+        eprintln!("Pièce jointe '{}' extraite dans {} ({} octets).", attachment.name, file.display(), attachment.size);
+    }
+    session.close()
+}
@@ -0,0 +1,120 @@
+use std::io::{self, Write};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::models::autotype::{AutotypeSequence, DEFAULT_SEQUENCE};
+use crate::models::exposure::{ExposureInterface, ExposureKind};
+use crate::models::lookup::EntryRef;
+use crate::utils::symbols;
+
+use super::session::Session;
+
+use backend::Keyboard;
+
+/// Émulation du clavier (fonctionnalité `autotype`)
+#[cfg(feature = "autotype")]
+mod backend {
+    use enigo::{Direction, Enigo, Key, Keyboard as _, Settings};
+    use std::thread;
+    use std::time::Duration;
+    
+    use crate::models::autotype::{Keystroke, SpecialKey};
+    
+    /// Clavier virtuel tapant dans la fenêtre qui a le focus
+    pub struct Keyboard(Enigo);
+    
+    impl Keyboard {
+        pub fn new() -> Result<Self, String> {
+            Enigo::new(&Settings::default()).map(Keyboard).map_err(|e| format!("Émulation du clavier indisponible: {}", e))
+        }
+        
+        pub fn send(&mut self, keystrokes: &[Keystroke]) -> Result<(), String> {
+            for keystroke in keystrokes {
+                let result = match keystroke {
+                    Keystroke::Text(text) => self.0.text(text),
+                    Keystroke::Key(SpecialKey::Tab) => self.0.key(Key::Tab, Direction::Click),
+                    Keystroke::Key(SpecialKey::Enter) => self.0.key(Key::Return, Direction::Click),
+                    Keystroke::Delay(ms) => {
+                        thread::sleep(Duration::from_millis(*ms));
+                        Ok(())
+                    }
+                };
+                result.map_err(|e| format!("Erreur de saisie: {}", e))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Remplaçant utilisé lorsque keeprust est compilé sans la fonctionnalité `autotype`
+#[cfg(not(feature = "autotype"))]
+mod backend {
+    use crate::models::autotype::Keystroke;
+    
+    pub struct Keyboard;
+    
+    impl Keyboard {
+        pub fn new() -> Result<Self, String> {
+            Err("Cette version de keeprust a été compilée sans la saisie automatique (fonctionnalité 'autotype').".to_string())
+        }
+        
+        pub fn send(&mut self, _keystrokes: &[Keystroke]) -> Result<(), String> {
+            Ok(())
+        }
+    }
+}
+
+/// Tape l'identifiant d'une entrée dans la fenêtre active, selon sa séquence de saisie, après un
+/// compte à rebours de `delay` secondes laissant le temps de placer le curseur
+pub fn cmd_autotype(path: &Path, id: &EntryRef, delay: u64) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    let id = &session.resolve(id)?;
+    let entry = session.entry(id)?;
+    let sequence = AutotypeSequence::for_entry(entry).map_err(|e| format!("Séquence de saisie de '{}': {}", entry.title, e))?;
+    let mut keyboard = Keyboard::new()?;
+    
+    if symbols::is_plain() {
+        // Un seul message plutôt qu'un décompte réécrit chaque seconde, illisible pour un lecteur d'écran
+        if delay > 0 {
+            eprintln!("Saisie de '{}' dans {} s : placez le curseur dans le premier champ.", entry.title, delay);
+            thread::sleep(Duration::from_secs(delay));
+        }
+    } else {
+        for remaining in (1..=delay).rev() {
+            eprint!("\rSaisie de '{}' dans {} s : placez le curseur dans le premier champ...", entry.title, remaining);
+            let _ = io::stderr().flush();
+            thread::sleep(Duration::from_secs(1));
+        }
+        if delay > 0 {
+            eprintln!();
+        }
+    }
+    
+    // Les codes à usage unique sont calculés après le compte à rebours
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_secs()).unwrap_or(0);
+    let keystrokes = sequence.keystrokes(entry, now).map_err(|e| e.to_string())?;
+    keyboard.send(&keystrokes)?;
+    println!("Identifiant de '{}' saisi.", entry.title);
+    
+    if sequence.types_password() {
+        session.record_exposure(&[*id], ExposureKind::Typed, ExposureInterface::Cli)?;
+    }
+    session.close()
+}
+
+/// Définit la séquence de saisie automatique d'une entrée, ou rétablit la séquence par défaut
+pub fn cmd_autotype_sequence(path: &Path, id: &EntryRef, sequence: Option<String>) -> Result<(), String> {
+    // Vérifier la séquence avant de demander le mot de passe
+    if let Some(sequence) = &sequence {
+        AutotypeSequence::parse(sequence).map_err(|e| format!("Séquence invalide: {}", e))?;
+    }
+    
+    let mut session = Session::open(path)?;
+    
+    let id = &session.resolve(id)?;
+    session.db.set_autotype_sequence(id, sequence.clone()).map_err(|e| e.to_string())?;
+    session.save()?;
+    println!("Séquence de saisie: {}", sequence.as_deref().unwrap_or(DEFAULT_SEQUENCE));
+    session.close()
+}
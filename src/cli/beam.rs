@@ -0,0 +1,415 @@
+#[cfg(not(feature = "beam"))]
+use std::net::IpAddr;
+#[cfg(not(feature = "beam"))]
+use std::path::Path;
+
+#[cfg(not(feature = "beam"))]
+use crate::models::lookup::EntryRef;
+
+/// Transmet un identifiant à un téléphone du réseau local, par un lien HTTPS à usage unique
+#[cfg(not(feature = "beam"))]
+pub fn cmd_beam(_path: &Path, _id: &EntryRef, _timeout: u64, _bind: Option<IpAddr>) -> Result<(), String> {
+    Err("Cette version de keeprust a été compilée sans la transmission par QR code (fonctionnalité 'beam').".to_string())
+}
+
+/// Partage une entrée en lecture seule sur le réseau local, par un lien HTTPS de courte durée
+#[cfg(not(feature = "beam"))]
+pub fn cmd_share(_path: &Path, _id: &EntryRef, _minutes: u64, _uses: u32, _bind: Option<IpAddr>) -> Result<(), String> {
+    Err("Cette version de keeprust a été compilée sans le partage par lien HTTPS (fonctionnalité 'beam').".to_string())
+}
+
+#[cfg(feature = "beam")]
+pub use server::{cmd_beam, cmd_share};
+
+#[cfg(feature = "beam")]
+mod server {
+    use qrcode::QrCode;
+    use qrcode::render::unicode::Dense1x2;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+    use rustls::{ServerConfig, ServerConnection, StreamOwned};
+    use sha2::{Digest, Sha256};
+    use std::io::{self, Read, Write};
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener, TcpStream, UdpSocket};
+    use std::path::Path;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+    
+    use crate::models::entry::Entry;
+    use crate::models::lookup::EntryRef;
+    use crate::models::share::{DEFAULT_SHARE_USES, ShareRegistry};
+    
+    use crate::cli::session::Session;
+    
+    /// Nombre de requêtes sans transmission tolérées avant d'arrêter le serveur
+    const MAX_REQUESTS: usize = 20;
+    
+    /// Taille maximale de l'en-tête d'une requête
+    const MAX_REQUEST_HEAD: usize = 8 * 1024;
+    
+    /// Délai de lecture d'une requête
+    const READ_TIMEOUT: Duration = Duration::from_secs(10);
+    
+    /// Intervalle d'attente d'une connexion
+    const ACCEPT_INTERVAL: Duration = Duration::from_millis(100);
+    
+    /// Adresse de cette machine sur le réseau local : celle de l'interface de la route par défaut
+    /// (aucun paquet n'est envoyé), sinon l'adresse de bouclage
+    fn lan_address() -> IpAddr {
+        UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+            .and_then(|socket| {
+                socket.connect((Ipv4Addr::new(192, 0, 2, 1), 9))?;
+                socket.local_addr()
+            })
+            .map(|address| address.ip())
+            .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST))
+    }
+    
+    /// Configuration TLS avec un certificat autosigné, créé pour cette seule transmission;
+    /// retourne aussi l'empreinte SHA-256 du certificat, à comparer sur le téléphone
+    fn tls_config(ip: IpAddr) -> Result<(Arc<ServerConfig>, String), String> {
+        let certified = rcgen::generate_simple_self_signed(vec![ip.to_string()]).map_err(|e| e.to_string())?;
+        let certificate = CertificateDer::from(certified.cert.der().to_vec());
+        let fingerprint = Sha256::digest(&certificate).iter().map(|byte| format!("{:02X}", byte)).collect::<Vec<_>>().join(":");
+        let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(certified.key_pair.serialize_der()));
+        
+        let config = ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .and_then(|builder| builder.with_no_client_auth().with_single_cert(vec![certificate], key))
+            .map_err(|e| e.to_string())?;
+        Ok((Arc::new(config), fingerprint))
+    }
+    
+    /// Méthode et chemin de la première ligne d'une requête HTTP ("GET /jeton HTTP/1.1")
+    fn request_line(head: &str) -> Option<(&str, &str)> {
+        let mut parts = head.lines().next()?.split_whitespace();
+        let (method, target, version) = (parts.next()?, parts.next()?, parts.next()?);
+        version.starts_with("HTTP/1.").then_some((method, target))
+    }
+    
+    /// Échappe un texte pour l'insérer dans une page HTML
+    fn html_escape(text: &str) -> String {
+        text.chars().fold(String::with_capacity(text.len()), |mut escaped, c| {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&#39;"),
+                c => escaped.push(c),
+            }
+            escaped
+        })
+    }
+    
+    /// Page HTML minimale (sans ressource externe)
+    fn page(body: &str) -> String {
+        format!(
+            "<!DOCTYPE html><html lang=\"fr\"><head><meta charset=\"utf-8\"><meta name=\"viewport\" content=\"width=device-width\">\
+             <title>keeprust</title></head><body style=\"font-family:sans-serif;padding:1em\">{}</body></html>",
+            body
+        )
+    }
+    
+    /// Page de confirmation : l'identifiant n'est envoyé qu'après un clic (POST), pour qu'un aperçu
+    /// du lien par l'application de lecture du QR code ne consomme pas le jeton
+    fn confirmation_page(target: &str) -> String {
+        page(&format!(
+            "<form method=\"post\" action=\"{}\"><p>Un identifiant vous est transmis. Il ne pourra être affiché qu'une fois.</p>\
+             <button type=\"submit\" style=\"font-size:1.2em\">Afficher</button></form>",
+            html_escape(target)
+        ))
+    }
+    
+    /// Page affichant l'identifiant transmis
+    fn credential_page(entry: &Entry) -> String {
+        let mut body = format!("<h1>{}</h1>", html_escape(&entry.title));
+        if !entry.username.is_empty() {
+            body.push_str(&format!("<p>Utilisateur<br><code>{}</code></p>", html_escape(&entry.username)));
+        }
+        body.push_str(&format!("<p>Mot de passe<br><code style=\"font-size:1.3em;word-break:break-all\">{}</code></p>", html_escape(&entry.password)));
+        if !entry.url.is_empty() {
+            body.push_str(&format!("<p>URL<br><code>{}</code></p>", html_escape(&entry.url)));
+        }
+        body.push_str("<p>Ce lien est désormais inutilisable.</p>");
+        page(&body)
+    }
+    
+    /// Lit l'en-tête d'une requête (jusqu'à la ligne vide)
+    fn read_request_head(stream: &mut impl Read) -> io::Result<String> {
+        let mut head = Vec::new();
+        let mut buffer = [0u8; 1024];
+        while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+            if head.len() > MAX_REQUEST_HEAD {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "en-tête de requête trop long"));
+            }
+            let read = stream.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            head.extend_from_slice(&buffer[..read]);
+        }
+        Ok(String::from_utf8_lossy(&head).into_owned())
+    }
+    
+    /// Envoie une réponse HTML qui ne doit être ni mise en cache ni transmise en référent
+    fn respond(stream: &mut impl Write, status: &str, body: &str) -> io::Result<()> {
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\n\
+             Cache-Control: no-store\r\nReferrer-Policy: no-referrer\r\nX-Content-Type-Options: nosniff\r\nConnection: close\r\n\r\n{}",
+            status, body.len(), body
+        )?;
+        stream.flush()
+    }
+    
+    /// Réponse à une requête : statut, page et indication que l'identifiant est transmis. Seul un
+    /// POST sur le chemin du jeton consomme celui-ci; un GET n'affiche que la confirmation
+    fn reply(head: &str, registry: &mut ShareRegistry, entry: &Entry) -> (&'static str, String, bool) {
+        match request_line(head) {
+            Some(("GET", target)) if target.len() > 1 => ("200 OK", confirmation_page(target), false),
+            Some(("POST", target)) if registry.redeem(target.trim_start_matches('/')) == Ok(entry.id) => {
+                ("200 OK", credential_page(entry), true)
+            }
+            _ => ("404 Not Found", page("<p>Lien invalide, expiré ou déjà utilisé.</p>"), false),
+        }
+    }
+    
+    /// Traite une connexion; retourne true si l'identifiant a été transmis
+    fn serve(stream: TcpStream, config: &Arc<ServerConfig>, registry: &mut ShareRegistry, entry: &Entry) -> io::Result<bool> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        let connection = ServerConnection::new(Arc::clone(config)).map_err(io::Error::other)?;
+        let mut tls = StreamOwned::new(connection, stream);
+        
+        let head = read_request_head(&mut tls)?;
+        let (status, body, sent) = reply(&head, registry, entry);
+        respond(&mut tls, status, &body)?;
+        tls.conn.send_close_notify();
+        let _ = tls.flush();
+        Ok(sent)
+    }
+    
+    /// Affiche un QR code dans le terminal (modules clairs sur fond sombre, lisible par les téléphones)
+    fn print_qr_code(data: &str) -> Result<(), String> {
+        let code = QrCode::new(data.as_bytes()).map_err(|e| e.to_string())?;
+        let rendered = code.render::<Dense1x2>()
+            .dark_color(Dense1x2::Light)
+            .light_color(Dense1x2::Dark)
+            .quiet_zone(true)
+            .build();
+        println!("{}", rendered);
+        Ok(())
+    }
+    
+    /// Destinataire d'un lien publié
+    #[derive(Clone, Copy)]
+    enum Recipient {
+        /// Téléphone de l'utilisateur (keeprust beam)
+        Phone,
+        
+        /// Collègue du même réseau (keeprust share)
+        Colleague,
+    }
+    
+    /// Publie une entrée sur un serveur HTTPS local de courte durée : un lien valable `ttl`, pour
+    /// `uses` consultations, affiché en QR code; le serveur s'arrête dès le lien épuisé ou expiré.
+    ///
+    /// Le certificat est autosigné et créé pour l'occasion : son empreinte est affichée pour être
+    /// comparée avec celle que présente le navigateur du destinataire.
+    fn publish(path: &Path, id: &EntryRef, ttl: Duration, uses: u32, bind: Option<IpAddr>, recipient: Recipient) -> Result<(), String> {
+        let session = Session::open(path)?;
+        let id = &session.resolve(id)?;
+        let entry = session.entry(id)?;
+        
+        let ip = bind.unwrap_or_else(lan_address);
+        let listener = TcpListener::bind((ip, 0)).map_err(|e| format!("Impossible d'écouter sur {}: {}", ip, e))?;
+        listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+        let address = SocketAddr::new(ip, listener.local_addr().map_err(|e| e.to_string())?.port());
+        let (config, fingerprint) = tls_config(ip)?;
+        
+        let mut registry = ShareRegistry::new();
+        let lifetime = chrono::Duration::from_std(ttl).map_err(|e| e.to_string())?;
+        let token = registry.mint(entry.id, lifetime, uses).map_err(|e| e.to_string())?;
+        let url = format!("https://{}/{}", address, token);
+        
+        let command = match recipient {
+            Recipient::Phone => {
+                println!("Scannez ce QR code avec un téléphone connecté au même réseau pour recevoir '{}':", entry.title);
+                "keeprust beam"
+            }
+            Recipient::Colleague => {
+                println!("Lien de partage en lecture seule de '{}', à transmettre à un collègue du même réseau (ou QR code):", entry.title);
+                "keeprust share"
+            }
+        };
+        print_qr_code(&url)?;
+        println!("{}", url);
+        println!("Empreinte du certificat (SHA-256): {}", fingerprint);
+        println!("Le navigateur signalera un certificat non reconnu : vérifiez l'empreinte avant de continuer.");
+        match uses {
+            1 => println!("Le lien expire dans {} seconde(s) et ne fonctionne qu'une fois (Ctrl+C pour annuler).", ttl.as_secs()),
+            _ => println!("Le lien expire dans {} seconde(s) et fonctionne {} fois (Ctrl+C pour annuler).", ttl.as_secs(), uses),
+        }
+        
+        let deadline = Instant::now() + ttl;
+        let (mut requests, mut sent) = (0, 0);
+        loop {
+            if Instant::now() >= deadline {
+                println!("Délai écoulé : {}.", if sent == 0 { "identifiant non transmis".to_string() } else { format!("identifiant transmis {} fois", sent) });
+                break;
+            }
+            if requests >= MAX_REQUESTS {
+                println!("Trop de requêtes sans transmission : serveur arrêté.");
+                break;
+            }
+            
+            match listener.accept() {
+                Ok((stream, peer)) => match serve(stream, &config, &mut registry, entry) {
+                    Ok(true) => {
+                        sent += 1;
+                        println!("Identifiant '{}' transmis à {} ({}/{}).", entry.title, peer.ip(), sent, uses);
+                        if registry.is_empty() {
+                            break;
+                        }
+                    }
+                    Ok(false) => requests += 1,
+                    Err(_) if registry.is_empty() => {
+                        println!("Connexion interrompue pendant l'envoi : le lien a été consommé, relancez '{}'.", command);
+                        break;
+                    }
+                    Err(e) => {
+                        eprintln!("Connexion de {} ignorée: {}", peer.ip(), e);
+                        requests += 1;
+                    }
+                },
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => thread::sleep(ACCEPT_INTERVAL),
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+        session.close()
+    }
+    
+    /// Transmet un identifiant à un téléphone du même réseau par un lien HTTPS à usage unique,
+    /// valable `timeout` secondes
+    pub fn cmd_beam(path: &Path, id: &EntryRef, timeout: u64, bind: Option<IpAddr>) -> Result<(), String> {
+        publish(path, id, Duration::from_secs(timeout), DEFAULT_SHARE_USES, bind, Recipient::Phone)
+    }
+    
+    /// Partage une entrée en lecture seule avec un collègue du même réseau, sans exporter de
+    /// fichier : lien HTTPS valable `minutes` minutes, pour `uses` consultations
+    pub fn cmd_share(path: &Path, id: &EntryRef, minutes: u64, uses: u32, bind: Option<IpAddr>) -> Result<(), String> {
+        publish(path, id, Duration::from_secs(minutes * 60), uses, bind, Recipient::Colleague)
+    }
+    
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        
+        fn sample() -> (ShareRegistry, Entry, String) {
+            let entry = Entry::builder().title("Wifi").username("bob").password("hunter2").build().unwrap();
+            let mut registry = ShareRegistry::new();
+            let token = registry.mint(entry.id, chrono::Duration::minutes(1), DEFAULT_SHARE_USES).unwrap();
+            (registry, entry, token)
+        }
+        
+        #[test]
+        fn test_request_line() {
+            assert_eq!(request_line("GET /abc HTTP/1.1\r\nHost: x\r\n\r\n"), Some(("GET", "/abc")));
+            assert_eq!(request_line("POST /abc HTTP/1.0\r\n\r\n"), Some(("POST", "/abc")));
+            assert_eq!(request_line("GET /abc\r\n"), None);
+            assert_eq!(request_line("GET /abc SPDY/3\r\n"), None);
+            assert_eq!(request_line(""), None);
+        }
+        
+        #[test]
+        fn test_pages_escape_html() {
+            let entry = Entry::builder().title("<Wifi>").username("bob").password("a\"b&c").build().unwrap();
+            let html = credential_page(&entry);
+            assert!(html.contains("<h1>&lt;Wifi&gt;</h1>"));
+            assert!(html.contains("a&quot;b&amp;c"));
+            assert!(confirmation_page("/x\"y").contains("action=\"/x&quot;y\""));
+        }
+        
+        #[test]
+        fn test_respond_headers() {
+            let mut response = Vec::new();
+            respond(&mut response, "404 Not Found", "é").unwrap();
+            let response = String::from_utf8(response).unwrap();
+            assert!(response.starts_with("HTTP/1.1 404 Not Found\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: 2\r\n"));
+            assert!(response.contains("Cache-Control: no-store\r\n"));
+            assert!(response.ends_with("\r\n\r\né"));
+        }
+        
+        #[test]
+        fn test_get_shows_confirmation_without_redeeming() {
+            let (mut registry, entry, token) = sample();
+            let (status, body, sent) = reply(&format!("GET /{} HTTP/1.1\r\n\r\n", token), &mut registry, &entry);
+            assert_eq!((status, sent), ("200 OK", false));
+            assert!(body.contains("method=\"post\""));
+            assert!(!body.contains("hunter2"));
+            assert_eq!(registry.len(), 1);
+        }
+        
+        #[test]
+        fn test_token_is_redeemed_only_once() {
+            let (mut registry, entry, token) = sample();
+            let request = format!("POST /{} HTTP/1.1\r\n\r\n", token);
+            
+            let (status, body, sent) = reply(&request, &mut registry, &entry);
+            assert_eq!((status, sent), ("200 OK", true));
+            assert!(body.contains("hunter2"));
+            assert!(registry.is_empty());
+            
+            let (status, body, sent) = reply(&request, &mut registry, &entry);
+            assert_eq!((status, sent), ("404 Not Found", false));
+            assert!(!body.contains("hunter2"));
+        }
+        
+        #[test]
+        fn test_shared_link_serves_each_permitted_use() {
+            let entry = Entry::builder().title("Wifi").password("hunter2").build().unwrap();
+            let mut registry = ShareRegistry::new();
+            let token = registry.mint(entry.id, chrono::Duration::minutes(10), 2).unwrap();
+            let request = format!("POST /{} HTTP/1.1\r\n\r\n", token);
+            
+            assert!(reply(&request, &mut registry, &entry).2);
+            assert_eq!(registry.len(), 1);
+            assert!(reply(&request, &mut registry, &entry).2);
+            assert!(registry.is_empty());
+            assert_eq!(reply(&request, &mut registry, &entry).0, "404 Not Found");
+        }
+        
+        #[test]
+        fn test_wrong_token_is_refused_and_keeps_the_link() {
+            let (mut registry, entry, _) = sample();
+            for request in ["POST /jeton-invalide HTTP/1.1\r\n\r\n", "POST / HTTP/1.1\r\n\r\n", "GET / HTTP/1.1\r\n\r\n", "PUT /x HTTP/1.1\r\n\r\n", "n'importe quoi"] {
+                let (status, body, sent) = reply(request, &mut registry, &entry);
+                assert_eq!((status, sent), ("404 Not Found", false), "{}", request);
+                assert!(!body.contains("hunter2"));
+            }
+            assert_eq!(registry.len(), 1);
+        }
+        
+        #[test]
+        fn test_expired_token_is_refused() {
+            let entry = Entry::builder().title("Wifi").password("hunter2").build().unwrap();
+            let mut registry = ShareRegistry::new();
+            let token = registry.mint(entry.id, chrono::Duration::zero(), DEFAULT_SHARE_USES).unwrap();
+            
+            let (status, _, sent) = reply(&format!("POST /{} HTTP/1.1\r\n\r\n", token), &mut registry, &entry);
+            assert_eq!((status, sent), ("404 Not Found", false));
+            assert!(registry.is_empty());
+        }
+        
+        #[test]
+        fn test_token_of_another_entry_sends_nothing() {
+            let (mut registry, entry, _) = sample();
+            let other = Entry::builder().title("Autre").password("x").build().unwrap();
+            let token = registry.mint(other.id, chrono::Duration::minutes(1), DEFAULT_SHARE_USES).unwrap();
+            
+            let (status, _, sent) = reply(&format!("POST /{} HTTP/1.1\r\n\r\n", token), &mut registry, &entry);
+            assert_eq!((status, sent), ("404 Not Found", false));
+        }
+    }
+}
\ No newline at end of file
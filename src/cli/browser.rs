@@ -0,0 +1,533 @@
+use serde_json::json;
+use std::path::Path;
+
+use crate::models::browser::PAIRING_MINUTES;
+
+use super::session::Session;
+
+/// Nom de l'hôte de messagerie native attendu par l'extension keepassxc-browser
+const HOST_NAME: &str = "org.keepassxc.keepassxc_browser";
+
+/// Identifiant de l'extension keepassxc-browser pour Firefox
+const FIREFOX_EXTENSION: &str = "keepassxc-browser@keepassxc.org";
+
+/// Origines de l'extension keepassxc-browser (Chrome Web Store, puis Edge Add-ons)
+const CHROMIUM_ORIGINS: [&str; 2] = [
+    "chrome-extension://oboonakemofpalcgghocfoadofidjkkk/",
+    "chrome-extension://pdffhmdngciaglkoonimfcmckehcpafo/",
+];
+
+/// Autorise pendant quelques minutes l'association d'une extension sous ce nom, limitée aux
+/// groupes de `scope` s'il n'est pas vide
+pub fn cmd_proxy_pair(path: &Path, name: &str, scope: &[String]) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    let until = session.db.open_browser_pairing(name, scope).map_err(|e| e.to_string())?;
+    session.save()?;
+    println!("Appairage '{}' ouvert jusqu'à {} ({} minutes).", name, until.with_timezone(&chrono::Local).format("%H:%M:%S"), PAIRING_MINUTES);
+    if !scope.is_empty() {
+        println!("L'extension n'aura accès qu'aux groupes: {}.", scope.join(", "));
+    }
+    println!("Cliquez maintenant sur « Connecter » dans l'extension keepassxc-browser.");
+    session.close()
+}
+
+/// Retire l'association d'une extension : elle ne peut plus lire ni enregistrer d'identifiants
+pub fn cmd_proxy_revoke(path: &Path, name: &str) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    session.db.revoke_browser(name).map_err(|e| e.to_string())?;
+    session.save()?;
+    println!("Association '{}' retirée.", name);
+    session.close()
+}
+
+/// Liste les extensions associées
+pub fn cmd_proxy_list(path: &Path) -> Result<(), String> {
+    let session = Session::open(path)?;
+    let associations = &session.db.metadata.browser_associations;
+    if associations.is_empty() {
+        println!("Aucune extension associée: utilisez 'keeprust proxy --pair NOM'.");
+    }
+    for association in associations {
+        let scope = if association.scope.is_empty() { String::new() } else { format!("; groupes: {}", association.scope.join(", ")) };
+        println!("{}  (associée le {}{})", association.name, association.created_at.with_timezone(&chrono::Local).format("%d-%m-%Y %H:%M"), scope);
+    }
+    session.close()
+}
+
+/// Affiche le manifeste de messagerie native à installer pour le navigateur
+pub fn cmd_proxy_manifest(path: &Path, browser: &str) -> Result<(), String> {
+    let allowed = match browser.to_lowercase().as_str() {
+        "firefox" => ("allowed_extensions", json!([FIREFOX_EXTENSION])),
+        "chromium" | "chrome" | "edge" | "brave" => ("allowed_origins", json!(CHROMIUM_ORIGINS)),
+        other => return Err(format!("Navigateur inconnu: '{}' (firefox ou chromium)", other)),
+    };
+    let executable = std::env::current_exe().map_err(|e| format!("Chemin de keeprust introuvable: {}", e))?;
+    let vault = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let wrapper = executable.with_file_name("keeprust-proxy");
+    
+    let mut manifest = json!({
+        "name": HOST_NAME,
+        "description": "keeprust (protocole keepassxc-browser)",
+        "path": wrapper,
+        "type": "stdio",
+    });
+    manifest[allowed.0] = allowed.1;
+    println!("{}", serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?);
+    
+    // Le navigateur lance l'hôte sans arguments ni terminal : un script fixe la base et le mot de passe
+    eprintln!("\nEnregistrez ce manifeste sous {}.json dans le répertoire NativeMessagingHosts du navigateur", HOST_NAME);
+    eprintln!("(Firefox : ~/.mozilla/native-messaging-hosts/), puis créez le script exécutable {} :", wrapper.display());
+    eprintln!("  #!/bin/sh");
+    eprintln!("  exec {} -d {} --password-file ~/.config/keeprust/proxy.pw proxy \"$@\"", executable.display(), vault.display());
+    Ok(())
+}
+
+/// Hôte de messagerie native keepassxc-browser : lit les requêtes de l'extension sur l'entrée
+/// standard jusqu'à la fermeture du navigateur
+#[cfg(not(feature = "browser"))]
+pub fn cmd_proxy(_path: &Path) -> Result<(), String> {
+    Err("Cette version de keeprust a été compilée sans l'intégration aux navigateurs (fonctionnalité 'browser').".to_string())
+}
+
+#[cfg(feature = "browser")]
+pub use host::cmd_proxy;
+
+#[cfg(feature = "browser")]
+mod host {
+    use base64::{Engine, engine::general_purpose};
+    use crypto_box::aead::{Aead, OsRng};
+    use crypto_box::{Nonce, PublicKey, SalsaBox, SecretKey};
+    use serde_json::{Value, json};
+    use std::collections::HashMap;
+    use std::fs;
+    use std::io::{self, Read, Write};
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
+    
+    use crate::events::{self, SecurityEvent};
+    use crate::models::clock;
+    use crate::models::database::Database;
+    use crate::models::entry::{Entry, site_name, url_host};
+    use crate::models::exposure::{ExposureInterface, ExposureKind};
+    use crate::models::id::EntryId;
+    use crate::models::scope::{GroupScope, ScopeError};
+    use crate::utils::password_generator::PasswordGenerator;
+    
+    use crate::cli::session::Session;
+    
+    /// Version de KeePassXC annoncée à l'extension, qui en déduit les fonctions disponibles
+    const PROTOCOL_VERSION: &str = "2.7.0";
+    
+    /// Groupe recevant les identifiants enregistrés depuis le navigateur
+    const BROWSER_GROUP: &str = "Navigateur";
+    
+    /// Taille maximale d'une requête de l'extension
+    const MAX_MESSAGE_LEN: usize = 1024 * 1024;
+    
+    // Codes d'erreur du protocole keepassxc-browser
+    const DATABASE_NOT_OPENED: u8 = 1;
+    const CLIENT_PUBLIC_KEY_NOT_RECEIVED: u8 = 3;
+    const CANNOT_DECRYPT_MESSAGE: u8 = 4;
+    const ACTION_CANCELLED_OR_DENIED: u8 = 6;
+    const CANNOT_ENCRYPT_MESSAGE: u8 = 7;
+    const ASSOCIATION_FAILED: u8 = 8;
+    const INCORRECT_ACTION: u8 = 12;
+    const NO_URL_PROVIDED: u8 = 14;
+    const NO_LOGINS_FOUND: u8 = 15;
+    
+    /// Erreur renvoyée à l'extension
+    struct Failure {
+        code: u8,
+        message: String,
+    }
+    
+    impl Failure {
+        fn new(code: u8, message: impl Into<String>) -> Self {
+            Self { code, message: message.into() }
+        }
+    }
+    
+    /// Lit un message préfixé par sa longueur (32 bits, ordre natif); None à la fermeture du navigateur
+    fn read_message(input: &mut impl Read) -> io::Result<Option<Value>> {
+        let mut length = [0u8; 4];
+        match input.read_exact(&mut length) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e),
+        }
+        let length = u32::from_ne_bytes(length) as usize;
+        if length > MAX_MESSAGE_LEN {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("message de {} octets", length)));
+        }
+        let mut message = vec![0u8; length];
+        input.read_exact(&mut message)?;
+        serde_json::from_slice(&message).map(Some).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+    
+    fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+        let bytes = serde_json::to_vec(message).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        output.write_all(&(bytes.len() as u32).to_ne_bytes())?;
+        output.write_all(&bytes)?;
+        output.flush()
+    }
+    
+    /// Nonce de la réponse : celui de la requête incrémenté (entier petit-boutiste, comme sodium_increment)
+    fn increment(nonce: &[u8]) -> Vec<u8> {
+        let mut next = nonce.to_vec();
+        for byte in next.iter_mut() {
+            let (value, overflow) = byte.overflowing_add(1);
+            *byte = value;
+            if !overflow {
+                break;
+            }
+        }
+        next
+    }
+    
+    fn field<'a>(message: &'a Value, name: &str) -> &'a str {
+        message[name].as_str().unwrap_or_default()
+    }
+    
+    fn decode(message: &Value, name: &str, code: u8) -> Result<Vec<u8>, Failure> {
+        general_purpose::STANDARD.decode(field(message, name)).map_err(|_| Failure::new(code, format!("Champ '{}' invalide", name)))
+    }
+    
+    /// Entrée telle que transmise à l'extension (UUID sans tirets, comme ceux de KeePassXC)
+    fn entry_json(path: &[String], entry: &Entry) -> Value {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_secs()).unwrap_or(0);
+        let totp = entry.otp.as_ref()
+            .filter(|otp| !otp.is_counter_based())
+            .and_then(|otp| otp.code_at(now).ok())
+            .unwrap_or_default();
+        json!({
+            "name": entry.title,
+            "login": entry.username,
+            "password": entry.password,
+            "uuid": entry.id.to_string().replace('-', ""),
+            "group": path.last().cloned().unwrap_or_default(),
+            "totp": totp,
+            "expired": entry.expires_at.is_some_and(|expires_at| expires_at < clock::now()).to_string(),
+            "stringFields": [],
+        })
+    }
+    
+    /// Canal chiffré avec l'extension et base ouverte à la première requête qui la nécessite
+    struct Host {
+        path: PathBuf,
+        secret: SecretKey,
+        
+        /// Clés publiques éphémères des extensions, par clientID
+        clients: HashMap<String, PublicKey>,
+        
+        /// Extensions ayant prouvé leur association sur ce canal : nom de l'association, par clientID
+        verified: HashMap<String, String>,
+        
+        session: Option<Session>,
+        modified: Option<SystemTime>,
+    }
+    
+    impl Host {
+        fn file_modified(&self) -> Option<SystemTime> {
+            fs::metadata(&self.path).and_then(|metadata| metadata.modified()).ok()
+        }
+        
+        /// Base ouverte avec --password-file ou KEEPRUST_PASSWORD (le navigateur ne fournit pas de
+        /// terminal), relue si une autre commande l'a modifiée
+        fn unlocked(&mut self) -> Result<&mut Session, Failure> {
+            let modified = self.file_modified();
+            if self.session.is_none() {
+                let session = Session::open(&self.path).map_err(|e| {
+                    eprintln!("{}", e);
+                    Failure::new(DATABASE_NOT_OPENED, "Base keeprust non ouverte")
+                })?;
+                self.session = Some(session);
+                self.modified = modified;
+            }
+            
+            let session = self.session.as_mut().ok_or_else(|| Failure::new(DATABASE_NOT_OPENED, "Base keeprust non ouverte"))?;
+            if modified != self.modified && !session.db.is_dirty() {
+                session.reload().map_err(|e| Failure::new(DATABASE_NOT_OPENED, e))?;
+                self.modified = modified;
+            }
+            Ok(session)
+        }
+        
+        /// Refus d'un accès hors de la portée de l'extension, signalé aux hooks (access-denied)
+        fn deny(&self, association: &str, entry: String) -> Failure {
+            let event = SecurityEvent::AccessDenied { client: association.to_string(), entry };
+            eprintln!("{}", event);
+            events::emit(&self.path, &event);
+            Failure::new(ACTION_CANCELLED_OR_DENIED, event.to_string())
+        }
+        
+        fn save(&mut self) -> Result<(), Failure> {
+            self.unlocked()?.save().map_err(|e| Failure::new(ACTION_CANCELLED_OR_DENIED, e))?;
+            Ok(())
+        }
+        
+        /// Réponse à une requête; les erreurs sont renvoyées en clair, comme le fait KeePassXC
+        fn handle(&mut self, request: &Value) -> Value {
+            let action = field(request, "action").to_string();
+            let result = if action == "change-public-keys" {
+                self.change_public_keys(request)
+            } else {
+                self.encrypted(&action, request)
+            };
+            result.unwrap_or_else(|failure| json!({
+                "action": action,
+                "errorCode": failure.code.to_string(),
+                "error": failure.message,
+            }))
+        }
+        
+        /// Échange de clés : l'extension envoie sa clé publique éphémère et reçoit celle de l'hôte
+        fn change_public_keys(&mut self, request: &Value) -> Result<Value, Failure> {
+            let key: [u8; 32] = decode(request, "publicKey", CLIENT_PUBLIC_KEY_NOT_RECEIVED)?.try_into()
+                .map_err(|_| Failure::new(CLIENT_PUBLIC_KEY_NOT_RECEIVED, "Clé publique invalide"))?;
+            let nonce = decode(request, "nonce", CLIENT_PUBLIC_KEY_NOT_RECEIVED)?;
+            self.clients.insert(field(request, "clientID").to_string(), PublicKey::from(key));
+            
+            Ok(json!({
+                "action": "change-public-keys",
+                "version": PROTOCOL_VERSION,
+                "publicKey": general_purpose::STANDARD.encode(self.secret.public_key().as_bytes()),
+                "nonce": general_purpose::STANDARD.encode(increment(&nonce)),
+                "success": "true",
+            }))
+        }
+        
+        /// Déchiffre une requête, la traite et chiffre la réponse avec le nonce suivant
+        fn encrypted(&mut self, action: &str, request: &Value) -> Result<Value, Failure> {
+            let client = field(request, "clientID").to_string();
+            let client_key = self.clients.get(&client).ok_or_else(|| Failure::new(CLIENT_PUBLIC_KEY_NOT_RECEIVED, "Clé publique du client non reçue"))?;
+            let channel = SalsaBox::new(client_key, &self.secret);
+            
+            let nonce = decode(request, "nonce", CANNOT_DECRYPT_MESSAGE)?;
+            if nonce.len() != 24 {
+                return Err(Failure::new(CANNOT_DECRYPT_MESSAGE, "Nonce invalide"));
+            }
+            let ciphertext = decode(request, "message", CANNOT_DECRYPT_MESSAGE)?;
+            let plaintext = channel.decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+                .map_err(|_| Failure::new(CANNOT_DECRYPT_MESSAGE, "Message indéchiffrable"))?;
+            let message: Value = serde_json::from_slice(&plaintext).map_err(|_| Failure::new(CANNOT_DECRYPT_MESSAGE, "Message invalide"))?;
+            
+            let mut response = self.dispatch(action, &client, &message)?;
+            let reply_nonce = increment(&nonce);
+            response["action"] = json!(action);
+            response["nonce"] = json!(general_purpose::STANDARD.encode(&reply_nonce));
+            response["version"] = json!(PROTOCOL_VERSION);
+            response["success"] = json!("true");
+            
+            let plaintext = serde_json::to_vec(&response).map_err(|e| Failure::new(CANNOT_ENCRYPT_MESSAGE, e.to_string()))?;
+            let ciphertext = channel.encrypt(Nonce::from_slice(&reply_nonce), plaintext.as_slice())
+                .map_err(|_| Failure::new(CANNOT_ENCRYPT_MESSAGE, "Chiffrement impossible"))?;
+            Ok(json!({
+                "action": action,
+                "message": general_purpose::STANDARD.encode(ciphertext),
+                "nonce": general_purpose::STANDARD.encode(&reply_nonce),
+            }))
+        }
+        
+        fn dispatch(&mut self, action: &str, client: &str, message: &Value) -> Result<Value, Failure> {
+            match action {
+                "get-databasehash" => Ok(json!({ "hash": self.unlocked()?.db.browser_hash() })),
+                "associate" => self.associate(client, message),
+                "test-associate" => {
+                    let id = field(message, "id");
+                    if !self.unlocked()?.db.is_browser_associated(id, field(message, "key")) {
+                        return Err(Failure::new(ASSOCIATION_FAILED, "Extension non associée"));
+                    }
+                    self.verified.insert(client.to_string(), id.to_string());
+                    Ok(json!({ "hash": self.unlocked()?.db.browser_hash(), "id": id }))
+                }
+                "get-logins" => self.get_logins(client, message),
+                "set-login" => self.set_login(client, message),
+                "generate-password" => {
+                    let password = PasswordGenerator::new().length(20).generate().map_err(|e| Failure::new(ACTION_CANCELLED_OR_DENIED, e.to_string()))?;
+                    Ok(json!({ "password": password }))
+                }
+                "lock-database" => {
+                    if let Some(session) = self.session.take() {
+                        session.lock().map_err(|e| Failure::new(ACTION_CANCELLED_OR_DENIED, e))?;
+                    }
+                    self.verified.clear();
+                    Ok(json!({}))
+                }
+                _ => Err(Failure::new(INCORRECT_ACTION, format!("Action non prise en charge: '{}'", action))),
+            }
+        }
+        
+        /// Association demandée par le bouton « Connecter » de l'extension : acceptée seulement
+        /// pendant un appairage ouvert avec 'keeprust proxy --pair'
+        fn associate(&mut self, client: &str, message: &Value) -> Result<Value, Failure> {
+            let id_key = field(message, "idKey").to_string();
+            if id_key.is_empty() {
+                return Err(Failure::new(ASSOCIATION_FAILED, "Clé d'identification manquante"));
+            }
+            let session = self.unlocked()?;
+            let name = session.db.associate_browser(&id_key).map_err(|e| {
+                eprintln!("Association refusée: {}", e);
+                Failure::new(ACTION_CANCELLED_OR_DENIED, e.to_string())
+            });
+            self.save()?;
+            let name = name?;
+            
+            eprintln!("Extension associée sous le nom '{}'.", name);
+            self.verified.insert(client.to_string(), name.clone());
+            Ok(json!({ "hash": self.unlocked()?.db.browser_hash(), "id": name }))
+        }
+        
+        /// Identifiants proposés pour une page; leur transmission est enregistrée comme une exposition
+        fn get_logins(&mut self, client: &str, message: &Value) -> Result<Value, Failure> {
+            let url = field(message, "url");
+            if url.is_empty() {
+                return Err(Failure::new(NO_URL_PROVIDED, "Aucune URL fournie"));
+            }
+            
+            let session = self.unlocked()?;
+            let keys = message["keys"].as_array().cloned().unwrap_or_default();
+            let association = keys.iter()
+                .find_map(|key| session.db.browser_association(field(key, "id"), field(key, "key")))
+                .ok_or_else(|| Failure::new(ASSOCIATION_FAILED, "Extension non associée"))?;
+            let (id, scope) = (association.name.clone(), association.group_scope());
+            
+            let logins = session.db.browser_logins(url, scope.as_ref());
+            if logins.is_empty() {
+                return Err(Failure::new(NO_LOGINS_FOUND, "Aucun identifiant pour ce site"));
+            }
+            let entries: Vec<Value> = logins.iter().map(|(path, entry)| entry_json(path, entry)).collect();
+            let ids: Vec<EntryId> = logins.iter().map(|(_, entry)| entry.id).collect();
+            let hash = session.db.browser_hash();
+            
+            session.record_exposure(&ids, ExposureKind::Provided, ExposureInterface::Browser)
+                .map_err(|e| Failure::new(ACTION_CANCELLED_OR_DENIED, e))?;
+            self.verified.insert(client.to_string(), id.clone());
+            Ok(json!({ "count": entries.len(), "entries": entries, "hash": hash, "id": id }))
+        }
+        
+        /// Enregistre l'identifiant saisi dans un formulaire : mise à jour de l'entrée désignée
+        /// par « uuid », sinon nouvelle entrée dans le groupe « Navigateur », dans la portée de
+        /// l'extension
+        fn set_login(&mut self, client: &str, message: &Value) -> Result<Value, Failure> {
+            let association = self.verified.get(client).cloned().ok_or_else(|| Failure::new(ASSOCIATION_FAILED, "Extension non associée"))?;
+            let url = field(message, "url").to_string();
+            let login = field(message, "login").to_string();
+            let password = field(message, "password").to_string();
+            let denied = |e: String| Failure::new(ACTION_CANCELLED_OR_DENIED, e);
+            
+            let uuid = field(message, "uuid").parse::<EntryId>();
+            if let Some(scope) = association_scope(&self.unlocked()?.db, &association) {
+                let refused = match &uuid {
+                    Ok(id) => matches!(self.unlocked()?.db.find_entry_in_scope(&scope, id), Err(ScopeError::Denied(_))).then(|| id.to_string()),
+                    Err(_) => (!scope.allows_group(BROWSER_GROUP)).then(|| format!("nouvelle (groupe {})", BROWSER_GROUP)),
+                };
+                if let Some(entry) = refused {
+                    return Err(self.deny(&association, entry));
+                }
+            }
+            
+            let session = self.unlocked()?;
+            match uuid {
+                Ok(id) => {
+                    session.db.update_entry(&id, |entry| entry.update(None, Some(login), Some(password), None, None))
+                        .map_err(|e| denied(e.to_string()))?;
+                    eprintln!("Identifiant mis à jour depuis le navigateur ({}).", id);
+                }
+                Err(_) => {
+                    let title = site_name(&url).or_else(|| url_host(&url)).unwrap_or_else(|| url.clone());
+                    let group = match session.db.root_group.subgroups.values().find(|group| group.name == BROWSER_GROUP) {
+                        Some(group) => group.id,
+                        None => session.db.add_group(None, BROWSER_GROUP.to_string()).map_err(|e| denied(e.to_string()))?,
+                    };
+                    let id = session.db.add_entry(Some(&group), Entry::new(title.clone(), login, password, url, String::new()))
+                        .map_err(|e| denied(e.to_string()))?;
+                    eprintln!("Identifiant '{}' enregistré depuis le navigateur ({}).", title, id);
+                }
+            }
+            self.save()?;
+            Ok(json!({ "count": null, "entries": null, "error": "", "hash": self.unlocked()?.db.browser_hash() }))
+        }
+    }
+    
+    /// Portée d'une extension associée; None si elle a accès à tous les groupes
+    fn association_scope(db: &Database, name: &str) -> Option<GroupScope> {
+        db.metadata.browser_associations.iter().find(|association| association.name == name).and_then(|association| association.group_scope())
+    }
+    
+    /// Hôte de messagerie native keepassxc-browser, lancé par le navigateur : lit les requêtes de
+    /// l'extension sur l'entrée standard jusqu'à la fermeture du navigateur
+    pub fn cmd_proxy(path: &Path) -> Result<(), String> {
+        let mut host = Host {
+            path: path.to_path_buf(),
+            secret: SecretKey::generate(&mut OsRng),
+            clients: HashMap::new(),
+            verified: HashMap::new(),
+            session: None,
+            modified: None,
+        };
+        
+        let mut input = io::stdin().lock();
+        let mut output = io::stdout().lock();
+        while let Some(request) = read_message(&mut input).map_err(|e| format!("Message du navigateur illisible: {}", e))? {
+            let response = host.handle(&request);
+            write_message(&mut output, &response).map_err(|e| format!("Réponse au navigateur impossible: {}", e))?;
+        }
+        
+        match host.session {
+            Some(session) => session.close(),
+            None => Ok(()),
+        }
+    }
+    
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        
+        #[test]
+        fn test_key_exchange_and_encrypted_request() {
+            let mut host = Host {
+                path: PathBuf::from("inexistant.krs"),
+                secret: SecretKey::generate(&mut OsRng),
+                clients: HashMap::new(),
+                verified: HashMap::new(),
+                session: None,
+                modified: None,
+            };
+            let client = SecretKey::generate(&mut OsRng);
+            let nonce = [0xffu8, 0xff, 7, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+            assert_eq!(&increment(&nonce)[..3], &[0, 0, 8]);
+            
+            // Échange de clés, en clair
+            let response = host.handle(&json!({
+                "action": "change-public-keys",
+                "publicKey": general_purpose::STANDARD.encode(client.public_key().as_bytes()),
+                "nonce": general_purpose::STANDARD.encode(nonce),
+                "clientID": "c1",
+            }));
+            assert_eq!(response["success"], "true");
+            let host_key: [u8; 32] = general_purpose::STANDARD.decode(field(&response, "publicKey")).unwrap().try_into().unwrap();
+            let channel = SalsaBox::new(&PublicKey::from(host_key), &client);
+            
+            // Requête chiffrée ne nécessitant pas la base
+            let message = channel.encrypt(Nonce::from_slice(&nonce), br#"{"action":"generate-password"}"#.as_slice()).unwrap();
+            let response = host.handle(&json!({
+                "action": "generate-password",
+                "message": general_purpose::STANDARD.encode(message),
+                "nonce": general_purpose::STANDARD.encode(nonce),
+                "clientID": "c1",
+            }));
+            let reply_nonce = decode(&response, "nonce", 0).ok().unwrap();
+            assert_eq!(reply_nonce, increment(&nonce));
+            let plaintext = channel.decrypt(Nonce::from_slice(&reply_nonce), decode(&response, "message", 0).ok().unwrap().as_slice()).unwrap();
+            let reply: Value = serde_json::from_slice(&plaintext).unwrap();
+            assert_eq!(reply["success"], "true");
+            assert_eq!(field(&reply, "password").chars().count(), 20);
+            
+            // Client inconnu, action inconnue
+            assert_eq!(host.handle(&json!({ "action": "get-logins", "clientID": "c2" }))["errorCode"], "3");
+            let message = channel.encrypt(Nonce::from_slice(&nonce), br#"{"action":"x"}"#.as_slice()).unwrap();
+            let response = host.handle(&json!({ "action": "x", "message": general_purpose::STANDARD.encode(message), "nonce": general_purpose::STANDARD.encode(nonce), "clientID": "c1" }));
+            assert_eq!(response["errorCode"], "12");
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+
+use crate::models::entry::{Entry, is_valid_url};
+use crate::models::id::{EntryId, GroupId};
+use super::commands;
+use super::policy;
+use super::session::{self, Session};
+
+/// Commentaire placé en tête du fichier à modifier
+const INSTRUCTIONS: &str = "\
+# Modifiez les champs puis enregistrez et fermez l'éditeur.
+# Ne modifiez pas les identifiants (id). Les mots de passe ne sont pas exportés.
+# Supprimer un bloc [[entry]] laisse l'entrée inchangée.
+";
+
+/// Contenu du fichier TOML soumis à l'éditeur
+#[derive(Debug, Serialize, Deserialize)]
+struct BulkFile {
+    #[serde(default, rename = "entry")]
+    entries: Vec<BulkEntry>,
+}
+
+/// Champs modifiables d'une entrée
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct BulkEntry {
+    id: EntryId,
+    title: String,
+    #[serde(default)]
+    username: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    notes: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+impl BulkEntry {
+    fn from_entry(entry: &Entry) -> Self {
+        Self {
+            id: entry.id,
+            title: entry.title.clone(),
+            username: entry.username.clone(),
+            url: entry.url.clone(),
+            notes: entry.notes.clone(),
+            tags: entry.tags.clone(),
+        }
+    }
+    
+    /// Liste les champs modifiés : (nom, ancienne valeur, nouvelle valeur)
+    fn changes(&self, edited: &BulkEntry) -> Vec<(&'static str, String, String)> {
+        let fields = [
+            ("title", &self.title, &edited.title),
+            ("username", &self.username, &edited.username),
+            ("url", &self.url, &edited.url),
+            ("notes", &self.notes, &edited.notes),
+        ];
+        
+        let mut changes: Vec<(&'static str, String, String)> = fields.into_iter()
+            .filter(|(_, before, after)| before != after)
+            .map(|(name, before, after)| (name, before.clone(), after.clone()))
+            .collect();
+        
+        if self.tags != edited.tags {
+            changes.push(("tags", self.tags.join(", "), edited.tags.join(", ")));
+        }
+        changes
+    }
+}
+
+/// Modifie les entrées d'un groupe dans l'éditeur de texte de l'utilisateur
+pub fn cmd_bulk_edit(path: &Path, group_id: Option<&GroupId>) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    
+    let group = match group_id {
+        Some(id) => session.db.find_group(id).ok_or_else(|| format!("Groupe avec ID '{}' non trouvé.", id))?,
+        None => &session.db.root_group,
+    };
+    
+    let mut originals: Vec<BulkEntry> = group.entries.values().map(BulkEntry::from_entry).collect();
+    originals.sort_by(|a, b| a.title.cmp(&b.title));
+    
+    if originals.is_empty() {
+        println!("Aucune entrée dans le groupe '{}'.", group.name);
+        return session.close();
+    }
+    
+    let group_name = group.name.clone();
+    let buffer = format!(
+        "# KeepRust - modification groupée du groupe '{}'\n{}\n{}",
+        group_name,
+        INSTRUCTIONS,
+        toml::to_string(&BulkFile { entries: originals.clone() }).map_err(|e| e.to_string())?
+    );
+    
+    let Some(edited) = edit_until_valid(&buffer, &originals)? else {
+        println!("Opération annulée.");
+        return session.close();
+    };
+    
+    // Ne conserver que les entrées réellement modifiées
+    let mut modified: Vec<(&BulkEntry, BulkEntry)> = edited.into_iter()
+        .filter_map(|entry| {
+            let original = originals.iter().find(|original| original.id == entry.id)?;
+            (*original != entry).then_some((original, entry))
+        })
+        .collect();
+    commands::skip_locked(&session.db, &mut modified, |(original, _)| original.id);
+    
+    if modified.is_empty() {
+        println!("Aucune modification.");
+        return session.close();
+    }
+    
+    println!("\nModifications:");
+    for (original, entry) in &modified {
+        println!("{} (ID: {})", original.title, original.id);
+        for (field, before, after) in original.changes(entry) {
+            println!("  {}: \"{}\" -> \"{}\"", field, before, after);
+        }
+    }
+    
+    println!();
+    if !session::confirm(&format!("Appliquer {} modification(s)?", modified.len()), false)? {
+        println!("Opération annulée.");
+        return session.close();
+    }
+    
+    for (_, edited) in &modified {
+        session.db.update_entry(&edited.id, |entry| {
+            let before = entry.clone();
+            entry.title = edited.title.clone();
+            entry.username = edited.username.clone();
+            entry.url = edited.url.clone();
+            entry.notes = edited.notes.clone();
+            entry.tags = edited.tags.clone();
+            entry.check_written_sizes(Some(&before))
+        }).map_err(|e| e.to_string())?.map_err(policy::oversized_error)?;
+    }
+    let ids: Vec<EntryId> = modified.iter().map(|(_, edited)| edited.id).collect();
+    policy::warn_oversized(&session.db, &ids);
+    
+    session.save()?;
+    println!("{} entrée(s) modifiée(s).", modified.len());
+    session.close()
+}
+
+/// Ouvre l'éditeur jusqu'à obtenir un fichier valide; retourne None si l'utilisateur abandonne
+fn edit_until_valid(buffer: &str, originals: &[BulkEntry]) -> Result<Option<Vec<BulkEntry>>, String> {
+    let file_path = env::temp_dir().join(format!("keeprust-bulk-{}.toml", EntryId::new().short()));
+    
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(&file_path)
+        .and_then(|mut file| file.write_all(buffer.as_bytes()))
+        .map_err(|e| e.to_string())?;
+    
+    let result = loop {
+        if let Err(e) = run_editor(&file_path) {
+            break Err(e);
+        }
+        
+        let content = match fs::read_to_string(&file_path) {
+            Ok(content) => content,
+            Err(e) => break Err(e.to_string()),
+        };
+        
+        match parse_edited(&content, originals) {
+            Ok(entries) => break Ok(Some(entries)),
+            Err(e) => {
+                // Sans terminal, rouvrir l'éditeur reproduirait la même erreur
+                if !session::is_interactive() {
+                    break Err(format!("Erreur dans le fichier: {}", e));
+                }
+                
+                println!("Erreur dans le fichier: {}", e);
+                match session::confirm("Rouvrir l'éditeur?", true) {
+                    Ok(true) => {}
+                    Ok(false) => break Ok(None),
+                    Err(e) => break Err(e),
+                }
+            }
+        }
+    };
+    
+    let _ = fs::remove_file(&file_path);
+    result
+}
+
+/// Lance l'éditeur défini par $VISUAL ou $EDITOR (vi ou notepad par défaut)
+fn run_editor(file_path: &Path) -> Result<(), String> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| if cfg!(windows) { "notepad" } else { "vi" }.to_string());
+    
+    // La variable peut contenir des arguments (ex: "code --wait")
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().ok_or("Aucun éditeur configuré ($EDITOR).")?;
+    
+    let status = Command::new(program)
+        .args(parts)
+        .arg(file_path)
+        .status()
+        .map_err(|e| format!("Impossible de lancer l'éditeur '{}': {}", program, e))?;
+    
+    if !status.success() {
+        return Err(format!("L'éditeur '{}' s'est terminé avec une erreur.", program));
+    }
+    Ok(())
+}
+
+/// Analyse et valide le fichier modifié
+fn parse_edited(content: &str, originals: &[BulkEntry]) -> Result<Vec<BulkEntry>, String> {
+    let file: BulkFile = toml::from_str(content).map_err(|e| e.to_string())?;
+    
+    let known: HashSet<EntryId> = originals.iter().map(|entry| entry.id).collect();
+    let mut seen = HashSet::new();
+    
+    file.entries.into_iter()
+        .map(|mut entry| {
+            if !known.contains(&entry.id) {
+                return Err(format!("Entrée inconnue dans ce groupe: {}", entry.id));
+            }
+            if !seen.insert(entry.id) {
+                return Err(format!("Entrée présente plusieurs fois: {}", entry.id));
+            }
+            
+            entry.title = entry.title.trim().to_string();
+            if entry.title.is_empty() {
+                return Err(format!("Le titre de l'entrée {} ne peut pas être vide", entry.id));
+            }
+            if !entry.url.is_empty() && !is_valid_url(&entry.url) {
+                return Err(format!("URL invalide pour '{}': {}", entry.title, entry.url));
+            }
+            Ok(entry)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn original() -> BulkEntry {
+        BulkEntry {
+            id: EntryId::new(),
+            title: "Mail".to_string(),
+            username: "bob".to_string(),
+            url: String::new(),
+            notes: String::new(),
+            tags: Vec::new(),
+        }
+    }
+    
+    #[test]
+    fn test_parse_edited_round_trip_and_validation() {
+        let originals = vec![original()];
+        let mut edited = originals[0].clone();
+        edited.username = "alice".to_string();
+        
+        let content = toml::to_string(&BulkFile { entries: vec![edited.clone()] }).unwrap();
+        assert_eq!(parse_edited(&content, &originals).unwrap(), vec![edited.clone()]);
+        assert_eq!(originals[0].changes(&edited), vec![("username", "bob".to_string(), "alice".to_string())]);
+        
+        edited.title = "  ".to_string();
+        let content = toml::to_string(&BulkFile { entries: vec![edited] }).unwrap();
+        assert!(parse_edited(&content, &originals).is_err());
+        
+        let content = toml::to_string(&BulkFile { entries: vec![original()] }).unwrap();
+        assert!(parse_edited(&content, &originals).unwrap_err().contains("inconnue"));
+    }
+}
\ No newline at end of file
@@ -1,27 +1,133 @@
-use crate::models::{database::Database, entry::Entry, group::Group, repository::Repository};
+use crate::models::{database::{Database, DatabaseError}, entry::{ColorLabel, CustomField, Entry, EntryError, is_valid_url, rewrite_url_domain, site_name, url_host}, repository::{MIN_ITERATIONS, PayloadFormat, Repository, RepositoryError}};
+use crate::models::clock;
+use crate::models::device::DeviceStamp;
+use crate::models::exposure::{ExposureInterface, ExposureKind};
+use crate::models::id::{EntryId, GroupId};
+use crate::models::lookup::EntryRef;
+use crate::models::inspect::{self, Inspection};
+use crate::models::stats::EXPIRING_SOON_DAYS;
+use crate::models::query::{Comparison, Predicate, Query, QueryContext};
+use crate::models::recovery;
+use crate::models::replace::{Replacement, TextField};
+use crate::models::search_index::IndexedEntry;
+use crate::models::walker::WalkControl;
+use crate::events::{self, SecurityEvent};
+use crate::crypto::key_derivation::{self, KdfParams};
+use crate::crypto::keyfile;
+use crate::crypto::otp::OtpConfig;
+use crate::crypto::selftest::{self, SelfTestResult};
+use crate::utils::breach::BreachChecker;
+use crate::utils::dates::{self, Direction};
+use crate::utils::fixture::{self, FixtureOptions};
 use crate::utils::password_generator::PasswordGenerator;
+use crate::utils::redact::Redactor;
+use crate::utils::symbols::{self, Symbol};
+use crate::utils::webdav;
+use chrono::{DateTime, Utc};
 use clap::Parser;
-use rpassword::read_password;
-use std::io::{self, Write};
-use std::path::Path;
-use crate::utils::clipboard::{copy_password_securely, copy_to_clipboard};
-use crate::utils::password_analyzer::{PasswordAnalyzer, PasswordStrength};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+use url::Url;
+use zeroize::Zeroizing;
+use crate::utils::clipboard::{SecureClipboard, copy_password_securely, copy_to_clipboard};
+use crate::utils::password_analyzer::PasswordStrength;
 
-use super::app::{Cli, Commands};
+use super::app::{AgentAction, Cli, Commands, FieldArgs, GroupAction, IndexAction, OtpAction, PolicyAction, ReportKind, ScriptAction, SigningAction, SyncAction, VaultsAction};
+use super::agent;
+use super::attachments;
+use super::autotype;
+use super::beam;
+use super::browser;
+use super::bulk_edit;
+use super::completions;
+use super::defaults::EntryDefaults;
+use super::device;
+use super::discovery;
+use super::git_sync;
+use super::group_picker;
+use super::help;
+use super::index;
+use super::interop;
+use super::login_item;
+use super::monitor;
+use super::otp;
+use super::output::{self, EntryDetail, EntrySummary};
+use super::plugins;
+use super::policy;
+use super::profiles::Profiles;
+use super::script;
+use super::secret_service;
+use super::session::{self, SearchSource, Session, SessionOptions};
+use super::shell;
+use super::signing;
+use super::strength;
+use super::sync;
+use super::tui;
+
+/// Variable d'environnement fixant le plancher d'itérations PBKDF2 (remplacée par --min-iterations)
+const MIN_ITERATIONS_ENV: &str = "KEEPRUST_MIN_ITERATIONS";
+
+/// Délai d'effacement du presse-papiers après un ajout rapide, en secondes
+const QUICK_CLEAR_SECONDS: u64 = 30;
 
 /// Exécute la commande CLI spécifiée
 pub fn run() -> Result<(), String> {
     let cli = Cli::parse();
     
-    // Chemin de la base de données
-    let db_path = Path::new(&cli.database);
+    // Chemin de la base de données : celle du profil choisi, sinon --database, sinon keeprust.krs
+    // (dans %APPDATA%\keeprust sous Windows)
+    let db_path = match (&cli.profile, &cli.database) {
+        (Some(name), _) => Profiles::load()?.get(name)?.path.clone(),
+        (None, Some(database)) => PathBuf::from(database),
+        (None, None) => discovery::default_database(),
+    };
+    
+    device::configure();
+    output::configure(cli.output);
+    symbols::set_plain(cli.plain);
+    session::configure(SessionOptions {
+        timings: cli.timings,
+        password_stdin: cli.password_stdin,
+        password_file: cli.password_file.clone(),
+        assume_yes: cli.yes,
+        as_of: cli.as_of.as_deref().map(|date| parse_date("--as-of", date, Direction::Past)).transpose()?,
+        min_iterations: match cli.min_iterations {
+            Some(min) => Some(min),
+            None => min_iterations_from_env()?,
+        },
+        keyfile: cli.keyfile.clone(),
+        include_trash: cli.include_trash,
+    });
+    
+    // Base par défaut absente : proposer celles trouvées aux emplacements usuels
+    let db_path = if cli.profile.is_none() && cli.database.is_none() && !db_path.exists()
+        && cli.command.as_ref().is_none_or(discovery::opens_database)
+    {
+        discovery::discover()?.unwrap_or(db_path)
+    } else {
+        db_path
+    };
+    if cli.profile.is_none() && cli.database.is_none() && matches!(cli.command, Some(Commands::New { .. })) {
+        discovery::prepare_default_database(&db_path)?;
+    }
+    let db_path = db_path.as_path();
+    
+    // Profil des scripts d'enregistrement : celui choisi, sinon celui qui désigne la base
+    let profile = cli.profile.clone().or_else(|| Some(Profiles::load().ok()?.for_path(db_path)?.0.to_string()));
+    if let Some(name) = &profile {
+        events::set_profile(name);
+    }
     
     // Si aucune commande n'est spécifiée, ouvrir la base de données (si elle existe)
     // ou afficher l'aide
     let command = match cli.command {
         Some(cmd) => cmd,
         None => {
-            if db_path.exists() {
+            if db_path.exists() || webdav::is_remote(db_path) {
                 Commands::Open
             } else {
                 print_help();
@@ -30,23 +136,130 @@ pub fn run() -> Result<(), String> {
         }
     };
     
-    // Exécuter la commande appropriée
+    dispatch(db_path, command)
+}
+
+/// Exécute une commande sur la base de données (ligne de commande ou shell interactif)
+pub fn dispatch(db_path: &Path, command: Commands) -> Result<(), String> {
     match command {
-        Commands::New { name, description } => cmd_new(db_path, name, description),
+        Commands::New { name, description, format, kdf } => cmd_new(db_path, name, description, format, kdf),
         Commands::Open => cmd_open(db_path),
-        Commands::Show { id, show_password } => cmd_show(db_path, &id, show_password),
-        Commands::List { group } => cmd_list(db_path, group.as_deref()),
-        Commands::Add { title, username, password, url, notes, group, generate, length } => 
-            cmd_add(db_path, title, username, password, url, notes, group, generate, length),
-        Commands::Generate { length, no_uppercase, no_lowercase, no_digits, no_symbols, exclude_similar, exclude_ambiguous } => {
+        Commands::Show { id, show_password, redact } => {
+            let redactor = if show_password { Redactor::Reveal } else { redact.unwrap_or_default() };
+            cmd_show(db_path, &id, redactor)
+        }
+        Commands::List { group, query, updated_since, older_than, all } => {
+            cmd_list(db_path, group.as_ref(), query.as_deref(), updated_since.as_deref(), older_than.as_deref(), all)
+        },
+        Commands::Search { query, ignore_case, all, long } => cmd_search(db_path, &query, ignore_case, all, long),
+        Commands::Add { title, username, from_url, from_otpauth, password, url, notes, group, generate, length, expires, tags, color, fields } => {
+            let prefill = Prefill::resolve(title, username, from_url.or(url), from_otpauth)?;
+            cmd_add(db_path, prefill, password, notes, group, generate, length, expires, tags, color, fields)
+        },
+        Commands::Quick { title, username, generate, copy } => cmd_quick_add(db_path, title, username, generate, copy),
+        Commands::Generate { passphrase: true, words, separator, capitalize, digit, .. } => {
+            cmd_generate_passphrase(words, &separator, capitalize, digit)
+        },
+        Commands::Generate { length, no_uppercase, no_lowercase, no_digits, no_symbols, exclude_similar, exclude_ambiguous, .. } => {
             cmd_generate(length, !no_uppercase, !no_lowercase, !no_digits, !no_symbols, exclude_similar, exclude_ambiguous)
         },
         Commands::AddGroup { name, parent } => cmd_add_group(db_path, name, parent),
-        Commands::Copy { id, timeout } => cmd_copy_password(db_path, &id, timeout),
+        Commands::Group { action: GroupAction::Rename { id, name } } => cmd_rename_group(db_path, &id, name),
+        Commands::Group { action: GroupAction::Move { id, to } } => cmd_move_group(db_path, &id, to.as_ref()),
+        Commands::Group { action: GroupAction::Delete { id, keep_contents, unlock_entry } } => cmd_delete_group(db_path, &id, keep_contents, unlock_entry),
+        Commands::Copy { id, timeout, field: Some(name), .. } => cmd_copy_field(db_path, &id, &name, timeout),
+        Commands::Copy { id, timeout, both: false, .. } => cmd_copy_password(db_path, &id, timeout),
+        Commands::Copy { id, timeout, both: true, delay, .. } => cmd_copy_both(db_path, &id, timeout, delay),
+        Commands::Beam { id, timeout, bind } => beam::cmd_beam(db_path, &id, timeout, bind),
+        Commands::Share { id, minutes, uses, bind } => beam::cmd_share(db_path, &id, minutes, uses, bind),
+        Commands::Autotype { id, set_sequence: Some(sequence), .. } => autotype::cmd_autotype_sequence(db_path, &id, Some(sequence)),
+        Commands::Autotype { id, reset_sequence: true, .. } => autotype::cmd_autotype_sequence(db_path, &id, None),
+        Commands::Autotype { id, delay, .. } => autotype::cmd_autotype(db_path, &id, delay),
+        Commands::Totp { id, copy } => otp::cmd_otp_code(db_path, &id, copy),
+        Commands::Otp { action } => match action {
+            OtpAction::Set { id, uri } => otp::cmd_otp_set(db_path, &id, uri),
+            OtpAction::ImportQr { image, id } => otp::cmd_otp_import_qr(db_path, &image, &id),
+            OtpAction::Code { id, copy } => otp::cmd_otp_code(db_path, &id, copy),
+            OtpAction::Uri { id } => otp::cmd_otp_uri(db_path, &id),
+            OtpAction::Remove { id } => otp::cmd_otp_remove(db_path, &id),
+        },
+        Commands::Attach { id, file, name } => attachments::cmd_attach(db_path, &id, &file, name.as_deref()),
+        Commands::Attachments { id } => attachments::cmd_attachments(db_path, &id),
+        Commands::Extract { id, name, file, preview } => {
+            attachments::cmd_extract(db_path, &id, &name, file.as_deref(), preview)
+        },
+        Commands::Edit { id, title, username, password, url, notes, generate, length, fields, remove_fields, unlock_entry } =>
+            cmd_edit(db_path, &id, title, username, password, url, notes, generate, length, fields, remove_fields, unlock_entry),
         Commands::CopyUser { id } => cmd_copy_username(db_path, &id),
+        Commands::Archive { id } => cmd_set_archived(db_path, &id, true),
+        Commands::Unarchive { id } => cmd_set_archived(db_path, &id, false),
+        Commands::LockEntry { id } => cmd_set_locked(db_path, &id, true),
+        Commands::UnlockEntry { id } => cmd_set_locked(db_path, &id, false),
+        Commands::Delete { id, permanent, unlock_entry } => cmd_delete(db_path, &id, permanent, unlock_entry),
+        Commands::Restore { id } => cmd_restore(db_path, &id),
+        Commands::EmptyTrash => cmd_empty_trash(db_path),
+        Commands::Label { id, color } => cmd_set_color(db_path, &id, color),
         Commands::Analyze { password } => cmd_analyze_password(password),
-        Commands::History { id } => cmd_show_history(db_path, &id),
-        Commands::Audit => cmd_audit_passwords(db_path),
+        Commands::History { id, diff } => cmd_show_history(db_path, &id, diff),
+        Commands::RewriteUrls { from, to, dry_run } => cmd_rewrite_urls(db_path, &from, &to, dry_run),
+        Commands::Replace { field, find, replace, regex, dry_run } => cmd_replace(db_path, field, &find, &replace, regex, dry_run),
+        Commands::Report { kind: ReportKind::Emails { address, all } } => cmd_report_emails(db_path, address.as_deref(), all),
+        Commands::Audit { all, min_score, hibp } => cmd_audit_passwords(db_path, all, min_score, hibp),
+        Commands::CheckBreach { id } => cmd_check_breach(db_path, &id),
+        Commands::Policy { action: PolicyAction::Check { policy: file } } => policy::cmd_policy_check(db_path, file.as_deref()),
+        Commands::Signing { action: SigningAction::Init } => signing::cmd_signing_init(db_path),
+        Commands::Signing { action: SigningAction::Rotate } => signing::cmd_signing_rotate(),
+        Commands::Signing { action: SigningAction::Trust { key } } => signing::cmd_signing_trust(&key),
+        Commands::Index { action: IndexAction::Enable } => index::cmd_index_enable(db_path),
+        Commands::Index { action: IndexAction::Disable } => index::cmd_index_disable(db_path),
+        Commands::Index { action: IndexAction::Status } => index::cmd_index_status(db_path),
+        Commands::Shell { lock_after } => shell::cmd_shell(db_path, lock_after),
+        Commands::Agent { action: None, lock_after, keychain } => agent::cmd_agent(lock_after, keychain),
+        Commands::Agent { action: Some(AgentAction::EnableLogin { lock_after }), .. } => login_item::cmd_enable_login(lock_after),
+        Commands::Agent { action: Some(AgentAction::DisableLogin), .. } => login_item::cmd_disable_login(),
+        Commands::Vaults { action } => match action.unwrap_or(VaultsAction::List) {
+            VaultsAction::List => agent::cmd_vaults_list(),
+            VaultsAction::Unlock { profile, lock_after } => agent::cmd_vaults_unlock(db_path, profile.as_deref(), lock_after),
+            VaultsAction::Lock { profile, all } => agent::cmd_vaults_lock(db_path, profile.as_deref(), all),
+        },
+        Commands::Lock => agent::cmd_lock(),
+        Commands::Tui { timeout } => tui::cmd_tui(db_path, timeout),
+        Commands::Proxy { pair: Some(name), scope, .. } => browser::cmd_proxy_pair(db_path, &name, &scope),
+        Commands::Proxy { revoke: Some(name), .. } => browser::cmd_proxy_revoke(db_path, &name),
+        Commands::Proxy { list: true, .. } => browser::cmd_proxy_list(db_path),
+        Commands::Proxy { manifest: Some(browser), .. } => browser::cmd_proxy_manifest(db_path, &browser),
+        Commands::Proxy { .. } => browser::cmd_proxy(db_path),
+        Commands::Service { lock_after, scope } => secret_service::cmd_service(db_path, lock_after, &scope),
+        Commands::Monitor { interval, hibp, once } => monitor::cmd_monitor(db_path, interval, hibp, once),
+        Commands::Device { rename } => device::cmd_device(rename),
+        Commands::Merge { file } => sync::cmd_merge(db_path, &file),
+        Commands::Sync { action: None } => git_sync::cmd_sync(db_path),
+        Commands::Sync { action: Some(SyncAction::Init { remote }) } => git_sync::cmd_sync_init(db_path, &remote),
+        Commands::Sync { action: Some(SyncAction::Disable) } => git_sync::cmd_sync_disable(db_path),
+        Commands::Conflicts { resolve } => sync::cmd_conflicts(db_path, resolve),
+        Commands::Export { format, group, file, no_passwords } => interop::cmd_export(db_path, format, group.as_ref(), file.as_deref(), no_passwords),
+        Commands::Import { format, file, group } => interop::cmd_import(db_path, format, &file, group.as_ref()),
+        Commands::ExportStructure { group, file } => interop::cmd_export_structure(db_path, group.as_ref(), file.as_deref()),
+        Commands::ImportStructure { file, group } => interop::cmd_import_structure(db_path, &file, group.as_ref()),
+        Commands::CopyGroup { id, to_db, to_group, move_group, keep_ids } =>
+            sync::cmd_copy_group(db_path, &id, &to_db, to_group.as_ref(), move_group, keep_ids),
+        Commands::ChangePassword => cmd_change_password(db_path),
+        Commands::KeygenKeyfile { file } => cmd_keygen_keyfile(&file),
+        Commands::UpgradeKdf { memory, passes, parallelism } => cmd_upgrade_kdf(db_path, memory, passes, parallelism),
+        Commands::KdfBenchmark { kdf } => cmd_kdf_benchmark(kdf),
+        Commands::Selftest => cmd_selftest(),
+        Commands::Recover => cmd_recover(db_path),
+        Commands::Inspect { file } => cmd_inspect(&file),
+        Commands::Help { topic } => help::cmd_help(topic.as_deref()),
+        Commands::Manpages { out } => help::cmd_manpages(&out),
+        Commands::Completions { shell } => completions::cmd_completions(shell),
+        Commands::Complete { shell, index, words } => completions::cmd_complete(shell, index, &words),
+        Commands::Script { action: ScriptAction::Run { file, args } } => script::cmd_script_run(db_path, &file, args),
+        Commands::External(args) => plugins::run_plugin(db_path, &args),
+        Commands::BulkEdit { group } => bulk_edit::cmd_bulk_edit(db_path, group.as_ref()),
+        Commands::GenFixture { entries, depth, seed, password, format, force } => {
+            cmd_gen_fixture(db_path, FixtureOptions { entries, depth, seed }, &password, format, force)
+        },
     }
 }
 
@@ -54,119 +267,129 @@ pub fn run() -> Result<(), String> {
 fn print_help() {
     println!("KeeRust - Gestionnaire de mots de passe sécurisé");
     println!("Utilisez --help pour afficher l'aide complète.");
+    
+    let plugins = plugins::list_plugins();
+    if !plugins.is_empty() {
+        println!("Commandes externes: {}", plugins.join(", "));
+    }
+}
+
+/// Mention de l'appareil à l'origine d'une modification (" sur portable-x"), vide s'il est inconnu
+fn device_suffix(device: Option<&DeviceStamp>) -> String {
+    device.map(|device| format!(" sur {}", device)).unwrap_or_default()
+}
+
+/// Plancher d'itérations défini par la variable d'environnement KEEPRUST_MIN_ITERATIONS
+fn min_iterations_from_env() -> Result<Option<u32>, String> {
+    match std::env::var(MIN_ITERATIONS_ENV) {
+        Ok(value) => value.trim().parse().map(Some)
+            .map_err(|_| format!("{} invalide: '{}' (nombre entier attendu).", MIN_ITERATIONS_ENV, value)),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Analyse la date d'une option (absolue ou relative, voir `dates::parse_date`)
+fn parse_date(flag: &str, date: &str, direction: Direction) -> Result<DateTime<Utc>, String> {
+    dates::parse_date(date, direction).map_err(|e| format!("{}: {}", flag, e))
 }
 
 /// Crée une nouvelle base de données
-fn cmd_new(path: &Path, name: Option<String>, description: Option<String>) -> Result<(), String> {
+fn cmd_new(path: &Path, name: Option<String>, description: Option<String>, format: PayloadFormat, kdf: KdfParams) -> Result<(), String> {
     // Vérifier si le fichier existe déjà
     if path.exists() {
         println!("Le fichier {} existe déjà.", path.display());
-        print!("Voulez-vous l'écraser? (o/N): ");
-        io::stdout().flush().map_err(|e| e.to_string())?;
-        
-        let mut input = String::new();
-        io::stdin().read_line(&mut input).map_err(|e| e.to_string())?;
-        
-        if !input.trim().eq_ignore_ascii_case("o") && !input.trim().eq_ignore_ascii_case("oui") {
+        if !session::confirm("Voulez-vous l'écraser?", false)? {
             println!("Opération annulée.");
             return Ok(());
         }
     }
+    let repo = Repository::new(path);
+    if repo.exists_on_server().map_err(|e| e.to_string())? {
+        return Err(format!("{} existe déjà sur le serveur.", path.display()));
+    }
     
     // Demander un nom si non spécifié
     let db_name = match name {
         Some(n) => n,
-        None => {
-            print!("Nom de la base de données: ");
-            io::stdout().flush().map_err(|e| e.to_string())?;
-            
-            let mut input = String::new();
-            io::stdin().read_line(&mut input).map_err(|e| e.to_string())?;
-            input.trim().to_string()
-        }
+        None => session::prompt_line("Nom de la base de données: ")?,
     };
     
     // Demander une description si non spécifiée
     let db_description = match description {
         Some(d) => d,
-        None => {
-            print!("Description (optionnelle): ");
-            io::stdout().flush().map_err(|e| e.to_string())?;
-            
-            let mut input = String::new();
-            io::stdin().read_line(&mut input).map_err(|e| e.to_string())?;
-            input.trim().to_string()
-        }
+        None if session::is_interactive() => session::prompt_line("Description (optionnelle): ")?,
+        None => String::new(),
     };
     
     // Demander un mot de passe maître
-    print!("Mot de passe maître: ");
-    io::stdout().flush().map_err(|e| e.to_string())?;
-    let password = read_password().map_err(|e| e.to_string())?;
+    let password = session::prompt_master_password("Mot de passe maître: ")?;
     
-    // Demander une confirmation du mot de passe
-    print!("Confirmez le mot de passe: ");
-    io::stdout().flush().map_err(|e| e.to_string())?;
-    let confirm_password = read_password().map_err(|e| e.to_string())?;
-    
-    // Vérifier que les mots de passe correspondent
-    if password != confirm_password {
-        return Err("Les mots de passe ne correspondent pas.".to_string());
+    // Demander une confirmation du mot de passe (inutile s'il n'est pas saisi au clavier)
+    if !session::master_password_provided() {
+        let confirm_password = session::prompt_password("Confirmez le mot de passe: ")?;
+        
+        // Vérifier que les mots de passe correspondent
+        if password != confirm_password {
+            return Err("Les mots de passe ne correspondent pas.".to_string());
+        }
     }
     
     // Créer la base de données
-    let db = Database::new(db_name, db_description);
+    let mut db = Database::new(db_name, db_description);
+    db.storage_format = format;
     
-    // Sauvegarder la base de données
-    let repo = Repository::new(path);
-    repo.save(&db, &password).map_err(|e| e.to_string())?;
+    // Sauvegarder la base de données (avec le fichier clé éventuel)
+    let secret = session::master_secret(password)?;
+    let mut timings = session::timings();
+    repo.save_with_kdf(&db, &secret, kdf, &mut timings).map_err(|e| e.to_string())?;
+    session::report_timings("enregistrement", &timings);
+    signing::sign_vault(path)?;
     
-    println!("Base de données créée avec succès: {}", path.display());
+    println!("Base de données créée avec succès: {} ({})", path.display(), kdf);
     Ok(())
 }
 
 /// Ouvre une base de données existante
 fn cmd_open(path: &Path) -> Result<(), String> {
-    // Vérifier si le fichier existe
-    if !path.exists() {
-        return Err(format!("Le fichier {} n'existe pas.", path.display()));
-    }
-    
-    // Demander le mot de passe
-    print!("Mot de passe: ");
-    io::stdout().flush().map_err(|e| e.to_string())?;
-    let password = read_password().map_err(|e| e.to_string())?;
-    
     // Ouvrir la base de données
-    let repo = Repository::new(path);
-    let db = match repo.load(&password) {
-        Ok(db) => db,
-        Err(e) => return Err(format!("Erreur lors de l'ouverture de la base de données: {}", e)),
-    };
+    let session = Session::open(path)?;
+    
+    // Compter les entrées (index de recherche déjà chargé, sinon toute l'arborescence)
+    let stats = session.stats();
+    let db = &session.db;
     
     // Afficher les informations de la base de données
     println!("\nBase de données: {}", db.metadata.name);
     println!("Description: {}", db.metadata.description);
     println!("Version: {}", db.metadata.version);
+    println!("Format: {}", db.storage_format);
     println!("Créée le: {}", db.metadata.created_at.format("%d-%m-%Y %H:%M:%S"));
-    println!("Modifiée le: {}", db.metadata.updated_at.format("%d-%m-%Y %H:%M:%S"));
+    println!("Modifiée le: {}{}", db.metadata.updated_at.format("%d-%m-%Y %H:%M:%S"), device_suffix(db.metadata.modified_on.as_ref()));
     
-    // Afficher la structure
+    // Afficher la structure avec le nombre d'entrées de chaque groupe
     println!("\nStructure:");
-    println!("- {}", db.root_group.name);
+    for group in &stats.groups {
+        println!("{}- {} ({} entrées)", "  ".repeat(group.depth), group.name, group.total_entries);
+    }
     
-    for (_, subgroup) in &db.root_group.subgroups {
-        println!("  - {} ({} entrées)", subgroup.name, subgroup.entries.len());
+    println!("\nNombre total d'entrées: {}", stats.total_entries);
+    println!("Nombre total de groupes: {}", db.count_groups());
+    
+    match db.metadata.last_audit {
+        Some(date) => println!("Dernier audit: {}", date.format("%d-%m-%Y %H:%M:%S")),
+        None => println!("Dernier audit: jamais"),
     }
     
-    // Compter le nombre total d'entrées
-    let mut total_entries = db.root_group.entries.len();
-    for (_, subgroup) in &db.root_group.subgroups {
-        total_entries += subgroup.entries.len();
+    if stats.expired > 0 {
+        println!("{}Entrées expirées: {}", Symbol::Warning, stats.expired);
     }
+    println!("Entrées expirant dans les {} jours: {}", EXPIRING_SOON_DAYS, stats.expiring_soon);
     
-    println!("\nNombre total d'entrées: {}", total_entries);
-    Ok(())
+    if stats.expired > 0 || stats.expiring_soon > 0 {
+        events::emit(path, &SecurityEvent::ExpiringEntries { expired: stats.expired, expiring_soon: stats.expiring_soon });
+    }
+    
+    session.close()
 }
 
 /// Génère un mot de passe aléatoire
@@ -187,70 +410,131 @@ fn cmd_generate(length: usize, use_uppercase: bool, use_lowercase: bool, use_dig
         .map_err(|e| e.to_string())?;
     
     // Afficher le mot de passe
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({ "password": password }));
+    }
     println!("{}", password);
     Ok(())
 }
 
-/// Affiche les informations d'une entrée spécifique
-fn cmd_show(path: &Path, id: &str, show_password: bool) -> Result<(), String> {
-    // Vérifier si le fichier existe
-    if !path.exists() {
-        return Err(format!("Le fichier {} n'existe pas.", path.display()));
-    }
-    
-    // Demander le mot de passe
-    print!("Mot de passe: ");
-    io::stdout().flush().map_err(|e| e.to_string())?;
-    let password = read_password().map_err(|e| e.to_string())?;
+/// Génère une phrase de passe
+fn cmd_generate_passphrase(words: usize, separator: &str, capitalize: bool, add_digit: bool) -> Result<(), String> {
+    let passphrase = PasswordGenerator::generate_passphrase(words, separator, capitalize, add_digit)
+        .map_err(|e| e.to_string())?;
     
+    if output::is_json() {
+        return output::print_json(&serde_json::json!({ "password": passphrase }));
+    }
+    println!("{}", passphrase);
+    Ok(())
+}
+
+/// Affiche les informations d'une entrée spécifique
+fn cmd_show(path: &Path, id: &EntryRef, redactor: Redactor) -> Result<(), String> {
     // Ouvrir la base de données
-    let repo = Repository::new(path);
-    let db = match repo.load(&password) {
-        Ok(db) => db,
-        Err(e) => return Err(format!("Erreur lors de l'ouverture de la base de données: {}", e)),
-    };
+    let mut session = Session::open(path)?;
+    let id = &session.resolve(id)?;
     
-    // Rechercher l'entrée
-    let entry = match db.find_entry(id) {
-        Some(entry) => entry,
-        None => return Err(format!("Entrée avec ID '{}' non trouvée.", id)),
-    };
+    // Rechercher l'entrée (refusée si elle est dans la corbeille) avant d'enregistrer l'affichage
+    session.entry(id)?;
+    if redactor == Redactor::Reveal {
+        session.record_exposure(&[*id], ExposureKind::Revealed, ExposureInterface::Cli)?;
+    }
+    let db = &session.db;
+    let entry = db.find_entry(id).ok_or_else(|| format!("Entrée avec ID '{}' non trouvée.", id))?;
+    
+    if output::is_json() {
+        output::print_json(&EntryDetail::new(&db.entry_path(id).unwrap_or_default(), entry, redactor))?;
+        return session.close();
+    }
     
     // Afficher les informations de l'entrée
     println!("\nEntrée: {}", entry.title);
     println!("ID: {}", entry.id);
     println!("Utilisateur: {}", entry.username);
-    if show_password {
-        println!("Mot de passe: {}", entry.password);
-    } else {
-        println!("Mot de passe: ********");
-    }
+    println!("Mot de passe: {}", redactor.redact(&entry.password));
     println!("URL: {}", entry.url);
     println!("Notes: {}", entry.notes);
+    if !entry.tags.is_empty() {
+        println!("Étiquettes: {}", entry.tags.join(", "));
+    }
+    if entry.locked {
+        println!("Verrouillée: oui (--unlock-entry pour la modifier ou la supprimer)");
+    }
+    if let Some(color) = entry.color {
+        println!("Couleur: {}", color);
+    }
+    if let Some(otp) = &entry.otp {
+        println!("Code à usage unique: {}", otp);
+    }
+    if !entry.custom_fields.is_empty() {
+        println!("Champs:");
+        for field in &entry.custom_fields {
+            println!("  {}: {}", field.name, field.display_value(redactor));
+        }
+    }
+    if !entry.attachments.is_empty() {
+        let names: Vec<&str> = entry.attachments.iter().map(|attachment| attachment.name.as_str()).collect();
+        println!("Pièces jointes: {}", names.join(", "));
+    }
+    if let Some(sequence) = &entry.autotype {
+        println!("Saisie automatique: {}", sequence);
+    }
     println!("Créé le: {}", entry.created_at.format("%d-%m-%Y %H:%M:%S"));
-    println!("Modifié le: {}", entry.updated_at.format("%d-%m-%Y %H:%M:%S"));
+    println!("Modifié le: {}{}", entry.updated_at.format("%d-%m-%Y %H:%M:%S"), device_suffix(entry.modified_on.as_ref()));
+    if let Some(exposure) = &entry.last_exposure {
+        println!("Mot de passe en clair: {}", exposure);
+    }
     
-    Ok(())
+    session.close()
 }
 
-/// Liste toutes les entrées ou les entrées d'un groupe spécifique
-fn cmd_list(path: &Path, group_id: Option<&str>) -> Result<(), String> {
-    // Vérifier si le fichier existe
-    if !path.exists() {
-        return Err(format!("Le fichier {} n'existe pas.", path.display()));
+/// Liste toutes les entrées ou les entrées d'un groupe spécifique (sans les entrées archivées, sauf avec `all`)
+fn cmd_list(path: &Path, group_id: Option<&GroupId>, query: Option<&str>, updated_since: Option<&str>,
+            older_than: Option<&str>, all: bool) -> Result<(), String> {
+    // Valider la requête avant de demander le mot de passe
+    let mut query = query.map(Query::parse).transpose().map_err(|e| e.to_string())?;
+    
+    // --updated-since et --older-than s'ajoutent à la requête comme des critères age:
+    let since = updated_since.map(|date| parse_date("--updated-since", date, Direction::Past)).transpose()?
+        .map(|date| Predicate::Age(Comparison::LessOrEqual, clock::now() - date));
+    let older = older_than.map(|duration| dates::parse_duration(duration).map_err(|e| format!("--older-than: {}", e)))
+        .transpose()?
+        .map(|duration| Predicate::Age(Comparison::Greater, duration));
+    for predicate in since.into_iter().chain(older) {
+        let term = Query::Term(predicate);
+        query = Some(match query {
+            Some(query) => Query::And(Box::new(query), Box::new(term)),
+            None => term,
+        });
     }
     
-    // Demander le mot de passe
-    print!("Mot de passe: ");
-    io::stdout().flush().map_err(|e| e.to_string())?;
-    let password = read_password().map_err(|e| e.to_string())?;
-    
     // Ouvrir la base de données
-    let repo = Repository::new(path);
-    let db = match repo.load(&password) {
-        Ok(db) => db,
-        Err(e) => return Err(format!("Erreur lors de l'ouverture de la base de données: {}", e)),
-    };
+    let session = Session::open(path)?;
+    let db = &session.db;
+    
+    // Si une requête est spécifiée, afficher uniquement les entrées correspondantes
+    if let Some(query) = query {
+        let context = QueryContext::with_analyzer(strength::analyzer()?).including_trash(session::include_trash());
+        let matches: Vec<_> = db.find_entries_with(&context, &query).into_iter()
+            .filter(|found| all || !found.entry.archived)
+            .collect();
+        
+        if output::is_json() {
+            let entries: Vec<_> = matches.iter().map(|found| EntrySummary::new(&found.path, found.entry)).collect();
+            output::print_json(&serde_json::json!({ "entries": entries }))?;
+            return session.close();
+        }
+        
+        println!("\n{} entrée(s) correspondante(s):", matches.len());
+        for found in matches {
+            println!("  - {}{}{} (ID: {})", found.entry.title, archived_marker(found.entry), color_marker(found.entry), found.entry.id);
+            println!("    Groupe: {}", found.path.join("/"));
+            println!("    Utilisateur: {}", found.entry.username);
+        }
+        
+        return session.close();
+    }
     
     // Si un ID de groupe est spécifié, afficher uniquement les entrées de ce groupe
     if let Some(gid) = group_id {
@@ -259,65 +543,490 @@ fn cmd_list(path: &Path, group_id: Option<&str>) -> Result<(), String> {
             None => return Err(format!("Groupe avec ID '{}' non trouvé.", gid)),
         };
         
+        let entries: Vec<&Entry> = group.entries.values().filter(|entry| all || !entry.archived).collect();
+        
+        if output::is_json() {
+            let path = db.group_path(gid).unwrap_or_default();
+            let entries: Vec<_> = entries.into_iter().map(|entry| EntrySummary::new(&path, entry)).collect();
+            output::print_json(&serde_json::json!({ "entries": entries }))?;
+            return session.close();
+        }
+        
         println!("\nEntrées du groupe '{}':", group.name);
-        if group.entries.is_empty() {
+        if entries.is_empty() {
             println!("  (Aucune entrée)");
         } else {
-            for (_, entry) in &group.entries {
-                println!("  - {} (ID: {})", entry.title, entry.id);
+            for entry in entries {
+                println!("  - {}{}{} (ID: {})", entry.title, archived_marker(entry), color_marker(entry), entry.id);
                 println!("    Utilisateur: {}", entry.username);
             }
         }
         
-        return Ok(());
+        return session.close();
     }
     
     // Sinon, afficher toutes les entrées organisées par groupe
+    if output::is_json() {
+        let mut entries = Vec::new();
+        let mut hidden = 0;
+        db.walk(|path, _, entry| {
+            match entry {
+                Some(entry) if entry.archived && !all => hidden += 1,
+                Some(entry) => entries.push(EntrySummary::new(path, entry)),
+                None => {}
+            }
+            WalkControl::Continue
+        });
+        output::print_json(&serde_json::json!({ "entries": entries, "hidden_archived": hidden }))?;
+        return session.close();
+    }
+    
     println!("\nToutes les entrées:");
     
-    // Entrées du groupe racine
-    if !db.root_group.entries.is_empty() {
-        println!("\nGroupe: {}", db.root_group.name);
-        for (_, entry) in &db.root_group.entries {
-            println!("  - {} (ID: {})", entry.title, entry.id);
-            println!("    Utilisateur: {}", entry.username);
+    // Parcourir tous les groupes de l'arborescence, quelle que soit leur profondeur
+    let mut hidden = 0;
+    db.walk(|path, group, entry| {
+        match entry {
+            None if group.entries.values().any(|entry| all || !entry.archived) => println!("\nGroupe: {}", path.join("/")),
+            None => {},
+            Some(entry) if entry.archived && !all => hidden += 1,
+            Some(entry) => {
+                println!("  - {}{}{} (ID: {})", entry.title, archived_marker(entry), color_marker(entry), entry.id);
+                println!("    Utilisateur: {}", entry.username);
+            }
         }
+        WalkControl::Continue
+    });
+    
+    if hidden > 0 {
+        println!("\n{} entrée(s) archivée(s) masquée(s) (--all pour les afficher).", hidden);
+    }
+    
+    session.close()
+}
+
+/// Recherche une sous-chaîne dans les champs texte de toutes les entrées
+fn cmd_search(path: &Path, text: &str, ignore_case: bool, all: bool, long: bool) -> Result<(), String> {
+    if text.is_empty() {
+        return Err("Le texte recherché ne peut pas être vide.".to_string());
     }
     
-    // Entrées des sous-groupes
-    for (_, group) in &db.root_group.subgroups {
-        if !group.entries.is_empty() {
-            println!("\nGroupe: {}", group.name);
-            for (_, entry) in &group.entries {
-                println!("  - {} (ID: {})", entry.title, entry.id);
+    // L'index chiffré, s'il est à jour, évite de déchiffrer toute la base
+    let (matches, session): (Vec<IndexedEntry>, _) = match Session::open_for_search(path)? {
+        SearchSource::Index(index) => (index.search(text, ignore_case).into_iter().cloned().collect(), None),
+        SearchSource::Session(session) => {
+            let found = session.db.search(text, ignore_case, session::include_trash()).into_iter()
+                .map(|found| IndexedEntry::new(&found.path, found.entry, found.trashed))
+                .collect();
+            (found, Some(session))
+        }
+    };
+    let matches: Vec<_> = matches.into_iter()
+        .filter(|entry| (all || !entry.archived) && (session::include_trash() || !entry.trashed))
+        .collect();
+    
+    if output::is_json() {
+        let entries: Vec<_> = matches.iter().map(EntrySummary::indexed).collect();
+        output::print_json(&serde_json::json!({ "entries": entries }))?;
+    } else {
+        println!("\n{} entrée(s) contenant '{}':", matches.len(), text);
+        for entry in &matches {
+            let id = if long { entry.id.to_string() } else { entry.id.short() };
+            println!("  [{}] {}/{}{}{}", id, entry.path.join("/"), entry.title, archived_tag(entry.archived), color_tag(entry.color));
+            if !entry.username.is_empty() {
                 println!("    Utilisateur: {}", entry.username);
             }
         }
     }
     
+    session.map_or(Ok(()), |session| session.close())
+}
+
+/// Mention ajoutée au titre des entrées archivées
+fn archived_marker(entry: &Entry) -> &'static str {
+    archived_tag(entry.archived)
+}
+
+/// Mention d'archivage, à partir du seul indicateur (entrée lue dans l'index de recherche)
+fn archived_tag(archived: bool) -> &'static str {
+    if archived { " [archivée]" } else { "" }
+}
+
+/// Couleur de l'entrée ajoutée à son titre (colorée si la sortie est un terminal)
+fn color_marker(entry: &Entry) -> String {
+    color_tag(entry.color)
+}
+
+/// Couleur ajoutée à un titre, à partir de la seule couleur (entrée lue dans l'index de recherche)
+fn color_tag(color: Option<ColorLabel>) -> String {
+    match color {
+        Some(color) if std::io::stdout().is_terminal() && !symbols::is_plain() => format!(" \x1b[{}m[{}]\x1b[0m", color.ansi_code(), color),
+        Some(color) => format!(" [{}]", color),
+        None => String::new(),
+    }
+}
+
+/// Place une entrée dans la corbeille, ou la supprime définitivement (`permanent`, ou entrée
+/// déjà dans la corbeille) après confirmation
+fn cmd_delete(path: &Path, id: &EntryRef, permanent: bool, unlock_entry: bool) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    let id = &session.resolve(id)?;
+    
+    let entry = session.db.find_entry(id).ok_or_else(|| format!("Entrée avec ID '{}' non trouvée.", id))?;
+    check_unlocked(entry, unlock_entry)?;
+    let title = entry.title.clone();
+    
+    let trash = |db: &mut Database| if unlock_entry { db.trash_entry_unlocked(id) } else { db.trash_entry(id) };
+    if !permanent && trash(&mut session.db).map_err(|e| e.to_string())? {
+        session.save()?;
+        println!("Entrée '{}' placée dans la corbeille ('keeprust restore {}' pour l'annuler).", title, id);
+        return session.close();
+    }
+    
+    if !session::confirm(&format!("Supprimer définitivement l'entrée '{}'? Cette action est irréversible.", title), false)? {
+        println!("Opération annulée.");
+        return session.close();
+    }
+    
+    session.db.remove_entry(id).map_err(|e| e.to_string())?;
+    session.save()?;
+    println!("Entrée '{}' supprimée définitivement.", title);
+    session.close()
+}
+
+/// Restaure une entrée de la corbeille
+fn cmd_restore(path: &Path, id: &EntryRef) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    let id = &session.resolve_trashed(id)?;
+    
+    let origin = session.db.find_entry(id).and_then(|entry| entry.deleted_from);
+    let group_id = session.db.restore_entry(id).map_err(|e| e.to_string())?;
+    let group_path = session.db.group_path(&group_id).unwrap_or_default().join("/");
+    let title = session.db.find_entry(id).map(|entry| entry.title.clone()).unwrap_or_default();
+    
+    session.save()?;
+    if origin.is_some_and(|origin| origin != group_id) {
+        println!("Entrée '{}' restaurée dans le groupe '{}' (son groupe d'origine n'existe plus).", title, group_path);
+    } else {
+        println!("Entrée '{}' restaurée dans le groupe '{}'.", title, group_path);
+    }
+    session.close()
+}
+
+/// Vide la corbeille après confirmation
+fn cmd_empty_trash(path: &Path) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    
+    let count = session.db.trash_group()
+        .map(|trash| trash.iter_groups().map(|group| group.entries.len()).sum::<usize>())
+        .unwrap_or(0);
+    if count == 0 {
+        println!("La corbeille est vide.");
+        return session.close();
+    }
+    
+    if !session::confirm(&format!("Supprimer définitivement les {} entrée(s) de la corbeille?", count), false)? {
+        println!("Opération annulée.");
+        return session.close();
+    }
+    
+    let removed = session.db.empty_trash();
+    session.save()?;
+    println!("{} entrée(s) supprimée(s) définitivement.", removed);
+    session.close()
+}
+
+/// Attribue ou retire la couleur d'une entrée
+fn cmd_set_color(path: &Path, id: &EntryRef, color: Option<ColorLabel>) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    let id = &session.resolve(id)?;
+    
+    let previous = session.db.set_color(id, color).map_err(|e| e.to_string())?;
+    let title = session.db.find_entry(id).map(|entry| entry.title.clone()).unwrap_or_default();
+    
+    if previous == color {
+        println!("L'entrée '{}' n'a pas changé de couleur.", title);
+        return session.close();
+    }
+    
+    session.save()?;
+    match color {
+        Some(color) => println!("Entrée '{}' étiquetée en {}.", title, color),
+        None => println!("Couleur retirée de l'entrée '{}'.", title),
+    }
+    session.close()
+}
+
+/// Vérifie et normalise un nom de domaine passé en argument
+fn parse_domain(domain: &str) -> Result<String, String> {
+    let domain = domain.trim().trim_end_matches('.').to_lowercase();
+    let valid = !domain.is_empty()
+        && !domain.contains(['/', ':', '@', '?', '#'])
+        && Url::parse(&format!("https://{}", domain)).is_ok_and(|url| url.host_str() == Some(domain.as_str()));
+    
+    if !valid {
+        return Err(format!("Domaine invalide: '{}' (exemple attendu: exemple.com)", domain));
+    }
+    Ok(domain)
+}
+
+/// Remplace un domaine dans les URL des entrées, après aperçu et confirmation
+fn cmd_rewrite_urls(path: &Path, from: &str, to: &str, dry_run: bool) -> Result<(), String> {
+    // Valider les domaines avant de demander le mot de passe
+    let from = parse_domain(from)?;
+    let to = parse_domain(to)?;
+    if from == to {
+        return Err("Les domaines source et destination sont identiques.".to_string());
+    }
+    
+    let mut session = Session::open(path)?;
+    
+    let mut changes = Vec::new();
+    session.db.walk(|_, _, entry| {
+        if let Some(entry) = entry
+            && let Some(url) = rewrite_url_domain(&entry.url, &from, &to)
+        {
+            changes.push((entry.id, entry.title.clone(), entry.url.clone(), url));
+        }
+        WalkControl::Continue
+    });
+    skip_locked(&session.db, &mut changes, |(id, ..)| *id);
+    
+    if changes.is_empty() {
+        println!("Aucune URL ne correspond au domaine '{}'.", from);
+        return session.close();
+    }
+    
+    println!("
+{} URL(s) à modifier:", changes.len());
+    for (id, title, old_url, new_url) in &changes {
+        println!("  - {} (ID: {})", title, id);
+        println!("    {} -> {}", old_url, new_url);
+    }
+    
+    if dry_run {
+        println!("
+Simulation: aucune modification enregistrée.");
+        return session.close();
+    }
+    
+    if !session::confirm("Appliquer ces modifications?", false)? {
+        println!("Modifications abandonnées.");
+        return session.close();
+    }
+    
+    for (id, _, _, url) in changes {
+        session.db.update_entry(&id, |entry| entry.url = url).map_err(|e| e.to_string())?;
+    }
+    
+    session.save()?;
+    println!("URL mises à jour.");
+    session.close()
+}
+
+/// Affiche les lignes modifiées d'un champ ("- ancienne", "+ nouvelle")
+fn print_line_diff(before: &str, after: &str) {
+    let before: Vec<_> = before.lines().collect();
+    let after: Vec<_> = after.lines().collect();
+    for index in 0..before.len().max(after.len()) {
+        let (old, new) = (before.get(index), after.get(index));
+        if old == new {
+            continue;
+        }
+        if let Some(old) = old {
+            println!("    - {}", old);
+        }
+        if let Some(new) = new {
+            println!("    + {}", new);
+        }
+    }
+}
+
+/// Remplace un texte dans un champ de toutes les entrées, après aperçu et confirmation
+fn cmd_replace(path: &Path, field: TextField, find: &str, replace: &str, regex: bool, dry_run: bool) -> Result<(), String> {
+    // Valider le motif avant de demander le mot de passe
+    let replacement = if regex { Replacement::regex(find, replace) } else { Replacement::literal(find, replace) }
+        .map_err(|e| e.to_string())?;
+    
+    let mut session = Session::open(path)?;
+    
+    let mut changes = session.db.plan_replacements(field, &replacement).map_err(|e| e.to_string())?;
+    skip_locked(&session.db, &mut changes, |change| change.id);
+    if changes.is_empty() {
+        println!("Aucune entrée ne contient '{}' dans le champ {}.", find, field);
+        return session.close();
+    }
+    
+    println!("\n{} entrée(s) à modifier (champ {}):", changes.len(), field);
+    for change in &changes {
+        println!("  - {} (ID: {})", change.title, change.id);
+        print_line_diff(&change.before, &change.after);
+    }
+    
+    if dry_run {
+        println!("\nSimulation: aucune modification enregistrée.");
+        return session.close();
+    }
+    
+    if !session::confirm("Appliquer ces modifications?", false)? {
+        println!("Modifications abandonnées.");
+        return session.close();
+    }
+    
+    for change in changes {
+        session.db.update_entry(&change.id, |entry| field.set(entry, change.after)).map_err(|e| e.to_string())?;
+    }
+    
+    session.save()?;
+    println!("Entrées mises à jour.");
+    session.close()
+}
+
+/// Affiche les comptes associés à chaque adresse email ou nom d'utilisateur
+fn cmd_report_emails(path: &Path, address: Option<&str>, all: bool) -> Result<(), String> {
+    let session = Session::open(path)?;
+    
+    let mut usages = session.db.accounts_by_identity(all);
+    if let Some(address) = address {
+        usages.retain(|usage| usage.identity.eq_ignore_ascii_case(address.trim()));
+        if usages.is_empty() {
+            println!("Aucune entrée n'utilise l'identifiant '{}'.", address);
+            return session.close();
+        }
+    }
+    
+    let emails = usages.iter().filter(|usage| usage.is_email()).count();
+    println!("
+{} identifiant(s), dont {} adresse(s) email:", usages.len(), emails);
+
+    for usage in &usages {
+        let kind = if usage.is_email() { "Adresse" } else { "Identifiant" };
+        println!("
+{}: {} ({} compte(s))", kind, usage.identity, usage.entries.len());
+        for found in &usage.entries {
+            println!("  - {}{} (ID: {})", found.entry.title, archived_marker(found.entry), found.entry.id);
+            println!("    Groupe: {}", found.path.join("/"));
+            if !found.entry.url.is_empty() {
+                println!("    URL: {}", found.entry.url);
+            }
+        }
+    }
+    
+    session.close()
+}
+
+/// Refuse de toucher à une entrée verrouillée sans --unlock-entry
+fn check_unlocked(entry: &Entry, unlock_entry: bool) -> Result<(), String> {
+    if !entry.locked {
+        return Ok(());
+    }
+    if !unlock_entry {
+        return Err(DatabaseError::EntryLocked(entry.title.clone()).to_string());
+    }
+    eprintln!("{}L'entrée '{}' est verrouillée : --unlock-entry fourni, elle reste verrouillée après l'opération.", Symbol::Warning, entry.title);
     Ok(())
 }
 
-/// Ajoute une nouvelle entrée
-fn cmd_add(path: &Path, title: String, username: String, password: Option<String>, 
-           url: Option<String>, notes: Option<String>, group_id: Option<String>,
-           generate: bool, length: usize) -> Result<(), String> {
-    // Vérifier si le fichier existe
-    if !path.exists() {
-        return Err(format!("Le fichier {} n'existe pas.", path.display()));
+/// Retire les entrées verrouillées d'une modification groupée en les signalant : elles ne sont
+/// modifiées qu'une par une, avec edit --unlock-entry
+pub(super) fn skip_locked<T>(db: &Database, items: &mut Vec<T>, id: impl Fn(&T) -> EntryId) {
+    items.retain(|item| match db.find_entry(&id(item)) {
+        Some(entry) if entry.locked => {
+            eprintln!("{}Entrée verrouillée ignorée: {} (ID: {}).", Symbol::Warning, entry.title, entry.id);
+            false
+        }
+        _ => true,
+    });
+}
+
+/// Verrouille ou déverrouille une entrée
+fn cmd_set_locked(path: &Path, id: &EntryRef, locked: bool) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    let id = &session.resolve(id)?;
+    
+    let previous = session.db.set_locked(id, locked).map_err(|e| e.to_string())?;
+    let title = session.db.find_entry(id).map(|entry| entry.title.clone()).unwrap_or_default();
+    
+    if previous == locked {
+        println!("L'entrée '{}' est déjà {}.", title, if locked { "verrouillée" } else { "déverrouillée" });
+        return session.close();
+    }
+    
+    session.save()?;
+    println!("Entrée '{}' {}.", title, if locked { "verrouillée" } else { "déverrouillée" });
+    session.close()
+}
+
+/// Archive ou restaure une entrée
+fn cmd_set_archived(path: &Path, id: &EntryRef, archived: bool) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    let id = &session.resolve(id)?;
+    
+    let previous = session.db.set_archived(id, archived).map_err(|e| e.to_string())?;
+    let title = session.db.find_entry(id).map(|entry| entry.title.clone()).unwrap_or_default();
+    
+    if previous == archived {
+        println!("L'entrée '{}' est déjà {}.", title, if archived { "archivée" } else { "active" });
+        return session.close();
     }
     
-    // Demander le mot de passe de la base de données
-    print!("Mot de passe de la base de données: ");
-    io::stdout().flush().map_err(|e| e.to_string())?;
-    let db_password = read_password().map_err(|e| e.to_string())?;
+    session.save()?;
+    println!("Entrée '{}' {}.", title, if archived { "archivée" } else { "restaurée" });
+    session.close()
+}
+
+/// Champs d'une nouvelle entrée, complétés depuis --from-url ou --from-otpauth
+struct Prefill {
+    title: String,
+    username: String,
+    url: Option<String>,
+    otp: Option<OtpConfig>,
+}
+
+impl Prefill {
+    /// Complète les champs non fournis : émetteur et compte de l'URI otpauth, nom du site de l'URL,
+    /// puis nom d'utilisateur proposé par defaults.toml (à confirmer dans un terminal)
+    fn resolve(title: Option<String>, username: Option<String>, url: Option<String>, otpauth: Option<String>) -> Result<Self, String> {
+        let otp = otpauth.as_deref().map(OtpConfig::from_uri).transpose().map_err(|e| e.to_string())?;
+        let issuer = otp.as_ref().and_then(|otp| otp.issuer.clone());
+        let account = otp.as_ref().and_then(|otp| otp.account.clone());
+        
+        let title = title
+            .or(issuer)
+            .or_else(|| url.as_deref().and_then(site_name))
+            .or_else(|| account.clone())
+            .ok_or("Impossible de déduire le titre de l'entrée; précisez --title.")?;
+        
+        let username = match username.or(account) {
+            Some(username) => username,
+            None => {
+                let defaults = EntryDefaults::load()?;
+                let host = url.as_deref().and_then(url_host);
+                let suggestion = defaults.suggest_username(host.as_deref()).unwrap_or_default().to_string();
+                if session::is_interactive() {
+                    prompt_field("Nom d'utilisateur", &suggestion)?.unwrap_or(suggestion)
+                } else {
+                    suggestion
+                }
+            }
+        };
+        
+        Ok(Self { title, username, url, otp })
+    }
+}
+
+/// Ajoute une nouvelle entrée
+#[allow(clippy::too_many_arguments)]
+fn cmd_add(path: &Path, prefill: Prefill, password: Option<String>, notes: Option<String>,
+           group_id: Option<GroupId>, generate: bool, length: usize, expires: Option<String>,
+           tags: Vec<String>, color: Option<ColorLabel>, fields: FieldArgs) -> Result<(), String> {
+    // Valider la date d'expiration et les champs avant de demander le mot de passe
+    let expires_at = expires.as_deref().map(|date| parse_date("--expires", date, Direction::Future)).transpose()?;
+    let fields = FieldSpec::parse_all(fields)?;
     
     // Ouvrir la base de données
-    let repo = Repository::new(path);
-    let mut db = match repo.load(&db_password) {
-        Ok(db) => db,
-        Err(e) => return Err(format!("Erreur lors de l'ouverture de la base de données: {}", e)),
-    };
+    let mut session = Session::open(path)?;
+    let db = &mut session.db;
     
     // Déterminer le mot de passe de l'entrée
     let entry_password = if generate {
@@ -332,165 +1041,408 @@ fn cmd_add(path: &Path, title: String, username: String, password: Option<String
         pwd
     } else {
         // Demander un mot de passe
-        print!("Mot de passe pour l'entrée: ");
-        io::stdout().flush().map_err(|e| e.to_string())?;
-        read_password().map_err(|e| e.to_string())?
+        session::prompt_password("Mot de passe pour l'entrée: ")?
     };
     
     // Créer l'entrée
-    let entry = Entry::new(
-        title,
-        username,
-        entry_password,
-        url.unwrap_or_default(),
-        notes.unwrap_or_default()
-    );
-    
-    // Déterminer le groupe cible
-    let target_group = if let Some(gid) = group_id {
-        match db.find_group_mut(&gid) {
-            Some(g) => g,
-            None => return Err(format!("Groupe avec ID '{}' non trouvé.", gid)),
-        }
-    } else {
-        &mut db.root_group
+    let mut builder = Entry::builder()
+        .title(prefill.title)
+        .username(prefill.username)
+        .password(entry_password)
+        .url(prefill.url.unwrap_or_default())
+        .notes(notes.unwrap_or_default())
+        .tags(tags)
+        .expires_at(expires_at)
+        .color(color);
+    for field in fields {
+        builder = builder.custom_field(field.resolve()?);
+    }
+    let mut entry = builder.build().map_err(|e| e.to_string())?;
+    entry.otp = prefill.otp;
+    entry.check_written_sizes(None).map_err(policy::oversized_error)?;
+    
+    // Ajouter l'entrée au groupe cible : sans --group, choisi dans le terminal (le groupe racine
+    // sans terminal)
+    let group_id = match group_id {
+        Some(id) => Some(id),
+        None if session::is_interactive() => Some(group_picker::pick_group(db)?),
+        None => None,
     };
-    
-    // Ajouter l'entrée au groupe
-    let entry_id = entry.id.clone();
-    target_group.add_entry(entry);
-    
-    // Marquer la base de données comme modifiée
-    db.mark_as_modified();
+    let entry_id = db.add_entry(group_id.as_ref(), entry).map_err(|e| e.to_string())?;
+    policy::warn_oversized(db, &[entry_id]);
     
     // Sauvegarder la base de données
-    repo.save(&db, &db_password).map_err(|e| e.to_string())?;
+    session.save()?;
     
     println!("Entrée ajoutée avec succès (ID: {}).", entry_id);
-    Ok(())
+    session.close()
 }
 
-/// Ajoute un nouveau groupe
-fn cmd_add_group(path: &Path, name: String, parent_id: Option<String>) -> Result<(), String> {
-    // Vérifier si le fichier existe
-    if !path.exists() {
-        return Err(format!("Le fichier {} n'existe pas.", path.display()));
+/// Ajout rapide : l'entrée est placée dans le groupe de defaults.toml, avec son nom d'utilisateur
+/// par défaut et, avec `generate`, un mot de passe produit selon ses réglages du générateur
+fn cmd_quick_add(path: &Path, title: String, username: Option<String>, generate: bool, copy: bool) -> Result<(), String> {
+    let defaults = EntryDefaults::load()?;
+    let username = username.or_else(|| defaults.username.clone()).unwrap_or_default();
+    let generated = generate.then(|| defaults.generator.generator().generate_complex())
+        .transpose()
+        .map_err(|e| e.to_string())?
+        .map(Zeroizing::new);
+    
+    let mut session = Session::open(path)?;
+    let group_id = match &defaults.group {
+        Some(group) => Some(session.db.find_group_by_path(group)
+            .ok_or_else(|| format!("Groupe '{}' de defaults.toml introuvable.", group))?.id),
+        None => None,
+    };
+    let password = match &generated {
+        Some(password) => password.to_string(),
+        None => session::prompt_password("Mot de passe pour l'entrée: ")?,
+    };
+    
+    let entry = Entry::builder()
+        .title(title)
+        .username(username)
+        .password(password)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let title = entry.title.clone();
+    let entry_id = session.db.add_entry(group_id.as_ref(), entry).map_err(|e| e.to_string())?;
+    session.save()?;
+    
+    println!("Entrée '{}' ajoutée (ID: {}).", title, entry_id);
+    match generated {
+        Some(password) if copy => {
+            let restore = copy_password_securely(&password, QUICK_CLEAR_SECONDS).map_err(|e| e.to_string())?;
+            println!("Mot de passe généré copié dans le presse-papiers (retiré dans {} secondes).", QUICK_CLEAR_SECONDS);
+            session.close()?;
+            restore.wait();
+            Ok(())
+        }
+        Some(password) => {
+            println!("Mot de passe généré: {}", password.as_str());
+            session.close()
+        }
+        None => session.close(),
     }
+}
+
+/// Demande la nouvelle valeur d'un champ; une réponse vide conserve la valeur actuelle
+fn prompt_field(label: &str, current: &str) -> Result<Option<String>, String> {
+    let value = session::prompt_line(&format!("{} [{}]: ", label, current))?;
+    Ok(if value.is_empty() { None } else { Some(value) })
+}
+
+/// Champ supplémentaire demandé en ligne de commande (--field NOM=VALEUR, --protected-field NOM[=VALEUR])
+struct FieldSpec {
+    name: String,
     
-    // Demander le mot de passe
-    print!("Mot de passe: ");
-    io::stdout().flush().map_err(|e| e.to_string())?;
-    let password = read_password().map_err(|e| e.to_string())?;
+    /// Absente pour un champ protégé dont la valeur sera saisie sans être affichée
+    value: Option<String>,
+    protected: bool,
+}
+
+impl FieldSpec {
+    /// Analyse les options de champs, avant l'ouverture de la base
+    fn parse_all(args: FieldArgs) -> Result<Vec<Self>, String> {
+        let mut specs = Vec::new();
+        for field in args.fields {
+            let (name, value) = field.split_once('=')
+                .ok_or_else(|| format!("Champ invalide '{}' (format attendu: NOM=VALEUR).", field))?;
+            specs.push(Self::new(name, Some(value), false)?);
+        }
+        for field in args.protected_fields {
+            let (name, value) = match field.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (field.as_str(), None),
+            };
+            specs.push(Self::new(name, value, true)?);
+        }
+        Ok(specs)
+    }
     
-    // Ouvrir la base de données
-    let repo = Repository::new(path);
-    let mut db = match repo.load(&password) {
-        Ok(db) => db,
-        Err(e) => return Err(format!("Erreur lors de l'ouverture de la base de données: {}", e)),
-    };
+    fn new(name: &str, value: Option<&str>, protected: bool) -> Result<Self, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(EntryError::EmptyFieldName.to_string());
+        }
+        Ok(Self { name: name.to_string(), value: value.map(str::to_string), protected })
+    }
     
-    // Déterminer le groupe parent
-    let parent_id = match parent_id {
-        Some(id) => {
-            // Vérifier que le groupe parent existe
-            if db.find_group(&id).is_none() {
-                return Err(format!("Groupe parent avec ID '{}' non trouvé.", id));
+    /// Champ complet; la valeur manquante d'un champ protégé est demandée sans être affichée
+    fn resolve(self) -> Result<CustomField, String> {
+        let value = match self.value {
+            Some(value) => value,
+            None => session::prompt_password(&format!("Valeur du champ '{}': ", self.name))?,
+        };
+        Ok(CustomField::new(self.name, value, self.protected))
+    }
+}
+
+/// Vérifie le titre et l'URL demandés pour une modification
+fn validate_edit(title: Option<&str>, url: Option<&str>) -> Result<(), String> {
+    if title.is_some_and(|title| title.trim().is_empty()) {
+        return Err("Le titre ne peut pas être vide.".to_string());
+    }
+    if let Some(url) = url
+        && !url.is_empty()
+        && !is_valid_url(url)
+    {
+        return Err(format!("URL invalide: {}", url));
+    }
+    Ok(())
+}
+
+/// Modifie une entrée existante; sans option, chaque champ est demandé interactivement
+#[allow(clippy::too_many_arguments)]
+fn cmd_edit(path: &Path, id: &EntryRef, mut title: Option<String>, mut username: Option<String>,
+            mut password: Option<String>, mut url: Option<String>, mut notes: Option<String>,
+            generate: bool, length: usize, fields: FieldArgs, remove_fields: Vec<String>, unlock_entry: bool) -> Result<(), String> {
+    let fields = FieldSpec::parse_all(fields)?;
+    let interactive = !generate && fields.is_empty() && remove_fields.is_empty()
+        && [&title, &username, &password, &url, &notes].iter().all(|field| field.is_none());
+    
+    // Valider les options avant de demander le mot de passe
+    validate_edit(title.as_deref(), url.as_deref())?;
+    
+    // Ouvrir la base de données
+    let mut session = Session::open(path)?;
+    let id = &session.resolve(id)?;
+    let entry = session.entry(id)?;
+    check_unlocked(entry, unlock_entry)?;
+    
+    if interactive {
+        println!("Modification de '{}' (Entrée pour conserver la valeur actuelle)", entry.title);
+        title = prompt_field("Titre", &entry.title)?;
+        username = prompt_field("Utilisateur", &entry.username)?;
+        url = prompt_field("URL", &entry.url)?;
+        notes = prompt_field("Notes", &entry.notes)?;
+        
+        let new_password = session::prompt_password("Nouveau mot de passe (vide pour conserver): ")?;
+        if !new_password.is_empty() {
+            if !session::password_from_stdin() && session::prompt_password("Confirmez le mot de passe: ")? != new_password {
+                return Err("Les mots de passe ne correspondent pas.".to_string());
             }
-            Some(id)
-        },
-        None => Some(db.root_group.id.clone()),
-    };
+            password = Some(new_password);
+        }
+    } else if generate {
+        let pwd = PasswordGenerator::new().length(length).generate_complex()
+            .map_err(|e| e.to_string())?;
+        println!("Mot de passe généré: {}", pwd);
+        password = Some(pwd);
+    }
     
-    // Créer le nouveau groupe
-    let new_group = Group::new(name.clone(), parent_id.clone());
-    let group_id = new_group.id.clone();
+    validate_edit(title.as_deref(), url.as_deref())?;
     
-    // Ajouter le groupe au parent
-    if let Some(pid) = parent_id {
-        if let Some(parent) = db.find_group_mut(&pid) {
-            parent.add_subgroup(new_group);
-        } else {
-            return Err("Erreur interne: groupe parent non trouvé.".to_string());
+    // Ignorer les champs inchangés
+    let title = title.map(|title| title.trim().to_string()).filter(|title| *title != entry.title);
+    let username = username.filter(|username| *username != entry.username);
+    let password = password.filter(|password| *password != entry.password);
+    let url = url.filter(|url| *url != entry.url);
+    let notes = notes.filter(|notes| *notes != entry.notes);
+    if let Some(name) = remove_fields.iter().find(|name| entry.custom_field(name).is_none()) {
+        return Err(format!("L'entrée n'a pas de champ '{}'.", name.trim()));
+    }
+    let mut changed_fields = Vec::new();
+    for field in fields {
+        let field = field.resolve()?;
+        if entry.custom_field(&field.name).is_none_or(|existing| existing.value != field.value || existing.protected != field.protected) {
+            changed_fields.push(field);
         }
-    } else {
-        // Cas improbable, mais gérons-le quand même
-        db.root_group.add_subgroup(new_group);
     }
     
-    // Marquer la base de données comme modifiée
-    db.mark_as_modified();
+    if [&title, &username, &password, &url, &notes].iter().all(|field| field.is_none()) && changed_fields.is_empty() && remove_fields.is_empty() {
+        println!("Aucune modification.");
+        return session.close();
+    }
+    
+    if let Some(password) = &password
+        && entry.has_used_password(password)
+    {
+        println!("{}Ce mot de passe a déjà été utilisé pour cette entrée.", Symbol::Warning);
+    }
+    
+    let update = |entry: &mut Entry| -> Result<(), String> {
+        let before = entry.clone();
+        entry.update(title, username, password, url, notes);
+        for name in &remove_fields {
+            entry.remove_custom_field(name);
+        }
+        changed_fields.into_iter().try_for_each(|field| entry.set_custom_field(field)).map_err(|e| e.to_string())?;
+        entry.check_written_sizes(Some(&before)).map_err(policy::oversized_error)
+    };
+    if unlock_entry { session.db.update_entry_unlocked(id, update) } else { session.db.update_entry(id, update) }
+        .map_err(|e| e.to_string())??;
+    policy::warn_oversized(&session.db, &[*id]);
     
     // Sauvegarder la base de données
-    repo.save(&db, &password).map_err(|e| e.to_string())?;
+    session.save()?;
+    
+    println!("Entrée modifiée avec succès (ID: {}).", id);
+    session.close()
+}
+
+/// Ajoute un nouveau groupe
+fn cmd_add_group(path: &Path, name: String, parent_id: Option<GroupId>) -> Result<(), String> {
+    // Ouvrir la base de données
+    let mut session = Session::open(path)?;
+    let db = &mut session.db;
+    
+    // Créer le groupe sous le parent (le groupe racine si non spécifié)
+    let group_id = db.add_group(parent_id.as_ref(), name.clone()).map_err(|e| e.to_string())?;
+    
+    // Sauvegarder la base de données
+    session.save()?;
     
     println!("Groupe '{}' ajouté avec succès (ID: {}).", name, group_id);
-    Ok(())
+    session.close()
 }
 
-/// Copie le mot de passe d'une entrée dans le presse-papiers
-fn cmd_copy_password(path: &Path, id: &str, timeout: u64) -> Result<(), String> {
-    // Vérifier si le fichier existe
-    if !path.exists() {
-        return Err(format!("Le fichier {} n'existe pas.", path.display()));
+/// Renomme un groupe
+fn cmd_rename_group(path: &Path, id: &GroupId, name: String) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    
+    let previous = session.db.rename_group(id, name.clone()).map_err(|e| e.to_string())?;
+    session.save()?;
+    
+    println!("Groupe '{}' renommé en '{}'.", previous, name);
+    session.close()
+}
+
+/// Déplace un groupe sous un autre groupe (le groupe racine si non spécifié)
+fn cmd_move_group(path: &Path, id: &GroupId, parent_id: Option<&GroupId>) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    let db = &mut session.db;
+    
+    db.move_group(id, parent_id).map_err(|e| e.to_string())?;
+    let group_path = db.group_path(id).unwrap_or_default().join("/");
+    session.save()?;
+    
+    println!("Groupe déplacé : {}", group_path);
+    session.close()
+}
+
+/// Supprime un groupe : ses entrées vont dans la corbeille avec ses sous-groupes, ou sont confiées
+/// à son parent avec --keep-contents
+fn cmd_delete_group(path: &Path, id: &GroupId, keep_contents: bool, unlock_entry: bool) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    
+    let group = session.db.find_group(id).ok_or_else(|| format!("Groupe avec ID '{}' non trouvé.", id))?;
+    let name = group.name.clone();
+    let subgroups = group.iter_groups().count() - 1;
+    let entries: usize = group.iter_groups().map(|group| group.entries.len()).sum();
+    let (direct_entries, direct_subgroups) = (group.entries.len(), group.subgroups.len());
+    let locked = group.iter_groups().flat_map(|group| group.entries.values()).filter(|entry| entry.locked).count();
+    
+    if keep_contents {
+        let parent_id = session.db.dissolve_group(id).map_err(|e| e.to_string())?;
+        let parent = session.db.group_path(&parent_id).unwrap_or_default().join("/");
+        session.save()?;
+        println!("Groupe '{}' supprimé; {} entrée(s) et {} sous-groupe(s) déplacé(s) dans '{}'.",
+            name, direct_entries, direct_subgroups, parent);
+        return session.close();
     }
     
-    // Demander le mot de passe
-    print!("Mot de passe: ");
-    io::stdout().flush().map_err(|e| e.to_string())?;
-    let password = read_password().map_err(|e| e.to_string())?;
+    if locked > 0 && !unlock_entry {
+        return Err(format!("Le groupe '{}' contient {} entrée(s) verrouillée(s) : ajoutez --unlock-entry pour les placer dans la corbeille.", name, locked));
+    }
+    if (entries > 0 || subgroups > 0) && !session::confirm(&format!(
+        "Supprimer le groupe '{}' et ses {} sous-groupe(s)? Ses {} entrée(s) seront placées dans la corbeille.",
+        name, subgroups, entries
+    ), false)? {
+        println!("Opération annulée.");
+        return session.close();
+    }
     
+    let trashed = if unlock_entry { session.db.delete_group_unlocked(id) } else { session.db.delete_group(id) }
+        .map_err(|e| e.to_string())?;
+    session.save()?;
+    println!("Groupe '{}' supprimé; {} entrée(s) placée(s) dans la corbeille.", name, trashed);
+    session.close()
+}
+
+/// Copie le mot de passe d'une entrée dans le presse-papiers
+fn cmd_copy_password(path: &Path, id: &EntryRef, timeout: u64) -> Result<(), String> {
     // Ouvrir la base de données
-    let repo = Repository::new(path);
-    let db = match repo.load(&password) {
-        Ok(db) => db,
-        Err(e) => return Err(format!("Erreur lors de l'ouverture de la base de données: {}", e)),
-    };
-    
+    let mut session = Session::open(path)?;
+    let id = &session.resolve(id)?;
     // Rechercher l'entrée
-    let entry = match db.find_entry(id) {
-        Some(entry) => entry,
-        None => return Err(format!("Entrée avec ID '{}' non trouvée.", id)),
-    };
+    let entry = session.entry(id)?;
     
     // Copier le mot de passe dans le presse-papiers
-    copy_password_securely(&entry.password, timeout)
+    let restore = copy_password_securely(&entry.password, timeout)
         .map_err(|e| e.to_string())?;
     
     println!("Mot de passe de '{}' copié dans le presse-papiers.", entry.title);
+    session.record_exposure(&[*id], ExposureKind::Copied, ExposureInterface::Cli)?;
+    session.close()?;
+    restore.wait();
     Ok(())
 }
 
-/// Copie le nom d'utilisateur d'une entrée dans le presse-papiers
-fn cmd_copy_username(path: &Path, id: &str) -> Result<(), String> {
-    // Vérifier si le fichier existe
-    if !path.exists() {
-        return Err(format!("Le fichier {} n'existe pas.", path.display()));
+/// Copie la valeur d'un champ supplémentaire; celle d'un champ protégé compte comme une exposition
+fn cmd_copy_field(path: &Path, id: &EntryRef, name: &str, timeout: u64) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    let id = &session.resolve(id)?;
+    let entry = session.entry(id)?;
+    let field = entry.custom_field(name).ok_or_else(|| format!("L'entrée '{}' n'a pas de champ '{}'.", entry.title, name.trim()))?;
+    
+    let restore = copy_password_securely(&field.value, timeout).map_err(|e| e.to_string())?;
+    println!("Champ '{}' de '{}' copié dans le presse-papiers.", field.name, entry.title);
+    if field.protected {
+        session.record_exposure(&[*id], ExposureKind::Copied, ExposureInterface::Cli)?;
+    }
+    session.close()?;
+    restore.wait();
+    Ok(())
+}
+
+/// Copie le nom d'utilisateur puis le mot de passe d'une entrée, pour remplir un formulaire de
+/// connexion : le mot de passe remplace le nom d'utilisateur quand l'utilisateur appuie sur Entrée
+/// (après l'avoir collé), ou après `delay` secondes
+fn cmd_copy_both(path: &Path, id: &EntryRef, timeout: u64, delay: Option<u64>) -> Result<(), String> {
+    if delay.is_none() && !session::is_interactive() {
+        return Err("--both attend la touche Entrée dans un terminal; utilisez --delay sans terminal.".to_string());
     }
     
-    // Demander le mot de passe
-    print!("Mot de passe: ");
-    io::stdout().flush().map_err(|e| e.to_string())?;
-    let password = read_password().map_err(|e| e.to_string())?;
+    let mut session = Session::open(path)?;
     
-    // Ouvrir la base de données
-    let repo = Repository::new(path);
-    let db = match repo.load(&password) {
-        Ok(db) => db,
-        Err(e) => return Err(format!("Erreur lors de l'ouverture de la base de données: {}", e)),
-    };
+    let id = &session.resolve(id)?;
+    let entry = session.entry(id)?;
     
+    let mut clipboard = SecureClipboard::new().map_err(|e| e.to_string())?;
+    clipboard.copy(&entry.username).map_err(|e| e.to_string())?;
+    match delay {
+        Some(seconds) => {
+            println!("Nom d'utilisateur de '{}' copié; le mot de passe le remplacera dans {} seconde(s).", entry.title, seconds);
+            thread::sleep(Duration::from_secs(seconds));
+        }
+        None => {
+            session::prompt_line(&format!("Nom d'utilisateur de '{}' copié. Collez-le, puis appuyez sur Entrée pour copier le mot de passe.", entry.title))?;
+        }
+    }
+    
+    let restore = clipboard.copy_with_timeout(&entry.password, timeout).map_err(|e| e.to_string())?;
+    println!("Mot de passe de '{}' copié dans le presse-papiers.", entry.title);
+    session.record_exposure(&[*id], ExposureKind::Copied, ExposureInterface::Cli)?;
+    session.close()?;
+    restore.wait();
+    Ok(())
+}
+
+/// Copie le nom d'utilisateur d'une entrée dans le presse-papiers
+fn cmd_copy_username(path: &Path, id: &EntryRef) -> Result<(), String> {
+    // Ouvrir la base de données
+    let session = Session::open(path)?;
+    let id = &session.resolve(id)?;
     // Rechercher l'entrée
-    let entry = match db.find_entry(id) {
-        Some(entry) => entry,
-        None => return Err(format!("Entrée avec ID '{}' non trouvée.", id)),
-    };
+    let entry = session.entry(id)?;
     
     // Copier le nom d'utilisateur dans le presse-papiers
     copy_to_clipboard(&entry.username)
         .map_err(|e| e.to_string())?;
     
     println!("Nom d'utilisateur de '{}' copié dans le presse-papiers.", entry.title);
-    Ok(())
+    session.close()
 }
 
 /// Analyse la force d'un mot de passe
@@ -498,111 +1450,525 @@ fn cmd_analyze_password(password: Option<String>) -> Result<(), String> {
     let pwd = match password {
         Some(p) => p,
         None => {
-            print!("Mot de passe à analyser: ");
-            io::stdout().flush().map_err(|e| e.to_string())?;
-            read_password().map_err(|e| e.to_string())?
+            session::prompt_password("Mot de passe à analyser: ")?
         }
     };
     
-    let analyzer = PasswordAnalyzer::new();
+    let analyzer = strength::analyzer()?;
     let analysis = analyzer.analyze(&pwd);
     
-    println!("\n🔍 Analyse du mot de passe:");
+    println!("\n{}Analyse du mot de passe:", Symbol::Heading("🔍"));
     println!("{}", analysis);
     
     Ok(())
 }
 
-/// Affiche l'historique des mots de passe d'une entrée
-fn cmd_show_history(path: &Path, id: &str) -> Result<(), String> {
-    if !path.exists() {
-        return Err(format!("Le fichier {} n'existe pas.", path.display()));
+/// Change le mot de passe maître : la base est rechiffrée avec une clé dérivée du nouveau mot de passe
+fn cmd_change_password(path: &Path) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    
+    let new_password = session::prompt_password("Nouveau mot de passe maître: ")?;
+    if new_password.is_empty() {
+        return Err("Le mot de passe maître ne peut pas être vide.".to_string());
+    }
+    if !session::password_from_stdin() && session::prompt_password("Confirmez le nouveau mot de passe: ")? != new_password {
+        return Err("Les mots de passe ne correspondent pas.".to_string());
     }
     
-    print!("Mot de passe: ");
-    io::stdout().flush().map_err(|e| e.to_string())?;
-    let password = read_password().map_err(|e| e.to_string())?;
+    let analysis = strength::analyzer()?.analyze(&new_password);
+    if analysis.strength < PasswordStrength::Medium {
+        println!("{}Ce mot de passe est {}.", Symbol::Warning, analysis.strength.to_string().to_lowercase());
+        if !session::confirm("Utiliser quand même ce mot de passe?", false)? {
+            println!("Opération annulée.");
+            return session.close();
+        }
+    }
     
-    let repo = Repository::new(path);
-    let db = match repo.load(&password) {
-        Ok(db) => db,
-        Err(e) => return Err(format!("Erreur lors de l'ouverture de la base de données: {}", e)),
+    session.change_password(session::master_secret(new_password)?)?;
+    println!("Mot de passe maître modifié. Les copies de la base protégées par l'ancien mot de passe restent lisibles avec celui-ci.");
+    session.close()
+}
+
+/// Crée un fichier clé aléatoire, lisible par son seul propriétaire
+fn cmd_keygen_keyfile(file: &Path) -> Result<(), String> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut output = options.open(file).map_err(|e| format!("Impossible de créer {}: {}", file.display(), e))?;
+    output.write_all(keyfile::generate().as_ref()).map_err(|e| e.to_string())?;
+    
+    println!("Fichier clé créé: {}", file.display());
+    println!("Conservez-en une copie de secours hors de l'ordinateur : sans lui, une base créée avec --keyfile ne peut plus être ouverte.");
+    Ok(())
+}
+
+/// Réenregistre la base avec Argon2id (paramètres recommandés ou indiqués) et un nouveau sel
+fn cmd_upgrade_kdf(path: &Path, memory_mib: Option<u32>, passes: Option<u32>, parallelism: Option<u32>) -> Result<(), String> {
+    let target = KdfParams::Argon2id {
+        memory_kib: memory_mib.map_or(key_derivation::DEFAULT_ARGON2_MEMORY_KIB, |memory| memory.saturating_mul(1024)),
+        iterations: passes.unwrap_or(key_derivation::DEFAULT_ARGON2_ITERATIONS),
+        parallelism: parallelism.unwrap_or(key_derivation::DEFAULT_ARGON2_PARALLELISM),
     };
+    target.validate().map_err(|e| e.to_string())?;
+    
+    let mut session = Session::open(path)?;
+    let previous = session.kdf_params();
     
-    let entry = match db.find_entry(id) {
-        Some(entry) => entry,
-        None => return Err(format!("Entrée avec ID '{}' non trouvée.", id)),
+    if previous == target {
+        println!("La base utilise déjà ces paramètres ({}).", previous);
+        return session.close();
+    }
+    
+    // Prévenir avant de réduire le coût d'une attaque par rapport aux paramètres actuels
+    let weaker = match (previous, target) {
+        (KdfParams::Argon2id { memory_kib, iterations, .. }, KdfParams::Argon2id { memory_kib: new_memory, iterations: new_iterations, .. }) => {
+            new_memory < memory_kib || new_iterations < iterations
+        }
+        _ => false,
+    };
+    if weaker || target.is_weak(MIN_ITERATIONS) {
+        println!("{}Les nouveaux paramètres ({}) sont moins robustes que les paramètres actuels ou recommandés.", Symbol::Warning, target);
+        if !session::confirm("Réenregistrer quand même?", false)? {
+            println!("Opération annulée.");
+            return session.close();
+        }
+    }
+    
+    session.set_kdf_params(target)?;
+    println!("Base réenregistrée: {} -> {}.", previous, target);
+    session.close()
+}
+
+/// Mesure la durée d'une dérivation de clé avec les paramètres recommandés de chaque algorithme
+fn cmd_kdf_benchmark(kdf: Option<KdfParams>) -> Result<(), String> {
+    let candidates: Vec<KdfParams> = match kdf {
+        Some(params) => vec![params],
+        None => key_derivation::selectable().map(|kdf| kdf.recommended()).collect(),
     };
     
-    println!("\n📜 Historique des mots de passe pour '{}':", entry.title);
+    for params in candidates {
+        let start = Instant::now();
+        key_derivation::derive_key_from_password("keeprust-benchmark", params).map_err(|e| e.to_string())?;
+        println!("{}: {} ms", params, start.elapsed().as_millis());
+    }
+    
+    println!("\nChoisissez l'algorithme à la création avec 'keeprust new --kdf <algorithme>'.");
+    Ok(())
+}
+
+/// Affiche l'historique des mots de passe d'une entrée
+fn cmd_show_history(path: &Path, id: &EntryRef, diff: bool) -> Result<(), String> {
+    // Ouvrir la base de données
+    let session = Session::open(path)?;
+    let id = &session.resolve(id)?;
+    let entry = session.entry(id)?;
+    
+    if output::is_json() {
+        // Les anciens mots de passe ne sont jamais inclus
+        let history: Vec<_> = entry.password_history.iter()
+            .map(|history| serde_json::json!({ "changed_at": history.changed_at, "changed_on": history.changed_on }))
+            .collect();
+        let mut json = serde_json::json!({
+            "id": entry.id,
+            "title": entry.title,
+            "updated_at": entry.updated_at,
+            "modified_on": entry.modified_on,
+            "history": history,
+        });
+        if diff {
+            // Les valeurs secrètes ne sont jamais enregistrées dans le journal
+            json["revisions"] = serde_json::json!(entry.revisions);
+        }
+        output::print_json(&json)?;
+        return session.close();
+    }
+    
+    if diff {
+        print_revisions(entry);
+        return session.close();
+    }
+    
+    println!("\n{}Historique des mots de passe pour '{}':", Symbol::Heading("📜"), entry.title);
+    println!("Dernière modification: {}{}", entry.updated_at.format("%d-%m-%Y %H:%M:%S"), device_suffix(entry.modified_on.as_ref()));
     
     if entry.password_history.is_empty() {
         println!("  (Aucun historique disponible)");
     } else {
         for (i, history) in entry.password_history.iter().enumerate() {
-            println!("  {}. Modifié le: {}", 
+            println!("  {}. Modifié le: {}{}", 
                 i + 1, 
-                history.changed_at.format("%d-%m-%Y %H:%M:%S")
+                history.changed_at.format("%d-%m-%Y %H:%M:%S"),
+                device_suffix(history.changed_on.as_ref())
             );
         }
     }
     
-    Ok(())
+    session.close()
 }
 
-/// Audit de sécurité de toutes les entrées
-fn cmd_audit_passwords(path: &Path) -> Result<(), String> {
-    if !path.exists() {
-        return Err(format!("Le fichier {} n'existe pas.", path.display()));
+/// Journal des modifications d'une entrée, de la plus ancienne à la plus récente
+fn print_revisions(entry: &Entry) {
+    println!("\n{}Journal des modifications de '{}':", Symbol::Heading("📜"), entry.title);
+    if entry.revisions.is_empty() {
+        println!("  (Aucune modification enregistrée)");
+        return;
     }
     
-    print!("Mot de passe: ");
-    io::stdout().flush().map_err(|e| e.to_string())?;
-    let password = read_password().map_err(|e| e.to_string())?;
+    let value = |value: &Option<String>| value.clone().unwrap_or_else(|| "—".to_string());
+    for revision in &entry.revisions {
+        let password = if revision.changes_password() { " [mot de passe changé]" } else { "" };
+        println!("  {}{}{}",
+            revision.changed_at.format("%d-%m-%Y %H:%M:%S"),
+            device_suffix(revision.changed_on.as_ref()),
+            password
+        );
+        for change in &revision.changes {
+            if change.before.is_none() && change.after.is_none() {
+                println!("    {}: modifié", change.label());
+            } else {
+                println!("    {}: {} → {}", change.label(), value(&change.before), value(&change.after));
+            }
+        }
+    }
+}
+
+/// Audit de sécurité de toutes les entrées
+fn cmd_audit_passwords(path: &Path, all: bool, min_score: Option<u8>, hibp: bool) -> Result<(), String> {
+    let analyzer = strength::analyzer()?;
     
-    let repo = Repository::new(path);
-    let db = match repo.load(&password) {
-        Ok(db) => db,
-        Err(e) => return Err(format!("Erreur lors de l'ouverture de la base de données: {}", e)),
-    };
+    // Ouvrir la base de données
+    let mut session = Session::open(path)?;
+    let db = &mut session.db;
+    
+    // Seuls les mots de passe modifiés depuis l'audit précédent sont réanalysés
+    let scores = db.strength_scores(&analyzer, |entry| all || !entry.archived);
     
-    let analyzer = PasswordAnalyzer::new();
     let mut weak_passwords = Vec::new();
-    let mut total_entries = 0;
+    let mut below_minimum = Vec::new();
+    let entries: Vec<(&Entry, u8)> = db.iter_entries()
+        .filter_map(|entry| scores.scores.get(&entry.id).map(|score| (entry, *score)))
+        .collect();
+    let total_entries = entries.len();
+    
+    for (entry, score) in entries {
+        let strength = analyzer.strength(score);
+        
+        if min_score.is_some_and(|min| score < min) {
+            below_minimum.push((entry, score));
+        }
+        if matches!(strength, PasswordStrength::VeryWeak | PasswordStrength::Weak) {
+            weak_passwords.push((entry, strength));
+        }
+    }
     
-    // Fonction pour auditer un groupe
-    fn audit_group(group: &Group, analyzer: &PasswordAnalyzer, weak_passwords: &mut Vec<(String, String, PasswordStrength)>, total_entries: &mut usize) {
-        for (_, entry) in &group.entries {
-            *total_entries += 1;
-            let analysis = analyzer.analyze(&entry.password);
+    // Le suivi d'un audit à l'autre ne porte que sur les entrées actives, avec ou sans --all
+    let weak_count = weak_passwords.iter().filter(|(entry, _)| !entry.archived).count();
+    let reused = db.reused_passwords(all);
+    let echoed = db.echoed_passwords(all);
+    let breaches = hibp.then(|| find_breaches(db.iter_entries().filter(|entry| all || !entry.archived)));
+    
+    if output::is_json() {
+        let mut paths = HashMap::new();
+        db.walk(|path, _, entry| {
+            if let Some(entry) = entry {
+                paths.insert(entry.id, path.to_vec());
+            }
+            WalkControl::Continue
+        });
+        let summary = |entry: &Entry| serde_json::json!(EntrySummary::new(&paths[&entry.id], entry));
+        let (breached, breach_error) = match &breaches {
+            Some(Ok(breached)) => (Some(breached.iter().map(|(entry, occurrences)| {
+                serde_json::json!({ "entry": summary(entry), "occurrences": occurrences })
+            }).collect::<Vec<_>>()), None),
+            Some(Err(e)) => (None, Some(e.as_str())),
+            None => (None, None),
+        };
+        output::print_json(&serde_json::json!({
+            "analyzed": total_entries,
+            "reused_scores": scores.reused,
+            "weak": weak_passwords.iter().map(|(entry, strength)| {
+                serde_json::json!({ "entry": summary(entry), "strength": strength })
+            }).collect::<Vec<_>>(),
+            "min_score": min_score,
+            "below_minimum": below_minimum.iter().map(|(entry, score)| {
+                serde_json::json!({ "entry": summary(entry), "score": score })
+            }).collect::<Vec<_>>(),
+            "reused_passwords": reused.iter().map(|group| {
+                group.iter().map(|found| EntrySummary::new(&found.path, found.entry)).collect::<Vec<_>>()
+            }).collect::<Vec<_>>(),
+            "echoed_passwords": echoed.iter().map(|echo| {
+                serde_json::json!({ "entry": EntrySummary::new(&echo.entry.path, echo.entry.entry), "field": echo.field.to_string() })
+            }).collect::<Vec<_>>(),
+            "breached": breached,
+            "breach_check_error": breach_error,
+        }))?;
+    } else {
+        println!("\n{}Audit de sécurité terminé:", Symbol::Heading("🔐"));
+        println!("Total d'entrées analysées: {}", total_entries);
+        if scores.reused > 0 {
+            println!("Mots de passe inchangés depuis l'audit précédent (non réanalysés): {}", scores.reused);
+        }
+        
+        if weak_passwords.is_empty() {
+            println!("{}Aucun mot de passe faible détecté!", Symbol::Success);
+        } else {
+            println!("{}{} mot(s) de passe faible(s) détecté(s):", Symbol::Warning, weak_passwords.len());
             
-            if matches!(analysis.strength, PasswordStrength::VeryWeak | PasswordStrength::Weak) {
-                weak_passwords.push((entry.id.clone(), entry.title.clone(), analysis.strength));
+            for (entry, strength) in &weak_passwords {
+                println!("  - {}{}{} (ID: {}) - Force: {}", entry.title, archived_marker(entry), color_marker(entry), entry.id, strength);
             }
+            
+            println!("\n{}Recommandation: Utilisez la commande 'generate' pour créer des mots de passe plus forts.", Symbol::Heading("💡"));
+        }
+        
+        if !reused.is_empty() {
+            println!("\n{}{} mot(s) de passe utilisé(s) par plusieurs entrées:", Symbol::Heading("🔁"), reused.len());
+            for (number, group) in reused.iter().enumerate() {
+                println!("  Mot de passe n°{} ({} entrées):", number + 1, group.len());
+                for found in group {
+                    println!("    - {}{} (ID: {}) - {}", found.entry.title, archived_marker(found.entry), found.entry.id, found.path.join("/"));
+                }
+            }
+            println!("Une fuite sur l'un de ces comptes expose les autres: utilisez un mot de passe différent pour chacun.");
         }
         
-        // Auditer récursivement les sous-groupes
-        for (_, subgroup) in &group.subgroups {
-            audit_group(subgroup, analyzer, weak_passwords, total_entries);
+        if !echoed.is_empty() {
+            println!("\n{}{} mot(s) de passe identique(s) au nom d'utilisateur ou au titre:", Symbol::Heading("🪞"), echoed.len());
+            for echo in &echoed {
+                let field = if echo.field == TextField::Username { "nom d'utilisateur" } else { "titre" };
+                println!("  - {}{} (ID: {}) - identique au {}", echo.entry.entry.title, archived_marker(echo.entry.entry), echo.entry.entry.id, field);
+            }
+        }
+        
+        if let Some(min) = min_score
+            && !below_minimum.is_empty()
+        {
+            println!("\n{}{} mot(s) de passe sous le score minimal de {}:", Symbol::Failure, below_minimum.len(), min);
+            for (entry, score) in &below_minimum {
+                println!("  - {}{} (ID: {}) - Score: {}/100", entry.title, archived_marker(entry), entry.id, score);
+            }
+        }
+        match &breaches {
+            Some(Ok(breached)) => report_breaches(breached),
+            Some(Err(e)) => eprintln!("Avertissement: vérification des fuites impossible: {}", e),
+            None => {}
         }
     }
+    let failed = below_minimum.len();
     
-    audit_group(&db.root_group, &analyzer, &mut weak_passwords, &mut total_entries);
-    
-    println!("\n🔐 Audit de sécurité terminé:");
-    println!("Total d'entrées analysées: {}", total_entries);
+    // Enregistrer la date et le résultat de l'audit (sans modifier la date de dernière modification)
+    if let Some(previous) = db.record_audit(weak_count)
+        && weak_count > previous
+    {
+        events::emit(path, &SecurityEvent::AuditRegression { previous, current: weak_count });
+    }
+    session.save()?;
+    session.close()?;
     
-    if weak_passwords.is_empty() {
-        println!("✅ Aucun mot de passe faible détecté!");
+    // Code de sortie non nul pour les vérifications automatisées
+    match min_score {
+        Some(min) if failed > 0 => Err(format!("{} mot(s) de passe sous le score minimal de {}.", failed, min)),
+        _ => Ok(()),
+    }
+}
+
+/// Vérifie des mots de passe auprès de Have I Been Pwned : entrées dont le mot de passe figure
+/// dans des fuites de données, avec son nombre d'apparitions
+fn find_breaches<'a>(entries: impl Iterator<Item = &'a Entry>) -> Result<Vec<(&'a Entry, u64)>, String> {
+    let mut checker = BreachChecker::new();
+    let mut breached = Vec::new();
+    for entry in entries.filter(|entry| !entry.password.is_empty()) {
+        match checker.occurrences(&entry.password).map_err(|e| e.to_string())? {
+            0 => {}
+            occurrences => breached.push((entry, occurrences)),
+        }
+    }
+    Ok(breached)
+}
+
+/// Affiche les mots de passe présents dans des fuites de données
+fn report_breaches(breached: &[(&Entry, u64)]) {
+    if breached.is_empty() {
+        println!("\n{}Aucun mot de passe présent dans les fuites de données connues (Have I Been Pwned).", Symbol::Success);
+        return;
+    }
+    println!("\n{}{} mot(s) de passe présent(s) dans des fuites de données:", Symbol::Alert, breached.len());
+    for (entry, occurrences) in breached {
+        println!("  - {}{} (ID: {}) - {} apparition(s)", entry.title, archived_marker(entry), entry.id, occurrences);
+    }
+    println!("Changez-les en priorité : ils figurent dans les listes essayées par les attaquants.");
+}
+
+/// Vérifie le mot de passe d'une entrée auprès de Have I Been Pwned (k-anonymat)
+fn cmd_check_breach(path: &Path, id: &EntryRef) -> Result<(), String> {
+    let session = Session::open(path)?;
+    let id = &session.resolve(id)?;
+    let entry = session.entry(id)?;
+    
+    if entry.password.is_empty() {
+        println!("L'entrée '{}' n'a pas de mot de passe.", entry.title);
     } else {
-        println!("⚠️  {} mot(s) de passe faible(s) détecté(s):", weak_passwords.len());
+        match BreachChecker::new().occurrences(&entry.password).map_err(|e| e.to_string())? {
+            0 => println!("{}Le mot de passe de '{}' n'apparaît dans aucune fuite de données connue.", Symbol::Success, entry.title),
+            occurrences => println!(
+                "{}Le mot de passe de '{}' apparaît {} fois dans des fuites de données: changez-le ('keeprust generate').",
+                Symbol::Alert, entry.title, occurrences
+            ),
+        }
+    }
+    session.close()
+}
+
+/// Exécute les tests d'intégrité de la cryptographie et du format de fichier
+fn cmd_selftest() -> Result<(), String> {
+    let mut results = selftest::run();
+    results.push(SelfTestResult { name: "Format de fichier (aller-retour)", result: file_format_round_trip() });
+    
+    let mut failures = 0;
+    for test in &results {
+        match &test.result {
+            Ok(()) => println!("{}{}", Symbol::Success, test.name),
+            Err(e) => {
+                println!("{}{}: {}", Symbol::Failure, test.name, e);
+                failures += 1;
+            }
+        }
+    }
+    
+    if failures > 0 {
+        return Err(format!("{} test(s) d'intégrité en échec: n'utilisez pas ce binaire pour vos données.", failures));
+    }
+    
+    println!("\nTous les tests d'intégrité ont réussi.");
+    Ok(())
+}
+
+/// Chiffre puis relit une base de données dans chaque format de sérialisation
+fn file_format_round_trip() -> Result<(), String> {
+    let mut db = Database::new("Selftest".to_string(), String::new());
+    let entry = Entry::builder()
+        .title("Test")
+        .username("utilisateur")
+        .password("mot de passe é€")
+        .build()
+        .map_err(|e| e.to_string())?;
+    let entry_id = db.add_entry(None, entry).map_err(|e| e.to_string())?;
+    
+    for format in [PayloadFormat::Json, PayloadFormat::Cbor, PayloadFormat::MessagePack] {
+        db.storage_format = format;
+        let bytes = Repository::encode(&db, "selftest").map_err(|e| e.to_string())?;
         
-        for (id, title, strength) in weak_passwords {
-            println!("  - {} (ID: {}) - Force: {}", title, id, strength);
+        let restored = Repository::decode(&bytes, "selftest").map_err(|e| format!("{}: {}", format, e))?;
+        match restored.find_entry(&entry_id) {
+            Some(entry) if entry.password == "mot de passe é€" && restored.storage_format == format => {},
+            _ => return Err(format!("{}: données relues différentes", format)),
         }
         
-        println!("\n💡 Recommandation: Utilisez la commande 'generate' pour créer des mots de passe plus forts.");
+        if Repository::decode(&bytes, "mauvais").is_ok() {
+            return Err(format!("{}: mauvais mot de passe accepté", format));
+        }
+    }
+    
+    Ok(())
+}
+
+/// Génère une base de test reproductible
+fn cmd_gen_fixture(path: &Path, options: FixtureOptions, password: &str, format: PayloadFormat, force: bool) -> Result<(), String> {
+    if path.exists() && !force {
+        return Err(format!("Le fichier {} existe déjà (utilisez --force pour l'écraser).", path.display()));
+    }
+    
+    let mut db = fixture::generate(&options);
+    db.storage_format = format;
+    
+    let secret = session::master_secret(password.to_string())?;
+    let mut timings = session::timings();
+    Repository::new(path).save_timed(&db, &secret, &mut timings).map_err(|e| e.to_string())?;
+    session::report_timings("enregistrement", &timings);
+    
+    println!("Base de test générée: {}", path.display());
+    println!("Entrées: {}, groupes: {}, graine: {}", db.count_entries(), db.count_groups(), options.seed);
+    println!("Mot de passe: {}", password);
+    Ok(())
+}
+
+/// Décrit un fichier sans le déchiffrer, avant de l'ouvrir normalement
+fn cmd_inspect(file: &Path) -> Result<(), String> {
+    let (size, inspection) = inspect::inspect_file(file).map_err(|e| format!("{}: {}", file.display(), e))?;
+    
+    println!("\n{}{} ({} octets)", Symbol::Heading("🔍"), file.display(), size);
+    print!("{}", inspection);
+    match inspection {
+        Inspection::Keeprust(report) if report.warnings().is_empty() => {
+            println!("Aucune anomalie relevée; pour l'ouvrir: keeprust -d {} open", file.display());
+        }
+        Inspection::Keeprust(_) => println!("Anomalies relevées: vérifiez la provenance du fichier ('keeprust recover' s'il est endommagé)."),
+        Inspection::Kdbx(_) | Inspection::Kdb => println!("Fichier KeePass: keeprust ne peut pas l'ouvrir directement."),
+    }
+    Ok(())
+}
+
+/// Propose des stratégies de récupération pour une base de données illisible
+fn cmd_recover(path: &Path) -> Result<(), String> {
+    if !path.exists() {
+        return Err(format!("Le fichier {} n'existe pas.", path.display()));
+    }
+    
+    let password = session::master_secret(session::prompt_master_password("Mot de passe: ")?)?;
+    
+    // Vérifier d'abord si le fichier est réellement endommagé
+    let repo = Repository::new(path);
+    match repo.load_timed(&password, &mut session::timings()) {
+        Ok(_) => {
+            println!("La base de données s'ouvre correctement: aucune récupération n'est nécessaire.");
+            return Ok(());
+        }
+        Err(RepositoryError::InvalidPassword) => {
+            println!("Problème détecté: mot de passe incorrect ou données chiffrées endommagées.");
+        }
+        Err(e) => println!("Problème détecté: {}", e),
+    }
+    
+    let candidates = recovery::attempt_recovery(path, &password);
+    if candidates.is_empty() {
+        return Err("Aucune stratégie de récupération n'a abouti (vérifiez le mot de passe ou cherchez une autre sauvegarde).".to_string());
+    }
+    
+    println!("\nOptions de récupération:");
+    for (i, candidate) in candidates.iter().enumerate() {
+        println!("  [{}] {} - {} entrée(s), modifiée le {}",
+                 i + 1,
+                 candidate.source,
+                 candidate.database.count_entries(),
+                 candidate.database.metadata.updated_at.format("%d-%m-%Y %H:%M:%S"));
     }
+    println!("  [0] Annuler");
+    
+    let input = session::prompt_line("Choix: ")?;
+    let choice = match input.parse::<usize>() {
+        Ok(0) => {
+            println!("Opération annulée.");
+            return Ok(());
+        }
+        Ok(n) if n <= candidates.len() => &candidates[n - 1],
+        _ => return Err("Choix invalide.".to_string()),
+    };
+    
+    // Conserver le fichier endommagé à côté de la base restaurée
+    let mut corrupt_path = path.as_os_str().to_owned();
+    corrupt_path.push(".corrupt");
+    std::fs::rename(path, &corrupt_path).map_err(|e| e.to_string())?;
+    
+    let mut timings = session::timings();
+    repo.save_timed(&choice.database, &password, &mut timings).map_err(|e| e.to_string())?;
+    session::report_timings("enregistrement", &timings);
+    signing::sign_vault(path)?;
     
+    println!("Base de données restaurée ({}).", choice.source);
+    println!("Le fichier endommagé a été conservé sous {}.", Path::new(&corrupt_path).display());
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,262 @@
+use clap::{Arg, Command, CommandFactory, ValueEnum};
+use std::any::TypeId;
+use std::path::PathBuf;
+
+use crate::models::database::Database;
+use crate::models::id::GroupId;
+use crate::models::lookup::EntryRef;
+
+use super::app::Cli;
+use super::discovery;
+use super::profiles::Profiles;
+use super::session::Session;
+
+/// Nom du programme dans les scripts de complétion
+const BIN_NAME: &str = "keeprust";
+
+/// Shells pour lesquels un script de complétion peut être généré
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+/// Valeur dynamique attendue à une position de la ligne de commande
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    Entry,
+    Group,
+}
+
+/// Affiche le script de complétion d'un shell : celui généré par clap_complete, précédé d'un appel
+/// à `keeprust __complete` qui propose les IDs d'entrées et de groupes de la base déverrouillée
+pub fn cmd_completions(shell: CompletionShell) -> Result<(), String> {
+    print!("{}", completion_script(shell)?);
+    Ok(())
+}
+
+/// Script de complétion complet d'un shell
+fn completion_script(shell: CompletionShell) -> Result<String, String> {
+    let generator = match shell {
+        CompletionShell::Bash => clap_complete::Shell::Bash,
+        CompletionShell::Zsh => clap_complete::Shell::Zsh,
+        CompletionShell::Fish => clap_complete::Shell::Fish,
+        CompletionShell::Powershell => clap_complete::Shell::PowerShell,
+    };
+    let mut script = Vec::new();
+    clap_complete::generate(generator, &mut Cli::command(), BIN_NAME, &mut script);
+    let script = String::from_utf8(script).map_err(|e| e.to_string())?;
+    
+    // Les valeurs dynamiques passent avant la complétion générée, qui prend le relais ailleurs
+    Ok(match shell {
+        CompletionShell::Bash => insert_after(&script, "_keeprust() {\n", BASH_HOOK)?,
+        CompletionShell::Zsh => insert_after(&script, "_keeprust() {\n", ZSH_HOOK)?,
+        CompletionShell::Powershell => insert_after(&script, "param($wordToComplete, $commandAst, $cursorPosition)\n", POWERSHELL_HOOK)?,
+        CompletionShell::Fish => script + FISH_HOOK,
+    })
+}
+
+/// Insère `hook` après la première occurrence de `anchor` dans le script généré
+fn insert_after(script: &str, anchor: &str, hook: &str) -> Result<String, String> {
+    let position = script.find(anchor)
+        .ok_or_else(|| "Script de complétion généré dans un format inattendu.".to_string())?;
+    let end = position + anchor.len();
+    Ok(format!("{}{}{}", &script[..end], hook, &script[end..]))
+}
+
+const BASH_HOOK: &str = r#"    local values
+    if values=$(keeprust __complete --shell bash --index "$COMP_CWORD" "${COMP_WORDS[@]}" 2>/dev/null); then
+        COMPREPLY=($(compgen -W "$values" -- "${COMP_WORDS[COMP_CWORD]}"))
+        return 0
+    fi
+"#;
+
+const ZSH_HOOK: &str = r#"    local values
+    if values=$(keeprust __complete --shell zsh --index $((CURRENT - 1)) "${words[@]}" 2>/dev/null); then
+        local -a candidates
+        candidates=(${(f)values})
+        _describe -t values 'valeur' candidates
+        return
+    fi
+"#;
+
+const POWERSHELL_HOOK: &str = r#"    $words = @($commandAst.CommandElements | ForEach-Object { $_.ToString() })
+    $index = if ($wordToComplete) { $words.Count - 1 } else { $words.Count }
+    $values = @(keeprust __complete --shell powershell --index $index @words '' 2>$null)
+    if ($LASTEXITCODE -eq 0) {
+        return $values | ForEach-Object {
+            $value, $description = $_ -split "`t", 2
+            [CompletionResult]::new($value, $value, [CompletionResultType]::ParameterValue, $description)
+        } | Where-Object { $_.CompletionText -like "$wordToComplete*" }
+    }
+"#;
+
+const FISH_HOOK: &str = r#"
+function __keeprust_values
+    set -l words (commandline -opc)
+    keeprust __complete --shell fish --index (count $words) $words "$(commandline -ct)" 2>/dev/null
+end
+complete -c keeprust -a '(__keeprust_values)'
+"#;
+
+/// Affiche les valeurs proposées pour le mot `index` de la ligne `words` (words[0] : le programme).
+///
+/// Échoue si ce mot n'est pas un ID d'entrée ou de groupe (le script passe alors à la complétion
+/// générée); n'affiche rien si la base n'est pas déverrouillée dans l'agent : rien n'est demandé.
+pub fn cmd_complete(shell: CompletionShell, index: usize, words: &[String]) -> Result<(), String> {
+    let kind = expected_value(words, index).ok_or_else(|| "Aucune valeur dynamique à cette position.".to_string())?;
+    let Some(session) = database_path(&words[..index.min(words.len())]).and_then(|path| Session::open_cached(&path)) else {
+        return Ok(());
+    };
+    
+    for (value, description) in candidates(&session.db, kind) {
+        println!("{}", format_candidate(shell, &value, &description));
+    }
+    Ok(())
+}
+
+/// IDs proposés avec leur description (titre de l'entrée, nom du groupe)
+fn candidates(db: &Database, kind: ValueKind) -> Vec<(String, String)> {
+    let mut candidates: Vec<(String, String)> = match kind {
+        ValueKind::Entry => db.iter_entries()
+            .filter(|entry| !db.is_in_trash(&entry.id))
+            .map(|entry| (entry.id.to_string(), entry.title.clone()))
+            .collect(),
+        ValueKind::Group => db.iter_groups()
+            .map(|group| (group.id.to_string(), group.name.clone()))
+            .collect(),
+    };
+    candidates.sort_by_key(|(_, description)| description.to_lowercase());
+    candidates
+}
+
+/// Ligne de candidat dans le format attendu par le script du shell
+fn format_candidate(shell: CompletionShell, value: &str, description: &str) -> String {
+    let description = description.replace(['\t', '\n', '\r'], " ");
+    match shell {
+        CompletionShell::Bash => value.to_string(),
+        CompletionShell::Zsh => format!("{}:{}", value, description.replace(':', "\\:")),
+        CompletionShell::Fish | CompletionShell::Powershell => format!("{}\t{}", value, description),
+    }
+}
+
+/// Base désignée par la ligne en cours de saisie : --profile, --database, sinon la base par défaut
+/// ou, à défaut, la première base trouvée aux emplacements usuels
+fn database_path(words: &[String]) -> Option<PathBuf> {
+    let mut database = None;
+    let mut profile = None;
+    let mut iter = words.iter().skip(1);
+    while let Some(word) = iter.next() {
+        match word.split_once('=') {
+            Some(("--database", value)) => database = Some(value.to_string()),
+            Some(("--profile", value)) => profile = Some(value.to_string()),
+            _ if word == "-d" || word == "--database" => database = iter.next().cloned(),
+            _ if word == "--profile" => profile = iter.next().cloned(),
+            _ => {}
+        }
+    }
+    
+    if let Some(name) = profile {
+        return Profiles::load().ok()?.get(&name).ok().map(|profile| profile.path.clone());
+    }
+    if let Some(database) = database {
+        return Some(PathBuf::from(database));
+    }
+    let default = discovery::default_database();
+    if default.exists() {
+        return Some(default);
+    }
+    discovery::candidates().into_iter().map(|candidate| candidate.path).next()
+}
+
+/// Nature de la valeur attendue au mot `index` de la ligne `words`, d'après la définition des
+/// commandes : argument d'une option ou argument positionnel d'une sous-commande
+pub fn expected_value(words: &[String], index: usize) -> Option<ValueKind> {
+    if index == 0 || index > words.len() {
+        return None;
+    }
+    
+    let mut root = Cli::command();
+    root.build();
+    let mut command = &root;
+    let mut positionals = 0;
+    let mut i = 1;
+    while i < index {
+        let word = &words[i];
+        if let Some(arg) = find_option(command, word) {
+            // La valeur d'une option suit, sauf avec --option=valeur
+            if arg.get_action().takes_values() && !word.contains('=') {
+                i += 1;
+            }
+        } else if !word.starts_with('-') {
+            match command.find_subcommand(word).filter(|_| positionals == 0) {
+                Some(subcommand) => command = subcommand,
+                None => positionals += 1,
+            }
+        }
+        i += 1;
+    }
+    
+    let current = words.get(index).map(String::as_str).unwrap_or_default();
+    let arg = match find_option(command, &words[index - 1]) {
+        Some(arg) if arg.get_action().takes_values() && !words[index - 1].contains('=') => arg,
+        _ if current.starts_with('-') => return None,
+        _ => {
+            let arguments: Vec<&Arg> = command.get_positionals().collect();
+            match arguments.get(positionals) {
+                Some(arg) => *arg,
+                // Les derniers arguments peuvent accepter plusieurs valeurs (plusieurs IDs)
+                None => arguments.last().filter(|arg| arg.get_num_args().is_some_and(|num| num.max_values() > 1))?,
+            }
+        }
+    };
+    
+    let parsed = arg.get_value_parser().type_id();
+    if parsed == TypeId::of::<EntryRef>() {
+        Some(ValueKind::Entry)
+    } else if parsed == TypeId::of::<GroupId>() {
+        Some(ValueKind::Group)
+    } else {
+        None
+    }
+}
+
+/// Option de la commande désignée par un mot (--nom, --nom=valeur ou -c)
+fn find_option<'a>(command: &'a Command, word: &str) -> Option<&'a Arg> {
+    if let Some(long) = word.strip_prefix("--") {
+        let name = long.split_once('=').map_or(long, |(name, _)| name);
+        return command.get_arguments().find(|arg| arg.get_long() == Some(name));
+    }
+    let mut chars = word.strip_prefix('-')?.chars();
+    match (chars.next(), chars.next()) {
+        (Some(short), None) => command.get_arguments().find(|arg| arg.get_short() == Some(short)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn words(line: &str) -> Vec<String> {
+        line.split(' ').map(str::to_string).collect()
+    }
+    
+    #[test]
+    fn test_expected_value_follows_command_definitions() {
+        let line = words("keeprust -d coffre.krs show ");
+        assert_eq!(expected_value(&line, 4), Some(ValueKind::Entry));
+        assert_eq!(expected_value(&line, 2), None);
+        
+        let line = words("keeprust list --group ");
+        assert_eq!(expected_value(&line, 3), Some(ValueKind::Group));
+        assert_eq!(expected_value(&words("keeprust list --"), 2), None);
+        assert_eq!(expected_value(&words("keeprust sh"), 1), None);
+        
+        for shell in [CompletionShell::Bash, CompletionShell::Zsh, CompletionShell::Fish, CompletionShell::Powershell] {
+            assert!(completion_script(shell).unwrap().contains("keeprust __complete --shell"));
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::events;
+use crate::utils::password_generator::PasswordGenerator;
+
+/// Nom du fichier des valeurs proposées pour les nouvelles entrées, dans le répertoire de configuration
+const DEFAULTS_FILE: &str = "defaults.toml";
+
+/// Réglages du générateur de mots de passe pour les ajouts rapides
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GeneratorDefaults {
+    pub length: usize,
+    pub symbols: bool,
+    pub exclude_similar: bool,
+}
+
+impl Default for GeneratorDefaults {
+    fn default() -> Self {
+        Self { length: 16, symbols: true, exclude_similar: false }
+    }
+}
+
+impl GeneratorDefaults {
+    /// Générateur configuré avec ces réglages
+    pub fn generator(&self) -> PasswordGenerator {
+        PasswordGenerator::new()
+            .length(self.length)
+            .symbols(self.symbols)
+            .exclude_similar(self.exclude_similar)
+    }
+}
+
+/// Valeurs proposées à la création d'une entrée :
+///
+/// ```toml
+/// username = "bob@example.com"
+/// group = "Perso/Web"     # groupe des ajouts rapides (keeprust quick)
+///
+/// [usernames]
+/// "example.com" = "bob"
+/// "work.example.org" = "b.martin"
+///
+/// [generator]
+/// length = 20
+/// symbols = false
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EntryDefaults {
+    /// Nom d'utilisateur proposé par défaut
+    #[serde(default)]
+    pub username: Option<String>,
+    
+    /// Nom d'utilisateur proposé pour un domaine et ses sous-domaines
+    #[serde(default)]
+    pub usernames: BTreeMap<String, String>,
+    
+    /// Chemin du groupe où `keeprust quick` ajoute les entrées (groupe racine si non défini)
+    #[serde(default)]
+    pub group: Option<String>,
+    
+    /// Réglages du générateur pour `keeprust quick --generate`
+    #[serde(default)]
+    pub generator: GeneratorDefaults,
+}
+
+/// Emplacement du fichier des valeurs par défaut
+pub fn defaults_path() -> Option<PathBuf> {
+    Some(events::config_dir()?.join(DEFAULTS_FILE))
+}
+
+impl EntryDefaults {
+    /// Charge les valeurs par défaut; aucune valeur si le fichier n'existe pas
+    pub fn load() -> Result<Self, String> {
+        let Some(path) = defaults_path() else {
+            return Ok(Self::default());
+        };
+        match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content).map_err(|e| format!("Fichier {} invalide: {}", path.display(), e)),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+    
+    /// Nom d'utilisateur proposé pour un hôte : celui du domaine le plus précis qui le couvre,
+    /// sinon la valeur générale
+    pub fn suggest_username(&self, host: Option<&str>) -> Option<&str> {
+        let domain_match = host.and_then(|host| {
+            self.usernames.iter()
+                .filter(|(domain, _)| {
+                    let domain = domain.trim_end_matches('.').to_lowercase();
+                    host == domain || host.strip_suffix(domain.as_str()).is_some_and(|sub| sub.ends_with('.'))
+                })
+                .max_by_key(|(domain, _)| domain.len())
+                .map(|(_, username)| username.as_str())
+        });
+        domain_match.or(self.username.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_suggest_username_by_domain() {
+        let defaults: EntryDefaults = toml::from_str(r#"
+            username = "bob@example.com"
+            
+            [usernames]
+            "example.org" = "bob"
+            "work.example.org" = "b.martin"
+        "#).unwrap();
+        
+        assert_eq!(defaults.suggest_username(Some("sso.work.example.org")), Some("b.martin"));
+        assert_eq!(defaults.suggest_username(Some("example.org")), Some("bob"));
+        assert_eq!(defaults.suggest_username(Some("notexample.org")), Some("bob@example.com"));
+        assert_eq!(defaults.suggest_username(None), Some("bob@example.com"));
+        assert!(toml::from_str::<EntryDefaults>("user = \"bob\"").is_err());
+        assert_eq!(defaults.generator, GeneratorDefaults::default());
+        
+        let quick: EntryDefaults = toml::from_str("group = \"Perso/Web\"\n[generator]\nlength = 24\nsymbols = false").unwrap();
+        assert_eq!(quick.group.as_deref(), Some("Perso/Web"));
+        assert_eq!(quick.generator.generator().generate().unwrap().len(), 24);
+    }
+}
\ No newline at end of file
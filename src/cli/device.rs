@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use uuid::Uuid;
+
+use crate::events;
+use crate::models::device::{self, DeviceStamp};
+use crate::utils::paths;
+
+/// Nom du fichier d'identité de l'appareil, dans le répertoire de configuration
+const DEVICE_FILE: &str = "device.toml";
+
+/// Identité de l'appareil, créée au premier lancement
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DeviceConfig {
+    id: String,
+    name: String,
+}
+
+/// Emplacement du fichier d'identité de l'appareil
+pub fn device_path() -> Option<PathBuf> {
+    Some(events::config_dir()?.join(DEVICE_FILE))
+}
+
+/// Nom de la machine, utilisé comme nom d'appareil par défaut
+fn hostname() -> String {
+    let from_file = || fs::read_to_string("/etc/hostname").ok();
+    let from_command = || Command::new("hostname").output().ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned());
+    
+    env::var("COMPUTERNAME").ok()
+        .or_else(from_file)
+        .or_else(from_command)
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "appareil".to_string())
+}
+
+/// Écrit le fichier d'identité de l'appareil
+fn write_config(path: &PathBuf, config: &DeviceConfig) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        paths::create_private_dir(parent).map_err(|e| e.to_string())?;
+    }
+    let content = toml::to_string(config).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Impossible d'enregistrer {}: {}", path.display(), e))
+}
+
+/// Charge l'identité de l'appareil, en la créant (nom de la machine, identifiant aléatoire) si besoin
+pub fn load_or_create() -> Result<DeviceStamp, String> {
+    let path = device_path().ok_or("Aucun répertoire de configuration pour l'identité de l'appareil.")?;
+    
+    let config = match fs::read_to_string(&path) {
+        Ok(content) => toml::from_str(&content).map_err(|e| format!("Fichier {} invalide: {}", path.display(), e))?,
+        Err(_) => {
+            let config = DeviceConfig { id: Uuid::new_v4().to_string(), name: hostname() };
+            write_config(&path, &config)?;
+            config
+        }
+    };
+    Ok(DeviceStamp { id: config.id, name: config.name })
+}
+
+/// Définit l'appareil courant pour marquer les modifications.
+///
+/// Sans identité accessible (pas de répertoire de configuration, fichier invalide), les
+/// modifications ne sont simplement pas marquées; `keeprust device` affiche l'erreur.
+pub fn configure() {
+    if let Ok(stamp) = load_or_create() {
+        device::set_device(stamp);
+    }
+}
+
+/// Affiche l'identité de l'appareil, ou la renomme
+pub fn cmd_device(rename: Option<String>) -> Result<(), String> {
+    let mut stamp = load_or_create()?;
+    let path = device_path().ok_or("Aucun répertoire de configuration.")?;
+    
+    if let Some(name) = rename {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err("Le nom de l'appareil ne peut pas être vide.".to_string());
+        }
+        stamp.name = name;
+        write_config(&path, &DeviceConfig { id: stamp.id.clone(), name: stamp.name.clone() })?;
+        println!("Appareil renommé: {}", stamp.name);
+        return Ok(());
+    }
+    
+    println!("Appareil: {}", stamp.name);
+    println!("ID: {}", stamp.id);
+    println!("Configuration: {}", path.display());
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::utils::{paths, termux};
+use super::app::{Commands, SigningAction, SyncAction, VaultsAction};
+use super::profiles::{self, Profiles};
+use super::session;
+
+/// Nom de la base ouverte sans --database ni --profile
+pub const DEFAULT_DATABASE: &str = "keeprust.krs";
+
+/// Base trouvée hors du répertoire courant, avec l'emplacement qui l'a fait trouver
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub path: PathBuf,
+    pub origin: String,
+}
+
+/// Répertoire des données de keeprust : XDG_DATA_HOME, sinon APPDATA, sinon ~/.local/share
+pub fn data_dir() -> Option<PathBuf> {
+    let data_dir = env::var_os("XDG_DATA_HOME").map(PathBuf::from)
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")))?;
+    Some(data_dir.join("keeprust"))
+}
+
+/// Base ouverte sans --database ni --profile : dans le répertoire des données sous Windows
+/// (%APPDATA%\keeprust, qui suit le profil itinérant), dans le répertoire courant ailleurs
+pub fn default_database() -> PathBuf {
+    if cfg!(windows)
+        && let Some(directory) = data_dir()
+    {
+        return directory.join(DEFAULT_DATABASE);
+    }
+    PathBuf::from(DEFAULT_DATABASE)
+}
+
+/// Crée le répertoire de la base par défaut avant sa création, accessible au seul utilisateur
+pub fn prepare_default_database(path: &Path) -> Result<(), String> {
+    match path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        Some(directory) => paths::create_private_dir(directory)
+            .map_err(|e| format!("Impossible de créer le répertoire {}: {}", directory.display(), e)),
+        None => Ok(()),
+    }
+}
+
+/// Indique si la commande ouvre une base existante (et peut donc en chercher une ailleurs)
+pub fn opens_database(command: &Commands) -> bool {
+    !matches!(
+        command,
+        Commands::New { .. }
+            | Commands::Generate { .. }
+            | Commands::GenFixture { .. }
+            | Commands::KdfBenchmark { .. }
+            | Commands::KeygenKeyfile { .. }
+            | Commands::Inspect { .. }
+            | Commands::Selftest
+            | Commands::Help { .. }
+            | Commands::Manpages { .. }
+            | Commands::Completions { .. }
+            | Commands::Complete { .. }
+            | Commands::Device { .. }
+            | Commands::Agent { .. }
+            | Commands::Vaults { action: None | Some(VaultsAction::List | VaultsAction::Lock { all: true, .. }) }
+            | Commands::Lock
+            | Commands::Signing { action: SigningAction::Rotate | SigningAction::Trust { .. } }
+            | Commands::Sync { action: Some(SyncAction::Init { .. }) }
+            | Commands::External(_)
+    )
+}
+
+/// Bases présentes aux emplacements usuels : répertoire des données, ~/Documents/keeprust.krs,
+/// le stockage partagé sous Termux (Android), puis les bases des profils (profiles.toml); sous
+/// Windows, d'abord keeprust.krs dans le répertoire courant, l'emplacement par défaut des
+/// versions précédentes
+pub fn candidates() -> Vec<Candidate> {
+    let mut candidates = Vec::new();
+    if cfg!(windows) {
+        candidates.push(Candidate { path: PathBuf::from(DEFAULT_DATABASE), origin: "répertoire courant".to_string() });
+    }
+    if let Some(directory) = data_dir()
+        && let Ok(files) = fs::read_dir(&directory)
+    {
+        let mut paths: Vec<PathBuf> = files.filter_map(Result::ok)
+            .map(|file| file.path())
+            .filter(|path| path.extension() == Some(OsStr::new("krs")))
+            .collect();
+        paths.sort();
+        candidates.extend(paths.into_iter().map(|path| Candidate { path, origin: "répertoire des données".to_string() }));
+    }
+    if let Some(home) = env::var_os("HOME") {
+        let path = PathBuf::from(home).join("Documents").join(DEFAULT_DATABASE);
+        candidates.push(Candidate { path, origin: "Documents".to_string() });
+    }
+    if termux::is_termux()
+        && let Some(shared) = termux::shared_storage()
+    {
+        for path in [shared.join("Documents").join(DEFAULT_DATABASE), shared.join(DEFAULT_DATABASE)] {
+            candidates.push(Candidate { path, origin: "stockage partagé Android".to_string() });
+        }
+    }
+    // Un fichier de profils invalide est signalé par --profile, pas ici
+    if let Ok(profiles) = Profiles::load() {
+        candidates.extend(profiles.profiles.into_iter().map(|(name, profile)| Candidate {
+            path: profile.path,
+            origin: format!("profil {}", name),
+        }));
+    }
+    existing(candidates)
+}
+
+/// Garde les fichiers existants, chacun une seule fois
+fn existing(candidates: Vec<Candidate>) -> Vec<Candidate> {
+    let mut seen = HashSet::new();
+    candidates.into_iter()
+        .filter(|candidate| candidate.path.is_file() && seen.insert(profiles::absolute(&candidate.path)))
+        .collect()
+}
+
+/// Propose les bases trouvées aux emplacements usuels quand la base par défaut n'existe pas.
+///
+/// Retourne celle choisie, ou None si aucune n'a été trouvée; sans terminal, rien n'est choisi à
+/// la place de l'utilisateur : les bases trouvées sont indiquées dans le message d'erreur.
+pub fn discover() -> Result<Option<PathBuf>, String> {
+    let default = default_database();
+    let mut candidates = candidates();
+    if candidates.is_empty() {
+        return Ok(None);
+    }
+    
+    let list: Vec<String> = candidates.iter().enumerate()
+        .map(|(i, candidate)| format!("  [{}] {} ({})", i + 1, candidate.path.display(), candidate.origin))
+        .collect();
+    if !session::is_interactive() {
+        return Err(format!(
+            "Le fichier {} n'existe pas. Bases trouvées:\n{}\nIndiquez la base avec --database ou --profile.",
+            default.display(), list.join("\n")
+        ));
+    }
+    
+    println!("Le fichier {} n'existe pas. Bases trouvées:", default.display());
+    for line in &list {
+        println!("{}", line);
+    }
+    println!("  [0] Annuler");
+    
+    let input = session::prompt_line("Choix: ")?;
+    let candidate = match input.parse::<usize>() {
+        Ok(0) => return Err("Opération annulée.".to_string()),
+        Ok(n) if n <= candidates.len() => candidates.swap_remove(n - 1),
+        _ => return Err("Choix invalide.".to_string()),
+    };
+    eprintln!("(ouvrez-la directement avec -d {})", candidate.path.display());
+    Ok(Some(candidate.path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_existing_candidates_are_deduplicated() {
+        let directory = env::temp_dir().join(format!("keeprust-discovery-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let vault = directory.join("perso.krs");
+        fs::write(&vault, b"KEEPRUST").unwrap();
+        
+        let candidate = |path: PathBuf, origin: &str| Candidate { path, origin: origin.to_string() };
+        let found = existing(vec![
+            candidate(directory.join("absente.krs"), "Documents"),
+            candidate(vault.clone(), "répertoire des données"),
+            candidate(directory.join(".").join("perso.krs"), "profil perso"),
+            candidate(directory.clone(), "profil dossier"),
+        ]);
+        assert_eq!(found, vec![candidate(vault, "répertoire des données")]);
+        
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
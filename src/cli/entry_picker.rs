@@ -0,0 +1,110 @@
+use crate::models::database::Database;
+use crate::models::id::EntryId;
+use crate::models::lookup::LookupError;
+
+use super::session;
+
+/// Ligne du menu : numéro, titre, utilisateur, groupe et début de l'ID
+fn entry_line(db: &Database, number: usize, id: &EntryId) -> String {
+    let Some(entry) = db.find_entry(id) else {
+        return format!("  {:>3}. {}", number, id);
+    };
+    let path = db.entry_path(id).map(|path| path.join("/")).unwrap_or_default();
+    let username = if entry.username.is_empty() { String::new() } else { format!(" ({})", entry.username) };
+    format!("  {:>3}. {}{} - {} [{}]", number, entry.title, username, path, id.short())
+}
+
+/// Liste des entrées candidates, une par ligne (message d'erreur hors terminal)
+pub fn candidate_lines(db: &Database, candidates: &[EntryId]) -> String {
+    candidates.iter().enumerate()
+        .map(|(index, id)| entry_line(db, index + 1, id))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Suite d'une désignation qui ne retient pas une seule entrée : menu dans un terminal
+/// (`interactive`), sinon erreur énumérant les entrées candidates
+pub fn settle(db: &Database, error: LookupError, interactive: bool) -> Result<EntryId, String> {
+    match &error {
+        LookupError::Ambiguous { reference, candidates } if interactive => pick_entry(db, reference, candidates),
+        LookupError::Ambiguous { candidates, .. } => Err(format!(
+            "{} Précisez le titre ou utilisez l'ID:\n{}", error, candidate_lines(db, candidates)
+        )),
+        LookupError::NotFound(_) => Err(error.to_string()),
+    }
+}
+
+/// Demande dans le terminal laquelle des entrées désignées par `reference` utiliser : un numéro
+/// choisit l'entrée, une réponse vide annule
+fn pick_entry(db: &Database, reference: &str, candidates: &[EntryId]) -> Result<EntryId, String> {
+    println!("\nPlusieurs entrées correspondent à '{}':", reference);
+    println!("{}", candidate_lines(db, candidates));
+    choose(candidates, || session::prompt_line("Numéro de l'entrée (Entrée pour annuler): "))
+}
+
+/// Entrée choisie parmi les candidates d'après les réponses lues par `ask`, redemandée tant que
+/// la réponse n'est pas un numéro du menu
+fn choose<F>(candidates: &[EntryId], mut ask: F) -> Result<EntryId, String>
+where
+    F: FnMut() -> Result<String, String>,
+{
+    loop {
+        let answer = ask()?;
+        if answer.is_empty() {
+            return Err("Opération annulée.".to_string());
+        }
+        match answer.parse::<usize>().ok().and_then(|number| number.checked_sub(1)).and_then(|index| candidates.get(index)) {
+            Some(id) => return Ok(*id),
+            None => println!("Réponse invalide: entrez un numéro entre 1 et {}.", candidates.len()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    
+    fn sample() -> (Database, EntryId, EntryId) {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let work = db.add_group(None, "Work".to_string()).unwrap();
+        let github = db.add_entry(Some(&work), Entry::new("GitHub".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new())).unwrap();
+        let gitlab = db.add_entry(None, Entry::new("GitLab".to_string(), String::new(), "pw".to_string(), String::new(), String::new())).unwrap();
+        (db, github, gitlab)
+    }
+    
+    #[test]
+    fn test_candidate_lines() {
+        let (db, github, gitlab) = sample();
+        assert_eq!(candidate_lines(&db, &[github, gitlab]), format!(
+            "    1. GitHub (bob) - Root/Work [{}]\n    2. GitLab - Root [{}]",
+            github.short(),
+            gitlab.short(),
+        ));
+    }
+    
+    #[test]
+    fn test_menu_asks_again_until_a_listed_number() {
+        let (_, github, gitlab) = sample();
+        let mut answers = vec!["3", "GitLab", "0", "2"].into_iter();
+        assert_eq!(choose(&[github, gitlab], || Ok(answers.next().unwrap().to_string())), Ok(gitlab));
+        assert_eq!(answers.next(), None);
+    }
+    
+    #[test]
+    fn test_empty_answer_cancels_the_menu() {
+        let (_, github, gitlab) = sample();
+        assert_eq!(choose(&[github, gitlab], || Ok(String::new())), Err("Opération annulée.".to_string()));
+    }
+    
+    #[test]
+    fn test_ambiguity_without_terminal_lists_candidates() {
+        let (db, github, gitlab) = sample();
+        let error = LookupError::Ambiguous { reference: "git".to_string(), candidates: vec![github, gitlab] };
+        
+        let message = settle(&db, error, false).unwrap_err();
+        assert!(message.starts_with("'git' correspond à 2 entrées. Précisez le titre ou utilisez l'ID:\n"));
+        assert!(message.ends_with(&candidate_lines(&db, &[github, gitlab])));
+        assert_eq!(settle(&db, LookupError::NotFound("x".to_string()), true).unwrap_err(), "Aucune entrée ne correspond à 'x'.");
+    }
+}
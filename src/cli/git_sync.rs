@@ -0,0 +1,455 @@
+#[cfg(not(feature = "git-sync"))]
+use std::path::Path;
+
+#[cfg(not(feature = "git-sync"))]
+use super::session::Session;
+
+#[cfg(not(feature = "git-sync"))]
+const DISABLED: &str = "Cette version de keeprust a été compilée sans la synchronisation par git (fonctionnalité 'git-sync').";
+
+/// Avant l'ouverture d'une base synchronisée : récupère les versions des autres appareils
+#[cfg(not(feature = "git-sync"))]
+pub fn before_open(_vault: &Path) -> Option<String> {
+    None
+}
+
+/// Fusionne la version distante d'une base modifiée des deux côtés dans la session
+#[cfg(not(feature = "git-sync"))]
+pub fn merge_diverged(_session: &mut Session, _upstream: &str) -> Result<(), String> {
+    Err(DISABLED.to_string())
+}
+
+/// Après l'enregistrement d'une base synchronisée : l'enregistre dans un commit et l'envoie
+#[cfg(not(feature = "git-sync"))]
+pub fn after_save(_vault: &Path) {}
+
+/// Indique si la base est synchronisée avec un dépôt git
+#[cfg(not(feature = "git-sync"))]
+pub fn is_synced(_vault: &Path) -> bool {
+    false
+}
+
+/// Active la synchronisation d'une base avec un dépôt git distant
+#[cfg(not(feature = "git-sync"))]
+pub fn cmd_sync_init(_path: &Path, _remote: &str) -> Result<(), String> {
+    Err(DISABLED.to_string())
+}
+
+/// Synchronise une base : récupère les versions des autres appareils, puis envoie la version locale
+#[cfg(not(feature = "git-sync"))]
+pub fn cmd_sync(_path: &Path) -> Result<(), String> {
+    Err(DISABLED.to_string())
+}
+
+/// Désactive la synchronisation d'une base
+#[cfg(not(feature = "git-sync"))]
+pub fn cmd_sync_disable(_path: &Path) -> Result<(), String> {
+    Err(DISABLED.to_string())
+}
+
+#[cfg(feature = "git-sync")]
+pub use repo::{after_save, before_open, cmd_sync, cmd_sync_disable, cmd_sync_init, is_synced, merge_diverged};
+
+#[cfg(feature = "git-sync")]
+mod repo {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+    
+    use crate::crypto::signing::signature_path;
+    use crate::events::{self, SecurityEvent};
+    use crate::models::device;
+    use crate::models::id::EntryId;
+    use crate::utils::paths;
+    use crate::utils::symbols::Symbol;
+    use crate::cli::profiles;
+    use crate::cli::session::Session;
+    use crate::cli::signing;
+    use crate::cli::sync;
+    
+    /// Clé de configuration git (du dépôt) listant les bases synchronisées, relatives à sa racine
+    const SYNC_KEY: &str = "keeprust.vault";
+    
+    /// Dépôt distant des bases synchronisées
+    const REMOTE: &str = "origin";
+    
+    /// Résultat de la récupération des versions des autres appareils
+    #[derive(Debug, PartialEq)]
+    pub enum Pull {
+        /// Rien de nouveau sur le dépôt distant
+        UpToDate,
+        
+        /// Versions distantes appliquées (avance rapide ou rebasage)
+        Updated,
+        
+        /// La base a été modifiée des deux côtés : à fusionner avec la version de cette référence
+        Diverged(String),
+    }
+    
+    /// Exécute une commande git dans le dépôt et retourne sa sortie
+    fn git(root: &Path, args: &[&str]) -> Result<String, String> {
+        let output = Command::new("git").arg("-C").arg(root).args(args).output()
+            .map_err(|e| format!("git introuvable: {}", e))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = stderr.lines().next().unwrap_or_default().trim().to_string();
+            return Err(format!("git {}: {}", args.first().copied().unwrap_or_default(), message));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+    
+    /// Exécute une commande git dont seul le succès importe (vérifications)
+    fn git_succeeds(root: &Path, args: &[&str]) -> bool {
+        Command::new("git").arg("-C").arg(root).args(args).output().is_ok_and(|output| output.status.success())
+    }
+    
+    /// Base synchronisée : dépôt git qui la contient et chemin du fichier dans le dépôt
+    struct SyncRepo {
+        root: PathBuf,
+        
+        /// Chemin de la base relatif à la racine du dépôt, séparé par des '/'
+        file: String,
+    }
+    
+    impl SyncRepo {
+        /// Dépôt git contenant la base, qu'elle y soit synchronisée ou non
+        fn enclosing(vault: &Path) -> Option<Self> {
+            let vault = profiles::absolute(vault);
+            let root = vault.parent()?.ancestors().find(|directory| directory.join(".git").exists())?.to_path_buf();
+            let file = vault.strip_prefix(&root).ok()?
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            Some(Self { root, file })
+        }
+        
+        /// Dépôt de la base si sa synchronisation est activée ('keeprust sync init')
+        fn find(vault: &Path) -> Option<Self> {
+            Self::enclosing(vault).filter(Self::is_registered)
+        }
+        
+        fn is_registered(&self) -> bool {
+            git(&self.root, &["config", "--get-all", SYNC_KEY]).is_ok_and(|vaults| vaults.lines().any(|line| line == self.file))
+        }
+        
+        fn git(&self, args: &[&str]) -> Result<String, String> {
+            git(&self.root, args)
+        }
+        
+        /// Base et, si elle existe, sa signature détachée
+        fn tracked_files(&self) -> Vec<String> {
+            let signature = signature_path(Path::new(&self.file)).to_string_lossy().into_owned();
+            let mut files = vec![self.file.clone()];
+            if self.root.join(&signature).exists() {
+                files.push(signature);
+            }
+            files
+        }
+        
+        fn branch(&self) -> Result<String, String> {
+            self.git(&["symbolic-ref", "--short", "HEAD"])
+        }
+        
+        fn has_commits(&self) -> bool {
+            git_succeeds(&self.root, &["rev-parse", "--verify", "--quiet", "HEAD"])
+        }
+        
+        /// Identité des commits : celle de l'utilisateur, sinon keeprust et le nom de l'appareil
+        fn identity(&self) -> Vec<String> {
+            if git_succeeds(&self.root, &["config", "user.email"]) {
+                return Vec::new();
+            }
+            let name = device::current().map(|device| device.name).unwrap_or_else(|| "keeprust".to_string());
+            vec!["-c".to_string(), format!("user.name=keeprust ({})", name), "-c".to_string(), "user.email=keeprust@localhost".to_string()]
+        }
+        
+        /// Git avec l'identité des commits
+        fn git_as_author(&self, args: &[&str]) -> Result<String, String> {
+            let identity = self.identity();
+            let mut all: Vec<&str> = identity.iter().map(String::as_str).collect();
+            all.extend_from_slice(args);
+            self.git(&all)
+        }
+        
+        /// Enregistre dans un commit la base telle qu'elle est sur le disque; retourne false si elle
+        /// n'a pas changé depuis le dernier commit
+        fn commit_pending(&self) -> Result<bool, String> {
+            if !self.root.join(&self.file).exists() {
+                return Ok(false);
+            }
+            let files = self.tracked_files();
+            let mut add = vec!["add", "--"];
+            add.extend(files.iter().map(String::as_str));
+            self.git(&add)?;
+            
+            let mut staged = vec!["diff", "--cached", "--quiet", "--"];
+            staged.extend(files.iter().map(String::as_str));
+            if self.has_commits() && git_succeeds(&self.root, &staged) {
+                return Ok(false);
+            }
+            
+            let device = device::current().map(|device| format!(" depuis {}", device.name)).unwrap_or_default();
+            let message = format!("keeprust: {} enregistrée{}", self.file, device);
+            let mut commit = vec!["commit", "--quiet", "-m", &message, "--"];
+            commit.extend(files.iter().map(String::as_str));
+            self.git_as_author(&commit)?;
+            Ok(true)
+        }
+        
+        /// Référence distante de la branche courante, si le dépôt distant l'a déjà
+        fn upstream(&self) -> Result<Option<String>, String> {
+            let upstream = format!("{}/{}", REMOTE, self.branch()?);
+            Ok(git_succeeds(&self.root, &["rev-parse", "--verify", "--quiet", &upstream]).then_some(upstream))
+        }
+        
+        /// Récupère les versions des autres appareils et les applique si la base n'a pas divergé
+        fn pull(&self) -> Result<Pull, String> {
+            self.commit_pending()?;
+            self.git(&["fetch", "--quiet", REMOTE])?;
+            let Some(upstream) = self.upstream()? else {
+                return Ok(Pull::UpToDate);
+            };
+            
+            if !self.has_commits() {
+                self.git(&["merge", "--quiet", "--ff-only", &upstream])?;
+                return Ok(Pull::Updated);
+            }
+            let count = |range: String| -> Result<usize, String> {
+                self.git(&["rev-list", "--count", &range])?.parse().map_err(|_| "git rev-list: nombre de commits illisible".to_string())
+            };
+            let behind = count(format!("HEAD..{}", upstream))?;
+            let ahead = count(format!("{}..HEAD", upstream))?;
+            
+            match (behind, ahead) {
+                (0, _) => Ok(Pull::UpToDate),
+                (_, 0) => {
+                    self.git(&["merge", "--quiet", "--ff-only", &upstream])?;
+                    Ok(Pull::Updated)
+                }
+                // Le rebasage ne réussit que si la base elle-même n'a pas changé des deux côtés
+                _ => match self.git_as_author(&["rebase", "--quiet", &upstream]) {
+                    Ok(_) => Ok(Pull::Updated),
+                    Err(_) => {
+                        let _ = self.git(&["rebase", "--abort"]);
+                        Ok(Pull::Diverged(upstream))
+                    }
+                },
+            }
+        }
+        
+        fn push(&self) -> Result<(), String> {
+            if !self.has_commits() {
+                return Ok(());
+            }
+            self.git(&["push", "--quiet", "--set-upstream", REMOTE, &self.branch()?]).map(|_| ())
+        }
+    }
+    
+    /// Version distante d'une base (et de sa signature), extraite dans un fichier temporaire le
+    /// temps de la fusion
+    struct RemoteCopy {
+        path: PathBuf,
+    }
+    
+    impl RemoteCopy {
+        fn extract(repo: &SyncRepo, upstream: &str) -> Result<Self, String> {
+            let copy = Self { path: std::env::temp_dir().join(format!("keeprust-sync-{}.krs", EntryId::new().short())) };
+            for (file, path) in [(repo.file.clone(), copy.path.clone()), (signature_path(Path::new(&repo.file)).to_string_lossy().into_owned(), signature_path(&copy.path))] {
+                let output = Command::new("git").arg("-C").arg(&repo.root).arg("show").arg(format!("{}:{}", upstream, file)).output()
+                    .map_err(|e| format!("git introuvable: {}", e))?;
+                // La signature est facultative; la base, non
+                if output.status.success() {
+                    fs::write(&path, &output.stdout).map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+                } else if file == repo.file {
+                    return Err(format!("Le dépôt distant ne contient pas {} ({}).", repo.file, upstream));
+                }
+            }
+            Ok(copy)
+        }
+    }
+    
+    impl Drop for RemoteCopy {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.path);
+            let _ = fs::remove_file(signature_path(&self.path));
+        }
+    }
+    
+    /// Avant l'ouverture d'une base synchronisée : récupère les versions des autres appareils.
+    ///
+    /// Retourne la référence distante à fusionner si la base a été modifiée des deux côtés; un
+    /// dépôt distant injoignable est signalé sans empêcher l'ouverture.
+    pub fn before_open(vault: &Path) -> Option<String> {
+        let repo = SyncRepo::find(vault)?;
+        match repo.pull() {
+            Ok(Pull::UpToDate) => None,
+            Ok(Pull::Updated) => {
+                eprintln!("Synchronisation: version de {} récupérée depuis le dépôt distant.", repo.file);
+                None
+            }
+            Ok(Pull::Diverged(upstream)) => Some(upstream),
+            Err(e) => {
+                eprintln!("{}Synchronisation git impossible, ouverture de la version locale: {}", Symbol::Warning, e);
+                None
+            }
+        }
+    }
+    
+    /// Fusionne la version distante d'une base modifiée des deux côtés dans la session, avec la
+    /// machinerie de `keeprust merge` (copies de conflit), puis enregistre et envoie le résultat
+    pub fn merge_diverged(session: &mut Session, upstream: &str) -> Result<(), String> {
+        let path = session.path().to_path_buf();
+        let repo = SyncRepo::find(&path).ok_or_else(|| format!("{} n'est plus synchronisée.", path.display()))?;
+        let copy = RemoteCopy::extract(&repo, upstream)?;
+        signing::verify_copy(&path, &copy.path)?;
+        
+        let remote = session.open_other(&copy.path)?;
+        let report = session.db.merge(&remote.db);
+        drop(remote);
+        drop(copy);
+        
+        // L'historique git retient les deux branches; le contenu est celui de la fusion ci-dessous
+        repo.git_as_author(&["merge", "--quiet", "--no-edit", "-s", "ours", "--allow-unrelated-histories", upstream])?;
+        if report.groups_added == 0 && report.added == 0 && report.updated == 0 && report.conflicts.is_empty() {
+            repo.push()?;
+        } else {
+            session.save()?;
+        }
+        
+        eprintln!(
+            "Synchronisation: {} modifiée des deux côtés, versions fusionnées ({} groupe(s) et {} entrée(s) ajouté(s), {} entrée(s) mise(s) à jour).",
+            repo.file, report.groups_added, report.added, report.updated
+        );
+        if !report.conflicts.is_empty() {
+            eprintln!("{}{} conflit(s) entre les versions des deux appareils.", Symbol::Warning, report.conflicts.len());
+            events::emit(&path, &SecurityEvent::SyncConflict { conflict_path: path.clone() });
+            sync::offer_resolution(session, &report.conflicts)?;
+        }
+        Ok(())
+    }
+    
+    /// Après l'enregistrement d'une base synchronisée : l'enregistre dans un commit et l'envoie; un
+    /// échec est signalé, l'envoi sera retenté à la prochaine synchronisation
+    pub fn after_save(vault: &Path) {
+        let Some(repo) = SyncRepo::find(vault) else {
+            return;
+        };
+        if let Err(e) = repo.commit_pending().and_then(|_| repo.push()) {
+            eprintln!("{}Synchronisation git: {} (modifications envoyées à la prochaine synchronisation: 'keeprust sync').", Symbol::Warning, e);
+        }
+    }
+    
+    /// Indique si la base est synchronisée avec un dépôt git
+    pub fn is_synced(vault: &Path) -> bool {
+        SyncRepo::find(vault).is_some()
+    }
+    
+    /// Active la synchronisation d'une base avec un dépôt git distant : le dépôt local est créé dans
+    /// son répertoire s'il n'est pas déjà dans un dépôt. Une base absente est récupérée sur le dépôt
+    /// distant (appareil supplémentaire).
+    pub fn cmd_sync_init(path: &Path, remote: &str) -> Result<(), String> {
+        let vault = profiles::absolute(path);
+        let directory = vault.parent().ok_or_else(|| format!("Répertoire de {} introuvable.", path.display()))?;
+        paths::create_private_dir(directory).map_err(|e| format!("Impossible de créer {}: {}", directory.display(), e))?;
+        
+        let repo = match SyncRepo::enclosing(&vault) {
+            Some(repo) => repo,
+            None => {
+                git(directory, &["init", "--quiet"])?;
+                SyncRepo::enclosing(&vault).ok_or("Dépôt git introuvable après sa création.")?
+            }
+        };
+        match repo.git(&["remote", "get-url", REMOTE]) {
+            Ok(url) if url == remote => {}
+            Ok(_) => { repo.git(&["remote", "set-url", REMOTE, remote])?; }
+            Err(_) => { repo.git(&["remote", "add", REMOTE, remote])?; }
+        }
+        
+        // Dépôt local vide : suivre la branche par défaut du dépôt distant
+        if !repo.has_commits()
+            && let Ok(head) = repo.git(&["ls-remote", "--symref", REMOTE, "HEAD"])
+            && let Some(branch) = head.lines().find_map(|line| line.strip_prefix("ref: refs/heads/")?.split_whitespace().next())
+        {
+            repo.git(&["symbolic-ref", "HEAD", &format!("refs/heads/{}", branch)])?;
+        }
+        if !repo.is_registered() {
+            repo.git(&["config", "--add", SYNC_KEY, &repo.file])?;
+        }
+        println!("Synchronisation de {} avec {} activée.", repo.file, remote);
+        cmd_sync(path)
+    }
+    
+    /// Synchronise une base : récupère et fusionne les versions des autres appareils (à
+    /// l'ouverture), puis envoie la version locale
+    pub fn cmd_sync(path: &Path) -> Result<(), String> {
+        let repo = SyncRepo::find(path)
+            .ok_or_else(|| format!("{} n'est pas synchronisée: activez la synchronisation avec 'keeprust sync init DÉPÔT'.", path.display()))?;
+        let session = Session::open(path)?;
+        repo.commit_pending()?;
+        repo.push()?;
+        
+        println!("{} synchronisée avec {} (branche {}).", path.display(), repo.git(&["remote", "get-url", REMOTE])?, repo.branch()?);
+        session.close()
+    }
+    
+    /// Désactive la synchronisation d'une base; le dépôt git et son historique sont conservés
+    pub fn cmd_sync_disable(path: &Path) -> Result<(), String> {
+        let repo = SyncRepo::find(path).ok_or_else(|| format!("{} n'est pas synchronisée.", path.display()))?;
+        repo.git(&["config", "--fixed-value", "--unset", SYNC_KEY, &repo.file])?;
+        println!("Synchronisation de {} désactivée (le dépôt git {} est conservé).", repo.file, repo.root.display());
+        Ok(())
+    }
+    
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        
+        #[test]
+        fn test_pull_fast_forwards_and_detects_divergence() {
+            let directory = std::env::temp_dir().join(format!("keeprust-git-sync-{}", EntryId::new().short()));
+            let remote = directory.join("distant.git");
+            fs::create_dir_all(&remote).unwrap();
+            git(&remote, &["init", "--quiet", "--bare"]).unwrap();
+            
+            // Deux appareils synchronisant la même base
+            let setup = |name: &str| {
+                let root = directory.join(name);
+                fs::create_dir_all(&root).unwrap();
+                git(&root, &["init", "--quiet"]).unwrap();
+                git(&root, &["symbolic-ref", "HEAD", "refs/heads/main"]).unwrap();
+                git(&root, &["remote", "add", REMOTE, remote.to_str().unwrap()]).unwrap();
+                git(&root, &["config", SYNC_KEY, "coffre.krs"]).unwrap();
+                SyncRepo::find(&root.join("coffre.krs")).unwrap()
+            };
+            let portable = setup("portable");
+            let bureau = setup("bureau");
+            assert!(SyncRepo::find(&directory.join("portable").join("autre.krs")).is_none());
+            
+            fs::write(portable.root.join("coffre.krs"), b"version 1").unwrap();
+            assert!(portable.commit_pending().unwrap());
+            assert!(!portable.commit_pending().unwrap());
+            portable.push().unwrap();
+            
+            assert_eq!(bureau.pull().unwrap(), Pull::Updated);
+            assert_eq!(fs::read(bureau.root.join("coffre.krs")).unwrap(), b"version 1");
+            assert_eq!(bureau.pull().unwrap(), Pull::UpToDate);
+            
+            // Modifiée des deux côtés : le rebasage échoue, la fusion revient à keeprust
+            fs::write(portable.root.join("coffre.krs"), b"version portable").unwrap();
+            assert!(portable.commit_pending().unwrap());
+            portable.push().unwrap();
+            fs::write(bureau.root.join("coffre.krs"), b"version bureau").unwrap();
+            assert_eq!(bureau.pull().unwrap(), Pull::Diverged("origin/main".to_string()));
+            assert_eq!(fs::read(bureau.root.join("coffre.krs")).unwrap(), b"version bureau");
+            
+            let copy = RemoteCopy::extract(&bureau, "origin/main").unwrap();
+            assert_eq!(fs::read(&copy.path).unwrap(), b"version portable");
+            let copy_path = copy.path.clone();
+            drop(copy);
+            assert!(!copy_path.exists());
+            
+            fs::remove_dir_all(&directory).unwrap();
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use crate::models::database::Database;
+use crate::models::id::GroupId;
+use crate::models::walker::WalkControl;
+
+use super::session;
+
+/// Groupe proposé dans le sélecteur
+#[derive(Debug, Clone, PartialEq)]
+struct GroupChoice {
+    id: GroupId,
+    
+    /// Noms des groupes depuis la racine, groupe inclus
+    path: Vec<String>,
+}
+
+impl GroupChoice {
+    /// Ligne du sélecteur : numéro, puis nom indenté selon la profondeur
+    fn line(&self, number: usize) -> String {
+        let name = self.path.last().map(String::as_str).unwrap_or_default();
+        format!("  {:>3}. {}{}", number, "  ".repeat(self.path.len().saturating_sub(1)), name)
+    }
+}
+
+/// Groupes pouvant recevoir une entrée, dans l'ordre de l'arborescence (corbeille exclue)
+fn group_choices(db: &Database) -> Vec<GroupChoice> {
+    let trash = db.metadata.trash_group_id;
+    let mut choices = Vec::new();
+    db.walk(|path, group, entry| {
+        if entry.is_some() {
+            return WalkControl::Continue;
+        }
+        if Some(group.id) == trash {
+            return WalkControl::SkipChildren;
+        }
+        choices.push(GroupChoice { id: group.id, path: path.to_vec() });
+        WalkControl::Continue
+    });
+    choices
+}
+
+/// Indices des groupes dont le chemin contient le texte recherché (sans tenir compte de la casse)
+fn matching(choices: &[GroupChoice], text: &str) -> Vec<usize> {
+    let text = text.to_lowercase();
+    choices.iter().enumerate()
+        .filter(|(_, choice)| choice.path.join("/").to_lowercase().contains(&text))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+
+/// Demande dans le terminal le groupe de destination d'une nouvelle entrée : un numéro choisit
+/// le groupe, un texte restreint la liste aux groupes dont le chemin le contient (et choisit le
+/// groupe s'il est le seul), une réponse vide retient le groupe racine
+pub fn pick_group(db: &Database) -> Result<GroupId, String> {
+    let choices = group_choices(db);
+    
+    println!("\nGroupe de destination:");
+    for (index, choice) in choices.iter().enumerate() {
+        println!("{}", choice.line(index + 1));
+    }
+    loop {
+        let answer = session::prompt_line("Numéro ou texte à rechercher (Entrée pour la racine): ")?;
+        if answer.is_empty() {
+            return Ok(db.root_group.id);
+        }
+        
+        if let Ok(number) = answer.parse::<usize>() {
+            match number.checked_sub(1).and_then(|index| choices.get(index)) {
+                Some(choice) => return Ok(choice.id),
+                None => {
+                    println!("Aucun groupe n°{}.", number);
+                    continue;
+                }
+            }
+        }
+        
+        match matching(&choices, &answer)[..] {
+            [] => println!("Aucun groupe ne contient '{}'.", answer),
+            [index] => {
+                println!("Groupe: {}", choices[index].path.join("/"));
+                return Ok(choices[index].id);
+            }
+            // Chemins complets : des groupes de même nom se distinguent par leurs parents
+            ref shown => {
+                for &index in shown {
+                    println!("  {:>3}. {}", index + 1, choices[index].path.join("/"));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_group_choices_and_search() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let work = db.add_group(None, "Work".to_string()).unwrap();
+        db.add_group(Some(&work), "Servers".to_string()).unwrap();
+        db.add_group(None, "Perso".to_string()).unwrap();
+        
+        let choices = group_choices(&db);
+        let paths: Vec<String> = choices.iter().map(|choice| choice.path.join("/")).collect();
+        assert_eq!(paths, vec!["Root", "Root/Perso", "Root/Work", "Root/Work/Servers"]);
+        assert_eq!(choices[3].line(4), "    4.     Servers");
+        
+        assert_eq!(matching(&choices, "work"), vec![2, 3]);
+        assert_eq!(matching(&choices, "SERV"), vec![3]);
+        assert!(matching(&choices, "banque").is_empty());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,375 @@
+use clap::CommandFactory;
+use std::fs;
+use std::path::Path;
+
+use crate::crypto::cipher::NONCE_LEN;
+use crate::crypto::key_derivation::{DEFAULT_ARGON2_ITERATIONS, DEFAULT_ARGON2_MEMORY_KIB, DEFAULT_ARGON2_PARALLELISM};
+use crate::events;
+use crate::models::chunks::ENTRIES_PER_CHUNK;
+use crate::models::limits::{MAX_FIELD_SIZE, MAX_NOTES_SIZE};
+use crate::models::repository::{BACKUP_SUFFIX, FILE_VERSION, FILE_VERSION_V1, FILE_VERSION_V2, FILE_VERSION_V3, FILE_VERSION_V4, MIN_ITERATIONS};
+
+use super::app::Cli;
+use super::policy::policy_path;
+
+/// Guide intégré consultable avec `keeprust help <sujet>`
+pub struct HelpTopic {
+    /// Nom du sujet
+    pub name: &'static str,
+    
+    /// Résumé affiché dans la liste des sujets
+    pub summary: &'static str,
+    
+    /// Contenu du guide (construit à partir des constantes réelles du programme)
+    pub body: fn() -> String,
+}
+
+/// Sujets d'aide disponibles
+pub const TOPICS: &[HelpTopic] = &[
+    HelpTopic { name: "security", summary: "Modèle de sécurité et limites", body: security },
+    HelpTopic { name: "format", summary: "Format du fichier de base de données", body: format },
+    HelpTopic { name: "sync", summary: "Synchroniser une base entre plusieurs appareils", body: sync },
+    HelpTopic { name: "hooks", summary: "Hooks sur événements de sécurité et scripts d'enregistrement", body: hooks },
+    HelpTopic { name: "policy", summary: "Politique commune à plusieurs bases (policy.toml)", body: policy },
+    HelpTopic { name: "scripts", summary: "Scripts Rhai (keeprust script run)", body: scripts },
+    HelpTopic { name: "plugins", summary: "Sous-commandes externes keeprust-<nom>", body: plugins },
+];
+
+/// Recherche un sujet d'aide par son nom
+pub fn find_topic(name: &str) -> Option<&'static HelpTopic> {
+    TOPICS.iter().find(|topic| topic.name.eq_ignore_ascii_case(name))
+}
+
+/// Affiche un guide, l'aide d'une commande, ou l'aide générale suivie de la liste des sujets
+pub fn cmd_help(topic: Option<&str>) -> Result<(), String> {
+    let Some(name) = topic else {
+        Cli::command().print_long_help().map_err(|e| e.to_string())?;
+        println!("\nSujets d'aide (keeprust help <sujet>):");
+        for topic in TOPICS {
+            println!("  {:<10} {}", topic.name, topic.summary);
+        }
+        return Ok(());
+    };
+    
+    if let Some(topic) = find_topic(name) {
+        println!("{}", (topic.body)());
+        return Ok(());
+    }
+    
+    let mut command = Cli::command();
+    match command.find_subcommand_mut(name) {
+        Some(subcommand) => {
+            let mut subcommand = subcommand.clone().bin_name(format!("keeprust {}", name));
+            subcommand.print_long_help().map_err(|e| e.to_string())
+        }
+        None => Err(format!(
+            "Sujet ou commande inconnu: '{}'. Sujets disponibles: {}.",
+            name,
+            TOPICS.iter().map(|topic| topic.name).collect::<Vec<_>>().join(", ")
+        )),
+    }
+}
+
+/// Génère les pages de manuel : une par commande (section 1) et une par sujet d'aide (section 7)
+pub fn cmd_manpages(out: &Path) -> Result<(), String> {
+    fs::create_dir_all(out).map_err(|e| e.to_string())?;
+    
+    clap_mangen::generate_to(Cli::command(), out).map_err(|e| e.to_string())?;
+    
+    for topic in TOPICS {
+        let page = out.join(format!("keeprust-{}.7", topic.name));
+        fs::write(&page, topic_manpage(topic)).map_err(|e| e.to_string())?;
+    }
+    
+    println!("Pages de manuel générées dans {}", out.display());
+    Ok(())
+}
+
+/// Convertit un guide en page de manuel (roff)
+fn topic_manpage(topic: &HelpTopic) -> String {
+    let mut page = format!(
+        ".TH KEEPRUST-{} 7 \"\" \"keeprust {}\"\n.SH NAME\nkeeprust-{} \\- {}\n.SH DESCRIPTION\n",
+        topic.name.to_uppercase(),
+        env!("CARGO_PKG_VERSION"),
+        topic.name,
+        topic.summary
+    );
+    
+    for line in (topic.body)().lines() {
+        if line.trim().is_empty() {
+            page.push_str(".PP\n");
+            continue;
+        }
+        
+        // Échapper les barres obliques inverses et les lignes interprétées comme des requêtes roff
+        let line = line.replace('\\', "\\\\");
+        if line.starts_with('.') || line.starts_with('\'') {
+            page.push_str("\\&");
+        }
+        page.push_str(&line);
+        page.push_str("\n.br\n");
+    }
+    page
+}
+
+fn security() -> String {
+    format!("\
+MODÈLE DE SÉCURITÉ
+
+La base de données est un fichier unique chiffré avec AES-256-GCM.
+La clé est dérivée du mot de passe maître avec Argon2id ({memory} Mio, {passes} passes, {lanes} voies par défaut,
+réglables avec 'keeprust upgrade-kdf') et un sel aléatoire renouvelé à chaque enregistrement.
+Le mot de passe maître n'est jamais stocké. Les fichiers plus anciens, dérivés avec PBKDF2-HMAC-SHA256,
+restent lisibles et passent à Argon2id au prochain enregistrement. 'keeprust new --kdf scrypt' crée une base
+dérivée avec scrypt (N = 2^17, r = 8, p = 1), pour rester compatible avec les outils qui l'utilisent;
+'keeprust kdf-benchmark' mesure le temps de dérivation de chaque algorithme sur cette machine.
+Avec --keyfile, l'empreinte SHA-256 d'un fichier clé ('keeprust keygen-keyfile') est ajoutée au mot de passe
+avant la dérivation : la base ne s'ouvre qu'avec les deux. Le fichier clé n'est enregistré nulle part.
+
+Chaque bloc du fichier est authentifié : toute modification du fichier est détectée à l'ouverture.
+Un fichier dont l'en-tête annonce moins de {min_iterations} itérations PBKDF2 (ancienne version, fichier modifié)
+est signalé à l'ouverture : 'keeprust upgrade-kdf' le réenregistre avec les paramètres recommandés.
+Le plancher se règle avec --min-iterations ou la variable KEEPRUST_MIN_ITERATIONS.
+'keeprust signing init' ajoute une signature Ed25519 détachée (coffre.krs.sig), vérifiée avant la saisie du
+mot de passe : un fichier remplacé sur un stockage partagé est refusé, même par quelqu'un qui ne peut pas le
+déchiffrer. La clé privée reste dans signing.toml; 'signing trust' accepte la clé d'un autre appareil.
+'keeprust index enable' crée un index de recherche (coffre.krs.idx) chiffré avec une clé dérivée du mot de passe
+maître : titres, identifiants, URL, notes et étiquettes, jamais les mots de passe. Les notes y sont recopiées
+en entier : un secret noté dans une entrée se retrouve aussi dans l'index, à protéger comme la base elle-même.
+'search' s'en sert tant qu'il correspond au fichier de la base; chaque enregistrement le met à jour.
+Le mot de passe maître est effacé de la mémoire à la fin de chaque commande; 'keeprust shell' le conserve
+entre ses commandes et l'efface après --lock-after minutes d'inactivité (5 par défaut) ou à la commande 'lock'.
+'keeprust tui' garde la base déverrouillée jusqu'à sa fermeture ou jusqu'à la touche v, qui l'efface de la
+mémoire et redemande le mot de passe maître; chaque modification y est enregistrée aussitôt.
+'keeprust agent' garde en mémoire verrouillée (hors du swap) la clé dérivée des bases déverrouillées avec
+'vaults unlock', jamais le mot de passe : les commandes qui les ouvrent ne le demandent plus et ne refont pas la
+dérivation de clé; il n'est redemandé que pour changer les paramètres de dérivation ou créer l'index de recherche.
+Chaque base se verrouille après son propre délai (lock_after de son profil dans profiles.toml, sinon --lock-after
+de l'agent) et toutes se verrouillent à la mise en veille; 'keeprust lock' (ou 'vaults lock --all') les verrouille
+avant de quitter son poste. Le socket de l'agent n'est accessible qu'à votre compte, dont l'identité est vérifiée
+à chaque connexion.
+'keeprust service' fournit la base aux applications (libsecret) sur le bus de session : tout processus de votre
+session peut lire les mots de passe tant qu'elle est déverrouillée. Elle se verrouille après --lock-after minutes
+sans requête (15 par défaut); chaque secret transmis est enregistré comme une exposition.
+'keeprust proxy' sert l'extension keepassxc-browser (voir --manifest); une extension n'est associée que pendant
+les 2 minutes suivant 'proxy --pair NOM'. Le navigateur ne fournit pas de terminal : le mot de passe maître vient
+de --password-file ou de KEEPRUST_PASSWORD.
+'service --scope GROUPE' et 'proxy --pair NOM --scope GROUPE' limitent le fournisseur ou l'extension à ces groupes
+et à leurs sous-groupes : les autres entrées ne sont pas proposées, et une demande qui en désigne une est refusée
+et déclenche l'événement access-denied. L'agent transmet la clé de la base, qui l'ouvre entièrement : il
+n'a pas de portée.
+
+Les mots de passe copiés avec 'copy' sont retirés du presse-papiers après le délai --timeout : son contenu précédent
+est rétabli, sauf si une autre copie a eu lieu entre-temps. La commande attend la fin du délai en affichant le temps
+restant; Ctrl+C rétablit le presse-papiers aussitôt.
+'keeprust autotype' tape l'identifiant dans la fenêtre qui a le focus à la fin du compte à rebours (--delay) :
+vérifiez qu'il s'agit bien du formulaire de connexion attendu.
+Les hooks, plugins et scripts ne reçoivent jamais les mots de passe des entrées.
+'keeprust beam' transmet un identifiant à un téléphone par un lien HTTPS à usage unique (certificat autosigné
+créé pour l'occasion, empreinte affichée), valable --timeout secondes; le serveur s'arrête après l'envoi.
+
+LIMITES
+
+keeprust ne protège pas contre un système déjà compromis (enregistreur de frappe, accès à la mémoire).
+Un mot de passe maître faible reste le principal risque : la dérivation ralentit les attaques,
+elle ne les empêche pas.
+Vérifiez le binaire avec 'keeprust selftest' après une mise à jour.",
+        memory = DEFAULT_ARGON2_MEMORY_KIB / 1024,
+        passes = DEFAULT_ARGON2_ITERATIONS,
+        lanes = DEFAULT_ARGON2_PARALLELISM,
+        min_iterations = MIN_ITERATIONS
+    )
+}
+
+fn format() -> String {
+    format!("\
+FORMAT DU FICHIER (version {version})
+
+  KEEPRUST        signature (8 octets)
+  version         1 octet
+  longueur        2 octets (big-endian), longueur de l'en-tête
+  en-tête         format de sérialisation (1 octet: 0 json, 1 cbor, 2 msgpack)
+                  algorithme de dérivation (1 octet: 1 PBKDF2, 2 Argon2id, 3 scrypt) puis ses paramètres (4 octets chacun)
+                  longueur du sel (1 octet) puis le sel
+  nombre de blocs 4 octets (big-endian)
+  blocs           pour chaque bloc: nonce ({nonce} octets), longueur (4 octets), données chiffrées + tag
+
+Le bloc 0 contient les métadonnées et l'arborescence des groupes, sans les entrées.
+Les blocs suivants contiennent au plus {per_chunk} entrées chacun.
+Chaque bloc est chiffré indépendamment, avec le début du fichier (signature, version et en-tête),
+son numéro et le nombre total de blocs comme données authentifiées : un en-tête modifié (sel,
+paramètres de dérivation abaissés) est refusé, et un bloc endommagé n'empêche pas de récupérer
+les autres ('keeprust recover').
+
+Les fichiers de version {v1} (un seul bloc chiffré), {v2} (clé dérivée avec PBKDF2), {v3} (en-tête JSON)
+et {v4} (en-tête non authentifié) restent lisibles et sont convertis au prochain enregistrement.
+
+L'enregistrement écrit un fichier temporaire puis le renomme : une interruption ne laisse jamais
+un fichier à moitié écrit. La version précédente est conservée dans <fichier>{backup}
+(utilisée par 'keeprust recover' et --as-of).
+
+'keeprust inspect <fichier>' décrit l'en-tête et les blocs d'un fichier reçu d'une source incertaine
+(ou d'une base KeePass KDBX) sans le déchiffrer, et signale les paramètres qui rendraient son ouverture
+dangereuse (mémoire Argon2 démesurée, blocs manquants).",
+        version = FILE_VERSION,
+        nonce = NONCE_LEN,
+        per_chunk = ENTRIES_PER_CHUNK,
+        v1 = FILE_VERSION_V1,
+        v2 = FILE_VERSION_V2,
+        v3 = FILE_VERSION_V3,
+        v4 = FILE_VERSION_V4,
+        backup = BACKUP_SUFFIX
+    )
+}
+
+fn sync() -> String {
+    "\
+SYNCHRONISATION
+
+keeprust ne synchronise pas lui-même les bases de données : le fichier .krs est autonome et chiffré,
+il peut être copié par n'importe quel outil (Syncthing, dossier partagé, dépôt git...).
+
+Pour synchroniser automatiquement à chaque enregistrement, configurez des scripts d'enregistrement
+(voir 'keeprust help hooks'), par exemple avec git:
+
+  [[save_hook]]
+  vault = \"/home/moi/coffre/keeprust.krs\"
+  pre = \"cd /home/moi/coffre && git pull --ff-only\"
+  post = \"cd /home/moi/coffre && git commit -qam sauvegarde && git push -q\"
+
+Un échec du script 'pre' annule l'enregistrement, ce qui évite d'écraser une version plus récente.
+
+Deux versions modifiées séparément (copie de conflit de Syncthing, fichier d'un autre appareil)
+se réunissent avec 'keeprust merge <autre.krs>'. Une entrée modifiée des deux côtés n'interrompt pas
+la fusion : l'autre version est ajoutée dans le même groupe, sous le nom
+\"Gmail (conflit depuis portable-x 2024-06-01)\", et l'événement sync-conflict est déclenché.
+'keeprust conflicts' liste les copies restant à résoudre.
+
+Si le fichier est endommagé après une synchronisation interrompue, utilisez 'keeprust recover'.".to_string()
+}
+
+fn hooks() -> String {
+    let path = events::hooks_path()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| "(aucun répertoire de configuration)".to_string());
+    
+    format!("\
+HOOKS
+
+Fichier de configuration: {path}
+(remplaçable par la variable d'environnement KEEPRUST_HOOKS)
+
+Hooks sur événements de sécurité:
+
+  [[hook]]
+  events = [\"failed-unlock\", \"audit-regression\"]   # tous les événements si absent
+  command = \"logger -t keeprust \\\"$KEEPRUST_MESSAGE\\\"\"
+  notify = true                                      # notification de bureau
+  webhook = \"https://exemple.org/keeprust\"           # POST JSON (via curl; fonctionnalité webhooks)
+
+Événements: failed-unlock, audit-regression, expiring-entries, breached-passwords, sync-conflict,
+access-denied (entrée refusée à 'service --scope' ou à une extension appairée avec --scope).
+'keeprust monitor' déclenche expiring-entries et breached-passwords pour chaque nouveau constat
+(les constats déjà signalés sont conservés dans monitor-state.json, à côté de ce fichier).
+Les commandes reçoivent KEEPRUST_EVENT, KEEPRUST_MESSAGE et KEEPRUST_VAULT.
+
+Scripts d'enregistrement:
+
+  [[save_hook]]
+  vault = \"keeprust.krs\"   # toutes les bases si absent
+  profile = \"travail\"      # profil de profiles.toml; tous les profils si absent
+  pre = \"...\"              # un échec annule l'enregistrement
+  post = \"...\"             # un échec est seulement signalé
+
+Le profil est celui choisi avec --profile, sinon le premier profil qui désigne la base : deux profils
+d'un même fichier peuvent ainsi avoir des scripts différents. Les scripts reçoivent KEEPRUST_EVENT
+(pre-save ou post-save), KEEPRUST_PROFILE et KEEPRUST_VAULT.")
+}
+
+fn policy() -> String {
+    let path = policy_path()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| "(aucun répertoire de configuration)".to_string());
+    
+    format!("\
+POLITIQUE
+
+Fichier de politique: {path}
+(un autre fichier, par exemple partagé dans un dépôt d'équipe, avec 'keeprust policy check --policy')
+
+  [kdf]
+  require_argon2id = true              # refuse les bases en PBKDF2 ou scrypt
+  min_pbkdf2_iterations = 600000
+  min_argon2_memory_kib = 65536
+  min_argon2_iterations = 3
+  
+  [entries]
+  min_entropy_bits = 60                # estimation d'après l'alphabet et la longueur
+  require_expiry_tags = [\"shared\"]     # date d'expiration obligatoire pour ces étiquettes
+  max_notes_kib = 16                   # notes plus volumineuses signalées (16 Kio par défaut)
+  max_field_kib = 4                    # idem pour la valeur d'un champ (4 Kio par défaut)
+  
+  [export]
+  forbidden_tags = [\"confidentiel\"]    # entrées jamais exportées en clair
+
+Toutes les règles sont facultatives. 'keeprust policy check' liste les écarts et se termine
+avec un code non nul si la base ne respecte pas la politique (utilisable en intégration continue).
+'keeprust export' exclut toujours les entrées portant une étiquette interdite par policy.toml.
+Sans max_notes_kib ni max_field_kib, les seuils par défaut ne donnent qu'un avertissement à l'ajout
+ou à la modification d'une entrée. Des notes de plus de {notes} Kio ou un champ de plus de {field} Kio
+sont refusés à l'ajout, à la modification et à l'import (les valeurs déjà enregistrées restent) :
+les contenus volumineux vont dans les pièces jointes ('keeprust attach').",
+        notes = MAX_NOTES_SIZE / 1024,
+        field = MAX_FIELD_SIZE / 1024)
+}
+
+fn scripts() -> String {
+    "\
+SCRIPTS
+
+  keeprust script run rapport.rhai [arguments...]
+
+Les scripts sont écrits en Rhai (https://rhai.rs) et s'exécutent sur la base déverrouillée.
+Fonctions disponibles:
+
+  entries()              toutes les entrées
+  search(requête)        entrées correspondant à une requête (syntaxe de 'list --query')
+  generate(longueur)     génère un mot de passe
+  update(id, #{...})     modifie title, username, url, notes ou tags
+
+Chaque entrée est un objet avec id, title, username, url, notes, tags, group, strength, score, archived,
+color, created_at, updated_at et expires_at. Les mots de passe ne sont jamais exposés aux scripts.
+Les arguments de la ligne de commande sont dans la constante ARGS.
+
+Les modifications demandées par update() sont listées à la fin du script et appliquées
+seulement après confirmation.".to_string()
+}
+
+fn plugins() -> String {
+    "\
+SOUS-COMMANDES EXTERNES
+
+Toute commande inconnue 'keeprust <nom>' exécute le programme 'keeprust-<nom>' trouvé dans le PATH,
+avec les arguments restants. Le programme reçoit:
+
+  KEEPRUST_DB    chemin de la base de données (option --database)
+  KEEPRUST_BIN   chemin de l'exécutable keeprust
+
+Les commandes externes disponibles sont listées lorsque keeprust est lancé sans base de données.".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_topic_manpages_escape_roff_requests() {
+        for topic in TOPICS {
+            let page = topic_manpage(topic);
+            assert!(page.starts_with(".TH KEEPRUST-"));
+            assert!(page.lines().filter(|line| line.starts_with('.')).all(|line| {
+                [".TH", ".SH", ".PP", ".br"].iter().any(|request| line.starts_with(request))
+            }));
+        }
+    }
+}
\ No newline at end of file
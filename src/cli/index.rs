@@ -0,0 +1,50 @@
+use std::fs;
+use std::path::Path;
+
+use crate::models::search_index::{self, SearchIndex};
+
+use super::session::{self, Session};
+
+/// Crée (ou reconstruit) l'index de recherche chiffré de la base
+pub fn cmd_index_enable(path: &Path) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    let update = session.enable_index()?;
+    println!("Index de recherche créé: {} ({} entrée(s)).", search_index::index_path(path).display(), update.added);
+    println!("Il est mis à jour à chaque enregistrement et utilisé par 'keeprust search'.");
+    println!("Il contient les notes des entrées (chiffrées avec une clé dérivée du mot de passe maître), pas les mots de passe.");
+    session.close()
+}
+
+/// Supprime l'index de recherche; les recherches déchiffrent de nouveau toute la base
+pub fn cmd_index_disable(path: &Path) -> Result<(), String> {
+    let index_path = search_index::index_path(path);
+    if !index_path.exists() {
+        println!("La base n'a pas d'index de recherche.");
+        return Ok(());
+    }
+    fs::remove_file(&index_path).map_err(|e| format!("Impossible de supprimer {}: {}", index_path.display(), e))?;
+    println!("Index de recherche supprimé: {}", index_path.display());
+    Ok(())
+}
+
+/// Indique si l'index de recherche existe et s'il correspond au fichier actuel de la base
+pub fn cmd_index_status(path: &Path) -> Result<(), String> {
+    let index_path = search_index::index_path(path);
+    let Ok(metadata) = fs::metadata(&index_path) else {
+        println!("Aucun index de recherche: activez-le avec 'keeprust index enable'.");
+        return Ok(());
+    };
+    
+    let password = session::master_secret(session::prompt_master_password("Mot de passe: ")?)?;
+    let (index, _) = SearchIndex::read(&index_path, &password).map_err(|e| format!("Index {}: {}", index_path.display(), e))?;
+    let fresh = search_index::vault_digest(path).is_ok_and(|digest| digest == index.vault_digest);
+    
+    println!("Index: {} ({} octets)", index_path.display(), metadata.len());
+    println!("Entrées indexées: {}", index.entries.len());
+    if fresh {
+        println!("État: à jour");
+    } else {
+        println!("État: périmé (base modifiée par une autre application); il sera mis à jour à la prochaine recherche.");
+    }
+    Ok(())
+}
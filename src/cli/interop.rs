@@ -0,0 +1,139 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use zeroize::Zeroize;
+
+use crate::models::export::{self, ExportFormat};
+use crate::models::exposure::{ExposureInterface, ExposureKind};
+use crate::models::id::{EntryId, GroupId};
+use crate::models::import::ImportFormat;
+use crate::models::structure::Structure;
+
+use super::policy;
+use super::session::{self, Session};
+
+/// Crée le fichier d'export, lisible par son seul propriétaire; un fichier existant n'est pas écrasé
+fn create_export_file(path: &Path) -> Result<std::fs::File, String> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path).map_err(|e| match e.kind() {
+        io::ErrorKind::AlreadyExists => format!("{} existe déjà; choisissez un autre fichier.", path.display()),
+        _ => format!("Impossible de créer {}: {}", path.display(), e),
+    })
+}
+
+/// Exporte les entrées (hors corbeille) en CSV ou en JSON.
+///
+/// Les mots de passe sont écrits en clair : l'export demande une confirmation explicite, sauf
+/// avec `no_passwords` qui n'exporte que les métadonnées. Les entrées dont une étiquette est
+/// interdite à l'export par policy.toml sont exclues.
+pub fn cmd_export(path: &Path, format: ExportFormat, group: Option<&GroupId>, output: Option<&Path>, no_passwords: bool) -> Result<(), String> {
+    let policy = policy::load(None)?;
+    let mut session = Session::open(path)?;
+    let mut records = session.db.export_records(group, !no_passwords).map_err(|e| e.to_string())?;
+    
+    // Les entrées portant une étiquette interdite par la politique ne quittent jamais la base
+    if let Some(policy) = &policy {
+        let count = records.len();
+        records.retain(|record| policy.forbidden_export_tag(&record.tags).is_none());
+        if records.len() < count {
+            eprintln!("{} entrée(s) exclue(s) par la politique (étiquettes: {}).", count - records.len(), policy.export.forbidden_tags.join(", "));
+        }
+    }
+    
+    if !no_passwords {
+        let destination = output.map(|output| output.display().to_string()).unwrap_or_else(|| "la sortie standard".to_string());
+        eprintln!("Attention: les {} mot(s) de passe seront écrits EN CLAIR dans {}.", records.len(), destination);
+        if !session::confirm("Exporter les mots de passe en clair?", false)? {
+            eprintln!("Export abandonné.");
+            return session.close();
+        }
+    }
+    
+    match output {
+        Some(output) => {
+            let mut file = io::BufWriter::new(create_export_file(output)?);
+            export::write_records(&records, format, &mut file).map_err(|e| e.to_string())?;
+            file.flush().map_err(|e| e.to_string())?;
+            eprintln!("{} entrée(s) exportée(s) dans {} ({}).", records.len(), output.display(), format);
+        }
+        None => {
+            let mut stdout = io::stdout().lock();
+            export::write_records(&records, format, &mut stdout).map_err(|e| e.to_string())?;
+            stdout.flush().map_err(|e| e.to_string())?;
+        }
+    }
+    if !no_passwords {
+        let ids: Vec<EntryId> = records.iter().map(|record| record.id).collect();
+        session.record_exposure(&ids, ExposureKind::Exported, ExposureInterface::Cli)?;
+    }
+    session.close()
+}
+
+/// Importe un fichier exporté par un autre gestionnaire (dossiers en groupes, éléments en entrées)
+/// et affiche le bilan : entrées importées, éléments ignorés et avertissements
+pub fn cmd_import(path: &Path, format: ImportFormat, file: &Path, group: Option<&GroupId>) -> Result<(), String> {
+    // Lire le fichier avant de demander le mot de passe maître
+    let mut content = fs::read_to_string(file).map_err(|e| format!("Impossible de lire {}: {}", file.display(), e))?;
+    
+    let mut session = Session::open(path)?;
+    let result = session.db.import(format, &content, group);
+    content.zeroize();
+    let report = result.map_err(|e| e.to_string())?;
+    
+    if report.imported > 0 || report.groups_created > 0 {
+        session.save()?;
+    }
+    println!("Import {} terminé:", format);
+    println!("  {} groupe(s) créé(s)", report.groups_created);
+    println!("  {} entrée(s) importée(s)", report.imported);
+    println!("  {} élément(s) ignoré(s)", report.skipped.len());
+    for skipped in &report.skipped {
+        println!("    - {}: {}", skipped.name, skipped.reason);
+    }
+    if !report.warnings.is_empty() {
+        println!("Avertissements:");
+        for warning in &report.warnings {
+            println!("  - {}", warning);
+        }
+    }
+    println!("\n{} contient vos mots de passe en clair : supprimez-le une fois l'import vérifié.", file.display());
+    session.close()
+}
+
+/// Exporte l'arborescence des groupes, sans aucune entrée, pour la reproduire dans d'autres bases
+pub fn cmd_export_structure(path: &Path, group: Option<&GroupId>, output: Option<&Path>) -> Result<(), String> {
+    let session = Session::open(path)?;
+    let structure = session.db.export_structure(group).map_err(|e| e.to_string())?;
+    let json = structure.to_json().map_err(|e| e.to_string())?;
+    
+    match output {
+        Some(output) => {
+            let mut file = create_export_file(output)?;
+            writeln!(file, "{}", json).map_err(|e| e.to_string())?;
+            eprintln!("{} groupe(s) exporté(s) dans {}.", structure.group_count(), output.display());
+        }
+        None => println!("{}", json),
+    }
+    session.close()
+}
+
+/// Crée dans la base les groupes d'une structure exportée qui n'y figurent pas encore
+pub fn cmd_import_structure(path: &Path, file: &Path, group: Option<&GroupId>) -> Result<(), String> {
+    // Lire et vérifier le fichier avant de demander le mot de passe maître
+    let content = fs::read_to_string(file).map_err(|e| format!("Impossible de lire {}: {}", file.display(), e))?;
+    let structure = Structure::from_json(&content).map_err(|e| format!("{}: {}", file.display(), e))?;
+    
+    let mut session = Session::open(path)?;
+    let report = session.db.import_structure(&structure, group).map_err(|e| e.to_string())?;
+    if report.created > 0 {
+        session.save()?;
+    }
+    println!("{} groupe(s) créé(s), {} déjà présent(s).", report.created, report.existing);
+    session.close()
+}
\ No newline at end of file
@@ -0,0 +1,197 @@
+use base64::{Engine as _, engine::general_purpose};
+use rand::RngCore;
+use rand::rngs::OsRng;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use zeroize::Zeroizing;
+
+use crate::events;
+use crate::utils::paths;
+use super::agent;
+
+/// Identifiant de l'agent auprès de launchd
+const LAUNCH_AGENT_LABEL: &str = "fr.keeprust.agent";
+
+/// Service et compte de la clé d'enveloppe de l'agent dans le trousseau
+const KEYCHAIN_SERVICE: &str = "keeprust-agent";
+const KEYCHAIN_ACCOUNT: &str = "wrapping-key";
+
+const MACOS_ONLY: &str = "Le lancement de l'agent à l'ouverture de session n'est disponible que sous macOS.";
+
+/// Fichier de l'agent de lancement : ~/Library/LaunchAgents/fr.keeprust.agent.plist
+fn launch_agent_path() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME")?;
+    Some(PathBuf::from(home).join("Library").join("LaunchAgents").join(format!("{}.plist", LAUNCH_AGENT_LABEL)))
+}
+
+fn escape_xml(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Agent de lancement : 'keeprust agent --keychain' à l'ouverture de session, sur le socket
+/// connu au moment de l'activation (launchd ne transmet pas l'environnement du shell)
+fn launch_agent_plist(executable: &Path, lock_after: u64, socket: &Path, log: &Path) -> String {
+    let string = |path: &Path| escape_xml(&path.to_string_lossy());
+    format!(r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{label}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{executable}</string>
+        <string>agent</string>
+        <string>--keychain</string>
+        <string>--lock-after</string>
+        <string>{lock_after}</string>
+    </array>
+    <key>EnvironmentVariables</key>
+    <dict>
+        <key>{socket_env}</key>
+        <string>{socket}</string>
+    </dict>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>ProcessType</key>
+    <string>Interactive</string>
+    <key>StandardOutPath</key>
+    <string>{log}</string>
+    <key>StandardErrorPath</key>
+    <string>{log}</string>
+</dict>
+</plist>
+"#,
+        label = LAUNCH_AGENT_LABEL,
+        executable = string(executable),
+        lock_after = lock_after,
+        socket_env = agent::SOCKET_ENV,
+        socket = string(socket),
+        log = string(log),
+    )
+}
+
+/// Exécute une commande système (security, launchctl) et retourne son message d'erreur en cas d'échec
+fn run(command: &mut Command, action: &str) -> Result<(), String> {
+    let output = command.output().map_err(|e| format!("{}: {}", action, e))?;
+    if !output.status.success() {
+        return Err(format!("{}: {}", action, String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(())
+}
+
+/// Enregistre une nouvelle clé d'enveloppe aléatoire dans le trousseau de session, lisible sans
+/// confirmation par le seul exécutable keeprust (toute autre application doit être autorisée)
+fn store_wrapping_key(executable: &Path) -> Result<(), String> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    OsRng.fill_bytes(&mut *key);
+    let encoded = Zeroizing::new(general_purpose::STANDARD.encode(*key));
+    
+    // Une clé existante garde sa liste d'applications autorisées : la remplacer entièrement
+    delete_wrapping_key();
+    run(
+        Command::new("security")
+            .args(["add-generic-password", "-s", KEYCHAIN_SERVICE, "-a", KEYCHAIN_ACCOUNT, "-l", "keeprust (agent)"])
+            .arg("-T").arg(executable)
+            .arg("-w").arg(encoded.as_str()),
+        "Impossible d'enregistrer la clé de l'agent dans le trousseau",
+    )
+}
+
+fn delete_wrapping_key() -> bool {
+    Command::new("security")
+        .args(["delete-generic-password", "-s", KEYCHAIN_SERVICE, "-a", KEYCHAIN_ACCOUNT])
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Clé d'enveloppe de l'agent, lue dans le trousseau
+pub fn wrapping_key() -> Result<Zeroizing<[u8; 32]>, String> {
+    if !cfg!(target_os = "macos") {
+        return Err(MACOS_ONLY.to_string());
+    }
+    let output = Command::new("security")
+        .args(["find-generic-password", "-s", KEYCHAIN_SERVICE, "-a", KEYCHAIN_ACCOUNT, "-w"])
+        .output()
+        .map_err(|e| format!("Trousseau inaccessible: {}", e))?;
+    let encoded = Zeroizing::new(output.stdout);
+    if !output.status.success() {
+        return Err("Clé de l'agent absente du trousseau: lancez 'keeprust agent enable-login'.".to_string());
+    }
+    let bytes = Zeroizing::new(general_purpose::STANDARD.decode(encoded.trim_ascii())
+        .map_err(|_| "Clé de l'agent invalide dans le trousseau.".to_string())?);
+    let key = <[u8; 32]>::try_from(bytes.as_slice()).map_err(|_| "Clé de l'agent invalide dans le trousseau.".to_string())?;
+    Ok(Zeroizing::new(key))
+}
+
+/// Lance l'agent à chaque ouverture de session (macOS) : agent de lancement launchd et clé
+/// d'enveloppe des secrets dans le trousseau. Les bases restent verrouillées jusqu'à
+/// 'keeprust vaults unlock'.
+pub fn cmd_enable_login(lock_after: u64) -> Result<(), String> {
+    if !cfg!(target_os = "macos") {
+        return Err(MACOS_ONLY.to_string());
+    }
+    let executable = std::env::current_exe().map_err(|e| format!("Chemin de keeprust introuvable: {}", e))?;
+    let plist = launch_agent_path().ok_or("Répertoire personnel introuvable (HOME).")?;
+    let socket = agent::socket_path().ok_or("Emplacement du socket de l'agent introuvable (définissez KEEPRUST_AGENT_SOCK).")?;
+    let log_directory = events::config_dir().ok_or("Répertoire de configuration introuvable.")?;
+    paths::create_private_dir(&log_directory).map_err(|e| format!("Impossible de créer {}: {}", log_directory.display(), e))?;
+    
+    store_wrapping_key(&executable)?;
+    
+    if let Some(directory) = plist.parent() {
+        fs::create_dir_all(directory).map_err(|e| format!("Impossible de créer {}: {}", directory.display(), e))?;
+    }
+    let content = launch_agent_plist(&executable, lock_after, &socket, &log_directory.join("agent.log"));
+    fs::write(&plist, content).map_err(|e| format!("Impossible d'enregistrer {}: {}", plist.display(), e))?;
+    
+    // Recharger un agent déjà enregistré, puis le lancer sans attendre la prochaine session
+    let _ = Command::new("launchctl").arg("unload").arg(&plist).output();
+    run(Command::new("launchctl").args(["load", "-w"]).arg(&plist), "Impossible d'enregistrer l'agent auprès de launchd")?;
+    
+    println!("L'agent keeprust démarrera à chaque ouverture de session ({}).", plist.display());
+    println!("Les bases restent verrouillées: déverrouillez-les avec 'keeprust vaults unlock'.");
+    println!("Les secrets gardés par l'agent sont chiffrés avec une clé du trousseau, réservée à {}.", executable.display());
+    Ok(())
+}
+
+/// Retire l'agent de l'ouverture de session et sa clé du trousseau
+pub fn cmd_disable_login() -> Result<(), String> {
+    if !cfg!(target_os = "macos") {
+        return Err(MACOS_ONLY.to_string());
+    }
+    let plist = launch_agent_path().ok_or("Répertoire personnel introuvable (HOME).")?;
+    let registered = plist.exists();
+    if registered {
+        let _ = Command::new("launchctl").args(["unload", "-w"]).arg(&plist).output();
+        fs::remove_file(&plist).map_err(|e| format!("Impossible de supprimer {}: {}", plist.display(), e))?;
+    }
+    let key_deleted = delete_wrapping_key();
+    
+    if registered || key_deleted {
+        println!("L'agent keeprust ne démarrera plus à l'ouverture de session.");
+    } else {
+        println!("L'agent keeprust n'était pas lancé à l'ouverture de session.");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_launch_agent_plist() {
+        let plist = launch_agent_plist(
+            Path::new("/Applications/Outils & Co/keeprust"),
+            30,
+            Path::new("/Users/marie/.config/keeprust/agent.sock"),
+            Path::new("/Users/marie/.config/keeprust/agent.log"),
+        );
+        assert!(plist.contains("<string>/Applications/Outils &amp; Co/keeprust</string>\n        <string>agent</string>\n        <string>--keychain</string>"));
+        assert!(plist.contains("<string>--lock-after</string>\n        <string>30</string>"));
+        assert!(plist.contains("<key>KEEPRUST_AGENT_SOCK</key>\n        <string>/Users/marie/.config/keeprust/agent.sock</string>"));
+        assert!(plist.contains("<key>RunAtLoad</key>\n    <true/>"));
+    }
+}
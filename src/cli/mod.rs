@@ -1,2 +1,34 @@
+pub mod agent;
 pub mod app;
-pub mod commands;
\ No newline at end of file
+pub mod attachments;
+pub mod autotype;
+pub mod beam;
+pub mod browser;
+pub mod bulk_edit;
+pub mod commands;
+pub mod completions;
+pub mod defaults;
+pub mod device;
+pub mod discovery;
+pub mod entry_picker;
+pub mod git_sync;
+pub mod group_picker;
+pub mod help;
+pub mod index;
+pub mod interop;
+pub mod login_item;
+pub mod monitor;
+pub mod otp;
+pub mod output;
+pub mod plugins;
+pub mod policy;
+pub mod profiles;
+pub mod resolve;
+pub mod script;
+pub mod secret_service;
+pub mod session;
+pub mod shell;
+pub mod signing;
+pub mod strength;
+pub mod sync;
+pub mod tui;
\ No newline at end of file
@@ -0,0 +1,262 @@
+use chrono::{DateTime, Duration, Utc};
+use data_encoding::HEXLOWER;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::events::{self, SecurityEvent};
+use crate::models::clock;
+use crate::models::database::Database;
+use crate::models::id::EntryId;
+use crate::models::stats::EXPIRING_SOON_DAYS;
+use crate::utils::breach::{self, BreachChecker};
+use crate::utils::paths;
+use crate::utils::symbols::Symbol;
+
+use super::session::Session;
+
+/// Nom du fichier conservant les constats déjà signalés, dans le répertoire de configuration
+const STATE_FILE: &str = "monitor-state.json";
+
+/// Constat de la surveillance sur une entrée
+#[derive(Debug, Clone, PartialEq)]
+enum Finding {
+    /// L'entrée a expiré
+    Expired { id: EntryId, title: String, expires_at: DateTime<Utc> },
+    
+    /// L'entrée expire dans les prochains jours
+    ExpiringSoon { id: EntryId, title: String, expires_at: DateTime<Utc> },
+    
+    /// Le mot de passe apparaît dans des fuites de données connues
+    Breached { id: EntryId, title: String, occurrences: u64, password_hash: String },
+}
+
+impl Finding {
+    /// Empreinte du constat, conservée pour ne pas le signaler deux fois.
+    ///
+    /// Elle change si la date d'expiration ou le mot de passe changent, et ne révèle pas l'entrée concernée.
+    fn fingerprint(&self) -> String {
+        let key = match self {
+            Finding::Expired { id, expires_at, .. } => format!("expired:{}:{}", id, expires_at.timestamp()),
+            Finding::ExpiringSoon { id, expires_at, .. } => format!("expiring:{}:{}", id, expires_at.timestamp()),
+            Finding::Breached { id, password_hash, .. } => format!("breached:{}:{}", id, password_hash),
+        };
+        HEXLOWER.encode(&Sha256::digest(key.as_bytes()))
+    }
+    
+    /// Description du constat pour l'affichage
+    fn describe(&self) -> String {
+        match self {
+            Finding::Expired { id, title, expires_at } => {
+                format!("{} (ID: {}) a expiré le {}", title, id, expires_at.format("%d-%m-%Y"))
+            }
+            Finding::ExpiringSoon { id, title, expires_at } => {
+                format!("{} (ID: {}) expire le {}", title, id, expires_at.format("%d-%m-%Y"))
+            }
+            Finding::Breached { id, title, occurrences, .. } => {
+                format!("{} (ID: {}) : mot de passe présent {} fois dans des fuites de données", title, id, occurrences)
+            }
+        }
+    }
+}
+
+/// Constats d'un passage de surveillance
+struct Scan {
+    findings: Vec<Finding>,
+    
+    /// La vérification des fuites n'a pas pu aboutir pour toutes les entrées
+    breach_check_failed: bool,
+}
+
+/// Recherche les entrées expirées ou expirant bientôt, et avec `breaches` les mots de passe compromis
+fn scan(db: &Database, now: DateTime<Utc>, breaches: Option<&mut BreachChecker>) -> Scan {
+    let soon = now + Duration::days(EXPIRING_SOON_DAYS);
+    let entries: Vec<_> = db.iter_entries()
+        .filter(|entry| !entry.archived && !db.is_in_trash(&entry.id))
+        .collect();
+    
+    let mut findings = Vec::new();
+    for entry in &entries {
+        let Some(expires_at) = entry.expires_at else {
+            continue;
+        };
+        let (id, title) = (entry.id, entry.title.clone());
+        if expires_at <= now {
+            findings.push(Finding::Expired { id, title, expires_at });
+        } else if expires_at <= soon {
+            findings.push(Finding::ExpiringSoon { id, title, expires_at });
+        }
+    }
+    
+    let mut breach_check_failed = false;
+    if let Some(checker) = breaches {
+        for entry in entries.iter().filter(|entry| !entry.password.is_empty()) {
+            match checker.occurrences(&entry.password) {
+                Ok(0) => {}
+                Ok(occurrences) => findings.push(Finding::Breached {
+                    id: entry.id,
+                    title: entry.title.clone(),
+                    occurrences,
+                    password_hash: breach::password_hash(&entry.password),
+                }),
+                Err(e) => {
+                    eprintln!("Avertissement: vérification des fuites impossible: {}", e);
+                    breach_check_failed = true;
+                    break;
+                }
+            }
+        }
+    }
+    
+    Scan { findings, breach_check_failed }
+}
+
+/// Emplacement du fichier d'état de la surveillance
+fn state_path() -> Option<PathBuf> {
+    Some(events::config_dir()?.join(STATE_FILE))
+}
+
+/// Constats déjà signalés, par base de données
+fn load_state() -> BTreeMap<String, BTreeSet<String>> {
+    state_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Enregistre les constats signalés pour une base de données
+fn save_state(vault: &str, seen: &BTreeSet<String>) -> Result<(), String> {
+    let path = state_path().ok_or("Aucun répertoire de configuration pour l'état de la surveillance.")?;
+    let mut state = load_state();
+    state.insert(vault.to_string(), seen.clone());
+    
+    if let Some(parent) = path.parent() {
+        paths::create_private_dir(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(&state).map_err(|e| e.to_string())?)
+        .map_err(|e| format!("Impossible d'enregistrer {}: {}", path.display(), e))
+}
+
+/// Sépare les nouveaux constats de ceux déjà signalés et retourne l'état à conserver.
+///
+/// Un constat résolu est oublié, pour être signalé à nouveau s'il réapparaît; si la vérification
+/// des fuites a échoué, les constats précédents sont conservés.
+fn new_findings<'a>(scan: &'a Scan, seen: &BTreeSet<String>) -> (Vec<&'a Finding>, BTreeSet<String>) {
+    let fresh = scan.findings.iter().filter(|finding| !seen.contains(&finding.fingerprint())).collect();
+    
+    let mut state: BTreeSet<String> = scan.findings.iter().map(Finding::fingerprint).collect();
+    if scan.breach_check_failed {
+        state.extend(seen.iter().cloned());
+    }
+    (fresh, state)
+}
+
+/// Affiche les nouveaux constats et déclenche les hooks correspondants
+fn report(path: &Path, findings: &[&Finding]) {
+    let now = clock::now().format("%d-%m-%Y %H:%M");
+    for finding in findings {
+        println!("[{}] {}{}", now, Symbol::Warning, finding.describe());
+    }
+    
+    let expired = findings.iter().filter(|finding| matches!(finding, Finding::Expired { .. })).count();
+    let expiring_soon = findings.iter().filter(|finding| matches!(finding, Finding::ExpiringSoon { .. })).count();
+    let breached = findings.iter().filter(|finding| matches!(finding, Finding::Breached { .. })).count();
+    
+    if expired > 0 || expiring_soon > 0 {
+        events::emit(path, &SecurityEvent::ExpiringEntries { expired, expiring_soon });
+    }
+    if breached > 0 {
+        events::emit(path, &SecurityEvent::BreachedPasswords { count: breached });
+    }
+}
+
+/// Date de modification du fichier, pour relire la base quand elle change
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// Surveille la base de données : entrées expirées ou expirant bientôt et, avec `hibp`,
+/// mots de passe présents dans des fuites de données connues.
+///
+/// Seuls les nouveaux constats sont signalés (affichage et hooks); les constats déjà signalés
+/// sont conservés d'une exécution à l'autre. Sans `once`, la vérification est répétée toutes
+/// les `interval_minutes` minutes.
+pub fn cmd_monitor(path: &Path, interval_minutes: u64, hibp: bool, once: bool) -> Result<(), String> {
+    if interval_minutes == 0 {
+        return Err("L'intervalle doit être d'au moins une minute.".to_string());
+    }
+    
+    let mut session = Session::open(path)?;
+    let vault = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()).display().to_string();
+    let mut last_modified = modified_at(path);
+    
+    if !once {
+        println!(
+            "Surveillance de {} toutes les {} minute(s){} (Ctrl+C pour arrêter).",
+            path.display(), interval_minutes, if hibp { ", fuites de données incluses" } else { "" }
+        );
+    }
+    
+    loop {
+        // Relire la base si elle a été modifiée depuis le passage précédent
+        let modified = modified_at(path);
+        if modified != last_modified {
+            session.reload()?;
+            last_modified = modified;
+        }
+        
+        let mut checker = BreachChecker::new();
+        let scan = scan(&session.db, clock::now(), hibp.then_some(&mut checker));
+        
+        let seen = load_state().remove(&vault).unwrap_or_default();
+        let (fresh, state) = new_findings(&scan, &seen);
+        
+        if !fresh.is_empty() {
+            report(path, &fresh);
+        } else if once {
+            println!("Aucun nouveau constat ({} déjà signalé(s)).", scan.findings.len());
+        }
+        
+        if let Err(e) = save_state(&vault, &state) {
+            eprintln!("Avertissement: {}", e);
+        }
+        
+        if once {
+            return session.close();
+        }
+        std::thread::sleep(std::time::Duration::from_secs(interval_minutes * 60));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    
+    #[test]
+    fn test_only_new_findings_are_reported() {
+        let now = clock::now();
+        let mut db = Database::new("Test".to_string(), String::new());
+        let expired = Entry::builder().title("Ancien").expires_at(Some(now - Duration::days(1))).build().unwrap();
+        let soon = Entry::builder().title("Bientôt").expires_at(Some(now + Duration::days(3))).build().unwrap();
+        let later = Entry::builder().title("Plus tard").expires_at(Some(now + Duration::days(365))).build().unwrap();
+        for entry in [expired, soon, later] {
+            db.add_entry(None, entry).unwrap();
+        }
+        
+        let first = scan(&db, now, None);
+        assert_eq!(first.findings.len(), 2);
+        let (fresh, state) = new_findings(&first, &BTreeSet::new());
+        assert_eq!(fresh.len(), 2);
+        
+        let second = scan(&db, now, None);
+        let (fresh, _) = new_findings(&second, &state);
+        assert!(fresh.is_empty());
+        
+        // Un constat conservé après un échec de vérification n'est pas signalé à nouveau
+        let failed = Scan { findings: Vec::new(), breach_check_failed: true };
+        assert_eq!(new_findings(&failed, &state).1, state);
+    }
+}
\ No newline at end of file
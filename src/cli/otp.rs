@@ -0,0 +1,172 @@
+use std::path::Path;
+use std::process::Command;
+
+use crate::crypto::otp::OtpConfig;
+use crate::models::clock;
+use crate::models::lookup::EntryRef;
+use crate::utils::clipboard::copy_password_securely;
+use crate::utils::symbols::Symbol;
+use super::session::{self, Session};
+
+/// Secondes écoulées depuis l'époque Unix, selon l'horloge de l'application
+fn unix_time() -> u64 {
+    clock::now().timestamp().max(0) as u64
+}
+
+/// Délai d'effacement du presse-papiers pour un code sans période (HOTP), en secondes
+const DEFAULT_CLEAR_SECONDS: u64 = 30;
+
+/// Programme utilisé pour lire les QR codes (paquet zbar-tools)
+const QR_DECODER: &str = "zbarimg";
+
+/// Associe un générateur de codes à une entrée
+pub fn cmd_otp_set(path: &Path, id: &EntryRef, uri: Option<String>) -> Result<(), String> {
+    // Valider l'URI avant de demander le mot de passe maître si elle est fournie
+    let config = uri.as_deref().map(OtpConfig::from_uri).transpose().map_err(|e| e.to_string())?;
+    store_otp(path, id, config)
+}
+
+/// Associe à une entrée le générateur de codes lu dans l'image d'un QR code (capture d'écran)
+pub fn cmd_otp_import_qr(path: &Path, image: &Path, id: &EntryRef) -> Result<(), String> {
+    // Décoder l'image avant de demander le mot de passe maître
+    let uris = decode_qr_codes(image)?;
+    let mut otp_uris = uris.iter().filter(|uri| uri.starts_with("otpauth://"));
+    
+    let uri = otp_uris.next().ok_or_else(|| {
+        if uris.iter().any(|uri| uri.starts_with("otpauth-migration://")) {
+            "Ce QR code est un export groupé de Google Authenticator (otpauth-migration://), non pris en charge: exportez les comptes un par un.".to_string()
+        } else {
+            format!("Aucun QR code otpauth:// trouvé dans {}.", image.display())
+        }
+    })?;
+    if otp_uris.next().is_some() {
+        println!("{}Plusieurs QR codes otpauth:// trouvés, seul le premier est utilisé.", Symbol::Warning);
+    }
+    
+    let config = OtpConfig::from_uri(uri).map_err(|e| e.to_string())?;
+    store_otp(path, id, Some(config))
+}
+
+/// Lit les QR codes d'une image avec zbarimg (évite d'embarquer un décodeur d'images)
+fn decode_qr_codes(image: &Path) -> Result<Vec<String>, String> {
+    if !image.is_file() {
+        return Err(format!("Image introuvable: {}", image.display()));
+    }
+    
+    let output = Command::new(QR_DECODER)
+        .args(["--quiet", "--raw", "-Sdisable", "-Sqrcode.enable"])
+        .arg(image)
+        .output()
+        .map_err(|e| format!("Impossible de lancer {} (installez zbar-tools): {}", QR_DECODER, e))?;
+    
+    // zbarimg se termine avec le code 4 lorsqu'aucun code n'est trouvé
+    if !output.status.success() && output.status.code() != Some(4) {
+        return Err(format!("{} n'a pas pu lire {}: {}", QR_DECODER, image.display(), String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+/// Enregistre un générateur sur une entrée (demandé sans écho s'il n'est pas fourni)
+fn store_otp(path: &Path, id: &EntryRef, config: Option<OtpConfig>) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    let id = &session.resolve(id)?;
+    let title = session.entry(id)?.title.clone();
+    
+    let config = match config {
+        Some(config) => config,
+        None => OtpConfig::from_uri(&session::prompt_password("URI otpauth://: ")?).map_err(|e| e.to_string())?,
+    };
+    
+    let replaced = session.db.find_entry(id).is_some_and(|entry| entry.otp.is_some());
+    if replaced && !session::confirm("Cette entrée a déjà un générateur de codes. Le remplacer?", false)? {
+        println!("Opération annulée.");
+        return session.close();
+    }
+    
+    println!("Générateur: {}", config);
+    session.db.update_entry(id, |entry| entry.otp = Some(config)).map_err(|e| e.to_string())?;
+    session.save()?;
+    
+    println!("Code à usage unique associé à l'entrée '{}'.", title);
+    session.close()
+}
+
+/// Affiche le code actuel d'une entrée, ou le copie dans le presse-papiers avec `copy`;
+/// le compteur HOTP est incrémenté et enregistré
+pub fn cmd_otp_code(path: &Path, id: &EntryRef, copy: bool) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    let id = &session.resolve(id)?;
+    let entry = session.entry(id)?;
+    let title = entry.title.clone();
+    let counter_based = entry.otp.as_ref()
+        .ok_or_else(|| format!("L'entrée '{}' n'a pas de code à usage unique (voir 'keeprust otp set').", entry.title))?
+        .is_counter_based();
+    
+    let now = unix_time();
+    let (code, remaining) = if counter_based {
+        // Le compteur doit être enregistré avant d'afficher le code pour ne jamais le réutiliser
+        let code = session.db.update_entry_metadata(id, |entry| entry.otp.as_mut().map(|otp| otp.next_code(now)))
+            .map_err(|e| e.to_string())?
+            .expect("générateur vérifié ci-dessus")
+            .map_err(|e| e.to_string())?;
+        session.save()?;
+        (code, None)
+    } else {
+        let otp = entry.otp.as_ref().expect("générateur vérifié ci-dessus");
+        (otp.code_at(now).map_err(|e| e.to_string())?, otp.seconds_remaining(now))
+    };
+    
+    if copy {
+        // Un code périodique est effacé du presse-papiers quand il cesse d'être valide
+        let restore = copy_password_securely(&code, remaining.unwrap_or(DEFAULT_CLEAR_SECONDS)).map_err(|e| e.to_string())?;
+        println!("Code de '{}' copié dans le presse-papiers.", title);
+        session.close()?;
+        restore.wait();
+        return Ok(());
+    }
+    
+    match remaining {
+        Some(remaining) => println!("Code: {} (valide encore {} s)", code, remaining),
+        None => println!("Code: {}", code),
+    }
+    session.close()
+}
+
+/// Affiche l'URI otpauth:// d'une entrée (pour l'importer dans une autre application)
+pub fn cmd_otp_uri(path: &Path, id: &EntryRef) -> Result<(), String> {
+    let session = Session::open(path)?;
+    let id = &session.resolve(id)?;
+    let entry = session.entry(id)?;
+    let otp = entry.otp.as_ref()
+        .ok_or_else(|| format!("L'entrée '{}' n'a pas de code à usage unique.", entry.title))?;
+    
+    println!("{}", otp.to_uri());
+    session.close()
+}
+
+/// Retire le générateur de codes d'une entrée
+pub fn cmd_otp_remove(path: &Path, id: &EntryRef) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    let id = &session.resolve(id)?;
+    let entry = session.entry(id)?;
+    if entry.otp.is_none() {
+        println!("L'entrée '{}' n'a pas de code à usage unique.", entry.title);
+        return session.close();
+    }
+    
+    let title = entry.title.clone();
+    if !session::confirm(&format!("Retirer le code à usage unique de '{}'? Le secret sera perdu.", title), false)? {
+        println!("Opération annulée.");
+        return session.close();
+    }
+    
+    session.db.update_entry(id, |entry| entry.otp = None).map_err(|e| e.to_string())?;
+    session.save()?;
+    println!("Code à usage unique retiré de l'entrée '{}'.", title);
+    session.close()
+}
\ No newline at end of file
@@ -0,0 +1,220 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::models::attachment::Attachment;
+use crate::models::device::DeviceStamp;
+use crate::models::entry::{ColorLabel, Entry};
+use crate::models::exposure::Exposure;
+use crate::models::id::EntryId;
+use crate::models::search_index::IndexedEntry;
+use crate::utils::redact::Redactor;
+
+/// Format de sortie des commandes (--output)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Texte destiné à être lu
+    #[default]
+    Text,
+    
+    /// Un document JSON par commande, sur une ligne, pour les scripts
+    Json,
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputFormat::Text => write!(f, "text"),
+            OutputFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "text" | "texte" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            other => Err(format!("Format de sortie inconnu: '{}' (text ou json)", other)),
+        }
+    }
+}
+
+static FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+
+/// Définit le format de sortie (à appeler une fois, avant toute commande)
+pub fn configure(format: OutputFormat) {
+    let _ = FORMAT.set(format);
+}
+
+/// Indique si les commandes doivent produire du JSON
+pub fn is_json() -> bool {
+    FORMAT.get().copied().unwrap_or_default() == OutputFormat::Json
+}
+
+/// Écrit un document JSON sur une ligne de la sortie standard
+pub fn print_json<T: Serialize>(value: &T) -> Result<(), String> {
+    let json = serde_json::to_string(value).map_err(|e| e.to_string())?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Objet d'erreur écrit sur la sortie standard lorsqu'une commande échoue en mode JSON
+#[derive(Debug, Serialize)]
+struct ErrorObject<'a> {
+    error: ErrorDetail<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorDetail<'a> {
+    message: &'a str,
+}
+
+/// Écrit l'erreur d'une commande : `{"error":{"message":"..."}}`
+pub fn print_error(message: &str) {
+    // La sérialisation d'une chaîne ne peut pas échouer
+    let _ = print_json(&ErrorObject { error: ErrorDetail { message } });
+}
+
+/// Champs d'une entrée communs à toutes les sorties JSON (jamais de secret)
+#[derive(Debug, Serialize)]
+pub struct EntrySummary<'a> {
+    pub id: EntryId,
+    pub title: &'a str,
+    pub username: &'a str,
+    pub url: &'a str,
+    
+    /// Chemin du groupe, noms séparés par des '/'
+    pub group: String,
+    pub tags: &'a [String],
+    pub color: Option<ColorLabel>,
+    pub archived: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl<'a> EntrySummary<'a> {
+    pub fn new(path: &[String], entry: &'a Entry) -> Self {
+        Self {
+            id: entry.id,
+            title: &entry.title,
+            username: &entry.username,
+            url: &entry.url,
+            group: path.join("/"),
+            tags: &entry.tags,
+            color: entry.color,
+            archived: entry.archived,
+            expires_at: entry.expires_at,
+        }
+    }
+    
+    /// Résumé d'une entrée lue dans l'index de recherche
+    pub fn indexed(entry: &'a IndexedEntry) -> Self {
+        Self {
+            id: entry.id,
+            title: &entry.title,
+            username: &entry.username,
+            url: &entry.url,
+            group: entry.path.join("/"),
+            tags: &entry.tags,
+            color: entry.color,
+            archived: entry.archived,
+            expires_at: entry.expires_at,
+        }
+    }
+}
+
+/// Champ supplémentaire d'une entrée affichée
+#[derive(Debug, Serialize)]
+pub struct FieldDetail<'a> {
+    pub name: &'a str,
+    pub value: String,
+    pub protected: bool,
+}
+
+/// Pièce jointe d'une entrée, sans son contenu
+#[derive(Debug, Serialize)]
+pub struct AttachmentDetail<'a> {
+    pub name: &'a str,
+    pub size: usize,
+    pub added_at: DateTime<Utc>,
+}
+
+impl<'a> AttachmentDetail<'a> {
+    pub fn new(attachment: &'a Attachment) -> Self {
+        Self { name: &attachment.name, size: attachment.size, added_at: attachment.added_at }
+    }
+}
+
+/// Entrée complète (`show`) : le mot de passe passe par le `Redactor` demandé
+#[derive(Debug, Serialize)]
+pub struct EntryDetail<'a> {
+    #[serde(flatten)]
+    pub summary: EntrySummary<'a>,
+    pub password: String,
+    pub notes: &'a str,
+    pub locked: bool,
+    
+    /// Description du générateur de codes à usage unique, sans son secret
+    pub otp: Option<String>,
+    
+    /// Champs supplémentaires; la valeur des champs protégés passe par le `Redactor`
+    pub custom_fields: Vec<FieldDetail<'a>>,
+    pub attachments: Vec<AttachmentDetail<'a>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub modified_on: Option<&'a DeviceStamp>,
+    
+    /// Dernier affichage, copie ou export du mot de passe en clair
+    pub last_exposure: Option<&'a Exposure>,
+}
+
+impl<'a> EntryDetail<'a> {
+    pub fn new(path: &[String], entry: &'a Entry, redactor: Redactor) -> Self {
+        Self {
+            summary: EntrySummary::new(path, entry),
+            password: redactor.redact(&entry.password),
+            notes: &entry.notes,
+            locked: entry.locked,
+            otp: entry.otp.as_ref().map(|otp| otp.to_string()),
+            custom_fields: entry.custom_fields.iter()
+                .map(|field| FieldDetail { name: &field.name, value: field.display_value(redactor), protected: field.protected })
+                .collect(),
+            attachments: entry.attachments.iter().map(AttachmentDetail::new).collect(),
+            created_at: entry.created_at,
+            updated_at: entry.updated_at,
+            modified_on: entry.modified_on.as_ref(),
+            last_exposure: entry.last_exposure.as_ref(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::CustomField;
+    
+    #[test]
+    fn test_entry_detail_field_names() {
+        let entry = Entry::builder().title("Banque").username("bob").password("k7#Qm2!vX9@pL4$w").tag("perso")
+            .custom_field(CustomField::new("Code", "4821", true))
+            .build()
+            .unwrap();
+        let path = vec!["Racine".to_string(), "Finances".to_string()];
+        
+        let json = serde_json::to_value(EntryDetail::new(&path, &entry, Redactor::Mask)).unwrap();
+        assert_eq!(json["id"], entry.id.to_string());
+        assert_eq!(json["title"], "Banque");
+        assert_eq!(json["group"], "Racine/Finances");
+        assert_eq!(json["tags"], serde_json::json!(["perso"]));
+        assert_eq!(json["password"], crate::utils::redact::MASK);
+        assert!(json["color"].is_null());
+        assert_eq!(json["custom_fields"], serde_json::json!([{ "name": "Code", "value": crate::utils::redact::MASK, "protected": true }]));
+        
+        let summary = serde_json::to_string(&EntrySummary::new(&path, &entry)).unwrap();
+        assert!(!summary.contains("k7#Qm2"));
+        assert_eq!("JSON".parse::<OutputFormat>(), Ok(OutputFormat::Json));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,91 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Préfixe des exécutables fournissant des sous-commandes externes
+const PLUGIN_PREFIX: &str = "keeprust-";
+
+/// Recherche l'exécutable `keeprust-<nom>` dans le PATH
+pub fn find_plugin(name: &str) -> Option<PathBuf> {
+    let file_name = format!("{}{}", PLUGIN_PREFIX, name);
+    let path = env::var_os("PATH")?;
+    
+    env::split_paths(&path).find_map(|directory| {
+        let candidates = if cfg!(windows) {
+            vec![directory.join(format!("{}.exe", file_name)), directory.join(&file_name)]
+        } else {
+            vec![directory.join(&file_name)]
+        };
+        candidates.into_iter().find(|candidate| is_executable(candidate))
+    })
+}
+
+/// Liste les sous-commandes externes disponibles dans le PATH
+pub fn list_plugins() -> Vec<String> {
+    let Some(path) = env::var_os("PATH") else {
+        return Vec::new();
+    };
+    
+    let mut names: Vec<String> = env::split_paths(&path)
+        .filter_map(|directory| directory.read_dir().ok())
+        .flatten()
+        .filter_map(|item| item.ok())
+        .filter(|item| is_executable(&item.path()))
+        .filter_map(|item| {
+            let file_name = item.file_name().to_string_lossy().into_owned();
+            let name = file_name.strip_prefix(PLUGIN_PREFIX)?;
+            Some(name.strip_suffix(".exe").unwrap_or(name).to_string())
+        })
+        .collect();
+    
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Exécute une sous-commande externe (`keeprust foo` lance `keeprust-foo`).
+///
+/// Le chemin de la base de données et celui de keeprust sont transmis par les variables
+/// KEEPRUST_DB et KEEPRUST_BIN.
+pub fn run_plugin(db_path: &Path, args: &[String]) -> Result<(), String> {
+    let (name, plugin_args) = args.split_first().ok_or("Sous-commande externe manquante.")?;
+    
+    let plugin = find_plugin(name).ok_or_else(|| format!(
+        "Commande inconnue: '{}' (aucun exécutable '{}{}' trouvé dans le PATH). Utilisez --help pour voir les commandes disponibles.",
+        name, PLUGIN_PREFIX, name
+    ))?;
+    
+    let mut command = Command::new(&plugin);
+    command.args(plugin_args).env("KEEPRUST_DB", db_path);
+    if let Ok(current_exe) = env::current_exe() {
+        command.env("KEEPRUST_BIN", current_exe);
+    }
+    
+    let status = command.status()
+        .map_err(|e| format!("Impossible de lancer {}: {}", plugin.display(), e))?;
+    
+    if !status.success() {
+        return Err(match status.code() {
+            Some(code) => format!("La commande externe '{}' a échoué (code {}).", name, code),
+            None => format!("La commande externe '{}' a été interrompue.", name),
+        });
+    }
+    Ok(())
+}
+
+/// Indique si un chemin désigne un fichier exécutable
+fn is_executable(path: &Path) -> bool {
+    let Ok(metadata) = path.metadata() else {
+        return false;
+    };
+    
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        metadata.is_file()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,94 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::events;
+use crate::models::database::Database;
+use crate::models::id::EntryId;
+use crate::models::limits::{OversizedValue, SizeLimits};
+use crate::models::policy::VaultPolicy;
+use crate::utils::symbols::Symbol;
+
+use super::session::Session;
+
+/// Nom du fichier de politique, dans le répertoire de configuration
+const POLICY_FILE: &str = "policy.toml";
+
+/// Emplacement par défaut du fichier de politique (voir `VaultPolicy` pour son contenu)
+pub fn policy_path() -> Option<PathBuf> {
+    Some(events::config_dir()?.join(POLICY_FILE))
+}
+
+/// Charge une politique : le fichier fourni (qui doit exister), sinon policy.toml du répertoire
+/// de configuration s'il existe
+pub fn load(file: Option<&Path>) -> Result<Option<VaultPolicy>, String> {
+    let path = match file {
+        Some(file) => file.to_path_buf(),
+        None => match policy_path() {
+            Some(path) if path.exists() => path,
+            _ => return Ok(None),
+        },
+    };
+    let content = fs::read_to_string(&path).map_err(|e| format!("Impossible de lire {}: {}", path.display(), e))?;
+    toml::from_str(&content).map(Some).map_err(|e| format!("Fichier {} invalide: {}", path.display(), e))
+}
+
+/// Seuils d'avertissement de taille des notes et des champs : ceux de policy.toml, sinon ceux par défaut
+pub fn size_limits() -> SizeLimits {
+    match load(None) {
+        Ok(Some(policy)) => policy.entries.size_limits(),
+        _ => SizeLimits::default(),
+    }
+}
+
+/// Message d'une valeur refusée car au-delà des plafonds de taille
+pub fn oversized_error(oversized: OversizedValue) -> String {
+    format!("{}: placez ce contenu dans une pièce jointe ('keeprust attach').", oversized)
+}
+
+/// Signale les notes et champs volumineux des entrées ajoutées ou modifiées
+pub fn warn_oversized(db: &Database, ids: &[EntryId]) {
+    let limits = size_limits();
+    let oversized: Vec<_> = ids.iter()
+        .filter_map(|id| db.find_entry(id))
+        .flat_map(|entry| entry.oversized_values(limits))
+        .collect();
+    if oversized.is_empty() {
+        return;
+    }
+    
+    for value in &oversized {
+        eprintln!("{}{}", Symbol::Warning, value);
+    }
+    eprintln!("    Notes et champs sont déchiffrés à chaque ouverture : placez les contenus volumineux dans une pièce jointe ('keeprust attach').");
+}
+
+/// Vérifie la base par rapport à la politique; échoue (code de sortie non nul) si elle n'est
+/// pas respectée, pour un usage dans un script ou une intégration continue
+pub fn cmd_policy_check(path: &Path, file: Option<&Path>) -> Result<(), String> {
+    let Some(policy) = load(file)? else {
+        let location = policy_path().map(|path| path.display().to_string()).unwrap_or_else(|| POLICY_FILE.to_string());
+        return Err(format!("Aucune politique: créez {} ou indiquez un fichier avec --policy.", location));
+    };
+    
+    let session = Session::open(path)?;
+    let violations = policy.check(&session.db, session.kdf_params());
+    
+    if !policy.export.forbidden_tags.is_empty() {
+        let excluded = session.db.iter_entries()
+            .filter(|entry| !session.db.is_in_trash(&entry.id) && policy.forbidden_export_tag(&entry.tags).is_some())
+            .count();
+        println!("{} entrée(s) exclue(s) des exports (étiquettes: {}).", excluded, policy.export.forbidden_tags.join(", "));
+    }
+    
+    if violations.is_empty() {
+        println!("La base {} respecte la politique.", path.display());
+        return session.close();
+    }
+    
+    println!("{} écart(s) à la politique:", violations.len());
+    for violation in &violations {
+        println!("  - {}", violation);
+    }
+    session.close()?;
+    Err(format!("La base {} ne respecte pas la politique.", path.display()))
+}
\ No newline at end of file
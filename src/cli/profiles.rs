@@ -0,0 +1,121 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::events;
+
+/// Nom du fichier des profils, dans le répertoire de configuration
+const PROFILES_FILE: &str = "profiles.toml";
+
+/// Base désignée par un nom court (--profile), avec son délai de verrouillage dans l'agent
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Chemin de la base (`~/` désigne le répertoire personnel)
+    pub path: PathBuf,
+    
+    /// Minutes sans utilisation avant que l'agent oublie son mot de passe (délai de l'agent si absent)
+    #[serde(default)]
+    pub lock_after: Option<u64>,
+}
+
+/// Profils configurés :
+///
+/// ```toml
+/// [profiles.travail]
+/// path = "~/Documents/travail.krs"
+/// lock_after = 10
+///
+/// [profiles.perso]
+/// path = "/home/bob/perso.krs"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profiles {
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// Emplacement du fichier des profils
+pub fn profiles_path() -> Option<PathBuf> {
+    Some(events::config_dir()?.join(PROFILES_FILE))
+}
+
+/// Remplace `~/` en tête du chemin par le répertoire personnel
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+impl Profiles {
+    /// Charge les profils; aucun profil si le fichier n'existe pas
+    pub fn load() -> Result<Self, String> {
+        let Some(path) = profiles_path() else {
+            return Ok(Self::default());
+        };
+        match fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content).map_err(|e| format!("Fichier {} invalide: {}", path.display(), e)),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+    
+    fn parse(content: &str) -> Result<Self, toml::de::Error> {
+        let mut profiles: Self = toml::from_str(content)?;
+        for profile in profiles.profiles.values_mut() {
+            profile.path = expand_home(&profile.path);
+        }
+        Ok(profiles)
+    }
+    
+    /// Profil désigné par son nom
+    pub fn get(&self, name: &str) -> Result<&Profile, String> {
+        self.profiles.get(name).ok_or_else(|| {
+            let known: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            if known.is_empty() {
+                format!("Profil '{}' inconnu: aucun profil défini dans {}.", name, PROFILES_FILE)
+            } else {
+                format!("Profil '{}' inconnu (profils: {}).", name, known.join(", "))
+            }
+        })
+    }
+    
+    /// Profil d'une base, s'il y en a un
+    pub fn for_path(&self, path: &Path) -> Option<(&str, &Profile)> {
+        let path = absolute(path);
+        self.profiles.iter()
+            .find(|(_, profile)| absolute(&profile.path) == path)
+            .map(|(name, profile)| (name.as_str(), profile))
+    }
+}
+
+/// Chemin absolu et canonique d'une base, pour la reconnaître quel que soit le répertoire courant
+pub fn absolute(path: &Path) -> PathBuf {
+    fs::canonicalize(path).or_else(|_| std::path::absolute(path)).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_profiles() {
+        let profiles = Profiles::parse(r#"
+            [profiles.travail]
+            path = "/srv/travail.krs"
+            lock_after = 10
+            
+            [profiles.perso]
+            path = "perso.krs"
+        "#).unwrap();
+        
+        assert_eq!(profiles.get("travail").unwrap().lock_after, Some(10));
+        assert_eq!(profiles.get("perso").unwrap().lock_after, None);
+        assert!(profiles.get("autre").unwrap_err().contains("perso, travail"));
+        assert_eq!(profiles.for_path(Path::new("/srv/travail.krs")).map(|(name, _)| name), Some("travail"));
+        assert!(Profiles::parse("[profiles.x]\nfile = \"a.krs\"").is_err());
+    }
+}
@@ -0,0 +1,340 @@
+use crate::models::entry::Entry;
+use crate::models::exposure::{ExposureInterface, ExposureKind};
+use crate::models::id::EntryId;
+use crate::models::merge::{ConflictField, Side, conflicting_fields};
+use crate::utils::redact::MASK;
+#[cfg(feature = "tui")]
+use crate::utils::symbols;
+
+use super::session::{self, Session};
+
+/// Copie de conflit à résoudre, avec son entrée d'origine et les champs qui diffèrent
+struct Conflict {
+    local: Entry,
+    remote: Entry,
+    fields: Vec<ConflictField>,
+}
+
+impl Conflict {
+    /// Valeur affichée d'un champ : masquée s'il est secret et non révélé, première ligne
+    /// seulement pour les notes
+    fn shown(&self, field: &ConflictField, side: Side, revealed: bool) -> String {
+        let entry = match side {
+            Side::Local => &self.local,
+            Side::Remote => &self.remote,
+        };
+        match field.value(entry) {
+            None => "(absent)".to_string(),
+            Some(value) if field.is_secret(entry) && !revealed && !value.is_empty() => MASK.to_string(),
+            Some(value) => match value.split_once('\n') {
+                Some((first, _)) => format!("{}…", first),
+                None => value,
+            },
+        }
+    }
+    
+    /// Origine d'une version : appareil et date de la dernière modification
+    fn origin(&self, side: Side) -> String {
+        let entry = match side {
+            Side::Local => &self.local,
+            Side::Remote => &self.remote,
+        };
+        let device = entry.modified_on.as_ref().map(|device| device.name.as_str()).unwrap_or("appareil inconnu");
+        format!("{}, {}", device, entry.updated_at.format("%d-%m-%Y %H:%M"))
+    }
+}
+
+/// Choix de l'utilisateur pour un conflit
+#[derive(Debug, PartialEq)]
+enum Decision {
+    /// Version retenue pour chaque champ qui diffère
+    Resolve(Vec<Side>),
+    
+    /// Conflit laissé en l'état (la copie reste dans la base)
+    Skip,
+}
+
+/// Décisions prises avant d'avoir parcouru tous les conflits ou de quitter, et entrées dont les
+/// secrets ont été affichés
+#[derive(Default)]
+struct Decisions {
+    chosen: Vec<Decision>,
+    revealed: Vec<EntryId>,
+}
+
+/// Résout les copies de conflit champ par champ : pour chaque conflit, l'utilisateur compare la
+/// version locale et la version distante et choisit la valeur à garder, en voyant le résultat.
+/// Retourne le nombre de conflits résolus; la base est enregistrée s'il y en a.
+pub fn resolve_conflicts(session: &mut Session, copies: &[EntryId]) -> Result<usize, String> {
+    let mut conflicts = Vec::new();
+    for id in copies {
+        let Some(remote) = session.db.find_entry(id) else {
+            continue;
+        };
+        match remote.conflict_of.and_then(|original| session.db.find_entry(&original)) {
+            Some(local) => conflicts.push(Conflict {
+                fields: conflicting_fields(local, remote),
+                local: local.clone(),
+                remote: remote.clone(),
+            }),
+            None => println!("'{}': entrée d'origine supprimée, gardez ou supprimez la copie à la main.", remote.title),
+        }
+    }
+    if conflicts.is_empty() {
+        return Ok(0);
+    }
+    
+    #[cfg(feature = "tui")]
+    let (decisions, interface) = if symbols::is_plain() {
+        (prompt_decisions(&conflicts)?, ExposureInterface::Cli)
+    } else {
+        (screen::decisions(&conflicts)?, ExposureInterface::Tui)
+    };
+    #[cfg(not(feature = "tui"))]
+    let (decisions, interface) = (prompt_decisions(&conflicts)?, ExposureInterface::Cli);
+    
+    session.record_exposure(&decisions.revealed, ExposureKind::Revealed, interface)?;
+    let mut resolved = 0;
+    for (conflict, decision) in conflicts.iter().zip(decisions.chosen) {
+        let Decision::Resolve(sides) = decision else {
+            continue;
+        };
+        let choices: Vec<_> = conflict.fields.iter().cloned().zip(sides).collect();
+        session.db.resolve_conflict(&conflict.remote.id, &choices).map_err(|e| e.to_string())?;
+        resolved += 1;
+    }
+    if resolved > 0 {
+        session.save()?;
+    }
+    Ok(resolved)
+}
+
+/// Résolution ligne par ligne (--plain, ou sans l'interface plein écran) : une question par champ,
+/// puis le résultat à confirmer
+fn prompt_decisions(conflicts: &[Conflict]) -> Result<Decisions, String> {
+    let mut decisions = Decisions::default();
+    for (index, conflict) in conflicts.iter().enumerate() {
+        println!("\nConflit {}/{}: {}", index + 1, conflicts.len(), conflict.local.title);
+        println!("  Local: {}", conflict.origin(Side::Local));
+        println!("  Distant: {}", conflict.origin(Side::Remote));
+        
+        let mut sides = Vec::new();
+        let mut revealed = false;
+        let mut field_index = 0;
+        while let Some(field) = conflict.fields.get(field_index) {
+            println!("  {}", field.label());
+            println!("    local: {}", conflict.shown(field, Side::Local, revealed));
+            println!("    distant: {}", conflict.shown(field, Side::Remote, revealed));
+            
+            let secret = field.is_secret(&conflict.local) || field.is_secret(&conflict.remote);
+            let question = match secret && !revealed {
+                true => "  Garder [l]ocal, [d]istant, [v]oir les valeurs, [p]asser ce conflit ou [q]uitter (l): ",
+                false => "  Garder [l]ocal, [d]istant, [p]asser ce conflit ou [q]uitter (l): ",
+            };
+            match session::prompt_line(question)?.trim().to_lowercase().as_str() {
+                "" | "l" => sides.push(Side::Local),
+                "d" => sides.push(Side::Remote),
+                "v" if secret => {
+                    revealed = true;
+                    if !decisions.revealed.contains(&conflict.local.id) {
+                        decisions.revealed.push(conflict.local.id);
+                    }
+                    continue;
+                }
+                "p" => break,
+                "q" => return Ok(decisions),
+                _ => {
+                    println!("  Réponse non reconnue.");
+                    continue;
+                }
+            }
+            field_index += 1;
+        }
+        if sides.len() < conflict.fields.len() {
+            decisions.chosen.push(Decision::Skip);
+            continue;
+        }
+        
+        println!("  Résultat:");
+        for (field, side) in conflict.fields.iter().zip(&sides) {
+            println!("    {}: {}", field.label(), conflict.shown(field, *side, revealed));
+        }
+        decisions.chosen.push(match session::confirm("  Appliquer ce résultat?", true)? {
+            true => Decision::Resolve(sides),
+            false => Decision::Skip,
+        });
+    }
+    Ok(decisions)
+}
+
+#[cfg(feature = "tui")]
+mod screen {
+    use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+    use ratatui::layout::{Constraint, Layout, Rect};
+    use ratatui::style::Stylize;
+    use ratatui::text::Line;
+    use ratatui::widgets::{Block, Paragraph};
+    use ratatui::{DefaultTerminal, Frame};
+    
+    use crate::models::merge::Side;
+    
+    use super::{Conflict, Decision, Decisions};
+    
+    const HELP: &str = "↑↓ champ  ←/l local  →/d distant  v voir  Entrée appliquer  p passer  q quitter";
+    
+    /// État de l'écran de résolution : conflit et champ courants, versions choisies
+    struct Resolver {
+        index: usize,
+        field: usize,
+        sides: Vec<Side>,
+        revealed: bool,
+        decisions: Decisions,
+    }
+    
+    impl Resolver {
+        fn new(conflicts: &[Conflict]) -> Self {
+            Self {
+                index: 0,
+                field: 0,
+                sides: vec![Side::Local; conflicts.first().map_or(0, |conflict| conflict.fields.len())],
+                revealed: false,
+                decisions: Decisions::default(),
+            }
+        }
+        
+        /// Enregistre la décision du conflit courant et passe au suivant
+        fn decide(&mut self, conflicts: &[Conflict], decision: Decision) {
+            self.decisions.chosen.push(decision);
+            self.index += 1;
+            self.field = 0;
+            self.revealed = false;
+            self.sides = vec![Side::Local; conflicts.get(self.index).map_or(0, |conflict| conflict.fields.len())];
+        }
+        
+        /// Applique une touche; retourne true lorsque la résolution est terminée
+        fn handle_key(&mut self, conflicts: &[Conflict], key: KeyEvent) -> bool {
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                return true;
+            }
+            let Some(conflict) = conflicts.get(self.index) else {
+                return true;
+            };
+            match key.code {
+                KeyCode::Up | KeyCode::Char('k') => self.field = self.field.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => self.field = (self.field + 1).min(self.sides.len().saturating_sub(1)),
+                KeyCode::Left | KeyCode::Char('l') => {
+                    if let Some(side) = self.sides.get_mut(self.field) {
+                        *side = Side::Local;
+                    }
+                }
+                KeyCode::Right | KeyCode::Char('d') => {
+                    if let Some(side) = self.sides.get_mut(self.field) {
+                        *side = Side::Remote;
+                    }
+                }
+                KeyCode::Char('v') => {
+                    self.revealed = !self.revealed;
+                    if self.revealed && !self.decisions.revealed.contains(&conflict.local.id) {
+                        self.decisions.revealed.push(conflict.local.id);
+                    }
+                }
+                KeyCode::Enter => self.decide(conflicts, Decision::Resolve(self.sides.clone())),
+                KeyCode::Char('p') | KeyCode::Tab => self.decide(conflicts, Decision::Skip),
+                KeyCode::Char('q') | KeyCode::Esc => return true,
+                _ => {}
+            }
+            self.index >= conflicts.len()
+        }
+        
+        /// Dessine les trois panneaux : version locale, version distante et résultat
+        fn draw(&self, frame: &mut Frame, conflicts: &[Conflict]) {
+            let Some(conflict) = conflicts.get(self.index) else {
+                return;
+            };
+            let [title, main, status] = Layout::vertical([Constraint::Length(1), Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+            let [local, remote, result] = Layout::horizontal([Constraint::Ratio(1, 3); 3]).areas(main);
+            
+            let heading = format!("Conflit {}/{}: {}", self.index + 1, conflicts.len(), conflict.local.title);
+            frame.render_widget(Paragraph::new(Line::from(heading).bold()), title);
+            self.draw_pane(frame, local, conflict, Some(Side::Local), format!("Local ({})", conflict.origin(Side::Local)));
+            self.draw_pane(frame, remote, conflict, Some(Side::Remote), format!("Distant ({})", conflict.origin(Side::Remote)));
+            self.draw_pane(frame, result, conflict, None, "Résultat".to_string());
+            frame.render_widget(Paragraph::new(Line::from(HELP).dim()), status);
+        }
+        
+        /// Panneau d'une version (`side`) ou du résultat (None) : un champ par ligne, valeur retenue en couleur
+        fn draw_pane(&self, frame: &mut Frame, area: Rect, conflict: &Conflict, side: Option<Side>, title: String) {
+            let lines: Vec<Line> = conflict.fields.iter().zip(&self.sides).enumerate().flat_map(|(index, (field, chosen))| {
+                let shown = conflict.shown(field, side.unwrap_or(*chosen), self.revealed);
+                let mut value = Line::from(format!("  {}", shown));
+                if side == Some(*chosen) {
+                    value = value.green().bold();
+                }
+                let label = match index == self.field {
+                    true => Line::from(field.label().to_string()).reversed(),
+                    false => Line::from(field.label().to_string()).dim(),
+                };
+                [label, value]
+            }).collect();
+            frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(title)), area);
+        }
+    }
+    
+    /// Résolution plein écran, jusqu'au dernier conflit ou jusqu'à ce que l'utilisateur quitte
+    pub(super) fn decisions(conflicts: &[Conflict]) -> Result<Decisions, String> {
+        let mut resolver = Resolver::new(conflicts);
+        let mut terminal = ratatui::init();
+        let result = run(&mut terminal, &mut resolver, conflicts);
+        ratatui::restore();
+        
+        result?;
+        Ok(resolver.decisions)
+    }
+    
+    fn run(terminal: &mut DefaultTerminal, resolver: &mut Resolver, conflicts: &[Conflict]) -> Result<(), String> {
+        loop {
+            terminal.draw(|frame| resolver.draw(frame, conflicts)).map_err(|e| e.to_string())?;
+            
+            let Event::Key(key) = event::read().map_err(|e| e.to_string())? else {
+                continue;
+            };
+            if key.kind == KeyEventKind::Press && resolver.handle_key(conflicts, key) {
+                return Ok(());
+            }
+        }
+    }
+    
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::models::database::Database;
+        use crate::models::entry::Entry;
+        use crate::models::merge::conflicting_fields;
+        
+        fn key(code: KeyCode) -> KeyEvent {
+            KeyEvent::new(code, KeyModifiers::NONE)
+        }
+        
+        #[test]
+        fn test_pick_fields_per_conflict() {
+            let mut db = Database::new("Test".to_string(), String::new());
+            let mail = db.add_entry(None, Entry::new("Gmail".to_string(), "bob".to_string(), "v1".to_string(), String::new(), String::new())).unwrap();
+            let local = db.find_entry(&mail).unwrap().clone();
+            let mut remote = local.clone();
+            remote.update(None, Some("robert".to_string()), Some("v2".to_string()), None, None);
+            let conflict = || Conflict { fields: conflicting_fields(&local, &remote), local: local.clone(), remote: remote.clone() };
+            let conflicts = [conflict(), conflict()];
+            
+            let mut resolver = Resolver::new(&conflicts);
+            assert_eq!(resolver.sides, vec![Side::Local, Side::Local]);
+            resolver.handle_key(&conflicts, key(KeyCode::Down));
+            resolver.handle_key(&conflicts, key(KeyCode::Right));
+            resolver.handle_key(&conflicts, key(KeyCode::Char('v')));
+            assert!(!resolver.handle_key(&conflicts, key(KeyCode::Enter)));
+            assert!(resolver.handle_key(&conflicts, key(KeyCode::Char('p'))));
+            
+            assert_eq!(resolver.decisions.chosen, vec![Decision::Resolve(vec![Side::Local, Side::Remote]), Decision::Skip]);
+            assert_eq!(resolver.decisions.revealed, vec![mail]);
+        }
+    }
+}
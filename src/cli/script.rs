@@ -0,0 +1,261 @@
+#[cfg(not(feature = "scripting"))]
+use std::path::Path;
+
+/// Exécute un script Rhai sur la base de données déverrouillée
+#[cfg(not(feature = "scripting"))]
+pub fn cmd_script_run(_db_path: &Path, _script: &Path, _args: Vec<String>) -> Result<(), String> {
+    Err("Cette version de keeprust a été compilée sans le support des scripts (fonctionnalité 'scripting').".to_string())
+}
+
+#[cfg(feature = "scripting")]
+pub use engine::cmd_script_run;
+
+#[cfg(feature = "scripting")]
+mod engine {
+    use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Scope};
+    use std::cell::RefCell;
+    use std::fs;
+    use std::path::Path;
+    use std::rc::Rc;
+    
+    use crate::cli::commands;
+    use crate::cli::policy;
+    use crate::cli::session::{self, Session};
+    use crate::cli::strength;
+    use crate::models::database::Database;
+    use crate::models::entry::{Entry, is_valid_url};
+    use crate::models::id::EntryId;
+    use crate::models::query::{Query, QueryContext};
+    use crate::models::walker::WalkControl;
+    use crate::utils::password_analyzer::PasswordAnalyzer;
+    use crate::utils::password_generator::PasswordGenerator;
+    
+    /// Nombre maximal d'opérations d'un script (évite les boucles infinies)
+    const MAX_OPERATIONS: u64 = 50_000_000;
+    
+    /// Champs modifiables par `update()`
+    const EDITABLE_FIELDS: &[&str] = &["title", "username", "url", "notes", "tags"];
+    
+    /// Modification demandée par un script, appliquée après confirmation
+    #[derive(Debug, Default)]
+    struct PendingUpdate {
+        id: EntryId,
+        title: Option<String>,
+        username: Option<String>,
+        url: Option<String>,
+        notes: Option<String>,
+        tags: Option<Vec<String>>,
+    }
+    
+    /// État partagé entre les fonctions exposées au script
+    struct ScriptContext {
+        db: Database,
+        analyzer: PasswordAnalyzer,
+        updates: RefCell<Vec<PendingUpdate>>,
+    }
+    
+    impl ScriptContext {
+        /// Représentation d'une entrée pour les scripts (le mot de passe n'est jamais exposé)
+        fn entry_map(&self, path: &[String], entry: &Entry) -> Dynamic {
+            let analysis = self.analyzer.analyze(&entry.password);
+            
+            let mut map = Map::new();
+            map.insert("id".into(), entry.id.to_string().into());
+            map.insert("title".into(), entry.title.clone().into());
+            map.insert("username".into(), entry.username.clone().into());
+            map.insert("url".into(), entry.url.clone().into());
+            map.insert("notes".into(), entry.notes.clone().into());
+            map.insert("tags".into(), entry.tags.iter().cloned().map(Dynamic::from).collect::<Array>().into());
+            map.insert("group".into(), path.join("/").into());
+            map.insert("strength".into(), analysis.strength.to_string().into());
+            map.insert("score".into(), (analysis.score as i64).into());
+            map.insert("created_at".into(), entry.created_at.to_rfc3339().into());
+            map.insert("updated_at".into(), entry.updated_at.to_rfc3339().into());
+            map.insert("archived".into(), entry.archived.into());
+            map.insert("color".into(), entry.color.map(|color| Dynamic::from(color.to_string())).unwrap_or(Dynamic::UNIT));
+            map.insert("expires_at".into(), entry.expires_at.map(|date| Dynamic::from(date.to_rfc3339())).unwrap_or(Dynamic::UNIT));
+            map.into()
+        }
+        
+        /// Toutes les entrées hors corbeille (sauf avec --include-trash), dans l'ordre du parcours
+        fn entries(&self) -> Array {
+            let trash = self.db.metadata.trash_group_id.filter(|_| !session::include_trash());
+            let mut entries = Array::new();
+            self.db.walk(|path, group, entry| {
+                if Some(group.id) == trash {
+                    return WalkControl::SkipChildren;
+                }
+                if let Some(entry) = entry {
+                    entries.push(self.entry_map(path, entry));
+                }
+                WalkControl::Continue
+            });
+            entries
+        }
+        
+        /// Entrées satisfaisant une requête (même syntaxe que `list --query`)
+        fn search(&self, query: &str) -> Result<Array, Box<EvalAltResult>> {
+            let query = Query::parse(query).map_err(|e| e.to_string())?;
+            let context = QueryContext::with_analyzer(self.analyzer.clone()).including_trash(session::include_trash());
+            Ok(self.db.find_entries_with(&context, &query).iter()
+                .map(|found| self.entry_map(&found.path, found.entry))
+                .collect())
+        }
+        
+        /// Enregistre une modification, validée immédiatement mais appliquée à la fin du script
+        fn update(&self, id: &str, changes: Map) -> Result<(), Box<EvalAltResult>> {
+            let id: EntryId = id.parse().map_err(|_| format!("ID d'entrée invalide: {}", id))?;
+            if self.db.find_entry(&id).is_none() {
+                return Err(format!("Entrée avec ID '{}' non trouvée", id).into());
+            }
+            
+            let mut update = PendingUpdate { id, ..Default::default() };
+            for (field, value) in changes {
+                let as_string = |value: Dynamic| value.into_string()
+                    .map_err(|kind| format!("Le champ '{}' doit être une chaîne (reçu: {})", field, kind));
+                
+                match field.as_str() {
+                    "title" => {
+                        let title = as_string(value)?;
+                        if title.trim().is_empty() {
+                            return Err("Le titre ne peut pas être vide".into());
+                        }
+                        update.title = Some(title);
+                    }
+                    "username" => update.username = Some(as_string(value)?),
+                    "url" => {
+                        let url = as_string(value)?;
+                        if !url.is_empty() && !is_valid_url(&url) {
+                            return Err(format!("URL invalide: {}", url).into());
+                        }
+                        update.url = Some(url);
+                    }
+                    "notes" => update.notes = Some(as_string(value)?),
+                    "tags" => {
+                        let tags = value.into_typed_array::<rhai::ImmutableString>()
+                            .map_err(|_| "Le champ 'tags' doit être un tableau de chaînes".to_string())?;
+                        update.tags = Some(tags.into_iter().map(|tag| tag.to_string()).collect());
+                    }
+                    other => {
+                        return Err(format!("Champ non modifiable: '{}' (champs possibles: {})", other, EDITABLE_FIELDS.join(", ")).into());
+                    }
+                }
+            }
+            
+            self.updates.borrow_mut().push(update);
+            Ok(())
+        }
+    }
+    
+    /// Crée le moteur avec les fonctions accessibles aux scripts
+    fn build_engine(context: &Rc<ScriptContext>) -> Engine {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.disable_symbol("eval");
+        
+        let ctx = Rc::clone(context);
+        engine.register_fn("entries", move || ctx.entries());
+        
+        let ctx = Rc::clone(context);
+        engine.register_fn("search", move |query: &str| ctx.search(query));
+        
+        let ctx = Rc::clone(context);
+        engine.register_fn("update", move |id: &str, changes: Map| ctx.update(id, changes));
+        
+        engine.register_fn("generate", |length: i64| -> Result<String, Box<EvalAltResult>> {
+            let length = usize::try_from(length).map_err(|_| "Longueur invalide".to_string())?;
+            PasswordGenerator::new().length(length).generate().map_err(|e| e.to_string().into())
+        });
+        
+        engine
+    }
+    
+    /// Exécute un script Rhai sur la base de données déverrouillée.
+    ///
+    /// Les scripts peuvent lire et rechercher les entrées (sans leurs mots de passe), générer des
+    /// mots de passe et demander des modifications, appliquées seulement après confirmation.
+    pub fn cmd_script_run(db_path: &Path, script: &Path, args: Vec<String>) -> Result<(), String> {
+        // Lire le script avant de demander le mot de passe
+        let source = fs::read_to_string(script)
+            .map_err(|e| format!("Impossible de lire le script {}: {}", script.display(), e))?;
+        
+        let mut session = Session::open(db_path)?;
+        
+        let context = Rc::new(ScriptContext {
+            db: session.db.clone(),
+            analyzer: strength::analyzer()?,
+            updates: RefCell::new(Vec::new()),
+        });
+        let engine = build_engine(&context);
+        
+        let mut scope = Scope::new();
+        scope.push_constant("ARGS", args.into_iter().map(Dynamic::from).collect::<Array>());
+        
+        engine.run_with_scope(&mut scope, &source)
+            .map_err(|e| format!("Erreur dans le script {}: {}", script.display(), e))?;
+        
+        let mut updates = context.updates.take();
+        commands::skip_locked(&session.db, &mut updates, |update| update.id);
+        if updates.is_empty() {
+            return session.close();
+        }
+        
+        println!("\nLe script demande {} modification(s):", updates.len());
+        for update in &updates {
+            if let Some(entry) = session.db.find_entry(&update.id) {
+                println!("  - {} (ID: {})", entry.title, entry.id);
+            }
+        }
+        
+        if !session::confirm("Appliquer ces modifications?", false)? {
+            println!("Modifications abandonnées.");
+            return session.close();
+        }
+        
+        let ids: Vec<EntryId> = updates.iter().map(|update| update.id).collect();
+        for update in updates {
+            session.db.update_entry(&update.id, |entry| {
+                let before = entry.clone();
+                if let Some(title) = update.title { entry.title = title; }
+                if let Some(username) = update.username { entry.username = username; }
+                if let Some(url) = update.url { entry.url = url; }
+                if let Some(notes) = update.notes { entry.notes = notes; }
+                if let Some(tags) = update.tags { entry.tags = tags; }
+                entry.check_written_sizes(Some(&before))
+            }).map_err(|e| e.to_string())?.map_err(policy::oversized_error)?;
+        }
+        policy::warn_oversized(&session.db, &ids);
+        
+        session.save()?;
+        println!("Modifications enregistrées.");
+        session.close()
+    }
+    
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        
+        #[test]
+        fn test_script_reads_entries_and_queues_updates() {
+            let mut db = Database::new("Test".to_string(), String::new());
+            let id = db.add_entry(None, Entry::new("Mail".to_string(), "bob".to_string(), "123456".to_string(), String::new(), String::new())).unwrap();
+            
+            let context = Rc::new(ScriptContext { db, analyzer: PasswordAnalyzer::new(), updates: RefCell::new(Vec::new()) });
+            let engine = build_engine(&context);
+            
+            let count: i64 = engine.eval(r#"
+                for entry in search("strength:<medium") {
+                    update(entry.id, #{ username: entry.username + "@example.org" });
+                }
+                entries().len()
+            "#).unwrap();
+            assert_eq!(count, 1);
+            
+            let updates = context.updates.borrow();
+            assert_eq!(updates[0].id, id);
+            assert_eq!(updates[0].username.as_deref(), Some("bob@example.org"));
+            assert!(engine.run(r#"update(entries()[0].id, #{ password: "x" })"#).is_err());
+            assert!(engine.eval::<Dynamic>(r#"entries()[0].password"#).unwrap().is_unit());
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,612 @@
+#[cfg(not(feature = "secret-service"))]
+use std::path::Path;
+
+/// Expose la base sur le bus de session D-Bus (org.freedesktop.secrets) pour les applications
+/// utilisant libsecret
+#[cfg(not(feature = "secret-service"))]
+pub fn cmd_service(_path: &Path, _lock_after: u64, _groups: &[String]) -> Result<(), String> {
+    Err("Cette version de keeprust a été compilée sans le fournisseur Secret Service (fonctionnalité 'secret-service').".to_string())
+}
+
+#[cfg(feature = "secret-service")]
+pub use provider::cmd_service;
+
+#[cfg(feature = "secret-service")]
+mod provider {
+    use serde::{Deserialize, Serialize};
+    use std::collections::{BTreeMap, HashMap};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex, MutexGuard};
+    use std::thread;
+    use std::time::{Duration, Instant, SystemTime};
+    use zbus::object_server::SignalEmitter;
+    use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Type, Value};
+    use zbus::{Connection, DBusError, ObjectServer, fdo, interface};
+    
+    use crate::events::{self, SecurityEvent};
+    use crate::models::exposure::{ExposureInterface, ExposureKind};
+    use crate::models::id::EntryId;
+    use crate::models::scope::{GroupScope, ScopeError};
+    use crate::models::secret_service::{self, SECRET_SERVICE_GROUP};
+    use crate::utils::symbols::Symbol;
+    
+    use crate::cli::session::Session;
+    
+    /// Nom réservé sur le bus de session par les fournisseurs Secret Service
+    const BUS_NAME: &str = "org.freedesktop.secrets";
+    
+    const SERVICE_PATH: &str = "/org/freedesktop/secrets";
+    
+    /// Unique collection exposée : la base ouverte
+    const COLLECTION_PATH: &str = "/org/freedesktop/secrets/collection/keeprust";
+    
+    /// Alias « default », utilisé par libsecret lorsqu'aucune collection n'est indiquée
+    const DEFAULT_ALIAS_PATH: &str = "/org/freedesktop/secrets/aliases/default";
+    
+    /// Chemin signifiant « aucun objet » (par exemple : aucune invite nécessaire)
+    const NO_OBJECT: &str = "/";
+    
+    /// Seul algorithme d'échange proposé : les secrets transitent en clair sur le bus de session,
+    /// accessible aux seuls processus de l'utilisateur
+    const PLAIN_ALGORITHM: &str = "plain";
+    
+    const LABEL_PROPERTY: &str = "org.freedesktop.Secret.Item.Label";
+    const ATTRIBUTES_PROPERTY: &str = "org.freedesktop.Secret.Item.Attributes";
+    
+    /// Nom du client dans les événements access-denied (les applications du bus sont anonymes)
+    const CLIENT_NAME: &str = "secret-service";
+    
+    /// Erreurs définies par la spécification Secret Service
+    #[derive(DBusError, Debug)]
+    #[zbus(prefix = "org.freedesktop.Secret.Error")]
+    enum SecretError {
+        #[zbus(error)]
+        ZBus(zbus::Error),
+        IsLocked(String),
+        NoSession(String),
+        NoSuchObject(String),
+    }
+    
+    impl SecretError {
+        fn locked() -> Self {
+            SecretError::IsLocked("La base keeprust est verrouillée".to_string())
+        }
+        
+        fn not_supported(message: &str) -> Self {
+            SecretError::ZBus(zbus::Error::FDO(Box::new(fdo::Error::NotSupported(message.to_string()))))
+        }
+        
+        fn failed(message: String) -> Self {
+            SecretError::ZBus(zbus::Error::FDO(Box::new(fdo::Error::Failed(message))))
+        }
+        
+        fn denied(message: String) -> Self {
+            SecretError::ZBus(zbus::Error::FDO(Box::new(fdo::Error::AccessDenied(message))))
+        }
+    }
+    
+    /// Secret transmis sur le bus : session, paramètres de l'algorithme, valeur et type de contenu
+    #[derive(Debug, Serialize, Deserialize, Type)]
+    struct Secret {
+        session: OwnedObjectPath,
+        parameters: Vec<u8>,
+        value: Vec<u8>,
+        content_type: String,
+    }
+    
+    /// Objet D-Bus d'une entrée (les tirets de l'UUID ne sont pas permis dans un chemin)
+    fn item_path(id: &EntryId) -> OwnedObjectPath {
+        OwnedObjectPath::try_from(format!("{}/{}", COLLECTION_PATH, id.to_string().replace('-', "_"))).expect("chemin d'objet valide")
+    }
+    
+    /// Entrée désignée par un chemin d'objet
+    fn item_id(path: &ObjectPath<'_>) -> Option<EntryId> {
+        path.as_str().strip_prefix(COLLECTION_PATH)?.strip_prefix('/')?.replace('_', "-").parse().ok()
+    }
+    
+    fn object_path(path: &str) -> OwnedObjectPath {
+        OwnedObjectPath::try_from(path.to_string()).expect("chemin d'objet valide")
+    }
+    
+    /// État partagé par les objets exposés
+    struct Vault {
+        path: PathBuf,
+        
+        /// Base déverrouillée; None lorsqu'elle est verrouillée
+        session: Option<Session>,
+        
+        /// Date de modification du fichier lors du dernier chargement ou enregistrement
+        modified: Option<SystemTime>,
+        
+        /// Sessions Secret Service ouvertes par les applications
+        sessions: Vec<OwnedObjectPath>,
+        
+        /// Numéro du prochain objet temporaire (session, invite)
+        next_object: u64,
+        
+        /// Dernière requête d'une application, pour le verrouillage après inactivité
+        last_used: Instant,
+        
+        /// Groupes exposés (--scope); tous si None
+        scope: Option<GroupScope>,
+    }
+    
+    type Shared = Arc<Mutex<Vault>>;
+    
+    /// Accès à l'état partagé (un verrou empoisonné n'empêche pas de verrouiller la base)
+    fn vault(shared: &Shared) -> MutexGuard<'_, Vault> {
+        shared.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+    
+    impl Vault {
+        fn file_modified(&self) -> Option<SystemTime> {
+            fs::metadata(&self.path).and_then(|metadata| metadata.modified()).ok()
+        }
+        
+        /// Session déverrouillée, relue si le fichier a été modifié par une autre commande
+        fn unlocked(&mut self) -> Result<&mut Session, SecretError> {
+            self.last_used = Instant::now();
+            let modified = self.file_modified();
+            let session = self.session.as_mut().ok_or_else(SecretError::locked)?;
+            if modified != self.modified && !session.db.is_dirty() {
+                session.reload().map_err(SecretError::failed)?;
+                self.modified = modified;
+            }
+            Ok(session)
+        }
+        
+        /// Enregistre les modifications de la base
+        fn save(&mut self) -> Result<(), SecretError> {
+            let session = self.session.as_mut().ok_or_else(SecretError::locked)?;
+            session.save().map_err(SecretError::failed)?;
+            Ok(())
+        }
+        
+        /// Éléments visibles dont les attributs contiennent ceux de la recherche
+        fn search(&mut self, query: &HashMap<String, String>) -> Result<Vec<EntryId>, SecretError> {
+            let scope = self.scope.clone();
+            Ok(self.unlocked()?.db.secret_items(scope.as_ref()).into_iter()
+                .filter(|(_, attributes)| secret_service::matches(attributes, query))
+                .map(|(id, _)| id)
+                .collect())
+        }
+        
+        /// Refus d'un accès hors de la portée, signalé aux hooks (access-denied)
+        fn deny(&self, entry: String) -> SecretError {
+            let event = SecurityEvent::AccessDenied { client: CLIENT_NAME.to_string(), entry };
+            eprintln!("{}{}", Symbol::Warning, event);
+            events::emit(&self.path, &event);
+            SecretError::denied(event.to_string())
+        }
+        
+        /// Attributs d'une entrée visible; une entrée hors de la portée est refusée
+        fn attributes(&mut self, id: &EntryId) -> Result<BTreeMap<String, String>, SecretError> {
+            let scope = self.scope.clone();
+            if let Some(scope) = &scope
+                && let Err(ScopeError::Denied(_)) = self.unlocked()?.db.find_entry_in_scope(scope, id)
+            {
+                return Err(self.deny(id.to_string()));
+            }
+            self.unlocked()?.db.secret_items(scope.as_ref()).into_iter()
+                .find(|(item, _)| item == id)
+                .map(|(_, attributes)| attributes)
+                .ok_or_else(|| SecretError::NoSuchObject(format!("Entrée {} introuvable", id)))
+        }
+        
+        fn check_session(&self, session: &ObjectPath<'_>) -> Result<(), SecretError> {
+            if self.sessions.iter().any(|open| open.as_str() == session.as_str()) {
+                Ok(())
+            } else {
+                Err(SecretError::NoSession(format!("Session {} inconnue", session)))
+            }
+        }
+        
+        /// Mots de passe des entrées, dont l'exposition est consignée dans le journal de la base
+        fn secrets(&mut self, ids: &[EntryId], session: &ObjectPath<'_>) -> Result<Vec<(EntryId, Secret)>, SecretError> {
+            self.check_session(session)?;
+            let mut secrets = Vec::new();
+            for id in ids {
+                // Seules les entrées hors corbeille sont exposées
+                self.attributes(id)?;
+                let unlocked = self.unlocked()?;
+                let entry = unlocked.db.find_entry(id).ok_or_else(|| SecretError::NoSuchObject(id.to_string()))?;
+                secrets.push((*id, Secret {
+                    session: session.to_owned().into(),
+                    parameters: Vec::new(),
+                    value: entry.password.as_bytes().to_vec(),
+                    content_type: "text/plain; charset=utf8".to_string(),
+                }));
+            }
+            
+            self.unlocked()?.record_exposure(ids, ExposureKind::Provided, ExposureInterface::SecretService).map_err(SecretError::failed)?;
+            Ok(secrets)
+        }
+        
+        /// Verrouille la base : le mot de passe maître et les données déchiffrées sont effacés
+        fn lock(&mut self) {
+            if let Some(session) = self.session.take() {
+                match session.lock() {
+                    Ok(_) => eprintln!("Base verrouillée."),
+                    Err(e) => eprintln!("{}{}", Symbol::Warning, e),
+                }
+            }
+        }
+        
+        fn new_object(&mut self, kind: &str) -> OwnedObjectPath {
+            self.next_object += 1;
+            object_path(&format!("{}/{}/s{}", SERVICE_PATH, kind, self.next_object))
+        }
+    }
+    
+    /// Publie les objets des entrées renvoyées aux applications (sans effet s'ils existent déjà)
+    async fn publish(server: &ObjectServer, shared: &Shared, ids: &[EntryId]) -> Result<Vec<OwnedObjectPath>, SecretError> {
+        let mut paths = Vec::new();
+        for id in ids {
+            let path = item_path(id);
+            server.at(&path, Item { id: *id, shared: shared.clone() }).await?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+    
+    /// Publie les objets de toutes les entrées de la base déverrouillée
+    fn publish_all(server: &zbus::blocking::ObjectServer, shared: &Shared) -> zbus::Result<()> {
+        let ids = vault(shared).search(&HashMap::new()).unwrap_or_default();
+        for id in ids {
+            server.at(item_path(&id), Item { id, shared: shared.clone() })?;
+        }
+        Ok(())
+    }
+    
+    /// org.freedesktop.Secret.Service
+    struct Service {
+        shared: Shared,
+    }
+    
+    #[interface(name = "org.freedesktop.Secret.Service")]
+    impl Service {
+        async fn open_session(&self, algorithm: &str, _input: Value<'_>, #[zbus(object_server)] server: &ObjectServer) -> Result<(OwnedValue, OwnedObjectPath), SecretError> {
+            if algorithm != PLAIN_ALGORITHM {
+                return Err(SecretError::not_supported("Seul l'algorithme 'plain' est pris en charge"));
+            }
+            let path = vault(&self.shared).new_object("session");
+            server.at(&path, SecretSession { shared: self.shared.clone(), path: path.clone() }).await?;
+            vault(&self.shared).sessions.push(path.clone());
+            Ok((OwnedValue::from(zbus::zvariant::Str::from("")), path))
+        }
+        
+        async fn create_collection(&self, _properties: HashMap<String, OwnedValue>, _alias: &str) -> (OwnedObjectPath, OwnedObjectPath) {
+            // Une seule collection : la base ouverte
+            (object_path(COLLECTION_PATH), object_path(NO_OBJECT))
+        }
+        
+        async fn search_items(&self, attributes: HashMap<String, String>, #[zbus(object_server)] server: &ObjectServer) -> Result<(Vec<OwnedObjectPath>, Vec<OwnedObjectPath>), SecretError> {
+            // Une base verrouillée ne révèle pas ses entrées : les applications la déverrouillent d'abord
+            let found = match vault(&self.shared).search(&attributes) {
+                Ok(found) => found,
+                Err(SecretError::IsLocked(_)) => return Ok((Vec::new(), Vec::new())),
+                Err(e) => return Err(e),
+            };
+            Ok((publish(server, &self.shared, &found).await?, Vec::new()))
+        }
+        
+        async fn unlock(&self, objects: Vec<OwnedObjectPath>, #[zbus(object_server)] server: &ObjectServer) -> Result<(Vec<OwnedObjectPath>, OwnedObjectPath), SecretError> {
+            let path = {
+                let mut vault = vault(&self.shared);
+                if vault.session.is_some() {
+                    vault.last_used = Instant::now();
+                    return Ok((objects, object_path(NO_OBJECT)));
+                }
+                vault.new_object("prompt")
+            };
+            server.at(&path, Prompt { shared: self.shared.clone(), path: path.clone(), objects }).await?;
+            Ok((Vec::new(), path))
+        }
+        
+        async fn lock(&self, objects: Vec<OwnedObjectPath>) -> (Vec<OwnedObjectPath>, OwnedObjectPath) {
+            vault(&self.shared).lock();
+            (objects, object_path(NO_OBJECT))
+        }
+        
+        async fn get_secrets(&self, items: Vec<OwnedObjectPath>, session: ObjectPath<'_>) -> Result<HashMap<OwnedObjectPath, Secret>, SecretError> {
+            let ids: Vec<EntryId> = items.iter().filter_map(|path| item_id(path)).collect();
+            let secrets = vault(&self.shared).secrets(&ids, &session)?;
+            Ok(secrets.into_iter().map(|(id, secret)| (item_path(&id), secret)).collect())
+        }
+        
+        async fn read_alias(&self, name: &str) -> OwnedObjectPath {
+            object_path(if name == "default" { COLLECTION_PATH } else { NO_OBJECT })
+        }
+        
+        async fn set_alias(&self, _name: &str, _collection: ObjectPath<'_>) -> Result<(), SecretError> {
+            Err(SecretError::not_supported("Les alias de keeprust ne sont pas modifiables"))
+        }
+        
+        #[zbus(property)]
+        async fn collections(&self) -> Vec<OwnedObjectPath> {
+            vec![object_path(COLLECTION_PATH)]
+        }
+    }
+    
+    /// org.freedesktop.Secret.Collection : la base ouverte
+    struct Collection {
+        shared: Shared,
+    }
+    
+    #[interface(name = "org.freedesktop.Secret.Collection")]
+    impl Collection {
+        async fn delete(&self) -> Result<OwnedObjectPath, SecretError> {
+            Err(SecretError::not_supported("La base keeprust ne peut pas être supprimée par une application"))
+        }
+        
+        async fn search_items(&self, attributes: HashMap<String, String>, #[zbus(object_server)] server: &ObjectServer) -> Result<Vec<OwnedObjectPath>, SecretError> {
+            let found = vault(&self.shared).search(&attributes)?;
+            publish(server, &self.shared, &found).await
+        }
+        
+        async fn create_item(&self, properties: HashMap<String, OwnedValue>, secret: Secret, replace: bool, #[zbus(object_server)] server: &ObjectServer) -> Result<(OwnedObjectPath, OwnedObjectPath), SecretError> {
+            let label = properties.get(LABEL_PROPERTY).and_then(|value| String::try_from(value.try_clone().ok()?).ok()).unwrap_or_default();
+            let attributes = properties.get(ATTRIBUTES_PROPERTY)
+                .and_then(|value| HashMap::<String, String>::try_from(value.try_clone().ok()?).ok())
+                .unwrap_or_default();
+            let password = String::from_utf8(secret.value).map_err(|_| SecretError::not_supported("Seuls les secrets textuels (UTF-8) sont pris en charge"))?;
+            
+            let id = {
+                let mut vault = vault(&self.shared);
+                vault.check_session(&secret.session)?;
+                // Les éléments créés par les applications vont dans le groupe « Secret Service »
+                let scope = vault.scope.clone();
+                if scope.as_ref().is_some_and(|scope| !scope.allows_group(SECRET_SERVICE_GROUP)) {
+                    return Err(vault.deny(format!("'{}' (groupe {})", label, SECRET_SERVICE_GROUP)));
+                }
+                let session = vault.unlocked()?;
+                let (id, created) = session.db.store_secret_item(&label, &attributes, password, replace, scope.as_ref()).map_err(|e| SecretError::failed(e.to_string()))?;
+                vault.save()?;
+                if created {
+                    eprintln!("Entrée '{}' créée par une application (groupe {}).", label, SECRET_SERVICE_GROUP);
+                } else {
+                    eprintln!("Secret de '{}' remplacé par une application.", label);
+                }
+                id
+            };
+            let paths = publish(server, &self.shared, &[id]).await?;
+            Ok((paths[0].clone(), object_path(NO_OBJECT)))
+        }
+        
+        #[zbus(property)]
+        async fn items(&self) -> Vec<OwnedObjectPath> {
+            // Les objets sont publiés au déverrouillage (l'arbre des objets ne peut pas être
+            // modifié pendant la lecture d'une propriété)
+            vault(&self.shared).search(&HashMap::new()).unwrap_or_default().iter().map(item_path).collect()
+        }
+        
+        #[zbus(property)]
+        async fn label(&self) -> String {
+            let vault = vault(&self.shared);
+            let name = vault.session.as_ref().map(|session| session.db.metadata.name.clone());
+            name.unwrap_or_else(|| vault.path.display().to_string())
+        }
+        
+        #[zbus(property)]
+        async fn locked(&self) -> bool {
+            vault(&self.shared).session.is_none()
+        }
+        
+        #[zbus(property)]
+        async fn created(&self) -> u64 {
+            let vault = vault(&self.shared);
+            vault.session.as_ref().map_or(0, |session| session.db.metadata.created_at.timestamp().max(0) as u64)
+        }
+        
+        #[zbus(property)]
+        async fn modified(&self) -> u64 {
+            let vault = vault(&self.shared);
+            vault.session.as_ref().map_or(0, |session| session.db.metadata.updated_at.timestamp().max(0) as u64)
+        }
+    }
+    
+    /// org.freedesktop.Secret.Item : une entrée de la base
+    struct Item {
+        id: EntryId,
+        shared: Shared,
+    }
+    
+    #[interface(name = "org.freedesktop.Secret.Item")]
+    impl Item {
+        /// Place l'entrée dans la corbeille
+        async fn delete(&self, #[zbus(object_server)] server: &ObjectServer) -> Result<OwnedObjectPath, SecretError> {
+            {
+                let mut vault = vault(&self.shared);
+                vault.attributes(&self.id)?;
+                let session = vault.unlocked()?;
+                session.db.trash_entry(&self.id).map_err(|e| SecretError::failed(e.to_string()))?;
+                vault.save()?;
+            }
+            server.remove::<Item, _>(item_path(&self.id)).await?;
+            Ok(object_path(NO_OBJECT))
+        }
+        
+        async fn get_secret(&self, session: ObjectPath<'_>) -> Result<Secret, SecretError> {
+            let mut secrets = vault(&self.shared).secrets(&[self.id], &session)?;
+            secrets.pop().map(|(_, secret)| secret).ok_or_else(|| SecretError::NoSuchObject(self.id.to_string()))
+        }
+        
+        async fn set_secret(&self, secret: Secret) -> Result<(), SecretError> {
+            let password = String::from_utf8(secret.value).map_err(|_| SecretError::not_supported("Seuls les secrets textuels (UTF-8) sont pris en charge"))?;
+            let mut vault = vault(&self.shared);
+            vault.check_session(&secret.session)?;
+            vault.attributes(&self.id)?;
+            let session = vault.unlocked()?;
+            session.db.update_entry(&self.id, |entry| entry.update(None, None, Some(password), None, None)).map_err(|e| SecretError::failed(e.to_string()))?;
+            vault.save()
+        }
+        
+        #[zbus(property)]
+        async fn locked(&self) -> bool {
+            vault(&self.shared).session.is_none()
+        }
+        
+        #[zbus(property)]
+        async fn attributes(&self) -> fdo::Result<HashMap<String, String>> {
+            let attributes = vault(&self.shared).attributes(&self.id).map_err(|e| fdo::Error::Failed(e.to_string()))?;
+            Ok(attributes.into_iter().collect())
+        }
+        
+        #[zbus(property)]
+        async fn label(&self) -> fdo::Result<String> {
+            let attributes = vault(&self.shared).attributes(&self.id).map_err(|e| fdo::Error::Failed(e.to_string()))?;
+            Ok(attributes.get("title").cloned().unwrap_or_default())
+        }
+        
+        #[zbus(property)]
+        async fn created(&self) -> u64 {
+            self.timestamp(|entry| entry.created_at.timestamp())
+        }
+        
+        #[zbus(property)]
+        async fn modified(&self) -> u64 {
+            self.timestamp(|entry| entry.updated_at.timestamp())
+        }
+    }
+    
+    impl Item {
+        fn timestamp(&self, field: impl Fn(&crate::models::entry::Entry) -> i64) -> u64 {
+            let vault = vault(&self.shared);
+            vault.session.as_ref().and_then(|session| session.db.find_entry(&self.id)).map_or(0, |entry| field(entry).max(0) as u64)
+        }
+    }
+    
+    /// org.freedesktop.Secret.Session : canal d'échange ouvert par une application
+    struct SecretSession {
+        shared: Shared,
+        path: OwnedObjectPath,
+    }
+    
+    #[interface(name = "org.freedesktop.Secret.Session")]
+    impl SecretSession {
+        async fn close(&self, #[zbus(object_server)] server: &ObjectServer) -> fdo::Result<()> {
+            vault(&self.shared).sessions.retain(|open| *open != self.path);
+            server.remove::<SecretSession, _>(&self.path).await?;
+            Ok(())
+        }
+    }
+    
+    /// org.freedesktop.Secret.Prompt : déverrouillage demandé par une application, le mot de
+    /// passe maître étant saisi dans le terminal du service
+    struct Prompt {
+        shared: Shared,
+        path: OwnedObjectPath,
+        
+        /// Objets à déverrouiller, renvoyés à l'application une fois la base ouverte
+        objects: Vec<OwnedObjectPath>,
+    }
+    
+    #[interface(name = "org.freedesktop.Secret.Prompt")]
+    impl Prompt {
+        async fn prompt(&self, _window_id: &str, #[zbus(connection)] connection: &Connection) -> fdo::Result<()> {
+            let shared = self.shared.clone();
+            let path = self.path.clone();
+            let objects = self.objects.clone();
+            let connection = zbus::blocking::Connection::from(connection.clone());
+            
+            // La saisie du mot de passe ne doit pas bloquer les autres requêtes
+            thread::spawn(move || {
+                let path_for_open = vault(&shared).path.clone();
+                eprintln!("\n{}Une application demande le déverrouillage de {}.", Symbol::Heading("🔐"), path_for_open.display());
+                let dismissed = match Session::open(&path_for_open) {
+                    Ok(session) => {
+                        let mut vault = vault(&shared);
+                        vault.session = Some(session);
+                        vault.modified = vault.file_modified();
+                        vault.last_used = Instant::now();
+                        drop(vault);
+                        if let Err(e) = publish_all(&connection.object_server(), &shared) {
+                            eprintln!("{}{}", Symbol::Warning, e);
+                        }
+                        eprintln!("Base déverrouillée.");
+                        false
+                    }
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        true
+                    }
+                };
+                let result = if dismissed { Vec::new() } else { objects };
+                let emitted = connection.emit_signal(
+                    None::<()>,
+                    &path,
+                    "org.freedesktop.Secret.Prompt",
+                    "Completed",
+                    &(dismissed, Value::from(result)),
+                );
+                if let Err(e) = emitted {
+                    eprintln!("{}Réponse à l'application impossible: {}", Symbol::Warning, e);
+                }
+                let _ = connection.object_server().remove::<Prompt, _>(&path);
+            });
+            Ok(())
+        }
+        
+        async fn dismiss(&self, #[zbus(signal_emitter)] emitter: SignalEmitter<'_>, #[zbus(object_server)] server: &ObjectServer) -> fdo::Result<()> {
+            Prompt::completed(&emitter, true, Value::from(Vec::<OwnedObjectPath>::new())).await?;
+            server.remove::<Prompt, _>(&self.path).await?;
+            Ok(())
+        }
+        
+        #[zbus(signal)]
+        async fn completed(emitter: &SignalEmitter<'_>, dismissed: bool, result: Value<'_>) -> zbus::Result<()>;
+    }
+    
+    /// Fournit la base ouverte aux applications utilisant libsecret (navigateurs, clients de
+    /// messagerie...) sur le bus de session, jusqu'à l'interruption du processus.
+    ///
+    /// La base est verrouillée après `lock_after` minutes sans requête (0 : jamais); une
+    /// application la déverrouille ensuite par une invite, le mot de passe étant saisi ici.
+    /// Avec `scope`, seules les entrées de ces groupes sont exposées : les autres sont refusées
+    /// et chaque refus déclenche l'événement access-denied.
+    pub fn cmd_service(path: &Path, lock_after: u64, groups: &[String]) -> Result<(), String> {
+        let scope = if groups.is_empty() { None } else { Some(GroupScope::new(groups).map_err(|e| e.to_string())?) };
+        let session = Session::open(path)?;
+        let shared: Shared = Arc::new(Mutex::new(Vault {
+            path: path.to_path_buf(),
+            session: Some(session),
+            modified: fs::metadata(path).and_then(|metadata| metadata.modified()).ok(),
+            sessions: Vec::new(),
+            next_object: 0,
+            last_used: Instant::now(),
+            scope,
+        }));
+        
+        let connection = zbus::blocking::connection::Builder::session()
+            .and_then(|builder| builder.name(BUS_NAME))
+            .and_then(|builder| builder.serve_at(SERVICE_PATH, Service { shared: shared.clone() }))
+            .and_then(|builder| builder.serve_at(COLLECTION_PATH, Collection { shared: shared.clone() }))
+            .and_then(|builder| builder.serve_at(DEFAULT_ALIAS_PATH, Collection { shared: shared.clone() }))
+            .and_then(|builder| builder.build())
+            .map_err(|e| format!(
+                "Impossible de publier {} sur le bus de session: {}\n(un autre fournisseur, comme gnome-keyring ou KWallet, est peut-être actif)",
+                BUS_NAME, e
+            ))?;
+        publish_all(&connection.object_server(), &shared).map_err(|e| e.to_string())?;
+        
+        println!("Fournisseur Secret Service actif ({}) pour {}.", BUS_NAME, path.display());
+        if !groups.is_empty() {
+            println!("Groupes exposés: {}.", groups.join(", "));
+        }
+        if lock_after > 0 {
+            println!("Verrouillage après {} minute(s) sans requête. Ctrl+C pour arrêter.", lock_after);
+        } else {
+            println!("Ctrl+C pour arrêter.");
+        }
+        
+        let idle_limit = Duration::from_secs(lock_after * 60);
+        loop {
+            thread::sleep(Duration::from_secs(1));
+            let mut vault = vault(&shared);
+            if lock_after > 0 && vault.session.is_some() && vault.last_used.elapsed() >= idle_limit {
+                vault.lock();
+            }
+        }
+    }
+}
@@ -0,0 +1,774 @@
+use chrono::{DateTime, Duration, Utc};
+use rpassword::read_password;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use zeroize::Zeroizing;
+
+use crate::events::{self, SecurityEvent};
+use crate::crypto::{key_derivation::{self, KdfParams}, keyfile};
+use crate::models::{database::{Database, DatabaseError}, recovery, repository::{Fetch, FileKey, MIN_ITERATIONS, Repository, RepositoryError, Timings}};
+use crate::models::exposure::{self, ExposureInterface, ExposureKind, ExposureRecord};
+use crate::models::entry::Entry;
+use crate::models::id::EntryId;
+use crate::models::lookup::EntryRef;
+use crate::models::saving::{self, LoadedIndex};
+use crate::models::search_index::{self, IndexKey, IndexUpdate, SearchIndex, SearchIndexError};
+use crate::models::signing;
+use crate::models::stats::DatabaseStats;
+use crate::models::trash::EntryState;
+use crate::utils::symbols::Symbol;
+
+use super::agent;
+use super::entry_picker;
+use super::git_sync;
+
+/// Variable d'environnement fournissant le mot de passe maître aux scripts
+pub const PASSWORD_ENV: &str = "KEEPRUST_PASSWORD";
+
+/// Options communes à toutes les sessions, issues de la ligne de commande
+#[derive(Debug, Clone, Default)]
+pub struct SessionOptions {
+    /// Affiche la durée des étapes de chargement et d'enregistrement
+    pub timings: bool,
+    
+    /// Lit les mots de passe sur l'entrée standard, une ligne par mot de passe demandé
+    pub password_stdin: bool,
+    
+    /// Fichier dont la première ligne est le mot de passe maître (--password-file)
+    pub password_file: Option<PathBuf>,
+    
+    /// Répond oui à toutes les confirmations
+    pub assume_yes: bool,
+    
+    /// Ouvre en lecture seule la dernière version de la base enregistrée au plus tard ce jour-là (--as-of)
+    pub as_of: Option<DateTime<Utc>>,
+    
+    /// Nombre minimal d'itérations PBKDF2 en dessous duquel un avertissement est affiché à l'ouverture
+    pub min_iterations: Option<u32>,
+    
+    /// Fichier clé combiné au mot de passe maître (--keyfile)
+    pub keyfile: Option<PathBuf>,
+    
+    /// Inclut les entrées de la corbeille dans les recherches et accepte leurs IDs (--include-trash)
+    pub include_trash: bool,
+}
+
+static OPTIONS: OnceLock<SessionOptions> = OnceLock::new();
+
+/// Session gardée déverrouillée entre les commandes du shell interactif
+static KEPT: Mutex<Option<Session>> = Mutex::new(None);
+
+/// Définit les options des sessions (à appeler une fois, avant toute ouverture)
+pub fn configure(options: SessionOptions) {
+    let _ = OPTIONS.set(options);
+}
+
+/// Options des sessions en vigueur
+fn options() -> &'static SessionOptions {
+    OPTIONS.get_or_init(SessionOptions::default)
+}
+
+/// Crée un relevé de durées, actif si `--timings` a été demandé
+pub fn timings() -> Timings {
+    if options().timings { Timings::new() } else { Timings::disabled() }
+}
+
+/// Affiche un relevé de durées sur la sortie d'erreur s'il contient des mesures
+pub fn report_timings(operation: &str, timings: &Timings) {
+    if !timings.steps.is_empty() {
+        eprintln!("Durées ({}):\n{}", operation, timings);
+    }
+}
+
+/// Indique si l'entrée standard est un terminal; sinon aucune question n'est posée
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal()
+}
+
+/// Indique si les entrées de la corbeille sont incluses (--include-trash)
+pub fn include_trash() -> bool {
+    options().include_trash
+}
+
+/// Indique si les mots de passe sont lus sur l'entrée standard (--password-stdin)
+pub fn password_from_stdin() -> bool {
+    options().password_stdin
+}
+
+/// Affiche une invite sur la sortie standard, ou sur la sortie d'erreur si celle-ci est redirigée
+pub(super) fn show_prompt(prompt: &str) -> Result<(), String> {
+    if io::stdout().is_terminal() {
+        print!("{}", prompt);
+        io::stdout().flush().map_err(|e| e.to_string())
+    } else {
+        eprint!("{}", prompt);
+        io::stderr().flush().map_err(|e| e.to_string())
+    }
+}
+
+/// Lit une ligne sur l'entrée standard, sans le retour à la ligne final
+fn read_stdin_line() -> Result<String, String> {
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input).map_err(|e| e.to_string())? == 0 {
+        return Err("Fin de l'entrée standard atteinte.".to_string());
+    }
+    
+    let trimmed = input.trim_end_matches(['\n', '\r']).len();
+    input.truncate(trimmed);
+    Ok(input)
+}
+
+/// Demande un mot de passe sans l'afficher.
+///
+/// Avec --password-stdin, le mot de passe est lu sur l'entrée standard; sans terminal et sans
+/// cette option, échoue immédiatement plutôt que d'attendre une saisie qui ne viendra pas.
+pub fn prompt_password(prompt: &str) -> Result<String, String> {
+    if options().password_stdin {
+        return read_stdin_line();
+    }
+    if !is_interactive() {
+        return Err(format!(
+            "Saisie impossible (« {} »): l'entrée standard n'est pas un terminal. Utilisez --password-stdin, --password-file ou la variable KEEPRUST_PASSWORD pour fournir le mot de passe.",
+            prompt.trim_end_matches([':', ' '])
+        ));
+    }
+    
+    show_prompt(prompt)?;
+    read_password().map_err(|e| e.to_string())
+}
+
+/// Mot de passe maître fourni sans saisie : --password-file, sinon la variable KEEPRUST_PASSWORD
+fn provided_master_password() -> Result<Option<String>, String> {
+    if let Some(path) = &options().password_file {
+        let content = Zeroizing::new(fs::read_to_string(path)
+            .map_err(|e| format!("Impossible de lire le fichier de mot de passe {}: {}", path.display(), e))?);
+        
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if fs::metadata(path).is_ok_and(|metadata| metadata.permissions().mode() & 0o077 != 0) {
+                eprintln!("{}{} est lisible par d'autres utilisateurs (chmod 600 recommandé).", Symbol::Warning, path.display());
+            }
+        }
+        return Ok(Some(content.lines().next().unwrap_or_default().to_string()));
+    }
+    
+    Ok(std::env::var(PASSWORD_ENV).ok().filter(|password| !password.is_empty()))
+}
+
+/// Indique si le mot de passe maître est fourni sans saisie au clavier (--password-stdin,
+/// --password-file ou KEEPRUST_PASSWORD) : aucune confirmation n'est alors demandée
+pub fn master_password_provided() -> bool {
+    let options = options();
+    options.password_stdin || options.password_file.is_some() || std::env::var_os(PASSWORD_ENV).is_some_and(|password| !password.is_empty())
+}
+
+/// Demande le mot de passe maître, sauf s'il est fourni par --password-file ou KEEPRUST_PASSWORD
+/// (--password-stdin garde la priorité : tous les mots de passe sont alors lus sur l'entrée standard)
+pub fn prompt_master_password(prompt: &str) -> Result<String, String> {
+    if !options().password_stdin
+        && let Some(password) = provided_master_password()?
+    {
+        return Ok(password);
+    }
+    prompt_password(prompt)
+}
+
+/// Secret maître transmis à la dérivation de clé : le mot de passe, combiné au contenu du
+/// fichier clé si --keyfile est utilisé (le mot de passe seul est alors effacé)
+pub fn master_secret(password: String) -> Result<Zeroizing<String>, String> {
+    let password = Zeroizing::new(password);
+    let Some(path) = &options().keyfile else {
+        return Ok(password);
+    };
+    
+    fs::read(path)
+        .map(Zeroizing::new)
+        .map_err(|e| format!("Impossible de lire le fichier clé {}: {}", path.display(), e))
+        .and_then(|keyfile| keyfile::combine(&password, &keyfile).map_err(|e| format!("{}: {}", e, path.display())))
+}
+
+/// Demande une saisie libre; échoue sans terminal
+pub fn prompt_line(prompt: &str) -> Result<String, String> {
+    if !is_interactive() {
+        return Err(format!(
+            "Saisie impossible (« {} »): l'entrée standard n'est pas un terminal.",
+            prompt.trim_end_matches([':', ' '])
+        ));
+    }
+    
+    show_prompt(prompt)?;
+    Ok(read_stdin_line()?.trim().to_string())
+}
+
+/// Pose une question fermée (o/n) et retourne la réponse, `default` si la réponse est vide ou inattendue.
+///
+/// Avec --yes la réponse est toujours oui; sans terminal, la réponse par défaut est retenue.
+pub fn confirm(question: &str, default: bool) -> Result<bool, String> {
+    if options().assume_yes {
+        return Ok(true);
+    }
+    if !is_interactive() {
+        eprintln!("{} -> {} (pas de terminal, réponse par défaut)", question, if default { "oui" } else { "non" });
+        return Ok(default);
+    }
+    
+    show_prompt(&format!("{} ({}): ", question, if default { "O/n" } else { "o/N" }))?;
+    let answer = read_stdin_line()?;
+    let answer = answer.trim();
+    
+    Ok(if answer.eq_ignore_ascii_case("o") || answer.eq_ignore_ascii_case("oui") {
+        true
+    } else if answer.eq_ignore_ascii_case("n") || answer.eq_ignore_ascii_case("non") {
+        false
+    } else {
+        default
+    })
+}
+
+/// Accès à la session gardée déverrouillée (un verrou empoisonné n'empêche pas de la verrouiller)
+fn kept() -> MutexGuard<'static, Option<Session>> {
+    KEPT.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Garde la session déverrouillée : les ouvertures suivantes de la même base réutilisent ses
+/// données déchiffrées sans redemander le mot de passe ni refaire la dérivation de clé
+pub fn keep_unlocked(session: Session) {
+    *kept() = Some(session);
+}
+
+/// Indique si une session est gardée déverrouillée
+pub fn is_kept_unlocked() -> bool {
+    kept().is_some()
+}
+
+/// Verrouille la session gardée déverrouillée : son mot de passe et ses données sont effacés
+pub fn lock_kept() -> Option<LockedSession> {
+    let session = kept().take()?;
+    session.lock().ok()
+}
+
+/// Verrouille la session gardée déverrouillée sans rien demander, si elle n'a aucune modification
+/// non enregistrée; retourne true si elle a été verrouillée
+pub fn lock_kept_quietly() -> bool {
+    let mut kept = kept();
+    if kept.as_ref().is_none_or(|session| session.db.is_dirty()) {
+        return false;
+    }
+    kept.take();
+    true
+}
+
+/// Message d'erreur d'ouverture; un mot de passe incorrect déclenche l'événement `failed-unlock`
+fn unlock_error(path: &Path, error: RepositoryError) -> String {
+    match error {
+        RepositoryError::InvalidPassword => {
+            events::emit(path, &SecurityEvent::FailedUnlock);
+            let hint = if options().keyfile.is_some() { " (ou fichier clé incorrect)" } else { "" };
+            format!("Erreur lors de l'ouverture de la base de données: {}{}", RepositoryError::InvalidPassword, hint)
+        }
+        e => format!(
+            "Erreur lors de l'ouverture de la base de données: {}\nLe fichier semble endommagé: essayez 'keeprust recover'.",
+            e
+        ),
+    }
+}
+
+/// Base de données déverrouillée pendant l'exécution d'une commande
+pub struct Session {
+    /// Repository utilisé pour relire et sauvegarder le fichier
+    repo: Repository,
+    
+    /// Mot de passe maître (effacé de la mémoire à la fermeture); None pour une base ouverte avec
+    /// la clé confiée à l'agent, qui l'enregistre sans changer de sel
+    password: Option<Zeroizing<String>>,
+    
+    /// Base de données déchiffrée
+    pub db: Database,
+    
+    /// Version antérieure ouverte avec --as-of : aucune modification ne peut être enregistrée
+    read_only: bool,
+    
+    /// Paramètres de dérivation de clé du fichier ouvert (mis à jour à chaque enregistrement)
+    kdf: KdfParams,
+    
+    /// Clé dérivée du fichier actuel, transmise à l'agent pour les ouvertures suivantes
+    key: FileKey,
+    
+    /// Index de recherche chiffré et sa clé, lus au premier enregistrement si la base en a un
+    index: Option<LoadedIndex>,
+}
+
+/// Base ouverte pour une recherche : son index s'il est à jour, sinon la base elle-même
+pub enum SearchSource {
+    Index(SearchIndex),
+    Session(Box<Session>),
+}
+
+impl Session {
+    /// Demande le mot de passe maître et ouvre la base de données
+    pub fn open(path: &Path) -> Result<Self, String> {
+        // Base WebDAV : le fichier ouvert est sa copie locale
+        let repo = Repository::new(path);
+        let path = repo.path().to_path_buf();
+        let path = path.as_path();
+        let refresh = options().as_of.is_none() && kept().as_ref().is_none_or(|kept| kept.repo.path() != path);
+        
+        // Base synchronisée avec git : récupérer d'abord les versions des autres appareils
+        let diverged = if refresh { git_sync::before_open(path) } else { None };
+        
+        // Base WebDAV : télécharger la version du serveur si elle a changé, sinon lire hors ligne
+        let offline = match refresh.then(|| repo.fetch()).transpose().map_err(|e| e.to_string())? {
+            Some(Fetch::Offline(reason)) => {
+                eprintln!("{}Serveur injoignable ({}): copie locale ouverte en lecture seule.", Symbol::Warning, reason);
+                true
+            }
+            _ => false,
+        };
+        
+        // Vérifier si le fichier existe
+        if !path.exists() {
+            return Err(format!("Le fichier {} n'existe pas.", path.display()));
+        }
+        
+        // Base déjà déverrouillée par le shell interactif
+        if options().as_of.is_none()
+            && let Some(kept) = kept().as_ref().filter(|kept| kept.repo.path() == path)
+        {
+            return Ok(Self {
+                repo: kept.repo.clone(),
+                password: kept.password.clone(),
+                db: kept.db.clone(),
+                read_only: false,
+                kdf: kept.kdf,
+                key: kept.key.clone(),
+                index: kept.index.clone(),
+            });
+        }
+        
+        // Base déverrouillée dans l'agent (keeprust vaults unlock)
+        let cached = agent::cached(path);
+        let vault = path;
+        
+        // Avec --as-of, ouvrir la dernière version enregistrée avant la date demandée
+        let (repo, read_only) = match options().as_of {
+            Some(date) => {
+                // Versions enregistrées jusqu'à la fin de la journée demandée
+                let (snapshot, modified) = recovery::snapshot_before(path, date + Duration::days(1)).ok_or_else(|| format!(
+                    "Aucune version de {} (ni sauvegarde) enregistrée au plus tard le {}.",
+                    path.display(), date.format("%d-%m-%Y")
+                ))?;
+                eprintln!("Lecture seule: version du {} ({})", modified.format("%d-%m-%Y %H:%M"), snapshot.display());
+                (Repository::new(snapshot), true)
+            }
+            None => {
+                // Un fichier remplacé est refusé avant la saisie du mot de passe
+                signing::verify_vault(path)?;
+                (repo, offline)
+            }
+        };
+        
+        let mut session = Self::unlock_with_agent_key(&repo, cached.as_ref(), read_only)?;
+        if !read_only {
+            session.warn_weak_kdf();
+            
+            // Fichier réenregistré par un autre processus : l'agent garde la clé de sa nouvelle version
+            if let Some(cached) = &cached
+                && cached.salt != session.key.salt
+            {
+                agent::remember_key(vault, &session.key);
+            }
+        }
+        if let Some(upstream) = diverged {
+            git_sync::merge_diverged(&mut session, &upstream)?;
+        }
+        Ok(session)
+    }
+    
+    /// Ouvre une autre base de données (copie synchronisée, base de destination...) avec le mot de
+    /// passe de cette session, ou en demandant le sien s'il est différent
+    pub fn open_other(&self, path: &Path) -> Result<Session, String> {
+        let repo = Repository::new(path);
+        repo.fetch().map_err(|e| e.to_string())?;
+        let path = repo.path();
+        if !path.exists() {
+            return Err(format!("Le fichier {} n'existe pas.", path.display()));
+        }
+        signing::verify_vault(path)?;
+        
+        let tried = match &self.password {
+            Some(password) => Self::unlock(&repo, Some(password.clone()), None, false),
+            None => Err(RepositoryError::InvalidPassword),
+        };
+        let session = match tried {
+            Err(RepositoryError::InvalidPassword) => {
+                let password = master_secret(prompt_password(&format!("Mot de passe de {}: ", path.display()))?)?;
+                Self::unlock(&repo, Some(password), None, false)
+            }
+            result => result,
+        }.map_err(|e| unlock_error(path, e))?;
+        
+        session.warn_weak_kdf();
+        Ok(session)
+    }
+    
+    /// Ouvre en lecture seule, sans rien demander, une base déverrouillée dans l'agent; None sinon.
+    ///
+    /// Ni téléchargement ni synchronisation : la copie locale est lue telle quelle (complétion du shell).
+    pub fn open_cached(path: &Path) -> Option<Self> {
+        let repo = Repository::new(path);
+        let cached = agent::cached(repo.path())?;
+        if !repo.path().exists() || signing::verify_vault(repo.path()).is_err() {
+            return None;
+        }
+        Self::unlock(&repo, None, Some(&cached), true).ok()
+    }
+    
+    /// Déchiffre le fichier avec le mot de passe fourni, ou avec `known` si c'est la clé du fichier actuel
+    fn unlock(repo: &Repository, password: Option<Zeroizing<String>>, known: Option<&FileKey>, read_only: bool) -> Result<Self, RepositoryError> {
+        let repo = repo.clone();
+        let mut timings = timings();
+        let (db, key) = repo.load_keyed(password.as_ref().map_or("", |password| password.as_str()), known, &mut timings)?;
+        report_timings("chargement", &timings);
+        
+        let kdf = repo.read_kdf_params()?;
+        let mut session = Self { repo, password, db, read_only, kdf, key, index: None };
+        session.apply_journal();
+        Ok(session)
+    }
+    
+    /// Déchiffre le fichier avec la clé confiée à l'agent, sinon avec le mot de passe demandé (fichier
+    /// réenregistré depuis par une autre application : la clé de l'agent ne l'ouvre plus)
+    fn unlock_with_agent_key(repo: &Repository, cached: Option<&FileKey>, read_only: bool) -> Result<Self, String> {
+        let result = match cached.map(|key| Self::unlock(repo, None, Some(key), read_only)) {
+            Some(Err(RepositoryError::InvalidPassword)) | None => {
+                Self::unlock(repo, Some(master_secret(prompt_master_password("Mot de passe: ")?)?), None, read_only)
+            }
+            Some(result) => result,
+        };
+        result.map_err(|e| unlock_error(repo.path(), e))
+    }
+    
+    /// Demande le mot de passe maître d'une base ouverte avec la clé confiée à l'agent (changement
+    /// des paramètres de dérivation, index de recherche), après avoir vérifié qu'il en dérive la clé
+    fn require_password(&mut self) -> Result<(), String> {
+        if self.password.is_none() {
+            let password = master_secret(prompt_master_password("Mot de passe: ")?)?;
+            let derived = key_derivation::verify_and_derive_key(&password, &self.key.salt, self.kdf).map_err(|e| e.to_string())?;
+            if Zeroizing::new(derived.key) != self.key.key {
+                return Err(unlock_error(self.repo.path(), RepositoryError::InvalidPassword));
+            }
+            self.password = Some(password);
+        }
+        Ok(())
+    }
+    
+    /// Mot de passe maître, s'il a été saisi
+    fn password(&self) -> Option<&str> {
+        self.password.as_ref().map(|password| password.as_str())
+    }
+    
+    /// Reporte sur la base les expositions consignées dans son journal depuis son enregistrement
+    fn apply_journal(&mut self) {
+        if let Err(e) = saving::apply_journal(self.repo.path(), &self.key, &mut self.db) {
+            eprintln!("{}Journal des expositions illisible: {}", Symbol::Warning, e);
+        }
+    }
+    
+    /// Statistiques de la base : comptées dans son index de recherche s'il est déjà chargé (il est
+    /// remis à jour à chaque enregistrement) et que la base n'a pas été modifiée depuis, sinon dans
+    /// la base elle-même. L'index n'est jamais lu pour l'occasion (dérivation de clé supplémentaire)
+    pub fn stats(&self) -> DatabaseStats {
+        match &self.index {
+            Some((index, _)) if !self.db.is_dirty() => self.db.stats_from_index(index),
+            _ => self.db.stats(),
+        }
+    }
+    
+    /// Ouvre une base pour y chercher des entrées : son index chiffré suffit s'il correspond au
+    /// fichier actuel; sinon la base est déchiffrée et l'index remis à jour pour les recherches suivantes
+    pub fn open_for_search(path: &Path) -> Result<SearchSource, String> {
+        let index_path = search_index::index_path(path);
+        let kept_open = kept().as_ref().is_some_and(|kept| kept.repo.path() == path);
+        if kept_open || options().as_of.is_some() || !index_path.exists() || !path.exists() || git_sync::is_synced(path) {
+            return Self::open(path).map(|session| SearchSource::Session(Box::new(session)));
+        }
+        
+        signing::verify_vault(path)?;
+        // Avec la clé confiée à l'agent, la base s'ouvre sans dérivation : l'index n'est pas lu
+        let repo = Repository::new(path);
+        let cached = agent::cached(path);
+        if cached.is_some() {
+            let session = Self::unlock_with_agent_key(&repo, cached.as_ref(), false)?;
+            session.warn_weak_kdf();
+            return Ok(SearchSource::Session(Box::new(session)));
+        }
+        
+        let password = master_secret(prompt_master_password("Mot de passe: ")?)?;
+        let stale = match SearchIndex::read(&index_path, &password) {
+            Ok((index, key)) => {
+                if search_index::vault_digest(path).is_ok_and(|digest| digest == index.vault_digest) {
+                    return Ok(SearchSource::Index(index));
+                }
+                Some((index, key))
+            }
+            // Mot de passe erroné ou index illisible : l'ouverture de la base tranchera
+            Err(_) => None,
+        };
+        
+        let mut session = Self::unlock(&repo, Some(password), None, false).map_err(|e| unlock_error(path, e))?;
+        session.warn_weak_kdf();
+        session.index = stale;
+        session.refresh_index_quietly();
+        Ok(SearchSource::Session(Box::new(session)))
+    }
+    
+    /// Signale des paramètres de dérivation de clé inférieurs au plancher configuré
+    fn warn_weak_kdf(&self) {
+        let min_iterations = options().min_iterations.unwrap_or(MIN_ITERATIONS);
+        if self.kdf.is_weak(min_iterations) {
+            eprintln!("{}Paramètres de dérivation de clé faibles ({}).", Symbol::Warning, self.kdf);
+            eprintln!("    Ils proviennent d'une ancienne version de keeprust, d'un réglage explicite ou d'une modification du fichier.");
+            eprintln!("    Exécutez 'keeprust upgrade-kdf' pour le réenregistrer avec les paramètres recommandés.");
+        }
+    }
+    
+    /// Chemin du fichier de la base ouverte
+    pub fn path(&self) -> &Path {
+        self.repo.path()
+    }
+    
+    /// Sauvegarde la base de données et la marque comme enregistrée
+    pub fn save(&mut self) -> Result<(), String> {
+        if self.read_only && options().as_of.is_some() {
+            return Err("Version antérieure ouverte en lecture seule (--as-of): les modifications ne peuvent pas être enregistrées.".to_string());
+        }
+        if self.read_only {
+            return Err("Copie locale ouverte hors ligne en lecture seule: les modifications ne peuvent pas être enregistrées tant que le serveur est injoignable.".to_string());
+        }
+        
+        // Sans mot de passe, un fichier PBKDF2 ne peut pas passer aux paramètres recommandés
+        if self.kdf.upgraded() != self.kdf {
+            self.require_password()?;
+        }
+        let mut timings = timings();
+        let saved = saving::save(&self.repo, &mut self.db, self.password.as_deref().map(String::as_str), self.kdf, &self.key, &mut self.index, &mut timings)?;
+        report_timings("enregistrement", &timings);
+        for warning in &saved.warnings {
+            eprintln!("{}{}", Symbol::Warning, warning);
+        }
+        self.key = saved.key;
+        self.kdf = saved.kdf;
+        agent::remember_key(self.repo.path(), &self.key);
+        git_sync::after_save(self.repo.path());
+        self.update_kept();
+        Ok(())
+    }
+    
+    /// Crée l'index de recherche chiffré de la base (coffre.krs.idx), ou le reconstruit entièrement
+    pub fn enable_index(&mut self) -> Result<IndexUpdate, String> {
+        if self.db.is_dirty() {
+            return Err("La base de données contient des modifications non enregistrées.".to_string());
+        }
+        self.require_password()?;
+        let key = IndexKey::new(self.password().unwrap_or_default(), self.kdf).map_err(|e| e.to_string())?;
+        self.index = Some((SearchIndex::default(), key));
+        let update = self.refresh_index(true).map_err(|e| format!("Erreur d'écriture de l'index de recherche: {}", e))?;
+        self.update_kept();
+        Ok(update.unwrap_or_default())
+    }
+    
+    /// Reporte le contenu de la base enregistrée dans son index de recherche, s'il existe
+    /// (`create` : l'écrit même s'il n'existe pas encore).
+    ///
+    /// Un index illisible avec le mot de passe actuel (mot de passe changé ailleurs) est reconstruit.
+    fn refresh_index(&mut self, create: bool) -> Result<Option<IndexUpdate>, SearchIndexError> {
+        saving::refresh_index(self.repo.path(), &self.db, self.password.as_deref().map(String::as_str), self.kdf, &mut self.index, create)
+    }
+    
+    /// Met à jour l'index de recherche après un enregistrement; un échec n'annule pas
+    /// l'enregistrement (l'index, périmé, est alors ignoré par les recherches)
+    fn refresh_index_quietly(&mut self) {
+        if let Err(e) = self.refresh_index(false) {
+            eprintln!("{}Index de recherche non mis à jour: {}", Symbol::Warning, e);
+        }
+    }
+    
+    /// Reporte l'état enregistré sur la session gardée déverrouillée de la même base
+    fn update_kept(&self) {
+        if let Some(kept) = kept().as_mut().filter(|kept| kept.repo.path() == self.repo.path()) {
+            kept.password = self.password.clone();
+            kept.db = self.db.clone();
+            kept.kdf = self.kdf;
+            kept.key = self.key.clone();
+            kept.index = self.index.clone();
+        }
+    }
+    
+    /// Relit le fichier, éventuellement modifié par un autre processus, avec le mot de passe de la session
+    pub fn reload(&mut self) -> Result<(), String> {
+        if self.db.is_dirty() {
+            return Err("La base de données contient des modifications non enregistrées.".to_string());
+        }
+        
+        let mut timings = timings();
+        // Fichier réenregistré par une autre application : sans mot de passe, la clé ne l'ouvre plus
+        let loaded = match self.repo.load_keyed(self.password().unwrap_or_default(), Some(&self.key), &mut timings) {
+            Err(RepositoryError::InvalidPassword) if self.password.is_none() => {
+                self.require_password()?;
+                self.repo.load_keyed(self.password().unwrap_or_default(), None, &mut timings)
+            }
+            loaded => loaded,
+        };
+        (self.db, self.key) = loaded.map_err(|e| format!("Erreur lors de la relecture de la base de données: {}", e))?;
+        report_timings("chargement", &timings);
+        self.kdf = self.repo.read_kdf_params().map_err(|e| e.to_string())?;
+        self.apply_journal();
+        self.update_kept();
+        Ok(())
+    }
+    
+    /// Paramètres de dérivation de clé du fichier
+    pub fn kdf_params(&self) -> KdfParams {
+        self.kdf
+    }
+    
+    /// Réenregistre le fichier avec d'autres paramètres de dérivation de clé (et un nouveau sel).
+    ///
+    /// En cas d'échec de l'enregistrement, les paramètres précédents sont conservés.
+    pub fn set_kdf_params(&mut self, kdf: KdfParams) -> Result<(), String> {
+        self.require_password()?;
+        let previous = std::mem::replace(&mut self.kdf, kdf);
+        
+        if let Err(e) = self.save() {
+            self.kdf = previous;
+            return Err(e);
+        }
+        Ok(())
+    }
+    
+    /// Remplace le mot de passe maître et réécrit le fichier avec une clé dérivée d'un nouveau sel.
+    ///
+    /// En cas d'échec de l'enregistrement, l'ancien mot de passe est conservé.
+    pub fn change_password(&mut self, new_password: Zeroizing<String>) -> Result<(), String> {
+        let previous = self.password.replace(new_password);
+        
+        // L'index de recherche sera chiffré avec une clé dérivée du nouveau mot de passe
+        let index = self.index.take();
+        
+        if let Err(e) = self.save() {
+            self.password = previous;
+            self.index = index;
+            return Err(e);
+        }
+        
+        // La base devra être de nouveau déverrouillée dans l'agent, avec le nouveau mot de passe
+        let _ = agent::lock(Some(self.repo.path()));
+        Ok(())
+    }
+    
+    /// Confie la clé dérivée du fichier à l'agent (jamais le mot de passe maître) : les commandes
+    /// suivantes l'ouvrent sans le redemander, jusqu'au délai de verrouillage (minutes; délai de
+    /// l'agent si None)
+    pub fn share_with_agent(&self, lock_after: Option<u64>) -> Result<(), String> {
+        agent::unlock(self.repo.path(), &self.key, lock_after)
+    }
+    
+    /// ID de l'entrée désignée par un ID, un début d'ID, un titre ou un motif (entrées de la
+    /// corbeille comprises avec --include-trash); si plusieurs entrées correspondent, un menu
+    /// permet de choisir, ou l'erreur les énumère hors terminal
+    pub fn resolve(&self, reference: &EntryRef) -> Result<EntryId, String> {
+        self.resolve_among(reference, include_trash())
+    }
+    
+    /// Comme `resolve`, en examinant toujours les entrées de la corbeille (restauration)
+    pub fn resolve_trashed(&self, reference: &EntryRef) -> Result<EntryId, String> {
+        self.resolve_among(reference, true)
+    }
+    
+    fn resolve_among(&self, reference: &EntryRef, include_trash: bool) -> Result<EntryId, String> {
+        self.db.resolve_entry(reference.as_str(), include_trash)
+            .or_else(|error| entry_picker::settle(&self.db, error, is_interactive()))
+    }
+    
+    /// Entrée désignée par son ID : une entrée de la corbeille est refusée sans --include-trash, et
+    /// une entrée archivée ou de la corbeille est signalée sur la sortie d'erreur
+    pub fn entry(&self, id: &EntryId) -> Result<&Entry, String> {
+        let entry = self.db.find_live_entry(id, include_trash()).map_err(|e| match e {
+            DatabaseError::InTrash(_) => format!("{} Restaurez-la avec 'keeprust restore {}' ou ajoutez --include-trash.", e, id),
+            e => e.to_string(),
+        })?;
+        match self.db.entry_state(id) {
+            Some(EntryState::Trashed) => eprintln!("{}L'entrée '{}' est dans la corbeille.", Symbol::Warning, entry.title),
+            Some(EntryState::Archived) => eprintln!("{}L'entrée '{}' est archivée.", Symbol::Warning, entry.title),
+            _ => {}
+        }
+        Ok(entry)
+    }
+    
+    /// Consigne l'exposition en clair de mots de passe (affichage, copie, export) par l'une des
+    /// interfaces dans le journal de la base, sans l'enregistrer (voir `exposure::journal_path`);
+    /// sans effet sur une version antérieure ouverte en lecture seule
+    pub fn record_exposure(&mut self, ids: &[EntryId], kind: ExposureKind, interface: ExposureInterface) -> Result<(), String> {
+        if self.read_only || ids.is_empty() {
+            return Ok(());
+        }
+        let records: Vec<ExposureRecord> = ids.iter().map(|id| ExposureRecord::now(*id, kind, interface)).collect();
+        exposure::append_journal(&exposure::journal_path(self.repo.path()), &self.key, &records)
+            .map_err(|e| format!("Erreur d'écriture du journal des expositions: {}", e))?;
+        self.db.apply_exposures(&records);
+        self.update_kept();
+        Ok(())
+    }
+    
+    /// Ferme la session en proposant d'enregistrer les modifications non sauvegardées
+    pub fn close(mut self) -> Result<(), String> {
+        if !self.db.is_dirty() {
+            return Ok(());
+        }
+        if self.read_only {
+            return Ok(());
+        }
+        
+        if !confirm("La base de données contient des modifications non enregistrées. Enregistrer?", true)? {
+            println!("Modifications abandonnées.");
+            return Ok(());
+        }
+        
+        self.save()?;
+        println!("Modifications enregistrées.");
+        Ok(())
+    }
+    
+    /// Verrouille la session après avoir proposé d'enregistrer les modifications non sauvegardées
+    pub fn lock(self) -> Result<LockedSession, String> {
+        let repo = self.repo.clone();
+        self.close()?;
+        Ok(LockedSession { repo })
+    }
+}
+
+/// Session verrouillée : le mot de passe maître et les données déchiffrées ont été effacés,
+/// seul l'emplacement du fichier est conservé pour la déverrouiller à nouveau.
+///
+/// L'appelant (shell, interface plein écran) conserve son propre état de navigation pendant le verrouillage.
+pub struct LockedSession {
+    repo: Repository,
+}
+
+impl LockedSession {
+    /// Chemin de la base de données verrouillée
+    pub fn path(&self) -> &Path {
+        self.repo.path()
+    }
+    
+    /// Rouvre la base de données avec le mot de passe maître saisi par l'appelant (combiné au
+    /// fichier clé de --keyfile); le fichier est relu, modifications d'autres appareils comprises
+    pub fn unlock(&self, password: String) -> Result<Session, String> {
+        signing::verify_vault(self.path())?;
+        Session::unlock(&self.repo, Some(master_secret(password)?), None, false).map_err(|e| unlock_error(self.path(), e))
+    }
+}
+
@@ -0,0 +1,189 @@
+use clap::Parser;
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::app::Commands;
+use super::commands;
+use super::session::{self, Session};
+
+/// Invite affichée lorsque la base est déverrouillée
+const PROMPT: &str = "keeprust> ";
+
+/// Invite affichée lorsque la base a été verrouillée
+const LOCKED_PROMPT: &str = "keeprust (verrouillée)> ";
+
+/// Intervalle de vérification de l'inactivité
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Ligne saisie dans le shell : une commande keeprust, sans les options globales
+#[derive(Parser, Debug)]
+#[command(name = "keeprust", no_binary_name = true, disable_help_subcommand = true, disable_version_flag = true)]
+struct ShellLine {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+/// Activité du shell, partagée avec la surveillance de l'inactivité
+struct Activity {
+    /// Fin de la dernière commande (ou ouverture du shell)
+    last: Instant,
+    
+    /// Une commande est en cours : l'inactivité n'est pas comptée
+    running: bool,
+}
+
+impl Activity {
+    /// Indique si le shell attend une commande depuis au moins `lock_after`
+    fn is_idle_for(&self, lock_after: Duration) -> bool {
+        !self.running && self.last.elapsed() >= lock_after
+    }
+}
+
+/// Accès à l'activité du shell, même si un thread a paniqué en la modifiant
+fn activity(shared: &Mutex<Activity>) -> MutexGuard<'_, Activity> {
+    shared.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Signale le verrouillage de la base pour inactivité
+fn report_idle_lock(lock_after: Duration) {
+    eprintln!("\nBase verrouillée après {} minute(s) d'inactivité.", lock_after.as_secs() / 60);
+}
+
+/// Verrouille la base après `lock_after` d'inactivité à l'invite (surveillance en arrière-plan).
+///
+/// Jamais pendant une commande, et sans rien demander : une base aux modifications non
+/// enregistrées reste déverrouillée, le thread principal la verrouille avant la commande suivante
+/// (en proposant de l'enregistrer) pour ne pas lire l'entrée standard en même temps que lui.
+fn spawn_idle_lock(lock_after: Duration, shared: Arc<Mutex<Activity>>) {
+    thread::spawn(move || loop {
+        thread::sleep(IDLE_CHECK_INTERVAL);
+        let activity = activity(&shared);
+        if activity.is_idle_for(lock_after) && session::lock_kept_quietly() {
+            report_idle_lock(lock_after);
+            let _ = session::show_prompt(LOCKED_PROMPT);
+        }
+    });
+}
+
+/// Rouvre la base verrouillée (nouvelle saisie du mot de passe)
+fn unlock(path: &Path) -> Result<(), String> {
+    session::keep_unlocked(Session::open(path)?);
+    Ok(())
+}
+
+/// Exécute une ligne saisie; retourne false pour quitter le shell
+fn run_line(path: &Path, line: &str) -> bool {
+    let Some(words) = shlex::split(line) else {
+        eprintln!("Erreur: guillemets non refermés.");
+        return true;
+    };
+    
+    match words.first().map(String::as_str) {
+        None => return true,
+        Some("exit" | "quit") => return false,
+        Some("lock") => {
+            if session::lock_kept().is_some() {
+                println!("Base verrouillée.");
+            }
+            return true;
+        }
+        Some(_) => {}
+    }
+    
+    let command = match ShellLine::try_parse_from(&words) {
+        Ok(line) => line.command,
+        Err(e) => {
+            let _ = e.print();
+            return true;
+        }
+    };
+    if matches!(command, Commands::Shell { .. }) {
+        eprintln!("Erreur: le shell est déjà ouvert.");
+        return true;
+    }
+    
+    if !session::is_kept_unlocked()
+        && let Err(e) = unlock(path)
+    {
+        eprintln!("Erreur: {}", e);
+        return true;
+    }
+    
+    if let Err(e) = commands::dispatch(path, command) {
+        eprintln!("Erreur: {}", e);
+    }
+    true
+}
+
+/// Shell interactif : déverrouille la base une fois, puis exécute les commandes saisies
+/// (`list`, `show`, `add`, `copy`...) sans redemander le mot de passe maître.
+///
+/// Après `lock_after_minutes` minutes d'inactivité, le mot de passe et les données déchiffrées
+/// sont effacés de la mémoire; la commande suivante redemande le mot de passe.
+pub fn cmd_shell(path: &Path, lock_after_minutes: u64) -> Result<(), String> {
+    session::keep_unlocked(Session::open(path)?);
+    
+    let shared = Arc::new(Mutex::new(Activity { last: Instant::now(), running: false }));
+    let lock_after = (lock_after_minutes > 0).then(|| Duration::from_secs(lock_after_minutes * 60));
+    if let Some(lock_after) = lock_after {
+        spawn_idle_lock(lock_after, Arc::clone(&shared));
+    }
+    
+    println!("Base {} déverrouillée. Commandes keeprust sans le préfixe (ex: list, show <id>), 'lock' pour verrouiller, 'exit' pour quitter.", path.display());
+    loop {
+        let prompt = if session::is_kept_unlocked() { PROMPT } else { LOCKED_PROMPT };
+        session::show_prompt(prompt)?;
+        
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line).map_err(|e| e.to_string())? == 0 {
+            println!();
+            break;
+        }
+        
+        // Base restée déverrouillée à l'invite (modifications non enregistrées) : verrouillée ici,
+        // entre deux commandes, puis rouverte par la commande saisie
+        let idle = lock_after.filter(|lock_after| activity(&shared).is_idle_for(*lock_after));
+        if let Some(lock_after) = idle
+            && session::lock_kept().is_some()
+        {
+            report_idle_lock(lock_after);
+        }
+        
+        activity(&shared).running = true;
+        let keep_going = run_line(path, line.trim());
+        *activity(&shared) = Activity { last: Instant::now(), running: false };
+        if !keep_going {
+            break;
+        }
+    }
+    
+    session::lock_kept();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_shell_lines_parse_as_commands() {
+        let words = shlex::split("search 'mon compte' --ignore-case").unwrap();
+        let line = ShellLine::try_parse_from(&words).unwrap();
+        assert!(matches!(line.command, Commands::Search { ref query, ignore_case: true, .. } if query == "mon compte"));
+        
+        assert!(ShellLine::try_parse_from(["--database", "autre.krs", "list"]).is_err());
+    }
+    
+    #[test]
+    fn test_idle_clock_is_paused_while_a_command_runs() {
+        let mut activity = Activity { last: Instant::now() - Duration::from_secs(120), running: true };
+        assert!(!activity.is_idle_for(Duration::from_secs(60)));
+        
+        activity.running = false;
+        assert!(activity.is_idle_for(Duration::from_secs(60)));
+        assert!(!activity.is_idle_for(Duration::from_secs(180)));
+    }
+}
\ No newline at end of file
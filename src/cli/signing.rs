@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use crate::crypto::signing;
+use crate::models::signing::{absolute, check, load, write, write_signature};
+
+use super::session::Session;
+
+pub use crate::models::signing::{ensure_signable, sign_vault, verify_copy, verify_vault};
+
+/// Exige la signature de la base : crée la clé de cet appareil si besoin, puis signe la base
+/// après l'avoir déverrouillée (le mot de passe atteste qu'elle est authentique)
+pub fn cmd_signing_init(path: &Path) -> Result<(), String> {
+    let mut config = load()?;
+    if config.is_signed(path) {
+        return Err(format!("La signature de {} est déjà exigée ('keeprust signing rotate' pour changer de clé).", path.display()));
+    }
+    
+    let session = Session::open(path)?;
+    session.close()?;
+    
+    let key = match config.secret_key {
+        Some(_) => config.secret()?,
+        None => {
+            let key = signing::generate();
+            config.secret_key = Some(signing::encode_secret(&key).to_string());
+            key
+        }
+    };
+    config.signed_vaults.push(absolute(path));
+    write(&config)?;
+    write_signature(path, &key)?;
+    
+    println!("Base signée: {}", signing::signature_path(path).display());
+    println!("Sa signature sera vérifiée avant chaque ouverture.");
+    println!("Clé publique de cet appareil: {}", signing::encode_public(&key.verifying_key()));
+    println!("Sur vos autres appareils: 'keeprust signing trust <clé publique>'.");
+    Ok(())
+}
+
+/// Remplace la clé de cet appareil et signe de nouveau toutes les bases avec la nouvelle clé,
+/// après avoir vérifié leurs signatures actuelles (l'ancienne clé n'est plus acceptée)
+pub fn cmd_signing_rotate() -> Result<(), String> {
+    let mut config = load()?;
+    let trusted = config.trusted()?;
+    let old_public = signing::encode_public(&config.secret()?.verifying_key());
+    
+    for vault in &config.signed_vaults {
+        check(vault, &trusted).map_err(|e| format!("{}\nRotation annulée.", e))?;
+    }
+    
+    let key = signing::generate();
+    config.secret_key = Some(signing::encode_secret(&key).to_string());
+    write(&config)?;
+    for vault in &config.signed_vaults {
+        write_signature(vault, &key)?;
+        println!("Base signée de nouveau: {}", vault.display());
+    }
+    
+    println!("Nouvelle clé publique de cet appareil: {}", signing::encode_public(&key.verifying_key()));
+    println!("Sur vos autres appareils, acceptez-la à la place de {} ('keeprust signing trust').", old_public);
+    Ok(())
+}
+
+/// Accepte les signatures d'un autre appareil
+pub fn cmd_signing_trust(public: &str) -> Result<(), String> {
+    let key = signing::decode_public(public).map_err(|e| format!("Clé publique invalide: {}", e))?;
+    let public = signing::encode_public(&key);
+    
+    let mut config = load()?;
+    if config.trusted_keys.contains(&public) {
+        println!("Clé déjà acceptée.");
+        return Ok(());
+    }
+    config.trusted_keys.push(public);
+    write(&config)?;
+    println!("Les signatures de cette clé sont désormais acceptées.");
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::events;
+use crate::utils::password_analyzer::{AnalyzerConfig, PasswordAnalyzer};
+
+/// Nom du fichier de réglage de l'analyseur de force, dans le répertoire de configuration
+const STRENGTH_FILE: &str = "strength.toml";
+
+/// Emplacement du fichier de réglage de l'analyseur :
+///
+/// ```toml
+/// [thresholds]   # score maximal de chaque niveau
+/// very_weak = 30
+/// weak = 50
+/// medium = 70
+/// strong = 85
+///
+/// [weights]      # points par critère
+/// min_length = 20
+/// four_char_types = 40
+/// ```
+pub fn strength_path() -> Option<PathBuf> {
+    Some(events::config_dir()?.join(STRENGTH_FILE))
+}
+
+/// Analyseur de force réglé par strength.toml; réglages par défaut si le fichier n'existe pas
+pub fn analyzer() -> Result<PasswordAnalyzer, String> {
+    let Some(path) = strength_path() else {
+        return Ok(PasswordAnalyzer::new());
+    };
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Ok(PasswordAnalyzer::new());
+    };
+    let config: AnalyzerConfig = toml::from_str(&content).map_err(|e| format!("Fichier {} invalide: {}", path.display(), e))?;
+    PasswordAnalyzer::with_config(config).map_err(|e| format!("Fichier {}: {}", path.display(), e))
+}
\ No newline at end of file
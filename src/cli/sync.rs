@@ -0,0 +1,116 @@
+use std::fs;
+use std::path::Path;
+
+use crate::events::{self, SecurityEvent};
+use crate::models::id::{EntryId, GroupId};
+
+use super::resolve;
+use super::session::{self, Session};
+
+/// Fusionne une autre version de la base dans celle-ci.
+///
+/// Les entrées modifiées des deux côtés ne font pas échouer la fusion : la version de l'autre
+/// fichier est conservée en copie de conflit dans le même groupe, et l'événement `sync-conflict`
+/// est déclenché.
+pub fn cmd_merge(path: &Path, other: &Path) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    let remote = session.open_other(other)?;
+    
+    let report = session.db.merge(&remote.db);
+    drop(remote);
+    
+    if report.groups_added == 0 && report.added == 0 && report.updated == 0 && report.conflicts.is_empty() {
+        println!("Aucune modification à fusionner depuis {}.", other.display());
+        return session.close();
+    }
+    
+    session.save()?;
+    println!("Fusion de {} terminée:", other.display());
+    println!("  {} groupe(s) ajouté(s)", report.groups_added);
+    println!("  {} entrée(s) ajoutée(s)", report.added);
+    println!("  {} entrée(s) mise(s) à jour", report.updated);
+    
+    if !report.conflicts.is_empty() {
+        println!("  {} conflit(s):", report.conflicts.len());
+        for id in &report.conflicts {
+            if let Some(copy) = session.db.find_entry(id) {
+                println!("    - {} (ID: {})", copy.title, copy.id);
+            }
+        }
+        events::emit(path, &SecurityEvent::SyncConflict { conflict_path: path.to_path_buf() });
+        offer_resolution(&mut session, &report.conflicts)?;
+    }
+    session.close()
+}
+
+/// Propose de résoudre aussitôt les conflits d'une fusion, champ par champ
+pub fn offer_resolution(session: &mut Session, conflicts: &[EntryId]) -> Result<(), String> {
+    if !session::is_interactive() || !session::confirm("Résoudre les conflits maintenant?", true)? {
+        println!("Résolvez les conflits plus tard avec 'keeprust conflicts --resolve'.");
+        return Ok(());
+    }
+    let resolved = resolve::resolve_conflicts(session, conflicts)?;
+    println!("{} conflit(s) résolu(s) sur {}.", resolved, conflicts.len());
+    Ok(())
+}
+
+/// Liste les copies de conflit en attente de résolution, ou avec `resolve` les résout une à une
+pub fn cmd_conflicts(path: &Path, resolve: bool) -> Result<(), String> {
+    let mut session = Session::open(path)?;
+    
+    let copies = session.db.conflict_copies();
+    if copies.is_empty() {
+        println!("Aucune copie de conflit.");
+        return session.close();
+    }
+    if resolve {
+        let ids: Vec<EntryId> = copies.iter().map(|(copy, _)| copy.id).collect();
+        let resolved = resolve::resolve_conflicts(&mut session, &ids)?;
+        println!("{} conflit(s) résolu(s) sur {}.", resolved, ids.len());
+        return session.close();
+    }
+    
+    println!("Copies de conflit ({}):", copies.len());
+    for (copy, original) in &copies {
+        match original {
+            Some(original) => println!("  {} (ID: {})\n    copie de '{}' (ID: {})", copy.title, copy.id, original.title, original.id),
+            None => println!("  {} (ID: {})\n    entrée d'origine supprimée", copy.title, copy.id),
+        }
+    }
+    println!("\nRésolvez-les champ par champ avec 'keeprust conflicts --resolve', ou gardez la bonne version ('keeprust edit') et placez l'autre dans la corbeille ('keeprust delete').");
+    session.close()
+}
+
+/// Copie un groupe et sa descendance dans une autre base, puis avec `move_group` le supprime de
+/// celle-ci.
+///
+/// La base de destination est enregistrée en premier : en cas d'échec, le groupe d'origine est intact.
+pub fn cmd_copy_group(path: &Path, id: &GroupId, target: &Path, parent: Option<&GroupId>, move_group: bool, keep_ids: bool) -> Result<(), String> {
+    if fs::canonicalize(path).ok().is_some_and(|source| fs::canonicalize(target).ok() == Some(source)) {
+        return Err("La base de destination doit être différente de la base d'origine.".to_string());
+    }
+    
+    let mut session = Session::open(path)?;
+    if move_group && session.db.root_group.id == *id {
+        return Err("Le groupe racine ne peut pas être déplacé; copiez-le sans --move ou déplacez ses sous-groupes.".to_string());
+    }
+    let subtree = session.db.clone_subtree(id, keep_ids).map_err(|e| e.to_string())?;
+    let (name, groups) = (subtree.name.clone(), subtree.iter_groups().count());
+    let entries: usize = subtree.iter_groups().map(|group| group.entries.len()).sum();
+    
+    let mut destination = session.open_other(target)?;
+    let copied = destination.db.insert_subtree(parent, subtree).map_err(|e| e.to_string())?;
+    destination.save()?;
+    destination.close()?;
+    println!(
+        "Groupe '{}' copié dans {} ({} groupe(s), {} entrée(s), ID: {}).",
+        name, target.display(), groups, entries, copied
+    );
+    
+    if move_group {
+        session.db.remove_group(id).map_err(|e| e.to_string())?;
+        session.save()?;
+        println!("Groupe '{}' supprimé de {}.", name, path.display());
+    }
+    session.close()
+}
\ No newline at end of file
@@ -0,0 +1,720 @@
+#[cfg(not(feature = "tui"))]
+use std::path::Path;
+
+/// Ouvre l'interface plein écran : arborescence des groupes, liste des entrées et détail
+#[cfg(not(feature = "tui"))]
+pub fn cmd_tui(_path: &Path, _timeout: u64) -> Result<(), String> {
+    Err("Cette version de keeprust a été compilée sans l'interface plein écran (fonctionnalité 'tui').".to_string())
+}
+
+#[cfg(feature = "tui")]
+pub use screen::cmd_tui;
+
+#[cfg(feature = "tui")]
+mod screen {
+    use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+    use ratatui::layout::{Constraint, Layout, Rect};
+    use ratatui::style::{Style, Stylize};
+    use ratatui::text::Line;
+    use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+    use ratatui::{DefaultTerminal, Frame};
+    use std::path::Path;
+    use zeroize::{Zeroize, Zeroizing};
+    
+    use crate::models::database::Database;
+    use crate::models::entry::Entry;
+    use crate::models::exposure::{ExposureInterface, ExposureKind};
+    use crate::models::id::{EntryId, GroupId};
+    use crate::models::walker::WalkControl;
+    use crate::utils::clipboard::copy_quietly;
+    use crate::utils::redact::MASK;
+    use crate::utils::symbols;
+    
+    use crate::cli::session::{self, LockedSession, Session};
+    
+    /// Aide affichée en bas de l'écran pour la navigation
+    const BROWSE_HELP: &str = "Tab panneau  ↑↓ déplacer  Entrée ouvrir  / rechercher  c copier le mot de passe  u copier l'utilisateur  e modifier  v verrouiller  q quitter";
+    
+    /// Aide affichée en bas de l'écran pour le détail d'une entrée
+    const DETAIL_HELP: &str = "p afficher/masquer le mot de passe  c copier le mot de passe  u copier l'utilisateur  e modifier  v verrouiller  Échap retour";
+    
+    /// Aide affichée en bas de l'écran pendant la modification d'un champ
+    const EDIT_HELP: &str = "Entrée enregistrer  Tab champ suivant  Échap annuler";
+    
+    /// Aide affichée en bas de l'écran de verrouillage
+    const LOCKED_HELP: &str = "Entrée déverrouiller  Échap quitter";
+    
+    /// Panneau recevant les touches de déplacement
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Pane {
+        Groups,
+        Entries,
+    }
+    
+    /// Champ d'une entrée modifiable en place
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Field {
+        Title,
+        Username,
+        Url,
+        Password,
+    }
+    
+    impl Field {
+        fn label(self) -> &'static str {
+            match self {
+                Field::Title => "Titre",
+                Field::Username => "Utilisateur",
+                Field::Url => "URL",
+                Field::Password => "Mot de passe",
+            }
+        }
+        
+        fn next(self) -> Field {
+            match self {
+                Field::Title => Field::Username,
+                Field::Username => Field::Url,
+                Field::Url => Field::Password,
+                Field::Password => Field::Title,
+            }
+        }
+        
+        fn value(self, entry: &Entry) -> &str {
+            match self {
+                Field::Title => &entry.title,
+                Field::Username => &entry.username,
+                Field::Url => &entry.url,
+                Field::Password => &entry.password,
+            }
+        }
+        
+        fn set(self, entry: &mut Entry, value: String) {
+            match self {
+                Field::Title => entry.title = value,
+                Field::Username => entry.username = value,
+                Field::Url => entry.url = value,
+                Field::Password => {
+                    entry.password.zeroize();
+                    entry.password = value;
+                }
+            }
+        }
+    }
+    
+    /// Mode de l'interface
+    #[derive(Debug, PartialEq)]
+    enum Mode {
+        /// Navigation dans les groupes et les entrées
+        Browse,
+        
+        /// Saisie du texte recherché, la liste des entrées se met à jour à chaque touche
+        Search,
+        
+        /// Détail d'une entrée, mot de passe masqué sauf demande explicite
+        Detail { id: EntryId, revealed: bool },
+        
+        /// Modification d'un champ de l'entrée affichée
+        Edit { id: EntryId, field: Field, buffer: String },
+        
+        /// Base verrouillée : saisie du mot de passe maître, rien de la base n'est affiché
+        Locked { password: Zeroizing<String> },
+    }
+    
+    /// Effet d'une touche à exécuter hors de l'état de l'interface (presse-papiers, fichier)
+    #[derive(Debug, PartialEq)]
+    enum Action {
+        None,
+        Quit,
+        CopyPassword(EntryId),
+        CopyUsername(EntryId),
+        Reveal(EntryId),
+        Lock,
+        
+        /// Mot de passe maître saisi sur l'écran de verrouillage
+        Unlock(Zeroizing<String>),
+        
+        /// La base a été modifiée : l'enregistrer puis afficher le message
+        Save(String),
+    }
+    
+    /// Groupe affiché dans l'arborescence
+    struct GroupRow {
+        id: GroupId,
+        
+        /// Noms des groupes depuis la racine, groupe inclus
+        path: Vec<String>,
+    }
+    
+    /// Base de l'interface : déverrouillée, ou verrouillée jusqu'à la saisie du mot de passe maître
+    enum Vault {
+        Unlocked(Box<Session>),
+        Locked(LockedSession),
+    }
+    
+    /// Groupes de la base dans l'ordre de l'arborescence
+    fn group_rows(db: &Database) -> Vec<GroupRow> {
+        let mut groups = Vec::new();
+        db.walk(|path, group, entry| {
+            if entry.is_none() {
+                groups.push(GroupRow { id: group.id, path: path.to_vec() });
+            }
+            WalkControl::Continue
+        });
+        groups
+    }
+    
+    /// État de l'interface, indépendant du terminal
+    struct App {
+        groups: Vec<GroupRow>,
+        group_index: usize,
+        entry_index: usize,
+        pane: Pane,
+        mode: Mode,
+        
+        /// Texte recherché dans toute la base; vide, la liste montre les entrées du groupe choisi
+        query: String,
+        
+        /// Message affiché jusqu'à la touche suivante
+        status: Option<String>,
+    }
+    
+    impl App {
+        fn new(db: &Database) -> Self {
+            Self { groups: group_rows(db), group_index: 0, entry_index: 0, pane: Pane::Groups, mode: Mode::Browse, query: String::new(), status: None }
+        }
+        
+        /// Oublie ce qui vient de la base (groupes, recherche, champ en cours de modification) et
+        /// attend le mot de passe maître; la position dans l'arborescence est conservée
+        fn lock(&mut self) {
+            if let Mode::Edit { buffer, .. } = &mut self.mode {
+                buffer.zeroize();
+            }
+            self.groups.clear();
+            self.query.clear();
+            self.entry_index = 0;
+            self.mode = Mode::Locked { password: Zeroizing::new(String::new()) };
+            self.status = None;
+        }
+        
+        /// Reprend la navigation dans la base déverrouillée, relue depuis le fichier
+        fn unlock(&mut self, db: &Database) {
+            self.groups = group_rows(db);
+            self.group_index = self.group_index.min(self.groups.len().saturating_sub(1));
+            self.mode = Mode::Browse;
+        }
+        
+        /// Entrées de la liste : résultats de la recherche, sinon entrées du groupe choisi
+        /// (par titre), chacune avec le chemin de son groupe
+        fn entries<'a>(&self, db: &'a Database) -> Vec<(Vec<String>, &'a Entry)> {
+            if !self.query.is_empty() {
+                return db.search(&self.query, true, session::include_trash()).into_iter().map(|found| (found.path, found.entry)).collect();
+            }
+            
+            let Some(row) = self.groups.get(self.group_index) else {
+                return Vec::new();
+            };
+            let Some(group) = db.find_group(&row.id) else {
+                return Vec::new();
+            };
+            let mut entries: Vec<&Entry> = group.entries.values().collect();
+            entries.sort_by_cached_key(|entry| entry.title.to_lowercase());
+            entries.into_iter().map(|entry| (row.path.clone(), entry)).collect()
+        }
+        
+        /// Entrée sélectionnée dans la liste
+        fn selected_entry(&self, db: &Database) -> Option<EntryId> {
+            self.entries(db).get(self.entry_index).map(|(_, entry)| entry.id)
+        }
+        
+        /// Déplace la sélection du panneau actif
+        fn move_selection(&mut self, db: &Database, down: bool) {
+            let step = |index: usize, len: usize| match down {
+                true => (index + 1).min(len.saturating_sub(1)),
+                false => index.saturating_sub(1),
+            };
+            match self.pane {
+                Pane::Groups => {
+                    self.group_index = step(self.group_index, self.groups.len());
+                    self.entry_index = 0;
+                }
+                Pane::Entries => self.entry_index = step(self.entry_index, self.entries(db).len()),
+            }
+        }
+        
+        /// Commence la modification du titre de l'entrée
+        fn start_edit(&mut self, db: &Database, id: EntryId) {
+            if let Some(entry) = db.find_entry(&id) {
+                self.mode = Mode::Edit { id, field: Field::Title, buffer: entry.title.clone() };
+            }
+        }
+        
+        /// Traite une touche et retourne l'effet à exécuter
+        fn handle_key(&mut self, db: &mut Database, key: KeyEvent) -> Action {
+            self.status = None;
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                return Action::Quit;
+            }
+            
+            match &mut self.mode {
+                Mode::Search => match key.code {
+                    KeyCode::Char(c) => {
+                        self.query.push(c);
+                        self.entry_index = 0;
+                    }
+                    KeyCode::Backspace => {
+                        self.query.pop();
+                        self.entry_index = 0;
+                    }
+                    KeyCode::Enter => {
+                        self.mode = Mode::Browse;
+                        self.pane = Pane::Entries;
+                    }
+                    KeyCode::Esc => {
+                        self.query.clear();
+                        self.entry_index = 0;
+                        self.mode = Mode::Browse;
+                    }
+                    _ => {}
+                },
+                Mode::Edit { id, field, buffer } => match key.code {
+                    KeyCode::Char(c) => buffer.push(c),
+                    KeyCode::Backspace => {
+                        buffer.pop();
+                    }
+                    KeyCode::Tab => {
+                        let next = field.next();
+                        buffer.zeroize();
+                        *buffer = db.find_entry(id).map(|entry| next.value(entry).to_string()).unwrap_or_default();
+                        *field = next;
+                    }
+                    KeyCode::Esc => {
+                        buffer.zeroize();
+                        self.mode = Mode::Detail { id: *id, revealed: false };
+                    }
+                    KeyCode::Enter => {
+                        let (id, field, value) = (*id, *field, std::mem::take(buffer));
+                        self.mode = Mode::Detail { id, revealed: false };
+                        if db.find_entry(&id).is_some_and(|entry| field.value(entry) == value) {
+                            return Action::None;
+                        }
+                        if field == Field::Title && value.trim().is_empty() {
+                            self.status = Some("Le titre ne peut pas être vide.".to_string());
+                            return Action::None;
+                        }
+                        return match db.update_entry(&id, |entry| field.set(entry, value)) {
+                            Ok(()) => Action::Save(format!("{} modifié.", field.label())),
+                            Err(e) => {
+                                self.status = Some(e.to_string());
+                                Action::None
+                            }
+                        };
+                    }
+                    _ => {}
+                },
+                Mode::Detail { id, revealed } => match key.code {
+                    KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('q') => self.mode = Mode::Browse,
+                    KeyCode::Char('p') => {
+                        *revealed = !*revealed;
+                        if *revealed {
+                            return Action::Reveal(*id);
+                        }
+                    }
+                    KeyCode::Char('c') => return Action::CopyPassword(*id),
+                    KeyCode::Char('u') => return Action::CopyUsername(*id),
+                    KeyCode::Char('v') => return Action::Lock,
+                    KeyCode::Char('e') => {
+                        let id = *id;
+                        self.start_edit(db, id);
+                    }
+                    _ => {}
+                },
+                // Écran de verrouillage : voir handle_locked_key, la base n'est plus disponible
+                Mode::Locked { .. } => {}
+                Mode::Browse => match key.code {
+                    KeyCode::Char('q') => return Action::Quit,
+                    KeyCode::Esc if !self.query.is_empty() => {
+                        self.query.clear();
+                        self.entry_index = 0;
+                    }
+                    KeyCode::Esc => return Action::Quit,
+                    KeyCode::Tab | KeyCode::Left | KeyCode::Right => {
+                        self.pane = match self.pane {
+                            Pane::Groups => Pane::Entries,
+                            Pane::Entries => Pane::Groups,
+                        };
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => self.move_selection(db, false),
+                    KeyCode::Down | KeyCode::Char('j') => self.move_selection(db, true),
+                    KeyCode::Char('/') => self.mode = Mode::Search,
+                    KeyCode::Char('v') => return Action::Lock,
+                    KeyCode::Enter if self.pane == Pane::Groups => {
+                        self.query.clear();
+                        self.pane = Pane::Entries;
+                    }
+                    KeyCode::Enter | KeyCode::Char('c' | 'u' | 'e') => {
+                        let Some(id) = self.selected_entry(db) else {
+                            return Action::None;
+                        };
+                        match key.code {
+                            KeyCode::Char('c') => return Action::CopyPassword(id),
+                            KeyCode::Char('u') => return Action::CopyUsername(id),
+                            KeyCode::Char('e') => self.start_edit(db, id),
+                            _ => self.mode = Mode::Detail { id, revealed: false },
+                        }
+                    }
+                    _ => {}
+                },
+            }
+            Action::None
+        }
+        
+        /// Traite une touche de l'écran de verrouillage : saisie du mot de passe maître
+        fn handle_locked_key(&mut self, key: KeyEvent) -> Action {
+            self.status = None;
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                return Action::Quit;
+            }
+            let Mode::Locked { password } = &mut self.mode else {
+                return Action::None;
+            };
+            
+            match key.code {
+                KeyCode::Char(c) => password.push(c),
+                KeyCode::Backspace => {
+                    password.pop();
+                }
+                KeyCode::Enter => return Action::Unlock(Zeroizing::new(std::mem::take(&mut **password))),
+                KeyCode::Esc => return Action::Quit,
+                _ => {}
+            }
+            Action::None
+        }
+        
+        /// Dessine l'interface
+        fn draw(&self, frame: &mut Frame, db: &Database) {
+            let [main, status] = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+            let [left, right] = Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)]).areas(main);
+            
+            self.draw_groups(frame, left);
+            match &self.mode {
+                Mode::Detail { id, .. } | Mode::Edit { id, .. } => self.draw_detail(frame, right, db, id),
+                _ => self.draw_entries(frame, right, db),
+            }
+            
+            let line = match (&self.mode, &self.status) {
+                (Mode::Search, _) => Line::from(format!("/{}", self.query)),
+                (_, Some(status)) => Line::from(status.as_str()).bold(),
+                (Mode::Detail { .. }, None) => Line::from(DETAIL_HELP).dim(),
+                (Mode::Edit { .. }, None) => Line::from(EDIT_HELP).dim(),
+                _ => Line::from(BROWSE_HELP).dim(),
+            };
+            frame.render_widget(Paragraph::new(line), status);
+        }
+        
+        /// Dessine l'écran de verrouillage : chemin de la base et saisie masquée du mot de passe
+        fn draw_locked(&self, frame: &mut Frame, path: &Path) {
+            let [main, status] = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+            let [_, middle, _] = Layout::vertical([Constraint::Fill(1), Constraint::Length(4), Constraint::Fill(1)]).areas(main);
+            let [_, area, _] = Layout::horizontal([Constraint::Percentage(20), Constraint::Percentage(60), Constraint::Percentage(20)]).areas(middle);
+            
+            let typed = match &self.mode {
+                Mode::Locked { password } => password.chars().count(),
+                _ => 0,
+            };
+            let lines = vec![
+                Line::from(path.display().to_string()).dim(),
+                Line::from(format!("Mot de passe maître: {}▏", "*".repeat(typed))),
+            ];
+            frame.render_widget(Paragraph::new(lines).block(Block::bordered().title("Base verrouillée")), area);
+            
+            let line = match &self.status {
+                Some(status) => Line::from(status.as_str()).bold(),
+                None => Line::from(LOCKED_HELP).dim(),
+            };
+            frame.render_widget(Paragraph::new(line), status);
+        }
+        
+        /// Cadre d'un panneau, mis en évidence lorsqu'il reçoit les touches de déplacement
+        fn block(&self, title: String, pane: Pane) -> Block<'static> {
+            let focused = self.pane == pane && self.mode == Mode::Browse;
+            Block::bordered().title(title).border_style(if focused { Style::new().cyan() } else { Style::new() })
+        }
+        
+        fn draw_groups(&self, frame: &mut Frame, area: Rect) {
+            let items: Vec<ListItem> = self.groups.iter().map(|row| {
+                let name = row.path.last().map(String::as_str).unwrap_or_default();
+                ListItem::new(format!("{}{}", "  ".repeat(row.path.len().saturating_sub(1)), name))
+            }).collect();
+            let list = List::new(items).block(self.block("Groupes".to_string(), Pane::Groups)).highlight_style(Style::new().reversed());
+            
+            let mut state = ListState::default().with_selected(Some(self.group_index));
+            frame.render_stateful_widget(list, area, &mut state);
+        }
+        
+        fn draw_entries(&self, frame: &mut Frame, area: Rect, db: &Database) {
+            let entries = self.entries(db);
+            let title = match self.query.is_empty() {
+                true => "Entrées".to_string(),
+                false => format!("Recherche '{}' ({} résultat(s))", self.query, entries.len()),
+            };
+            
+            let items: Vec<ListItem> = entries.iter().map(|(path, entry)| {
+                let mut line = format!("{:<30} {:<25} {}", entry.title, entry.username, entry.url);
+                if !self.query.is_empty() {
+                    line.push_str(&format!("  [{}]", path.join("/")));
+                }
+                match entry.archived {
+                    true => ListItem::new(line).dim(),
+                    false => ListItem::new(line),
+                }
+            }).collect();
+            let list = List::new(items).block(self.block(title, Pane::Entries)).highlight_style(Style::new().reversed());
+            
+            let mut state = ListState::default().with_selected((!entries.is_empty()).then_some(self.entry_index));
+            frame.render_stateful_widget(list, area, &mut state);
+        }
+        
+        fn draw_detail(&self, frame: &mut Frame, area: Rect, db: &Database, id: &EntryId) {
+            let Some(entry) = db.find_entry(id) else {
+                frame.render_widget(Paragraph::new("Entrée introuvable.").block(Block::bordered()), area);
+                return;
+            };
+            
+            let field_line = |field: Field| -> Line {
+                let value = match &self.mode {
+                    Mode::Edit { field: edited, buffer, .. } if *edited == field => {
+                        let shown = match field {
+                            Field::Password => "*".repeat(buffer.chars().count()),
+                            _ => buffer.clone(),
+                        };
+                        return Line::from(format!("{:<14} {}▏", field.label(), shown)).yellow();
+                    }
+                    Mode::Detail { revealed: true, .. } if field == Field::Password => entry.password.clone(),
+                    _ if field == Field::Password => MASK.to_string(),
+                    _ => field.value(entry).to_string(),
+                };
+                Line::from(format!("{:<14} {}", field.label(), value))
+            };
+            
+            let mut lines = vec![field_line(Field::Title), field_line(Field::Username), field_line(Field::Password), field_line(Field::Url)];
+            if let Some(path) = db.entry_path(id) {
+                lines.push(Line::from(format!("{:<14} {}", "Groupe", path.join("/"))));
+            }
+            if !entry.tags.is_empty() {
+                lines.push(Line::from(format!("{:<14} {}", "Étiquettes", entry.tags.join(", "))));
+            }
+            if let Some(expires_at) = entry.expires_at {
+                lines.push(Line::from(format!("{:<14} {}", "Expire le", expires_at.format("%d-%m-%Y"))));
+            }
+            lines.push(Line::from(format!("{:<14} {}", "Modifiée le", entry.updated_at.format("%d-%m-%Y %H:%M:%S"))));
+            if let Some(exposure) = &entry.last_exposure {
+                lines.push(Line::from(format!("{:<14} {}", "En clair", exposure)));
+            }
+            if !entry.notes.is_empty() {
+                lines.push(Line::from(""));
+                lines.extend(entry.notes.lines().map(|line| Line::from(line.to_string())));
+            }
+            
+            let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false }).block(Block::bordered().title(entry.title.clone()));
+            frame.render_widget(paragraph, area);
+        }
+    }
+    
+    /// Exécute l'effet d'une touche et retourne le message à afficher
+    fn perform(session: &mut Session, action: Action, timeout: u64) -> Result<Option<String>, String> {
+        match action {
+            Action::None | Action::Quit | Action::Lock | Action::Unlock(_) => Ok(None),
+            Action::CopyPassword(id) => {
+                let entry = session.db.find_entry(&id).ok_or_else(|| format!("Entrée avec ID '{}' non trouvée.", id))?;
+                copy_quietly(&entry.password, timeout).map_err(|e| e.to_string())?;
+                let message = match timeout {
+                    0 => format!("Mot de passe de '{}' copié.", entry.title),
+                    _ => format!("Mot de passe de '{}' copié (retiré dans {} secondes).", entry.title, timeout),
+                };
+                session.record_exposure(&[id], ExposureKind::Copied, ExposureInterface::Tui)?;
+                Ok(Some(message))
+            }
+            Action::CopyUsername(id) => {
+                let entry = session.db.find_entry(&id).ok_or_else(|| format!("Entrée avec ID '{}' non trouvée.", id))?;
+                copy_quietly(&entry.username, 0).map_err(|e| e.to_string())?;
+                Ok(Some(format!("Nom d'utilisateur de '{}' copié.", entry.title)))
+            }
+            Action::Reveal(id) => {
+                session.record_exposure(&[id], ExposureKind::Revealed, ExposureInterface::Tui)?;
+                Ok(None)
+            }
+            Action::Save(message) => {
+                session.save()?;
+                Ok(Some(message))
+            }
+        }
+    }
+    
+    /// Verrouille la base; une modification dont l'enregistrement a échoué est réessayée plutôt
+    /// que proposée dans le terminal, occupé par l'interface
+    fn lock(app: &mut App, mut session: Box<Session>) -> Result<Vault, String> {
+        if session.db.is_dirty()
+            && let Err(e) = session.save()
+        {
+            app.status = Some(format!("Erreur: {}", e));
+            return Ok(Vault::Unlocked(session));
+        }
+        app.lock();
+        Ok(Vault::Locked(session.lock()?))
+    }
+    
+    /// Déverrouille la base avec le mot de passe saisi; un refus laisse l'écran de verrouillage
+    fn unlock(app: &mut App, locked: LockedSession, mut password: Zeroizing<String>) -> Vault {
+        match locked.unlock(std::mem::take(&mut *password)) {
+            Ok(session) => {
+                app.unlock(&session.db);
+                Vault::Unlocked(Box::new(session))
+            }
+            Err(e) => {
+                app.status = Some(format!("Erreur: {}", e));
+                Vault::Locked(locked)
+            }
+        }
+    }
+    
+    /// Boucle principale : dessine, lit une touche, exécute son effet; retourne la base à la sortie
+    fn run(terminal: &mut DefaultTerminal, app: &mut App, mut vault: Vault, timeout: u64) -> Result<Vault, String> {
+        loop {
+            terminal.draw(|frame| match &vault {
+                Vault::Unlocked(session) => app.draw(frame, &session.db),
+                Vault::Locked(locked) => app.draw_locked(frame, locked.path()),
+            }).map_err(|e| e.to_string())?;
+            
+            let Event::Key(key) = event::read().map_err(|e| e.to_string())? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            
+            let action = match &mut vault {
+                Vault::Unlocked(session) => app.handle_key(&mut session.db, key),
+                Vault::Locked(_) => app.handle_locked_key(key),
+            };
+            vault = match (vault, action) {
+                (vault, Action::Quit) => return Ok(vault),
+                (Vault::Unlocked(session), Action::Lock) => lock(app, session)?,
+                (Vault::Locked(locked), Action::Unlock(password)) => unlock(app, locked, password),
+                (Vault::Unlocked(mut session), action) => {
+                    match perform(&mut session, action, timeout) {
+                        Ok(Some(message)) => app.status = Some(message),
+                        Ok(None) => {}
+                        Err(e) => app.status = Some(format!("Erreur: {}", e)),
+                    }
+                    Vault::Unlocked(session)
+                }
+                (vault, _) => vault,
+            };
+        }
+    }
+    
+    /// Ouvre l'interface plein écran : arborescence des groupes, liste des entrées et détail
+    pub fn cmd_tui(path: &Path, timeout: u64) -> Result<(), String> {
+        if !session::is_interactive() {
+            return Err("L'interface plein écran nécessite un terminal.".to_string());
+        }
+        if symbols::is_plain() {
+            return Err("L'interface plein écran n'est pas disponible avec --plain: utilisez 'keeprust shell'.".to_string());
+        }
+        
+        let session = Session::open(path)?;
+        let mut app = App::new(&session.db);
+        
+        let mut terminal = ratatui::init();
+        let result = run(&mut terminal, &mut app, Vault::Unlocked(Box::new(session)), timeout);
+        ratatui::restore();
+        
+        match result? {
+            Vault::Unlocked(session) => session.close(),
+            Vault::Locked(_) => Ok(()),
+        }
+    }
+    
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        
+        fn key(code: KeyCode) -> KeyEvent {
+            KeyEvent::new(code, KeyModifiers::NONE)
+        }
+        
+        #[test]
+        fn test_navigation_search_and_edit() {
+            let mut db = Database::new("Test".to_string(), String::new());
+            let work = db.add_group(None, "Work".to_string()).unwrap();
+            db.add_entry(Some(&work), Entry::builder().title("VPN").username("bob").password("secret").build().unwrap()).unwrap();
+            let mail = db.add_entry(Some(&work), Entry::builder().title("Mail").username("bob").password("secret").build().unwrap()).unwrap();
+            db.add_entry(None, Entry::builder().title("Banque").password("secret").build().unwrap()).unwrap();
+            
+            let mut app = App::new(&db);
+            assert_eq!(app.groups.iter().map(|row| row.path.join("/")).collect::<Vec<_>>(), vec!["Root", "Root/Work"]);
+            
+            // Groupe Work, entrées triées par titre
+            app.handle_key(&mut db, key(KeyCode::Down));
+            assert_eq!(app.handle_key(&mut db, key(KeyCode::Enter)), Action::None);
+            assert_eq!(app.pane, Pane::Entries);
+            assert_eq!(app.selected_entry(&db), Some(mail));
+            assert_eq!(app.handle_key(&mut db, key(KeyCode::Char('c'))), Action::CopyPassword(mail));
+            
+            // Recherche incrémentale dans toute la base
+            app.handle_key(&mut db, key(KeyCode::Char('/')));
+            for c in "BAN".chars() {
+                app.handle_key(&mut db, key(KeyCode::Char(c)));
+            }
+            let found = app.entries(&db);
+            assert_eq!(found.len(), 1);
+            assert_eq!(found[0].1.title, "Banque");
+            app.handle_key(&mut db, key(KeyCode::Esc));
+            assert_eq!(app.entries(&db).len(), 2);
+            
+            // Modification du nom d'utilisateur depuis le détail
+            app.handle_key(&mut db, key(KeyCode::Enter));
+            assert_eq!(app.mode, Mode::Detail { id: mail, revealed: false });
+            assert_eq!(app.handle_key(&mut db, key(KeyCode::Char('p'))), Action::Reveal(mail));
+            app.handle_key(&mut db, key(KeyCode::Char('e')));
+            app.handle_key(&mut db, key(KeyCode::Tab));
+            app.handle_key(&mut db, key(KeyCode::Backspace));
+            assert_eq!(app.handle_key(&mut db, key(KeyCode::Enter)), Action::Save("Utilisateur modifié.".to_string()));
+            assert_eq!(db.find_entry(&mail).unwrap().username, "bo");
+            
+            assert_eq!(app.handle_key(&mut db, key(KeyCode::Esc)), Action::None);
+            assert_eq!(app.handle_key(&mut db, key(KeyCode::Char('q'))), Action::Quit);
+        }
+        
+        #[test]
+        fn test_lock_screen_forgets_the_base_and_reads_the_password() {
+            let mut db = Database::new("Test".to_string(), String::new());
+            let work = db.add_group(None, "Work".to_string()).unwrap();
+            let mail = db.add_entry(Some(&work), Entry::builder().title("Mail").password("secret").build().unwrap()).unwrap();
+            
+            let mut app = App::new(&db);
+            app.handle_key(&mut db, key(KeyCode::Down));
+            app.handle_key(&mut db, key(KeyCode::Enter));
+            app.handle_key(&mut db, key(KeyCode::Enter));
+            assert_eq!(app.mode, Mode::Detail { id: mail, revealed: false });
+            assert_eq!(app.handle_key(&mut db, key(KeyCode::Char('v'))), Action::Lock);
+            
+            app.lock();
+            assert!(app.groups.is_empty());
+            for c in "pwx".chars() {
+                assert_eq!(app.handle_locked_key(key(KeyCode::Char(c))), Action::None);
+            }
+            app.handle_locked_key(key(KeyCode::Backspace));
+            assert_eq!(app.handle_locked_key(key(KeyCode::Enter)), Action::Unlock(Zeroizing::new("pw".to_string())));
+            assert_eq!(app.mode, Mode::Locked { password: Zeroizing::new(String::new()) });
+            
+            // Retour au groupe où se trouvait la sélection
+            app.unlock(&db);
+            assert_eq!(app.mode, Mode::Browse);
+            assert_eq!(app.selected_entry(&db), Some(mail));
+            assert_eq!(app.handle_locked_key(key(KeyCode::Esc)), Action::None);
+            app.lock();
+            assert_eq!(app.handle_locked_key(key(KeyCode::Esc)), Action::Quit);
+        }
+    }
+}
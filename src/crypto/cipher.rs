@@ -1,13 +1,15 @@
 use aes_gcm::{
-    aead::{Aead, AeadCore, KeyInit, OsRng},
-    Aes256Gcm, Key
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    Aes256Gcm, Key, Nonce
 };
+use rand::{CryptoRng, RngCore};
 use base64::{Engine as _, engine::general_purpose};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Enumération des erreurs possibles lors du chiffrement/déchiffrement
 #[derive(Error, Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum CipherError {
     #[error("Erreur de chiffrement: {0}")]
     EncryptionError(String),
@@ -30,7 +32,13 @@ pub struct EncryptedData {
 }
 
 /// Chiffre des données avec AES-256-GCM
+#[allow(dead_code)]
 pub fn encrypt(plaintext: &[u8], key: &[u8; 32]) -> Result<EncryptedData, CipherError> {
+    encrypt_with_rng(plaintext, key, &mut OsRng)
+}
+
+/// Chiffre des données avec AES-256-GCM en utilisant le générateur aléatoire fourni pour le nonce
+pub fn encrypt_with_rng<R: RngCore + CryptoRng>(plaintext: &[u8], key: &[u8; 32], rng: &mut R) -> Result<EncryptedData, CipherError> {
     // Créer la clé AES
     let key = Key::<Aes256Gcm>::from_slice(key);
     
@@ -38,7 +46,7 @@ pub fn encrypt(plaintext: &[u8], key: &[u8; 32]) -> Result<EncryptedData, Cipher
     let cipher = Aes256Gcm::new(key);
     
     // Générer un nonce aléatoire
-    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let nonce = Aes256Gcm::generate_nonce(rng);
     
     // Chiffrer les données
     let ciphertext = cipher.encrypt(&nonce, plaintext)
@@ -79,4 +87,44 @@ pub fn decrypt(encrypted_data: &EncryptedData, key: &[u8; 32]) -> Result<Vec<u8>
         .map_err(|e| CipherError::DecryptionError(e.to_string()))?;
     
     Ok(plaintext)
+}
+
+/// Taille du nonce AES-GCM en octets
+pub const NONCE_LEN: usize = 12;
+
+/// Bloc chiffré indépendamment des autres, stocké sous forme binaire
+#[derive(Debug, Clone)]
+pub struct EncryptedChunk {
+    /// Nonce propre au bloc
+    pub nonce: [u8; NONCE_LEN],
+    
+    /// Données chiffrées suivies de l'étiquette d'authentification
+    pub ciphertext: Vec<u8>,
+}
+
+/// Chiffre un bloc avec AES-256-GCM en authentifiant des données associées (non chiffrées)
+pub fn encrypt_chunk(plaintext: &[u8], key: &[u8; 32], aad: &[u8]) -> Result<EncryptedChunk, CipherError> {
+    encrypt_chunk_with_rng(plaintext, key, aad, &mut OsRng)
+}
+
+/// Version de `encrypt_chunk` utilisant le générateur aléatoire fourni pour le nonce
+pub fn encrypt_chunk_with_rng<R: RngCore + CryptoRng>(plaintext: &[u8], key: &[u8; 32], aad: &[u8], rng: &mut R) -> Result<EncryptedChunk, CipherError> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(rng);
+    
+    let ciphertext = cipher.encrypt(&nonce, Payload { msg: plaintext, aad })
+        .map_err(|e| CipherError::EncryptionError(e.to_string()))?;
+    
+    Ok(EncryptedChunk {
+        nonce: nonce.into(),
+        ciphertext,
+    })
+}
+
+/// Déchiffre un bloc et vérifie son authenticité ainsi que celle des données associées
+pub fn decrypt_chunk(chunk: &EncryptedChunk, key: &[u8; 32], aad: &[u8]) -> Result<Vec<u8>, CipherError> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    
+    cipher.decrypt(Nonce::from_slice(&chunk.nonce), Payload { msg: &chunk.ciphertext, aad })
+        .map_err(|e| CipherError::DecryptionError(e.to_string()))
 }
\ No newline at end of file
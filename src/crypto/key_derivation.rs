@@ -1,8 +1,43 @@
-use ring::{pbkdf2, rand::{SecureRandom, SystemRandom}};
-use std::num::NonZeroU32;
+use argon2::{Algorithm, Argon2, Params, Version};
+use pbkdf2::pbkdf2_hmac;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use serde::{Serialize, Deserialize};
+use sha2::Sha256;
+use std::fmt;
 use thiserror::Error;
 use base64::{Engine, engine::general_purpose};
 
+/// Mémoire utilisée par Argon2id pour les nouveaux enregistrements, en Kio (64 Mio, RFC 9106)
+pub const DEFAULT_ARGON2_MEMORY_KIB: u32 = 64 * 1024;
+/// Nombre de passes Argon2id pour les nouveaux enregistrements
+pub const DEFAULT_ARGON2_ITERATIONS: u32 = 3;
+/// Nombre de voies Argon2id pour les nouveaux enregistrements
+pub const DEFAULT_ARGON2_PARALLELISM: u32 = 4;
+/// Mémoire Argon2id en dessous de laquelle les paramètres sont jugés faibles, en Kio (19 Mio)
+pub const MIN_ARGON2_MEMORY_KIB: u32 = 19 * 1024;
+/// Mémoire Argon2id maximale acceptée, en Kio (4 Gio) : les paramètres sont lus dans l'en-tête
+/// du fichier, avant toute authentification
+pub const MAX_ARGON2_MEMORY_KIB: u32 = 4 * 1024 * 1024;
+/// Nombre maximal de passes Argon2id accepté
+pub const MAX_ARGON2_ITERATIONS: u32 = 64;
+/// Nombre maximal de voies Argon2id accepté
+pub const MAX_ARGON2_PARALLELISM: u32 = 64;
+/// Nombre maximal d'itérations PBKDF2 accepté (quelques secondes de calcul)
+pub const MAX_PBKDF2_ITERATIONS: u32 = 10_000_000;
+/// Coût scrypt (log2 de N) pour les nouvelles bases créées avec scrypt (N = 2^17, recommandation OWASP)
+pub const DEFAULT_SCRYPT_LOG_N: u32 = 17;
+/// Taille de bloc scrypt (r) pour les nouvelles bases
+pub const DEFAULT_SCRYPT_BLOCK_SIZE: u32 = 8;
+/// Parallélisme scrypt (p) pour les nouvelles bases
+pub const DEFAULT_SCRYPT_PARALLELISM: u32 = 1;
+/// Coût scrypt (log2 de N) en dessous duquel les paramètres sont jugés faibles
+pub const MIN_SCRYPT_LOG_N: u32 = 15;
+/// Mémoire scrypt maximale acceptée (128 × r × N octets), soit 1 Gio : N = 2^20 avec r = 8
+pub const MAX_SCRYPT_MEMORY_BYTES: u64 = 1 << 30;
+/// Parallélisme scrypt (p) maximal accepté
+pub const MAX_SCRYPT_PARALLELISM: u32 = 16;
+
 /// Enumération des erreurs possibles lors de la dérivation de clé
 #[derive(Error, Debug)]
 pub enum KeyDerivationError {
@@ -10,6 +45,327 @@ pub enum KeyDerivationError {
     DerivationError(String),
 }
 
+/// Algorithme et paramètres de dérivation de clé, enregistrés dans l'en-tête du fichier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "algorithm", rename_all = "lowercase")]
+pub enum KdfParams {
+    /// PBKDF2-HMAC-SHA256 (fichiers enregistrés avant la version 3 du format)
+    Pbkdf2 { iterations: u32 },
+    
+    /// Argon2id (RFC 9106), résistant aux attaques sur GPU grâce à son coût en mémoire
+    Argon2id { memory_kib: u32, iterations: u32, parallelism: u32 },
+    
+    /// scrypt (RFC 7914), pour les bases migrées depuis des outils qui l'utilisent
+    Scrypt { log_n: u32, block_size: u32, parallelism: u32 },
+}
+
+impl KdfParams {
+    /// Paramètres utilisés pour tout nouvel enregistrement
+    pub fn recommended() -> Self {
+        KdfParams::Argon2id {
+            memory_kib: DEFAULT_ARGON2_MEMORY_KIB,
+            iterations: DEFAULT_ARGON2_ITERATIONS,
+            parallelism: DEFAULT_ARGON2_PARALLELISM,
+        }
+    }
+    
+    /// Indique si les paramètres sont faibles : moins de `min_iterations` itérations PBKDF2,
+    /// ou moins de mémoire que `MIN_ARGON2_MEMORY_KIB` pour Argon2id
+    pub fn is_weak(&self, min_iterations: u32) -> bool {
+        self.kdf().is_weak(&self.values(), min_iterations)
+    }
+    
+    /// Paramètres à utiliser au prochain enregistrement : un fichier PBKDF2 passe aux paramètres
+    /// recommandés, les paramètres Argon2id ou scrypt choisis pour le fichier sont conservés
+    pub fn upgraded(self) -> Self {
+        match self.kdf().is_legacy() {
+            true => Self::recommended(),
+            false => self,
+        }
+    }
+    
+    /// Vérifie que les paramètres sont utilisables
+    pub fn validate(&self) -> Result<(), KeyDerivationError> {
+        self.kdf().validate(&self.values())
+    }
+    
+    /// Algorithme désigné par les paramètres
+    pub fn kdf(&self) -> &'static dyn Kdf {
+        match self {
+            KdfParams::Pbkdf2 { .. } => &Pbkdf2,
+            KdfParams::Argon2id { .. } => &Argon2id,
+            KdfParams::Scrypt { .. } => &Scrypt,
+        }
+    }
+    
+    /// Valeurs des paramètres, dans l'ordre de `Kdf::parameters`
+    pub fn values(&self) -> Vec<u32> {
+        match *self {
+            KdfParams::Pbkdf2 { iterations } => vec![iterations],
+            KdfParams::Argon2id { memory_kib, iterations, parallelism } => vec![memory_kib, iterations, parallelism],
+            KdfParams::Scrypt { log_n, block_size, parallelism } => vec![log_n, block_size, parallelism],
+        }
+    }
+    
+    /// Paramètres avec leur description, pour un affichage indépendant de l'algorithme
+    pub fn described(&self) -> Vec<(&'static KdfParameter, u32)> {
+        self.kdf().parameters().iter().zip(self.values()).collect()
+    }
+}
+
+impl fmt::Display for KdfParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kdf().name())?;
+        for (parameter, value) in self.described() {
+            match parameter.unit {
+                "" => write!(f, ", {}={}", parameter.name, value)?,
+                unit => write!(f, ", {} {}", value, unit)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Paramètre numérique d'un algorithme de dérivation, enregistré sur 4 octets dans l'en-tête binaire
+#[derive(Debug, PartialEq, Eq)]
+pub struct KdfParameter {
+    /// Nom du paramètre
+    pub name: &'static str,
+    
+    /// Unité affichée après la valeur (vide : la valeur est affichée après le nom)
+    pub unit: &'static str,
+}
+
+/// Algorithme de dérivation de clé.
+///
+/// Chaque algorithme est inscrit dans `REGISTRY` sous l'identifiant qu'il occupe dans l'en-tête
+/// binaire des fichiers : l'en-tête est lu et écrit sans connaître les algorithmes disponibles.
+pub trait Kdf: Sync {
+    /// Identifiant de l'algorithme dans l'en-tête binaire (jamais réattribué)
+    fn id(&self) -> u8;
+    
+    /// Nom affiché
+    fn name(&self) -> &'static str;
+    
+    /// Nom utilisé dans les fichiers et sur la ligne de commande (`keeprust new --kdf`)
+    fn key(&self) -> &'static str;
+    
+    /// Algorithme conservé pour ouvrir les anciens fichiers, remplacé au prochain enregistrement
+    fn is_legacy(&self) -> bool {
+        false
+    }
+    
+    /// Paramètres utilisés pour une nouvelle base
+    fn recommended(&self) -> KdfParams;
+    
+    /// Paramètres de l'algorithme, dans leur ordre d'enregistrement
+    fn parameters(&self) -> &'static [KdfParameter];
+    
+    /// Paramètres correspondant aux valeurs lues dans l'en-tête; None si leur nombre ne
+    /// correspond pas à `parameters`
+    fn params(&self, values: &[u32]) -> Option<KdfParams>;
+    
+    /// Vérifie que les valeurs sont utilisables
+    fn validate(&self, values: &[u32]) -> Result<(), KeyDerivationError>;
+    
+    /// Indique si les valeurs sont trop faibles (`min_iterations` : plancher PBKDF2 configuré)
+    fn is_weak(&self, values: &[u32], min_iterations: u32) -> bool;
+    
+    /// Dérive une clé de 256 bits
+    fn derive(&self, password: &str, salt: &[u8], values: &[u32]) -> Result<[u8; 32], KeyDerivationError>;
+}
+
+/// Algorithmes disponibles, par identifiant d'en-tête
+pub const REGISTRY: &[&dyn Kdf] = &[&Pbkdf2, &Argon2id, &Scrypt];
+
+/// Algorithme inscrit sous l'identifiant d'en-tête `id`
+pub fn find(id: u8) -> Option<&'static dyn Kdf> {
+    REGISTRY.iter().copied().find(|kdf| kdf.id() == id)
+}
+
+/// Algorithmes proposés pour une nouvelle base (hors algorithmes conservés pour les anciens fichiers)
+pub fn selectable() -> impl Iterator<Item = &'static dyn Kdf> {
+    REGISTRY.iter().copied().filter(|kdf| !kdf.is_legacy())
+}
+
+/// Paramètres recommandés de l'algorithme `key` (argon2id, scrypt)
+impl std::str::FromStr for KdfParams {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key = s.trim().to_lowercase();
+        selectable().find(|kdf| kdf.key() == key).map(|kdf| kdf.recommended()).ok_or_else(|| {
+            let keys: Vec<&str> = selectable().map(|kdf| kdf.key()).collect();
+            format!("Algorithme de dérivation inconnu: '{}' ({})", s, keys.join(" ou "))
+        })
+    }
+}
+
+/// PBKDF2-HMAC-SHA256
+struct Pbkdf2;
+
+impl Kdf for Pbkdf2 {
+    fn id(&self) -> u8 {
+        1
+    }
+    
+    fn name(&self) -> &'static str {
+        "PBKDF2-HMAC-SHA256"
+    }
+    
+    fn key(&self) -> &'static str {
+        "pbkdf2"
+    }
+    
+    fn is_legacy(&self) -> bool {
+        true
+    }
+    
+    fn recommended(&self) -> KdfParams {
+        KdfParams::recommended()
+    }
+    
+    fn parameters(&self) -> &'static [KdfParameter] {
+        &[KdfParameter { name: "itérations", unit: "itérations" }]
+    }
+    
+    fn params(&self, values: &[u32]) -> Option<KdfParams> {
+        match *values {
+            [iterations] => Some(KdfParams::Pbkdf2 { iterations }),
+            _ => None,
+        }
+    }
+    
+    fn validate(&self, values: &[u32]) -> Result<(), KeyDerivationError> {
+        match values {
+            [0] => Err(KeyDerivationError::DerivationError("Le nombre d'itérations doit être supérieur à 0".to_string())),
+            [iterations] if *iterations > MAX_PBKDF2_ITERATIONS => Err(KeyDerivationError::DerivationError(
+                format!("{} itérations PBKDF2: le maximum accepté est {}", iterations, MAX_PBKDF2_ITERATIONS)
+            )),
+            [_] => Ok(()),
+            _ => Err(KeyDerivationError::DerivationError("Paramètres PBKDF2 invalides".to_string())),
+        }
+    }
+    
+    fn is_weak(&self, values: &[u32], min_iterations: u32) -> bool {
+        values.first().is_none_or(|&iterations| iterations < min_iterations)
+    }
+    
+    fn derive(&self, password: &str, salt: &[u8], values: &[u32]) -> Result<[u8; 32], KeyDerivationError> {
+        self.validate(values)?;
+        Ok(pbkdf2_sha256(password, salt, values[0]))
+    }
+}
+
+/// Argon2id (RFC 9106)
+struct Argon2id;
+
+impl Kdf for Argon2id {
+    fn id(&self) -> u8 {
+        2
+    }
+    
+    fn name(&self) -> &'static str {
+        "Argon2id"
+    }
+    
+    fn key(&self) -> &'static str {
+        "argon2id"
+    }
+    
+    fn recommended(&self) -> KdfParams {
+        KdfParams::recommended()
+    }
+    
+    fn parameters(&self) -> &'static [KdfParameter] {
+        &[
+            KdfParameter { name: "mémoire", unit: "Kio" },
+            KdfParameter { name: "passes", unit: "passe(s)" },
+            KdfParameter { name: "voies", unit: "voie(s)" },
+        ]
+    }
+    
+    fn params(&self, values: &[u32]) -> Option<KdfParams> {
+        match *values {
+            [memory_kib, iterations, parallelism] => Some(KdfParams::Argon2id { memory_kib, iterations, parallelism }),
+            _ => None,
+        }
+    }
+    
+    fn validate(&self, values: &[u32]) -> Result<(), KeyDerivationError> {
+        argon2_params(values).map(|_| ())
+    }
+    
+    fn is_weak(&self, values: &[u32], _min_iterations: u32) -> bool {
+        values.first().is_none_or(|&memory_kib| memory_kib < MIN_ARGON2_MEMORY_KIB)
+    }
+    
+    fn derive(&self, password: &str, salt: &[u8], values: &[u32]) -> Result<[u8; 32], KeyDerivationError> {
+        let params = argon2_params(values)?;
+        let mut key = [0u8; 32];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(password.as_bytes(), salt, &mut key)
+            .map_err(|e| KeyDerivationError::DerivationError(e.to_string()))?;
+        Ok(key)
+    }
+}
+
+/// scrypt (RFC 7914)
+struct Scrypt;
+
+impl Kdf for Scrypt {
+    fn id(&self) -> u8 {
+        3
+    }
+    
+    fn name(&self) -> &'static str {
+        "scrypt"
+    }
+    
+    fn key(&self) -> &'static str {
+        "scrypt"
+    }
+    
+    fn recommended(&self) -> KdfParams {
+        KdfParams::Scrypt {
+            log_n: DEFAULT_SCRYPT_LOG_N,
+            block_size: DEFAULT_SCRYPT_BLOCK_SIZE,
+            parallelism: DEFAULT_SCRYPT_PARALLELISM,
+        }
+    }
+    
+    fn parameters(&self) -> &'static [KdfParameter] {
+        &[
+            KdfParameter { name: "log2(N)", unit: "" },
+            KdfParameter { name: "r", unit: "" },
+            KdfParameter { name: "p", unit: "" },
+        ]
+    }
+    
+    fn params(&self, values: &[u32]) -> Option<KdfParams> {
+        match *values {
+            [log_n, block_size, parallelism] => Some(KdfParams::Scrypt { log_n, block_size, parallelism }),
+            _ => None,
+        }
+    }
+    
+    fn validate(&self, values: &[u32]) -> Result<(), KeyDerivationError> {
+        scrypt_params(values).map(|_| ())
+    }
+    
+    fn is_weak(&self, values: &[u32], _min_iterations: u32) -> bool {
+        values.first().is_none_or(|&log_n| log_n < MIN_SCRYPT_LOG_N)
+    }
+    
+    fn derive(&self, password: &str, salt: &[u8], values: &[u32]) -> Result<[u8; 32], KeyDerivationError> {
+        let params = scrypt_params(values)?;
+        let mut key = [0u8; 32];
+        scrypt::scrypt(password.as_bytes(), salt, &params, &mut key)
+            .map_err(|e| KeyDerivationError::DerivationError(e.to_string()))?;
+        Ok(key)
+    }
+}
+
 /// Structure contenant la clé dérivée et le sel utilisé
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -20,56 +376,172 @@ pub struct MasterKey {
     /// Clé dérivée (32 octets)
     pub key: [u8; 32],
     
-    /// Nombre d'itérations utilisées
-    pub iterations: u32,
+    /// Paramètres de dérivation utilisés
+    pub params: KdfParams,
 }
 
 /// Dérive une clé à partir d'un mot de passe
-pub fn derive_key_from_password(password: &str, iterations: u32) -> Result<MasterKey, KeyDerivationError> {
+pub fn derive_key_from_password(password: &str, params: KdfParams) -> Result<MasterKey, KeyDerivationError> {
+    derive_key_from_password_with_rng(password, params, &mut OsRng)
+}
+
+/// Dérive une clé à partir d'un mot de passe en utilisant le générateur aléatoire fourni pour le sel
+pub fn derive_key_from_password_with_rng<R: RngCore + CryptoRng>(password: &str, params: KdfParams, rng: &mut R) -> Result<MasterKey, KeyDerivationError> {
     // Générer un sel aléatoire
-    let rng = SystemRandom::new();
     let mut salt_bytes = [0u8; 16];
-    rng.fill(&mut salt_bytes).map_err(|_| KeyDerivationError::DerivationError("Échec de génération de sel aléatoire".to_string()))?;
+    rng.try_fill_bytes(&mut salt_bytes).map_err(|_| KeyDerivationError::DerivationError("Échec de génération de sel aléatoire".to_string()))?;
     
     // Convertir le sel en base64 pour le stockage
     let salt = general_purpose::STANDARD.encode(salt_bytes);
     
     // Dériver la clé
-    let mut key = [0u8; 32]; // 256 bits
-    pbkdf2::derive(
-        pbkdf2::PBKDF2_HMAC_SHA256,
-        NonZeroU32::new(iterations).unwrap(),
-        &salt_bytes,
-        password.as_bytes(),
-        &mut key,
-    );
+    let key = derive(password, &salt_bytes, params)?;
     
     Ok(MasterKey {
         salt,
         key,
-        iterations,
+        params,
     })
 }
 
 /// Vérifie un mot de passe et dérive la clé correspondante
-pub fn verify_and_derive_key(password: &str, salt: &str, iterations: u32) -> Result<MasterKey, KeyDerivationError> {
+pub fn verify_and_derive_key(password: &str, salt: &str, params: KdfParams) -> Result<MasterKey, KeyDerivationError> {
     // Décoder le sel de base64
     let salt_bytes = general_purpose::STANDARD.decode(salt)
         .map_err(|e| KeyDerivationError::DerivationError(format!("Format de sel invalide: {}", e)))?;
     
     // Dériver la clé
-    let mut key = [0u8; 32]; // 256 bits
-    pbkdf2::derive(
-        pbkdf2::PBKDF2_HMAC_SHA256,
-        NonZeroU32::new(iterations).unwrap(),
-        &salt_bytes,
-        password.as_bytes(),
-        &mut key,
-    );
+    let key = derive(password, &salt_bytes, params)?;
     
     Ok(MasterKey {
         salt: salt.to_string(),
         key,
-        iterations,
+        params,
     })
+}
+
+/// Dérive une clé de 256 bits avec l'algorithme désigné par les paramètres
+fn derive(password: &str, salt: &[u8], params: KdfParams) -> Result<[u8; 32], KeyDerivationError> {
+    params.kdf().derive(password, salt, &params.values())
+}
+
+/// Paramètres Argon2 validés (mémoire en Kio, nombre de passes, nombre de voies)
+fn argon2_params(values: &[u32]) -> Result<Params, KeyDerivationError> {
+    let &[memory_kib, iterations, parallelism] = values else {
+        return Err(KeyDerivationError::DerivationError("Paramètres Argon2id invalides".to_string()));
+    };
+    if memory_kib > MAX_ARGON2_MEMORY_KIB {
+        return Err(KeyDerivationError::DerivationError(format!(
+            "Paramètres Argon2id invalides: {} Mio de mémoire, le maximum accepté est {} Mio", memory_kib / 1024, MAX_ARGON2_MEMORY_KIB / 1024
+        )));
+    }
+    if iterations > MAX_ARGON2_ITERATIONS || parallelism > MAX_ARGON2_PARALLELISM {
+        return Err(KeyDerivationError::DerivationError(format!(
+            "Paramètres Argon2id invalides: au plus {} passes et {} voies", MAX_ARGON2_ITERATIONS, MAX_ARGON2_PARALLELISM
+        )));
+    }
+    Params::new(memory_kib, iterations, parallelism, Some(32))
+        .map_err(|e| KeyDerivationError::DerivationError(format!("Paramètres Argon2id invalides: {}", e)))
+}
+
+/// Paramètres scrypt validés (log2 de N, taille de bloc r, parallélisme p)
+fn scrypt_params(values: &[u32]) -> Result<scrypt::Params, KeyDerivationError> {
+    let &[log_n, block_size, parallelism] = values else {
+        return Err(KeyDerivationError::DerivationError("Paramètres scrypt invalides".to_string()));
+    };
+    let memory = 1u64.checked_shl(log_n).map(|n| n.saturating_mul(128).saturating_mul(u64::from(block_size)));
+    let log_n = u8::try_from(log_n).ok().filter(|&log_n| log_n >= 1)
+        .ok_or_else(|| KeyDerivationError::DerivationError("Paramètres scrypt invalides: log2(N) doit être au moins 1".to_string()))?;
+    if memory.is_none_or(|memory| memory > MAX_SCRYPT_MEMORY_BYTES) {
+        return Err(KeyDerivationError::DerivationError(format!(
+            "Paramètres scrypt invalides: 128 × r × N dépasse le maximum accepté de {} Mio", MAX_SCRYPT_MEMORY_BYTES / (1024 * 1024)
+        )));
+    }
+    if parallelism > MAX_SCRYPT_PARALLELISM {
+        return Err(KeyDerivationError::DerivationError(format!(
+            "Paramètres scrypt invalides: p = {}, le maximum accepté est {}", parallelism, MAX_SCRYPT_PARALLELISM
+        )));
+    }
+    scrypt::Params::new(log_n, block_size, parallelism, 32)
+        .map_err(|e| KeyDerivationError::DerivationError(format!("Paramètres scrypt invalides: {}", e)))
+}
+
+/// Calcule PBKDF2-HMAC-SHA256 sur 256 bits (implémentation en Rust pur, compatible WebAssembly)
+fn pbkdf2_sha256(password: &str, salt: &[u8], iterations: u32) -> [u8; 32] {
+    let mut key = [0u8; 32]; // 256 bits
+    pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, iterations, &mut key);
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_pbkdf2_matches_reference_vector() {
+        // RFC 7914, section 11 : P = "passwd", S = "salt", c = 1 (32 premiers octets)
+        let master_key = verify_and_derive_key("passwd", "c2FsdA==", KdfParams::Pbkdf2 { iterations: 1 }).unwrap();
+        let expected = [
+            0x55, 0xac, 0x04, 0x6e, 0x56, 0xe3, 0x08, 0x9f, 0xec, 0x16, 0x91, 0xc2, 0x25, 0x44, 0xb6, 0x05,
+            0xf9, 0x41, 0x85, 0x21, 0x6d, 0xde, 0x04, 0x65, 0xe6, 0x8b, 0x9d, 0x57, 0xc2, 0x0d, 0xac, 0xbc,
+        ];
+        
+        assert_eq!(master_key.key, expected);
+        assert!(verify_and_derive_key("passwd", "c2FsdA==", KdfParams::Pbkdf2 { iterations: 0 }).is_err());
+    }
+    
+    #[test]
+    fn test_argon2id_matches_reference_vector() {
+        // Vecteur de l'implémentation de référence (phc-winner-argon2) : t = 2, m = 256 Kio, p = 1
+        let params = KdfParams::Argon2id { memory_kib: 256, iterations: 2, parallelism: 1 };
+        let master_key = verify_and_derive_key("password", &general_purpose::STANDARD.encode("somesalt"), params).unwrap();
+        let expected = [
+            0x9d, 0xfe, 0xb9, 0x10, 0xe8, 0x0b, 0xad, 0x03, 0x11, 0xfe, 0xe2, 0x0f, 0x9c, 0x0e, 0x2b, 0x12,
+            0xc1, 0x79, 0x87, 0xb4, 0xca, 0xc9, 0x0c, 0x2e, 0xf5, 0x4d, 0x5b, 0x30, 0x21, 0xc6, 0x8b, 0xfe,
+        ];
+        
+        assert_eq!(master_key.key, expected);
+        assert!(KdfParams::Argon2id { memory_kib: 1, iterations: 1, parallelism: 1 }.validate().is_err());
+        assert!(!KdfParams::recommended().is_weak(u32::MAX));
+    }
+    
+    #[test]
+    fn test_registry_round_trip() {
+        let all = [KdfParams::Pbkdf2 { iterations: 600_000 }, KdfParams::recommended(), Scrypt.recommended()];
+        for params in all {
+            let kdf = find(params.kdf().id()).unwrap();
+            assert_eq!(kdf.params(&params.values()), Some(params));
+            assert_eq!(kdf.params(&[]), None);
+        }
+        assert_eq!(KdfParams::recommended().to_string(), "Argon2id, 65536 Kio, 3 passe(s), 4 voie(s)");
+        assert_eq!(all[0].to_string(), "PBKDF2-HMAC-SHA256, 600000 itérations");
+        assert_eq!(all[2].to_string(), "scrypt, log2(N)=17, r=8, p=1");
+        assert!(find(0).is_none());
+        
+        assert_eq!("scrypt".parse::<KdfParams>(), Ok(all[2]));
+        assert!("pbkdf2".parse::<KdfParams>().is_err());
+        assert_eq!(all[0].upgraded(), KdfParams::recommended());
+        assert_eq!(all[2].upgraded(), all[2]);
+    }
+    
+    #[test]
+    fn test_scrypt_matches_reference_vector() {
+        // RFC 7914, section 12 : P = "password", S = "NaCl", N = 1024, r = 8, p = 16 (32 premiers octets)
+        let params = KdfParams::Scrypt { log_n: 10, block_size: 8, parallelism: 16 };
+        let master_key = verify_and_derive_key("password", &general_purpose::STANDARD.encode("NaCl"), params).unwrap();
+        let expected = [
+            0xfd, 0xba, 0xbe, 0x1c, 0x9d, 0x34, 0x72, 0x00, 0x78, 0x56, 0xe7, 0x19, 0x0d, 0x01, 0xe9, 0xfe,
+            0x7c, 0x6a, 0xd7, 0xcb, 0xc8, 0x23, 0x78, 0x30, 0xe7, 0x73, 0x76, 0x63, 0x4b, 0x37, 0x31, 0x62,
+        ];
+        
+        assert_eq!(master_key.key, expected);
+        assert!(KdfParams::Scrypt { log_n: 40, block_size: 8, parallelism: 1 }.validate().is_err());
+        
+        // Mémoire bornée à 1 Gio (128 × r × N) et parallélisme borné
+        assert!(KdfParams::Scrypt { log_n: 20, block_size: 8, parallelism: 1 }.validate().is_ok());
+        assert!(KdfParams::Scrypt { log_n: 21, block_size: 8, parallelism: 1 }.validate().is_err());
+        assert!(KdfParams::Scrypt { log_n: 17, block_size: u32::MAX, parallelism: 1 }.validate().is_err());
+        assert!(KdfParams::Scrypt { log_n: 17, block_size: 8, parallelism: u32::MAX }.validate().is_err());
+        assert!(KdfParams::Scrypt { log_n: 14, block_size: 8, parallelism: 1 }.is_weak(0));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,74 @@
+use data_encoding::HEXLOWER;
+use rand::{RngCore, rngs::OsRng};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+/// Taille d'un fichier clé généré, en octets
+pub const KEYFILE_LEN: usize = 32;
+
+/// Erreurs liées au fichier clé
+#[derive(Error, Debug, PartialEq)]
+pub enum KeyFileError {
+    #[error("Le fichier clé est vide")]
+    Empty,
+    
+    #[error("Le mot de passe ne peut pas contenir de caractère nul avec un fichier clé")]
+    NulInPassword,
+}
+
+/// Contenu d'un nouveau fichier clé : `KEYFILE_LEN` octets aléatoires
+pub fn generate() -> Zeroizing<[u8; KEYFILE_LEN]> {
+    let mut key = Zeroizing::new([0u8; KEYFILE_LEN]);
+    OsRng.fill_bytes(key.as_mut());
+    key
+}
+
+/// Secret maître combinant le mot de passe et le contenu d'un fichier clé, transmis tel quel à
+/// la dérivation de clé : mot de passe, octet nul, puis l'empreinte SHA-256 du fichier.
+///
+/// Le mot de passe ne doit pas contenir d'octet nul, pour que le séparateur reste non ambigu.
+/// N'importe quel fichier non vide peut servir de fichier clé; son contenu n'est enregistré nulle
+/// part et la base ne s'ouvre plus sans lui.
+pub fn combine(password: &str, keyfile: &[u8]) -> Result<Zeroizing<String>, KeyFileError> {
+    if keyfile.is_empty() {
+        return Err(KeyFileError::Empty);
+    }
+    if password.contains('\0') {
+        return Err(KeyFileError::NulInPassword);
+    }
+    let digest = Zeroizing::new(Sha256::digest(keyfile).to_vec());
+    let digest = Zeroizing::new(HEXLOWER.encode(&digest));
+    
+    // Capacité exacte : aucune réallocation ne laisse de copie du mot de passe en mémoire
+    let mut secret = Zeroizing::new(String::with_capacity(password.len() + 1 + digest.len()));
+    secret.push_str(password);
+    secret.push('\0');
+    secret.push_str(&digest);
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::key_derivation::{self, KdfParams};
+    
+    #[test]
+    fn test_keyfile_changes_derived_key() {
+        let keyfile = generate();
+        assert_ne!(*keyfile, *generate());
+        
+        let params = KdfParams::Pbkdf2 { iterations: 10 };
+        let master = key_derivation::derive_key_from_password(&combine("pw", keyfile.as_ref()).unwrap(), params).unwrap();
+        let same = key_derivation::verify_and_derive_key(&combine("pw", keyfile.as_ref()).unwrap(), &master.salt, params).unwrap();
+        let without = key_derivation::verify_and_derive_key("pw", &master.salt, params).unwrap();
+        let other = key_derivation::verify_and_derive_key(&combine("pw", b"autre").unwrap(), &master.salt, params).unwrap();
+        
+        assert_eq!(same.key, master.key);
+        assert_ne!(without.key, master.key);
+        assert_ne!(other.key, master.key);
+        assert_eq!(combine("pw", b""), Err(KeyFileError::Empty));
+        assert_eq!(combine("p\0w", b"cle"), Err(KeyFileError::NulInPassword));
+        assert_eq!(combine("pw", b"cle").unwrap().len(), "pw".len() + 1 + 2 * 32);
+    }
+}
\ No newline at end of file
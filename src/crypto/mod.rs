@@ -1,2 +1,6 @@
 pub mod cipher;
-pub mod key_derivation;
\ No newline at end of file
+pub mod key_derivation;
+pub mod keyfile;
+pub mod selftest;
+pub mod otp;
+pub mod signing;
\ No newline at end of file
@@ -0,0 +1,381 @@
+use data_encoding::BASE32_NOPAD;
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+use url::Url;
+use zeroize::Zeroize;
+
+use crate::utils::redact::Redactor;
+
+/// Nombre de chiffres par défaut d'un code (RFC 4226)
+const DEFAULT_DIGITS: u32 = 6;
+
+/// Durée de validité par défaut d'un code TOTP, en secondes (RFC 6238)
+const DEFAULT_PERIOD: u64 = 30;
+
+/// Longueur des codes Steam Guard
+const STEAM_DIGITS: u32 = 5;
+
+/// Alphabet des codes Steam Guard
+const STEAM_ALPHABET: &[u8] = b"23456789BCDFGHJKMNPQRTVWXY";
+
+/// Enumération des erreurs possibles liées aux codes à usage unique
+#[derive(Error, Debug, PartialEq)]
+pub enum OtpError {
+    #[error("URI otpauth invalide: {0}")]
+    InvalidUri(String),
+    
+    #[error("Type d'OTP non pris en charge: '{0}' (totp, hotp ou steam)")]
+    UnsupportedType(String),
+    
+    #[error("Algorithme non pris en charge: '{0}' (SHA1, SHA256 ou SHA512)")]
+    UnsupportedAlgorithm(String),
+    
+    #[error("Secret OTP invalide (base32 attendu)")]
+    InvalidSecret,
+    
+    #[error("Valeur invalide pour le paramètre '{0}': '{1}'")]
+    InvalidParameter(String, String),
+}
+
+/// Fonction de hachage utilisée par le HMAC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum OtpAlgorithm {
+    #[default]
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl fmt::Display for OtpAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OtpAlgorithm::Sha1 => write!(f, "SHA1"),
+            OtpAlgorithm::Sha256 => write!(f, "SHA256"),
+            OtpAlgorithm::Sha512 => write!(f, "SHA512"),
+        }
+    }
+}
+
+impl FromStr for OtpAlgorithm {
+    type Err = OtpError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().replace('-', "").as_str() {
+            "SHA1" => Ok(OtpAlgorithm::Sha1),
+            "SHA256" => Ok(OtpAlgorithm::Sha256),
+            "SHA512" => Ok(OtpAlgorithm::Sha512),
+            _ => Err(OtpError::UnsupportedAlgorithm(s.to_string())),
+        }
+    }
+}
+
+/// Type de code à usage unique
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OtpKind {
+    /// Code basé sur l'heure (RFC 6238)
+    Totp { period: u64 },
+    
+    /// Code basé sur un compteur, incrémenté à chaque code généré (RFC 4226)
+    Hotp { counter: u64 },
+    
+    /// Code Steam Guard : TOTP affiché sur 5 caractères alphanumériques
+    Steam { period: u64 },
+}
+
+impl fmt::Display for OtpKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OtpKind::Totp { period } => write!(f, "TOTP ({} s)", period),
+            OtpKind::Hotp { counter } => write!(f, "HOTP (compteur {})", counter),
+            OtpKind::Steam { .. } => write!(f, "Steam Guard"),
+        }
+    }
+}
+
+/// Paramètres d'un générateur de codes à usage unique (importés d'une URI otpauth://)
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct OtpConfig {
+    /// Type de code et son paramètre (période ou compteur)
+    pub kind: OtpKind,
+    
+    /// Secret partagé, encodé en base32 (majuscules, sans remplissage)
+    pub secret: String,
+    
+    /// Fonction de hachage
+    #[serde(default)]
+    pub algorithm: OtpAlgorithm,
+    
+    /// Nombre de chiffres des codes
+    pub digits: u32,
+    
+    /// Émetteur (nom du service)
+    #[serde(default)]
+    pub issuer: Option<String>,
+    
+    /// Nom du compte
+    #[serde(default)]
+    pub account: Option<String>,
+}
+
+impl OtpConfig {
+    /// Analyse une URI `otpauth://totp|hotp|steam/<émetteur>:<compte>?secret=...`.
+    ///
+    /// Les paramètres algorithm, digits, period et counter sont optionnels; `encoder=steam`
+    /// (convention de KeePassXC) désigne un code Steam Guard.
+    pub fn from_uri(uri: &str) -> Result<Self, OtpError> {
+        let url = Url::parse(uri.trim()).map_err(|e| OtpError::InvalidUri(e.to_string()))?;
+        if url.scheme() != "otpauth" {
+            return Err(OtpError::InvalidUri("le schéma doit être otpauth://".to_string()));
+        }
+        
+        let label = percent_decode(url.path().trim_start_matches('/'));
+        let (mut issuer, account) = match label.split_once(':') {
+            Some((issuer, account)) => (Some(issuer.trim().to_string()), account.trim().to_string()),
+            None => (None, label.trim().to_string()),
+        };
+        
+        let mut secret = None;
+        let mut algorithm = OtpAlgorithm::default();
+        let mut digits = None;
+        let mut period = DEFAULT_PERIOD;
+        let mut counter = 0;
+        let mut steam_encoder = false;
+        
+        for (key, value) in url.query_pairs() {
+            let invalid = || OtpError::InvalidParameter(key.to_string(), value.to_string());
+            match key.as_ref() {
+                "secret" => secret = Some(normalize_secret(&value)?),
+                "issuer" if !value.trim().is_empty() => issuer = Some(value.trim().to_string()),
+                "algorithm" => algorithm = value.parse()?,
+                "digits" => digits = Some(value.parse::<u32>().ok().filter(|digits| (4..=10).contains(digits)).ok_or_else(invalid)?),
+                "period" => period = value.parse::<u64>().ok().filter(|period| *period > 0).ok_or_else(invalid)?,
+                "counter" => counter = value.parse::<u64>().map_err(|_| invalid())?,
+                "encoder" => steam_encoder = value.eq_ignore_ascii_case("steam"),
+                _ => {}
+            }
+        }
+        
+        let kind = match url.host_str().unwrap_or_default().to_lowercase().as_str() {
+            "totp" if steam_encoder => OtpKind::Steam { period },
+            "totp" => OtpKind::Totp { period },
+            "hotp" => OtpKind::Hotp { counter },
+            "steam" => OtpKind::Steam { period },
+            other => return Err(OtpError::UnsupportedType(other.to_string())),
+        };
+        let digits = match kind {
+            OtpKind::Steam { .. } => STEAM_DIGITS,
+            _ => digits.unwrap_or(DEFAULT_DIGITS),
+        };
+        
+        Ok(Self {
+            kind,
+            secret: secret.ok_or(OtpError::InvalidSecret)?,
+            algorithm,
+            digits,
+            issuer,
+            account: Some(account).filter(|account| !account.is_empty()),
+        })
+    }
+    
+    /// Reconstruit l'URI otpauth:// (les codes Steam utilisent `encoder=steam`)
+    pub fn to_uri(&self) -> String {
+        let kind = match self.kind {
+            OtpKind::Hotp { .. } => "hotp",
+            _ => "totp",
+        };
+        let label = match (&self.issuer, &self.account) {
+            (Some(issuer), Some(account)) => format!("{}:{}", issuer, account),
+            (Some(name), None) | (None, Some(name)) => name.clone(),
+            (None, None) => String::new(),
+        };
+        
+        let mut url = Url::parse(&format!("otpauth://{}", kind)).expect("URI otpauth valide");
+        url.set_path(&format!("/{}", label));
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("secret", &self.secret);
+            if let Some(issuer) = &self.issuer {
+                query.append_pair("issuer", issuer);
+            }
+            query.append_pair("algorithm", &self.algorithm.to_string());
+            query.append_pair("digits", &self.digits.to_string());
+            match self.kind {
+                OtpKind::Totp { period } => { query.append_pair("period", &period.to_string()); }
+                OtpKind::Hotp { counter } => { query.append_pair("counter", &counter.to_string()); }
+                OtpKind::Steam { period } => { query.append_pair("period", &period.to_string()).append_pair("encoder", "steam"); }
+            }
+        }
+        url.to_string()
+    }
+    
+    /// Indique si les codes dépendent d'un compteur à conserver (HOTP)
+    pub fn is_counter_based(&self) -> bool {
+        matches!(self.kind, OtpKind::Hotp { .. })
+    }
+    
+    /// Code valide à l'instant donné (secondes depuis l'époque Unix); pour HOTP, code du compteur actuel
+    pub fn code_at(&self, unix_time: u64) -> Result<String, OtpError> {
+        let counter = match self.kind {
+            OtpKind::Totp { period } | OtpKind::Steam { period } => unix_time / period,
+            OtpKind::Hotp { counter } => counter,
+        };
+        self.code_for_counter(counter)
+    }
+    
+    /// Génère le prochain code; pour HOTP, le compteur est incrémenté et doit être enregistré
+    pub fn next_code(&mut self, unix_time: u64) -> Result<String, OtpError> {
+        let code = self.code_at(unix_time)?;
+        if let OtpKind::Hotp { counter } = &mut self.kind {
+            *counter += 1;
+        }
+        Ok(code)
+    }
+    
+    /// Nombre de secondes pendant lesquelles le code actuel reste valide (codes basés sur l'heure)
+    pub fn seconds_remaining(&self, unix_time: u64) -> Option<u64> {
+        match self.kind {
+            OtpKind::Totp { period } | OtpKind::Steam { period } => Some(period - unix_time % period),
+            OtpKind::Hotp { .. } => None,
+        }
+    }
+    
+    /// Calcule le code correspondant à une valeur du compteur (RFC 4226, section 5.3)
+    fn code_for_counter(&self, counter: u64) -> Result<String, OtpError> {
+        let mut key = BASE32_NOPAD.decode(self.secret.as_bytes()).map_err(|_| OtpError::InvalidSecret)?;
+        let message = counter.to_be_bytes();
+        let digest = match self.algorithm {
+            OtpAlgorithm::Sha1 => hmac::<Hmac<Sha1>>(&key, &message),
+            OtpAlgorithm::Sha256 => hmac::<Hmac<Sha256>>(&key, &message),
+            OtpAlgorithm::Sha512 => hmac::<Hmac<Sha512>>(&key, &message),
+        };
+        key.zeroize();
+        
+        // Troncature dynamique : 31 bits lus à la position indiquée par le dernier demi-octet
+        let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+        let mut value = u32::from_be_bytes([digest[offset], digest[offset + 1], digest[offset + 2], digest[offset + 3]]) & 0x7fff_ffff;
+        
+        if let OtpKind::Steam { .. } = self.kind {
+            let mut code = String::with_capacity(STEAM_DIGITS as usize);
+            for _ in 0..STEAM_DIGITS {
+                code.push(STEAM_ALPHABET[(value % STEAM_ALPHABET.len() as u32) as usize] as char);
+                value /= STEAM_ALPHABET.len() as u32;
+            }
+            return Ok(code);
+        }
+        
+        let modulus = 10u64.pow(self.digits);
+        Ok(format!("{:0width$}", u64::from(value) % modulus, width = self.digits as usize))
+    }
+}
+
+impl fmt::Display for OtpConfig {
+    /// Décrit le générateur sans révéler le secret
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}, {} caractères", self.kind, self.algorithm, self.digits)?;
+        if let Some(issuer) = &self.issuer {
+            write!(f, ", émetteur: {}", issuer)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for OtpConfig {
+    /// Format de débogage sans le secret partagé
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OtpConfig")
+            .field("kind", &self.kind)
+            .field("secret", &Redactor::Mask.redact(&self.secret))
+            .field("algorithm", &self.algorithm)
+            .field("digits", &self.digits)
+            .field("issuer", &self.issuer)
+            .field("account", &self.account)
+            .finish()
+    }
+}
+
+impl Drop for OtpConfig {
+    /// Efface le secret de la mémoire
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+/// Calcule un HMAC avec l'algorithme demandé
+fn hmac<M: Mac + KeyInit>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = <M as KeyInit>::new_from_slice(key).expect("HMAC accepte des clés de toute longueur");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Normalise un secret base32 (espaces, minuscules et remplissage tolérés) et vérifie qu'il est décodable
+fn normalize_secret(secret: &str) -> Result<String, OtpError> {
+    let secret: String = secret.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-' && *c != '=')
+        .collect::<String>()
+        .to_uppercase();
+    
+    match BASE32_NOPAD.decode(secret.as_bytes()) {
+        Ok(mut bytes) if !bytes.is_empty() => {
+            bytes.zeroize();
+            Ok(secret)
+        }
+        _ => Err(OtpError::InvalidSecret),
+    }
+}
+
+/// Décode les séquences %XX d'un libellé d'URI
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = text.get(i + 1..i + 3).and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+            continue;
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_rfc_vectors_and_uri_round_trip() {
+        // RFC 4226, annexe D : secret "12345678901234567890"
+        let mut hotp = OtpConfig::from_uri("otpauth://hotp/ACME:bob?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ").unwrap();
+        assert_eq!(hotp.next_code(0).unwrap(), "755224");
+        assert_eq!(hotp.next_code(0).unwrap(), "287082");
+        assert_eq!(hotp.kind, OtpKind::Hotp { counter: 2 });
+        assert_eq!(hotp.issuer.as_deref(), Some("ACME"));
+        
+        // RFC 6238, annexe B : T = 59, 8 chiffres
+        let sha256 = OtpConfig::from_uri("otpauth://totp/x?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZA&algorithm=SHA256&digits=8").unwrap();
+        assert_eq!(sha256.code_at(59).unwrap(), "46119246");
+        assert_eq!(sha256.seconds_remaining(59), Some(1));
+        assert_eq!(OtpConfig::from_uri(&sha256.to_uri()).unwrap(), sha256);
+        
+        let steam = OtpConfig::from_uri("otpauth://totp/Steam:bob?secret=gezd%20gnbv&encoder=steam").unwrap();
+        let code = steam.code_at(59).unwrap();
+        assert_eq!(code.len(), 5);
+        assert!(code.bytes().all(|c| STEAM_ALPHABET.contains(&c)));
+        assert_eq!(OtpConfig::from_uri(&steam.to_uri()).unwrap(), steam);
+        
+        assert_eq!(OtpConfig::from_uri("otpauth://totp/x?secret=!!"), Err(OtpError::InvalidSecret));
+        assert_eq!(OtpConfig::from_uri("otpauth://totp/x?secret=GEZA&algorithm=MD5"), Err(OtpError::UnsupportedAlgorithm("MD5".to_string())));
+    }
+}
\ No newline at end of file
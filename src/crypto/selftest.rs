@@ -0,0 +1,156 @@
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use base64::{Engine as _, engine::general_purpose};
+
+use super::{cipher, key_derivation::{self, KdfParams}};
+
+/// Résultat d'un test d'intégrité
+pub struct SelfTestResult {
+    /// Nom du test
+    pub name: &'static str,
+    
+    /// Résultat (message d'erreur en cas d'échec)
+    pub result: Result<(), String>,
+}
+
+/// Générateur « aléatoire » produisant toujours le même octet, pour rejouer les vecteurs de test
+struct FixedRng(u8);
+
+impl RngCore for FixedRng {
+    fn next_u32(&mut self) -> u32 {
+        u32::from_ne_bytes([self.0; 4])
+    }
+    
+    fn next_u64(&mut self) -> u64 {
+        u64::from_ne_bytes([self.0; 8])
+    }
+    
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        dest.fill(self.0);
+    }
+    
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+// Réservé aux vecteurs de test : ne doit jamais servir à chiffrer de vraies données
+impl CryptoRng for FixedRng {}
+
+/// Décode une chaîne hexadécimale (les espaces sont ignorés)
+fn hex(value: &str) -> Vec<u8> {
+    let digits: Vec<u8> = value.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    digits.chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap())
+        .collect()
+}
+
+/// Vérifie qu'une valeur calculée correspond à la valeur attendue
+fn check(name: &str, actual: &[u8], expected: &[u8]) -> Result<(), String> {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(format!("{}: résultat inattendu", name))
+    }
+}
+
+/// AES-256-GCM, vecteur « Test Case 14 » de la spécification GCM (McGrew & Viega)
+fn aes_gcm_known_answer() -> Result<(), String> {
+    let key = [0u8; 32];
+    let expected = hex("cea7403d4d606b6e074ec5d3baf39d18 d0d1c8a799996bf0265b98b5d48ab919");
+    
+    // Nonce nul fourni par un générateur fixe
+    let encrypted = cipher::encrypt_with_rng(&[0u8; 16], &key, &mut FixedRng(0)).map_err(|e| e.to_string())?;
+    let ciphertext = general_purpose::STANDARD.decode(&encrypted.ciphertext).map_err(|e| e.to_string())?;
+    check("chiffrement", &ciphertext, &expected)?;
+    
+    let plaintext = cipher::decrypt(&encrypted, &key).map_err(|e| e.to_string())?;
+    check("déchiffrement", &plaintext, &[0u8; 16])?;
+    
+    // Une étiquette d'authentification altérée doit être rejetée
+    let mut tampered = ciphertext;
+    tampered[31] ^= 1;
+    let tampered = cipher::EncryptedData {
+        nonce: encrypted.nonce,
+        ciphertext: general_purpose::STANDARD.encode(tampered),
+    };
+    match cipher::decrypt(&tampered, &key) {
+        Ok(_) => Err("données altérées acceptées".to_string()),
+        Err(_) => Ok(()),
+    }
+}
+
+/// PBKDF2-HMAC-SHA256, vecteurs de la RFC 7914 (section 11)
+fn pbkdf2_known_answer() -> Result<(), String> {
+    let vectors = [
+        ("passwd", "salt", 1, "55ac046e56e3089fec1691c22544b605 f94185216dde0465e68b9d57c20dacbc"),
+        ("Password", "NaCl", 80_000, "4ddcd8f60b98be21830cee5ef22701f9 641a4418d04c0414aeff08876b34ab56"),
+    ];
+    
+    for (password, salt, iterations, expected) in vectors {
+        let salt = general_purpose::STANDARD.encode(salt);
+        let master_key = key_derivation::verify_and_derive_key(password, &salt, KdfParams::Pbkdf2 { iterations }).map_err(|e| e.to_string())?;
+        check(&format!("{} itération(s)", iterations), &master_key.key, &hex(expected))?;
+    }
+    
+    Ok(())
+}
+
+/// Argon2id, vecteurs de l'implémentation de référence (phc-winner-argon2)
+fn argon2id_known_answer() -> Result<(), String> {
+    let vectors = [
+        (256, 2, 1, "9dfeb910e80bad0311fee20f9c0e2b12 c17987b4cac90c2ef54d5b3021c68bfe"),
+        (65_536, 2, 1, "09316115d5cf24ed5a15a31a3ba326e5 cf32edc24702987c02b6566f61913cf7"),
+    ];
+    
+    let salt = general_purpose::STANDARD.encode("somesalt");
+    for (memory_kib, iterations, parallelism, expected) in vectors {
+        let params = KdfParams::Argon2id { memory_kib, iterations, parallelism };
+        let master_key = key_derivation::verify_and_derive_key("password", &salt, params).map_err(|e| e.to_string())?;
+        check(&format!("{} Kio", memory_kib), &master_key.key, &hex(expected))?;
+    }
+    
+    Ok(())
+}
+
+/// scrypt, vecteurs de la RFC 7914 (section 12)
+fn scrypt_known_answer() -> Result<(), String> {
+    let vectors = [
+        ("password", "NaCl", 10, 8, 16, "fdbabe1c9d3472007856e7190d01e9fe 7c6ad7cbc8237830e77376634b373162"),
+        ("pleaseletmein", "SodiumChloride", 14, 8, 1, "7023bdcb3afd7348461c06cd81fd38eb fda8fbba904f8e3ea9b543f6545da1f2"),
+    ];
+    
+    for (password, salt, log_n, block_size, parallelism, expected) in vectors {
+        let salt = general_purpose::STANDARD.encode(salt);
+        let params = KdfParams::Scrypt { log_n, block_size, parallelism };
+        let master_key = key_derivation::verify_and_derive_key(password, &salt, params).map_err(|e| e.to_string())?;
+        check(&format!("N = 2^{}", log_n), &master_key.key, &hex(expected))?;
+    }
+    
+    Ok(())
+}
+
+/// Vérifie que le générateur aléatoire du système fournit des valeurs distinctes
+fn random_source() -> Result<(), String> {
+    let mut first = [0u8; 32];
+    let mut second = [0u8; 32];
+    OsRng.try_fill_bytes(&mut first).map_err(|e| e.to_string())?;
+    OsRng.try_fill_bytes(&mut second).map_err(|e| e.to_string())?;
+    
+    if first == second || first == [0u8; 32] {
+        return Err("valeurs aléatoires répétées".to_string());
+    }
+    Ok(())
+}
+
+/// Exécute les tests à réponse connue des primitives cryptographiques
+pub fn run() -> Vec<SelfTestResult> {
+    vec![
+        SelfTestResult { name: "Générateur aléatoire du système", result: random_source() },
+        SelfTestResult { name: "AES-256-GCM (vecteur GCM n°14)", result: aes_gcm_known_answer() },
+        SelfTestResult { name: "PBKDF2-HMAC-SHA256 (RFC 7914)", result: pbkdf2_known_answer() },
+        SelfTestResult { name: "Argon2id (implémentation de référence)", result: argon2id_known_answer() },
+        SelfTestResult { name: "scrypt (RFC 7914)", result: scrypt_known_answer() },
+    ]
+}
\ No newline at end of file
@@ -0,0 +1,114 @@
+use data_encoding::HEXLOWER;
+use ed25519_dalek::{SECRET_KEY_LENGTH, Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand::rngs::OsRng;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+/// Extension ajoutée au nom de la base pour sa signature détachée (coffre.krs.sig)
+pub const SIGNATURE_EXTENSION: &str = "sig";
+
+/// Algorithme indiqué en tête de la signature détachée
+const SIGNATURE_ALGORITHM: &str = "ed25519";
+
+/// Erreurs liées à la signature d'un fichier
+#[derive(Error, Debug, PartialEq)]
+pub enum SignatureError {
+    #[error("clé invalide: {0}")]
+    InvalidKey(String),
+    
+    #[error("signature détachée illisible")]
+    Malformed,
+    
+    #[error("signé par une clé non reconnue ({0})")]
+    UnknownSigner(String),
+    
+    #[error("la signature ne correspond pas au contenu du fichier")]
+    Mismatch,
+}
+
+/// Nouvelle clé de signature aléatoire
+pub fn generate() -> SigningKey {
+    SigningKey::generate(&mut OsRng)
+}
+
+/// Clé privée en hexadécimal
+pub fn encode_secret(key: &SigningKey) -> Zeroizing<String> {
+    Zeroizing::new(HEXLOWER.encode(key.as_bytes()))
+}
+
+/// Lit une clé privée en hexadécimal
+pub fn decode_secret(hex: &str) -> Result<SigningKey, SignatureError> {
+    let bytes = Zeroizing::new(HEXLOWER.decode(hex.trim().as_bytes()).map_err(|e| SignatureError::InvalidKey(e.to_string()))?);
+    let bytes: &[u8; SECRET_KEY_LENGTH] = bytes.as_slice().try_into()
+        .map_err(|_| SignatureError::InvalidKey(format!("{} octets attendus", SECRET_KEY_LENGTH)))?;
+    Ok(SigningKey::from_bytes(bytes))
+}
+
+/// Clé publique en hexadécimal
+pub fn encode_public(key: &VerifyingKey) -> String {
+    HEXLOWER.encode(key.as_bytes())
+}
+
+/// Lit une clé publique en hexadécimal
+pub fn decode_public(hex: &str) -> Result<VerifyingKey, SignatureError> {
+    let bytes = HEXLOWER.decode(hex.trim().to_lowercase().as_bytes()).map_err(|e| SignatureError::InvalidKey(e.to_string()))?;
+    let bytes = bytes.as_slice().try_into().map_err(|_| SignatureError::InvalidKey("32 octets attendus".to_string()))?;
+    VerifyingKey::from_bytes(bytes).map_err(|e| SignatureError::InvalidKey(e.to_string()))
+}
+
+/// Emplacement de la signature détachée d'un fichier
+pub fn signature_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(SIGNATURE_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Signature détachée d'un contenu : une ligne `ed25519 <clé publique> <signature>`
+pub fn sign(key: &SigningKey, content: &[u8]) -> String {
+    let signature = key.sign(content);
+    format!("{} {} {}\n", SIGNATURE_ALGORITHM, encode_public(&key.verifying_key()), HEXLOWER.encode(&signature.to_bytes()))
+}
+
+/// Vérifie la signature détachée d'un contenu : elle doit avoir été produite par l'une des clés
+/// de confiance, dont la clé retenue est retournée
+pub fn verify(trusted: &[VerifyingKey], content: &[u8], detached: &str) -> Result<VerifyingKey, SignatureError> {
+    let [algorithm, public, signature] = detached.split_whitespace().collect::<Vec<_>>()[..] else {
+        return Err(SignatureError::Malformed);
+    };
+    if algorithm != SIGNATURE_ALGORITHM {
+        return Err(SignatureError::Malformed);
+    }
+    
+    let signer = decode_public(public).map_err(|_| SignatureError::Malformed)?;
+    let signature = HEXLOWER.decode(signature.as_bytes()).ok()
+        .and_then(|bytes| Signature::from_slice(&bytes).ok())
+        .ok_or(SignatureError::Malformed)?;
+    let key = trusted.iter().find(|key| **key == signer).ok_or_else(|| SignatureError::UnknownSigner(encode_public(&signer)))?;
+    key.verify(content, &signature).map_err(|_| SignatureError::Mismatch)?;
+    Ok(*key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_sign_and_verify() {
+        let key = generate();
+        let trusted = [key.verifying_key()];
+        let detached = sign(&key, b"chiffre");
+        assert_eq!(verify(&trusted, b"chiffre", &detached), Ok(key.verifying_key()));
+        assert_eq!(verify(&trusted, b"remplace", &detached), Err(SignatureError::Mismatch));
+        assert_eq!(verify(&trusted, b"chiffre", "ed25519 00"), Err(SignatureError::Malformed));
+        
+        let intruder = generate();
+        assert!(matches!(verify(&trusted, b"remplace", &sign(&intruder, b"remplace")), Err(SignatureError::UnknownSigner(_))));
+        
+        let restored = decode_secret(&encode_secret(&key)).unwrap();
+        assert_eq!(restored.verifying_key(), key.verifying_key());
+        assert_eq!(decode_public(&encode_public(&key.verifying_key())), Ok(key.verifying_key()));
+        assert_eq!(signature_path(Path::new("/tmp/coffre.krs")), PathBuf::from("/tmp/coffre.krs.sig"));
+    }
+}
\ No newline at end of file
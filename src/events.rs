@@ -0,0 +1,401 @@
+use serde::Deserialize;
+use std::env;
+use std::fmt;
+use std::fs;
+#[cfg(feature = "webhooks")]
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+#[cfg(feature = "webhooks")]
+use std::process::Stdio;
+use std::sync::OnceLock;
+
+use crate::models::clock;
+
+/// Variable d'environnement désignant un fichier de hooks à utiliser à la place du fichier par défaut
+const HOOKS_ENV: &str = "KEEPRUST_HOOKS";
+
+/// Délai maximal d'envoi d'un webhook, en secondes
+#[cfg(feature = "webhooks")]
+const WEBHOOK_TIMEOUT_SECS: u32 = 5;
+
+/// Événement de sécurité pouvant déclencher des hooks
+#[derive(Debug, Clone, PartialEq)]
+pub enum SecurityEvent {
+    /// Échec de déverrouillage (mot de passe maître incorrect)
+    FailedUnlock,
+    
+    /// Le nombre de mots de passe faibles a augmenté depuis l'audit précédent
+    AuditRegression { previous: usize, current: usize },
+    
+    /// Des entrées sont expirées ou expirent bientôt
+    ExpiringEntries { expired: usize, expiring_soon: usize },
+    
+    /// Des mots de passe apparaissent dans des fuites de données connues (Have I Been Pwned)
+    BreachedPasswords { count: usize },
+    
+    /// Des modifications concurrentes d'un même fichier ont été détectées
+    SyncConflict { conflict_path: PathBuf },
+    
+    /// Un client à accès restreint (extension de navigateur, fournisseur Secret Service) a demandé
+    /// une entrée hors des groupes qui lui sont autorisés
+    AccessDenied { client: String, entry: String },
+}
+
+impl SecurityEvent {
+    /// Nom de l'événement, utilisé dans la configuration des hooks
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SecurityEvent::FailedUnlock => "failed-unlock",
+            SecurityEvent::AuditRegression { .. } => "audit-regression",
+            SecurityEvent::ExpiringEntries { .. } => "expiring-entries",
+            SecurityEvent::BreachedPasswords { .. } => "breached-passwords",
+            SecurityEvent::SyncConflict { .. } => "sync-conflict",
+            SecurityEvent::AccessDenied { .. } => "access-denied",
+        }
+    }
+}
+
+impl fmt::Display for SecurityEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecurityEvent::FailedUnlock => write!(f, "Échec de déverrouillage de la base de données"),
+            SecurityEvent::AuditRegression { previous, current } => write!(
+                f,
+                "Audit: {} mot(s) de passe faible(s) (contre {} lors de l'audit précédent)",
+                current, previous
+            ),
+            SecurityEvent::ExpiringEntries { expired, expiring_soon } => write!(
+                f,
+                "{} entrée(s) expirée(s), {} expirant bientôt",
+                expired, expiring_soon
+            ),
+            SecurityEvent::BreachedPasswords { count } => write!(
+                f,
+                "{} mot(s) de passe trouvé(s) dans des fuites de données connues",
+                count
+            ),
+            SecurityEvent::SyncConflict { conflict_path } => write!(
+                f,
+                "Conflit de synchronisation: copie conservée dans {}",
+                conflict_path.display()
+            ),
+            SecurityEvent::AccessDenied { client, entry } => write!(
+                f,
+                "Accès refusé à '{}': entrée {} hors des groupes autorisés",
+                client, entry
+            ),
+        }
+    }
+}
+
+/// Action déclenchée par un ou plusieurs événements
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Hook {
+    /// Événements concernés (tous si la liste est vide)
+    #[serde(default)]
+    pub events: Vec<String>,
+    
+    /// Commande shell à exécuter (reçoit KEEPRUST_EVENT, KEEPRUST_MESSAGE et KEEPRUST_VAULT)
+    #[serde(default)]
+    pub command: Option<String>,
+    
+    /// Affiche une notification de bureau
+    #[serde(default)]
+    pub notify: bool,
+    
+    /// URL à laquelle envoyer l'événement en JSON (POST)
+    #[serde(default)]
+    pub webhook: Option<String>,
+}
+
+impl Hook {
+    /// Indique si le hook doit être déclenché pour cet événement
+    pub fn matches(&self, event: &SecurityEvent) -> bool {
+        self.events.is_empty() || self.events.iter().any(|kind| kind == event.kind())
+    }
+}
+
+/// Étape de l'enregistrement à laquelle un script est exécuté
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SaveStage {
+    /// Avant l'écriture du fichier : un échec annule l'enregistrement
+    Pre,
+    
+    /// Après l'écriture du fichier : un échec est seulement signalé
+    Post,
+}
+
+/// Scripts exécutés autour de l'enregistrement d'une base de données
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SaveHook {
+    /// Base de données concernée (toutes si non spécifiée)
+    #[serde(default)]
+    pub vault: Option<PathBuf>,
+    
+    /// Profil de profiles.toml concerné (tous si non spécifié) : deux profils désignant la même
+    /// base peuvent avoir des scripts différents
+    #[serde(default)]
+    pub profile: Option<String>,
+    
+    /// Commande shell exécutée avant l'enregistrement
+    #[serde(default)]
+    pub pre: Option<String>,
+    
+    /// Commande shell exécutée après l'enregistrement
+    #[serde(default)]
+    pub post: Option<String>,
+}
+
+impl SaveHook {
+    /// Indique si le script s'applique à cette base de données, ouverte avec ce profil
+    pub fn applies_to(&self, vault: &Path, profile: Option<&str>) -> bool {
+        if self.profile.is_some() && self.profile.as_deref() != profile {
+            return false;
+        }
+        match &self.vault {
+            None => true,
+            Some(configured) => match (configured.canonicalize(), vault.canonicalize()) {
+                (Ok(configured), Ok(vault)) => configured == vault,
+                _ => configured == vault,
+            },
+        }
+    }
+}
+
+/// Contenu du fichier de configuration des hooks
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HooksConfig {
+    #[serde(default, rename = "hook")]
+    hooks: Vec<Hook>,
+    
+    #[serde(default, rename = "save_hook")]
+    save_hooks: Vec<SaveHook>,
+}
+
+static HOOKS: OnceLock<HooksConfig> = OnceLock::new();
+
+/// Profil avec lequel la base a été ouverte (scripts d'enregistrement)
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Emplacement du fichier de hooks : $KEEPRUST_HOOKS, sinon `keeprust/hooks.toml` dans le
+/// répertoire de configuration de l'utilisateur
+pub fn hooks_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(HOOKS_ENV) {
+        return Some(PathBuf::from(path));
+    }
+    
+    Some(config_dir()?.join("hooks.toml"))
+}
+
+/// Répertoire de configuration de keeprust (`keeprust` dans le répertoire de configuration de l'utilisateur)
+pub fn config_dir() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME").map(PathBuf::from)
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("keeprust"))
+}
+
+/// Hooks configurés (lus une seule fois; un fichier invalide est signalé puis ignoré)
+fn config() -> &'static HooksConfig {
+    HOOKS.get_or_init(|| {
+        let Some(path) = hooks_path() else {
+            return HooksConfig::default();
+        };
+        let Ok(content) = fs::read_to_string(&path) else {
+            return HooksConfig::default();
+        };
+        
+        toml::from_str(&content).unwrap_or_else(|e| {
+            eprintln!("Avertissement: fichier de hooks {} ignoré: {}", path.display(), e);
+            HooksConfig::default()
+        })
+    })
+}
+
+/// Indique le profil de profiles.toml avec lequel la base est ouverte, pour choisir ses scripts
+/// d'enregistrement (à appeler une seule fois, au lancement)
+pub fn set_profile(name: &str) {
+    let _ = PROFILE.set(name.to_string());
+}
+
+/// Déclenche les hooks concernés par un événement.
+///
+/// Les hooks ne font jamais échouer l'opération en cours : leurs erreurs sont seulement signalées.
+pub fn emit(vault: &Path, event: &SecurityEvent) {
+    for hook in config().hooks.iter().filter(|hook| hook.matches(event)) {
+        if let Err(e) = run_hook(hook, vault, event) {
+            eprintln!("Avertissement: hook '{}' en échec: {}", event.kind(), e);
+        }
+    }
+}
+
+/// Exécute les scripts d'enregistrement de la base de données pour une étape.
+///
+/// Avant l'enregistrement, le premier échec est retourné pour annuler l'écriture;
+/// après, les échecs sont seulement signalés.
+pub fn run_save_hooks(vault: &Path, stage: SaveStage) -> Result<(), String> {
+    let profile = PROFILE.get().map(String::as_str);
+    let hooks = config().save_hooks.iter().filter(|hook| hook.applies_to(vault, profile));
+    
+    for hook in hooks {
+        let command = match stage {
+            SaveStage::Pre => &hook.pre,
+            SaveStage::Post => &hook.post,
+        };
+        let Some(command) = command else {
+            continue;
+        };
+        
+        let stage_name = match stage {
+            SaveStage::Pre => "pre-save",
+            SaveStage::Post => "post-save",
+        };
+        match run_shell(command, &[("KEEPRUST_EVENT", stage_name), ("KEEPRUST_PROFILE", profile.unwrap_or_default())], vault) {
+            Ok(()) => {}
+            Err(e) if stage == SaveStage::Pre => {
+                return Err(format!("Enregistrement annulé par le script pre-save: {}", e));
+            }
+            Err(e) => eprintln!("Avertissement: script post-save en échec: {}", e),
+        }
+    }
+    Ok(())
+}
+
+/// Exécute une commande avec le shell du système
+fn run_shell(command: &str, vars: &[(&str, &str)], vault: &Path) -> Result<(), String> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    
+    let status = shell.arg(command)
+        .envs(vars.iter().copied())
+        .env("KEEPRUST_VAULT", vault)
+        .status()
+        .map_err(|e| e.to_string())?;
+    if !status.success() {
+        return Err(format!("la commande '{}' s'est terminée avec une erreur", command));
+    }
+    Ok(())
+}
+
+/// Exécute les actions d'un hook
+fn run_hook(hook: &Hook, vault: &Path, event: &SecurityEvent) -> Result<(), String> {
+    let message = event.to_string();
+    
+    if let Some(command) = &hook.command {
+        run_shell(command, &[("KEEPRUST_EVENT", event.kind()), ("KEEPRUST_MESSAGE", &message)], vault)?;
+    }
+    
+    if hook.notify {
+        notify(&message)?;
+    }
+    
+    if let Some(url) = &hook.webhook {
+        let payload = serde_json::json!({
+            "event": event.kind(),
+            "message": message,
+            "vault": vault.display().to_string(),
+            "timestamp": clock::now().to_rfc3339(),
+        });
+        post_webhook(url, &payload.to_string())?;
+    }
+    
+    Ok(())
+}
+
+/// Affiche une notification de bureau avec l'outil natif du système
+fn notify(message: &str) -> Result<(), String> {
+    let mut command = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification \"{}\" with title \"KeepRust\"",
+            message.replace('\\', "\\\\").replace('"', "\\\"")
+        );
+        let mut command = Command::new("osascript");
+        command.arg("-e").arg(script);
+        command
+    } else if cfg!(windows) {
+        return Err("notifications de bureau non disponibles sur cette plateforme".to_string());
+    } else {
+        let mut command = Command::new("notify-send");
+        command.arg("KeepRust").arg(message);
+        command
+    };
+    
+    command.status()
+        .map_err(|e| format!("impossible d'afficher la notification: {}", e))
+        .map(|_| ())
+}
+
+/// Envoie l'événement en JSON (délégué à curl pour ne pas embarquer de client HTTP)
+#[cfg(feature = "webhooks")]
+fn post_webhook(url: &str, payload: &str) -> Result<(), String> {
+    let mut child = Command::new("curl")
+        .args(["-fsS", "-X", "POST", "-H", "Content-Type: application/json", "--data-binary", "@-"])
+        .arg("-m").arg(WEBHOOK_TIMEOUT_SECS.to_string())
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("impossible de lancer curl: {}", e))?;
+    
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(payload.as_bytes()).map_err(|e| e.to_string())?;
+    }
+    
+    let status = child.wait().map_err(|e| e.to_string())?;
+    if !status.success() {
+        return Err(format!("échec de l'envoi vers {}", url));
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "webhooks"))]
+fn post_webhook(_url: &str, _payload: &str) -> Result<(), String> {
+    Err("cette version de keeprust a été compilée sans l'envoi de webhooks (fonctionnalité 'webhooks')".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_hooks_config_and_event_filter() {
+        let config: HooksConfig = toml::from_str(r#"
+            [[hook]]
+            events = ["failed-unlock"]
+            command = "logger keeprust"
+            
+            [[hook]]
+            notify = true
+            
+            [[save_hook]]
+            pre = "git pull"
+            post = "git commit -am sauvegarde"
+            
+            [[save_hook]]
+            profile = "travail"
+            post = "rsync keeprust.krs serveur:"
+        "#).unwrap();
+        
+        let regression = SecurityEvent::AuditRegression { previous: 1, current: 3 };
+        assert!(config.hooks[0].matches(&SecurityEvent::FailedUnlock));
+        assert!(!config.hooks[0].matches(&regression));
+        assert!(config.hooks[1].matches(&regression));
+        assert!(config.save_hooks[0].applies_to(Path::new("keeprust.krs"), None));
+        assert!(config.save_hooks[1].applies_to(Path::new("keeprust.krs"), Some("travail")));
+        assert!(!config.save_hooks[1].applies_to(Path::new("keeprust.krs"), Some("perso")));
+        assert!(!config.save_hooks[1].applies_to(Path::new("keeprust.krs"), None));
+        
+        assert!(toml::from_str::<HooksConfig>("[[hook]]\nshell = \"x\"").is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,371 @@
+//! Interface C (ABI stable) pour les applications mobiles (Android/iOS).
+//!
+//! Les wrappers natifs réutilisent ainsi exactement le même code de format et de chiffrement.
+//! Les résultats de liste/recherche sont retournés en JSON. Toute chaîne retournée doit être
+//! libérée avec `kr_string_free`; en cas d'échec, `kr_last_error` décrit la dernière erreur.
+//! L'en-tête C correspondant se trouve dans `include/keeprust.h`; les bibliothèques dynamique et
+//! statique sont compilées par le paquet `keeprust-ffi` (`cargo build -p keeprust-ffi`).
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::path::PathBuf;
+use std::ptr;
+use serde::Serialize;
+use zeroize::Zeroize;
+
+use crate::crypto::key_derivation::KdfParams;
+use crate::models::database::Database;
+use crate::models::entry::{ColorLabel, Entry};
+use crate::models::id::EntryId;
+use crate::models::query::Query;
+use crate::models::repository::{FileKey, Repository, Timings};
+use crate::models::saving::{self, LoadedIndex};
+use crate::models::signing;
+use crate::models::walker::WalkControl;
+
+thread_local! {
+    /// Dernière erreur survenue sur le thread appelant
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Base de données ouverte, manipulée par les applications via un pointeur opaque
+pub struct KrVault {
+    /// Chemin du fichier de la base de données
+    path: PathBuf,
+    
+    /// Mot de passe maître (effacé de la mémoire à la libération)
+    password: String,
+    
+    /// Base de données déchiffrée
+    db: Database,
+    
+    /// Paramètres de dérivation de clé du fichier (mis à jour à chaque enregistrement)
+    kdf: KdfParams,
+    
+    /// Clé dérivée du fichier actuel (déchiffre le journal des expositions)
+    key: FileKey,
+    
+    /// Index de recherche chiffré et sa clé, lus au premier enregistrement si la base en a un
+    index: Option<LoadedIndex>,
+}
+
+impl Drop for KrVault {
+    /// Efface le mot de passe maître de la mémoire
+    fn drop(&mut self) {
+        self.password.zeroize();
+    }
+}
+
+/// Entrée telle qu'exposée aux applications (sans mot de passe)
+#[derive(Serialize)]
+struct FfiEntry<'a> {
+    id: String,
+    title: &'a str,
+    username: &'a str,
+    url: &'a str,
+    tags: &'a [String],
+    group: String,
+    archived: bool,
+    color: Option<ColorLabel>,
+}
+
+impl<'a> FfiEntry<'a> {
+    fn new(path: &[String], entry: &'a Entry) -> Self {
+        Self {
+            id: entry.id.to_string(),
+            title: &entry.title,
+            username: &entry.username,
+            url: &entry.url,
+            tags: &entry.tags,
+            group: path.join("/"),
+            archived: entry.archived,
+            color: entry.color,
+        }
+    }
+}
+
+/// Enregistre la base exactement comme la ligne de commande (scripts d'enregistrement, journal
+/// des expositions, signature, index de recherche); les avertissements sont ignorés
+fn save(vault: &mut KrVault) -> Result<(), String> {
+    let repo = Repository::new(&vault.path);
+    let saved = saving::save(&repo, &mut vault.db, Some(&vault.password), vault.kdf, &vault.key, &mut vault.index, &mut Timings::disabled())?;
+    vault.kdf = saved.kdf;
+    vault.key = saved.key;
+    Ok(())
+}
+
+/// Enregistre le message d'erreur pour `kr_last_error`
+fn set_last_error(message: impl ToString) {
+    let message = CString::new(message.to_string().replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Convertit une chaîne C en `&str`
+///
+/// # Safety
+/// `value` doit être nul ou pointer vers une chaîne C valide terminée par un zéro.
+unsafe fn to_str<'a>(value: *const c_char, name: &str) -> Result<&'a str, String> {
+    if value.is_null() {
+        return Err(format!("Paramètre '{}' nul", name));
+    }
+    
+    // SAFETY: le pointeur est non nul et valide selon le contrat de l'appelant
+    unsafe { CStr::from_ptr(value) }
+        .to_str()
+        .map_err(|_| format!("Paramètre '{}' invalide (UTF-8 attendu)", name))
+}
+
+/// Transfère une chaîne à l'appelant, ou retourne un pointeur nul en enregistrant l'erreur
+fn into_c_string(result: Result<String, String>) -> *mut c_char {
+    match result.and_then(|value| CString::new(value).map_err(|e| e.to_string())) {
+        Ok(value) => value.into_raw(),
+        Err(e) => {
+            set_last_error(e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Sérialise une liste d'entrées en JSON
+fn entries_to_json(entries: &[FfiEntry]) -> Result<String, String> {
+    serde_json::to_string(entries).map_err(|e| e.to_string())
+}
+
+/// Ouvre une base de données; retourne un pointeur nul en cas d'erreur
+///
+/// # Safety
+/// `path` et `password` doivent être des chaînes C valides terminées par un zéro.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn kr_vault_open(path: *const c_char, password: *const c_char) -> *mut KrVault {
+    let open = || -> Result<KrVault, String> {
+        // SAFETY: contrat de l'appelant
+        let path = PathBuf::from(unsafe { to_str(path, "path") }?);
+        // SAFETY: contrat de l'appelant
+        let password = unsafe { to_str(password, "password") }?.to_string();
+        
+        signing::verify_vault(&path)?;
+        let repo = Repository::new(&path);
+        let (db, key) = repo.load_keyed(&password, None, &mut Timings::disabled()).map_err(|e| e.to_string())?;
+        let kdf = repo.read_kdf_params().map_err(|e| e.to_string())?;
+        Ok(KrVault { path, password, db, kdf, key, index: None })
+    };
+    
+    match open() {
+        Ok(vault) => Box::into_raw(Box::new(vault)),
+        Err(e) => {
+            set_last_error(e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Sauvegarde la base de données dans son fichier comme la ligne de commande (scripts
+/// d'enregistrement, signature, index de recherche); retourne 0 en cas de succès, -1 sinon
+///
+/// # Safety
+/// `vault` doit provenir de `kr_vault_open` et ne pas avoir été libéré.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn kr_vault_save(vault: *mut KrVault) -> i32 {
+    // SAFETY: contrat de l'appelant
+    let Some(vault) = (unsafe { vault.as_mut() }) else {
+        set_last_error("Paramètre 'vault' nul");
+        return -1;
+    };
+    
+    match save(vault) {
+        Ok(()) => 0,
+        Err(e) => {
+            set_last_error(e);
+            -1
+        }
+    }
+}
+
+/// Libère une base de données ouverte (sans effet sur un pointeur nul)
+///
+/// # Safety
+/// `vault` doit provenir de `kr_vault_open` et ne pas avoir déjà été libéré.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn kr_vault_free(vault: *mut KrVault) {
+    if !vault.is_null() {
+        // SAFETY: le pointeur provient de Box::into_raw dans kr_vault_open
+        drop(unsafe { Box::from_raw(vault) });
+    }
+}
+
+/// Liste toutes les entrées hors corbeille au format JSON (tableau d'objets sans mot de passe)
+///
+/// # Safety
+/// `vault` doit provenir de `kr_vault_open` et ne pas avoir été libéré.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn kr_vault_list(vault: *const KrVault) -> *mut c_char {
+    // SAFETY: contrat de l'appelant
+    let Some(vault) = (unsafe { vault.as_ref() }) else {
+        set_last_error("Paramètre 'vault' nul");
+        return ptr::null_mut();
+    };
+    
+    let trash = vault.db.metadata.trash_group_id;
+    let mut entries = Vec::new();
+    vault.db.walk(|path, group, entry| {
+        if Some(group.id) == trash {
+            return WalkControl::SkipChildren;
+        }
+        if let Some(entry) = entry {
+            entries.push(FfiEntry::new(path, entry));
+        }
+        WalkControl::Continue
+    });
+    
+    into_c_string(entries_to_json(&entries))
+}
+
+/// Recherche les entrées hors corbeille correspondant à une requête (même syntaxe que
+/// `list --query`), au format JSON
+///
+/// # Safety
+/// `vault` doit provenir de `kr_vault_open`; `query` doit être une chaîne C valide.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn kr_vault_search(vault: *const KrVault, query: *const c_char) -> *mut c_char {
+    // SAFETY: contrat de l'appelant
+    let Some(vault) = (unsafe { vault.as_ref() }) else {
+        set_last_error("Paramètre 'vault' nul");
+        return ptr::null_mut();
+    };
+    
+    // SAFETY: contrat de l'appelant
+    let result = unsafe { to_str(query, "query") }
+        .and_then(|query| Query::parse(query).map_err(|e| e.to_string()))
+        .and_then(|query| {
+            let entries: Vec<FfiEntry> = vault.db.find_entries(&query)
+                .iter()
+                .filter(|found| !vault.db.is_in_trash(&found.entry.id))
+                .map(|found| FfiEntry::new(&found.path, found.entry))
+                .collect();
+            entries_to_json(&entries)
+        });
+    
+    into_c_string(result)
+}
+
+/// Retourne le mot de passe d'une entrée hors corbeille (pour la copie côté application)
+///
+/// # Safety
+/// `vault` doit provenir de `kr_vault_open`; `entry_id` doit être une chaîne C valide.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn kr_vault_get_password(vault: *const KrVault, entry_id: *const c_char) -> *mut c_char {
+    // SAFETY: contrat de l'appelant
+    let Some(vault) = (unsafe { vault.as_ref() }) else {
+        set_last_error("Paramètre 'vault' nul");
+        return ptr::null_mut();
+    };
+    
+    // SAFETY: contrat de l'appelant
+    let result = unsafe { to_str(entry_id, "entry_id") }
+        .and_then(|id| id.parse::<EntryId>().map_err(|e| e.to_string()))
+        .and_then(|id| {
+            vault.db.find_live_entry(&id, false)
+                .map(|entry| entry.password.clone())
+                .map_err(|e| e.to_string())
+        });
+    
+    into_c_string(result)
+}
+
+/// Retourne le message de la dernière erreur du thread courant, ou un pointeur nul
+#[unsafe(no_mangle)]
+pub extern "C" fn kr_last_error() -> *mut c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null_mut(), |message| message.clone().into_raw())
+    })
+}
+
+/// Libère (et efface) une chaîne retournée par la bibliothèque
+///
+/// # Safety
+/// `value` doit provenir d'une fonction `kr_*` et ne pas avoir déjà été libérée.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn kr_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        // SAFETY: le pointeur provient de CString::into_raw
+        let mut bytes = unsafe { CString::from_raw(value) }.into_bytes();
+        bytes.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use zeroize::Zeroizing;
+    use crate::models::search_index::{self, IndexKey, SearchIndex};
+    
+    /// Chaîne retournée par la bibliothèque, libérée après lecture
+    fn take(value: *mut c_char) -> Option<String> {
+        if value.is_null() {
+            return None;
+        }
+        // SAFETY: chaîne retournée par une fonction kr_*
+        let text = unsafe { CStr::from_ptr(value) }.to_str().unwrap().to_string();
+        // SAFETY: libérée une seule fois
+        unsafe { kr_string_free(value) };
+        Some(text)
+    }
+    
+    #[test]
+    fn test_trashed_entries_are_not_exposed() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let kept = db.add_entry(None, Entry::new("Gmail".to_string(), "bob".to_string(), "kept-pw".to_string(), String::new(), String::new())).unwrap();
+        let trashed = db.add_entry(None, Entry::new("GitHub".to_string(), "bob".to_string(), "trashed-pw".to_string(), String::new(), String::new())).unwrap();
+        db.trash_entry(&trashed).unwrap();
+        let key = FileKey { salt: String::new(), key: Zeroizing::new([0; 32]) };
+        let vault = KrVault { path: PathBuf::from("inutilise.krs"), password: "pw".to_string(), db, kdf: KdfParams::recommended(), key, index: None };
+        
+        let query = CString::new("username:bob").unwrap();
+        // SAFETY: base et requête valides
+        let found = take(unsafe { kr_vault_search(&vault, query.as_ptr()) }).unwrap();
+        assert!(found.contains(&kept.to_string()));
+        assert!(!found.contains(&trashed.to_string()));
+        
+        let id = CString::new(kept.to_string()).unwrap();
+        // SAFETY: base et ID valides
+        assert_eq!(take(unsafe { kr_vault_get_password(&vault, id.as_ptr()) }).as_deref(), Some("kept-pw"));
+        let id = CString::new(trashed.to_string()).unwrap();
+        // SAFETY: base et ID valides
+        assert_eq!(take(unsafe { kr_vault_get_password(&vault, id.as_ptr()) }), None);
+        assert!(take(kr_last_error()).unwrap().contains("corbeille"));
+    }
+    
+    #[test]
+    fn test_save_updates_the_search_index() {
+        let directory = std::env::temp_dir().join(format!("keeprust-ffi-{}", crate::models::id::GroupId::new().short()));
+        fs::create_dir_all(&directory).unwrap();
+        let repo = Repository::new(directory.join("coffre.krs"));
+        let cheap = KdfParams::Argon2id { memory_kib: 8, iterations: 1, parallelism: 1 };
+        let mut db = Database::new("Test".to_string(), String::new());
+        db.add_entry(None, Entry::new("Gmail".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new())).unwrap();
+        repo.save_with_kdf(&db, "pw", cheap, &mut Timings::disabled()).unwrap();
+        let mut index = Some((SearchIndex::default(), IndexKey::new("pw", cheap).unwrap()));
+        saving::refresh_index(repo.path(), &db, Some("pw"), cheap, &mut index, true).unwrap();
+        
+        let path = CString::new(repo.path().to_str().unwrap()).unwrap();
+        let password = CString::new("pw").unwrap();
+        // SAFETY: chemin et mot de passe valides
+        let vault = unsafe { kr_vault_open(path.as_ptr(), password.as_ptr()) };
+        assert!(!vault.is_null());
+        // SAFETY: base ouverte ci-dessus
+        let added = unsafe { &mut *vault }.db.add_entry(None, Entry::new("Forum".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new())).unwrap();
+        // SAFETY: base ouverte ci-dessus
+        assert_eq!(unsafe { kr_vault_save(vault) }, 0);
+        // SAFETY: libérée une seule fois
+        unsafe { kr_vault_free(vault) };
+        
+        let (index, _) = SearchIndex::read(&search_index::index_path(repo.path()), "pw").unwrap();
+        assert_eq!(index.vault_digest, search_index::vault_digest(repo.path()).unwrap());
+        assert!(index.entries.iter().any(|entry| entry.id == added));
+        
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+//! keeprust : gestionnaire de mots de passe chiffrés (similaire à KeePass).
+//!
+//! La bibliothèque expose le modèle de données, le format de fichier et les outils de génération
+//! et d'analyse de mots de passe, pour intégrer keeprust dans d'autres programmes; l'exécutable
+//! `keeprust` n'en est qu'une interface en ligne de commande.
+//!
+//! Les types principaux sont réexportés à la racine :
+//!
+//! ```no_run
+//! use keeprust::{Database, Entry, PasswordGenerator, Repository};
+//!
+//! let repo = Repository::new("coffre.krs");
+//! let mut db = repo.load("mot de passe maître").unwrap_or_else(|_| Database::new("Coffre".into(), String::new()));
+//!
+//! let password = PasswordGenerator::new().length(24).generate().unwrap();
+//! let entry = Entry::builder().title("Gmail").username("bob@example.org").password(password).build().unwrap();
+//! db.add_entry(None, entry).unwrap();
+//! repo.save(&db, "mot de passe maître").unwrap();
+//! ```
+//!
+//! Pour wasm32-unknown-unknown, seuls `crypto`, `models`, `events` et `utils` sont compilés (sans
+//! fonctionnalité optionnelle) : `cargo check -p keeprust --lib --target wasm32-unknown-unknown --no-default-features`.
+
+#[cfg(not(target_arch = "wasm32"))]
+#[doc(hidden)]
+pub mod cli;
+pub mod crypto;
+pub mod events;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod models;
+pub mod utils;
+
+pub use models::database::{Database, DatabaseError};
+pub use models::entry::{Entry, EntryError};
+pub use models::group::Group;
+pub use models::id::{EntryId, GroupId};
+pub use models::repository::{Repository, RepositoryError};
+pub use utils::password_analyzer::{AnalyzerConfig, PasswordAnalysis, PasswordAnalyzer, PasswordStrength};
+pub use utils::password_generator::PasswordGenerator;
@@ -1,16 +1,15 @@
-mod cli;
-mod crypto;
-mod models;
-mod utils;
-
-use crate::cli::commands;
-use utils::password_generator::PasswordGenerator;
+use keeprust::PasswordGenerator;
+use keeprust::cli::{commands, output};
 
 fn main() {
     // Si des arguments sont passés, exécuter l'interface CLI
     if std::env::args().len() > 1 {
         match commands::run() {
             Ok(_) => (),
+            Err(e) if output::is_json() => {
+                output::print_error(&e);
+                std::process::exit(1);
+            }
             Err(e) => {
                 eprintln!("Erreur: {}", e);
                 std::process::exit(1);
@@ -0,0 +1,157 @@
+use base64::{Engine as _, engine::general_purpose};
+use chrono::{DateTime, Utc};
+use miniz_oxide::deflate::compress_to_vec;
+use miniz_oxide::inflate::decompress_to_vec_with_limit;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use thiserror::Error;
+use zeroize::{Zeroize, Zeroizing};
+
+use super::clock;
+
+/// Taille maximale d'une pièce jointe, avant compression (10 Mio)
+pub const MAX_ATTACHMENT_SIZE: usize = 10 * 1024 * 1024;
+
+/// Taille maximale des pièces jointes d'une entrée, une fois compressées (25 Mio)
+pub const MAX_ENTRY_ATTACHMENTS_SIZE: usize = 25 * 1024 * 1024;
+
+/// Niveau de compression deflate (0 à 10)
+const COMPRESSION_LEVEL: u8 = 6;
+
+/// Enumération des erreurs liées aux pièces jointes
+#[derive(Error, Debug, PartialEq)]
+pub enum AttachmentError {
+    #[error("Nom de pièce jointe invalide: '{0}'")]
+    InvalidName(String),
+    
+    #[error("La pièce jointe '{name}' est trop volumineuse ({size} octets, maximum {max})")]
+    TooLarge { name: String, size: usize, max: usize },
+    
+    #[error("Les pièces jointes de l'entrée dépasseraient {max} octets une fois compressées ({size} octets)")]
+    EntryFull { size: usize, max: usize },
+    
+    #[error("La pièce jointe '{0}' est corrompue")]
+    Corrupted(String),
+}
+
+/// Fichier joint à une entrée, compressé (deflate) dans la charge utile chiffrée de la base
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Attachment {
+    /// Nom du fichier, sans répertoire
+    pub name: String,
+    
+    /// Taille d'origine en octets
+    pub size: usize,
+    
+    /// Date d'ajout
+    pub added_at: DateTime<Utc>,
+    
+    /// Contenu compressé (en base64 dans la charge utile)
+    #[serde(serialize_with = "encode_data", deserialize_with = "decode_data")]
+    data: Vec<u8>,
+}
+
+impl Attachment {
+    /// Compresse un contenu à joindre sous le nom donné, après vérification du nom et de la taille
+    pub fn new(name: &str, content: &[u8]) -> Result<Self, AttachmentError> {
+        let name = name.trim();
+        if !is_valid_name(name) {
+            return Err(AttachmentError::InvalidName(name.to_string()));
+        }
+        if content.len() > MAX_ATTACHMENT_SIZE {
+            return Err(AttachmentError::TooLarge { name: name.to_string(), size: content.len(), max: MAX_ATTACHMENT_SIZE });
+        }
+        
+        Ok(Self {
+            name: name.to_string(),
+            size: content.len(),
+            added_at: clock::now(),
+            data: compress_to_vec(content, COMPRESSION_LEVEL),
+        })
+    }
+    
+    /// Taille occupée dans la base (contenu compressé)
+    pub fn stored_size(&self) -> usize {
+        self.data.len()
+    }
+    
+    /// Contenu décompressé, effacé de la mémoire après usage
+    pub fn content(&self) -> Result<Zeroizing<Vec<u8>>, AttachmentError> {
+        let content = decompress_to_vec_with_limit(&self.data, MAX_ATTACHMENT_SIZE)
+            .map_err(|_| AttachmentError::Corrupted(self.name.clone()))?;
+        if content.len() != self.size {
+            return Err(AttachmentError::Corrupted(self.name.clone()));
+        }
+        Ok(Zeroizing::new(content))
+    }
+}
+
+/// Un nom de pièce jointe est un nom de fichier seul : extrait tel quel, il ne doit pas pouvoir
+/// désigner un autre répertoire
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\']) && !name.chars().any(char::is_control)
+}
+
+/// Compare deux noms de pièce jointe sans tenir compte de la casse
+pub(super) fn same_attachment_name(a: &str, b: &str) -> bool {
+    a.trim().to_lowercase() == b.trim().to_lowercase()
+}
+
+fn encode_data<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&general_purpose::STANDARD.encode(data))
+}
+
+fn decode_data<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let encoded = Zeroizing::new(String::deserialize(deserializer)?);
+    general_purpose::STANDARD.decode(encoded.as_bytes()).map_err(serde::de::Error::custom)
+}
+
+impl Drop for Attachment {
+    /// Efface le contenu de la mémoire lorsque la pièce jointe est détruite
+    fn drop(&mut self) {
+        self.data.zeroize();
+    }
+}
+
+impl fmt::Debug for Attachment {
+    /// Format de débogage sans le contenu
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Attachment")
+            .field("name", &self.name)
+            .field("size", &self.size)
+            .field("added_at", &self.added_at)
+            .field("stored_size", &self.data.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    
+    #[test]
+    fn test_attachment_round_trip_and_limits() {
+        let content = "Code de secours: 1234-5678\n".repeat(100);
+        let attachment = Attachment::new(" codes.txt ", content.as_bytes()).unwrap();
+        assert_eq!(attachment.name, "codes.txt");
+        assert_eq!(attachment.size, content.len());
+        assert!(attachment.stored_size() < content.len());
+        
+        let json = serde_json::to_string(&attachment).unwrap();
+        let restored: Attachment = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.content().unwrap().as_slice(), content.as_bytes());
+        
+        assert_eq!(Attachment::new("../clé.pem", b"x").unwrap_err(), AttachmentError::InvalidName("../clé.pem".to_string()));
+        assert!(matches!(Attachment::new("gros.bin", &vec![0; MAX_ATTACHMENT_SIZE + 1]), Err(AttachmentError::TooLarge { .. })));
+        
+        let mut entry = Entry::new("Banque".to_string(), String::new(), String::new(), String::new(), String::new());
+        assert_eq!(entry.attach(attachment), Ok(false));
+        assert_eq!(entry.attach(Attachment::new("CODES.txt", b"nouveaux codes").unwrap()), Ok(true));
+        assert_eq!(entry.attachment("codes.TXT").map(|attachment| attachment.size), Some(14));
+        
+        let mut corrupted = restored.clone();
+        corrupted.size += 1;
+        assert_eq!(corrupted.content().unwrap_err(), AttachmentError::Corrupted("codes.txt".to_string()));
+    }
+}
\ No newline at end of file
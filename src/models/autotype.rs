@@ -0,0 +1,205 @@
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+use super::database::{Database, DatabaseError};
+use super::entry::Entry;
+use super::id::EntryId;
+
+/// Séquence utilisée pour les entrées sans séquence propre
+pub const DEFAULT_SEQUENCE: &str = "{USERNAME}{TAB}{PASSWORD}{ENTER}";
+
+/// Pause maximale d'un {DELAY}, en millisecondes
+const MAX_DELAY_MS: u64 = 60_000;
+
+/// Erreurs dans une séquence de saisie automatique
+#[derive(Error, Debug, PartialEq)]
+pub enum AutotypeError {
+    #[error("Accolade non refermée à la position {0}")]
+    Unclosed(usize),
+    
+    #[error("Code inconnu: {{{0}}} (USERNAME, PASSWORD, TITLE, URL, TOTP, TAB, ENTER, DELAY n)")]
+    UnknownPlaceholder(String),
+    
+    #[error("Pause invalide: '{0}' (millisecondes, au plus {MAX_DELAY_MS})")]
+    InvalidDelay(String),
+    
+    #[error("{{TOTP}}: {0}")]
+    Otp(String),
+}
+
+/// Touche spéciale d'une séquence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecialKey {
+    Tab,
+    Enter,
+}
+
+/// Champ d'une entrée inséré par une séquence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Username,
+    Password,
+    Title,
+    Url,
+    Totp,
+}
+
+/// Élément d'une séquence analysée
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Text(String),
+    Field(Field),
+    Key(SpecialKey),
+    Delay(u64),
+}
+
+/// Action de saisie, après remplacement des champs par les valeurs de l'entrée
+#[derive(Debug, PartialEq)]
+pub enum Keystroke {
+    /// Texte à taper (effacé de la mémoire après la saisie)
+    Text(Zeroizing<String>),
+    Key(SpecialKey),
+    
+    /// Pause, en millisecondes
+    Delay(u64),
+}
+
+/// Séquence de saisie automatique, avec la syntaxe de KeePass : texte littéral, champs
+/// `{USERNAME}`, `{PASSWORD}`, `{TITLE}`, `{URL}`, `{TOTP}`, touches `{TAB}`, `{ENTER}`,
+/// pauses `{DELAY 500}`; `{{}` et `{}}` tapent une accolade
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutotypeSequence {
+    tokens: Vec<Token>,
+}
+
+impl AutotypeSequence {
+    /// Analyse une séquence
+    pub fn parse(text: &str) -> Result<Self, AutotypeError> {
+        let mut tokens = Vec::new();
+        let mut literal = String::new();
+        let mut rest = text;
+        
+        while let Some(start) = rest.find('{') {
+            literal.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            
+            // Accolades littérales : {{} et {}}
+            let (code, consumed) = if let Some(stripped) = after.strip_prefix("{}").or_else(|| after.strip_prefix("}}")) {
+                (&after[..1], after.len() - stripped.len())
+            } else {
+                let end = after.find('}').ok_or(AutotypeError::Unclosed(text.len() - rest.len() + start))?;
+                (&after[..end], end + 1)
+            };
+            rest = &after[consumed..];
+            
+            let token = match code.trim().to_uppercase().as_str() {
+                "{" | "}" => {
+                    literal.push_str(code);
+                    continue;
+                }
+                "USERNAME" => Token::Field(Field::Username),
+                "PASSWORD" => Token::Field(Field::Password),
+                "TITLE" => Token::Field(Field::Title),
+                "URL" => Token::Field(Field::Url),
+                "TOTP" => Token::Field(Field::Totp),
+                "TAB" => Token::Key(SpecialKey::Tab),
+                "ENTER" => Token::Key(SpecialKey::Enter),
+                other => match other.strip_prefix("DELAY") {
+                    Some(delay) => {
+                        let delay = delay.trim();
+                        match delay.parse::<u64>() {
+                            Ok(ms) if ms <= MAX_DELAY_MS => Token::Delay(ms),
+                            _ => return Err(AutotypeError::InvalidDelay(delay.to_string())),
+                        }
+                    }
+                    None => return Err(AutotypeError::UnknownPlaceholder(code.to_string())),
+                },
+            };
+            if !literal.is_empty() {
+                tokens.push(Token::Text(std::mem::take(&mut literal)));
+            }
+            tokens.push(token);
+        }
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            tokens.push(Token::Text(literal));
+        }
+        
+        Ok(Self { tokens })
+    }
+    
+    /// Séquence propre à l'entrée, sinon la séquence par défaut
+    pub fn for_entry(entry: &Entry) -> Result<Self, AutotypeError> {
+        Self::parse(entry.autotype.as_deref().unwrap_or(DEFAULT_SEQUENCE))
+    }
+    
+    /// Indique si la séquence tape le mot de passe
+    pub fn types_password(&self) -> bool {
+        self.tokens.contains(&Token::Field(Field::Password))
+    }
+    
+    /// Actions de saisie pour une entrée; `unix_time` sert au calcul de {TOTP}
+    pub fn keystrokes(&self, entry: &Entry, unix_time: u64) -> Result<Vec<Keystroke>, AutotypeError> {
+        self.tokens.iter().map(|token| Ok(match token {
+            Token::Text(text) => Keystroke::Text(Zeroizing::new(text.clone())),
+            Token::Key(key) => Keystroke::Key(*key),
+            Token::Delay(ms) => Keystroke::Delay(*ms),
+            Token::Field(field) => Keystroke::Text(Zeroizing::new(match field {
+                Field::Username => entry.username.clone(),
+                Field::Password => entry.password.clone(),
+                Field::Title => entry.title.clone(),
+                Field::Url => entry.url.clone(),
+                Field::Totp => totp(entry, unix_time)?,
+            })),
+        })).collect()
+    }
+}
+
+/// Code à usage unique périodique de l'entrée (un code à compteur ne peut pas être tapé sans
+/// enregistrer la base)
+fn totp(entry: &Entry, unix_time: u64) -> Result<String, AutotypeError> {
+    let otp = entry.otp.as_ref().ok_or_else(|| AutotypeError::Otp("l'entrée n'a pas de code à usage unique".to_string()))?;
+    if otp.is_counter_based() {
+        return Err(AutotypeError::Otp("code à compteur (HOTP) non pris en charge, utilisez 'keeprust otp code'".to_string()));
+    }
+    otp.code_at(unix_time).map_err(|e| AutotypeError::Otp(e.to_string()))
+}
+
+impl Database {
+    /// Définit la séquence de saisie automatique d'une entrée (None : séquence par défaut)
+    pub fn set_autotype_sequence(&mut self, entry_id: &EntryId, sequence: Option<String>) -> Result<(), DatabaseError> {
+        self.update_entry(entry_id, |entry| entry.autotype = sequence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_and_resolve_sequence() {
+        let entry = Entry::builder().title("Banque").username("bob").password("s3cret").build().unwrap();
+        let keystrokes = AutotypeSequence::for_entry(&entry).unwrap().keystrokes(&entry, 0).unwrap();
+        assert_eq!(keystrokes, vec![
+            Keystroke::Text(Zeroizing::new("bob".to_string())),
+            Keystroke::Key(SpecialKey::Tab),
+            Keystroke::Text(Zeroizing::new("s3cret".to_string())),
+            Keystroke::Key(SpecialKey::Enter),
+        ]);
+        
+        let sequence = AutotypeSequence::parse("id={username}{DELAY 200}{{}x{}}{enter}").unwrap();
+        assert!(!sequence.types_password());
+        assert_eq!(sequence.keystrokes(&entry, 0).unwrap(), vec![
+            Keystroke::Text(Zeroizing::new("id=".to_string())),
+            Keystroke::Text(Zeroizing::new("bob".to_string())),
+            Keystroke::Delay(200),
+            Keystroke::Text(Zeroizing::new("{x}".to_string())),
+            Keystroke::Key(SpecialKey::Enter),
+        ]);
+        
+        assert_eq!(AutotypeSequence::parse("{USERNAME"), Err(AutotypeError::Unclosed(0)));
+        assert_eq!(AutotypeSequence::parse("{F1}"), Err(AutotypeError::UnknownPlaceholder("F1".to_string())));
+        assert!(matches!(AutotypeSequence::parse("{DELAY abc}"), Err(AutotypeError::InvalidDelay(_))));
+        assert!(matches!(AutotypeSequence::parse("{TOTP}").unwrap().keystrokes(&entry, 0), Err(AutotypeError::Otp(_))));
+    }
+}
@@ -0,0 +1,208 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::clock;
+use super::database::Database;
+use super::entry::{Entry, url_host};
+use super::scope::{GroupScope, ScopeError};
+use super::walker::WalkControl;
+
+/// Durée pendant laquelle une extension peut s'associer après 'keeprust proxy --pair'
+pub const PAIRING_MINUTES: i64 = 2;
+
+/// Erreurs liées aux associations des extensions de navigateur
+#[derive(Error, Debug, PartialEq)]
+pub enum BrowserError {
+    #[error("Aucun appairage en cours : lancez 'keeprust proxy --pair NOM' puis connectez l'extension")]
+    NoPairing,
+    
+    #[error("L'appairage '{0}' a expiré")]
+    PairingExpired(String),
+    
+    #[error("Une extension est déjà associée sous le nom '{0}'")]
+    DuplicateName(String),
+    
+    #[error("Aucune extension associée sous le nom '{0}'")]
+    UnknownAssociation(String),
+    
+    #[error(transparent)]
+    Scope(#[from] ScopeError),
+}
+
+/// Extension keepassxc-browser autorisée à lire et enregistrer des identifiants
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrowserAssociation {
+    /// Nom choisi lors de l'appairage, renvoyé à l'extension comme identifiant
+    pub name: String,
+    
+    /// Clé d'identification de l'extension (base64), présentée à chaque connexion
+    pub key: String,
+    
+    /// Date de l'association
+    pub created_at: DateTime<Utc>,
+    
+    /// Groupes auxquels l'extension a accès ("Web/Perso"); tous si vide
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scope: Vec<String>,
+}
+
+impl BrowserAssociation {
+    /// Portée de l'extension; None si elle a accès à tous les groupes
+    pub fn group_scope(&self) -> Option<GroupScope> {
+        // Une portée illisible ne donne accès à rien
+        (!self.scope.is_empty()).then(|| GroupScope::new(&self.scope).unwrap_or_default())
+    }
+}
+
+/// Appairage autorisé : la prochaine demande d'association reçoit ce nom
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrowserPairing {
+    pub name: String,
+    pub until: DateTime<Utc>,
+    
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scope: Vec<String>,
+}
+
+/// Indique si l'hôte d'une entrée correspond au site demandé (même hôte ou sous-domaine)
+fn host_matches(entry_host: &str, site_host: &str) -> bool {
+    site_host == entry_host || site_host.strip_suffix(entry_host).is_some_and(|subdomain| subdomain.ends_with('.'))
+}
+
+impl Database {
+    /// Empreinte de la base communiquée aux extensions, qui distinguent ainsi plusieurs bases
+    pub fn browser_hash(&self) -> String {
+        format!("{:x}", Sha256::digest(self.root_group.id.to_string().as_bytes()))
+    }
+    
+    /// Autorise l'association d'une extension sous ce nom pendant quelques minutes, limitée aux
+    /// groupes de `scope` s'il n'est pas vide
+    pub fn open_browser_pairing(&mut self, name: &str, scope: &[String]) -> Result<DateTime<Utc>, BrowserError> {
+        if self.metadata.browser_associations.iter().any(|association| association.name == name) {
+            return Err(BrowserError::DuplicateName(name.to_string()));
+        }
+        GroupScope::new(scope)?;
+        let until = clock::now() + Duration::minutes(PAIRING_MINUTES);
+        self.metadata.browser_pairing = Some(BrowserPairing { name: name.to_string(), until, scope: scope.to_vec() });
+        self.is_modified = true;
+        Ok(until)
+    }
+    
+    /// Associe l'extension qui présente cette clé, si un appairage est en cours; il est consommé
+    pub fn associate_browser(&mut self, key: &str) -> Result<String, BrowserError> {
+        let pairing = self.metadata.browser_pairing.take().ok_or(BrowserError::NoPairing)?;
+        self.is_modified = true;
+        if pairing.until < clock::now() {
+            return Err(BrowserError::PairingExpired(pairing.name));
+        }
+        
+        self.metadata.browser_associations.push(BrowserAssociation {
+            name: pairing.name.clone(),
+            key: key.to_string(),
+            created_at: clock::now(),
+            scope: pairing.scope,
+        });
+        Ok(pairing.name)
+    }
+    
+    /// Association de l'extension qui présente ce nom et cette clé
+    pub fn browser_association(&self, name: &str, key: &str) -> Option<&BrowserAssociation> {
+        self.metadata.browser_associations.iter().find(|association| association.name == name && association.key == key)
+    }
+    
+    /// Indique si l'extension est associée sous ce nom avec cette clé
+    pub fn is_browser_associated(&self, name: &str, key: &str) -> bool {
+        self.browser_association(name, key).is_some()
+    }
+    
+    /// Retire l'association d'une extension
+    pub fn revoke_browser(&mut self, name: &str) -> Result<(), BrowserError> {
+        let before = self.metadata.browser_associations.len();
+        self.metadata.browser_associations.retain(|association| association.name != name);
+        if self.metadata.browser_associations.len() == before {
+            return Err(BrowserError::UnknownAssociation(name.to_string()));
+        }
+        self.is_modified = true;
+        Ok(())
+    }
+    
+    /// Entrées proposées pour une page : même hôte ou domaine parent, hors corbeille et archives,
+    /// dans la portée de l'extension s'il y en a une. Les correspondances exactes viennent en
+    /// premier, puis l'ordre alphabétique des titres.
+    pub fn browser_logins(&self, url: &str, scope: Option<&GroupScope>) -> Vec<(Vec<String>, &Entry)> {
+        let Some(site) = url_host(url) else {
+            return Vec::new();
+        };
+        let trash = self.metadata.trash_group_id;
+        let mut logins = Vec::new();
+        self.walk(|path, group, entry| {
+            if Some(group.id) == trash {
+                return WalkControl::SkipChildren;
+            }
+            if let Some(entry) = entry
+                && !entry.archived
+                && scope.is_none_or(|scope| scope.allows(path))
+                && url_host(&entry.url).is_some_and(|host| host_matches(&host, &site))
+            {
+                logins.push((path.to_vec(), entry));
+            }
+            WalkControl::Continue
+        });
+        
+        logins.sort_by_key(|(_, entry)| (url_host(&entry.url).as_deref() != Some(site.as_str()), entry.title.to_lowercase()));
+        logins
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_pairing_and_logins() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        assert_eq!(db.associate_browser("k1"), Err(BrowserError::NoPairing));
+        
+        db.open_browser_pairing("firefox", &[]).unwrap();
+        assert_eq!(db.associate_browser("k1").unwrap(), "firefox");
+        assert!(db.is_browser_associated("firefox", "k1"));
+        assert!(!db.is_browser_associated("firefox", "k2"));
+        assert_eq!(db.open_browser_pairing("firefox", &[]), Err(BrowserError::DuplicateName("firefox".to_string())));
+        
+        let root = Entry::builder().title("Exemple").url("https://example.org").password("a").build().unwrap();
+        let account = Entry::builder().title("Compte").url("https://accounts.example.org/login").password("b").build().unwrap();
+        let other = Entry::builder().title("Autre").url("https://notexample.org").password("c").build().unwrap();
+        for entry in [root, account, other] {
+            db.add_entry(None, entry).unwrap();
+        }
+        
+        let titles: Vec<&str> = db.browser_logins("https://accounts.example.org/signin?next=/", None).iter().map(|(_, entry)| entry.title.as_str()).collect();
+        assert_eq!(titles, vec!["Compte", "Exemple"]);
+        assert!(db.browser_logins("pas une url", None).is_empty());
+        
+        db.revoke_browser("firefox").unwrap();
+        assert!(!db.is_browser_associated("firefox", "k1"));
+    }
+    
+    #[test]
+    fn test_scoped_association_sees_only_its_groups() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        assert_eq!(db.open_browser_pairing("chromium", &[" / ".to_string()]), Err(BrowserError::Scope(ScopeError::EmptyPath)));
+        db.open_browser_pairing("chromium", &["Web".to_string()]).unwrap();
+        db.associate_browser("k1").unwrap();
+        let scope = db.browser_association("chromium", "k1").unwrap().group_scope();
+        assert!(scope.is_some());
+        
+        let web = db.add_group(None, "Web".to_string()).unwrap();
+        db.add_entry(Some(&web), Entry::builder().title("Forum").url("https://example.org").password("a").build().unwrap()).unwrap();
+        db.add_entry(None, Entry::builder().title("Banque").url("https://example.org/banque").password("b").build().unwrap()).unwrap();
+        
+        let titles = |scope: Option<&GroupScope>| -> Vec<String> {
+            db.browser_logins("https://example.org", scope).iter().map(|(_, entry)| entry.title.clone()).collect()
+        };
+        assert_eq!(titles(scope.as_ref()), vec!["Forum"]);
+        assert_eq!(titles(None), vec!["Banque", "Forum"]);
+    }
+}
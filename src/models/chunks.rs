@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use std::mem;
+
+use super::database::Database;
+use super::entry::Entry;
+use super::group::Group;
+use super::id::GroupId;
+
+/// Nombre maximal d'entrées par bloc chiffré
+pub const ENTRIES_PER_CHUNK: usize = 64;
+
+/// Entrées d'un bloc, chacune accompagnée de l'identifiant de son groupe
+pub type EntryBatch = Vec<(GroupId, Entry)>;
+
+/// Sépare la base de données en un squelette (métadonnées et arborescence des groupes, sans entrées)
+/// et en lots d'entrées destinés à être chiffrés indépendamment
+pub fn split(db: &Database) -> (Database, Vec<EntryBatch>) {
+    let mut skeleton = db.clone();
+    let mut entries = Vec::new();
+    take_entries(&mut skeleton.root_group, &mut entries);
+    
+    let mut batches = Vec::new();
+    let mut entries = entries.into_iter();
+    loop {
+        let batch: EntryBatch = entries.by_ref().take(ENTRIES_PER_CHUNK).collect();
+        if batch.is_empty() {
+            break;
+        }
+        batches.push(batch);
+    }
+    
+    (skeleton, batches)
+}
+
+/// Retire récursivement les entrées d'un groupe et de ses sous-groupes
+fn take_entries(group: &mut Group, entries: &mut EntryBatch) {
+    entries.extend(mem::take(&mut group.entries).into_values().map(|entry| (group.id, entry)));
+    
+    for subgroup in group.subgroups.values_mut() {
+        take_entries(subgroup, entries);
+    }
+}
+
+/// Replace les entrées dans le squelette, sans modifier les dates ni l'état « modifié ».
+///
+/// Les entrées dont le groupe n'existe pas dans le squelette sont placées dans le groupe racine;
+/// leur nombre est retourné.
+pub fn assemble(skeleton: &mut Database, entries: EntryBatch) -> usize {
+    let mut by_group: HashMap<GroupId, Vec<Entry>> = HashMap::new();
+    for (group_id, entry) in entries {
+        by_group.entry(group_id).or_default().push(entry);
+    }
+    
+    put_entries(&mut skeleton.root_group, &mut by_group);
+    
+    let orphans: Vec<Entry> = by_group.into_values().flatten().collect();
+    let orphan_count = orphans.len();
+    for entry in orphans {
+        skeleton.root_group.entries.insert(entry.id, entry);
+    }
+    orphan_count
+}
+
+/// Place récursivement les entrées de chaque groupe
+fn put_entries(group: &mut Group, by_group: &mut HashMap<GroupId, Vec<Entry>>) {
+    if let Some(entries) = by_group.remove(&group.id) {
+        group.entries.extend(entries.into_iter().map(|entry| (entry.id, entry)));
+    }
+    
+    for subgroup in group.subgroups.values_mut() {
+        put_entries(subgroup, by_group);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use std::sync::OnceLock;
+
+/// Source de temps utilisée par les modèles
+pub type TimeSource = fn() -> DateTime<Utc>;
+
+static CLOCK: OnceLock<TimeSource> = OnceLock::new();
+
+/// Remplace l'horloge système (utile sur les cibles sans horloge comme wasm32-unknown-unknown).
+///
+/// L'horloge ne peut être définie qu'une seule fois et doit l'être avant toute utilisation des modèles;
+/// retourne false si une horloge était déjà en place.
+#[allow(dead_code)]
+pub fn set_clock(source: TimeSource) -> bool {
+    CLOCK.set(source).is_ok()
+}
+
+/// Retourne l'instant courant selon l'horloge configurée (horloge système par défaut)
+pub fn now() -> DateTime<Utc> {
+    CLOCK.get_or_init(|| Utc::now)()
+}
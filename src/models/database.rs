@@ -1,7 +1,51 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use super::group::Group;
-use super::entry::Entry;
+use thiserror::Error;
+use super::browser::{BrowserAssociation, BrowserPairing};
+use super::clock;
+use super::device::{self, DeviceStamp};
+use super::group::{Group, GroupIter};
+use super::entry::{ColorLabel, Entry};
+use super::id::{EntryId, GroupId};
+use super::repository::PayloadFormat;
+use super::revision::Snapshot;
+use super::search_index::SearchIndex;
+use super::stats::DatabaseStats;
+use super::strength_cache::StrengthCache;
+
+/// Enumération des erreurs possibles lors de la modification de la base de données
+#[derive(Error, Debug, PartialEq)]
+pub enum DatabaseError {
+    #[error("Groupe avec ID '{0}' non trouvé.")]
+    GroupNotFound(GroupId),
+    
+    #[error("Entrée avec ID '{0}' non trouvée.")]
+    EntryNotFound(EntryId),
+    
+    #[error("L'entrée '{0}' n'est pas dans la corbeille.")]
+    NotInTrash(EntryId),
+    
+    #[error("L'entrée '{0}' est dans la corbeille.")]
+    InTrash(EntryId),
+    
+    #[error("L'ID '{0}' existe déjà dans la base de données.")]
+    DuplicateId(String),
+    
+    #[error("Le groupe racine ne peut pas être supprimé ni déplacé.")]
+    RootGroup,
+    
+    #[error("L'entrée '{0}' n'est pas une copie de conflit.")]
+    NotAConflictCopy(EntryId),
+    
+    #[error("Le groupe '{0}' ne peut pas être déplacé dans sa propre descendance.")]
+    GroupCycle(GroupId),
+    
+    #[error("La corbeille ne peut être ni déplacée, ni supprimée, ni recevoir de groupe ('keeprust empty-trash' la vide).")]
+    TrashGroup,
+    
+    #[error("L'entrée '{0}' est verrouillée : déverrouillez-la (keeprust unlock-entry) ou, avec edit, delete et group delete, ajoutez --unlock-entry.")]
+    EntryLocked(String),
+}
 
 /// Contient les métadonnées de la base de données
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +64,34 @@ pub struct DatabaseMetadata {
     
     /// Version du format de la base de données
     pub version: String,
+    
+    /// Date et heure du dernier audit de sécurité
+    #[serde(default)]
+    pub last_audit: Option<DateTime<Utc>>,
+    
+    /// Nombre de mots de passe faibles relevés lors du dernier audit
+    #[serde(default)]
+    pub last_audit_weak: Option<usize>,
+    
+    /// Groupe servant de corbeille (créé lors de la première suppression)
+    #[serde(default)]
+    pub trash_group_id: Option<GroupId>,
+    
+    /// Appareil de la dernière modification
+    #[serde(default)]
+    pub modified_on: Option<DeviceStamp>,
+    
+    /// Scores de force du dernier audit, pour ne réanalyser que les mots de passe modifiés
+    #[serde(default)]
+    pub strength_cache: StrengthCache,
+    
+    /// Extensions de navigateur associées (keeprust proxy)
+    #[serde(default)]
+    pub browser_associations: Vec<BrowserAssociation>,
+    
+    /// Appairage d'extension en attente
+    #[serde(default)]
+    pub browser_pairing: Option<BrowserPairing>,
 }
 
 /// Représente une base de données complète de mots de passe
@@ -34,12 +106,16 @@ pub struct Database {
     /// Indique si la base de données a été modifiée depuis le dernier enregistrement
     #[serde(skip)]
     pub is_modified: bool,
+    
+    /// Format de sérialisation utilisé lors de l'enregistrement (lu depuis l'en-tête du fichier)
+    #[serde(skip)]
+    pub storage_format: PayloadFormat,
 }
 
 impl Database {
     /// Crée une nouvelle base de données vide
     pub fn new(name: String, description: String) -> Self {
-        let now = Utc::now();
+        let now = clock::now();
         Self {
             metadata: DatabaseMetadata {
                 name,
@@ -47,19 +123,60 @@ impl Database {
                 created_at: now,
                 updated_at: now,
                 version: env!("CARGO_PKG_VERSION").to_string(),
+                last_audit: None,
+                last_audit_weak: None,
+                trash_group_id: None,
+                modified_on: device::current(),
+                strength_cache: StrengthCache::default(),
+                browser_associations: Vec::new(),
+                browser_pairing: None,
             },
             root_group: Group::new("Root".to_string(), None),
             is_modified: true,
+            storage_format: PayloadFormat::default(),
         }
     }
     
+    /// Calcule les statistiques de l'arborescence complète
+    pub fn stats(&self) -> DatabaseStats {
+        let entries = self.iter_groups().flat_map(|group| group.entries.values().map(|entry| (group.id, entry.expires_at)));
+        DatabaseStats::compute(&self.root_group, entries)
+    }
+    
+    /// Mêmes statistiques, avec les entrées comptées dans l'index de recherche de la base (qui doit
+    /// correspondre à son contenu)
+    pub fn stats_from_index(&self, index: &SearchIndex) -> DatabaseStats {
+        let entries = index.entries.iter().filter_map(|entry| Some((entry.group?, entry.expires_at)));
+        DatabaseStats::compute(&self.root_group, entries)
+    }
+    
+    /// Retourne un itérateur sur tous les groupes de la base de données (groupe racine inclus)
+    pub fn iter_groups(&self) -> GroupIter<'_> {
+        self.root_group.iter_groups()
+    }
+    
+    /// Retourne un itérateur sur toutes les entrées de la base de données, quel que soit leur groupe
+    pub fn iter_entries(&self) -> impl Iterator<Item = &Entry> {
+        self.iter_groups().flat_map(|group| group.entries.values())
+    }
+    
+    /// Compte toutes les entrées de la base de données
+    pub fn count_entries(&self) -> usize {
+        self.iter_groups().map(|group| group.entries.len()).sum()
+    }
+    
+    /// Compte tous les groupes de la base de données (groupe racine inclus)
+    pub fn count_groups(&self) -> usize {
+        self.iter_groups().count()
+    }
+    
     /// Recherche une entrée par son ID dans toute la base de données
-    pub fn find_entry<'a>(&'a self, entry_id: &str) -> Option<&'a Entry> {
+    pub fn find_entry<'a>(&'a self, entry_id: &EntryId) -> Option<&'a Entry> {
         self.find_entry_in_group(&self.root_group, entry_id)
     }
     
     /// Fonction récursive pour rechercher une entrée dans un groupe et ses sous-groupes
-    fn find_entry_in_group<'a>(&self, group: &'a Group, entry_id: &str) -> Option<&'a Entry> {
+    fn find_entry_in_group<'a>(&self, group: &'a Group, entry_id: &EntryId) -> Option<&'a Entry> {
         // Recherche dans les entrées directes du groupe
         if let Some(entry) = group.entries.get(entry_id) {
             return Some(entry);
@@ -76,15 +193,25 @@ impl Database {
     }
     
     /// Recherche un groupe par son ID dans toute la base de données
-    pub fn find_group<'a>(&'a self, group_id: &str) -> Option<&'a Group> {
-        if self.root_group.id == group_id {
+    pub fn find_group<'a>(&'a self, group_id: &GroupId) -> Option<&'a Group> {
+        if self.root_group.id == *group_id {
             return Some(&self.root_group);
         }
         self.find_group_in_group(&self.root_group, group_id)
     }
     
+    /// Recherche un groupe par son chemin de noms, avec ou sans le groupe racine
+    /// ("Work/Servers" ou "Root/Work/Servers"), sans tenir compte de la casse
+    pub fn find_group_by_path(&self, path: &str) -> Option<&Group> {
+        let mut names: Vec<&str> = path.split('/').map(str::trim).filter(|name| !name.is_empty()).collect();
+        if names.first().is_some_and(|name| name.eq_ignore_ascii_case(&self.root_group.name)) {
+            names.remove(0);
+        }
+        find_group_by_names(&self.root_group, &names)
+    }
+    
     /// Fonction récursive pour rechercher un groupe dans un groupe et ses sous-groupes
-    fn find_group_in_group<'a>(&self, group: &'a Group, group_id: &str) -> Option<&'a Group> {
+    fn find_group_in_group<'a>(&self, group: &'a Group, group_id: &GroupId) -> Option<&'a Group> {
         // Recherche dans les sous-groupes directs
         if let Some(found_group) = group.subgroups.get(group_id) {
             return Some(found_group);
@@ -100,10 +227,162 @@ impl Database {
         None
     }
     
+    /// Ajoute une entrée dans un groupe (le groupe racine si aucun ID n'est fourni)
+    pub fn add_entry(&mut self, group_id: Option<&GroupId>, entry: Entry) -> Result<EntryId, DatabaseError> {
+        let group = match group_id {
+            Some(gid) => self.find_group_mut(gid).ok_or(DatabaseError::GroupNotFound(*gid))?,
+            None => &mut self.root_group,
+        };
+        
+        let entry_id = entry.id;
+        group.add_entry(entry);
+        self.mark_as_modified();
+        Ok(entry_id)
+    }
+    
+    /// Crée un groupe sous un parent (le groupe racine si aucun ID n'est fourni)
+    pub fn add_group(&mut self, parent_id: Option<&GroupId>, name: String) -> Result<GroupId, DatabaseError> {
+        let parent = match parent_id {
+            Some(pid) => self.find_group_mut(pid).ok_or(DatabaseError::GroupNotFound(*pid))?,
+            None => &mut self.root_group,
+        };
+        
+        let group = Group::new(name, Some(parent.id));
+        let group_id = group.id;
+        parent.add_subgroup(group);
+        self.mark_as_modified();
+        Ok(group_id)
+    }
+    
+    /// Modifie une entrée via une fonction et met à jour les horodatages de l'entrée,
+    /// de son groupe et de la base de données; une entrée verrouillée est refusée
+    /// (`DatabaseError::EntryLocked`)
+    pub fn update_entry<F, T>(&mut self, entry_id: &EntryId, update: F) -> Result<T, DatabaseError>
+    where
+        F: FnOnce(&mut Entry) -> T,
+    {
+        self.update_entry_with(entry_id, false, update)
+    }
+    
+    /// Comme `update_entry`, y compris pour une entrée verrouillée (confirmation explicite de
+    /// l'utilisateur, ou version déjà présente appliquée lors d'une fusion)
+    pub fn update_entry_unlocked<F, T>(&mut self, entry_id: &EntryId, update: F) -> Result<T, DatabaseError>
+    where
+        F: FnOnce(&mut Entry) -> T,
+    {
+        self.update_entry_with(entry_id, true, update)
+    }
+    
+    fn update_entry_with<F, T>(&mut self, entry_id: &EntryId, unlock: bool, update: F) -> Result<T, DatabaseError>
+    where
+        F: FnOnce(&mut Entry) -> T,
+    {
+        self.check_unlocked(entry_id, unlock)?;
+        let group = self.find_entry_group_mut(entry_id)
+            .ok_or(DatabaseError::EntryNotFound(*entry_id))?;
+        
+        let now = clock::now();
+        let entry = group.entries.get_mut(entry_id)
+            .ok_or(DatabaseError::EntryNotFound(*entry_id))?;
+        let before = Snapshot::of(entry);
+        let result = update(entry);
+        entry.updated_at = now;
+        entry.modified_on = device::current();
+        entry.record_revision(&before);
+        group.updated_at = now;
+        
+        self.mark_as_modified();
+        Ok(result)
+    }
+    
+    /// Archive ou désarchive une entrée, sans modifier sa date de dernière modification;
+    /// retourne l'état précédent
+    pub fn set_archived(&mut self, entry_id: &EntryId, archived: bool) -> Result<bool, DatabaseError> {
+        let entry = self.find_entry_group_mut(entry_id)
+            .and_then(|group| group.entries.get_mut(entry_id))
+            .ok_or(DatabaseError::EntryNotFound(*entry_id))?;
+        
+        let previous = std::mem::replace(&mut entry.archived, archived);
+        if previous != archived {
+            self.mark_as_modified();
+        }
+        Ok(previous)
+    }
+    
+    /// Refuse une entrée verrouillée, sauf avec `unlock`
+    pub(super) fn check_unlocked(&self, entry_id: &EntryId, unlock: bool) -> Result<(), DatabaseError> {
+        match self.find_entry(entry_id) {
+            Some(entry) if entry.locked && !unlock => Err(DatabaseError::EntryLocked(entry.title.clone())),
+            Some(_) => Ok(()),
+            None => Err(DatabaseError::EntryNotFound(*entry_id)),
+        }
+    }
+    
+    /// Verrouille ou déverrouille une entrée, sans modifier sa date de dernière modification;
+    /// retourne l'état précédent
+    pub fn set_locked(&mut self, entry_id: &EntryId, locked: bool) -> Result<bool, DatabaseError> {
+        let entry = self.find_entry_group_mut(entry_id)
+            .and_then(|group| group.entries.get_mut(entry_id))
+            .ok_or(DatabaseError::EntryNotFound(*entry_id))?;
+        
+        let previous = std::mem::replace(&mut entry.locked, locked);
+        if previous != locked {
+            self.mark_as_modified();
+        }
+        Ok(previous)
+    }
+    
+    /// Modifie des données internes d'une entrée (ex: compteur HOTP) sans changer sa date de
+    /// dernière modification
+    pub fn update_entry_metadata<F, T>(&mut self, entry_id: &EntryId, update: F) -> Result<T, DatabaseError>
+    where
+        F: FnOnce(&mut Entry) -> T,
+    {
+        let entry = self.find_entry_group_mut(entry_id)
+            .and_then(|group| group.entries.get_mut(entry_id))
+            .ok_or(DatabaseError::EntryNotFound(*entry_id))?;
+        
+        let result = update(entry);
+        self.mark_as_modified();
+        Ok(result)
+    }
+    
+    /// Définit ou retire l'étiquette de couleur d'une entrée, sans modifier sa date de dernière
+    /// modification; retourne l'étiquette précédente
+    pub fn set_color(&mut self, entry_id: &EntryId, color: Option<ColorLabel>) -> Result<Option<ColorLabel>, DatabaseError> {
+        let entry = self.find_entry_group_mut(entry_id)
+            .and_then(|group| group.entries.get_mut(entry_id))
+            .ok_or(DatabaseError::EntryNotFound(*entry_id))?;
+        
+        let previous = std::mem::replace(&mut entry.color, color);
+        if previous != color {
+            self.mark_as_modified();
+        }
+        Ok(previous)
+    }
+    
+    /// Supprime définitivement une entrée de la base de données
+    pub fn remove_entry(&mut self, entry_id: &EntryId) -> Result<Entry, DatabaseError> {
+        let entry = self.find_entry_group_mut(entry_id)
+            .and_then(|group| group.remove_entry(entry_id))
+            .ok_or(DatabaseError::EntryNotFound(*entry_id))?;
+        
+        self.mark_as_modified();
+        Ok(entry)
+    }
+    
+    /// Recherche le groupe (mutable) contenant une entrée
+    pub(super) fn find_entry_group_mut(&mut self, entry_id: &EntryId) -> Option<&mut Group> {
+        let group_id = self.iter_groups()
+            .find(|group| group.entries.contains_key(entry_id))?
+            .id;
+        self.find_group_mut(&group_id)
+    }
+    
     /// Recherche un groupe mutable par son ID
-    pub fn find_group_mut(&mut self, group_id: &str) -> Option<&mut Group> {
+    pub(super) fn find_group_mut(&mut self, group_id: &GroupId) -> Option<&mut Group> {
         // Cas spécial pour le groupe racine
-        if self.root_group.id == group_id {
+        if self.root_group.id == *group_id {
             return Some(&mut self.root_group);
         }
         
@@ -120,19 +399,19 @@ impl Database {
         // 3. Obtenir le groupe cible (dernier ID du chemin)
         current.subgroups.get_mut(path.last()?)
     }
-
+    
     /// Trouve le chemin (en IDs) vers un groupe spécifique
-    fn find_path_to_group(&self, group: &Group, target_id: &str, mut path: Vec<String>) -> Option<Vec<String>> {
+    fn find_path_to_group(&self, group: &Group, target_id: &GroupId, mut path: Vec<GroupId>) -> Option<Vec<GroupId>> {
         // Vérifier les sous-groupes directs
         if group.subgroups.contains_key(target_id) {
-            path.push(target_id.to_string());
+            path.push(*target_id);
             return Some(path);
         }
         
         // Recherche récursive dans les sous-groupes
         for (id, subgroup) in &group.subgroups {
             let mut new_path = path.clone();
-            new_path.push(id.clone());
+            new_path.push(*id);
             
             if let Some(result) = self.find_path_to_group(subgroup, target_id, new_path) {
                 return Some(result);
@@ -145,6 +424,160 @@ impl Database {
     /// Marque la base de données comme modifiée et met à jour l'horodatage
     pub fn mark_as_modified(&mut self) {
         self.is_modified = true;
-        self.metadata.updated_at = Utc::now();
+        self.metadata.updated_at = clock::now();
+        self.metadata.modified_on = device::current();
+    }
+    
+    /// Indique si la base de données contient des modifications non enregistrées
+    pub fn is_dirty(&self) -> bool {
+        self.is_modified
+    }
+    
+    /// Marque la base de données comme enregistrée
+    pub fn mark_as_saved(&mut self) {
+        self.is_modified = false;
+    }
+    
+    /// Enregistre la date et le nombre de mots de passe faibles du dernier audit
+    /// (sans changer la date de dernière modification); retourne le nombre relevé par l'audit précédent
+    pub fn record_audit(&mut self, weak_count: usize) -> Option<usize> {
+        self.metadata.last_audit = Some(clock::now());
+        self.is_modified = true;
+        self.metadata.last_audit_weak.replace(weak_count)
+    }
+}
+
+/// Sous-groupe atteint en suivant des noms depuis un groupe (plusieurs sous-groupes peuvent
+/// porter le même nom : chacun est essayé)
+fn find_group_by_names<'a>(group: &'a Group, names: &[&str]) -> Option<&'a Group> {
+    let Some((name, rest)) = names.split_first() else {
+        return Some(group);
+    };
+    group.subgroups.values()
+        .filter(|subgroup| subgroup.name.eq_ignore_ascii_case(name))
+        .find_map(|subgroup| find_group_by_names(subgroup, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn sample_database() -> Database {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let root_id = db.root_group.id;
+        
+        let mut work = Group::new("Work".to_string(), Some(root_id));
+        let mut servers = Group::new("Servers".to_string(), Some(work.id));
+        servers.add_entry(Entry::new("ssh".to_string(), "root".to_string(), "pw".to_string(), String::new(), String::new()));
+        work.add_entry(Entry::new("VPN".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new()));
+        work.add_subgroup(servers);
+        
+        db.root_group.add_entry(Entry::new("Mail".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new()));
+        db.root_group.add_subgroup(work);
+        db
+    }
+    
+    #[test]
+    fn test_counts_traverse_whole_tree() {
+        let db = sample_database();
+        
+        assert_eq!(db.count_entries(), 3);
+        assert_eq!(db.count_groups(), 3);
+        assert_eq!(db.iter_entries().count(), 3);
+        assert!(db.iter_entries().any(|e| e.title == "ssh"));
+    }
+    
+    #[test]
+    fn test_find_group_by_path() {
+        let db = sample_database();
+        
+        assert_eq!(db.find_group_by_path("work/Servers").map(|group| group.name.as_str()), Some("Servers"));
+        assert_eq!(db.find_group_by_path("Root/Work/").map(|group| group.name.as_str()), Some("Work"));
+        assert_eq!(db.find_group_by_path("").map(|group| group.id), Some(db.root_group.id));
+        assert!(db.find_group_by_path("Servers").is_none());
+    }
+    
+    #[test]
+    fn test_mutations_mark_database_as_modified() {
+        let mut db = sample_database();
+        db.mark_as_saved();
+        
+        let group_id = db.add_group(None, "Perso".to_string()).unwrap();
+        assert!(db.is_dirty());
+        
+        db.mark_as_saved();
+        let entry = Entry::new("Forum".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new());
+        let entry_id = db.add_entry(Some(&group_id), entry).unwrap();
+        assert!(db.is_dirty());
+        assert_eq!(db.find_group(&group_id).unwrap().entries.len(), 1);
+        
+        db.mark_as_saved();
+        db.update_entry(&entry_id, |entry| entry.username = "alice".to_string()).unwrap();
+        assert!(db.is_dirty());
+        assert_eq!(db.find_entry(&entry_id).unwrap().username, "alice");
+        
+        db.mark_as_saved();
+        let updated_at = db.find_entry(&entry_id).unwrap().updated_at;
+        assert!(!db.set_archived(&entry_id, true).unwrap());
+        assert!(db.is_dirty());
+        assert!(db.find_entry(&entry_id).unwrap().archived);
+        assert_eq!(db.find_entry(&entry_id).unwrap().updated_at, updated_at);
+        
+        db.mark_as_saved();
+        assert_eq!(db.set_color(&entry_id, Some(ColorLabel::Red)).unwrap(), None);
+        assert_eq!(db.set_color(&entry_id, Some(ColorLabel::Red)).unwrap(), Some(ColorLabel::Red));
+        assert_eq!(db.find_entry(&entry_id).unwrap().updated_at, updated_at);
+        
+        db.mark_as_saved();
+        assert!(!db.set_locked(&entry_id, true).unwrap());
+        assert!(db.is_dirty());
+        assert!(db.find_entry(&entry_id).unwrap().locked);
+        assert_eq!(db.find_entry(&entry_id).unwrap().updated_at, updated_at);
+        
+        db.mark_as_saved();
+        assert_eq!(db.remove_entry(&entry_id).unwrap().title, "Forum");
+        assert!(db.is_dirty());
+        assert_eq!(db.remove_entry(&entry_id).unwrap_err(), DatabaseError::EntryNotFound(entry_id));
+    }
+    
+    #[test]
+    fn test_locked_entry_refuses_updates_without_override() {
+        let mut db = sample_database();
+        let entry = Entry::new("Secours".to_string(), "root".to_string(), "pw".to_string(), String::new(), String::new());
+        let entry_id = db.add_entry(None, entry).unwrap();
+        db.set_locked(&entry_id, true).unwrap();
+        
+        let locked = DatabaseError::EntryLocked("Secours".to_string());
+        assert_eq!(db.update_entry(&entry_id, |entry| entry.password = "autre".to_string()).unwrap_err(), locked);
+        assert_eq!(db.set_autotype_sequence(&entry_id, Some("{PASSWORD}".to_string())).unwrap_err(), locked);
+        assert_eq!(db.find_entry(&entry_id).unwrap().password, "pw");
+        
+        db.update_entry_unlocked(&entry_id, |entry| entry.password = "autre".to_string()).unwrap();
+        assert_eq!(db.find_entry(&entry_id).unwrap().password, "autre");
+        assert!(db.find_entry(&entry_id).unwrap().locked);
+    }
+    
+    #[test]
+    fn test_add_entry_to_unknown_group() {
+        let mut db = sample_database();
+        let entry = Entry::new("Forum".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new());
+        
+        let unknown = GroupId::new();
+        
+        assert_eq!(db.add_entry(Some(&unknown), entry).unwrap_err(), DatabaseError::GroupNotFound(unknown));
+    }
+    
+    #[test]
+    fn test_stats_match_counts() {
+        let db = sample_database();
+        let stats = db.stats();
+        
+        assert_eq!(stats.total_entries, db.count_entries());
+        assert_eq!(stats.groups.len(), db.count_groups());
+        assert_eq!(stats.groups[0].total_entries, 3);
+        
+        let indexed = db.stats_from_index(&SearchIndex::build(&db, String::new()));
+        assert_eq!(indexed.total_entries, stats.total_entries);
+        assert_eq!(indexed.groups.iter().map(|group| group.total_entries).collect::<Vec<_>>(), [3, 2, 1]);
     }
 }
\ No newline at end of file
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Appareil à l'origine d'une modification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceStamp {
+    /// Identifiant propre à chaque installation
+    pub id: String,
+    
+    /// Nom lisible de l'appareil (ex: "portable-x"), tel qu'il était au moment de la modification
+    pub name: String,
+}
+
+impl fmt::Display for DeviceStamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+static DEVICE: OnceLock<DeviceStamp> = OnceLock::new();
+
+/// Définit l'appareil courant, utilisé pour marquer les modifications.
+///
+/// L'appareil ne peut être défini qu'une seule fois; retourne false s'il l'était déjà.
+pub fn set_device(device: DeviceStamp) -> bool {
+    DEVICE.set(device).is_ok()
+}
+
+/// Appareil courant (aucun si l'application ne l'a pas défini, ex: bibliothèque ou tests)
+pub fn current() -> Option<DeviceStamp> {
+    DEVICE.get().cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    
+    #[test]
+    fn test_modifications_are_stamped_with_current_device() {
+        let laptop = DeviceStamp { id: "1234".to_string(), name: "portable-x".to_string() };
+        set_device(laptop.clone());
+        
+        let mut entry = Entry::new("Mail".to_string(), "bob".to_string(), "ancien".to_string(), String::new(), String::new());
+        entry.modified_on = None;
+        entry.update(None, None, Some("nouveau".to_string()), None, None);
+        
+        assert_eq!(entry.modified_on, Some(laptop.clone()));
+        assert_eq!(entry.password_history[0].changed_on, Some(laptop));
+    }
+}
\ No newline at end of file
@@ -1,22 +1,121 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
 use zeroize::Zeroize;
 use std::fmt;
 use std::collections::VecDeque;
+use thiserror::Error;
+use url::Url;
+use crate::crypto::otp::OtpConfig;
+use crate::utils::redact::Redactor;
+use super::attachment::{self, Attachment, AttachmentError, MAX_ENTRY_ATTACHMENTS_SIZE};
+use super::clock;
+use super::device::{self, DeviceStamp};
+use super::exposure::Exposure;
+use super::id::{EntryId, GroupId};
+use super::revision::{MAX_REVISIONS, Revision, Snapshot};
 
+/// Enumération des erreurs de validation lors de la construction d'une entrée
+#[derive(Error, Debug, PartialEq)]
+pub enum EntryError {
+    #[error("Le titre de l'entrée ne peut pas être vide")]
+    EmptyTitle,
+    
+    #[error("URL invalide: '{0}'")]
+    InvalidUrl(String),
+    
+    #[error("Le nom d'un champ ne peut pas être vide")]
+    EmptyFieldName,
+}
+
+/// Étiquette de couleur d'une entrée
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorLabel {
+    Red,
+    Orange,
+    Green,
+    Blue,
+}
+
+impl ColorLabel {
+    /// Code de couleur ANSI pour l'affichage dans un terminal
+    pub fn ansi_code(&self) -> &'static str {
+        match self {
+            ColorLabel::Red => "31",
+            ColorLabel::Orange => "33",
+            ColorLabel::Green => "32",
+            ColorLabel::Blue => "34",
+        }
+    }
+}
+
+impl fmt::Display for ColorLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorLabel::Red => write!(f, "rouge"),
+            ColorLabel::Orange => write!(f, "orange"),
+            ColorLabel::Green => write!(f, "vert"),
+            ColorLabel::Blue => write!(f, "bleu"),
+        }
+    }
+}
+
+impl std::str::FromStr for ColorLabel {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "red" | "rouge" => Ok(ColorLabel::Red),
+            "orange" => Ok(ColorLabel::Orange),
+            "green" | "vert" => Ok(ColorLabel::Green),
+            "blue" | "bleu" => Ok(ColorLabel::Blue),
+            other => Err(format!("Couleur inconnue: '{}' (rouge, orange, vert ou bleu)", other)),
+        }
+    }
+}
 
 // Modifier la structure Entry pour inclure l'historique
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct PasswordHistory {
     pub password: String,
     pub changed_at: DateTime<Utc>,
+    
+    /// Appareil sur lequel le mot de passe a été remplacé
+    #[serde(default)]
+    pub changed_on: Option<DeviceStamp>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Champ supplémentaire d'une entrée (question de sécurité, clé d'API, numéro de compte...)
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomField {
+    pub name: String,
+    pub value: String,
+    
+    /// Valeur secrète : masquée à l'affichage comme le mot de passe et jamais indexée
+    #[serde(default)]
+    pub protected: bool,
+}
+
+impl CustomField {
+    pub fn new(name: impl Into<String>, value: impl Into<String>, protected: bool) -> Self {
+        Self { name: name.into(), value: value.into(), protected }
+    }
+    
+    /// Valeur à afficher : celle d'un champ protégé passe par le `Redactor`
+    pub fn display_value(&self, redactor: Redactor) -> String {
+        if self.protected { redactor.redact(&self.value) } else { self.value.clone() }
+    }
+}
+
+/// Compare deux noms de champ sans tenir compte de la casse (accents compris) ni des espaces autour
+fn same_field_name(a: &str, b: &str) -> bool {
+    a.trim().to_lowercase() == b.trim().to_lowercase()
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Entry {
     /// Identifiant unique pour cette entrée
-    pub id: String,
+    pub id: EntryId,
     
     /// Titre descriptif de l'entrée (ex: "Gmail", "Facebook", etc.)
     pub title: String,
@@ -37,34 +136,115 @@ pub struct Entry {
     /// Notes supplémentaires
     pub notes: String,
     
+    /// Étiquettes libres permettant de classer et filtrer les entrées
+    #[serde(default)]
+    pub tags: Vec<String>,
+    
     /// Date et heure de création de l'entrée
     pub created_at: DateTime<Utc>,
     
     /// Date et heure de dernière modification
     pub updated_at: DateTime<Utc>,
+    
+    /// Appareil de la dernière modification
+    #[serde(default)]
+    pub modified_on: Option<DeviceStamp>,
+    
+    /// Date d'expiration du mot de passe (aucune si non définie)
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    
+    /// Entrée archivée (compte inactif) : masquée par défaut dans les listes et les audits
+    #[serde(default)]
+    pub archived: bool,
+    
+    /// Entrée verrouillée (ex: identifiants de secours) : edit et delete la refusent sans
+    /// confirmation explicite (--unlock-entry)
+    #[serde(default)]
+    pub locked: bool,
+    
+    /// Étiquette de couleur (aucune si non définie)
+    #[serde(default)]
+    pub color: Option<ColorLabel>,
+    
+    /// Générateur de codes à usage unique (double authentification)
+    #[serde(default)]
+    pub otp: Option<OtpConfig>,
+    
+    /// Groupe d'origine d'une entrée placée dans la corbeille
+    #[serde(default)]
+    pub deleted_from: Option<GroupId>,
+    
+    /// Parents du groupe d'origine, du plus proche au groupe racine : la restauration se fait dans
+    /// le plus proche encore présent si le groupe d'origine a été supprimé entre-temps
+    #[serde(default)]
+    pub deleted_from_parents: Vec<GroupId>,
+    
+    /// Entrée dont celle-ci est une copie de conflit (modifiée des deux côtés lors d'une fusion)
+    #[serde(default)]
+    pub conflict_of: Option<EntryId>,
+    
+    /// Dernière fois que le mot de passe a été affiché, copié ou exporté en clair
+    #[serde(default)]
+    pub last_exposure: Option<Exposure>,
+    
+    /// Séquence de saisie automatique propre à l'entrée (voir `AutotypeSequence`)
+    #[serde(default)]
+    pub autotype: Option<String>,
+    
+    /// Champs supplémentaires, dans l'ordre d'ajout (noms uniques, sans tenir compte de la casse)
+    #[serde(default)]
+    pub custom_fields: Vec<CustomField>,
+    
+    /// Fichiers joints, compressés (noms uniques, sans tenir compte de la casse)
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    
+    /// Journal des modifications, de la plus ancienne à la plus récente (limité aux 50 dernières)
+    #[serde(default)]
+    pub revisions: VecDeque<Revision>,
 }
 
 impl Entry {
     /// Crée une nouvelle entrée avec les informations fournies
     pub fn new(title: String, username: String, password: String, url: String, notes: String) -> Self {
-        let now = Utc::now();
+        let now = clock::now();
         Self {
-            id: Uuid::new_v4().to_string(),
+            id: EntryId::new(),
             title,
             username,
             password,
             password_history: VecDeque::new(),
             url,
             notes,
+            tags: Vec::new(),
             created_at: now,
             updated_at: now,
+            modified_on: device::current(),
+            expires_at: None,
+            archived: false,
+            locked: false,
+            color: None,
+            otp: None,
+            deleted_from: None,
+            deleted_from_parents: Vec::new(),
+            conflict_of: None,
+            last_exposure: None,
+            autotype: None,
+            custom_fields: Vec::new(),
+            attachments: Vec::new(),
+            revisions: VecDeque::new(),
         }
     }
     
+    /// Retourne un constructeur d'entrée avec validation
+    pub fn builder() -> EntryBuilder {
+        EntryBuilder::default()
+    }
+    
     /// Met à jour l'entrée et actualise l'horodatage de modification
-    #[allow(dead_code)]
     pub fn update(&mut self, title: Option<String>, username: Option<String>, 
-                  password: Option<String>, url: Option<String>, _notes: Option<String>) {
+                  password: Option<String>, url: Option<String>, notes: Option<String>) {
         if let Some(title) = title {
             self.title = title;
         }
@@ -76,19 +256,7 @@ impl Entry {
         if let Some(mut new_password) = password {
             // Ajouter l'ancien mot de passe à l'historique
             if !self.password.is_empty() {
-                let old_password = PasswordHistory {
-                    password: self.password.clone(),
-                    changed_at: Utc::now(),
-                };
-                
-                self.password_history.push_front(old_password);
-                
-                // Limiter l'historique à 10 entrées
-                if self.password_history.len() > 10 {
-                    if let Some(mut removed) = self.password_history.pop_back() {
-                        removed.password.zeroize();
-                    }
-                }
+                self.remember_password(self.password.clone(), device::current());
             }
             
             // Effacer l'ancien mot de passe de manière sécurisée
@@ -103,16 +271,49 @@ impl Entry {
             self.url = url;
         }
         
-        if let Some(notes) = _notes {
+        if let Some(notes) = notes {
             self.notes = notes;
         }
         
-        // Mise à jour de l'horodatage
-        self.updated_at = Utc::now();
+        // Mise à jour de l'horodatage et de l'appareil
+        self.updated_at = clock::now();
+        self.modified_on = device::current();
+    }
+    
+    /// Ajoute un mot de passe remplacé en tête de l'historique, limité aux 10 derniers
+    pub fn remember_password(&mut self, password: String, changed_on: Option<DeviceStamp>) {
+        self.password_history.push_front(PasswordHistory {
+            password,
+            changed_at: clock::now(),
+            changed_on,
+        });
+        
+        if self.password_history.len() > 10
+            && let Some(mut removed) = self.password_history.pop_back()
+        {
+            removed.password.zeroize();
+        }
+    }
+    
+    /// Note dans le journal des modifications les champs qui diffèrent de `before` (sans effet
+    /// si aucun n'a changé)
+    pub fn record_revision(&mut self, before: &Snapshot) {
+        let changes = before.changes(&Snapshot::of(self));
+        if changes.is_empty() {
+            return;
+        }
+        
+        self.revisions.push_back(Revision {
+            changed_at: self.updated_at,
+            changed_on: self.modified_on.clone(),
+            changes,
+        });
+        while self.revisions.len() > MAX_REVISIONS {
+            self.revisions.pop_front();
+        }
     }
     
     /// Vérifie si un mot de passe a déjà été utilisé
-    #[allow(dead_code)]
     pub fn has_used_password(&self, password: &str) -> bool {
         if self.password == password {
             return true;
@@ -121,6 +322,68 @@ impl Entry {
         self.password_history.iter().any(|h| h.password == password)
     }
     
+    /// Vérifie si l'entrée porte une étiquette (sans tenir compte de la casse)
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+    
+    /// Champ supplémentaire désigné par son nom (sans tenir compte de la casse)
+    pub fn custom_field(&self, name: &str) -> Option<&CustomField> {
+        self.custom_fields.iter().find(|field| same_field_name(&field.name, name))
+    }
+    
+    /// Ajoute un champ supplémentaire, ou remplace la valeur et la protection du champ de même nom
+    pub fn set_custom_field(&mut self, mut field: CustomField) -> Result<(), EntryError> {
+        field.name = field.name.trim().to_string();
+        if field.name.is_empty() {
+            return Err(EntryError::EmptyFieldName);
+        }
+        
+        match self.custom_fields.iter_mut().find(|existing| same_field_name(&existing.name, &field.name)) {
+            Some(existing) => {
+                existing.value.zeroize();
+                *existing = field;
+            }
+            None => self.custom_fields.push(field),
+        }
+        Ok(())
+    }
+    
+    /// Retire un champ supplémentaire; indique s'il existait
+    pub fn remove_custom_field(&mut self, name: &str) -> bool {
+        let Some(index) = self.custom_fields.iter().position(|field| same_field_name(&field.name, name)) else {
+            return false;
+        };
+        self.custom_fields.remove(index).value.zeroize();
+        true
+    }
+    
+    /// Pièce jointe désignée par son nom (sans tenir compte de la casse)
+    pub fn attachment(&self, name: &str) -> Option<&Attachment> {
+        self.attachments.iter().find(|existing| attachment::same_attachment_name(&existing.name, name))
+    }
+    
+    /// Joint un fichier, ou remplace la pièce jointe de même nom; indique si elle a été remplacée.
+    ///
+    /// Les pièces jointes d'une entrée ne peuvent pas dépasser `MAX_ENTRY_ATTACHMENTS_SIZE` octets
+    /// une fois compressées.
+    pub fn attach(&mut self, attachment: Attachment) -> Result<bool, AttachmentError> {
+        let existing = self.attachments.iter().position(|existing| attachment::same_attachment_name(&existing.name, &attachment.name));
+        let size: usize = self.attachments.iter().enumerate()
+            .filter(|(index, _)| Some(*index) != existing)
+            .map(|(_, other)| other.stored_size())
+            .sum::<usize>() + attachment.stored_size();
+        if size > MAX_ENTRY_ATTACHMENTS_SIZE {
+            return Err(AttachmentError::EntryFull { size, max: MAX_ENTRY_ATTACHMENTS_SIZE });
+        }
+        
+        match existing {
+            Some(index) => self.attachments[index] = attachment,
+            None => self.attachments.push(attachment),
+        }
+        Ok(existing.is_some())
+    }
+    
     /// Efface le mot de passe de manière sécurisée
     #[allow(dead_code)]
     pub fn clear_password(&mut self) {
@@ -135,11 +398,278 @@ impl Entry {
     }
 }
 
+/// Constructeur d'entrée: `Entry::builder().title("Gmail").username("bob").build()`
+#[derive(Default)]
+pub struct EntryBuilder {
+    title: String,
+    username: String,
+    password: String,
+    url: String,
+    notes: String,
+    tags: Vec<String>,
+    expires_at: Option<DateTime<Utc>>,
+    color: Option<ColorLabel>,
+    custom_fields: Vec<CustomField>,
+}
+
+impl EntryBuilder {
+    /// Définit le titre (obligatoire)
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+    
+    /// Définit le nom d'utilisateur
+    pub fn username(mut self, username: impl Into<String>) -> Self {
+        self.username = username.into();
+        self
+    }
+    
+    /// Définit le mot de passe
+    pub fn password(mut self, password: impl Into<String>) -> Self {
+        self.password = password.into();
+        self
+    }
+    
+    /// Définit l'URL (validée lors de la construction)
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+    
+    /// Définit les notes
+    pub fn notes(mut self, notes: impl Into<String>) -> Self {
+        self.notes = notes.into();
+        self
+    }
+    
+    /// Ajoute une étiquette (les doublons sont ignorés)
+    #[allow(dead_code)]
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        let tag = tag.into();
+        if !self.tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+            self.tags.push(tag);
+        }
+        self
+    }
+    
+    /// Ajoute plusieurs étiquettes
+    pub fn tags<I, S>(self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        tags.into_iter().fold(self, |builder, tag| builder.tag(tag))
+    }
+    
+    /// Définit la date d'expiration du mot de passe
+    pub fn expires_at(mut self, expires_at: Option<DateTime<Utc>>) -> Self {
+        self.expires_at = expires_at;
+        self
+    }
+    
+    /// Définit l'étiquette de couleur
+    pub fn color(mut self, color: Option<ColorLabel>) -> Self {
+        self.color = color;
+        self
+    }
+    
+    /// Ajoute un champ supplémentaire (un champ de même nom est remplacé)
+    pub fn custom_field(mut self, field: CustomField) -> Self {
+        self.custom_fields.push(field);
+        self
+    }
+    
+    /// Valide les champs et construit l'entrée
+    pub fn build(mut self) -> Result<Entry, EntryError> {
+        self.title = self.title.trim().to_string();
+        if self.title.is_empty() {
+            return Err(EntryError::EmptyTitle);
+        }
+        
+        self.url = self.url.trim().to_string();
+        if !self.url.is_empty() && !is_valid_url(&self.url) {
+            return Err(EntryError::InvalidUrl(std::mem::take(&mut self.url)));
+        }
+        
+        let mut entry = Entry::new(
+            std::mem::take(&mut self.title),
+            std::mem::take(&mut self.username),
+            std::mem::take(&mut self.password),
+            std::mem::take(&mut self.url),
+            std::mem::take(&mut self.notes),
+        );
+        entry.tags = std::mem::take(&mut self.tags);
+        entry.expires_at = self.expires_at;
+        entry.color = self.color;
+        for field in std::mem::take(&mut self.custom_fields) {
+            entry.set_custom_field(field)?;
+        }
+        Ok(entry)
+    }
+}
+
+impl Drop for EntryBuilder {
+    /// Efface le mot de passe et les champs protégés si le constructeur est abandonné
+    fn drop(&mut self) {
+        self.password.zeroize();
+        for field in self.custom_fields.iter_mut().filter(|field| field.protected) {
+            field.value.zeroize();
+        }
+    }
+}
+
+/// Vérifie qu'une URL est valide; les adresses sans schéma ("exemple.com") sont acceptées
+pub fn is_valid_url(url: &str) -> bool {
+    if url.contains(char::is_whitespace) {
+        return false;
+    }
+    
+    match Url::parse(url) {
+        Ok(parsed) => parsed.has_host() || !matches!(parsed.scheme(), "http" | "https"),
+        Err(_) => Url::parse(&format!("https://{}", url)).is_ok_and(|parsed| parsed.has_host()),
+    }
+}
+
+/// Hôte d'une URL, en minuscules; les adresses sans schéma ("exemple.com/login") sont acceptées
+pub fn url_host(url: &str) -> Option<String> {
+    let parsed = Url::parse(url).ok().filter(|parsed| parsed.has_host())
+        .or_else(|| Url::parse(&format!("https://{}", url)).ok())?;
+    Some(parsed.host_str()?.to_string())
+}
+
+/// Suffixes de second niveau courants sous un domaine national ("co.uk", "com.au"...)
+const SECOND_LEVEL_SUFFIXES: [&str; 7] = ["co", "com", "org", "net", "gov", "edu", "ac"];
+
+/// Nom de site déduit d'une URL, pour titrer une entrée :
+/// "https://accounts.example.com/signup" donne "Example", "shop.example.co.uk" aussi.
+///
+/// Les adresses IP et les hôtes sans domaine sont retournés tels quels.
+pub fn site_name(url: &str) -> Option<String> {
+    let host = url_host(url)?;
+    let labels: Vec<&str> = host.trim_end_matches('.').split('.').collect();
+    if labels.len() < 2 || host.parse::<std::net::Ipv4Addr>().is_ok() || host.starts_with('[') {
+        return Some(host);
+    }
+    
+    let top = labels[labels.len() - 1];
+    let second = labels[labels.len() - 2];
+    let name = if labels.len() >= 3 && top.len() == 2 && SECOND_LEVEL_SUFFIXES.contains(&second) {
+        labels[labels.len() - 3]
+    } else {
+        second
+    };
+    
+    let mut chars = name.chars();
+    let first = chars.next()?;
+    Some(first.to_uppercase().chain(chars).collect())
+}
+
+/// Remplace le domaine `from` (ou l'un de ses sous-domaines) par `to` dans une URL.
+///
+/// Le reste de l'URL est conservé tel quel; retourne None si l'URL ne désigne pas ce domaine.
+pub fn rewrite_url_domain(url: &str, from: &str, to: &str) -> Option<String> {
+    let host = url_host(url)?;
+    let host = host.as_str();
+    let from = from.trim().trim_end_matches('.').to_lowercase();
+    
+    let new_host = if host == from {
+        to.to_string()
+    } else {
+        let subdomain = host.strip_suffix(&from)?.strip_suffix('.')?;
+        format!("{}.{}", subdomain, to)
+    };
+    
+    // Localiser l'hôte dans le texte d'origine (après le schéma et les identifiants éventuels)
+    let mut start = url.find("://").map(|position| position + 3).unwrap_or(0);
+    let authority_end = url[start..].find(['/', '?', '#']).map(|position| start + position).unwrap_or(url.len());
+    if let Some(at) = url[start..authority_end].rfind('@') {
+        start += at + 1;
+    }
+    let offset = url[start..].to_lowercase().find(host)?;
+    let host_start = start + offset;
+    
+    Some(format!("{}{}{}", &url[..host_start], new_host, &url[host_start + host.len()..]))
+}
+
 impl Drop for Entry {
-    /// Efface le mot de passe de la mémoire lorsque l'entrée est détruite
+    /// Efface le mot de passe et les champs protégés de la mémoire lorsque l'entrée est détruite
     fn drop(&mut self) {
         self.password.zeroize();
         self.clear_password_history();
+        for field in self.custom_fields.iter_mut().filter(|field| field.protected) {
+            field.value.zeroize();
+        }
+    }
+}
+
+impl fmt::Debug for CustomField {
+    /// Format de débogage sans la valeur d'un champ protégé
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomField")
+            .field("name", &self.name)
+            .field("value", &self.display_value(Redactor::Mask))
+            .field("protected", &self.protected)
+            .finish()
+    }
+}
+
+impl fmt::Debug for PasswordHistory {
+    /// Format de débogage sans l'ancien mot de passe
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PasswordHistory")
+            .field("password", &Redactor::Mask.redact(&self.password))
+            .field("changed_at", &self.changed_at)
+            .field("changed_on", &self.changed_on)
+            .finish()
+    }
+}
+
+impl fmt::Debug for Entry {
+    /// Format de débogage (journaux, messages d'erreur) sans le mot de passe
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Entry")
+            .field("id", &self.id)
+            .field("title", &self.title)
+            .field("username", &self.username)
+            .field("password", &Redactor::Mask.redact(&self.password))
+            .field("password_history", &self.password_history)
+            .field("url", &self.url)
+            .field("notes", &self.notes)
+            .field("tags", &self.tags)
+            .field("created_at", &self.created_at)
+            .field("updated_at", &self.updated_at)
+            .field("modified_on", &self.modified_on)
+            .field("expires_at", &self.expires_at)
+            .field("archived", &self.archived)
+            .field("locked", &self.locked)
+            .field("color", &self.color)
+            .field("otp", &self.otp)
+            .field("deleted_from", &self.deleted_from)
+            .field("deleted_from_parents", &self.deleted_from_parents)
+            .field("conflict_of", &self.conflict_of)
+            .field("last_exposure", &self.last_exposure)
+            .field("autotype", &self.autotype)
+            .field("custom_fields", &self.custom_fields)
+            .field("attachments", &self.attachments)
+            .field("revisions", &self.revisions)
+            .finish()
+    }
+}
+
+impl fmt::Debug for EntryBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EntryBuilder")
+            .field("title", &self.title)
+            .field("username", &self.username)
+            .field("password", &Redactor::Mask.redact(&self.password))
+            .field("url", &self.url)
+            .field("notes", &self.notes)
+            .field("tags", &self.tags)
+            .field("expires_at", &self.expires_at)
+            .field("color", &self.color)
+            .field("custom_fields", &self.custom_fields)
+            .finish()
     }
 }
 
@@ -154,4 +684,89 @@ impl fmt::Display for Entry {
             self.updated_at.format("%d-%m-%Y %H:%M:%S")
         )
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_builder_sets_fields() {
+        let entry = Entry::builder()
+            .title("  Gmail ")
+            .username("bob@example.com")
+            .password("secret")
+            .url("https://mail.google.com")
+            .tag("mail")
+            .tag("Mail")
+            .build()
+            .unwrap();
+        
+        assert_eq!(entry.title, "Gmail");
+        assert_eq!(entry.password, "secret");
+        assert_eq!(entry.tags, vec!["mail"]);
+        assert!(!format!("{:?}", entry).contains("secret"));
+    }
+    
+    #[test]
+    fn test_builder_validation() {
+        assert_eq!(Entry::builder().title("  ").build().unwrap_err(), EntryError::EmptyTitle);
+        assert_eq!(Entry::builder().title("x").url("http://exa mple").build().unwrap_err(),
+            EntryError::InvalidUrl("http://exa mple".to_string()));
+        assert!(Entry::builder().title("x").url("example.com/login").build().is_ok());
+        assert!(Entry::builder().title("x").url("ssh://server:22").build().is_ok());
+    }
+    
+    #[test]
+    fn test_update_records_password_history() {
+        let mut entry = Entry::new("Mail".to_string(), "bob".to_string(), "old".to_string(), String::new(), String::new());
+        
+        entry.update(None, None, Some("new".to_string()), None, Some("2FA activée".to_string()));
+        assert_eq!(entry.password, "new");
+        assert_eq!(entry.notes, "2FA activée");
+        assert!(entry.has_used_password("old"));
+        
+        entry.update(Some("Courriel".to_string()), None, None, None, None);
+        assert_eq!(entry.password_history.len(), 1);
+    }
+    
+    #[test]
+    fn test_custom_fields() {
+        let mut entry = Entry::builder()
+            .title("Banque")
+            .custom_field(CustomField::new("Numéro de compte", "FR76 3000", false))
+            .custom_field(CustomField::new(" Code ", "4821", true))
+            .build()
+            .unwrap();
+        assert_eq!(entry.custom_field("code").map(|field| field.value.as_str()), Some("4821"));
+        assert!(!format!("{:?}", entry).contains("4821"));
+        
+        entry.set_custom_field(CustomField::new("NUMÉRO DE COMPTE", "FR76 4000", false)).unwrap();
+        assert_eq!(entry.custom_fields.len(), 2);
+        assert_eq!(entry.custom_fields[0].value, "FR76 4000");
+        assert_eq!(entry.set_custom_field(CustomField::new("  ", "x", false)), Err(EntryError::EmptyFieldName));
+        
+        assert!(entry.remove_custom_field("Code"));
+        assert!(!entry.remove_custom_field("Code"));
+        assert_eq!(Entry::builder().title("x").custom_field(CustomField::new("", "x", false)).build().unwrap_err(), EntryError::EmptyFieldName);
+    }
+    
+    #[test]
+    fn test_rewrite_url_domain() {
+        assert_eq!(rewrite_url_domain("https://Old.com/login?next=old.com", "old.com", "new.com").as_deref(), Some("https://new.com/login?next=old.com"));
+        assert_eq!(rewrite_url_domain("sso.old.com/saml", "old.com", "new.io").as_deref(), Some("sso.new.io/saml"));
+        assert_eq!(rewrite_url_domain("https://bob@old.com:8443", "old.com", "new.com").as_deref(), Some("https://bob@new.com:8443"));
+        assert_eq!(rewrite_url_domain("https://bold.com", "old.com", "new.com"), None);
+        assert_eq!(rewrite_url_domain("", "old.com", "new.com"), None);
+    }
+    
+    #[test]
+    fn test_site_name() {
+        assert_eq!(site_name("https://accounts.example.com/signup").as_deref(), Some("Example"));
+        assert_eq!(site_name("shop.example.co.uk").as_deref(), Some("Example"));
+        assert_eq!(site_name("https://github.io").as_deref(), Some("Github"));
+        assert_eq!(site_name("http://192.168.1.1:8080/admin").as_deref(), Some("192.168.1.1"));
+        assert_eq!(site_name("http://localhost:3000").as_deref(), Some("localhost"));
+        assert_eq!(site_name(""), None);
+    }
 }
\ No newline at end of file
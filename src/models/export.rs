@@ -0,0 +1,209 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::io::{self, Write};
+use thiserror::Error;
+use zeroize::Zeroize;
+
+use super::database::{Database, DatabaseError};
+use super::entry::CustomField;
+use super::id::{EntryId, GroupId};
+use super::walker::WalkControl;
+
+/// Colonnes du CSV, dans l'ordre et avec les noms de l'export de KeePassXC (réimportable tel quel)
+const CSV_COLUMNS: [&str; 10] = ["Group", "Title", "Username", "Password", "URL", "Notes", "TOTP", "Icon", "Last Modified", "Created"];
+
+/// Erreurs lors d'un export en clair
+#[derive(Error, Debug)]
+pub enum ExportError {
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+    
+    #[error("Erreur d'écriture CSV: {0}")]
+    Csv(#[from] csv::Error),
+    
+    #[error("Erreur d'écriture JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    
+    #[error("Erreur d'écriture: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Format d'un export en clair
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportFormat::Csv => write!(f, "csv"),
+            ExportFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            other => Err(format!("Format d'export inconnu: '{}' (csv ou json)", other)),
+        }
+    }
+}
+
+/// Entrée exportée, avec le chemin de son groupe ("Root/Travail")
+#[derive(Debug, Serialize)]
+pub struct ExportRecord {
+    pub id: EntryId,
+    pub group: String,
+    pub title: String,
+    pub username: String,
+    
+    /// Absent d'un export sans mots de passe
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    
+    pub url: String,
+    pub notes: String,
+    
+    /// URI otpauth:// du générateur de codes (secret inclus, donc absente sans mots de passe)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub totp: Option<String>,
+    
+    /// Champs supplémentaires (sans les champs protégés dans un export sans mots de passe)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<CustomField>,
+    
+    pub tags: Vec<String>,
+    pub archived: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl Drop for ExportRecord {
+    /// Efface les secrets de la mémoire une fois l'export écrit
+    fn drop(&mut self) {
+        self.password.zeroize();
+        self.totp.zeroize();
+        for field in self.fields.iter_mut().filter(|field| field.protected) {
+            field.value.zeroize();
+        }
+    }
+}
+
+/// Date au format attendu par KeePassXC ("2024-05-01T12:00:00Z")
+fn csv_date(date: &DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+impl Database {
+    /// Entrées à exporter (hors corbeille), dans l'ordre du parcours : toute la base, ou un
+    /// groupe et sa descendance. Sans `include_passwords`, ni les mots de passe ni les secrets
+    /// des codes à usage unique ne sont repris.
+    pub fn export_records(&self, group_id: Option<&GroupId>, include_passwords: bool) -> Result<Vec<ExportRecord>, ExportError> {
+        let groups: Option<HashSet<GroupId>> = group_id
+            .map(|id| self.find_group(id).ok_or(DatabaseError::GroupNotFound(*id)))
+            .transpose()?
+            .map(|group| group.iter_groups().map(|group| group.id).collect());
+        
+        let mut records = Vec::new();
+        self.walk(|path, group, entry| {
+            let Some(entry) = entry else {
+                return WalkControl::Continue;
+            };
+            if self.is_in_trash(&entry.id) || groups.as_ref().is_some_and(|groups| !groups.contains(&group.id)) {
+                return WalkControl::Continue;
+            }
+            
+            records.push(ExportRecord {
+                id: entry.id,
+                group: path.join("/"),
+                title: entry.title.clone(),
+                username: entry.username.clone(),
+                password: include_passwords.then(|| entry.password.clone()),
+                url: entry.url.clone(),
+                notes: entry.notes.clone(),
+                totp: entry.otp.as_ref().filter(|_| include_passwords).map(|otp| otp.to_uri()),
+                fields: entry.custom_fields.iter().filter(|field| include_passwords || !field.protected).cloned().collect(),
+                tags: entry.tags.clone(),
+                archived: entry.archived,
+                created_at: entry.created_at,
+                updated_at: entry.updated_at,
+                expires_at: entry.expires_at,
+            });
+            WalkControl::Continue
+        });
+        Ok(records)
+    }
+}
+
+/// Écrit des entrées exportées dans le format demandé
+pub fn write_records(records: &[ExportRecord], format: ExportFormat, out: impl Write) -> Result<(), ExportError> {
+    match format {
+        ExportFormat::Csv => {
+            let mut writer = csv::WriterBuilder::new().quote_style(csv::QuoteStyle::Always).from_writer(out);
+            writer.write_record(CSV_COLUMNS)?;
+            for record in records {
+                writer.write_record([
+                    record.group.as_str(),
+                    &record.title,
+                    &record.username,
+                    record.password.as_deref().unwrap_or_default(),
+                    &record.url,
+                    &record.notes,
+                    record.totp.as_deref().unwrap_or_default(),
+                    "0",
+                    &csv_date(&record.updated_at),
+                    &csv_date(&record.created_at),
+                ])?;
+            }
+            writer.flush()?;
+        }
+        ExportFormat::Json => {
+            let mut out = out;
+            serde_json::to_writer_pretty(&mut out, records)?;
+            writeln!(out)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    
+    #[test]
+    fn test_export_csv_and_json() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let work = db.add_group(None, "Travail".to_string()).unwrap();
+        db.add_entry(Some(&work), Entry::builder().title("VPN").username("bob").password("s3cret").notes("ligne 1\n\"ligne 2\"").build().unwrap()).unwrap();
+        db.add_entry(None, Entry::builder().title("Forum").password("x").build().unwrap()).unwrap();
+        
+        let records = db.export_records(Some(&work), true).unwrap();
+        let mut csv = Vec::new();
+        write_records(&records, ExportFormat::Csv, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with("\"Group\",\"Title\",\"Username\",\"Password\",\"URL\",\"Notes\",\"TOTP\",\"Icon\",\"Last Modified\",\"Created\"\n"));
+        assert!(csv.contains("\"Root/Travail\",\"VPN\",\"bob\",\"s3cret\",\"\",\"ligne 1\n\"\"ligne 2\"\"\""));
+        assert!(!csv.contains("Forum"));
+        
+        let records = db.export_records(None, false).unwrap();
+        assert_eq!(records.len(), 2);
+        let mut json = Vec::new();
+        write_records(&records, ExportFormat::Json, &mut json).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert!(json.as_array().unwrap().iter().all(|record| record.get("password").is_none()));
+        
+        let missing = GroupId::new();
+        assert!(matches!(db.export_records(Some(&missing), true), Err(ExportError::Database(DatabaseError::GroupNotFound(_)))));
+    }
+}
\ No newline at end of file
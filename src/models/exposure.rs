@@ -0,0 +1,233 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+use crate::crypto::cipher::{self, EncryptedData};
+
+use super::clock;
+use super::database::Database;
+use super::device::{self, DeviceStamp};
+use super::id::EntryId;
+use super::repository::FileKey;
+
+/// Suffixe ajouté au nom de la base pour son journal des expositions (coffre.krs.exposures)
+pub const JOURNAL_SUFFIX: &str = ".exposures";
+
+/// Interface par laquelle un mot de passe a été exposé
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExposureInterface {
+    /// Ligne de commande et shell interactif
+    Cli,
+    
+    /// Interface plein écran du terminal
+    Tui,
+    
+    /// API REST de l'agent
+    Rest,
+    
+    /// Fournisseur Secret Service (D-Bus)
+    SecretService,
+    
+    /// Extension de navigateur (keeprust proxy)
+    Browser,
+}
+
+impl fmt::Display for ExposureInterface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExposureInterface::Cli => write!(f, "ligne de commande"),
+            ExposureInterface::Tui => write!(f, "interface plein écran"),
+            ExposureInterface::Rest => write!(f, "API REST"),
+            ExposureInterface::SecretService => write!(f, "Secret Service (D-Bus)"),
+            ExposureInterface::Browser => write!(f, "extension de navigateur"),
+        }
+    }
+}
+
+/// Manière dont un mot de passe a été exposé en clair
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExposureKind {
+    /// Affiché à l'écran
+    Revealed,
+    
+    /// Copié dans le presse-papiers
+    Copied,
+    
+    /// Écrit dans un export en clair
+    Exported,
+    
+    /// Tapé dans une autre fenêtre par la saisie automatique
+    Typed,
+    
+    /// Transmis à une application par le Secret Service
+    Provided,
+}
+
+impl fmt::Display for ExposureKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExposureKind::Revealed => write!(f, "affiché"),
+            ExposureKind::Copied => write!(f, "copié"),
+            ExposureKind::Exported => write!(f, "exporté"),
+            ExposureKind::Typed => write!(f, "saisi automatiquement"),
+            ExposureKind::Provided => write!(f, "transmis à une application"),
+        }
+    }
+}
+
+/// Dernière exposition en clair du mot de passe d'une entrée
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Exposure {
+    pub at: DateTime<Utc>,
+    pub kind: ExposureKind,
+    pub interface: ExposureInterface,
+    
+    /// Appareil sur lequel le mot de passe a été exposé
+    #[serde(default)]
+    pub device: Option<DeviceStamp>,
+}
+
+impl fmt::Display for Exposure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} le {} ({}", self.kind, self.at.format("%d-%m-%Y %H:%M:%S"), self.interface)?;
+        if let Some(device) = &self.device {
+            write!(f, ", {}", device)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Exposition consignée dans le journal d'une base, en attendant son prochain enregistrement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExposureRecord {
+    pub entry_id: EntryId,
+    pub exposure: Exposure,
+}
+
+impl ExposureRecord {
+    /// Exposition du mot de passe d'une entrée à l'instant présent, sur cet appareil
+    pub fn now(entry_id: EntryId, kind: ExposureKind, interface: ExposureInterface) -> Self {
+        Self { entry_id, exposure: Exposure { at: clock::now(), kind, interface, device: device::current() } }
+    }
+}
+
+/// Ligne du journal : expositions chiffrées avec la clé du fichier de la base, désignée par son sel
+#[derive(Serialize, Deserialize)]
+struct JournalLine {
+    salt: String,
+    
+    #[serde(flatten)]
+    records: EncryptedData,
+}
+
+/// Emplacement du journal des expositions d'une base (coffre.krs.exposures).
+///
+/// Afficher ou copier un mot de passe n'enregistre pas la base (ce qui remplacerait sa sauvegarde
+/// `.bak`, relancerait la dérivation de clé et la synchronisation) : l'exposition est ajoutée à ce
+/// journal, reporté à l'ouverture puis repris par le prochain enregistrement, qui le supprime.
+pub fn journal_path(vault: &Path) -> PathBuf {
+    let mut name = vault.file_name().unwrap_or_default().to_os_string();
+    name.push(JOURNAL_SUFFIX);
+    vault.with_file_name(name)
+}
+
+/// Ajoute des expositions au journal, chiffrées avec la clé du fichier actuel de la base
+pub fn append_journal(path: &Path, key: &FileKey, records: &[ExposureRecord]) -> io::Result<()> {
+    let plaintext = Zeroizing::new(serde_json::to_vec(records).map_err(io::Error::other)?);
+    let records = cipher::encrypt(&plaintext, &key.key).map_err(|e| io::Error::other(e.to_string()))?;
+    let mut line = serde_json::to_string(&JournalLine { salt: key.salt.clone(), records }).map_err(io::Error::other)?;
+    line.push('\n');
+    OpenOptions::new().create(true).append(true).open(path)?.write_all(line.as_bytes())
+}
+
+/// Expositions du journal lisibles avec la clé du fichier actuel de la base. Les lignes écrites
+/// pour une version remplacée sans reprendre le journal (synchronisation) sont ignorées.
+pub fn read_journal(path: &Path, key: &FileKey) -> io::Result<Vec<ExposureRecord>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    Ok(content.lines()
+        .filter_map(|line| serde_json::from_str::<JournalLine>(line).ok())
+        .filter(|line| line.salt == key.salt)
+        .filter_map(|line| cipher::decrypt(&line.records, &key.key).ok().map(Zeroizing::new))
+        .filter_map(|plaintext| serde_json::from_slice::<Vec<ExposureRecord>>(&plaintext).ok())
+        .flatten()
+        .collect())
+}
+
+impl Database {
+    /// Reporte des expositions sur les entrées sans changer leur date de dernière modification ni
+    /// marquer la base comme modifiée : elles sont enregistrées avec la prochaine modification.
+    /// Les entrées disparues sont ignorées, et une exposition ne remplace pas une plus récente.
+    pub fn apply_exposures(&mut self, records: &[ExposureRecord]) {
+        for record in records {
+            if let Some(entry) = self.find_entry_group_mut(&record.entry_id).and_then(|group| group.entries.get_mut(&record.entry_id))
+                && entry.last_exposure.as_ref().is_none_or(|known| known.at <= record.exposure.at)
+            {
+                entry.last_exposure = Some(record.exposure.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    
+    #[test]
+    fn test_apply_exposures_keeps_the_base_unmodified() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let id = db.add_entry(None, Entry::builder().title("Banque").password("secret").build().unwrap()).unwrap();
+        db.mark_as_saved();
+        let updated_at = db.find_entry(&id).unwrap().updated_at;
+        assert_eq!(db.find_entry(&id).unwrap().last_exposure, None);
+        
+        let copied = ExposureRecord::now(id, ExposureKind::Copied, ExposureInterface::Cli);
+        db.apply_exposures(&[copied.clone(), ExposureRecord::now(EntryId::new(), ExposureKind::Revealed, ExposureInterface::Tui)]);
+        let entry = db.find_entry(&id).unwrap();
+        let exposure = entry.last_exposure.as_ref().unwrap();
+        assert_eq!((exposure.kind, exposure.interface), (ExposureKind::Copied, ExposureInterface::Cli));
+        assert_eq!(entry.updated_at, updated_at);
+        assert!(!db.is_dirty());
+        assert!(exposure.to_string().starts_with("copié le "));
+        
+        // Une exposition plus ancienne, lue plus tard dans le journal, ne remplace pas la dernière
+        let mut older = ExposureRecord::now(id, ExposureKind::Revealed, ExposureInterface::Cli);
+        older.exposure.at = copied.exposure.at - chrono::Duration::minutes(5);
+        db.apply_exposures(&[older]);
+        assert_eq!(db.find_entry(&id).unwrap().last_exposure, Some(copied.exposure));
+    }
+    
+    #[test]
+    fn test_journal_round_trip_with_the_file_key() {
+        let directory = std::env::temp_dir().join(format!("keeprust-exposures-{}", crate::models::id::GroupId::new().short()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = journal_path(&directory.join("coffre.krs"));
+        assert_eq!(path.file_name().unwrap(), "coffre.krs.exposures");
+        
+        let key = FileKey { salt: "c2Vs".to_string(), key: Zeroizing::new([7u8; 32]) };
+        assert_eq!(read_journal(&path, &key).unwrap(), Vec::new());
+        
+        let first = ExposureRecord::now(EntryId::new(), ExposureKind::Copied, ExposureInterface::Cli);
+        let second = ExposureRecord::now(EntryId::new(), ExposureKind::Typed, ExposureInterface::Cli);
+        append_journal(&path, &key, std::slice::from_ref(&first)).unwrap();
+        append_journal(&path, &key, std::slice::from_ref(&second)).unwrap();
+        assert_eq!(read_journal(&path, &key).unwrap(), vec![first.clone(), second]);
+        assert!(!fs::read_to_string(&path).unwrap().contains(&first.entry_id.to_string()));
+        
+        // Lignes écrites pour une version précédente du fichier : ignorées
+        let replaced = FileKey { salt: "bm91dmVhdQ".to_string(), key: Zeroizing::new([8u8; 32]) };
+        assert_eq!(read_journal(&path, &replaced).unwrap(), Vec::new());
+        
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
\ No newline at end of file
@@ -1,26 +1,27 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
 use std::collections::HashMap;
+use super::clock;
 use super::entry::Entry;
+use super::id::{EntryId, GroupId};
 
 /// Représente un groupe ou dossier pour organiser les entrées
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Group {
     /// Identifiant unique pour ce groupe
-    pub id: String,
+    pub id: GroupId,
     
     /// Nom du groupe
     pub name: String,
     
     /// Entrées contenues dans ce groupe
-    pub entries: HashMap<String, Entry>,
+    pub entries: HashMap<EntryId, Entry>,
     
     /// Sous-groupes contenus dans ce groupe
-    pub subgroups: HashMap<String, Group>,
+    pub subgroups: HashMap<GroupId, Group>,
     
     /// ID du groupe parent (None pour le groupe racine)
-    pub parent_id: Option<String>,
+    pub parent_id: Option<GroupId>,
     
     /// Date et heure de création du groupe
     pub created_at: DateTime<Utc>,
@@ -31,10 +32,10 @@ pub struct Group {
 
 impl Group {
     /// Crée un nouveau groupe avec le nom spécifié
-    pub fn new(name: String, parent_id: Option<String>) -> Self {
-        let now = Utc::now();
+    pub fn new(name: String, parent_id: Option<GroupId>) -> Self {
+        let now = clock::now();
         Self {
-            id: Uuid::new_v4().to_string(),
+            id: GroupId::new(),
             name,
             entries: HashMap::new(),
             subgroups: HashMap::new(),
@@ -46,61 +47,80 @@ impl Group {
     
     /// Ajoute une entrée au groupe
     pub fn add_entry(&mut self, entry: Entry) -> &Entry {
-        let id = entry.id.clone();
-        self.entries.insert(id.clone(), entry);
-        self.updated_at = Utc::now();
+        let id = entry.id;
+        self.entries.insert(id, entry);
+        self.updated_at = clock::now();
         self.entries.get(&id).unwrap()
     }
     
     /// Ajoute un sous-groupe à ce groupe
     pub fn add_subgroup(&mut self, group: Group) -> &Group {
-        let id = group.id.clone();
-        self.subgroups.insert(id.clone(), group);
-        self.updated_at = Utc::now();
+        let id = group.id;
+        self.subgroups.insert(id, group);
+        self.updated_at = clock::now();
         self.subgroups.get(&id).unwrap()
     }
     
     /// Récupère une entrée par son ID
     #[allow(dead_code)]
-    pub fn get_entry(&self, id: &str) -> Option<&Entry> {
+    pub fn get_entry(&self, id: &EntryId) -> Option<&Entry> {
         self.entries.get(id)
     }
     
     /// Récupère une entrée mutable par son ID
     #[allow(dead_code)]
-    pub fn get_entry_mut(&mut self, id: &str) -> Option<&mut Entry> {
+    pub fn get_entry_mut(&mut self, id: &EntryId) -> Option<&mut Entry> {
         self.entries.get_mut(id)
     }
     
     /// Supprime une entrée par son ID
-    #[allow(dead_code)]
-    pub fn remove_entry(&mut self, id: &str) -> Option<Entry> {
+    pub fn remove_entry(&mut self, id: &EntryId) -> Option<Entry> {
         let result = self.entries.remove(id);
         if result.is_some() {
-            self.updated_at = Utc::now();
+            self.updated_at = clock::now();
         }
         result
     }
     
     /// Récupère un sous-groupe par son ID
     #[allow(dead_code)]
-    pub fn get_subgroup(&self, id: &str) -> Option<&Group> {
+    pub fn get_subgroup(&self, id: &GroupId) -> Option<&Group> {
         self.subgroups.get(id)
     }
     
     /// Récupère un sous-groupe mutable par son ID
     #[allow(dead_code)]
-    pub fn get_subgroup_mut(&mut self, id: &str) -> Option<&mut Group> {
+    pub fn get_subgroup_mut(&mut self, id: &GroupId) -> Option<&mut Group> {
         self.subgroups.get_mut(id)
     }
     
+    /// Retourne un itérateur sur ce groupe et tous ses sous-groupes (parcours en profondeur)
+    pub fn iter_groups(&self) -> GroupIter<'_> {
+        GroupIter { stack: vec![self] }
+    }
+    
     /// Supprime un sous-groupe par son ID
     #[allow(dead_code)]
-    pub fn remove_subgroup(&mut self, id: &str) -> Option<Group> {
+    pub fn remove_subgroup(&mut self, id: &GroupId) -> Option<Group> {
         let result = self.subgroups.remove(id);
         if result.is_some() {
-            self.updated_at = Utc::now();
+            self.updated_at = clock::now();
         }
         result
     }
+}
+
+/// Itérateur en profondeur sur un groupe et l'ensemble de ses descendants
+pub struct GroupIter<'a> {
+    stack: Vec<&'a Group>,
+}
+
+impl<'a> Iterator for GroupIter<'a> {
+    type Item = &'a Group;
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        let group = self.stack.pop()?;
+        self.stack.extend(group.subgroups.values());
+        Some(group)
+    }
 }
\ No newline at end of file
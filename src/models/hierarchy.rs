@@ -0,0 +1,167 @@
+use super::clock;
+use super::database::{Database, DatabaseError};
+use super::id::{EntryId, GroupId};
+
+impl Database {
+    /// Renomme un groupe; retourne son ancien nom
+    pub fn rename_group(&mut self, group_id: &GroupId, name: String) -> Result<String, DatabaseError> {
+        let group = self.find_group_mut(group_id).ok_or(DatabaseError::GroupNotFound(*group_id))?;
+        
+        let previous = std::mem::replace(&mut group.name, name);
+        group.updated_at = clock::now();
+        self.mark_as_modified();
+        Ok(previous)
+    }
+    
+    /// Déplace un groupe et toute sa descendance sous un autre parent (le groupe racine si aucun
+    /// ID n'est fourni); ni le groupe racine ni la corbeille ne peuvent être déplacés, et un
+    /// groupe ne peut pas l'être dans sa propre descendance ni dans la corbeille
+    pub fn move_group(&mut self, group_id: &GroupId, parent_id: Option<&GroupId>) -> Result<(), DatabaseError> {
+        let parent_id = parent_id.copied().unwrap_or(self.root_group.id);
+        let group = self.find_group(group_id).ok_or(DatabaseError::GroupNotFound(*group_id))?;
+        let current_parent = group.parent_id.ok_or(DatabaseError::RootGroup)?;
+        if self.metadata.trash_group_id == Some(*group_id) || self.is_trash_group(&parent_id) {
+            return Err(DatabaseError::TrashGroup);
+        }
+        if group.iter_groups().any(|group| group.id == parent_id) {
+            return Err(DatabaseError::GroupCycle(*group_id));
+        }
+        if self.find_group(&parent_id).is_none() {
+            return Err(DatabaseError::GroupNotFound(parent_id));
+        }
+        if current_parent == parent_id {
+            return Ok(());
+        }
+        
+        let mut group = self.find_group_mut(&current_parent)
+            .and_then(|parent| parent.remove_subgroup(group_id))
+            .ok_or(DatabaseError::GroupNotFound(*group_id))?;
+        group.parent_id = Some(parent_id);
+        self.find_group_mut(&parent_id)
+            .ok_or(DatabaseError::GroupNotFound(parent_id))?
+            .add_subgroup(group);
+        self.mark_as_modified();
+        Ok(())
+    }
+    
+    /// Supprime un groupe en confiant ses entrées et ses sous-groupes à son parent; retourne l'ID
+    /// du parent
+    pub fn dissolve_group(&mut self, group_id: &GroupId) -> Result<GroupId, DatabaseError> {
+        let parent_id = self.removable_group_parent(group_id)?;
+        let mut group = self.find_group_mut(&parent_id)
+            .and_then(|parent| parent.remove_subgroup(group_id))
+            .ok_or(DatabaseError::GroupNotFound(*group_id))?;
+        
+        let parent = self.find_group_mut(&parent_id).ok_or(DatabaseError::GroupNotFound(parent_id))?;
+        for (_, entry) in group.entries.drain() {
+            parent.add_entry(entry);
+        }
+        for (_, mut subgroup) in group.subgroups.drain() {
+            subgroup.parent_id = Some(parent_id);
+            parent.add_subgroup(subgroup);
+        }
+        self.mark_as_modified();
+        Ok(parent_id)
+    }
+    
+    /// Supprime un groupe et toute sa descendance après avoir placé ses entrées dans la corbeille
+    /// (restaurées ensuite dans le plus proche parent encore présent); retourne le nombre d'entrées
+    /// placées dans la corbeille. Rien n'est supprimé si le groupe contient une entrée verrouillée
+    pub fn delete_group(&mut self, group_id: &GroupId) -> Result<usize, DatabaseError> {
+        self.delete_group_with(group_id, false)
+    }
+    
+    /// Comme `delete_group`, y compris avec des entrées verrouillées (--unlock-entry)
+    pub fn delete_group_unlocked(&mut self, group_id: &GroupId) -> Result<usize, DatabaseError> {
+        self.delete_group_with(group_id, true)
+    }
+    
+    fn delete_group_with(&mut self, group_id: &GroupId, unlock: bool) -> Result<usize, DatabaseError> {
+        self.removable_group_parent(group_id)?;
+        let entry_ids: Vec<EntryId> = self.find_group(group_id)
+            .ok_or(DatabaseError::GroupNotFound(*group_id))?
+            .iter_groups()
+            .flat_map(|group| group.entries.keys().copied())
+            .collect();
+        
+        // Vérifier toutes les entrées avant d'en placer une seule dans la corbeille
+        for entry_id in &entry_ids {
+            self.check_unlocked(entry_id, unlock)?;
+        }
+        for entry_id in &entry_ids {
+            self.trash_entry_with(entry_id, unlock)?;
+        }
+        self.remove_group(group_id)?;
+        Ok(entry_ids.len())
+    }
+    
+    /// Parent d'un groupe qui peut être supprimé : ni le groupe racine, ni la corbeille, ni l'un
+    /// des groupes qui la contiennent
+    fn removable_group_parent(&self, group_id: &GroupId) -> Result<GroupId, DatabaseError> {
+        let group = self.find_group(group_id).ok_or(DatabaseError::GroupNotFound(*group_id))?;
+        let parent_id = group.parent_id.ok_or(DatabaseError::RootGroup)?;
+        if self.metadata.trash_group_id.is_some_and(|trash_id| group.iter_groups().any(|group| group.id == trash_id)) {
+            return Err(DatabaseError::TrashGroup);
+        }
+        Ok(parent_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    
+    #[test]
+    fn test_group_rename_move_and_delete() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let work = db.add_group(None, "Work".to_string()).unwrap();
+        let servers = db.add_group(Some(&work), "Servers".to_string()).unwrap();
+        let ssh = db.add_entry(Some(&servers), Entry::new("ssh".to_string(), "root".to_string(), "pw".to_string(), String::new(), String::new())).unwrap();
+        let vpn = db.add_entry(Some(&work), Entry::new("VPN".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new())).unwrap();
+        let root = db.root_group.id;
+        
+        assert_eq!(db.rename_group(&servers, "Serveurs".to_string()).unwrap(), "Servers");
+        assert_eq!(db.find_group_by_path("Work/Serveurs").unwrap().id, servers);
+        
+        // Déplacement : parent_id suit, pas de cycle ni de déplacement du groupe racine
+        assert_eq!(db.move_group(&work, Some(&servers)).unwrap_err(), DatabaseError::GroupCycle(work));
+        assert_eq!(db.move_group(&root, None).unwrap_err(), DatabaseError::RootGroup);
+        db.move_group(&servers, None).unwrap();
+        assert_eq!(db.find_group(&servers).unwrap().parent_id, Some(root));
+        assert_eq!(db.find_entry(&ssh).unwrap().title, "ssh");
+        
+        // Dissolution : le contenu remonte au parent
+        db.move_group(&servers, Some(&work)).unwrap();
+        assert_eq!(db.dissolve_group(&work).unwrap(), root);
+        assert!(db.find_group(&work).is_none());
+        assert!(db.root_group.entries.contains_key(&vpn));
+        assert_eq!(db.find_group(&servers).unwrap().parent_id, Some(root));
+        
+        // Suppression : les entrées passent par la corbeille, qui ne peut être ni déplacée ni supprimée
+        assert_eq!(db.delete_group(&servers).unwrap(), 1);
+        assert!(db.is_in_trash(&ssh));
+        assert_eq!(db.restore_entry(&ssh).unwrap(), root);
+        let trash = db.metadata.trash_group_id.unwrap();
+        assert_eq!(db.delete_group(&trash).unwrap_err(), DatabaseError::TrashGroup);
+        assert_eq!(db.move_group(&trash, None).unwrap_err(), DatabaseError::TrashGroup);
+        let archives = db.add_group(None, "Archives".to_string()).unwrap();
+        assert_eq!(db.move_group(&archives, Some(&trash)).unwrap_err(), DatabaseError::TrashGroup);
+    }    
+    #[test]
+    fn test_delete_group_keeps_everything_with_a_locked_entry() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let work = db.add_group(None, "Work".to_string()).unwrap();
+        let servers = db.add_group(Some(&work), "Servers".to_string()).unwrap();
+        let vpn = db.add_entry(Some(&work), Entry::new("VPN".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new())).unwrap();
+        let root = db.add_entry(Some(&servers), Entry::new("root".to_string(), "root".to_string(), "pw".to_string(), String::new(), String::new())).unwrap();
+        db.set_locked(&root, true).unwrap();
+        
+        assert_eq!(db.delete_group(&work).unwrap_err(), DatabaseError::EntryLocked("root".to_string()));
+        assert!(!db.is_in_trash(&vpn));
+        assert!(db.find_group(&servers).is_some());
+        
+        assert_eq!(db.delete_group_unlocked(&work).unwrap(), 2);
+        assert!(db.is_in_trash(&root));
+    }
+}
@@ -0,0 +1,94 @@
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Erreur levée lorsqu'un identifiant n'est pas un UUID valide
+#[derive(Error, Debug, PartialEq)]
+#[error("Identifiant invalide: '{0}'")]
+pub struct InvalidIdError(pub String);
+
+/// Définit un identifiant typé basé sur un UUID, sérialisé comme une simple chaîne
+macro_rules! define_id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(Uuid);
+        
+        impl $name {
+            /// Génère un nouvel identifiant aléatoire
+            pub fn new() -> Self {
+                Self(Uuid::new_v4())
+            }
+            
+            /// Génère un identifiant à partir du générateur aléatoire fourni (reproductible avec une graine)
+            #[allow(dead_code)]
+            pub fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+                let mut bytes = [0u8; 16];
+                rng.fill_bytes(&mut bytes);
+                Self(uuid::Builder::from_random_bytes(bytes).into_uuid())
+            }
+            
+            /// Retourne la forme courte de l'identifiant (8 premiers caractères)
+            #[allow(dead_code)]
+            pub fn short(&self) -> String {
+                self.0.simple().to_string()[..8].to_string()
+            }
+        }
+        
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+        
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+        
+        impl FromStr for $name {
+            type Err = InvalidIdError;
+            
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Uuid::parse_str(s.trim())
+                    .map(Self)
+                    .map_err(|_| InvalidIdError(s.to_string()))
+            }
+        }
+    };
+}
+
+define_id!(
+    /// Identifiant unique d'une entrée
+    EntryId
+);
+
+define_id!(
+    /// Identifiant unique d'un groupe
+    GroupId
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_id_round_trip_as_plain_string() {
+        let id = EntryId::new();
+        let json = serde_json::to_string(&id).unwrap();
+        
+        assert_eq!(json, format!("\"{}\"", id));
+        assert_eq!(serde_json::from_str::<EntryId>(&json).unwrap(), id);
+        assert_eq!(id.to_string().parse::<EntryId>().unwrap(), id);
+    }
+    
+    #[test]
+    fn test_invalid_id_is_rejected() {
+        assert_eq!("abc".parse::<GroupId>(), Err(InvalidIdError("abc".to_string())));
+    }
+}
@@ -0,0 +1,354 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use thiserror::Error;
+use zeroize::Zeroize;
+
+use crate::crypto::otp::OtpConfig;
+
+use super::database::{Database, DatabaseError};
+use super::entry::Entry;
+use super::id::GroupId;
+use super::limits::SizeLimits;
+
+/// Erreurs lors d'un import
+#[derive(Error, Debug)]
+pub enum ImportError {
+    #[error("Fichier d'export invalide: {0}")]
+    InvalidFile(String),
+    
+    #[error("Cet export Bitwarden est chiffré; exportez le coffre au format « .json » non chiffré")]
+    EncryptedExport,
+    
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+}
+
+/// Format d'un fichier importé
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// Export JSON non chiffré de Bitwarden
+    Bitwarden,
+}
+
+impl fmt::Display for ImportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportFormat::Bitwarden => write!(f, "bitwarden"),
+        }
+    }
+}
+
+impl std::str::FromStr for ImportFormat {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "bitwarden" => Ok(ImportFormat::Bitwarden),
+            other => Err(format!("Format d'import inconnu: '{}' (bitwarden)", other)),
+        }
+    }
+}
+
+/// Enregistrement ignoré lors d'un import, avec la raison
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedRecord {
+    pub name: String,
+    pub reason: String,
+}
+
+/// Bilan d'un import
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub groups_created: usize,
+    pub imported: usize,
+    pub skipped: Vec<SkippedRecord>,
+    
+    /// Entrées importées dont une partie n'a pas pu être reprise (code à usage unique illisible...)
+    pub warnings: Vec<String>,
+}
+
+/// Export JSON de Bitwarden (seuls les champs repris sont lus)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BitwardenExport {
+    #[serde(default)]
+    encrypted: bool,
+    
+    #[serde(default)]
+    folders: Vec<BitwardenFolder>,
+    
+    #[serde(default)]
+    items: Vec<BitwardenItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitwardenFolder {
+    id: String,
+    name: String,
+}
+
+/// Élément du coffre Bitwarden : 1 = identifiant, 2 = note sécurisée, 3 = carte, 4 = identité
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BitwardenItem {
+    #[serde(rename = "type")]
+    kind: u8,
+    
+    #[serde(default)]
+    folder_id: Option<String>,
+    
+    #[serde(default)]
+    name: String,
+    
+    #[serde(default)]
+    notes: Option<String>,
+    
+    #[serde(default)]
+    login: Option<BitwardenLogin>,
+    
+    #[serde(default)]
+    fields: Vec<BitwardenField>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitwardenLogin {
+    #[serde(default)]
+    uris: Option<Vec<BitwardenUri>>,
+    
+    #[serde(default)]
+    username: Option<String>,
+    
+    #[serde(default)]
+    password: Option<String>,
+    
+    #[serde(default)]
+    totp: Option<String>,
+}
+
+impl Drop for BitwardenLogin {
+    /// Efface les secrets lus dans l'export
+    fn drop(&mut self) {
+        self.password.zeroize();
+        self.totp.zeroize();
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct BitwardenUri {
+    #[serde(default)]
+    uri: Option<String>,
+}
+
+/// Champ personnalisé d'un élément; repris dans les notes de l'entrée
+#[derive(Debug, Deserialize)]
+struct BitwardenField {
+    #[serde(default)]
+    name: Option<String>,
+    
+    #[serde(default)]
+    value: Option<String>,
+}
+
+impl Drop for BitwardenField {
+    /// Les champs masqués contiennent souvent des secrets
+    fn drop(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+const BITWARDEN_LOGIN: u8 = 1;
+const BITWARDEN_SECURE_NOTE: u8 = 2;
+
+/// Lit un code à usage unique Bitwarden : URI otpauth://, « steam://SECRET » ou secret base32 seul
+fn parse_bitwarden_totp(totp: &str, issuer: &str, account: &str) -> Option<OtpConfig> {
+    let totp = totp.trim();
+    let mut config = if totp.starts_with("otpauth://") {
+        OtpConfig::from_uri(totp).ok()?
+    } else {
+        let (secret, encoder) = match totp.strip_prefix("steam://") {
+            Some(secret) => (secret, "&encoder=steam"),
+            None => (totp, ""),
+        };
+        let secret: String = secret.chars().filter(|c| !c.is_whitespace()).collect();
+        OtpConfig::from_uri(&format!("otpauth://totp/?secret={}{}", secret, encoder)).ok()?
+    };
+    
+    config.issuer.get_or_insert_with(|| issuer.to_string());
+    if config.account.is_none() && !account.is_empty() {
+        config.account = Some(account.to_string());
+    }
+    Some(config)
+}
+
+/// Notes de l'entrée : notes Bitwarden, puis URI supplémentaires et champs personnalisés
+fn bitwarden_notes(item: &BitwardenItem, extra_uris: &[&str]) -> String {
+    let mut notes = item.notes.clone().unwrap_or_default();
+    let mut append = |line: String| {
+        if !notes.is_empty() {
+            notes.push('\n');
+        }
+        notes.push_str(&line);
+    };
+    
+    for uri in extra_uris {
+        append(format!("URL: {}", uri));
+    }
+    for field in &item.fields {
+        append(format!("{}: {}", field.name.as_deref().unwrap_or_default(), field.value.as_deref().unwrap_or_default()));
+    }
+    notes
+}
+
+impl Database {
+    /// Importe un fichier exporté par un autre gestionnaire de mots de passe, sous un groupe
+    /// (le groupe racine si aucun ID n'est fourni)
+    pub fn import(&mut self, format: ImportFormat, content: &str, parent_id: Option<&GroupId>) -> Result<ImportReport, ImportError> {
+        match format {
+            ImportFormat::Bitwarden => self.import_bitwarden(content, parent_id),
+        }
+    }
+    
+    /// Groupe correspondant à un chemin de dossier ("Travail/Serveurs"), créé s'il n'existe pas
+    fn group_for_path(&mut self, parent_id: GroupId, path: &str, report: &mut ImportReport) -> Result<GroupId, DatabaseError> {
+        let mut current = parent_id;
+        for name in path.split('/').map(str::trim).filter(|name| !name.is_empty()) {
+            let existing = self.find_group(&current)
+                .ok_or(DatabaseError::GroupNotFound(current))?
+                .subgroups.values()
+                .find(|group| group.name == name)
+                .map(|group| group.id);
+            current = match existing {
+                Some(id) => id,
+                None => {
+                    report.groups_created += 1;
+                    self.add_group(Some(&current), name.to_string())?
+                }
+            };
+        }
+        Ok(current)
+    }
+    
+    /// Importe l'export JSON non chiffré de Bitwarden : les dossiers deviennent des groupes
+    /// (« A/B » donne des groupes imbriqués), les identifiants et notes sécurisées des entrées.
+    ///
+    /// Les cartes et identités, sans équivalent ici, sont ignorées et signalées dans le bilan.
+    fn import_bitwarden(&mut self, content: &str, parent_id: Option<&GroupId>) -> Result<ImportReport, ImportError> {
+        let export: BitwardenExport = serde_json::from_str(content).map_err(|e| ImportError::InvalidFile(e.to_string()))?;
+        if export.encrypted {
+            return Err(ImportError::EncryptedExport);
+        }
+        
+        let parent_id = parent_id.copied().unwrap_or(self.root_group.id);
+        if self.find_group(&parent_id).is_none() {
+            return Err(DatabaseError::GroupNotFound(parent_id).into());
+        }
+        
+        let mut report = ImportReport::default();
+        let mut folders = HashMap::new();
+        for folder in &export.folders {
+            let group_id = self.group_for_path(parent_id, &folder.name, &mut report)?;
+            folders.insert(folder.id.as_str(), group_id);
+        }
+        
+        for item in &export.items {
+            let name = item.name.trim();
+            let skip = |reason: &str| SkippedRecord { name: name.to_string(), reason: reason.to_string() };
+            if !matches!(item.kind, BITWARDEN_LOGIN | BITWARDEN_SECURE_NOTE) {
+                let kind = match item.kind {
+                    3 => "carte bancaire",
+                    4 => "identité",
+                    _ => "type d'élément",
+                };
+                report.skipped.push(skip(&format!("{} non prise en charge", kind)));
+                continue;
+            }
+            
+            let login = item.login.as_ref();
+            let uris: Vec<&str> = login.and_then(|login| login.uris.as_ref())
+                .map(|uris| uris.iter().filter_map(|uri| uri.uri.as_deref()).map(str::trim).filter(|uri| !uri.is_empty()).collect())
+                .unwrap_or_default();
+            let username = login.and_then(|login| login.username.as_deref()).unwrap_or_default();
+            
+            let mut builder = Entry::builder()
+                .title(name)
+                .username(username)
+                .password(login.and_then(|login| login.password.as_deref()).unwrap_or_default())
+                .notes(bitwarden_notes(item, uris.get(1..).unwrap_or_default()));
+            if let Some(url) = uris.first() {
+                builder = builder.url(*url);
+            }
+            let mut entry = match builder.build() {
+                Ok(entry) => entry,
+                Err(e) => {
+                    report.skipped.push(skip(&e.to_string()));
+                    continue;
+                }
+            };
+            
+            if let Some(totp) = login.and_then(|login| login.totp.as_deref()).filter(|totp| !totp.trim().is_empty()) {
+                match parse_bitwarden_totp(totp, name, username) {
+                    Some(config) => entry.otp = Some(config),
+                    None => report.warnings.push(format!("{}: code à usage unique illisible, non importé", name)),
+                }
+            }
+            
+            // Une valeur au-delà des plafonds empêcherait d'enregistrer la base
+            if let Some(oversized) = entry.oversized_values(SizeLimits::HARD).first() {
+                report.skipped.push(skip(&format!("{} de {} octets, trop volumineux", oversized.value, oversized.size)));
+                continue;
+            }
+            report.warnings.extend(entry.oversized_values(SizeLimits::default()).iter().map(|oversized| {
+                format!("{}: {} de {} octets, à placer dans une pièce jointe", name, oversized.value, oversized.size)
+            }));
+            
+            let group_id = item.folder_id.as_deref().and_then(|id| folders.get(id)).copied().unwrap_or(parent_id);
+            self.add_entry(Some(&group_id), entry)?;
+            report.imported += 1;
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const EXPORT: &str = r#"{
+        "encrypted": false,
+        "folders": [{ "id": "f1", "name": "Travail/Serveurs" }],
+        "items": [
+            {
+                "id": "i1", "folderId": "f1", "type": 1, "name": "VPN", "notes": null, "favorite": false,
+                "login": {
+                    "uris": [{ "match": null, "uri": "https://vpn.example.com" }, { "match": null, "uri": "https://backup.example.com" }],
+                    "username": "bob", "password": "s3cret", "totp": "JBSWY3DPEHPK3PXP"
+                },
+                "fields": [{ "name": "PIN", "value": "1234", "type": 1 }]
+            },
+            { "id": "i2", "folderId": null, "type": 2, "name": "Wifi", "notes": "clé: abcd", "secureNote": { "type": 0 } },
+            { "id": "i3", "folderId": null, "type": 3, "name": "Visa", "card": { "number": "4111" } },
+            { "id": "i4", "folderId": null, "type": 1, "name": "Forum", "login": { "totp": "pas un secret !" } }
+        ]
+    }"#;
+    
+    #[test]
+    fn test_import_bitwarden() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let report = db.import(ImportFormat::Bitwarden, EXPORT, None).unwrap();
+        assert_eq!((report.groups_created, report.imported), (2, 3));
+        assert_eq!(report.skipped, vec![SkippedRecord { name: "Visa".to_string(), reason: "carte bancaire non prise en charge".to_string() }]);
+        assert_eq!(report.warnings.len(), 1);
+        
+        let vpn = db.iter_entries().find(|entry| entry.title == "VPN").unwrap();
+        assert_eq!((vpn.username.as_str(), vpn.password.as_str(), vpn.url.as_str()), ("bob", "s3cret", "https://vpn.example.com"));
+        assert_eq!(vpn.notes, "URL: https://backup.example.com\nPIN: 1234");
+        assert_eq!(vpn.otp.as_ref().unwrap().issuer.as_deref(), Some("VPN"));
+        let servers = db.iter_groups().find(|group| group.name == "Serveurs").unwrap();
+        assert!(servers.entries.contains_key(&vpn.id));
+        
+        // Un second import réutilise les groupes existants
+        assert_eq!(db.import(ImportFormat::Bitwarden, EXPORT, None).unwrap().groups_created, 0);
+        assert!(matches!(db.import(ImportFormat::Bitwarden, r#"{"encrypted": true}"#, None), Err(ImportError::EncryptedExport)));
+    }
+}
\ No newline at end of file
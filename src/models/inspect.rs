@@ -0,0 +1,430 @@
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use thiserror::Error;
+
+use crate::crypto::cipher::NONCE_LEN;
+use crate::crypto::key_derivation::KdfParams;
+use crate::utils::symbols::Symbol;
+
+use super::repository::{FILE_HEADER, FILE_VERSION, FILE_VERSION_V1, FILE_VERSION_V4, FileHeader, MIN_ITERATIONS, PayloadFormat};
+
+/// Taille maximale d'un fichier inspecté; au-delà, il n'est pas lu
+pub const MAX_INSPECT_FILE_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Taille maximale d'un champ d'en-tête KDBX (les champs légitimes font quelques centaines d'octets)
+const MAX_KDBX_FIELD_LEN: usize = 64 * 1024;
+
+/// Taille maximale de l'en-tête KDBX complet
+const MAX_KDBX_HEADER_LEN: usize = 1024 * 1024;
+/// Mémoire de dérivation (Argon2, scrypt) au-delà de laquelle l'ouverture est jugée dangereuse, en Kio (4 Gio)
+const MAX_SANE_KDF_MEMORY_KIB: u64 = 4 * 1024 * 1024;
+
+/// Nombre d'itérations au-delà duquel l'ouverture bloquerait pendant des minutes
+const MAX_SANE_ITERATIONS: u64 = 100_000_000;
+
+/// Signatures des fichiers KeePass (entiers de 4 octets en little-endian)
+const KDBX_SIGNATURE_1: u32 = 0x9AA2_D903;
+const KDBX_SIGNATURE_2: u32 = 0xB54B_FB67;
+const KDB_SIGNATURE_2: u32 = 0xB54B_FB66;
+
+/// Erreurs d'inspection d'un fichier
+#[derive(Error, Debug, PartialEq)]
+pub enum InspectError {
+    #[error("Impossible de lire le fichier: {0}")]
+    Io(String),
+    
+    #[error("Fichier trop volumineux pour être inspecté ({size} octets, {max} au plus)")]
+    TooLarge { size: u64, max: u64 },
+    
+    #[error("Format non reconnu (ni keeprust ni KeePass)")]
+    UnknownFormat,
+    
+    #[error("Fichier tronqué ({0})")]
+    Truncated(&'static str),
+    
+    #[error("Champ d'en-tête trop long ({0} octets)")]
+    FieldTooLarge(usize),
+    
+    #[error("En-tête invalide: {0}")]
+    InvalidHeader(String),
+}
+
+/// Lecture bornée d'un contenu non fiable : toute longueur annoncée est comparée à ce qui reste
+/// avant d'être utilisée, rien n'est alloué d'après le fichier
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize, what: &'static str) -> Result<&'a [u8], InspectError> {
+        if len > self.bytes.len() {
+            return Err(InspectError::Truncated(what));
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+    
+    fn array<const N: usize>(&mut self, what: &'static str) -> Result<[u8; N], InspectError> {
+        Ok(self.take(N, what)?.try_into().expect("longueur vérifiée"))
+    }
+}
+
+/// Fichier keeprust (.krs), décrit sans déchiffrement
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeeprustReport {
+    pub version: u8,
+    pub format: PayloadFormat,
+    pub kdf: KdfParams,
+    pub salt_len: usize,
+    
+    /// Nombre de blocs chiffrés annoncé par le fichier (aucun pour la version 1, bloc unique)
+    pub announced_chunks: Option<u32>,
+    
+    /// Blocs complets effectivement présents
+    pub chunks: u32,
+    pub ciphertext_bytes: u64,
+    
+    /// Octets inattendus après le dernier bloc
+    pub trailing_bytes: usize,
+}
+
+/// Fichier KeePass (KDBX), décrit à partir de son en-tête externe
+#[derive(Debug, Clone, PartialEq)]
+pub struct KdbxReport {
+    pub major: u16,
+    pub minor: u16,
+    pub cipher: Option<String>,
+    pub compression: Option<String>,
+    pub kdf: Option<String>,
+    pub header_len: usize,
+    
+    /// Paramètres dont l'ouverture serait dangereuse (mémoire, durée)
+    pub warnings: Vec<String>,
+}
+
+/// Résultat de l'inspection d'un fichier
+#[derive(Debug, Clone, PartialEq)]
+pub enum Inspection {
+    Keeprust(KeeprustReport),
+    Kdbx(KdbxReport),
+    
+    /// Base KeePass 1.x (KDB), dont seule la signature est reconnue
+    Kdb,
+}
+
+impl KeeprustReport {
+    /// Paramètres dont l'ouverture serait dangereuse ou la protection insuffisante
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        match self.kdf {
+            KdfParams::Argon2id { memory_kib, iterations, .. } => {
+                if u64::from(memory_kib) > MAX_SANE_KDF_MEMORY_KIB {
+                    warnings.push(format!("l'ouverture réserverait {} Mio de mémoire (Argon2id)", memory_kib / 1024));
+                }
+                if u64::from(iterations) > MAX_SANE_ITERATIONS {
+                    warnings.push(format!("{} passes Argon2id: l'ouverture prendrait des heures", iterations));
+                }
+            }
+            KdfParams::Pbkdf2 { iterations } if u64::from(iterations) > MAX_SANE_ITERATIONS => {
+                warnings.push(format!("{} itérations PBKDF2: l'ouverture prendrait des minutes", iterations));
+            }
+            KdfParams::Pbkdf2 { .. } => {}
+            KdfParams::Scrypt { log_n, block_size, parallelism } => {
+                // Mémoire réservée : 128 × r × N octets
+                let memory_kib = 1u128.checked_shl(log_n).unwrap_or(u128::MAX).saturating_mul(u128::from(block_size)) / 8;
+                if memory_kib > u128::from(MAX_SANE_KDF_MEMORY_KIB) {
+                    warnings.push(format!("l'ouverture réserverait {} Mio de mémoire (scrypt)", memory_kib / 1024));
+                }
+                if u64::from(parallelism) > MAX_SANE_ITERATIONS {
+                    warnings.push(format!("parallélisme scrypt de {}: l'ouverture prendrait des heures", parallelism));
+                }
+            }
+        }
+        if self.kdf.is_weak(MIN_ITERATIONS) {
+            warnings.push(format!("dérivation de clé faible ({})", self.kdf));
+        }
+        if self.announced_chunks.is_some_and(|announced| announced != self.chunks) {
+            warnings.push(format!("{} bloc(s) annoncé(s), {} présent(s)", self.announced_chunks.unwrap_or_default(), self.chunks));
+        }
+        if self.trailing_bytes > 0 {
+            warnings.push(format!("{} octet(s) inattendu(s) en fin de fichier", self.trailing_bytes));
+        }
+        warnings
+    }
+}
+
+impl fmt::Display for Inspection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Inspection::Keeprust(report) => {
+                writeln!(f, "Format: keeprust, version {}", report.version)?;
+                writeln!(f, "Données en clair: {}", report.format)?;
+                writeln!(f, "Dérivation de clé: {}", report.kdf)?;
+                writeln!(f, "Sel: {} octets", report.salt_len)?;
+                match report.announced_chunks {
+                    Some(announced) => writeln!(f, "Blocs chiffrés: {} présent(s) sur {} annoncé(s), {} octets", report.chunks, announced, report.ciphertext_bytes)?,
+                    None => writeln!(f, "Bloc chiffré unique: {} octets", report.ciphertext_bytes)?,
+                }
+                write_warnings(f, &report.warnings())
+            }
+            Inspection::Kdbx(report) => {
+                writeln!(f, "Format: KeePass KDBX {}.{}", report.major, report.minor)?;
+                writeln!(f, "Chiffrement: {}", report.cipher.as_deref().unwrap_or("non indiqué"))?;
+                writeln!(f, "Compression: {}", report.compression.as_deref().unwrap_or("non indiquée"))?;
+                writeln!(f, "Dérivation de clé: {}", report.kdf.as_deref().unwrap_or("non indiquée"))?;
+                writeln!(f, "En-tête: {} octets", report.header_len)?;
+                write_warnings(f, &report.warnings)
+            }
+            Inspection::Kdb => writeln!(f, "Format: KeePass 1.x (KDB), non pris en charge"),
+        }
+    }
+}
+
+fn write_warnings(f: &mut fmt::Formatter<'_>, warnings: &[String]) -> fmt::Result {
+    for warning in warnings {
+        writeln!(f, "{}{}", Symbol::Warning, warning)?;
+    }
+    Ok(())
+}
+
+/// Lit un fichier d'au plus `MAX_INSPECT_FILE_SIZE` octets et l'inspecte; retourne sa taille et
+/// sa description
+pub fn inspect_file(path: &Path) -> Result<(u64, Inspection), InspectError> {
+    let file = File::open(path).map_err(|e| InspectError::Io(e.to_string()))?;
+    let size = file.metadata().map_err(|e| InspectError::Io(e.to_string()))?.len();
+    if size > MAX_INSPECT_FILE_SIZE {
+        return Err(InspectError::TooLarge { size, max: MAX_INSPECT_FILE_SIZE });
+    }
+    
+    // La taille peut changer entre-temps : la lecture reste bornée
+    let mut bytes = Vec::new();
+    file.take(MAX_INSPECT_FILE_SIZE + 1).read_to_end(&mut bytes).map_err(|e| InspectError::Io(e.to_string()))?;
+    if bytes.len() as u64 > MAX_INSPECT_FILE_SIZE {
+        return Err(InspectError::TooLarge { size: bytes.len() as u64, max: MAX_INSPECT_FILE_SIZE });
+    }
+    Ok((bytes.len() as u64, inspect(&bytes)?))
+}
+
+/// Décrit un fichier keeprust ou KeePass sans le déchiffrer ni matérialiser son contenu
+pub fn inspect(bytes: &[u8]) -> Result<Inspection, InspectError> {
+    if bytes.starts_with(FILE_HEADER) {
+        return inspect_keeprust(bytes).map(Inspection::Keeprust);
+    }
+    
+    let mut reader = Reader { bytes };
+    let signature_1 = u32::from_le_bytes(reader.array("signature").map_err(|_| InspectError::UnknownFormat)?);
+    let signature_2 = u32::from_le_bytes(reader.array("signature").map_err(|_| InspectError::UnknownFormat)?);
+    match (signature_1, signature_2) {
+        (KDBX_SIGNATURE_1, KDBX_SIGNATURE_2) => inspect_kdbx(reader).map(Inspection::Kdbx),
+        (KDBX_SIGNATURE_1, KDB_SIGNATURE_2) => Ok(Inspection::Kdb),
+        _ => Err(InspectError::UnknownFormat),
+    }
+}
+
+fn inspect_keeprust(bytes: &[u8]) -> Result<KeeprustReport, InspectError> {
+    let mut reader = Reader { bytes };
+    reader.take(FILE_HEADER.len(), "signature")?;
+    let [version] = reader.array("version")?;
+    if !(FILE_VERSION_V1..=FILE_VERSION).contains(&version) {
+        return Err(InspectError::InvalidHeader(format!("version {} inconnue", version)));
+    }
+    
+    let header_len = u16::from_be_bytes(reader.array("en-tête")?) as usize;
+    let header_bytes = reader.take(header_len, "en-tête")?;
+    let header = if version >= FILE_VERSION_V4 {
+        FileHeader::from_binary(header_bytes).map_err(|e| InspectError::InvalidHeader(e.to_string()))?
+    } else {
+        serde_json::from_slice::<FileHeader>(header_bytes).map_err(|e| InspectError::InvalidHeader(e.to_string()))?
+    };
+    let kdf = header.kdf_params().map_err(|e| InspectError::InvalidHeader(e.to_string()))?;
+    // Sel en base64 : 4 caractères pour 3 octets
+    let salt_len = header.salt.trim_end_matches('=').len() * 3 / 4;
+    
+    let mut report = KeeprustReport {
+        version,
+        format: header.format,
+        kdf,
+        salt_len,
+        announced_chunks: None,
+        chunks: 0,
+        ciphertext_bytes: 0,
+        trailing_bytes: 0,
+    };
+    if version == FILE_VERSION_V1 {
+        // Bloc unique encodé en JSON : mesuré sans être analysé
+        report.ciphertext_bytes = reader.bytes.len() as u64;
+        return Ok(report);
+    }
+    
+    let announced = u32::from_be_bytes(reader.array("bloc chiffré")?);
+    report.announced_chunks = Some(announced);
+    // Chaque bloc est sauté sans être copié; le nombre annoncé ne borne que la boucle
+    while report.chunks < announced {
+        let chunk = reader.take(NONCE_LEN, "bloc chiffré")
+            .and_then(|_| reader.array::<4>("bloc chiffré"))
+            .and_then(|len| reader.take(u32::from_be_bytes(len) as usize, "bloc chiffré"));
+        match chunk {
+            Ok(ciphertext) => {
+                report.chunks += 1;
+                report.ciphertext_bytes += ciphertext.len() as u64;
+            }
+            Err(_) => break,
+        }
+    }
+    if report.chunks == announced {
+        report.trailing_bytes = reader.bytes.len();
+    }
+    Ok(report)
+}
+
+/// Nom d'un algorithme KeePass d'après son UUID
+fn kdbx_algorithm(uuid: &[u8]) -> String {
+    const KNOWN: [(&str, [u8; 16]); 6] = [
+        ("AES-256", [0x31, 0xc1, 0xf2, 0xe6, 0xbf, 0x71, 0x43, 0x50, 0xbe, 0x58, 0x05, 0x21, 0x6a, 0xfc, 0x5a, 0xff]),
+        ("ChaCha20", [0xd6, 0x03, 0x8a, 0x2b, 0x8b, 0x6f, 0x4c, 0xb5, 0xa5, 0x24, 0x33, 0x9a, 0x31, 0xdb, 0xb5, 0x9a]),
+        ("Twofish", [0xad, 0x68, 0xf2, 0x9f, 0x57, 0x6f, 0x4b, 0xb9, 0xa3, 0x6a, 0xd4, 0x7a, 0xf9, 0x65, 0x34, 0x6c]),
+        ("AES-KDF", [0xc9, 0xd9, 0xf3, 0x9a, 0x62, 0x8a, 0x44, 0x60, 0xbf, 0x74, 0x0d, 0x08, 0xc1, 0x8a, 0x4f, 0xea]),
+        ("Argon2d", [0xef, 0x63, 0x6d, 0xdf, 0x8c, 0x29, 0x44, 0x4b, 0x91, 0xf7, 0xa9, 0xa4, 0x03, 0xe3, 0x0a, 0x0c]),
+        ("Argon2id", [0x9e, 0x29, 0x8b, 0x19, 0x56, 0xdb, 0x47, 0x73, 0xb2, 0x3d, 0xfc, 0x3e, 0xc6, 0xf0, 0xa1, 0xe6]),
+    ];
+    KNOWN.iter()
+        .find(|(_, known)| known == uuid)
+        .map(|(name, _)| name.to_string())
+        .unwrap_or_else(|| "inconnu".to_string())
+}
+
+/// Identifiants des champs de l'en-tête externe KDBX
+const KDBX_END_OF_HEADER: u8 = 0;
+const KDBX_CIPHER_ID: u8 = 2;
+const KDBX_COMPRESSION: u8 = 3;
+const KDBX_TRANSFORM_ROUNDS: u8 = 6;
+const KDBX_KDF_PARAMETERS: u8 = 11;
+
+fn inspect_kdbx(mut reader: Reader<'_>) -> Result<KdbxReport, InspectError> {
+    let start = reader.bytes.len();
+    let minor = u16::from_le_bytes(reader.array("version")?);
+    let major = u16::from_le_bytes(reader.array("version")?);
+    let mut report = KdbxReport { major, minor, cipher: None, compression: None, kdf: None, header_len: 0, warnings: Vec::new() };
+    
+    loop {
+        let [id] = reader.array("en-tête")?;
+        let len = if major >= 4 {
+            u32::from_le_bytes(reader.array("en-tête")?) as usize
+        } else {
+            u16::from_le_bytes(reader.array("en-tête")?) as usize
+        };
+        if len > MAX_KDBX_FIELD_LEN {
+            return Err(InspectError::FieldTooLarge(len));
+        }
+        let data = reader.take(len, "en-tête")?;
+        if start - reader.bytes.len() > MAX_KDBX_HEADER_LEN {
+            return Err(InspectError::FieldTooLarge(start - reader.bytes.len()));
+        }
+        
+        match id {
+            KDBX_END_OF_HEADER => break,
+            KDBX_CIPHER_ID => report.cipher = Some(kdbx_algorithm(data)),
+            KDBX_COMPRESSION => report.compression = Some(match data {
+                [0, 0, 0, 0] => "aucune".to_string(),
+                [1, 0, 0, 0] => "gzip".to_string(),
+                _ => "inconnue".to_string(),
+            }),
+            KDBX_TRANSFORM_ROUNDS => {
+                let rounds = data.try_into().map(u64::from_le_bytes).map_err(|_| InspectError::InvalidHeader("TransformRounds".to_string()))?;
+                report.kdf = Some(format!("AES-KDF, {} tours", rounds));
+                if rounds > MAX_SANE_ITERATIONS {
+                    report.warnings.push(format!("{} tours AES-KDF: l'ouverture prendrait des minutes", rounds));
+                }
+            }
+            KDBX_KDF_PARAMETERS => report.kdf = Some(kdbx_kdf(data, &mut report.warnings)?),
+            _ => {}
+        }
+    }
+    
+    report.header_len = start - reader.bytes.len() + 8;
+    Ok(report)
+}
+
+/// Décrit les paramètres de dérivation KDBX 4 (dictionnaire de variantes : type, clé et valeur)
+fn kdbx_kdf(data: &[u8], warnings: &mut Vec<String>) -> Result<String, InspectError> {
+    let mut reader = Reader { bytes: data };
+    reader.take(2, "en-tête")?;
+    
+    let (mut algorithm, mut details) = (None, Vec::new());
+    loop {
+        let [kind] = reader.array("en-tête")?;
+        if kind == 0 {
+            break;
+        }
+        let key_len = u32::from_le_bytes(reader.array("en-tête")?) as usize;
+        let key = reader.take(key_len, "en-tête")?;
+        let value_len = u32::from_le_bytes(reader.array("en-tête")?) as usize;
+        let value = reader.take(value_len, "en-tête")?;
+        
+        let number = match value.len() {
+            4 => value.try_into().ok().map(|bytes| u64::from(u32::from_le_bytes(bytes))),
+            8 => value.try_into().ok().map(u64::from_le_bytes),
+            _ => None,
+        };
+        match (key, number) {
+            (b"$UUID", _) => algorithm = Some(kdbx_algorithm(value)),
+            (b"R", Some(rounds)) | (b"I", Some(rounds)) => {
+                details.push(format!("{} itération(s)", rounds));
+                if rounds > MAX_SANE_ITERATIONS {
+                    warnings.push(format!("{} itérations: l'ouverture prendrait des heures", rounds));
+                }
+            }
+            (b"M", Some(memory)) => {
+                details.push(format!("{} Kio", memory / 1024));
+                if memory / 1024 > MAX_SANE_KDF_MEMORY_KIB {
+                    warnings.push(format!("l'ouverture réserverait {} Mio de mémoire", memory / 1024 / 1024));
+                }
+            }
+            (b"P", Some(parallelism)) => details.push(format!("{} voie(s)", parallelism)),
+            _ => {}
+        }
+    }
+    
+    let mut description = algorithm.unwrap_or_else(|| "inconnu".to_string());
+    if !details.is_empty() {
+        description = format!("{}, {}", description, details.join(", "));
+    }
+    Ok(description)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::database::Database;
+    use crate::models::repository::{Repository, Timings};
+    
+    #[test]
+    fn test_inspect_keeprust_and_hostile_files() {
+        let db = Database::new("Test".to_string(), String::new());
+        let kdf = KdfParams::Argon2id { memory_kib: 8, iterations: 1, parallelism: 1 };
+        let mut bytes = Repository::encode_with_kdf(&db, "pw", kdf, &mut Timings::disabled()).unwrap();
+        
+        let Inspection::Keeprust(report) = inspect(&bytes).unwrap() else { panic!("format keeprust attendu") };
+        assert_eq!((report.version, report.kdf, report.salt_len), (FILE_VERSION, kdf, 16));
+        assert_eq!(report.announced_chunks, Some(report.chunks));
+        assert_eq!(report.warnings(), vec![format!("dérivation de clé faible ({})", kdf)]);
+        
+        // Un fichier tronqué est décrit sans erreur, avec ses blocs manquants
+        bytes.truncate(bytes.len() - 1);
+        let Inspection::Keeprust(truncated) = inspect(&bytes).unwrap() else { panic!("format keeprust attendu") };
+        assert_eq!(truncated.chunks + 1, truncated.announced_chunks.unwrap());
+        
+        // En-tête KDBX 4 annonçant un champ démesuré : refusé sans allocation
+        let mut kdbx = Vec::new();
+        kdbx.extend(KDBX_SIGNATURE_1.to_le_bytes());
+        kdbx.extend(KDBX_SIGNATURE_2.to_le_bytes());
+        kdbx.extend([1, 0, 4, 0, KDBX_CIPHER_ID]);
+        kdbx.extend(u32::MAX.to_le_bytes());
+        assert_eq!(inspect(&kdbx), Err(InspectError::FieldTooLarge(u32::MAX as usize)));
+        
+        assert_eq!(inspect(b"PK\x03\x04"), Err(InspectError::UnknownFormat));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,140 @@
+use thiserror::Error;
+
+use super::entry::Entry;
+use super::id::EntryId;
+
+/// Taille maximale des notes d'une entrée, en octets : au-delà, de nouvelles notes sont refusées
+pub const MAX_NOTES_SIZE: usize = 1024 * 1024;
+
+/// Taille maximale de la valeur d'un champ supplémentaire, en octets
+pub const MAX_FIELD_SIZE: usize = 256 * 1024;
+
+/// Taille des notes au-delà de laquelle un avertissement est affiché (par défaut)
+pub const NOTES_WARNING_SIZE: usize = 16 * 1024;
+
+/// Taille de la valeur d'un champ au-delà de laquelle un avertissement est affiché (par défaut)
+pub const FIELD_WARNING_SIZE: usize = 4 * 1024;
+
+/// Tailles maximales des notes et des champs supplémentaires d'une entrée, en octets.
+///
+/// Notes et champs sont déchiffrés à chaque ouverture de la base, contrairement au contenu des
+/// pièces jointes qui reste compressé jusqu'à son extraction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeLimits {
+    pub notes: usize,
+    pub field: usize,
+}
+
+impl SizeLimits {
+    /// Plafonds des valeurs saisies, importées ou modifiées
+    pub const HARD: SizeLimits = SizeLimits { notes: MAX_NOTES_SIZE, field: MAX_FIELD_SIZE };
+}
+
+impl Default for SizeLimits {
+    /// Seuils d'avertissement
+    fn default() -> Self {
+        Self { notes: NOTES_WARNING_SIZE, field: FIELD_WARNING_SIZE }
+    }
+}
+
+/// Notes ou champ supplémentaire d'une entrée dépassant une taille maximale
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("{title} (ID: {id}): {value} de {size} octets (au-delà de {limit} octets)")]
+pub struct OversizedValue {
+    pub id: EntryId,
+    pub title: String,
+    
+    /// "notes" ou "champ 'NOM'"
+    pub value: String,
+    
+    pub size: usize,
+    pub limit: usize,
+}
+
+impl Entry {
+    /// Notes et champs supplémentaires de l'entrée dépassant les tailles maximales fournies
+    pub fn oversized_values(&self, limits: SizeLimits) -> Vec<OversizedValue> {
+        let mut values = Vec::new();
+        if self.notes.len() > limits.notes {
+            values.push(self.oversized("notes".to_string(), self.notes.len(), limits.notes));
+        }
+        for field in self.custom_fields.iter().filter(|field| field.value.len() > limits.field) {
+            values.push(self.oversized(format!("champ '{}'", field.name), field.value.len(), limits.field));
+        }
+        values
+    }
+    
+    /// Vérifie les valeurs écrites dans l'entrée par rapport aux plafonds (`SizeLimits::HARD`) :
+    /// seules celles qui diffèrent de `before` (toutes pour une nouvelle entrée) sont refusées,
+    /// une valeur déjà enregistrée reste acceptée
+    pub fn check_written_sizes(&self, before: Option<&Entry>) -> Result<(), OversizedValue> {
+        if self.notes.len() > MAX_NOTES_SIZE && before.is_none_or(|before| before.notes != self.notes) {
+            return Err(self.oversized("notes".to_string(), self.notes.len(), MAX_NOTES_SIZE));
+        }
+        for field in self.custom_fields.iter().filter(|field| field.value.len() > MAX_FIELD_SIZE) {
+            let stored = before.and_then(|before| before.custom_fields.iter().find(|stored| stored.name == field.name));
+            if stored.is_none_or(|stored| stored.value != field.value) {
+                return Err(self.oversized(format!("champ '{}'", field.name), field.value.len(), MAX_FIELD_SIZE));
+            }
+        }
+        Ok(())
+    }
+    
+    fn oversized(&self, value: String, size: usize, limit: usize) -> OversizedValue {
+        OversizedValue { id: self.id, title: self.title.clone(), value, size, limit }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::CustomField;
+    
+    fn entry(notes: String) -> Entry {
+        Entry::new("Serveur".to_string(), "root".to_string(), "pw".to_string(), String::new(), notes)
+    }
+    
+    #[test]
+    fn test_oversized_values_against_warning_sizes() {
+        let mut entry = entry("x".repeat(NOTES_WARNING_SIZE + 1));
+        entry.set_custom_field(CustomField::new("Clé", "k".repeat(FIELD_WARNING_SIZE), false)).unwrap();
+        
+        let oversized = entry.oversized_values(SizeLimits::default());
+        assert_eq!(oversized.len(), 1);
+        assert_eq!(oversized[0].value, "notes");
+        assert_eq!(oversized[0].size, NOTES_WARNING_SIZE + 1);
+        assert_eq!(oversized[0].limit, NOTES_WARNING_SIZE);
+        assert!(entry.oversized_values(SizeLimits::HARD).is_empty());
+    }
+    
+    #[test]
+    fn test_new_entry_over_the_cap_is_refused() {
+        let new = entry("x".repeat(MAX_NOTES_SIZE + 1));
+        let refused = new.check_written_sizes(None).unwrap_err();
+        assert_eq!(refused.value, "notes");
+        assert_eq!(refused.limit, MAX_NOTES_SIZE);
+        assert!(entry("x".repeat(MAX_NOTES_SIZE)).check_written_sizes(None).is_ok());
+    }
+    
+    #[test]
+    fn test_field_written_over_the_cap_is_refused() {
+        let before = entry(String::new());
+        let mut after = before.clone();
+        after.set_custom_field(CustomField::new("Clé", "k".repeat(MAX_FIELD_SIZE + 1), false)).unwrap();
+        
+        assert_eq!(after.check_written_sizes(Some(&before)).unwrap_err().value, "champ 'Clé'");
+    }
+    
+    #[test]
+    fn test_values_already_stored_over_the_cap_are_kept() {
+        // Notes enregistrées avant l'introduction des plafonds : une autre modification passe
+        let before = entry("x".repeat(MAX_NOTES_SIZE + 1));
+        let mut after = before.clone();
+        after.username = "admin".to_string();
+        assert!(after.check_written_sizes(Some(&before)).is_ok());
+        
+        // Mais de nouvelles notes au-delà du plafond sont refusées
+        after.notes.push('y');
+        assert!(after.check_written_sizes(Some(&before)).is_err());
+    }
+}
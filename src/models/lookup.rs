@@ -0,0 +1,174 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+use super::database::Database;
+use super::entry::Entry;
+use super::id::EntryId;
+
+/// Nombre minimal de caractères d'un préfixe d'ID (plus court, il désignerait trop d'entrées)
+pub const MIN_ID_PREFIX: usize = 4;
+
+/// Désignation d'une entrée là où un ID est attendu : ID complet, préfixe d'ID, titre, début ou
+/// partie du titre, ou motif approché (lettres du titre dans l'ordre, ex: "gml" pour "Gmail")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryRef(String);
+
+impl EntryRef {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for EntryRef {
+    type Err = Infallible;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.trim().to_string()))
+    }
+}
+
+impl fmt::Display for EntryRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Erreur levée lorsqu'une désignation ne correspond pas à une seule entrée
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum LookupError {
+    #[error("Aucune entrée ne correspond à '{0}'.")]
+    NotFound(String),
+    
+    #[error("'{reference}' correspond à {} entrées.", candidates.len())]
+    Ambiguous { reference: String, candidates: Vec<EntryId> },
+}
+
+/// Critère de recherche : entrée et titre en minuscules
+type Criterion<'a> = &'a dyn Fn(&Entry, &str) -> bool;
+
+/// Indique si les caractères de `pattern` apparaissent dans `text`, dans l'ordre
+fn is_subsequence(pattern: &str, text: &str) -> bool {
+    let mut chars = text.chars();
+    pattern.chars().all(|wanted| chars.any(|c| c == wanted))
+}
+
+impl Database {
+    /// ID de l'entrée désignée par `reference`. Un ID complet est retourné tel quel; sinon les
+    /// critères sont essayés du plus précis au plus large (titre exact, préfixe d'ID, début du
+    /// titre, partie du titre, motif approché), sans tenir compte de la casse : le premier qui
+    /// retient au moins une entrée décide. Plusieurs entrées retenues sont retournées dans
+    /// `LookupError::Ambiguous`, triées par titre. Les entrées de la corbeille ne sont examinées
+    /// qu'avec `include_trash`.
+    pub fn resolve_entry(&self, reference: &str, include_trash: bool) -> Result<EntryId, LookupError> {
+        let reference = reference.trim();
+        if let Ok(id) = reference.parse::<EntryId>() {
+            return Ok(id);
+        }
+        
+        let wanted = reference.to_lowercase();
+        let id_prefix = wanted.replace('-', "");
+        let entries: Vec<(&Entry, String)> = self.iter_entries()
+            .filter(|entry| include_trash || !self.is_in_trash(&entry.id))
+            .map(|entry| (entry, entry.title.to_lowercase()))
+            .collect();
+        
+        // Le titre exact passe avant le préfixe d'ID : un titre comme "2024" ou "cafe" n'est pas
+        // masqué par une entrée dont l'ID commence par les mêmes caractères
+        let criteria: [Criterion; 5] = [
+            &|_, title| title == wanted,
+            &|entry, _| id_prefix.len() >= MIN_ID_PREFIX
+                && id_prefix.chars().all(|c| c.is_ascii_hexdigit())
+                && entry.id.to_string().replace('-', "").starts_with(&id_prefix),
+            &|_, title| title.starts_with(&wanted),
+            &|_, title| title.contains(&wanted),
+            &|_, title| !wanted.is_empty() && is_subsequence(&wanted, title),
+        ];
+        for matches in criteria {
+            let mut found: Vec<&(&Entry, String)> = entries.iter().filter(|(entry, title)| matches(entry, title)).collect();
+            match found[..] {
+                [] => continue,
+                [(entry, _)] => return Ok(entry.id),
+                _ => {
+                    found.sort_by(|(_, a), (_, b)| a.cmp(b));
+                    return Err(LookupError::Ambiguous {
+                        reference: reference.to_string(),
+                        candidates: found.iter().map(|(entry, _)| entry.id).collect(),
+                    });
+                }
+            }
+        }
+        Err(LookupError::NotFound(reference.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn entry(title: &str) -> Entry {
+        Entry::new(title.to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new())
+    }
+    
+    #[test]
+    fn test_resolve_entry_by_id_or_id_prefix() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let gmail = db.add_entry(None, entry("Gmail")).unwrap();
+        db.add_entry(None, entry("GitHub")).unwrap();
+        
+        assert_eq!(db.resolve_entry(&gmail.to_string(), false), Ok(gmail));
+        assert_eq!(db.resolve_entry(&gmail.to_string()[..8], false), Ok(gmail));
+        assert_eq!(db.resolve_entry(&gmail.to_string()[..8].to_uppercase(), false), Ok(gmail));
+    }
+    
+    #[test]
+    fn test_exact_title_before_id_prefix() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let other = db.add_entry(None, entry("Serveur")).unwrap();
+        let prefix = other.to_string()[..4].to_string();
+        let titled = db.add_entry(None, entry(&prefix)).unwrap();
+        
+        assert_eq!(db.resolve_entry(&prefix, false), Ok(titled));
+        assert_eq!(db.resolve_entry(&other.to_string()[..8], false), Ok(other));
+    }
+    
+    #[test]
+    fn test_resolve_entry_by_title_prefix_or_pattern() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let gmail = db.add_entry(None, entry("Gmail")).unwrap();
+        let github = db.add_entry(None, entry("GitHub")).unwrap();
+        let gitlab = db.add_entry(None, entry("GitLab")).unwrap();
+        let git = db.add_entry(None, entry("git")).unwrap();
+        
+        assert_eq!(db.resolve_entry("GIT", false), Ok(git));
+        assert_eq!(db.resolve_entry("githu", false), Ok(github));
+        assert_eq!(db.resolve_entry("lab", false), Ok(gitlab));
+        assert_eq!(db.resolve_entry("gml", false), Ok(gmail));
+    }
+    
+    #[test]
+    fn test_ambiguous_and_unknown_references() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let github = db.add_entry(None, entry("GitHub")).unwrap();
+        let gitlab = db.add_entry(None, entry("GitLab")).unwrap();
+        let git = db.add_entry(None, entry("git")).unwrap();
+        
+        assert_eq!(db.resolve_entry("gi", false), Err(LookupError::Ambiguous {
+            reference: "gi".to_string(),
+            candidates: vec![git, github, gitlab],
+        }));
+        assert_eq!(db.resolve_entry("banque", false), Err(LookupError::NotFound("banque".to_string())));
+        assert_eq!(db.resolve_entry("gi", false).unwrap_err().to_string(), "'gi' correspond à 3 entrées.");
+    }
+    
+    #[test]
+    fn test_trashed_entries_need_include_trash() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let gmail = db.add_entry(None, entry("Gmail")).unwrap();
+        db.trash_entry(&gmail).unwrap();
+        
+        assert_eq!(db.resolve_entry("gml", false), Err(LookupError::NotFound("gml".to_string())));
+        assert_eq!(db.resolve_entry("gml", true), Ok(gmail));
+    }
+}
@@ -0,0 +1,379 @@
+use super::database::{Database, DatabaseError};
+use super::entry::Entry;
+use super::group::Group;
+use super::id::EntryId;
+
+/// Début du suffixe ajouté au titre d'une copie de conflit
+const CONFLICT_SUFFIX: &str = " (conflit depuis ";
+
+/// Bilan de la fusion d'une autre version de la base de données
+#[derive(Debug, Default, PartialEq)]
+pub struct MergeReport {
+    /// Groupes absents de cette base, créés
+    pub groups_added: usize,
+    
+    /// Entrées absentes de cette base, ajoutées
+    pub added: usize,
+    
+    /// Entrées remplacées par leur version plus récente
+    pub updated: usize,
+    
+    /// Copies de conflit créées pour les entrées modifiées des deux côtés
+    pub conflicts: Vec<EntryId>,
+}
+
+/// Relation entre la version locale et la version distante d'une même entrée
+#[derive(Debug, PartialEq)]
+enum Lineage {
+    /// Versions identiques
+    Same,
+    
+    /// La version distante prolonge la version locale
+    RemoteNewer,
+    
+    /// La version locale prolonge la version distante
+    LocalNewer,
+    
+    /// Les deux versions ont changé de mot de passe indépendamment
+    Diverged,
+}
+
+/// Indique si `entry` a connu le mot de passe actuel de `other` (actuel ou dans l'historique)
+fn knows_password(entry: &Entry, other: &Entry) -> bool {
+    other.password.is_empty() || entry.has_used_password(&other.password)
+}
+
+/// Compare deux versions d'une entrée.
+///
+/// Une version plus récente qui a connu le mot de passe de l'autre la remplace; si aucune ne
+/// connaît le mot de passe de l'autre, les modifications sont irréconciliables.
+fn lineage(local: &Entry, remote: &Entry) -> Lineage {
+    if local.updated_at == remote.updated_at && local.password == remote.password {
+        return Lineage::Same;
+    }
+    
+    if remote.updated_at > local.updated_at && knows_password(remote, local) {
+        Lineage::RemoteNewer
+    } else if local.updated_at >= remote.updated_at && knows_password(local, remote) {
+        Lineage::LocalNewer
+    } else {
+        Lineage::Diverged
+    }
+}
+
+/// Copie de conflit de la version distante : "Gmail (conflit depuis portable-x 2024-06-01)"
+fn conflict_copy(local: &Entry, remote: &Entry) -> Entry {
+    let origin = remote.modified_on.as_ref()
+        .map(|device| device.name.clone())
+        .unwrap_or_else(|| "un autre appareil".to_string());
+    
+    let mut copy = remote.clone();
+    copy.id = EntryId::new();
+    copy.title = format!("{}{}{} {})", remote.title, CONFLICT_SUFFIX, origin, remote.updated_at.format("%Y-%m-%d"));
+    copy.conflict_of = Some(local.id);
+    copy.deleted_from = None;
+    copy.deleted_from_parents.clear();
+    copy
+}
+
+/// Titre d'une entrée, sans le suffixe de sa copie de conflit
+fn original_title(entry: &Entry) -> &str {
+    match entry.conflict_of {
+        Some(_) => entry.title.rfind(CONFLICT_SUFFIX).map_or(entry.title.as_str(), |end| &entry.title[..end]),
+        None => &entry.title,
+    }
+}
+
+/// Champ comparé lors de la résolution d'un conflit
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConflictField {
+    Title,
+    Username,
+    Password,
+    Url,
+    Notes,
+    Tags,
+    
+    /// Champ supplémentaire, désigné par son nom
+    Custom(String),
+}
+
+/// Version d'un champ retenue lors de la résolution d'un conflit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Side {
+    /// Entrée d'origine, conservée dans la base
+    #[default]
+    Local,
+    
+    /// Copie de conflit, venue de l'autre version
+    Remote,
+}
+
+impl ConflictField {
+    pub fn label(&self) -> &str {
+        match self {
+            ConflictField::Title => "Titre",
+            ConflictField::Username => "Utilisateur",
+            ConflictField::Password => "Mot de passe",
+            ConflictField::Url => "URL",
+            ConflictField::Notes => "Notes",
+            ConflictField::Tags => "Étiquettes",
+            ConflictField::Custom(name) => name,
+        }
+    }
+    
+    /// Valeur du champ dans une version de l'entrée (None : champ supplémentaire absent)
+    pub fn value(&self, entry: &Entry) -> Option<String> {
+        match self {
+            ConflictField::Title => Some(original_title(entry).to_string()),
+            ConflictField::Username => Some(entry.username.clone()),
+            ConflictField::Password => Some(entry.password.clone()),
+            ConflictField::Url => Some(entry.url.clone()),
+            ConflictField::Notes => Some(entry.notes.clone()),
+            ConflictField::Tags => Some(entry.tags.join(", ")),
+            ConflictField::Custom(name) => entry.custom_field(name).map(|field| field.value.clone()),
+        }
+    }
+    
+    /// Valeur secrète, masquée à l'affichage
+    pub fn is_secret(&self, entry: &Entry) -> bool {
+        match self {
+            ConflictField::Password => true,
+            ConflictField::Custom(name) => entry.custom_field(name).is_some_and(|field| field.protected),
+            _ => false,
+        }
+    }
+    
+    /// Donne à `entry` la valeur du champ dans `source`
+    fn apply(&self, source: &Entry, entry: &mut Entry) {
+        match self {
+            ConflictField::Title => entry.title = original_title(source).to_string(),
+            ConflictField::Username => entry.username = source.username.clone(),
+            ConflictField::Password => entry.update(None, None, Some(source.password.clone()), None, None),
+            ConflictField::Url => entry.url = source.url.clone(),
+            ConflictField::Notes => entry.notes = source.notes.clone(),
+            ConflictField::Tags => entry.tags = source.tags.clone(),
+            ConflictField::Custom(name) => match source.custom_field(name) {
+                Some(field) => entry.set_custom_field(field.clone()).expect("nom de champ valide"),
+                None => {
+                    entry.remove_custom_field(name);
+                }
+            },
+        }
+    }
+}
+
+/// Champs dont la valeur diffère entre une entrée et sa copie de conflit
+pub fn conflicting_fields(local: &Entry, copy: &Entry) -> Vec<ConflictField> {
+    let mut fields = vec![
+        ConflictField::Title,
+        ConflictField::Username,
+        ConflictField::Password,
+        ConflictField::Url,
+        ConflictField::Notes,
+        ConflictField::Tags,
+    ];
+    for field in local.custom_fields.iter().chain(&copy.custom_fields) {
+        let custom = ConflictField::Custom(field.name.clone());
+        if !fields.iter().any(|known| matches!(known, ConflictField::Custom(name) if name.eq_ignore_ascii_case(&field.name))) {
+            fields.push(custom);
+        }
+    }
+    fields.retain(|field| field.value(local) != field.value(copy) || field.is_secret(local) != field.is_secret(copy));
+    fields
+}
+
+impl Database {
+    /// Fusionne une autre version de la base (copie synchronisée, sauvegarde d'un autre appareil).
+    ///
+    /// Les groupes et entrées absents sont ajoutés avec leurs IDs, les entrées modifiées d'un seul
+    /// côté prennent leur version la plus récente. Une entrée modifiée des deux côtés est conservée
+    /// telle quelle et la version distante est ajoutée à côté d'elle en copie de conflit, à résoudre
+    /// à la main; une même copie n'est jamais créée deux fois.
+    pub fn merge(&mut self, other: &Database) -> MergeReport {
+        let mut report = MergeReport::default();
+        
+        // Les parents sont parcourus avant leurs sous-groupes
+        for remote_group in other.iter_groups().filter(|group| group.id != other.root_group.id) {
+            if self.find_group(&remote_group.id).is_some() {
+                continue;
+            }
+            
+            let parent_id = remote_group.parent_id
+                .filter(|parent| self.find_group(parent).is_some())
+                .unwrap_or(self.root_group.id);
+            let mut group = Group::new(remote_group.name.clone(), Some(parent_id));
+            group.id = remote_group.id;
+            group.created_at = remote_group.created_at;
+            self.find_group_mut(&parent_id)
+                .expect("le groupe parent existe")
+                .add_subgroup(group);
+            
+            if self.metadata.trash_group_id.is_none() && other.metadata.trash_group_id == Some(remote_group.id) {
+                self.metadata.trash_group_id = Some(remote_group.id);
+            }
+            report.groups_added += 1;
+        }
+        
+        for remote_group in other.iter_groups() {
+            let group_id = if remote_group.id == other.root_group.id { self.root_group.id } else { remote_group.id };
+            
+            for remote in remote_group.entries.values() {
+                let Some(local) = self.find_entry(&remote.id) else {
+                    self.add_entry(Some(&group_id), remote.clone()).expect("le groupe existe");
+                    report.added += 1;
+                    continue;
+                };
+                
+                match lineage(local, remote) {
+                    Lineage::Same | Lineage::LocalNewer => {}
+                    Lineage::RemoteNewer => {
+                        self.update_entry_metadata(&remote.id, |entry| *entry = remote.clone())
+                            .expect("l'entrée existe");
+                        report.updated += 1;
+                    }
+                    Lineage::Diverged => {
+                        let already_copied = self.iter_entries().any(|entry| {
+                            entry.conflict_of == Some(remote.id)
+                                && entry.updated_at == remote.updated_at
+                                && entry.password == remote.password
+                        });
+                        if already_copied {
+                            continue;
+                        }
+                        
+                        let copy = conflict_copy(local, remote);
+                        let local_group = self.iter_groups()
+                            .find(|group| group.entries.contains_key(&remote.id))
+                            .expect("l'entrée existe")
+                            .id;
+                        report.conflicts.push(self.add_entry(Some(&local_group), copy).expect("le groupe existe"));
+                    }
+                }
+            }
+        }
+        
+        report
+    }
+    
+    /// Résout un conflit : l'entrée d'origine prend la valeur de la copie pour les champs choisis
+    /// (`Side::Remote`) et garde la sienne pour les autres, puis la copie est supprimée.
+    ///
+    /// Le mot de passe écarté reste dans l'historique : une nouvelle fusion de la même version ne
+    /// recrée pas le conflit.
+    pub fn resolve_conflict(&mut self, copy_id: &EntryId, choices: &[(ConflictField, Side)]) -> Result<EntryId, DatabaseError> {
+        let copy = self.find_entry(copy_id).ok_or(DatabaseError::EntryNotFound(*copy_id))?.clone();
+        let original = copy.conflict_of.ok_or(DatabaseError::NotAConflictCopy(*copy_id))?;
+        
+        // Les deux versions existent déjà : le verrou de l'entrée n'empêche pas d'en garder une
+        self.update_entry_unlocked(&original, |entry| {
+            for (field, _) in choices.iter().filter(|(_, side)| *side == Side::Remote) {
+                field.apply(&copy, entry);
+            }
+            if !entry.has_used_password(&copy.password) {
+                entry.remember_password(copy.password.clone(), copy.modified_on.clone());
+            }
+        })?;
+        self.remove_entry(copy_id)?;
+        Ok(original)
+    }
+    
+    /// Copies de conflit en attente de résolution (hors corbeille), avec l'entrée dont elles sont
+    /// la copie si elle existe encore
+    pub fn conflict_copies(&self) -> Vec<(&Entry, Option<&Entry>)> {
+        let mut copies: Vec<_> = self.iter_entries()
+            .filter(|entry| !self.is_in_trash(&entry.id))
+            .filter_map(|entry| Some((entry, self.find_entry(&entry.conflict_of?))))
+            .collect();
+        copies.sort_by(|(a, _), (b, _)| a.title.cmp(&b.title));
+        copies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::CustomField;
+    use chrono::Duration;
+    
+    #[test]
+    fn test_merge_keeps_diverged_entries_as_conflict_copies() {
+        let mut local = Database::new("Test".to_string(), String::new());
+        let work = local.add_group(None, "Work".to_string()).unwrap();
+        let mail = local.add_entry(Some(&work), Entry::new("Gmail".to_string(), "bob".to_string(), "v1".to_string(), String::new(), String::new())).unwrap();
+        let vpn = local.add_entry(None, Entry::new("VPN".to_string(), "bob".to_string(), "v1".to_string(), String::new(), String::new())).unwrap();
+        
+        // L'autre appareil change les deux mots de passe et ajoute une entrée dans un nouveau groupe
+        let mut remote = local.clone();
+        let later = |entry: &mut Entry, password: &str| {
+            entry.update(None, None, Some(password.to_string()), None, None);
+            entry.updated_at += Duration::minutes(5);
+        };
+        remote.update_entry_metadata(&mail, |entry| later(entry, "distant")).unwrap();
+        remote.update_entry_metadata(&vpn, |entry| later(entry, "v2")).unwrap();
+        let perso = remote.add_group(None, "Perso".to_string()).unwrap();
+        remote.add_entry(Some(&perso), Entry::new("Forum".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new())).unwrap();
+        
+        // Localement, seul le mot de passe de Gmail change
+        local.update_entry(&mail, |entry| entry.update(None, None, Some("local".to_string()), None, None)).unwrap();
+        
+        let report = local.merge(&remote);
+        assert_eq!((report.groups_added, report.added, report.updated), (1, 1, 1));
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(local.find_entry(&vpn).unwrap().password, "v2");
+        assert_eq!(local.find_entry(&mail).unwrap().password, "local");
+        
+        let copy = local.find_entry(&report.conflicts[0]).unwrap();
+        assert!(copy.title.starts_with("Gmail (conflit depuis "));
+        assert_eq!(copy.password, "distant");
+        assert!(local.find_group(&work).unwrap().entries.contains_key(&copy.id));
+        assert_eq!(local.conflict_copies()[0].1.unwrap().id, mail);
+        
+        // Une nouvelle fusion ne recrée pas la même copie
+        assert_eq!(local.merge(&remote), MergeReport::default());
+    }
+    
+    #[test]
+    fn test_resolve_conflict_field_by_field() {
+        let mut local = Database::new("Test".to_string(), String::new());
+        let mail = local.add_entry(None, Entry::new("Gmail".to_string(), "bob".to_string(), "v1".to_string(), String::new(), String::new())).unwrap();
+        
+        let mut remote = local.clone();
+        remote.update_entry(&mail, |entry| {
+            entry.update(None, Some("robert".to_string()), Some("distant".to_string()), None, Some("note".to_string()));
+            entry.set_custom_field(CustomField::new("PIN", "1234", true)).unwrap();
+            entry.updated_at += Duration::minutes(5);
+        }).unwrap();
+        local.update_entry(&mail, |entry| entry.update(None, None, Some("local".to_string()), Some("https://mail.google.com".to_string()), None)).unwrap();
+        
+        let copy = local.merge(&remote).conflicts[0];
+        let fields = conflicting_fields(local.find_entry(&mail).unwrap(), local.find_entry(&copy).unwrap());
+        assert_eq!(fields, vec![
+            ConflictField::Username,
+            ConflictField::Password,
+            ConflictField::Url,
+            ConflictField::Notes,
+            ConflictField::Custom("PIN".to_string()),
+        ]);
+        
+        // Mot de passe et champ PIN distants, le reste local
+        let choices: Vec<_> = fields.into_iter()
+            .map(|field| {
+                let side = if matches!(field, ConflictField::Password | ConflictField::Custom(_)) { Side::Remote } else { Side::Local };
+                (field, side)
+            })
+            .collect();
+        assert_eq!(local.resolve_conflict(&copy, &choices).unwrap(), mail);
+        assert!(local.find_entry(&copy).is_none());
+        
+        let resolved = local.find_entry(&mail).unwrap();
+        assert_eq!((resolved.title.as_str(), resolved.username.as_str(), resolved.password.as_str()), ("Gmail", "bob", "distant"));
+        assert_eq!(resolved.url, "https://mail.google.com");
+        assert_eq!(resolved.custom_field("pin").unwrap().value, "1234");
+        assert!(resolved.has_used_password("local"));
+        assert_eq!(local.resolve_conflict(&mail, &[]), Err(DatabaseError::NotAConflictCopy(mail)));
+        
+        // La même version distante ne recrée pas de conflit
+        assert_eq!(local.merge(&remote), MergeReport::default());
+    }
+}
\ No newline at end of file
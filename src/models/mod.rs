@@ -1,4 +1,37 @@
+pub mod clock;
+pub mod device;
+pub mod attachment;
 pub mod entry;
+pub mod id;
 pub mod group;
 pub mod database;
-pub mod repository;
\ No newline at end of file
+pub mod repository;
+pub mod stats;
+pub mod walker;
+pub mod chunks;
+pub mod query;
+pub mod recovery;
+pub mod share;
+pub mod report;
+pub mod trash;
+pub mod merge;
+pub mod transfer;
+pub mod hierarchy;
+pub mod replace;
+pub mod revision;
+pub mod limits;
+pub mod lookup;
+pub mod export;
+pub mod import;
+pub mod policy;
+pub mod scope;
+pub mod strength_cache;
+pub mod exposure;
+pub mod inspect;
+pub mod structure;
+pub mod autotype;
+pub mod search_index;
+pub mod saving;
+pub mod signing;
+pub mod secret_service;
+pub mod browser;
\ No newline at end of file
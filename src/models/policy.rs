@@ -0,0 +1,243 @@
+use serde::Deserialize;
+use std::fmt;
+
+use crate::crypto::key_derivation::KdfParams;
+use crate::utils::password_analyzer::estimate_entropy_bits;
+
+use super::database::Database;
+use super::entry::Entry;
+use super::id::EntryId;
+use super::limits::{MAX_FIELD_SIZE, MAX_NOTES_SIZE, OversizedValue, SizeLimits};
+
+/// Exigences sur la dérivation de la clé maître
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KdfPolicy {
+    /// Refuse les bases dont la clé n'est pas dérivée avec Argon2id (PBKDF2, scrypt)
+    pub require_argon2id: bool,
+    
+    /// Nombre minimal d'itérations PBKDF2
+    pub min_pbkdf2_iterations: Option<u32>,
+    
+    /// Mémoire minimale d'Argon2id, en Kio
+    pub min_argon2_memory_kib: Option<u32>,
+    
+    /// Nombre minimal de passes d'Argon2id
+    pub min_argon2_iterations: Option<u32>,
+}
+
+/// Exigences sur les entrées
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EntryPolicy {
+    /// Entropie minimale des mots de passe, en bits (estimation d'après l'alphabet et la longueur)
+    pub min_entropy_bits: Option<u32>,
+    
+    /// Étiquettes imposant une date d'expiration aux entrées qui les portent
+    pub require_expiry_tags: Vec<String>,
+    
+    /// Taille des notes au-delà de laquelle un avertissement est affiché, en Kio (16 par défaut)
+    pub max_notes_kib: Option<usize>,
+    
+    /// Taille de la valeur d'un champ supplémentaire au-delà de laquelle un avertissement est
+    /// affiché, en Kio (4 par défaut)
+    pub max_field_kib: Option<usize>,
+}
+
+impl EntryPolicy {
+    /// Seuils d'avertissement de taille, bornés par les plafonds vérifiés à l'enregistrement
+    pub fn size_limits(&self) -> SizeLimits {
+        let defaults = SizeLimits::default();
+        SizeLimits {
+            notes: self.max_notes_kib.map_or(defaults.notes, |kib| (kib * 1024).min(MAX_NOTES_SIZE)),
+            field: self.max_field_kib.map_or(defaults.field, |kib| (kib * 1024).min(MAX_FIELD_SIZE)),
+        }
+    }
+}
+
+/// Exigences sur les exports en clair
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExportPolicy {
+    /// Étiquettes dont les entrées ne sont jamais exportées
+    pub forbidden_tags: Vec<String>,
+}
+
+/// Politique commune à des bases de données (policy.toml) :
+///
+/// ```toml
+/// [kdf]
+/// require_argon2id = true
+/// min_argon2_memory_kib = 65536
+///
+/// [entries]
+/// min_entropy_bits = 60
+/// require_expiry_tags = ["shared"]
+/// max_notes_kib = 16
+///
+/// [export]
+/// forbidden_tags = ["confidentiel"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct VaultPolicy {
+    pub kdf: KdfPolicy,
+    pub entries: EntryPolicy,
+    pub export: ExportPolicy,
+}
+
+/// Écart entre une base et la politique
+#[derive(Debug, Clone, PartialEq)]
+pub enum PolicyViolation {
+    /// Dérivation de la clé maître insuffisante
+    WeakKdf { params: KdfParams, requirement: String },
+    
+    /// Mot de passe d'entropie insuffisante
+    LowEntropy { id: EntryId, title: String, bits: u32, min_bits: u32 },
+    
+    /// Entrée sans date d'expiration alors qu'une de ses étiquettes l'exige
+    MissingExpiry { id: EntryId, title: String, tag: String },
+    
+    /// Notes ou champ plus volumineux que le seuil fixé par la politique
+    Oversized(OversizedValue),
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyViolation::WeakKdf { params, requirement } => {
+                write!(f, "dérivation de clé insuffisante ({}): {}", params, requirement)
+            }
+            PolicyViolation::LowEntropy { id, title, bits, min_bits } => {
+                write!(f, "{} (ID: {}): mot de passe de {} bits d'entropie, {} requis", title, id, bits, min_bits)
+            }
+            PolicyViolation::MissingExpiry { id, title, tag } => {
+                write!(f, "{} (ID: {}): date d'expiration obligatoire pour l'étiquette '{}'", title, id, tag)
+            }
+            PolicyViolation::Oversized(oversized) => write!(f, "{}", oversized),
+        }
+    }
+}
+
+impl VaultPolicy {
+    /// Exigences de dérivation non satisfaites par les paramètres du fichier
+    fn kdf_requirements(&self, params: KdfParams) -> Vec<String> {
+        let kdf = &self.kdf;
+        let mut unmet = Vec::new();
+        match params {
+            KdfParams::Pbkdf2 { iterations } => {
+                if kdf.require_argon2id {
+                    unmet.push("Argon2id exigé ('keeprust upgrade-kdf')".to_string());
+                }
+                if let Some(min) = kdf.min_pbkdf2_iterations.filter(|min| iterations < *min) {
+                    unmet.push(format!("au moins {} itérations PBKDF2", min));
+                }
+            }
+            KdfParams::Argon2id { memory_kib, iterations, .. } => {
+                if let Some(min) = kdf.min_argon2_memory_kib.filter(|min| memory_kib < *min) {
+                    unmet.push(format!("au moins {} Kio de mémoire", min));
+                }
+                if let Some(min) = kdf.min_argon2_iterations.filter(|min| iterations < *min) {
+                    unmet.push(format!("au moins {} passe(s)", min));
+                }
+            }
+            KdfParams::Scrypt { .. } => {
+                if kdf.require_argon2id {
+                    unmet.push("Argon2id exigé ('keeprust upgrade-kdf')".to_string());
+                }
+            }
+        }
+        unmet
+    }
+    
+    /// Étiquette de l'entrée imposant une date d'expiration, si elle n'en a pas
+    fn missing_expiry_tag(&self, entry: &Entry) -> Option<&str> {
+        if entry.expires_at.is_some() {
+            return None;
+        }
+        self.entries.require_expiry_tags.iter().find(|tag| entry.has_tag(tag)).map(String::as_str)
+    }
+    
+    /// Étiquette interdisant l'export d'une entrée portant les étiquettes fournies
+    pub fn forbidden_export_tag(&self, tags: &[String]) -> Option<&str> {
+        self.export.forbidden_tags.iter()
+            .find(|forbidden| tags.iter().any(|tag| tag.eq_ignore_ascii_case(forbidden)))
+            .map(String::as_str)
+    }
+    
+    /// Vérifie une base et les paramètres de dérivation de son fichier; les entrées de la
+    /// corbeille sont ignorées
+    pub fn check(&self, db: &Database, kdf: KdfParams) -> Vec<PolicyViolation> {
+        let mut violations: Vec<PolicyViolation> = self.kdf_requirements(kdf).into_iter()
+            .map(|requirement| PolicyViolation::WeakKdf { params: kdf, requirement })
+            .collect();
+        
+        // Les seuils de taille par défaut ne donnent que des avertissements : seuls ceux fixés par
+        // la politique sont exigés
+        let configured = self.entries.size_limits();
+        let size_limits = SizeLimits {
+            notes: self.entries.max_notes_kib.map_or(MAX_NOTES_SIZE, |_| configured.notes),
+            field: self.entries.max_field_kib.map_or(MAX_FIELD_SIZE, |_| configured.field),
+        };
+        
+        let mut entries: Vec<&Entry> = db.iter_entries().filter(|entry| !db.is_in_trash(&entry.id)).collect();
+        entries.sort_by(|a, b| a.title.cmp(&b.title));
+        
+        for entry in entries {
+            if let Some(min_bits) = self.entries.min_entropy_bits
+                && !entry.password.is_empty()
+            {
+                let bits = estimate_entropy_bits(&entry.password) as u32;
+                if bits < min_bits {
+                    violations.push(PolicyViolation::LowEntropy { id: entry.id, title: entry.title.clone(), bits, min_bits });
+                }
+            }
+            if let Some(tag) = self.missing_expiry_tag(entry) {
+                violations.push(PolicyViolation::MissingExpiry { id: entry.id, title: entry.title.clone(), tag: tag.to_string() });
+            }
+            violations.extend(entry.oversized_values(size_limits).into_iter().map(PolicyViolation::Oversized));
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_policy_check() {
+        let policy: VaultPolicy = toml::from_str(r#"
+            [kdf]
+            require_argon2id = true
+            min_argon2_memory_kib = 65536
+            
+            [entries]
+            min_entropy_bits = 60
+            require_expiry_tags = ["shared"]
+            
+            [export]
+            forbidden_tags = ["Confidentiel"]
+        "#).unwrap();
+        
+        let mut db = Database::new("Test".to_string(), String::new());
+        let weak = Entry::builder().title("Wifi").password("azerty").tag("Shared").build().unwrap();
+        let strong = Entry::builder().title("Banque").password("k7#Qm2!vX9@pL4$w").tag("shared").expires_at(Some(chrono::Utc::now())).build().unwrap();
+        let weak_id = db.add_entry(None, weak).unwrap();
+        db.add_entry(None, strong).unwrap();
+        
+        let strong_kdf = KdfParams::Argon2id { memory_kib: 65536, iterations: 3, parallelism: 1 };
+        let violations = policy.check(&db, strong_kdf);
+        assert_eq!(violations, vec![
+            PolicyViolation::LowEntropy { id: weak_id, title: "Wifi".to_string(), bits: 28, min_bits: 60 },
+            PolicyViolation::MissingExpiry { id: weak_id, title: "Wifi".to_string(), tag: "shared".to_string() },
+        ]);
+        
+        let kdf_violations = policy.check(&Database::new("Vide".to_string(), String::new()), KdfParams::Pbkdf2 { iterations: 600_000 });
+        assert!(matches!(kdf_violations.as_slice(), [PolicyViolation::WeakKdf { .. }]));
+        
+        assert_eq!(policy.forbidden_export_tag(&["perso".to_string(), "confidentiel".to_string()]), Some("Confidentiel"));
+        assert_eq!(policy.forbidden_export_tag(&["perso".to_string()]), None);
+        assert!(toml::from_str::<VaultPolicy>("[entries]\nmin_entropy = 60").is_err());
+    }
+}
\ No newline at end of file
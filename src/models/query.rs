@@ -0,0 +1,549 @@
+use chrono::Duration;
+use thiserror::Error;
+
+use crate::utils::dates;
+use crate::utils::password_analyzer::{PasswordAnalyzer, PasswordStrength};
+use super::clock;
+use super::database::Database;
+use super::entry::{ColorLabel, Entry};
+use super::walker::WalkControl;
+
+/// Enumération des erreurs possibles lors de l'analyse d'une requête
+#[derive(Error, Debug, PartialEq)]
+pub enum QueryError {
+    #[error("Requête vide")]
+    Empty,
+    
+    #[error("Champ inconnu: '{0}'")]
+    UnknownField(String),
+    
+    #[error("Valeur invalide pour '{field}': '{value}'")]
+    InvalidValue { field: String, value: String },
+    
+    #[error("Parenthèse fermante manquante")]
+    MissingParenthesis,
+    
+    #[error("Élément inattendu: '{0}'")]
+    UnexpectedToken(String),
+    
+    #[error("Guillemet fermant manquant")]
+    UnterminatedQuote,
+}
+
+/// Opérateur de comparaison utilisé par les champs numériques ou ordonnés
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    /// Compare deux valeurs ordonnées avec cet opérateur
+    fn compare<T: PartialOrd>(self, left: T, right: T) -> bool {
+        match self {
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Equal => left == right,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::Greater => left > right,
+        }
+    }
+}
+
+/// Condition élémentaire portant sur une entrée
+#[derive(Debug, Clone, PartialEq)]
+pub enum Predicate {
+    /// Texte recherché dans le titre, l'utilisateur, l'URL et les notes
+    Text(String),
+    Title(String),
+    Username(String),
+    Url(String),
+    Notes(String),
+    /// Étiquette exacte (sans tenir compte de la casse)
+    Tag(String),
+    /// Texte recherché dans le chemin du groupe (ex: "Root/Travail")
+    Group(String),
+    /// Force du mot de passe
+    Strength(Comparison, PasswordStrength),
+    /// Âge du mot de passe (depuis la dernière modification de l'entrée)
+    Age(Comparison, Duration),
+    /// Entrée archivée ou non
+    Archived(bool),
+    /// Étiquette de couleur (None : aucune couleur)
+    Color(Option<ColorLabel>),
+}
+
+/// Arbre syntaxique d'une requête de filtrage
+#[derive(Debug, Clone, PartialEq)]
+pub enum Query {
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+    Not(Box<Query>),
+    Term(Predicate),
+}
+
+/// Entrée correspondant à une requête, avec le chemin de son groupe
+#[derive(Debug)]
+pub struct QueryMatch<'a> {
+    /// Noms des groupes depuis la racine jusqu'au groupe de l'entrée
+    pub path: Vec<String>,
+    
+    /// Entrée trouvée
+    pub entry: &'a Entry,
+    
+    /// Entrée située dans la corbeille (seulement si elle a été incluse dans la recherche)
+    pub trashed: bool,
+}
+
+/// Contexte d'évaluation partagé entre toutes les entrées
+pub struct QueryContext {
+    analyzer: PasswordAnalyzer,
+    
+    /// Inclut les entrées de la corbeille, exclues par défaut
+    include_trash: bool,
+}
+
+impl QueryContext {
+    pub fn new() -> Self {
+        Self::with_analyzer(PasswordAnalyzer::new())
+    }
+    
+    /// Contexte évaluant les critères de force avec un analyseur configuré
+    pub fn with_analyzer(analyzer: PasswordAnalyzer) -> Self {
+        Self { analyzer, include_trash: false }
+    }
+    
+    /// Inclut (ou non) les entrées de la corbeille dans les résultats
+    pub fn including_trash(mut self, include_trash: bool) -> Self {
+        self.include_trash = include_trash;
+        self
+    }
+}
+
+impl Default for QueryContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Query {
+    /// Analyse une requête textuelle, par exemple `tag:banque AND (strength:<medium OR age:>1y)`.
+    ///
+    /// Les termes juxtaposés sans opérateur sont combinés avec AND.
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Err(QueryError::Empty);
+        }
+        
+        let mut parser = Parser { tokens, position: 0 };
+        let query = parser.parse_or()?;
+        
+        match parser.peek() {
+            None => Ok(query),
+            Some(token) => Err(QueryError::UnexpectedToken(token.to_string())),
+        }
+    }
+    
+    /// Vérifie si une entrée satisfait la requête
+    pub fn matches(&self, context: &QueryContext, path: &[String], entry: &Entry) -> bool {
+        match self {
+            Query::And(left, right) => left.matches(context, path, entry) && right.matches(context, path, entry),
+            Query::Or(left, right) => left.matches(context, path, entry) || right.matches(context, path, entry),
+            Query::Not(inner) => !inner.matches(context, path, entry),
+            Query::Term(predicate) => predicate.matches(context, path, entry),
+        }
+    }
+}
+
+impl Predicate {
+    fn matches(&self, context: &QueryContext, path: &[String], entry: &Entry) -> bool {
+        match self {
+            Predicate::Text(text) => [&entry.title, &entry.username, &entry.url, &entry.notes]
+                .iter()
+                .any(|field| contains_ignore_case(field, text)),
+            Predicate::Title(text) => contains_ignore_case(&entry.title, text),
+            Predicate::Username(text) => contains_ignore_case(&entry.username, text),
+            Predicate::Url(text) => contains_ignore_case(&entry.url, text),
+            Predicate::Notes(text) => contains_ignore_case(&entry.notes, text),
+            Predicate::Tag(tag) => entry.has_tag(tag),
+            Predicate::Group(text) => contains_ignore_case(&path.join("/"), text),
+            Predicate::Strength(comparison, strength) => {
+                let analysis = context.analyzer.analyze(&entry.password);
+                comparison.compare(&analysis.strength, strength)
+            },
+            Predicate::Age(comparison, duration) => {
+                comparison.compare(clock::now() - entry.updated_at, *duration)
+            },
+            Predicate::Archived(archived) => entry.archived == *archived,
+            Predicate::Color(color) => entry.color == *color,
+        }
+    }
+}
+
+impl Database {
+    /// Retourne toutes les entrées satisfaisant une requête, dans l'ordre du parcours
+    pub fn find_entries(&self, query: &Query) -> Vec<QueryMatch<'_>> {
+        self.find_entries_with(&QueryContext::new(), query)
+    }
+    
+    /// Variante de `find_entries` avec un contexte d'évaluation fourni (analyseur configuré,
+    /// entrées de la corbeille incluses ou non)
+    pub fn find_entries_with(&self, context: &QueryContext, query: &Query) -> Vec<QueryMatch<'_>> {
+        let trash = self.trash_group_ids();
+        let mut matches = Vec::new();
+        
+        self.walk(|path, group, entry| {
+            let trashed = trash.contains(&group.id);
+            if let Some(entry) = entry
+                && (context.include_trash || !trashed)
+                && query.matches(context, path, entry)
+            {
+                matches.push(QueryMatch { path: path.to_vec(), entry, trashed });
+            }
+            WalkControl::Continue
+        });
+        
+        matches
+    }
+    
+    /// Retourne les entrées dont le titre, l'utilisateur, l'URL ou les notes contiennent `text`,
+    /// dans l'ordre du parcours; celles de la corbeille seulement avec `include_trash`
+    pub fn search(&self, text: &str, ignore_case: bool, include_trash: bool) -> Vec<QueryMatch<'_>> {
+        let trash = self.trash_group_ids();
+        let mut matches = Vec::new();
+        
+        self.walk(|path, group, entry| {
+            let trashed = trash.contains(&group.id);
+            if let Some(entry) = entry
+                && (include_trash || !trashed)
+                && [&entry.title, &entry.username, &entry.url, &entry.notes].iter().any(|field| {
+                    if ignore_case { contains_ignore_case(field, text) } else { field.contains(text) }
+                })
+            {
+                matches.push(QueryMatch { path: path.to_vec(), entry, trashed });
+            }
+            WalkControl::Continue
+        });
+        
+        matches
+    }
+}
+
+/// Recherche une sous-chaîne sans tenir compte de la casse
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+/// Élément lexical d'une requête
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LeftParen,
+    RightParen,
+    And,
+    Or,
+    Not,
+    /// Terme non entouré de guillemets (peut contenir un champ)
+    Word(String),
+    /// Texte entre guillemets, toujours recherché tel quel
+    Quoted(String),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::LeftParen => write!(f, "("),
+            Token::RightParen => write!(f, ")"),
+            Token::And => write!(f, "AND"),
+            Token::Or => write!(f, "OR"),
+            Token::Not => write!(f, "NOT"),
+            Token::Word(word) => write!(f, "{}", word),
+            Token::Quoted(text) => write!(f, "\"{}\"", text),
+        }
+    }
+}
+
+/// Découpe une requête en éléments lexicaux
+fn tokenize(input: &str) -> Result<Vec<Token>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            },
+            '(' => {
+                chars.next();
+                tokens.push(Token::LeftParen);
+            },
+            ')' => {
+                chars.next();
+                tokens.push(Token::RightParen);
+            },
+            '"' => {
+                chars.next();
+                tokens.push(Token::Quoted(read_quoted(&mut chars)?));
+            },
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' {
+                        break;
+                    }
+                    chars.next();
+                    // Autoriser les valeurs entre guillemets après un champ: title:"Mon compte"
+                    if c == '"' {
+                        word.push_str(&read_quoted(&mut chars)?);
+                    } else {
+                        word.push(c);
+                    }
+                }
+                
+                tokens.push(match word.as_str() {
+                    "AND" | "and" | "&&" => Token::And,
+                    "OR" | "or" | "||" => Token::Or,
+                    "NOT" | "not" | "!" => Token::Not,
+                    _ => Token::Word(word),
+                });
+            },
+        }
+    }
+    
+    Ok(tokens)
+}
+
+/// Lit un texte jusqu'au guillemet fermant (le guillemet ouvrant est déjà consommé)
+fn read_quoted(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Result<String, QueryError> {
+    let mut text = String::new();
+    for c in chars.by_ref() {
+        if c == '"' {
+            return Ok(text);
+        }
+        text.push(c);
+    }
+    Err(QueryError::UnterminatedQuote)
+}
+
+/// Analyseur syntaxique à descente récursive
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+    
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+    
+    /// or := and (OR and)*
+    fn parse_or(&mut self) -> Result<Query, QueryError> {
+        let mut left = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            let right = self.parse_and()?;
+            left = Query::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+    
+    /// and := unary ((AND)? unary)*
+    fn parse_and(&mut self) -> Result<Query, QueryError> {
+        let mut left = self.parse_unary()?;
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.next();
+                },
+                Some(Token::Not | Token::LeftParen | Token::Word(_) | Token::Quoted(_)) => {},
+                _ => break,
+            }
+            let right = self.parse_unary()?;
+            left = Query::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+    
+    /// unary := NOT unary | '(' or ')' | terme
+    fn parse_unary(&mut self) -> Result<Query, QueryError> {
+        match self.next() {
+            Some(Token::Not) => Ok(Query::Not(Box::new(self.parse_unary()?))),
+            Some(Token::LeftParen) => {
+                let query = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RightParen) => Ok(query),
+                    _ => Err(QueryError::MissingParenthesis),
+                }
+            },
+            Some(Token::Quoted(text)) => Ok(Query::Term(Predicate::Text(text))),
+            Some(Token::Word(word)) => Ok(Query::Term(parse_term(&word)?)),
+            Some(token) => Err(QueryError::UnexpectedToken(token.to_string())),
+            None => Err(QueryError::UnexpectedToken("fin de requête".to_string())),
+        }
+    }
+}
+
+/// Convertit un terme (`champ:valeur` ou texte libre) en prédicat
+fn parse_term(word: &str) -> Result<Predicate, QueryError> {
+    let Some((field, value)) = word.split_once(':') else {
+        return Ok(Predicate::Text(word.to_string()));
+    };
+    
+    let value = value.to_string();
+    match field.to_lowercase().as_str() {
+        "title" | "titre" => Ok(Predicate::Title(value)),
+        "username" | "user" | "utilisateur" => Ok(Predicate::Username(value)),
+        "url" => Ok(Predicate::Url(value)),
+        "notes" | "note" => Ok(Predicate::Notes(value)),
+        "tag" => Ok(Predicate::Tag(value)),
+        "group" | "groupe" => Ok(Predicate::Group(value)),
+        "strength" | "force" => {
+            let (comparison, rest) = parse_comparison(&value);
+            let strength = parse_strength(rest).ok_or_else(|| invalid_value(field, &value))?;
+            Ok(Predicate::Strength(comparison, strength))
+        },
+        "age" => {
+            let (comparison, rest) = parse_comparison(&value);
+            let duration = dates::parse_duration(rest).map_err(|_| invalid_value(field, &value))?;
+            Ok(Predicate::Age(comparison, duration))
+        },
+        "archived" | "archive" | "archivee" | "archivée" => match value.to_lowercase().as_str() {
+            "yes" | "oui" | "true" => Ok(Predicate::Archived(true)),
+            "no" | "non" | "false" => Ok(Predicate::Archived(false)),
+            _ => Err(invalid_value(field, &value)),
+        },
+        "color" | "couleur" => match value.to_lowercase().as_str() {
+            "none" | "aucune" => Ok(Predicate::Color(None)),
+            color => color.parse().map(|color| Predicate::Color(Some(color))).map_err(|_| invalid_value(field, &value)),
+        },
+        _ => Err(QueryError::UnknownField(field.to_string())),
+    }
+}
+
+fn invalid_value(field: &str, value: &str) -> QueryError {
+    QueryError::InvalidValue { field: field.to_string(), value: value.to_string() }
+}
+
+/// Sépare l'opérateur de comparaison éventuel du reste de la valeur
+fn parse_comparison(value: &str) -> (Comparison, &str) {
+    for (prefix, comparison) in [
+        ("<=", Comparison::LessOrEqual),
+        (">=", Comparison::GreaterOrEqual),
+        ("<", Comparison::Less),
+        (">", Comparison::Greater),
+        ("=", Comparison::Equal),
+    ] {
+        if let Some(rest) = value.strip_prefix(prefix) {
+            return (comparison, rest);
+        }
+    }
+    (Comparison::Equal, value)
+}
+
+/// Reconnaît un niveau de force en anglais ou en français
+fn parse_strength(value: &str) -> Option<PasswordStrength> {
+    match value.to_lowercase().replace(['-', '_'], "").as_str() {
+        "veryweak" | "tresfaible" | "trèsfaible" => Some(PasswordStrength::VeryWeak),
+        "weak" | "faible" => Some(PasswordStrength::Weak),
+        "medium" | "moyen" => Some(PasswordStrength::Medium),
+        "strong" | "fort" => Some(PasswordStrength::Strong),
+        "verystrong" | "tresfort" | "trèsfort" => Some(PasswordStrength::VeryStrong),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn term(predicate: Predicate) -> Box<Query> {
+        Box::new(Query::Term(predicate))
+    }
+    
+    #[test]
+    fn test_parse_precedence_and_grouping() {
+        let query = Query::parse("tag:bank AND (strength:<medium OR age:>1y)").unwrap();
+        
+        assert_eq!(query, Query::And(
+            term(Predicate::Tag("bank".to_string())),
+            Box::new(Query::Or(
+                term(Predicate::Strength(Comparison::Less, PasswordStrength::Medium)),
+                term(Predicate::Age(Comparison::Greater, Duration::days(365))),
+            )),
+        ));
+    }
+    
+    #[test]
+    fn test_parse_implicit_and_not_and_quotes() {
+        let query = Query::parse("mail NOT title:\"old account\"").unwrap();
+        
+        assert_eq!(query, Query::And(
+            term(Predicate::Text("mail".to_string())),
+            Box::new(Query::Not(term(Predicate::Title("old account".to_string())))),
+        ));
+    }
+    
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Query::parse("  "), Err(QueryError::Empty));
+        assert_eq!(Query::parse("(tag:a"), Err(QueryError::MissingParenthesis));
+        assert_eq!(Query::parse("colour:red"), Err(QueryError::UnknownField("colour".to_string())));
+        assert!(matches!(Query::parse("age:>soon"), Err(QueryError::InvalidValue { .. })));
+        assert!(matches!(Query::parse("archived:maybe"), Err(QueryError::InvalidValue { .. })));
+        assert_eq!(Query::parse("tag:a)"), Err(QueryError::UnexpectedToken(")".to_string())));
+    }
+    
+    #[test]
+    fn test_matches_entry() {
+        let context = QueryContext::new();
+        let mut entry = Entry::new("Ma Banque".to_string(), "bob".to_string(), "123456".to_string(),
+            "https://banque.example".to_string(), String::new());
+        entry.tags.push("Bank".to_string());
+        let path = vec!["Root".to_string(), "Finances".to_string()];
+        
+        let query = Query::parse("tag:bank AND (strength:<medium OR age:>1y)").unwrap();
+        assert!(query.matches(&context, &path, &entry));
+        
+        entry.color = Some(ColorLabel::Red);
+        assert!(Query::parse("couleur:rouge").unwrap().matches(&context, &path, &entry));
+        assert!(!Query::parse("color:none").unwrap().matches(&context, &path, &entry));
+        
+        let query = Query::parse("group:finances NOT url:example").unwrap();
+        assert!(!query.matches(&context, &path, &entry));
+    }
+    
+    #[test]
+    fn test_search_case_sensitivity() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        db.add_entry(None, Entry::new("Mail".to_string(), "bob".to_string(), "pw".to_string(), String::new(), "compte perso".to_string())).unwrap();
+        
+        assert_eq!(db.search("Mail", false, false).len(), 1);
+        assert!(db.search("mail", false, false).is_empty());
+        assert_eq!(db.search("mail", true, false).len(), 1);
+        assert_eq!(db.search("perso", false, false)[0].path, vec!["Root".to_string()]);
+    }
+    
+    #[test]
+    fn test_trashed_entries_are_excluded_unless_included() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let mail = db.add_entry(None, Entry::new("Mail".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new())).unwrap();
+        db.trash_entry(&mail).unwrap();
+        
+        let query = Query::parse("title:mail").unwrap();
+        assert!(db.find_entries(&query).is_empty());
+        assert!(db.search("Mail", false, false).is_empty());
+        
+        let found = db.find_entries_with(&QueryContext::new().including_trash(true), &query);
+        assert!(found[0].trashed);
+        assert!(db.search("Mail", false, true)[0].trashed);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,334 @@
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use ciborium::Value as Cbor;
+use serde_json::Value as Json;
+use uuid::Uuid;
+
+use super::database::{Database, DatabaseMetadata};
+use super::entry::Entry;
+use super::group::Group;
+use super::id::GroupId;
+use super::repository::{FILE_VERSION, FILE_VERSION_V1, FILE_VERSION_V3, FILE_VERSION_V4, PayloadFormat, Repository, RepositoryError, Timings};
+
+/// Origine d'une base de données récupérée
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecoverySource {
+    /// Fichier de sauvegarde lisible
+    Backup(PathBuf),
+    
+    /// Fichier relu en ignorant son numéro de version
+    IgnoredVersion,
+    
+    /// Données relues avec un autre format de sérialisation que celui annoncé par l'en-tête
+    OtherFormat(PayloadFormat),
+    
+    /// Blocs intacts d'un conteneur par blocs, les blocs endommagés étant abandonnés
+    IntactChunks { lost_chunks: usize, total_chunks: usize },
+    
+    /// Entrées et groupes récupérés un par un, les éléments illisibles étant abandonnés
+    Salvaged { lost_entries: usize, lost_groups: usize },
+}
+
+impl fmt::Display for RecoverySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecoverySource::Backup(path) => write!(f, "Sauvegarde {}", path.display()),
+            RecoverySource::IgnoredVersion => write!(f, "Lecture en ignorant le numéro de version du fichier"),
+            RecoverySource::OtherFormat(format) => write!(f, "Lecture des données au format {}", format),
+            RecoverySource::IntactChunks { lost_chunks, total_chunks } => write!(
+                f,
+                "Blocs intacts uniquement ({} bloc(s) sur {} endommagé(s) abandonné(s))",
+                lost_chunks, total_chunks
+            ),
+            RecoverySource::Salvaged { lost_entries, lost_groups } => write!(
+                f,
+                "Récupération partielle ({} entrée(s) et {} groupe(s) illisibles abandonnés)",
+                lost_entries, lost_groups
+            ),
+        }
+    }
+}
+
+/// Base de données récupérée et sa provenance
+pub struct RecoveryCandidate {
+    pub source: RecoverySource,
+    pub database: Database,
+}
+
+/// Éléments abandonnés pendant une récupération partielle
+#[derive(Default)]
+struct SalvageLosses {
+    entries: usize,
+    groups: usize,
+}
+
+/// Retourne les sauvegardes d'un fichier (`<fichier>.bak*` ou `<fichier>~`), de la plus récente à la plus ancienne
+pub fn backup_paths(path: &Path) -> Vec<PathBuf> {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return Vec::new();
+    };
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    
+    let Ok(read_dir) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+    
+    let backup_prefix = format!("{}.bak", name);
+    let mut backups: Vec<(PathBuf, std::time::SystemTime)> = read_dir
+        .filter_map(|item| item.ok())
+        .filter(|item| {
+            let file_name = item.file_name();
+            let file_name = file_name.to_string_lossy();
+            file_name.starts_with(&backup_prefix) || file_name == format!("{}~", name)
+        })
+        .filter_map(|item| {
+            let modified = item.metadata().and_then(|metadata| metadata.modified()).ok()?;
+            Some((item.path(), modified))
+        })
+        .collect();
+    
+    backups.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+    backups.into_iter().map(|(path, _)| path).collect()
+}
+
+/// Retourne la version d'un fichier (le fichier lui-même ou l'une de ses sauvegardes) la plus récente
+/// parmi celles modifiées avant `cutoff`, avec sa date de modification
+pub fn snapshot_before(path: &Path, cutoff: DateTime<Utc>) -> Option<(PathBuf, DateTime<Utc>)> {
+    std::iter::once(path.to_path_buf())
+        .chain(backup_paths(path))
+        .filter_map(|candidate| {
+            let modified: DateTime<Utc> = fs::metadata(&candidate).and_then(|metadata| metadata.modified()).ok()?.into();
+            Some((candidate, modified))
+        })
+        .filter(|(_, modified)| *modified < cutoff)
+        .max_by_key(|(_, modified)| *modified)
+}
+
+/// Essaie toutes les stratégies de récupération d'un fichier illisible.
+///
+/// Les candidats sont classés du plus fiable au moins fiable : lecture du fichier lui-même
+/// (version ignorée, autre format, récupération partielle) puis sauvegardes.
+pub fn attempt_recovery(path: &Path, password: &str) -> Vec<RecoveryCandidate> {
+    let mut candidates = Vec::new();
+    
+    if let Ok(bytes) = fs::read(path) {
+        candidates.extend(recover_from_bytes(&bytes, password));
+    }
+    
+    for backup in backup_paths(path) {
+        if let Ok(database) = Repository::new(&backup).load(password) {
+            candidates.push(RecoveryCandidate { source: RecoverySource::Backup(backup), database });
+        }
+    }
+    
+    candidates
+}
+
+/// Stratégies applicables au contenu du fichier lui-même
+fn recover_from_bytes(bytes: &[u8], password: &str) -> Option<RecoveryCandidate> {
+    // Conteneur par blocs : conserver les blocs intacts, en forçant au besoin la version
+    for version in [None, Some(FILE_VERSION), Some(FILE_VERSION_V4), Some(FILE_VERSION_V3)] {
+        if let Ok((database, lost_chunks, total_chunks)) = Repository::salvage_chunks(bytes, password, version) {
+            let source = if lost_chunks == 0 && version.is_some() {
+                RecoverySource::IgnoredVersion
+            } else {
+                RecoverySource::IntactChunks { lost_chunks, total_chunks }
+            };
+            return Some(RecoveryCandidate { source, database });
+        }
+    }
+    
+    // Fichier à bloc unique (version 1)
+    let mut timings = Timings::disabled();
+    let (format, plaintext, ignored_version) = match Repository::decrypt_payload(bytes, password, None, &mut timings) {
+        Ok((format, plaintext)) => (format, plaintext, false),
+        Err(RepositoryError::UnsupportedFileVersion) => {
+            let (format, plaintext) = Repository::decrypt_payload(bytes, password, Some(FILE_VERSION_V1), &mut timings).ok()?;
+            (format, plaintext, true)
+        }
+        Err(_) => return None,
+    };
+    
+    if let Ok(database) = format.deserialize(&plaintext) {
+        let source = if ignored_version { RecoverySource::IgnoredVersion } else { RecoverySource::OtherFormat(format) };
+        return Some(RecoveryCandidate { source, database });
+    }
+    
+    // L'en-tête peut annoncer un format erroné
+    for other in [PayloadFormat::Json, PayloadFormat::Cbor, PayloadFormat::MessagePack] {
+        if other != format
+            && let Ok(database) = other.deserialize(&plaintext)
+        {
+            return Some(RecoveryCandidate { source: RecoverySource::OtherFormat(other), database });
+        }
+    }
+    
+    salvage(&plaintext, format)
+}
+
+/// Reconstruit la base de données élément par élément à partir de sa structure générique
+fn salvage(plaintext: &[u8], format: PayloadFormat) -> Option<RecoveryCandidate> {
+    let value: Cbor = match format {
+        PayloadFormat::Json => serde_json::from_slice(plaintext).ok()?,
+        PayloadFormat::Cbor => ciborium::from_reader(plaintext).ok()?,
+        PayloadFormat::MessagePack => rmp_serde::from_slice(plaintext).ok()?,
+    };
+    let value = to_json(value);
+    
+    let root = value.get("root_group").filter(|root| root.is_object())?;
+    let mut losses = SalvageLosses::default();
+    let root_group = salvage_group(root, None, &mut losses);
+    
+    let mut database = Database::new("Base récupérée".to_string(), String::new());
+    if let Some(metadata) = value.get("metadata").and_then(|metadata| serde_json::from_value::<DatabaseMetadata>(metadata.clone()).ok()) {
+        database.metadata = metadata;
+    }
+    database.root_group = root_group;
+    database.storage_format = format;
+    
+    Some(RecoveryCandidate {
+        source: RecoverySource::Salvaged { lost_entries: losses.entries, lost_groups: losses.groups },
+        database,
+    })
+}
+
+/// Reconstruit un groupe en ignorant les entrées et sous-groupes illisibles
+fn salvage_group(value: &Json, parent_id: Option<GroupId>, losses: &mut SalvageLosses) -> Group {
+    let name = value.get("name").and_then(Json::as_str).unwrap_or("Groupe récupéré");
+    let mut group = Group::new(name.to_string(), parent_id);
+    if let Some(id) = value.get("id").and_then(Json::as_str).and_then(|id| id.parse().ok()) {
+        group.id = id;
+    }
+    
+    if let Some(entries) = value.get("entries").and_then(Json::as_object) {
+        for entry in entries.values() {
+            match serde_json::from_value::<Entry>(entry.clone()) {
+                Ok(entry) => {
+                    group.add_entry(entry);
+                }
+                Err(_) => losses.entries += 1,
+            }
+        }
+    }
+    
+    if let Some(subgroups) = value.get("subgroups").and_then(Json::as_object) {
+        for subgroup in subgroups.values() {
+            if subgroup.is_object() {
+                let subgroup = salvage_group(subgroup, Some(group.id), losses);
+                group.add_subgroup(subgroup);
+            } else {
+                losses.groups += 1;
+            }
+        }
+    }
+    
+    // Conserver les dates d'origine lorsqu'elles sont lisibles
+    if let Some(created_at) = value.get("created_at").and_then(|date| serde_json::from_value(date.clone()).ok()) {
+        group.created_at = created_at;
+    }
+    if let Some(updated_at) = value.get("updated_at").and_then(|date| serde_json::from_value(date.clone()).ok()) {
+        group.updated_at = updated_at;
+    }
+    
+    group
+}
+
+/// Convertit une valeur générique en JSON (les identifiants binaires redeviennent des UUID textuels)
+fn to_json(value: Cbor) -> Json {
+    match value {
+        Cbor::Integer(integer) => {
+            let integer = i128::from(integer);
+            i64::try_from(integer).map(Json::from)
+                .or_else(|_| u64::try_from(integer).map(Json::from))
+                .unwrap_or(Json::Null)
+        }
+        Cbor::Float(float) => Json::from(float),
+        Cbor::Text(text) => Json::String(text),
+        Cbor::Bool(boolean) => Json::Bool(boolean),
+        Cbor::Null => Json::Null,
+        Cbor::Bytes(bytes) => match Uuid::from_slice(&bytes) {
+            Ok(uuid) => Json::String(uuid.to_string()),
+            Err(_) => Json::Array(bytes.into_iter().map(Json::from).collect()),
+        },
+        Cbor::Array(items) => Json::Array(items.into_iter().map(to_json).collect()),
+        Cbor::Map(pairs) => Json::Object(
+            pairs.into_iter()
+                .map(|(key, value)| {
+                    let key = match to_json(key) {
+                        Json::String(key) => key,
+                        other => other.to_string(),
+                    };
+                    (key, to_json(value))
+                })
+                .collect(),
+        ),
+        Cbor::Tag(_, value) => to_json(*value),
+        _ => Json::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn sample_database() -> Database {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let work = db.add_group(None, "Work".to_string()).unwrap();
+        db.add_entry(None, Entry::new("Mail".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new())).unwrap();
+        db.add_entry(Some(&work), Entry::new("VPN".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new())).unwrap();
+        db
+    }
+    
+    #[test]
+    fn test_salvage_skips_unreadable_entries() {
+        let db = sample_database();
+        let mut value = serde_json::to_value(&db).unwrap();
+        for entry in value["root_group"]["entries"].as_object_mut().unwrap().values_mut() {
+            entry["created_at"] = Json::from(42);
+        }
+        let plaintext = serde_json::to_vec(&value).unwrap();
+        assert!(PayloadFormat::Json.deserialize(&plaintext).is_err());
+        
+        let candidate = salvage(&plaintext, PayloadFormat::Json).unwrap();
+        assert_eq!(candidate.source, RecoverySource::Salvaged { lost_entries: 1, lost_groups: 0 });
+        assert_eq!(candidate.database.count_entries(), 1);
+        assert_eq!(candidate.database.metadata.name, "Test");
+    }
+    
+    #[test]
+    fn test_salvage_reads_binary_formats() {
+        let db = sample_database();
+        
+        for format in [PayloadFormat::Cbor, PayloadFormat::MessagePack] {
+            let plaintext = format.serialize(&db).unwrap();
+            let candidate = salvage(&plaintext, format).unwrap();
+            
+            assert_eq!(candidate.database.count_entries(), 2);
+            assert_eq!(serde_json::to_value(&candidate.database.root_group).unwrap(), serde_json::to_value(&db.root_group).unwrap());
+        }
+    }
+    
+    #[test]
+    fn test_snapshot_before_picks_newest_older_version() {
+        let directory = std::env::temp_dir().join(format!("keeprust-asof-{}", GroupId::new().short()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("coffre.krs");
+        
+        let day = |day: u32| chrono::NaiveDate::from_ymd_opt(2024, 5, day).unwrap().and_hms_opt(12, 0, 0).unwrap().and_utc();
+        for (file, modified) in [("coffre.krs", day(20)), ("coffre.krs.bak1", day(1)), ("coffre.krs.bak2", day(10))] {
+            let file = fs::File::create(directory.join(file)).unwrap();
+            file.set_modified(modified.into()).unwrap();
+        }
+        
+        assert_eq!(snapshot_before(&path, day(15)).unwrap().0, directory.join("coffre.krs.bak2"));
+        assert_eq!(snapshot_before(&path, day(25)).unwrap().0, path);
+        assert!(snapshot_before(&path, day(1)).is_none());
+        
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
\ No newline at end of file
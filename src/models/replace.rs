@@ -0,0 +1,178 @@
+use regex::{NoExpand, Regex};
+use std::fmt;
+use thiserror::Error;
+
+use super::database::Database;
+use super::entry::{Entry, is_valid_url};
+use super::id::EntryId;
+
+/// Erreurs lors d'un remplacement de texte dans les entrées
+#[derive(Error, Debug, PartialEq)]
+pub enum ReplaceError {
+    #[error("Le texte recherché ne peut pas être vide")]
+    EmptyPattern,
+    
+    #[error("Expression régulière invalide: {0}")]
+    InvalidRegex(String),
+    
+    #[error("Le remplacement rendrait l'entrée '{title}' invalide: {reason}")]
+    InvalidResult { title: String, reason: String },
+}
+
+/// Champ texte d'une entrée modifiable par remplacement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextField {
+    Title,
+    Username,
+    Url,
+    Notes,
+}
+
+impl TextField {
+    /// Valeur du champ dans une entrée
+    pub fn get<'a>(&self, entry: &'a Entry) -> &'a str {
+        match self {
+            TextField::Title => &entry.title,
+            TextField::Username => &entry.username,
+            TextField::Url => &entry.url,
+            TextField::Notes => &entry.notes,
+        }
+    }
+    
+    /// Remplace la valeur du champ dans une entrée
+    pub fn set(&self, entry: &mut Entry, value: String) {
+        match self {
+            TextField::Title => entry.title = value,
+            TextField::Username => entry.username = value,
+            TextField::Url => entry.url = value,
+            TextField::Notes => entry.notes = value,
+        }
+    }
+    
+    /// Vérifie qu'une nouvelle valeur reste valide pour ce champ
+    fn validate(&self, value: &str) -> Result<(), String> {
+        match self {
+            TextField::Title if value.trim().is_empty() => Err("titre vide".to_string()),
+            TextField::Url if !value.is_empty() && !is_valid_url(value) => Err(format!("URL invalide '{}'", value)),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for TextField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextField::Title => write!(f, "title"),
+            TextField::Username => write!(f, "username"),
+            TextField::Url => write!(f, "url"),
+            TextField::Notes => write!(f, "notes"),
+        }
+    }
+}
+
+impl std::str::FromStr for TextField {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "title" | "titre" => Ok(TextField::Title),
+            "username" | "utilisateur" => Ok(TextField::Username),
+            "url" => Ok(TextField::Url),
+            "notes" => Ok(TextField::Notes),
+            other => Err(format!("Champ inconnu: '{}' (title, username, url ou notes)", other)),
+        }
+    }
+}
+
+/// Remplacement de texte : littéral, ou expression régulière dont le remplacement peut
+/// reprendre les groupes capturés ($1, ${nom})
+#[derive(Debug, Clone)]
+pub struct Replacement {
+    pattern: Regex,
+    replacement: String,
+    expand: bool,
+}
+
+impl Replacement {
+    /// Remplacement d'un texte exact
+    pub fn literal(find: &str, replacement: &str) -> Result<Self, ReplaceError> {
+        if find.is_empty() {
+            return Err(ReplaceError::EmptyPattern);
+        }
+        let pattern = Regex::new(&regex::escape(find)).map_err(|e| ReplaceError::InvalidRegex(e.to_string()))?;
+        Ok(Self { pattern, replacement: replacement.to_string(), expand: false })
+    }
+    
+    /// Remplacement des correspondances d'une expression régulière
+    pub fn regex(find: &str, replacement: &str) -> Result<Self, ReplaceError> {
+        if find.is_empty() {
+            return Err(ReplaceError::EmptyPattern);
+        }
+        let pattern = Regex::new(find).map_err(|e| ReplaceError::InvalidRegex(e.to_string()))?;
+        Ok(Self { pattern, replacement: replacement.to_string(), expand: true })
+    }
+    
+    /// Applique le remplacement à un texte; None si le texte n'est pas modifié
+    pub fn apply(&self, text: &str) -> Option<String> {
+        let replaced = if self.expand {
+            self.pattern.replace_all(text, self.replacement.as_str())
+        } else {
+            self.pattern.replace_all(text, NoExpand(&self.replacement))
+        };
+        (replaced != text).then(|| replaced.into_owned())
+    }
+}
+
+/// Modification prévue sur une entrée
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedChange {
+    pub id: EntryId,
+    pub title: String,
+    pub before: String,
+    pub after: String,
+}
+
+impl Database {
+    /// Prévoit un remplacement de texte dans un champ de toutes les entrées (hors corbeille),
+    /// sans rien modifier; échoue si une valeur obtenue serait invalide
+    pub fn plan_replacements(&self, field: TextField, replacement: &Replacement) -> Result<Vec<PlannedChange>, ReplaceError> {
+        let mut changes = Vec::new();
+        for entry in self.iter_entries().filter(|entry| !self.is_in_trash(&entry.id)) {
+            let before = field.get(entry);
+            let Some(after) = replacement.apply(before) else {
+                continue;
+            };
+            field.validate(&after)
+                .map_err(|reason| ReplaceError::InvalidResult { title: entry.title.clone(), reason })?;
+            changes.push(PlannedChange { id: entry.id, title: entry.title.clone(), before: before.to_string(), after });
+        }
+        
+        changes.sort_by(|a, b| a.title.cmp(&b.title));
+        Ok(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_plan_literal_and_regex_replacements() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let entry = Entry::builder().title("Banque").username("bob").notes("Agence: Lyon\nTél: 04 00 00 00 00").build().unwrap();
+        db.add_entry(None, entry).unwrap();
+        db.add_entry(None, Entry::builder().title("Forum").notes("aucune agence").build().unwrap()).unwrap();
+        
+        let literal = Replacement::literal("Lyon", "$1 Paris").unwrap();
+        let changes = db.plan_replacements(TextField::Notes, &literal).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].after, "Agence: $1 Paris\nTél: 04 00 00 00 00");
+        
+        let regex = Replacement::regex(r"Tél: (\d\d) ", "Téléphone: +33 ${1}").unwrap();
+        assert_eq!(db.plan_replacements(TextField::Notes, &regex).unwrap()[0].after, "Agence: Lyon\nTéléphone: +33 0400 00 00 00");
+        
+        let emptying = Replacement::regex(".+", "").unwrap();
+        assert!(matches!(db.plan_replacements(TextField::Title, &emptying), Err(ReplaceError::InvalidResult { .. })));
+        assert!(matches!(Replacement::regex("(", "x"), Err(ReplaceError::InvalidRegex(_))));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use super::database::Database;
+use super::entry::Entry;
+use super::query::QueryMatch;
+use super::replace::TextField;
+use super::walker::WalkControl;
+
+/// Entrées partageant un même identifiant (nom d'utilisateur ou adresse email)
+#[derive(Debug)]
+pub struct IdentityUsage<'a> {
+    /// Identifiant, tel qu'écrit dans la première entrée rencontrée
+    pub identity: String,
+    
+    /// Entrées utilisant cet identifiant, dans l'ordre du parcours
+    pub entries: Vec<QueryMatch<'a>>,
+}
+
+impl IdentityUsage<'_> {
+    /// Indique si l'identifiant est une adresse email
+    pub fn is_email(&self) -> bool {
+        is_email(&self.identity)
+    }
+}
+
+/// Indique si un identifiant ressemble à une adresse email
+pub fn is_email(identity: &str) -> bool {
+    match identity.split_once('@') {
+        Some((local, domain)) => !local.is_empty() && domain.contains('.') && !domain.contains('@'),
+        None => false,
+    }
+}
+
+/// Entrée dont le mot de passe reprend un autre de ses champs
+#[derive(Debug)]
+pub struct EchoedPassword<'a> {
+    pub entry: QueryMatch<'a>,
+    
+    /// Champ repris : nom d'utilisateur (ou partie locale de l'adresse email) ou titre
+    pub field: TextField,
+}
+
+/// Champ de l'entrée que reprend son mot de passe (sans tenir compte de la casse ni des espaces)
+fn echoed_field(entry: &Entry) -> Option<TextField> {
+    let password = identity_key(&entry.password);
+    let username = identity_key(&entry.username);
+    let local_part = username.split_once('@').map_or(username.as_str(), |(local, _)| local);
+    
+    if !username.is_empty() && (password == username || password == local_part) {
+        Some(TextField::Username)
+    } else if password == identity_key(&entry.title) {
+        Some(TextField::Title)
+    } else {
+        None
+    }
+}
+
+/// Clé de regroupement : les identifiants sont comparés sans tenir compte de la casse ni des espaces
+fn identity_key(identity: &str) -> String {
+    identity.trim().to_lowercase()
+}
+
+impl Database {
+    /// Regroupe les entrées par nom d'utilisateur, du plus utilisé au moins utilisé.
+    ///
+    /// Les entrées sans nom d'utilisateur et celles de la corbeille sont ignorées, ainsi que les
+    /// entrées archivées sauf si `include_archived` est vrai.
+    pub fn accounts_by_identity(&self, include_archived: bool) -> Vec<IdentityUsage<'_>> {
+        let mut usages: Vec<IdentityUsage> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        
+        self.walk(|path, _, entry| {
+            let Some(entry) = entry else {
+                return WalkControl::Continue;
+            };
+            let key = identity_key(&entry.username);
+            if key.is_empty() || (entry.archived && !include_archived) || self.is_in_trash(&entry.id) {
+                return WalkControl::Continue;
+            }
+            
+            let position = *index.entry(key).or_insert_with(|| {
+                usages.push(IdentityUsage { identity: entry.username.trim().to_string(), entries: Vec::new() });
+                usages.len() - 1
+            });
+            usages[position].entries.push(QueryMatch { path: path.to_vec(), entry, trashed: false });
+            WalkControl::Continue
+        });
+        
+        usages.sort_by(|a, b| b.entries.len().cmp(&a.entries.len())
+            .then_with(|| identity_key(&a.identity).cmp(&identity_key(&b.identity))));
+        usages
+    }
+    
+    /// Regroupe les entrées utilisant un même mot de passe (à l'identique), du plus réutilisé au
+    /// moins réutilisé; seuls les mots de passe partagés par au moins deux entrées sont retenus.
+    ///
+    /// Les entrées sans mot de passe et celles de la corbeille sont ignorées, ainsi que les
+    /// entrées archivées sauf si `include_archived` est vrai.
+    pub fn reused_passwords(&self, include_archived: bool) -> Vec<Vec<QueryMatch<'_>>> {
+        let mut groups: Vec<Vec<QueryMatch>> = Vec::new();
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        
+        self.walk(|path, _, entry| {
+            let Some(entry) = entry else {
+                return WalkControl::Continue;
+            };
+            if entry.password.is_empty() || (entry.archived && !include_archived) || self.is_in_trash(&entry.id) {
+                return WalkControl::Continue;
+            }
+            
+            let position = *index.entry(entry.password.as_str()).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[position].push(QueryMatch { path: path.to_vec(), entry, trashed: false });
+            WalkControl::Continue
+        });
+        
+        groups.retain(|group| group.len() > 1);
+        groups.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a[0].entry.title.cmp(&b[0].entry.title)));
+        groups
+    }
+    
+    /// Entrées dont le mot de passe est leur nom d'utilisateur ou leur titre, dans l'ordre du
+    /// parcours (mêmes exclusions que `reused_passwords`)
+    pub fn echoed_passwords(&self, include_archived: bool) -> Vec<EchoedPassword<'_>> {
+        let mut echoed = Vec::new();
+        self.walk(|path, _, entry| {
+            let Some(entry) = entry else {
+                return WalkControl::Continue;
+            };
+            if entry.password.is_empty() || (entry.archived && !include_archived) || self.is_in_trash(&entry.id) {
+                return WalkControl::Continue;
+            }
+            
+            if let Some(field) = echoed_field(entry) {
+                echoed.push(EchoedPassword { entry: QueryMatch { path: path.to_vec(), entry, trashed: false }, field });
+            }
+            WalkControl::Continue
+        });
+        echoed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    
+    #[test]
+    fn test_accounts_grouped_by_identity() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        for (title, username) in [("Mail", "Bob@Example.org"), ("Forum", "bob"), ("Banque", " bob@example.org"), ("Notes", "")] {
+            db.add_entry(None, Entry::new(title.to_string(), username.to_string(), "x".to_string(), String::new(), String::new())).unwrap();
+        }
+        
+        let usages = db.accounts_by_identity(false);
+        assert_eq!(usages.len(), 2);
+        assert_eq!(usages[0].entries.len(), 2);
+        assert!(usages[0].is_email());
+        assert_eq!(usages[1].identity, "bob");
+        assert!(!usages[1].is_email());
+    }
+    
+    #[test]
+    fn test_reused_and_echoed_passwords() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        for (title, username, password) in [
+            ("Mail", "bob@example.org", "Soleil2024"),
+            ("Forum", "bob", "Soleil2024"),
+            ("Banque", "bob", "soleil2024"),
+            ("Wifi", "", "wifi"),
+            ("Cloud", "Alice@example.org", "alice"),
+            ("Notes", "", ""),
+        ] {
+            db.add_entry(None, Entry::new(title.to_string(), username.to_string(), password.to_string(), String::new(), String::new())).unwrap();
+        }
+        
+        let reused = db.reused_passwords(false);
+        assert_eq!(reused.len(), 1);
+        let mut titles: Vec<&str> = reused[0].iter().map(|found| found.entry.title.as_str()).collect();
+        titles.sort();
+        assert_eq!(titles, ["Forum", "Mail"]);
+        
+        let mut echoed: Vec<(&str, TextField)> = db.echoed_passwords(false).iter().map(|echo| (echo.entry.entry.title.as_str(), echo.field)).collect();
+        echoed.sort_by_key(|(title, _)| *title);
+        assert_eq!(echoed, [("Cloud", TextField::Username), ("Wifi", TextField::Title)]);
+    }
+}
\ No newline at end of file
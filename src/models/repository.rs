@@ -1,16 +1,36 @@
-use std::fs::File;
+use std::fmt;
+use std::fs::{self, File};
 use std::io::{Read, Write};
+use base64::{Engine as _, engine::general_purpose};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use serde::{Serialize, Deserialize};
+use serde::de::DeserializeOwned;
+use zeroize::Zeroizing;
 
-use crate::crypto::{cipher, key_derivation};
+use crate::crypto::{cipher, key_derivation::{self, KdfParams}};
+use crate::models::chunks;
 use crate::models::database::Database;
+use crate::utils::paths;
+use crate::utils::webdav::{Download, WebDav, WebDavError};
 
 /// Signature de fichier pour identifier notre format
-const FILE_HEADER: &[u8] = b"KEEPRUST";
-/// Version du format de fichier
-const FILE_VERSION: u8 = 1;
+pub(super) const FILE_HEADER: &[u8] = b"KEEPRUST";
+/// Version du format de fichier (5 : en-tête binaire authentifié avec chaque bloc)
+pub const FILE_VERSION: u8 = 5;
+/// Version 4 du format (en-tête binaire non authentifié), toujours lisible
+pub const FILE_VERSION_V4: u8 = 4;
+/// Version 3 du format (en-tête JSON avec les paramètres Argon2id), toujours lisible
+pub const FILE_VERSION_V3: u8 = 3;
+/// Version 2 du format (conteneur par blocs, clé dérivée avec PBKDF2), toujours lisible
+pub const FILE_VERSION_V2: u8 = 2;
+/// Première version du format (un seul bloc chiffré), toujours lisible
+pub const FILE_VERSION_V1: u8 = 1;
+/// Suffixe de la copie de la version précédente du fichier
+pub const BACKUP_SUFFIX: &str = ".bak";
+/// Nombre d'itérations PBKDF2 en dessous duquel l'ouverture d'un fichier est signalée comme risquée
+pub const MIN_ITERATIONS: u32 = 100_000;
 
 /// Enumération des erreurs possibles lors des opérations de repository
 #[derive(Error, Debug)]
@@ -21,6 +41,9 @@ pub enum RepositoryError {
     #[error("Erreur de sérialisation: {0}")]
     SerializationError(#[from] serde_json::Error),
     
+    #[error("Erreur de sérialisation ({0}): {1}")]
+    PayloadError(PayloadFormat, String),
+    
     #[error("Erreur de chiffrement: {0}")]
     EncryptionError(#[from] cipher::CipherError),
     
@@ -35,132 +58,1085 @@ pub enum RepositoryError {
     
     #[error("Mot de passe invalide")]
     InvalidPassword,
+    
+    #[error("Bloc de données n°{0} endommagé ou manquant")]
+    CorruptedChunk(usize),
+    
+    #[error("Stockage WebDAV: {0}")]
+    RemoteError(#[from] WebDavError),
+    
+    #[error("{0} a été modifiée sur le serveur depuis son ouverture; vos modifications sont conservées dans {1}: fusionnez-les avec 'keeprust -d {0} merge {1}'")]
+    RemoteConflict(String, String),
+    
+    #[error("{0} existe déjà sur le serveur")]
+    RemoteExists(String),
+}
+
+/// Format de sérialisation des données en clair, avant chiffrement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadFormat {
+    /// JSON (format historique, lisible pour le débogage)
+    #[default]
+    Json,
+    
+    /// CBOR (RFC 8949), compact et rapide à analyser
+    Cbor,
+    
+    /// MessagePack
+    #[serde(rename = "msgpack")]
+    MessagePack,
+}
+
+impl PayloadFormat {
+    /// Sérialise une valeur (base de données, lot d'entrées) dans ce format
+    pub fn serialize<T: Serialize>(self, value: &T) -> Result<Vec<u8>, RepositoryError> {
+        match self {
+            PayloadFormat::Json => Ok(serde_json::to_vec(value)?),
+            PayloadFormat::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes).map_err(|e| RepositoryError::PayloadError(self, e.to_string()))?;
+                Ok(bytes)
+            }
+            // Structures sérialisées avec les noms de champs pour rester compatible avec serde(default)
+            PayloadFormat::MessagePack => rmp_serde::to_vec_named(value).map_err(|e| RepositoryError::PayloadError(self, e.to_string())),
+        }
+    }
+    
+    /// Désérialise une base de données depuis ce format
+    pub fn deserialize(self, bytes: &[u8]) -> Result<Database, RepositoryError> {
+        let mut db: Database = self.deserialize_value(bytes)?;
+        db.storage_format = self;
+        Ok(db)
+    }
+    
+    /// Désérialise une valeur quelconque depuis ce format
+    pub fn deserialize_value<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, RepositoryError> {
+        match self {
+            PayloadFormat::Json => Ok(serde_json::from_slice(bytes)?),
+            PayloadFormat::Cbor => ciborium::from_reader(bytes).map_err(|e| RepositoryError::PayloadError(self, e.to_string())),
+            PayloadFormat::MessagePack => rmp_serde::from_slice(bytes).map_err(|e| RepositoryError::PayloadError(self, e.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for PayloadFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PayloadFormat::Json => write!(f, "json"),
+            PayloadFormat::Cbor => write!(f, "cbor"),
+            PayloadFormat::MessagePack => write!(f, "msgpack"),
+        }
+    }
+}
+
+impl std::str::FromStr for PayloadFormat {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "json" => Ok(PayloadFormat::Json),
+            "cbor" => Ok(PayloadFormat::Cbor),
+            "msgpack" | "messagepack" => Ok(PayloadFormat::MessagePack),
+            other => Err(format!("Format inconnu: '{}' (json, cbor ou msgpack)", other)),
+        }
+    }
 }
 
 /// En-tête du fichier contenant les métadonnées nécessaires pour le déchiffrement
 #[derive(Debug, Serialize, Deserialize)]
-struct FileHeader {
-    salt: String,
-    iterations: u32,
+pub(super) struct FileHeader {
+    pub(super) salt: String,
+    
+    /// Nombre d'itérations PBKDF2 (fichiers des versions 1 et 2)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    iterations: Option<u32>,
+    
+    /// Algorithme et paramètres de dérivation de clé (à partir de la version 3)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kdf: Option<KdfParams>,
+    
+    /// Format des données en clair (JSON pour les fichiers créés avant son introduction)
+    #[serde(default)]
+    pub(super) format: PayloadFormat,
+}
+
+/// Identifiants binaires des formats de sérialisation (en-tête binaire)
+const FORMAT_JSON: u8 = 0;
+const FORMAT_CBOR: u8 = 1;
+const FORMAT_MSGPACK: u8 = 2;
+
+/// Lit un entier de 4 octets en big-endian
+fn read_u32(bytes: &mut &[u8]) -> Result<u32, RepositoryError> {
+    let mut buffer = [0u8; 4];
+    bytes.read_exact(&mut buffer)?;
+    Ok(u32::from_be_bytes(buffer))
+}
+
+impl FileHeader {
+    /// Paramètres de dérivation de clé annoncés par l'en-tête
+    pub(super) fn kdf_params(&self) -> Result<KdfParams, RepositoryError> {
+        match (self.kdf, self.iterations) {
+            (Some(kdf), _) => Ok(kdf),
+            (None, Some(iterations)) => Ok(KdfParams::Pbkdf2 { iterations }),
+            (None, None) => Err(RepositoryError::InvalidFileFormat),
+        }
+    }
+    
+    /// En-tête binaire (à partir de la version 4) : format (1 octet), algorithme de dérivation (1 octet) et ses
+    /// paramètres (entiers de 4 octets), longueur du sel (1 octet) puis le sel brut
+    fn to_binary(&self) -> Result<Vec<u8>, RepositoryError> {
+        let mut bytes = vec![match self.format {
+            PayloadFormat::Json => FORMAT_JSON,
+            PayloadFormat::Cbor => FORMAT_CBOR,
+            PayloadFormat::MessagePack => FORMAT_MSGPACK,
+        }];
+        
+        let kdf = self.kdf_params()?;
+        bytes.push(kdf.kdf().id());
+        for value in kdf.values() {
+            bytes.extend(value.to_be_bytes());
+        }
+        
+        let salt = general_purpose::STANDARD.decode(&self.salt).map_err(|_| RepositoryError::InvalidFileFormat)?;
+        let salt_len = u8::try_from(salt.len()).map_err(|_| RepositoryError::InvalidFileFormat)?;
+        bytes.push(salt_len);
+        bytes.extend(salt);
+        Ok(bytes)
+    }
+    
+    /// Lit un en-tête binaire (à partir de la version 4)
+    pub(super) fn from_binary(mut bytes: &[u8]) -> Result<Self, RepositoryError> {
+        let mut ids = [0u8; 2];
+        bytes.read_exact(&mut ids)?;
+        
+        let format = match ids[0] {
+            FORMAT_JSON => PayloadFormat::Json,
+            FORMAT_CBOR => PayloadFormat::Cbor,
+            FORMAT_MSGPACK => PayloadFormat::MessagePack,
+            _ => return Err(RepositoryError::InvalidFileFormat),
+        };
+        let algorithm = key_derivation::find(ids[1]).ok_or(RepositoryError::InvalidFileFormat)?;
+        let values = algorithm.parameters().iter().map(|_| read_u32(&mut bytes)).collect::<Result<Vec<_>, _>>()?;
+        let kdf = algorithm.params(&values).ok_or(RepositoryError::InvalidFileFormat)?;
+        
+        let mut salt_len = [0u8; 1];
+        bytes.read_exact(&mut salt_len)?;
+        let mut salt = vec![0u8; salt_len[0] as usize];
+        bytes.read_exact(&mut salt)?;
+        if !bytes.is_empty() {
+            return Err(RepositoryError::InvalidFileFormat);
+        }
+        
+        Ok(Self { salt: general_purpose::STANDARD.encode(salt), iterations: None, kdf: Some(kdf), format })
+    }
+}
+
+const STEP_IO: &str = "E/S fichier";
+const STEP_SERIALIZE: &str = "Sérialisation";
+const STEP_DESERIALIZE: &str = "Désérialisation";
+const STEP_KDF: &str = "Dérivation de clé";
+const STEP_ENCRYPT: &str = "Chiffrement";
+const STEP_DECRYPT: &str = "Déchiffrement";
+
+/// Durées des étapes d'un enregistrement ou d'un chargement
+#[derive(Debug, Clone, Default)]
+pub struct Timings {
+    /// Mesure activée (désactivée, aucune horloge n'est consultée : compatible wasm32)
+    enabled: bool,
+    
+    /// Étapes dans l'ordre d'exécution
+    pub steps: Vec<(&'static str, Duration)>,
+}
+
+impl Timings {
+    /// Crée un relevé de durées actif
+    pub fn new() -> Self {
+        Self { enabled: true, steps: Vec::new() }
+    }
+    
+    /// Crée un relevé inactif qui n'enregistre rien
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+    
+    /// Exécute une étape en mesurant sa durée si le relevé est actif
+    fn measure<T>(&mut self, step: &'static str, run: impl FnOnce() -> T) -> T {
+        if !self.enabled {
+            return run();
+        }
+        
+        let start = Instant::now();
+        let result = run();
+        self.steps.push((step, start.elapsed()));
+        result
+    }
+    
+    /// Durée totale des étapes mesurées
+    pub fn total(&self) -> Duration {
+        self.steps.iter().map(|(_, duration)| *duration).sum()
+    }
+}
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (step, duration) in &self.steps {
+            writeln!(f, "  {:<20} {:>10.2} ms", step, duration.as_secs_f64() * 1000.0)?;
+        }
+        write!(f, "  {:<20} {:>10.2} ms", "Total", self.total().as_secs_f64() * 1000.0)
+    }
 }
 
 /// Gère la persistance de la base de données
+#[derive(Clone)]
 pub struct Repository {
+    /// Fichier de la base (copie locale pour une base WebDAV)
     path: PathBuf,
+    
+    remote: Option<WebDav>,
+}
+
+/// État de la copie locale d'une base après `Repository::fetch`
+#[derive(Debug, PartialEq)]
+pub enum Fetch {
+    /// Base locale, sans serveur
+    Local,
+    
+    /// Nouvelle version téléchargée depuis le serveur
+    Downloaded,
+    
+    /// La copie locale est à jour
+    Unchanged,
+    
+    /// Serveur injoignable : la copie locale, peut-être ancienne, est lue hors ligne
+    Offline(String),
+}
+
+/// Clé dérivée d'un fichier et sel de l'en-tête dont elle provient : tant que le fichier n'est
+/// pas réenregistré (avec un nouveau sel), elle le déchiffre sans refaire la dérivation
+#[derive(Clone)]
+pub struct FileKey {
+    /// Sel de l'en-tête (encodé en base64)
+    pub salt: String,
+    
+    pub key: Zeroizing<[u8; 32]>,
 }
 
 impl Repository {
-    /// Crée un nouveau repository pointant vers le chemin spécifié
+    /// Crée un nouveau repository pointant vers le chemin spécifié, ou vers une base WebDAV
+    /// (`webdavs://hôte/chemin/coffre.krs`) lue et enregistrée à travers sa copie locale
     pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        let remote = path.as_ref().to_str().and_then(WebDav::parse);
         Self {
-            path: path.as_ref().to_path_buf(),
+            path: remote.as_ref().map_or_else(|| path.as_ref().to_path_buf(), WebDav::cache_path),
+            remote,
+        }
+    }
+    
+    /// Chemin du fichier de base de données
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    
+    /// Serveur WebDAV de la base, s'il y en a un
+    pub fn remote(&self) -> Option<&WebDav> {
+        self.remote.as_ref()
+    }
+    
+    /// Met à jour la copie locale d'une base WebDAV avant sa lecture : la version du serveur
+    /// n'est téléchargée que si son ETag a changé, et la copie existante reste lisible hors ligne
+    pub fn fetch(&self) -> Result<Fetch, RepositoryError> {
+        let Some(remote) = &self.remote else {
+            return Ok(Fetch::Local);
+        };
+        match remote.get(remote.cached_etag().as_deref()) {
+            Ok(Download::NotModified) => Ok(Fetch::Unchanged),
+            Ok(Download::Modified { bytes, etag }) => {
+                self.write_atomically(&bytes)?;
+                remote.store_etag(etag.as_deref())?;
+                Ok(Fetch::Downloaded)
+            }
+            Err(WebDavError::Unreachable(reason)) if self.path.exists() => Ok(Fetch::Offline(reason)),
+            Err(e) => Err(e.into()),
         }
     }
     
     /// Sauvegarde la base de données dans un fichier chiffré
+    #[allow(dead_code)]
     pub fn save(&self, db: &Database, password: &str) -> Result<(), RepositoryError> {
-        // Sérialiser la base de données en JSON
-        let json_data = serde_json::to_string(db)?;
+        self.save_timed(db, password, &mut Timings::disabled())
+    }
+    
+    /// Sauvegarde la base de données en mesurant la durée de chaque étape
+    pub fn save_timed(&self, db: &Database, password: &str, timings: &mut Timings) -> Result<(), RepositoryError> {
+        self.save_with_kdf(db, password, KdfParams::recommended(), timings)
+    }
+    
+    /// Sauvegarde la base de données avec les paramètres de dérivation de clé fournis
+    pub fn save_with_kdf(&self, db: &Database, password: &str, kdf: KdfParams, timings: &mut Timings) -> Result<(), RepositoryError> {
+        self.save_keyed(db, password, kdf, timings).map(|_| ())
+    }
+    
+    /// Version de `save_with_kdf` retournant la clé dérivée pour le nouveau sel du fichier
+    pub fn save_keyed(&self, db: &Database, password: &str, kdf: KdfParams, timings: &mut Timings) -> Result<FileKey, RepositoryError> {
+        let (bytes, key) = Self::encode_keyed(db, password, kdf, FILE_VERSION, timings)?;
+        
+        // Écrire dans le fichier
+        timings.measure(STEP_IO, || self.write(&bytes))?;
+        Ok(key)
+    }
+    
+    /// Sauvegarde la base de données avec la clé du fichier actuel, sans mot de passe : le sel et
+    /// les paramètres de dérivation (`kdf`, ceux du fichier) sont conservés
+    pub fn save_with_key(&self, db: &Database, key: &FileKey, kdf: KdfParams, timings: &mut Timings) -> Result<(), RepositoryError> {
+        let bytes = Self::encode_with_key(db, key, kdf, FILE_VERSION, timings)?;
+        timings.measure(STEP_IO, || self.write(&bytes))
+    }
+    
+    /// Chemin de la copie de la version précédente, conservée à chaque enregistrement
+    pub fn backup_path(&self) -> PathBuf {
+        let mut name = self.path.file_name().unwrap_or_default().to_os_string();
+        name.push(BACKUP_SUFFIX);
+        self.path.with_file_name(name)
+    }
+    
+    /// Vérifie, avant d'y créer une base, si le serveur WebDAV a déjà ce fichier; une copie
+    /// locale qui ne correspond plus à aucune version du serveur est oubliée
+    pub fn exists_on_server(&self) -> Result<bool, RepositoryError> {
+        let Some(remote) = &self.remote else {
+            return Ok(false);
+        };
+        match remote.head() {
+            Ok(_) => Ok(true),
+            Err(WebDavError::NotFound(_)) => {
+                remote.store_etag(None)?;
+                Ok(false)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+    
+    /// Enregistre le fichier; une base WebDAV est d'abord envoyée au serveur, à condition qu'il
+    /// ait toujours la version dont provient la copie locale (sinon elle est mise de côté)
+    fn write(&self, bytes: &[u8]) -> Result<(), RepositoryError> {
+        let Some(remote) = &self.remote else {
+            return self.write_atomically(bytes);
+        };
+        let etag = remote.cached_etag();
+        match remote.put(bytes, etag.as_deref()) {
+            Ok(uploaded) => {
+                self.write_atomically(bytes)?;
+                remote.store_etag(uploaded.as_deref())?;
+                Ok(())
+            }
+            Err(WebDavError::Conflict(location)) if etag.is_some() => {
+                let kept = self.path.with_extension("conflit.krs");
+                fs::write(&kept, bytes)?;
+                Err(RepositoryError::RemoteConflict(location, kept.display().to_string()))
+            }
+            Err(WebDavError::Conflict(location)) => Err(RepositoryError::RemoteExists(location)),
+            Err(e) => Err(e.into()),
+        }
+    }
+    
+    /// Remplace le fichier sans jamais le laisser à moitié écrit.
+    ///
+    /// Le contenu est écrit dans un fichier temporaire du même répertoire, synchronisé sur le
+    /// disque, puis renommé par-dessus l'original après avoir copié celui-ci dans `<fichier>.bak`.
+    fn write_atomically(&self, bytes: &[u8]) -> Result<(), RepositoryError> {
+        // Suivre un éventuel lien symbolique pour remplacer le fichier qu'il désigne (sous Windows,
+        // le chemin obtenu est étendu : chemins longs et partages UNC)
+        let target = fs::canonicalize(&self.path).unwrap_or_else(|_| paths::extended_length(&self.path));
+        let previous = fs::metadata(&target).ok().filter(|metadata| metadata.is_file());
+        
+        let mut temp_name = target.file_name().unwrap_or_default().to_os_string();
+        temp_name.push(format!(".{}.tmp", std::process::id()));
+        let temp_path = target.with_file_name(temp_name);
+        
+        let result = (|| -> Result<(), RepositoryError> {
+            let mut file = File::create(&temp_path)?;
+            if let Some(metadata) = &previous {
+                file.set_permissions(metadata.permissions())?;
+            }
+            file.write_all(bytes)?;
+            file.sync_all()?;
+            
+            if let Some(metadata) = &previous {
+                Self::new(&target).copy_to_backup(metadata)?;
+            }
+            fs::rename(&temp_path, &target)?;
+            Ok(())
+        })();
+        
+        if result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+            return result;
+        }
+        
+        // Rendre le renommage durable (sans effet sur les systèmes qui ne le permettent pas)
+        if let Some(directory) = target.parent().filter(|parent| !parent.as_os_str().is_empty())
+            && let Ok(directory) = File::open(directory)
+        {
+            let _ = directory.sync_all();
+        }
+        Ok(())
+    }
+    
+    /// Copie le fichier actuel dans `<fichier>.bak`, en conservant sa date de modification
+    fn copy_to_backup(&self, metadata: &fs::Metadata) -> Result<(), RepositoryError> {
+        let backup = self.backup_path();
+        fs::copy(&self.path, &backup)?;
         
+        let file = File::options().write(true).open(&backup)?;
+        if let Ok(modified) = metadata.modified() {
+            file.set_modified(modified)?;
+        }
+        file.sync_all()?;
+        Ok(())
+    }
+    
+    /// Charge la base de données à partir d'un fichier chiffré
+    #[allow(dead_code)]
+    pub fn load(&self, password: &str) -> Result<Database, RepositoryError> {
+        self.load_timed(password, &mut Timings::disabled())
+    }
+    
+    /// Charge la base de données en mesurant la durée de chaque étape
+    pub fn load_timed(&self, password: &str, timings: &mut Timings) -> Result<Database, RepositoryError> {
+        self.load_keyed(password, None, timings).map(|(db, _)| db)
+    }
+    
+    /// Charge la base de données avec `known` si elle correspond au sel du fichier, sans refaire
+    /// la dérivation de clé; retourne aussi la clé utilisée
+    pub fn load_keyed(&self, password: &str, known: Option<&FileKey>, timings: &mut Timings) -> Result<(Database, FileKey), RepositoryError> {
+        // Lire le fichier
+        let bytes = timings.measure(STEP_IO, || -> Result<Vec<u8>, RepositoryError> {
+            let mut bytes = Vec::new();
+            File::open(paths::extended_length(&self.path))?.read_to_end(&mut bytes)?;
+            Ok(bytes)
+        })?;
+        
+        Self::decode_keyed(&bytes, password, known, timings)
+    }
+    
+    /// Lit les paramètres de dérivation de clé du fichier, sans le déchiffrer
+    pub fn read_kdf_params(&self) -> Result<KdfParams, RepositoryError> {
+        let mut bytes = Vec::new();
+        File::open(paths::extended_length(&self.path))?.read_to_end(&mut bytes)?;
+        Self::kdf_params(&bytes)
+    }
+    
+    /// Paramètres de dérivation de clé annoncés par l'en-tête d'un fichier
+    pub fn kdf_params(bytes: &[u8]) -> Result<KdfParams, RepositoryError> {
+        RawFile::parse(bytes, None, true)?.header.kdf_params()
+    }
+    
+    /// Chiffre la base de données au format de fichier KeepRust, sans accès au système de fichiers
+    pub fn encode(db: &Database, password: &str) -> Result<Vec<u8>, RepositoryError> {
+        Self::encode_timed(db, password, &mut Timings::disabled())
+    }
+    
+    /// Version de `encode` mesurant la durée de chaque étape
+    pub fn encode_timed(db: &Database, password: &str, timings: &mut Timings) -> Result<Vec<u8>, RepositoryError> {
+        Self::encode_with_kdf(db, password, KdfParams::recommended(), timings)
+    }
+    
+    /// Chiffre la base de données dans un conteneur par blocs (version 5), avec les paramètres de dérivation fournis
+    pub fn encode_with_kdf(db: &Database, password: &str, kdf: KdfParams, timings: &mut Timings) -> Result<Vec<u8>, RepositoryError> {
+        Self::encode_chunked(db, password, kdf, FILE_VERSION, timings)
+    }
+    
+    /// Écrit un conteneur par blocs avec l'octet de version fourni; l'en-tête n'est authentifié
+    /// avec chaque bloc qu'à partir de la version 5
+    fn encode_chunked(db: &Database, password: &str, kdf: KdfParams, version: u8, timings: &mut Timings) -> Result<Vec<u8>, RepositoryError> {
+        Self::encode_keyed(db, password, kdf, version, timings).map(|(bytes, _)| bytes)
+    }
+    
+    /// Version de `encode_chunked` retournant aussi la clé dérivée
+    fn encode_keyed(db: &Database, password: &str, kdf: KdfParams, version: u8, timings: &mut Timings) -> Result<(Vec<u8>, FileKey), RepositoryError> {
         // Dériver une clé à partir du mot de passe
-        let iterations = 100_000; // Un nombre élevé d'itérations pour une meilleure sécurité
-        let master_key = key_derivation::derive_key_from_password(password, iterations)?;
+        let master_key = timings.measure(STEP_KDF, || key_derivation::derive_key_from_password(password, kdf))?;
+        let key = FileKey { salt: master_key.salt, key: Zeroizing::new(master_key.key) };
         
-        // Chiffrer les données JSON
-        let encrypted_data = cipher::encrypt(json_data.as_bytes(), &master_key.key)?;
+        let bytes = Self::encode_with_key(db, &key, kdf, version, timings)?;
+        Ok((bytes, key))
+    }
+    
+    /// Écrit un conteneur par blocs chiffré avec une clé déjà dérivée (`kdf` : paramètres qui l'ont produite)
+    fn encode_with_key(db: &Database, key: &FileKey, kdf: KdfParams, version: u8, timings: &mut Timings) -> Result<Vec<u8>, RepositoryError> {
+        // Sérialiser le squelette puis chaque lot d'entrées dans le format de stockage
+        let format = db.storage_format;
+        let plaintexts = timings.measure(STEP_SERIALIZE, || -> Result<Vec<Zeroizing<Vec<u8>>>, RepositoryError> {
+            let (skeleton, batches) = chunks::split(db);
+            let mut plaintexts = vec![Zeroizing::new(format.serialize(&skeleton)?)];
+            for batch in &batches {
+                plaintexts.push(Zeroizing::new(format.serialize(batch)?));
+            }
+            Ok(plaintexts)
+        })?;
         
-        // Créer un en-tête avec le sel et les itérations
+        // Créer un en-tête avec le sel et les paramètres de dérivation
         let header = FileHeader {
-            salt: master_key.salt.clone(),
-            iterations,
+            salt: key.salt.clone(),
+            iterations: None,
+            kdf: Some(kdf),
+            format,
         };
         
-        // Sérialiser l'en-tête
-        let header_json = serde_json::to_string(&header)?;
-        let header_length = header_json.len() as u16;
-        
-        // Écrire dans le fichier
-        let mut file = File::create(&self.path)?;
-        
-        // Écrire la signature magique
-        file.write_all(FILE_HEADER)?;
+        // Signature, version, longueur de l'en-tête (2 octets en big-endian) puis l'en-tête
+        let mut bytes = file_prefix(version, &header.to_binary()?);
+        let authenticated_header = if version == FILE_VERSION { bytes.clone() } else { Vec::new() };
         
-        // Écrire la version
-        file.write_all(&[FILE_VERSION])?;
+        // Chiffrer chaque bloc indépendamment
+        let count = plaintexts.len() as u32;
+        let encrypted_chunks = timings.measure(STEP_ENCRYPT, || {
+            plaintexts.iter()
+                .enumerate()
+                .map(|(index, plaintext)| cipher::encrypt_chunk(plaintext, &key.key, &chunk_aad(&authenticated_header, index as u32, count)))
+                .collect::<Result<Vec<_>, _>>()
+        })?;
         
-        // Écrire la longueur de l'en-tête (2 octets en big-endian)
-        file.write_all(&header_length.to_be_bytes())?;
+        // Écrire le nombre de blocs puis chaque bloc (nonce, longueur, données chiffrées)
+        bytes.write_all(&count.to_be_bytes())?;
+        for chunk in &encrypted_chunks {
+            bytes.write_all(&chunk.nonce)?;
+            bytes.write_all(&(chunk.ciphertext.len() as u32).to_be_bytes())?;
+            bytes.write_all(&chunk.ciphertext)?;
+        }
         
-        // Écrire l'en-tête
-        file.write_all(header_json.as_bytes())?;
+        Ok(bytes)
+    }
+    
+    /// Déchiffre une base de données à partir du contenu d'un fichier KeepRust
+    pub fn decode(bytes: &[u8], password: &str) -> Result<Database, RepositoryError> {
+        Self::decode_timed(bytes, password, &mut Timings::disabled())
+    }
+    
+    /// Version de `decode` mesurant la durée de chaque étape
+    pub fn decode_timed(bytes: &[u8], password: &str, timings: &mut Timings) -> Result<Database, RepositoryError> {
+        Self::decode_keyed(bytes, password, None, timings).map(|(db, _)| db)
+    }
+    
+    /// Version de `decode` utilisant `known` si elle correspond au sel du fichier
+    fn decode_keyed(bytes: &[u8], password: &str, known: Option<&FileKey>, timings: &mut Timings) -> Result<(Database, FileKey), RepositoryError> {
+        let file = RawFile::parse(bytes, None, false)?;
+        let file_key = match known.filter(|known| known.salt == file.header.salt) {
+            Some(known) => known.clone(),
+            None => FileKey { salt: file.header.salt.clone(), key: file.derive_key(password, timings)? },
+        };
+        let key = &file_key.key;
         
-        // Écrire les données chiffrées
-        let encrypted_json = serde_json::to_string(&encrypted_data)?;
-        file.write_all(encrypted_json.as_bytes())?;
+        let db = match file.body {
+            RawBody::Single(encrypted_data) => {
+                let plaintext = decrypt_single(&encrypted_data, key, timings)?;
+                
+                // Désérialiser selon le format annoncé dans l'en-tête
+                timings.measure(STEP_DESERIALIZE, || file.header.format.deserialize(&plaintext))?
+            }
+            RawBody::Chunks { count, chunks } => {
+                assemble_chunks(file.header.format, &file.authenticated_header, count, &chunks, key, false, timings)?.0
+            }
+        };
+        Ok((db, file_key))
+    }
+    
+    /// Déchiffre le contenu d'un fichier version 1 (bloc unique) sans le désérialiser.
+    ///
+    /// Retourne le format annoncé par l'en-tête et les données en clair. `version` force
+    /// la disposition utilisée pour la lecture, quel que soit l'octet de version du fichier.
+    pub fn decrypt_payload(bytes: &[u8], password: &str, version: Option<u8>, timings: &mut Timings) -> Result<(PayloadFormat, Zeroizing<Vec<u8>>), RepositoryError> {
+        let file = RawFile::parse(bytes, version, false)?;
+        let RawBody::Single(encrypted_data) = &file.body else {
+            return Err(RepositoryError::UnsupportedFileVersion);
+        };
         
-        Ok(())
+        let key = file.derive_key(password, timings)?;
+        let plaintext = decrypt_single(encrypted_data, &key, timings)?;
+        Ok((file.header.format, plaintext))
     }
     
-    /// Charge la base de données à partir d'un fichier chiffré
-    pub fn load(&self, password: &str) -> Result<Database, RepositoryError> {
-        // Lire le fichier
-        let mut file = File::open(&self.path)?;
+    /// Relit un conteneur par blocs en abandonnant les blocs endommagés ou manquants.
+    ///
+    /// Retourne la base reconstituée, le nombre de blocs perdus et le nombre total de blocs.
+    pub fn salvage_chunks(bytes: &[u8], password: &str, version: Option<u8>) -> Result<(Database, usize, usize), RepositoryError> {
+        let mut timings = Timings::disabled();
+        let file = RawFile::parse(bytes, version, true)?;
+        let RawBody::Chunks { count, chunks } = &file.body else {
+            return Err(RepositoryError::UnsupportedFileVersion);
+        };
         
+        let key = file.derive_key(password, &mut timings)?;
+        let (db, lost) = assemble_chunks(file.header.format, &file.authenticated_header, *count, chunks, &key, true, &mut timings)?;
+        Ok((db, lost, *count as usize))
+    }
+    
+    /// Vérifie si le fichier de base de données existe
+    #[allow(dead_code)]
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+}
+
+/// Contenu d'un fichier analysé mais pas encore déchiffré
+struct RawFile {
+    header: FileHeader,
+    
+    /// Début du fichier (signature, version et en-tête) authentifié avec chaque bloc; vide avant la version 5
+    authenticated_header: Vec<u8>,
+    
+    body: RawBody,
+}
+
+/// Données chiffrées selon la version du fichier
+enum RawBody {
+    /// Version 1 : un seul bloc encodé en JSON/base64
+    Single(cipher::EncryptedData),
+    
+    /// Versions 2 à 5 : blocs authentifiés indépendamment (éventuellement moins que `count` si le fichier est tronqué)
+    Chunks { count: u32, chunks: Vec<cipher::EncryptedChunk> },
+}
+
+impl RawFile {
+    /// Analyse la structure d'un fichier.
+    ///
+    /// `version` force la disposition à utiliser; avec `lenient`, la lecture des blocs s'arrête
+    /// au premier bloc tronqué au lieu d'échouer.
+    fn parse(mut bytes: &[u8], version: Option<u8>, lenient: bool) -> Result<Self, RepositoryError> {
         // Lire et vérifier la signature magique
         let mut magic = [0u8; 8];
-        file.read_exact(&mut magic)?;
+        bytes.read_exact(&mut magic)?;
         
-        if &magic != FILE_HEADER {
+        if magic != FILE_HEADER {
             return Err(RepositoryError::InvalidFileFormat);
         }
         
-        // Lire et vérifier la version
-        let mut version = [0u8; 1];
-        file.read_exact(&mut version)?;
+        // Lire la version
+        let mut version_byte = [0u8; 1];
+        bytes.read_exact(&mut version_byte)?;
+        let version = version.unwrap_or(version_byte[0]);
         
-        if version[0] != FILE_VERSION {
+        if ![FILE_VERSION_V1, FILE_VERSION_V2, FILE_VERSION_V3, FILE_VERSION_V4, FILE_VERSION].contains(&version) {
             return Err(RepositoryError::UnsupportedFileVersion);
         }
         
         // Lire la longueur de l'en-tête
         let mut header_len_bytes = [0u8; 2];
-        file.read_exact(&mut header_len_bytes)?;
+        bytes.read_exact(&mut header_len_bytes)?;
         let header_len = u16::from_be_bytes(header_len_bytes) as usize;
         
         // Lire l'en-tête
         let mut header_bytes = vec![0u8; header_len];
-        file.read_exact(&mut header_bytes)?;
+        bytes.read_exact(&mut header_bytes)?;
         
-        let header: FileHeader = serde_json::from_slice(&header_bytes)?;
+        // En-tête binaire à partir de la version 4, JSON auparavant
+        let header = if version >= FILE_VERSION_V4 {
+            FileHeader::from_binary(&header_bytes)?
+        } else {
+            serde_json::from_slice(&header_bytes)?
+        };
         
-        // Lire le reste du fichier (données chiffrées)
-        let mut encrypted_bytes = Vec::new();
-        file.read_to_end(&mut encrypted_bytes)?;
+        // Reconstitué avec la version retenue, pour relire un fichier dont l'octet de version est endommagé
+        let authenticated_header = if version == FILE_VERSION { file_prefix(version, &header_bytes) } else { Vec::new() };
         
-        let encrypted_data: cipher::EncryptedData = serde_json::from_slice(&encrypted_bytes)?;
+        // Le reste du contenu correspond aux données chiffrées
+        let body = if version == FILE_VERSION_V1 {
+            RawBody::Single(serde_json::from_slice(bytes)?)
+        } else {
+            let mut count_bytes = [0u8; 4];
+            bytes.read_exact(&mut count_bytes)?;
+            let count = u32::from_be_bytes(count_bytes);
+            
+            let mut chunks = Vec::new();
+            for index in 0..count {
+                match read_chunk(&mut bytes) {
+                    Ok(chunk) => chunks.push(chunk),
+                    Err(_) if lenient => break,
+                    Err(_) => return Err(RepositoryError::CorruptedChunk(index as usize)),
+                }
+            }
+            RawBody::Chunks { count, chunks }
+        };
         
-        // Dériver la clé à partir du mot de passe et du sel
-        let master_key = key_derivation::verify_and_derive_key(
+        Ok(Self { header, authenticated_header, body })
+    }
+    
+    /// Dérive la clé à partir du mot de passe, du sel et des paramètres de l'en-tête
+    fn derive_key(&self, password: &str, timings: &mut Timings) -> Result<Zeroizing<[u8; 32]>, RepositoryError> {
+        // Paramètres lus dans l'en-tête, non authentifié : bornés avant de réserver quoi que ce soit
+        let params = self.header.kdf_params()?;
+        params.validate()?;
+        let master_key = timings.measure(STEP_KDF, || key_derivation::verify_and_derive_key(
             password, 
-            &header.salt, 
-            header.iterations
-        )?;
-        
-        // Déchiffrer les données
-        let json_data = match cipher::decrypt(&encrypted_data, &master_key.key) {
-            Ok(data) => data,
-            Err(_) => return Err(RepositoryError::InvalidPassword),
+            &self.header.salt, 
+            params
+        ))?;
+        Ok(Zeroizing::new(master_key.key))
+    }
+}
+
+/// Lit un bloc : nonce, longueur (4 octets en big-endian) puis données chiffrées
+fn read_chunk(bytes: &mut &[u8]) -> Result<cipher::EncryptedChunk, RepositoryError> {
+    let mut nonce = [0u8; cipher::NONCE_LEN];
+    bytes.read_exact(&mut nonce)?;
+    
+    let mut len_bytes = [0u8; 4];
+    bytes.read_exact(&mut len_bytes)?;
+    let len = u32::from_be_bytes(len_bytes) as usize;
+    
+    // Une longueur incohérente indique un fichier tronqué ou endommagé
+    if len > bytes.len() {
+        return Err(RepositoryError::InvalidFileFormat);
+    }
+    
+    let (ciphertext, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Ok(cipher::EncryptedChunk { nonce, ciphertext: ciphertext.to_vec() })
+}
+
+/// Début du fichier : signature, version, longueur de l'en-tête (2 octets en big-endian) et en-tête
+fn file_prefix(version: u8, header: &[u8]) -> Vec<u8> {
+    let mut bytes = FILE_HEADER.to_vec();
+    bytes.push(version);
+    bytes.extend((header.len() as u16).to_be_bytes());
+    bytes.extend(header);
+    bytes
+}
+
+/// Données associées authentifiées avec chaque bloc : le début du fichier (version 5), la position
+/// du bloc et le nombre total de blocs.
+///
+/// Une modification de l'en-tête (sel, paramètres de dérivation, format) ou des blocs supprimés,
+/// dupliqués ou réordonnés font échouer le déchiffrement.
+fn chunk_aad(header: &[u8], index: u32, count: u32) -> Vec<u8> {
+    let mut aad = header.to_vec();
+    aad.extend(index.to_be_bytes());
+    aad.extend(count.to_be_bytes());
+    aad
+}
+
+/// Déchiffre le bloc unique d'un fichier version 1
+fn decrypt_single(encrypted_data: &cipher::EncryptedData, key: &[u8; 32], timings: &mut Timings) -> Result<Zeroizing<Vec<u8>>, RepositoryError> {
+    match timings.measure(STEP_DECRYPT, || cipher::decrypt(encrypted_data, key)) {
+        Ok(data) => Ok(Zeroizing::new(data)),
+        Err(_) => Err(RepositoryError::InvalidPassword),
+    }
+}
+
+/// Déchiffre et réassemble les blocs d'un fichier version 2 à 5.
+///
+/// En mode strict, le premier bloc illisible provoque une erreur (mot de passe invalide si aucun
+/// bloc n'est lisible). Avec `lenient`, les blocs illisibles sont abandonnés et comptés.
+fn assemble_chunks(format: PayloadFormat, authenticated_header: &[u8], count: u32, chunks: &[cipher::EncryptedChunk], key: &[u8; 32], lenient: bool, timings: &mut Timings) -> Result<(Database, usize), RepositoryError> {
+    let plaintexts: Vec<Option<Zeroizing<Vec<u8>>>> = timings.measure(STEP_DECRYPT, || {
+        chunks.iter()
+            .enumerate()
+            .map(|(index, chunk)| cipher::decrypt_chunk(chunk, key, &chunk_aad(authenticated_header, index as u32, count)).ok().map(Zeroizing::new))
+            .collect()
+    });
+    
+    if plaintexts.iter().all(Option::is_none) {
+        return Err(RepositoryError::InvalidPassword);
+    }
+    
+    let missing = count as usize - chunks.len();
+    if !lenient {
+        if let Some(index) = plaintexts.iter().position(Option::is_none) {
+            return Err(RepositoryError::CorruptedChunk(index));
+        }
+        if missing > 0 {
+            return Err(RepositoryError::CorruptedChunk(chunks.len()));
+        }
+    }
+    
+    timings.measure(STEP_DESERIALIZE, || {
+        let mut lost = missing;
+        let mut plaintexts = plaintexts.into_iter();
+        
+        // Le premier bloc contient le squelette (métadonnées et groupes)
+        let skeleton = plaintexts.next().flatten().map(|plaintext| format.deserialize(&plaintext));
+        let mut db = match skeleton {
+            Some(Ok(db)) => db,
+            Some(Err(e)) if !lenient => return Err(e),
+            _ => {
+                lost += 1;
+                let mut db = Database::new("Base récupérée".to_string(), String::new());
+                db.storage_format = format;
+                db
+            }
         };
         
-        // Désérialiser en Database
-        let db: Database = serde_json::from_slice(&json_data)?;
+        // Les blocs suivants contiennent les entrées
+        let mut entries = Vec::new();
+        for plaintext in plaintexts {
+            match plaintext.map(|plaintext| format.deserialize_value::<chunks::EntryBatch>(&plaintext)) {
+                Some(Ok(batch)) => entries.extend(batch),
+                Some(Err(e)) if !lenient => return Err(e),
+                _ => lost += 1,
+            }
+        }
         
-        Ok(db)
+        chunks::assemble(&mut db, entries);
+        db.is_modified = false;
+        Ok((db, lost))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    use crate::models::group::Group;
+    use std::time::Instant;
+    
+    const FORMATS: [PayloadFormat; 3] = [PayloadFormat::Json, PayloadFormat::Cbor, PayloadFormat::MessagePack];
+    
+    fn sample_database(entries: usize) -> Database {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let mut group = Group::new("Work".to_string(), Some(db.root_group.id));
+        for i in 0..entries {
+            let entry = Entry::builder()
+                .title(format!("Entrée {}", i))
+                .username("bob")
+                .password("S3cret!pass")
+                .url("https://example.com")
+                .tag("travail")
+                .build()
+                .unwrap();
+            group.add_entry(entry);
+        }
+        db.root_group.add_subgroup(group);
+        db
     }
     
-    /// Vérifie si le fichier de base de données existe
-    #[allow(dead_code)]
-    pub fn exists(&self) -> bool {
-        self.path.exists()
+    #[test]
+    fn test_payload_formats_round_trip() {
+        let db = sample_database(3);
+        
+        for format in FORMATS {
+            let bytes = format.serialize(&db).unwrap();
+            let restored = format.deserialize(&bytes).unwrap();
+            
+            assert_eq!(restored.storage_format, format);
+            assert_eq!(restored.count_entries(), 3);
+            assert_eq!(serde_json::to_value(&restored).unwrap(), serde_json::to_value(&db).unwrap());
+        }
+    }
+    
+    /// Écrit un fichier au format version 1 (bloc unique JSON/base64)
+    fn encode_v1(db: &Database, password: &str) -> Vec<u8> {
+        let master_key = key_derivation::derive_key_from_password(password, KdfParams::Pbkdf2 { iterations: 10 }).unwrap();
+        let encrypted = cipher::encrypt(&serde_json::to_vec(db).unwrap(), &master_key.key).unwrap();
+        let header = serde_json::to_string(&FileHeader { salt: master_key.salt, iterations: Some(10), kdf: None, format: PayloadFormat::Json }).unwrap();
+        
+        let mut bytes = FILE_HEADER.to_vec();
+        bytes.push(FILE_VERSION_V1);
+        bytes.extend((header.len() as u16).to_be_bytes());
+        bytes.extend(header.as_bytes());
+        bytes.extend(serde_json::to_vec(&encrypted).unwrap());
+        bytes
+    }
+    
+    #[test]
+    fn test_version_1_files_are_still_readable() {
+        let db = sample_database(3);
+        let restored = Repository::decode(&encode_v1(&db, "pw"), "pw").unwrap();
+        
+        assert_eq!(restored.count_entries(), 3);
+        assert!(matches!(Repository::decode(&encode_v1(&db, "pw"), "autre"), Err(RepositoryError::InvalidPassword)));
+    }
+    
+    /// Réécrit un fichier version 4 avec l'en-tête JSON d'une version antérieure
+    fn with_json_header(bytes: &[u8], version: u8, header: impl Fn(&FileHeader) -> serde_json::Value) -> Vec<u8> {
+        let header_len = u16::from_be_bytes([bytes[9], bytes[10]]) as usize;
+        let legacy = header(&FileHeader::from_binary(&bytes[11..11 + header_len]).unwrap()).to_string();
+        
+        let mut rewritten = FILE_HEADER.to_vec();
+        rewritten.push(version);
+        rewritten.extend((legacy.len() as u16).to_be_bytes());
+        rewritten.extend(legacy.as_bytes());
+        rewritten.extend(&bytes[11 + header_len..]);
+        rewritten
+    }
+    
+    /// Écrit un fichier au format version 2 (blocs, en-tête PBKDF2 sans paramètres Argon2id)
+    fn encode_v2(db: &Database, password: &str) -> Vec<u8> {
+        let bytes = Repository::encode_chunked(db, password, KdfParams::Pbkdf2 { iterations: 10 }, FILE_VERSION_V4, &mut Timings::disabled()).unwrap();
+        with_json_header(&bytes, FILE_VERSION_V2, |header| serde_json::json!({ "salt": header.salt, "iterations": 10, "format": header.format }))
+    }
+    
+    #[test]
+    fn test_version_2_files_are_still_readable() {
+        let db = sample_database(3);
+        let bytes = encode_v2(&db, "pw");
+        
+        assert_eq!(Repository::decode(&bytes, "pw").unwrap().count_entries(), 3);
+        assert_eq!(Repository::kdf_params(&bytes).unwrap(), KdfParams::Pbkdf2 { iterations: 10 });
+        assert!(matches!(Repository::decode(&bytes, "autre"), Err(RepositoryError::InvalidPassword)));
+    }
+    
+    #[test]
+    fn test_version_3_json_header_is_still_readable() {
+        let db = sample_database(2);
+        let kdf = KdfParams::Argon2id { memory_kib: 64, iterations: 1, parallelism: 1 };
+        let bytes = Repository::encode_chunked(&db, "pw", kdf, FILE_VERSION_V4, &mut Timings::disabled()).unwrap();
+        assert_eq!(Repository::decode(&bytes, "pw").unwrap().count_entries(), 2);
+        
+        // En-tête binaire : format, algorithme, 3 paramètres, longueur et octets du sel
+        assert_eq!(u16::from_be_bytes([bytes[9], bytes[10]]), 1 + 1 + 12 + 1 + 16);
+        
+        let v3 = with_json_header(&bytes, FILE_VERSION_V3, |header| serde_json::to_value(header).unwrap());
+        assert_eq!(Repository::decode(&v3, "pw").unwrap().count_entries(), 2);
+        assert_eq!(Repository::kdf_params(&v3).unwrap(), kdf);
+    }
+    
+    #[test]
+    fn test_header_tampering_is_detected() {
+        let db = sample_database(2);
+        let bytes = Repository::encode_with_kdf(&db, "pw", KdfParams::Pbkdf2 { iterations: 10 }, &mut Timings::disabled()).unwrap();
+        assert_eq!(bytes[8], FILE_VERSION);
+        assert_eq!(Repository::decode(&bytes, "pw").unwrap().count_entries(), 2);
+        
+        // Format de sérialisation (JSON -> CBOR) : la clé reste la même, mais plus l'en-tête authentifié
+        let mut tampered = bytes.clone();
+        assert_eq!(tampered[11], FORMAT_JSON);
+        tampered[11] = FORMAT_CBOR;
+        assert!(matches!(Repository::decode(&tampered, "pw"), Err(RepositoryError::InvalidPassword)));
+        
+        // Fichier présenté comme une version 4 (en-tête non authentifié)
+        let mut downgraded = bytes.clone();
+        downgraded[8] = FILE_VERSION_V4;
+        assert!(matches!(Repository::decode(&downgraded, "pw"), Err(RepositoryError::InvalidPassword)));
+        
+        // Octet de version endommagé : la version forcée permet encore la récupération
+        assert_eq!(Repository::salvage_chunks(&downgraded, "pw", Some(FILE_VERSION)).unwrap().1, 0);
+    }
+    
+    #[test]
+    fn test_hostile_kdf_parameters_are_rejected_before_derivation() {
+        let db = sample_database(1);
+        let argon2 = KdfParams::Argon2id { memory_kib: 64, iterations: 1, parallelism: 1 };
+        let bytes = Repository::encode_with_kdf(&db, "pw", argon2, &mut Timings::disabled()).unwrap();
+        assert_eq!(bytes[12], 2);
+        
+        // En-tête binaire : format, algorithme, puis mémoire, passes et voies (4 octets chacun)
+        for offset in [13, 17, 21] {
+            let mut tampered = bytes.clone();
+            tampered[offset..offset + 4].copy_from_slice(&u32::MAX.to_be_bytes());
+            assert!(matches!(Repository::decode(&tampered, "pw"), Err(RepositoryError::KeyDerivationError(_))));
+        }
+        
+        let mut pbkdf2 = Repository::encode_with_kdf(&db, "pw", KdfParams::Pbkdf2 { iterations: 10 }, &mut Timings::disabled()).unwrap();
+        pbkdf2[13..17].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(matches!(Repository::decode(&pbkdf2, "pw"), Err(RepositoryError::KeyDerivationError(_))));
+    }
+    
+    #[test]
+    fn test_damaged_chunk_only_loses_its_entries() {
+        let db = sample_database(chunks::ENTRIES_PER_CHUNK * 2 + 5);
+        let mut bytes = Repository::encode_with_kdf(&db, "pw", KdfParams::Pbkdf2 { iterations: 10 }, &mut Timings::disabled()).unwrap();
+        assert_eq!(Repository::decode(&bytes, "pw").unwrap().count_entries(), db.count_entries());
+        
+        // Altérer un octet du dernier bloc (le plus petit lot d'entrées)
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        
+        assert!(matches!(Repository::decode(&bytes, "pw"), Err(RepositoryError::CorruptedChunk(3))));
+        
+        let (salvaged, lost, total) = Repository::salvage_chunks(&bytes, "pw", None).unwrap();
+        assert_eq!((lost, total), (1, 4));
+        assert_eq!(salvaged.count_entries(), chunks::ENTRIES_PER_CHUNK * 2);
+        assert_eq!(salvaged.count_groups(), db.count_groups());
+    }
+    
+    #[test]
+    fn test_save_keeps_previous_version_as_backup() {
+        let directory = std::env::temp_dir().join(format!("keeprust-save-{}", crate::models::id::GroupId::new().short()));
+        fs::create_dir_all(&directory).unwrap();
+        let repo = Repository::new(directory.join("coffre.krs"));
+        let cheap = KdfParams::Pbkdf2 { iterations: 10 };
+        
+        repo.save_with_kdf(&sample_database(1), "pw", cheap, &mut Timings::disabled()).unwrap();
+        assert!(!repo.backup_path().exists());
+        
+        repo.save_with_kdf(&sample_database(2), "pw", cheap, &mut Timings::disabled()).unwrap();
+        assert_eq!(repo.load("pw").unwrap().count_entries(), 2);
+        assert_eq!(Repository::new(repo.backup_path()).load("pw").unwrap().count_entries(), 1);
+        assert_eq!(fs::read_dir(&directory).unwrap().count(), 2);
+        
+        fs::remove_dir_all(&directory).unwrap();
+    }
+    
+    #[test]
+    fn test_known_key_skips_derivation_until_next_save() {
+        let directory = std::env::temp_dir().join(format!("keeprust-key-{}", crate::models::id::GroupId::new().short()));
+        fs::create_dir_all(&directory).unwrap();
+        let repo = Repository::new(directory.join("coffre.krs"));
+        let cheap = KdfParams::Pbkdf2 { iterations: 10 };
+        
+        let key = repo.save_keyed(&sample_database(1), "pw", cheap, &mut Timings::disabled()).unwrap();
+        let (db, loaded) = repo.load_keyed("pw", None, &mut Timings::disabled()).unwrap();
+        assert_eq!(db.count_entries(), 1);
+        assert_eq!((loaded.salt.as_str(), *loaded.key), (key.salt.as_str(), *key.key));
+        
+        // La clé connue suffit : le mot de passe n'est pas dérivé
+        assert!(repo.load_keyed("autre", Some(&key), &mut Timings::disabled()).is_ok());
+        
+        // Après un nouvel enregistrement (nouveau sel), elle est ignorée
+        repo.save_keyed(&sample_database(2), "pw", cheap, &mut Timings::disabled()).unwrap();
+        assert!(matches!(repo.load_keyed("autre", Some(&key), &mut Timings::disabled()), Err(RepositoryError::InvalidPassword)));
+        assert_eq!(repo.load_keyed("pw", Some(&key), &mut Timings::disabled()).unwrap().0.count_entries(), 2);
+        
+        fs::remove_dir_all(&directory).unwrap();
+    }
+    
+    #[test]
+    fn test_save_with_known_key_keeps_salt_and_password() {
+        let directory = std::env::temp_dir().join(format!("keeprust-known-{}", crate::models::id::GroupId::new().short()));
+        fs::create_dir_all(&directory).unwrap();
+        let repo = Repository::new(directory.join("coffre.krs"));
+        let cheap = KdfParams::Pbkdf2 { iterations: 10 };
+        
+        // Sans mot de passe (clé confiée à l'agent) : même sel, même clé, toujours le même mot de passe
+        let key = repo.save_keyed(&sample_database(1), "pw", cheap, &mut Timings::disabled()).unwrap();
+        repo.save_with_key(&sample_database(2), &key, cheap, &mut Timings::disabled()).unwrap();
+        let (db, loaded) = repo.load_keyed("pw", None, &mut Timings::disabled()).unwrap();
+        assert_eq!(db.count_entries(), 2);
+        assert_eq!((loaded.salt.as_str(), *loaded.key), (key.salt.as_str(), *key.key));
+        assert!(repo.load_keyed("autre", Some(&key), &mut Timings::disabled()).is_ok());
+        
+        fs::remove_dir_all(&directory).unwrap();
+    }
+    
+    #[test]
+    fn test_kdf_params_are_read_without_password() {
+        let db = sample_database(1);
+        let weak = Repository::encode_with_kdf(&db, "pw", KdfParams::Pbkdf2 { iterations: 10 }, &mut Timings::disabled()).unwrap();
+        assert_eq!(Repository::kdf_params(&weak).unwrap(), KdfParams::Pbkdf2 { iterations: 10 });
+        assert!(Repository::kdf_params(&weak).unwrap().is_weak(MIN_ITERATIONS));
+        
+        let current = Repository::encode(&db, "pw").unwrap();
+        assert_eq!(Repository::kdf_params(&current).unwrap(), KdfParams::recommended());
+        assert!(!KdfParams::recommended().is_weak(MIN_ITERATIONS));
+        assert!(Repository::kdf_params(&encode_v1(&db, "pw")).unwrap().is_weak(MIN_ITERATIONS));
+    }
+    
+    /// Mesure la taille et le temps de (dé)sérialisation de chaque format.
+    /// Lancer avec `cargo test --release bench_payload_formats -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_payload_formats() {
+        let db = sample_database(10_000);
+        
+        for format in FORMATS {
+            let start = Instant::now();
+            let bytes = format.serialize(&db).unwrap();
+            let serialize = start.elapsed();
+            
+            let start = Instant::now();
+            format.deserialize(&bytes).unwrap();
+            let deserialize = start.elapsed();
+            
+            println!("{}: {} octets, sérialisation {:?}, désérialisation {:?}", format, bytes.len(), serialize, deserialize);
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,153 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use super::device::DeviceStamp;
+use super::entry::Entry;
+
+/// Nombre de modifications conservées par entrée (les plus anciennes sont oubliées)
+pub const MAX_REVISIONS: usize = 50;
+
+/// Champ modifié lors d'une mise à jour d'une entrée, avec ses valeurs avant et après.
+///
+/// Les valeurs secrètes (mot de passe, notes, champs protégés, code à usage unique) ne sont
+/// jamais conservées : seule la modification est notée.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// Champ : title, username, password, url, notes, tags, expires, color, otp,
+    /// field:<nom> (champ supplémentaire) ou attachment:<nom> (pièce jointe)
+    pub field: String,
+    
+    #[serde(default)]
+    pub before: Option<String>,
+    
+    #[serde(default)]
+    pub after: Option<String>,
+}
+
+/// Mise à jour d'une entrée : date, appareil et champs modifiés
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Revision {
+    pub changed_at: DateTime<Utc>,
+    
+    #[serde(default)]
+    pub changed_on: Option<DeviceStamp>,
+    
+    pub changes: Vec<FieldChange>,
+}
+
+impl FieldChange {
+    /// Libellé du champ
+    pub fn label(&self) -> String {
+        match self.field.as_str() {
+            "title" => "Titre".to_string(),
+            "username" => "Utilisateur".to_string(),
+            "password" => "Mot de passe".to_string(),
+            "url" => "URL".to_string(),
+            "notes" => "Notes".to_string(),
+            "tags" => "Étiquettes".to_string(),
+            "expires" => "Expiration".to_string(),
+            "color" => "Couleur".to_string(),
+            "otp" => "Code à usage unique".to_string(),
+            field => match field.split_once(':') {
+                Some(("field", name)) => format!("Champ '{}'", name),
+                Some(("attachment", name)) => format!("Pièce jointe '{}'", name),
+                _ => field.to_string(),
+            },
+        }
+    }
+}
+
+impl Revision {
+    /// Indique si le mot de passe a changé lors de cette modification
+    pub fn changes_password(&self) -> bool {
+        self.changes.iter().any(|change| change.field == "password")
+    }
+}
+
+/// Valeur comparable d'un champ d'une entrée
+struct FieldValue {
+    field: String,
+    value: Zeroizing<String>,
+    secret: bool,
+}
+
+/// Valeurs des champs d'une entrée avant une mise à jour, à comparer avec celles d'après
+pub struct Snapshot(Vec<FieldValue>);
+
+impl Snapshot {
+    pub fn of(entry: &Entry) -> Self {
+        let value = |field: &str, value: String, secret: bool| FieldValue { field: field.to_string(), value: Zeroizing::new(value), secret };
+        let mut values = vec![
+            value("title", entry.title.clone(), false),
+            value("username", entry.username.clone(), false),
+            value("password", entry.password.clone(), true),
+            value("url", entry.url.clone(), false),
+            value("notes", entry.notes.clone(), true),
+            value("tags", entry.tags.join(", "), false),
+            value("expires", entry.expires_at.map(|date| date.format("%d-%m-%Y").to_string()).unwrap_or_default(), false),
+            value("color", entry.color.map(|color| color.to_string()).unwrap_or_default(), false),
+            value("otp", entry.otp.as_ref().and_then(|otp| serde_json::to_string(otp).ok()).unwrap_or_default(), true),
+        ];
+        for field in &entry.custom_fields {
+            values.push(value(&format!("field:{}", field.name.to_lowercase()), field.value.clone(), field.protected));
+        }
+        for attachment in &entry.attachments {
+            values.push(value(&format!("attachment:{}", attachment.name.to_lowercase()), format!("{} octet(s)", attachment.size), false));
+        }
+        Self(values)
+    }
+    
+    /// Champs dont la valeur diffère entre cet état et `after`, dans l'ordre des champs de l'entrée
+    pub fn changes(&self, after: &Snapshot) -> Vec<FieldChange> {
+        let find = |snapshot: &Snapshot, field: &str| snapshot.0.iter().position(|value| value.field == field);
+        let shown = |value: &FieldValue| (!value.secret && !value.value.is_empty()).then(|| value.value.to_string());
+        
+        let mut changes = Vec::new();
+        for new in &after.0 {
+            match find(self, &new.field).map(|index| &self.0[index]) {
+                Some(old) if old.value == new.value && old.secret == new.secret => {}
+                Some(old) => changes.push(FieldChange { field: new.field.clone(), before: shown(old), after: shown(new) }),
+                None => changes.push(FieldChange { field: new.field.clone(), before: None, after: shown(new) }),
+            }
+        }
+        for old in self.0.iter().filter(|old| find(after, &old.field).is_none()) {
+            changes.push(FieldChange { field: old.field.clone(), before: shown(old), after: None });
+        }
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::CustomField;
+    
+    #[test]
+    fn test_field_changes_hide_secrets() {
+        let mut entry = Entry::new("Gmail".to_string(), "bob".to_string(), "v1".to_string(), String::new(), "note".to_string());
+        entry.set_custom_field(CustomField::new("PIN", "1234", true)).unwrap();
+        entry.set_custom_field(CustomField::new("Question", "Ville", false)).unwrap();
+        let before = Snapshot::of(&entry);
+        
+        entry.update(None, Some("robert".to_string()), Some("v2".to_string()), Some("https://mail.google.com".to_string()), Some("autre".to_string()));
+        entry.set_custom_field(CustomField::new("PIN", "0000", true)).unwrap();
+        entry.remove_custom_field("Question");
+        
+        let changes = before.changes(&Snapshot::of(&entry));
+        let change = |field: &str, before: Option<&str>, after: Option<&str>| FieldChange {
+            field: field.to_string(),
+            before: before.map(str::to_string),
+            after: after.map(str::to_string),
+        };
+        assert_eq!(changes, vec![
+            change("username", Some("bob"), Some("robert")),
+            change("password", None, None),
+            change("url", None, Some("https://mail.google.com")),
+            change("notes", None, None),
+            change("field:pin", None, None),
+            change("field:question", Some("Ville"), None),
+        ]);
+        assert!(Snapshot::of(&entry).changes(&Snapshot::of(&entry)).is_empty());
+    }
+}
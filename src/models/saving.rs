@@ -0,0 +1,110 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::crypto::key_derivation::KdfParams;
+use crate::events::{self, SaveStage};
+use crate::models::database::Database;
+use crate::models::exposure;
+use crate::models::repository::{FileKey, Repository, Timings};
+use crate::models::search_index::{self, IndexKey, IndexUpdate, SearchIndex, SearchIndexError};
+use crate::models::signing;
+
+/// Index de recherche chiffré d'une base, avec sa clé
+pub type LoadedIndex = (SearchIndex, IndexKey);
+
+/// Résultat d'un enregistrement
+pub struct Saved {
+    /// Clé dérivée du fichier enregistré (nouveau sel)
+    pub key: FileKey,
+    
+    /// Paramètres de dérivation de clé du fichier enregistré
+    pub kdf: KdfParams,
+    
+    /// Problèmes qui n'ont pas empêché l'enregistrement (journal des expositions, index de recherche)
+    pub warnings: Vec<String>,
+}
+
+/// Enregistre une base déverrouillée, de la même façon pour la ligne de commande et pour
+/// l'interface C : scripts pre-save et post-save, expositions consignées dans le journal,
+/// signature détachée et index de recherche.
+///
+/// `key` est la clé du fichier actuel (elle déchiffre le journal des expositions); `index`,
+/// l'index de recherche déjà lu s'il y en a un (il est lu au besoin, puis conservé).
+///
+/// Sans mot de passe (base ouverte avec la clé confiée à l'agent), le fichier est chiffré de
+/// nouveau avec `key`, sans changer de sel ni de paramètres, et un index de recherche qui n'a pas
+/// encore été lu reste périmé jusqu'au prochain enregistrement avec le mot de passe.
+pub fn save(repo: &Repository, db: &mut Database, password: Option<&str>, kdf: KdfParams, key: &FileKey, index: &mut Option<LoadedIndex>, timings: &mut Timings) -> Result<Saved, String> {
+    let path = repo.path();
+    let mut warnings = Vec::new();
+    
+    signing::ensure_signable(path)?;
+    events::run_save_hooks(path, SaveStage::Pre)?;
+    
+    // Expositions consignées par d'autres commandes depuis l'ouverture : enregistrées avec la base
+    if let Err(e) = apply_journal(path, key, db) {
+        warnings.push(format!("Journal des expositions illisible: {}", e));
+    }
+    let (kdf, key) = match password {
+        Some(password) => {
+            let kdf = kdf.upgraded();
+            (kdf, repo.save_keyed(db, password, kdf, timings).map_err(|e| e.to_string())?)
+        }
+        None if kdf.upgraded() == kdf => {
+            repo.save_with_key(db, key, kdf, timings).map_err(|e| e.to_string())?;
+            (kdf, key.clone())
+        }
+        None => return Err("Le mot de passe maître est nécessaire pour changer les paramètres de dérivation de clé.".to_string()),
+    };
+    let journal = exposure::journal_path(path);
+    if let Err(e) = fs::remove_file(&journal)
+        && e.kind() != io::ErrorKind::NotFound
+    {
+        warnings.push(format!("Journal des expositions {} non supprimé: {}", journal.display(), e));
+    }
+    signing::sign_vault(path)?;
+    
+    events::run_save_hooks(path, SaveStage::Post)?;
+    db.mark_as_saved();
+    
+    // Un échec n'annule pas l'enregistrement : l'index, périmé, est alors ignoré par les recherches
+    if let Err(e) = refresh_index(path, db, password, kdf, index, false) {
+        warnings.push(format!("Index de recherche non mis à jour: {}", e));
+    }
+    Ok(Saved { key, kdf, warnings })
+}
+
+/// Reporte dans la base les expositions consignées dans son journal
+pub fn apply_journal(vault: &Path, key: &FileKey, db: &mut Database) -> io::Result<()> {
+    let records = exposure::read_journal(&exposure::journal_path(vault), key)?;
+    db.apply_exposures(&records);
+    Ok(())
+}
+
+/// Reporte le contenu de la base enregistrée dans son index de recherche, s'il existe
+/// (`create` : l'écrit même s'il n'existe pas encore).
+///
+/// Un index illisible avec le mot de passe actuel (mot de passe changé ailleurs) est reconstruit;
+/// sans mot de passe, un index qui n'a pas encore été lu est laissé tel quel.
+pub fn refresh_index(vault: &Path, db: &Database, password: Option<&str>, kdf: KdfParams, index: &mut Option<LoadedIndex>, create: bool) -> Result<Option<IndexUpdate>, SearchIndexError> {
+    let path = search_index::index_path(vault);
+    if !create && !path.exists() {
+        *index = None;
+        return Ok(None);
+    }
+    
+    let (loaded, key) = match (index.take(), password) {
+        (Some(loaded), _) => loaded,
+        (None, None) => return Ok(None),
+        (None, Some(password)) => match SearchIndex::read(&path, password) {
+            Ok(loaded) => loaded,
+            Err(SearchIndexError::Io(e)) => return Err(SearchIndexError::Io(e)),
+            Err(_) => (SearchIndex::default(), IndexKey::new(password, kdf)?),
+        },
+    };
+    let (loaded, key) = index.insert((loaded, key));
+    let update = loaded.update(db, search_index::vault_digest(vault)?);
+    loaded.write(&path, key)?;
+    Ok(Some(update))
+}
\ No newline at end of file
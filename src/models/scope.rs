@@ -0,0 +1,126 @@
+use thiserror::Error;
+
+use super::database::Database;
+use super::entry::Entry;
+use super::id::EntryId;
+use super::query::{Query, QueryContext, QueryMatch};
+use super::walker::WalkControl;
+
+/// Erreurs liées à la portée d'un client (extension de navigateur, fournisseur Secret Service)
+#[derive(Error, Debug, PartialEq)]
+pub enum ScopeError {
+    #[error("Chemin de groupe autorisé vide")]
+    EmptyPath,
+    
+    #[error("Entrée introuvable: {0}")]
+    EntryNotFound(EntryId),
+    
+    #[error("Accès refusé: l'entrée {0} est hors des groupes autorisés")]
+    Denied(EntryId),
+}
+
+/// Groupes visibles par un client à accès restreint (extension de navigateur, fournisseur Secret
+/// Service) : chaque groupe autorisé et toute sa descendance.
+///
+/// Les chemins sont relatifs au groupe racine ("Infrastructure/CI") et comparés sans tenir
+/// compte de la casse. Une portée vide ne donne accès à aucune entrée.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GroupScope {
+    allowed: Vec<Vec<String>>,
+}
+
+impl GroupScope {
+    /// Portée limitée aux groupes indiqués par leur chemin ("Infrastructure/CI")
+    pub fn new<I, S>(paths: I) -> Result<Self, ScopeError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let allowed = paths.into_iter()
+            .map(|path| {
+                let components: Vec<String> = path.as_ref().split('/')
+                    .map(str::trim)
+                    .filter(|component| !component.is_empty())
+                    .map(str::to_lowercase)
+                    .collect();
+                if components.is_empty() { Err(ScopeError::EmptyPath) } else { Ok(components) }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { allowed })
+    }
+    
+    /// Indique si un groupe est visible, d'après son chemin depuis la racine (groupe racine inclus,
+    /// comme dans les parcours de l'arborescence)
+    pub fn allows(&self, path: &[String]) -> bool {
+        self.allows_relative(path.get(1..).unwrap_or_default())
+    }
+    
+    /// Indique si un groupe est visible, d'après son chemin relatif au groupe racine ("Navigateur")
+    pub fn allows_group(&self, path: &str) -> bool {
+        let relative: Vec<String> = path.split('/').map(str::trim).filter(|name| !name.is_empty()).map(str::to_string).collect();
+        self.allows_relative(&relative)
+    }
+    
+    fn allows_relative(&self, relative: &[String]) -> bool {
+        self.allowed.iter().any(|allowed| {
+            allowed.len() <= relative.len()
+                && allowed.iter().zip(relative).all(|(allowed, name)| name.to_lowercase() == *allowed)
+        })
+    }
+}
+
+impl Database {
+    /// Entrée accessible dans une portée; une entrée existante hors de la portée est refusée
+    /// (l'appelant consigne le refus)
+    pub fn find_entry_in_scope(&self, scope: &GroupScope, entry_id: &EntryId) -> Result<&Entry, ScopeError> {
+        let mut found = None;
+        self.walk(|path, _, entry| match entry {
+            Some(entry) if entry.id == *entry_id => {
+                found = Some((scope.allows(path), entry));
+                WalkControl::Stop
+            }
+            _ => WalkControl::Continue,
+        });
+        
+        match found {
+            Some((true, entry)) => Ok(entry),
+            Some((false, _)) => Err(ScopeError::Denied(*entry_id)),
+            None => Err(ScopeError::EntryNotFound(*entry_id)),
+        }
+    }
+    
+    /// Variante de `find_entries_with` limitée aux groupes d'une portée
+    pub fn find_entries_in_scope(&self, scope: &GroupScope, context: &QueryContext, query: &Query) -> Vec<QueryMatch<'_>> {
+        let mut matches = self.find_entries_with(context, query);
+        matches.retain(|found| scope.allows(&found.path));
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_scope_limits_queries_and_lookups() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let infra = db.add_group(None, "Infrastructure".to_string()).unwrap();
+        let ci = db.add_group(Some(&infra), "CI".to_string()).unwrap();
+        let runners = db.add_group(Some(&ci), "Runners".to_string()).unwrap();
+        let deploy = db.add_entry(Some(&runners), Entry::builder().title("Deploy key").build().unwrap()).unwrap();
+        let root_ssh = db.add_entry(Some(&infra), Entry::builder().title("Root SSH").build().unwrap()).unwrap();
+        
+        let scope = GroupScope::new(["infrastructure/ci/"]).unwrap();
+        let all = Query::parse("title:o").unwrap();
+        let visible = db.find_entries_in_scope(&scope, &QueryContext::new(), &all);
+        assert_eq!(visible.iter().map(|found| found.entry.id).collect::<Vec<_>>(), vec![deploy]);
+        
+        assert_eq!(db.find_entry_in_scope(&scope, &deploy).unwrap().title, "Deploy key");
+        assert_eq!(db.find_entry_in_scope(&scope, &root_ssh).unwrap_err(), ScopeError::Denied(root_ssh));
+        assert!(db.find_entries_in_scope(&GroupScope::default(), &QueryContext::new(), &all).is_empty());
+        assert_eq!(GroupScope::new([" / "]), Err(ScopeError::EmptyPath));
+        assert!(!scope.allows(&["Root".to_string(), "Infrastructure".to_string()]));
+        assert!(scope.allows_group("Infrastructure/CI/Runners"));
+        assert!(!scope.allows_group("Navigateur"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,341 @@
+use chrono::{DateTime, Utc};
+use data_encoding::HEXLOWER;
+use rand::RngCore;
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+use crate::crypto::cipher;
+use crate::crypto::key_derivation::{self, KdfParams, KeyDerivationError};
+
+use super::database::Database;
+use super::entry::{ColorLabel, Entry};
+use super::id::{EntryId, GroupId};
+use super::walker::WalkControl;
+
+/// Signature des fichiers d'index
+const INDEX_MAGIC: &[u8] = b"KRSINDEX";
+
+/// Version du format de fichier d'index
+const INDEX_VERSION: u8 = 2;
+
+/// Suffixe ajouté au nom de la base pour son index (coffre.krs.idx)
+pub const INDEX_SUFFIX: &str = ".idx";
+
+/// Erreurs de lecture ou d'écriture d'un index de recherche
+#[derive(Error, Debug)]
+pub enum SearchIndexError {
+    #[error("Erreur d'entrée/sortie: {0}")]
+    Io(#[from] io::Error),
+    
+    #[error("Fichier d'index invalide")]
+    InvalidFormat,
+    
+    #[error("Mot de passe invalide ou index endommagé")]
+    InvalidPassword,
+    
+    #[error("Erreur de dérivation de clé: {0}")]
+    KeyDerivation(#[from] KeyDerivationError),
+    
+    #[error("Erreur de sérialisation de l'index: {0}")]
+    Serialization(String),
+}
+
+/// Champs consultables d'une entrée : ni mot de passe, ni historique, ni code à usage unique, mais
+/// les notes y sont recopiées telles quelles (comme `Database::search`, la recherche les parcourt)
+/// et peuvent contenir des secrets; l'index est chiffré pour cette raison
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedEntry {
+    pub id: EntryId,
+    
+    /// Noms des groupes menant à l'entrée
+    pub path: Vec<String>,
+    
+    /// Groupe contenant l'entrée (absent des résultats tirés directement de la base)
+    pub group: Option<GroupId>,
+    pub title: String,
+    pub username: String,
+    pub url: String,
+    pub notes: String,
+    pub tags: Vec<String>,
+    pub color: Option<ColorLabel>,
+    pub archived: bool,
+    
+    /// Entrée située dans la corbeille
+    #[serde(default)]
+    pub trashed: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl IndexedEntry {
+    pub fn new(path: &[String], entry: &Entry, trashed: bool) -> Self {
+        Self {
+            id: entry.id,
+            path: path.to_vec(),
+            group: None,
+            title: entry.title.clone(),
+            username: entry.username.clone(),
+            url: entry.url.clone(),
+            notes: entry.notes.clone(),
+            tags: entry.tags.clone(),
+            color: entry.color,
+            archived: entry.archived,
+            trashed,
+            expires_at: entry.expires_at,
+            updated_at: entry.updated_at,
+        }
+    }
+    
+    /// Mêmes champs que `Database::search` : titre, utilisateur, URL et notes
+    fn matches(&self, text: &str, ignore_case: bool) -> bool {
+        let lowered = text.to_lowercase();
+        [&self.title, &self.username, &self.url, &self.notes].iter().any(|field| {
+            if ignore_case { field.to_lowercase().contains(&lowered) } else { field.contains(text) }
+        })
+    }
+}
+
+/// Bilan d'une mise à jour de l'index
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct IndexUpdate {
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+}
+
+impl IndexUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.added == 0 && self.updated == 0 && self.removed == 0
+    }
+}
+
+/// Index de recherche d'une base, chiffré dans un fichier séparé (coffre.krs.idx) : `search`
+/// le déchiffre sans relire ni désérialiser toute la base.
+///
+/// L'index n'est utilisé que s'il correspond au contenu actuel du fichier de la base (empreinte
+/// SHA-256); il est mis à jour à chaque enregistrement, en ne recopiant que les entrées modifiées.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchIndex {
+    /// Empreinte du fichier de la base indexée
+    pub vault_digest: String,
+    
+    /// Entrées dans l'ordre du parcours de la base
+    pub entries: Vec<IndexedEntry>,
+}
+
+impl SearchIndex {
+    /// Index complet d'une base
+    pub fn build(db: &Database, vault_digest: String) -> Self {
+        let mut index = Self::default();
+        index.update(db, vault_digest);
+        index
+    }
+    
+    /// Reporte les modifications de la base : les entrées inchangées (même date de modification,
+    /// même groupe, toujours dans la corbeille ou hors de celle-ci) sont reprises telles quelles
+    pub fn update(&mut self, db: &Database, vault_digest: String) -> IndexUpdate {
+        let mut previous: std::collections::HashMap<EntryId, IndexedEntry> = self.entries.drain(..).map(|entry| (entry.id, entry)).collect();
+        let mut report = IndexUpdate::default();
+        let trash = db.trash_group_ids();
+        
+        db.walk(|path, group, entry| {
+            if let Some(entry) = entry {
+                let trashed = trash.contains(&group.id);
+                let fresh = || IndexedEntry { group: Some(group.id), ..IndexedEntry::new(path, entry, trashed) };
+                let indexed = match previous.remove(&entry.id) {
+                    Some(indexed) if indexed.updated_at == entry.updated_at && indexed.path == path
+                        && indexed.group == Some(group.id) && indexed.trashed == trashed => indexed,
+                    Some(_) => {
+                        report.updated += 1;
+                        fresh()
+                    }
+                    None => {
+                        report.added += 1;
+                        fresh()
+                    }
+                };
+                self.entries.push(indexed);
+            }
+            WalkControl::Continue
+        });
+        
+        report.removed = previous.len();
+        self.vault_digest = vault_digest;
+        report
+    }
+    
+    /// Entrées dont le titre, l'utilisateur, l'URL ou les notes contiennent `text`
+    pub fn search(&self, text: &str, ignore_case: bool) -> Vec<&IndexedEntry> {
+        self.entries.iter().filter(|entry| entry.matches(text, ignore_case)).collect()
+    }
+    
+    /// Lit et déchiffre un fichier d'index; retourne aussi la clé pour les mises à jour suivantes
+    pub fn read(path: &Path, password: &str) -> Result<(Self, IndexKey), SearchIndexError> {
+        let bytes = fs::read(path)?;
+        let (key, header_len) = IndexKey::from_header(&bytes, password)?;
+        let (header, mut body) = bytes.split_at(header_len);
+        
+        let mut nonce = [0u8; cipher::NONCE_LEN];
+        body.read_exact(&mut nonce).map_err(|_| SearchIndexError::InvalidFormat)?;
+        let chunk = cipher::EncryptedChunk { nonce, ciphertext: body.to_vec() };
+        let plaintext = Zeroizing::new(cipher::decrypt_chunk(&chunk, &key.key, header).map_err(|_| SearchIndexError::InvalidPassword)?);
+        
+        let index = ciborium::from_reader(plaintext.as_slice()).map_err(|e| SearchIndexError::Serialization(e.to_string()))?;
+        Ok((index, key))
+    }
+    
+    /// Chiffre l'index avec `key` et remplace le fichier
+    pub fn write(&self, path: &Path, key: &IndexKey) -> Result<(), SearchIndexError> {
+        let mut plaintext = Zeroizing::new(Vec::new());
+        ciborium::into_writer(self, &mut *plaintext).map_err(|e| SearchIndexError::Serialization(e.to_string()))?;
+        
+        let mut bytes = key.header();
+        let chunk = cipher::encrypt_chunk(&plaintext, &key.key, &bytes).map_err(|e| SearchIndexError::Serialization(e.to_string()))?;
+        bytes.extend(chunk.nonce);
+        bytes.extend(chunk.ciphertext);
+        
+        // Écrire à côté puis renommer, pour ne jamais laisser un index à moitié écrit
+        let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+        temp_name.push(format!(".{}.tmp", std::process::id()));
+        let temp_path = path.with_file_name(temp_name);
+        fs::write(&temp_path, &bytes)?;
+        fs::rename(&temp_path, path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp_path);
+        })?;
+        Ok(())
+    }
+}
+
+/// Clé de chiffrement d'un index, dérivée du mot de passe maître avec son propre sel : elle reste
+/// valable d'un enregistrement de la base à l'autre, jusqu'au changement du mot de passe
+#[derive(Clone)]
+pub struct IndexKey {
+    kdf: KdfParams,
+    salt: Vec<u8>,
+    key: Zeroizing<[u8; 32]>,
+}
+
+impl IndexKey {
+    /// Nouvelle clé, avec un sel aléatoire
+    pub fn new(password: &str, kdf: KdfParams) -> Result<Self, SearchIndexError> {
+        let mut salt = vec![0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        let key = Zeroizing::new(kdf.kdf().derive(password, &salt, &kdf.values())?);
+        Ok(Self { kdf, salt, key })
+    }
+    
+    /// Signature, version, algorithme de dérivation et ses paramètres, longueur du sel puis le
+    /// sel (authentifiés avec les données chiffrées)
+    fn header(&self) -> Vec<u8> {
+        let mut bytes = INDEX_MAGIC.to_vec();
+        bytes.push(INDEX_VERSION);
+        bytes.push(self.kdf.kdf().id());
+        for value in self.kdf.values() {
+            bytes.extend(value.to_be_bytes());
+        }
+        bytes.push(self.salt.len() as u8);
+        bytes.extend(&self.salt);
+        bytes
+    }
+    
+    /// Dérive la clé annoncée par l'en-tête d'un fichier d'index; retourne aussi la longueur de l'en-tête
+    fn from_header(bytes: &[u8], password: &str) -> Result<(Self, usize), SearchIndexError> {
+        let mut rest = bytes.strip_prefix(INDEX_MAGIC).ok_or(SearchIndexError::InvalidFormat)?;
+        let mut take = |len: usize| -> Result<&[u8], SearchIndexError> {
+            if rest.len() < len {
+                return Err(SearchIndexError::InvalidFormat);
+            }
+            let (taken, remaining) = rest.split_at(len);
+            rest = remaining;
+            Ok(taken)
+        };
+        
+        if take(1)? != [INDEX_VERSION] {
+            return Err(SearchIndexError::InvalidFormat);
+        }
+        let algorithm = key_derivation::find(take(1)?[0]).ok_or(SearchIndexError::InvalidFormat)?;
+        let values = algorithm.parameters().iter()
+            .map(|_| take(4).map(|value| u32::from_be_bytes([value[0], value[1], value[2], value[3]])))
+            .collect::<Result<Vec<_>, _>>()?;
+        // Paramètres bornés avant de dériver quoi que ce soit (en-tête non authentifié)
+        let kdf = algorithm.params(&values).ok_or(SearchIndexError::InvalidFormat)?;
+        kdf.validate()?;
+        let salt_len = take(1)?[0] as usize;
+        let salt = take(salt_len)?.to_vec();
+        
+        let header_len = bytes.len() - rest.len();
+        let key = Zeroizing::new(kdf.kdf().derive(password, &salt, &kdf.values())?);
+        Ok((Self { kdf, salt, key }, header_len))
+    }
+}
+
+/// Emplacement de l'index d'une base (coffre.krs.idx)
+pub fn index_path(vault: &Path) -> PathBuf {
+    let mut name = vault.file_name().unwrap_or_default().to_os_string();
+    name.push(INDEX_SUFFIX);
+    vault.with_file_name(name)
+}
+
+/// Empreinte SHA-256 du fichier d'une base, pour reconnaître un index périmé
+pub fn vault_digest(vault: &Path) -> io::Result<String> {
+    Ok(HEXLOWER.encode(&Sha256::digest(fs::read(vault)?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_incremental_update_and_encrypted_round_trip() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let work = db.add_group(None, "Travail".to_string()).unwrap();
+        let mail = db.add_entry(Some(&work), Entry::builder().title("Messagerie").username("bob").password("s3cret").build().unwrap()).unwrap();
+        let bank = db.add_entry(None, Entry::builder().title("Banque").notes("agence centre").build().unwrap()).unwrap();
+        
+        let mut index = SearchIndex::build(&db, "v1".to_string());
+        assert_eq!(index.search("CENTRE", true).iter().map(|entry| entry.id).collect::<Vec<_>>(), vec![bank]);
+        assert!(index.search("CENTRE", false).is_empty());
+        
+        db.update_entry(&mail, |entry| entry.username = "alice".to_string()).unwrap();
+        db.remove_entry(&bank).unwrap();
+        db.add_entry(None, Entry::builder().title("Forum").build().unwrap()).unwrap();
+        assert_eq!(index.update(&db, "v2".to_string()), IndexUpdate { added: 1, updated: 1, removed: 1 });
+        assert_eq!(index.search("alice", false)[0].path.last().map(String::as_str), Some("Travail"));
+        
+        let directory = std::env::temp_dir().join(format!("keeprust-index-{}", crate::models::id::GroupId::new().short()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = index_path(&directory.join("coffre.krs"));
+        let kdf = KdfParams::Pbkdf2 { iterations: 10 };
+        index.write(&path, &IndexKey::new("pw", kdf).unwrap()).unwrap();
+        assert!(!fs::read(&path).unwrap().windows(5).any(|window| window == b"alice"));
+        
+        let (read, _) = SearchIndex::read(&path, "pw").unwrap();
+        assert_eq!(read, index);
+        assert!(matches!(SearchIndex::read(&path, "autre"), Err(SearchIndexError::InvalidPassword)));
+        
+        fs::remove_dir_all(&directory).unwrap();
+    }
+    
+    #[test]
+    fn test_hostile_kdf_parameters_are_rejected_before_derivation() {
+        let directory = std::env::temp_dir().join(format!("keeprust-index-kdf-{}", crate::models::id::GroupId::new().short()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = index_path(&directory.join("coffre.krs"));
+        SearchIndex::default().write(&path, &IndexKey::new("pw", KdfParams::Pbkdf2 { iterations: 10 }).unwrap()).unwrap();
+        
+        // Signature, version et algorithme, puis le nombre d'itérations
+        let mut bytes = fs::read(&path).unwrap();
+        let offset = INDEX_MAGIC.len() + 2;
+        bytes[offset..offset + 4].copy_from_slice(&u32::MAX.to_be_bytes());
+        fs::write(&path, &bytes).unwrap();
+        assert!(matches!(SearchIndex::read(&path, "pw"), Err(SearchIndexError::KeyDerivation(_))));
+        
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::{BTreeMap, HashMap};
+
+use super::database::{Database, DatabaseError};
+use super::entry::{Entry, is_valid_url, url_host};
+use super::id::EntryId;
+use super::scope::GroupScope;
+use super::walker::WalkControl;
+
+/// Groupe recevant les secrets enregistrés par les applications (CreateItem)
+pub const SECRET_SERVICE_GROUP: &str = "Secret Service";
+
+/// Attributs calculés à partir des champs de l'entrée; les autres attributs d'un élément sont
+/// conservés dans ses étiquettes sous la forme `nom=valeur`
+const DERIVED_ATTRIBUTES: &[&str] = &["title", "username", "user", "url", "server", "keeprust:id", "keeprust:group"];
+
+/// Attributs Secret Service d'une entrée : titre, identifiant (`username` et `user`, noms
+/// utilisés par libsecret), URL et son hôte (`server`), identifiant et groupe keeprust, puis les
+/// étiquettes `nom=valeur`
+pub fn item_attributes(path: &[String], entry: &Entry) -> BTreeMap<String, String> {
+    let mut attributes = BTreeMap::new();
+    attributes.insert("title".to_string(), entry.title.clone());
+    if !entry.username.is_empty() {
+        attributes.insert("username".to_string(), entry.username.clone());
+        attributes.insert("user".to_string(), entry.username.clone());
+    }
+    if !entry.url.is_empty() {
+        attributes.insert("url".to_string(), entry.url.clone());
+        if let Some(host) = url_host(&entry.url) {
+            attributes.insert("server".to_string(), host);
+        }
+    }
+    attributes.insert("keeprust:id".to_string(), entry.id.to_string());
+    // Le groupe racine n'est pas repris
+    attributes.insert("keeprust:group".to_string(), path.iter().skip(1).cloned().collect::<Vec<_>>().join("/"));
+    
+    for tag in &entry.tags {
+        if let Some((name, value)) = tag.split_once('=')
+            && !name.is_empty()
+            && !DERIVED_ATTRIBUTES.contains(&name)
+        {
+            attributes.insert(name.to_string(), value.to_string());
+        }
+    }
+    attributes
+}
+
+/// Indique si les attributs contiennent tous ceux de la recherche, à l'identique
+pub fn matches(attributes: &BTreeMap<String, String>, query: &HashMap<String, String>) -> bool {
+    query.iter().all(|(name, value)| attributes.get(name) == Some(value))
+}
+
+impl Database {
+    /// Éléments exposés par le Secret Service : les entrées hors corbeille, limitées à `scope`
+    /// s'il est fourni, avec leurs attributs
+    pub fn secret_items(&self, scope: Option<&GroupScope>) -> Vec<(EntryId, BTreeMap<String, String>)> {
+        let trash = self.metadata.trash_group_id;
+        let mut items = Vec::new();
+        self.walk(|path, group, entry| {
+            if Some(group.id) == trash {
+                return WalkControl::SkipChildren;
+            }
+            if let Some(entry) = entry
+                && scope.is_none_or(|scope| scope.allows(path))
+            {
+                items.push((entry.id, item_attributes(path, entry)));
+            }
+            WalkControl::Continue
+        });
+        items
+    }
+    
+    /// Enregistre un secret transmis par une application. Avec `replace`, l'entrée dont les
+    /// attributs contiennent tous ceux fournis reçoit le nouveau secret; sinon une entrée est
+    /// créée dans le groupe « Secret Service ». Seules les entrées de `scope` peuvent être
+    /// remplacées. Retourne l'entrée et indique si elle a été créée.
+    pub fn store_secret_item(&mut self, label: &str, attributes: &HashMap<String, String>, secret: String, replace: bool, scope: Option<&GroupScope>) -> Result<(EntryId, bool), DatabaseError> {
+        if replace && !attributes.is_empty()
+            && let Some(id) = self.secret_items(scope).into_iter().find(|(_, existing)| matches(existing, attributes)).map(|(id, _)| id)
+        {
+            let title = (!label.is_empty()).then(|| label.to_string());
+            self.update_entry(&id, |entry| entry.update(title, None, Some(secret), None, None))?;
+            return Ok((id, false));
+        }
+        
+        let attribute = |names: &[&str]| names.iter().find_map(|name| attributes.get(*name)).cloned().unwrap_or_default();
+        let title = if label.is_empty() { attribute(&["title", "service", "application"]) } else { label.to_string() };
+        let url = attribute(&["url"]);
+        let url = if is_valid_url(&url) { url } else { String::new() };
+        let mut entry = Entry::new(
+            if title.is_empty() { SECRET_SERVICE_GROUP.to_string() } else { title },
+            attribute(&["username", "user", "account"]),
+            secret,
+            url,
+            String::new(),
+        );
+        
+        // Attributs sans champ correspondant, dans un ordre stable
+        let mut extra: Vec<_> = attributes.iter()
+            .filter(|(name, _)| !DERIVED_ATTRIBUTES.contains(&name.as_str()))
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        extra.sort();
+        entry.tags = extra;
+        
+        let group = match self.root_group.subgroups.values().find(|group| group.name == SECRET_SERVICE_GROUP) {
+            Some(group) => group.id,
+            None => self.add_group(None, SECRET_SERVICE_GROUP.to_string())?,
+        };
+        Ok((self.add_entry(Some(&group), entry)?, true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_attributes_and_stored_items() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let id = db.add_entry(None, Entry::builder().title("Forge").username("bob").url("https://git.example.org/login").password("pw").build().unwrap()).unwrap();
+        
+        let items = db.secret_items(None);
+        assert_eq!(items.len(), 1);
+        let attributes = &items[0].1;
+        assert_eq!(attributes["server"], "git.example.org");
+        assert_eq!(attributes["user"], "bob");
+        assert_eq!(attributes["keeprust:id"], id.to_string());
+        
+        let query: HashMap<String, String> = [("server", "git.example.org"), ("user", "bob")].iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        assert!(matches(attributes, &query));
+        
+        // Un secret d'application : attributs inconnus conservés dans les étiquettes
+        let chrome: HashMap<String, String> = [("application", "chrome")].iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let (created, is_new) = db.store_secret_item("Chrome Safe Storage", &chrome, "k1".to_string(), true, None).unwrap();
+        assert!(is_new);
+        assert_eq!(db.find_entry(&created).unwrap().tags, vec!["application=chrome".to_string()]);
+        assert!(db.find_group_by_path(SECRET_SERVICE_GROUP).is_some());
+        
+        // Remplacement du même élément
+        let (replaced, is_new) = db.store_secret_item("Chrome Safe Storage", &chrome, "k2".to_string(), true, None).unwrap();
+        assert_eq!((replaced, is_new), (created, false));
+        assert_eq!(db.find_entry(&created).unwrap().password, "k2");
+        assert_eq!(db.secret_items(None).iter().filter(|(_, attributes)| matches(attributes, &chrome)).count(), 1);
+    }
+    
+    #[test]
+    fn test_scope_limits_items_and_replacement() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let ci = db.add_group(None, "CI".to_string()).unwrap();
+        let deploy = db.add_entry(Some(&ci), Entry::builder().title("Deploy").password("d1").build().unwrap()).unwrap();
+        db.add_entry(None, Entry::builder().title("Banque").password("b1").build().unwrap()).unwrap();
+        
+        let scope = GroupScope::new(["CI"]).unwrap();
+        let visible: Vec<EntryId> = db.secret_items(Some(&scope)).into_iter().map(|(id, _)| id).collect();
+        assert_eq!(visible, vec![deploy]);
+        
+        // Un élément hors de la portée n'est pas remplacé : un nouvel élément est créé
+        let banque: HashMap<String, String> = [("title".to_string(), "Banque".to_string())].into_iter().collect();
+        let (_, is_new) = db.store_secret_item("Banque", &banque, "b2".to_string(), true, Some(&scope)).unwrap();
+        assert!(is_new);
+        assert!(db.iter_entries().any(|entry| entry.title == "Banque" && entry.password == "b1"));
+    }
+}
@@ -0,0 +1,156 @@
+use base64::{Engine as _, engine::general_purpose};
+use chrono::{DateTime, Duration, Utc};
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use thiserror::Error;
+
+use super::clock;
+use super::id::EntryId;
+
+/// Durée de validité par défaut d'un lien de partage (keeprust share)
+pub const DEFAULT_SHARE_TTL_MINUTES: u64 = 10;
+
+/// Nombre d'utilisations par défaut d'un lien de partage
+pub const DEFAULT_SHARE_USES: u32 = 1;
+
+/// Taille en octets de la partie aléatoire d'un jeton
+const TOKEN_LEN: usize = 32;
+
+/// Erreurs liées aux jetons de partage
+#[derive(Error, Debug, PartialEq)]
+pub enum ShareError {
+    #[error("Jeton de partage inconnu")]
+    UnknownToken,
+    
+    #[error("Jeton de partage expiré")]
+    Expired,
+    
+    #[error("Le nombre d'utilisations doit être supérieur à zéro")]
+    NoUses,
+}
+
+/// Droit de lecture seule sur une entrée, accordé à un jeton
+#[derive(Debug, Clone)]
+pub struct ShareGrant {
+    /// Entrée consultable
+    pub entry_id: EntryId,
+    
+    /// Date d'expiration du jeton
+    pub expires_at: DateTime<Utc>,
+    
+    /// Nombre de consultations restantes
+    pub remaining_uses: u32,
+}
+
+/// Registre des jetons de partage en cours de validité.
+///
+/// Les jetons ne sont conservés qu'en mémoire, le temps de vie du processus qui les émet;
+/// seule leur empreinte SHA-256 est stockée.
+#[derive(Debug, Default)]
+pub struct ShareRegistry {
+    grants: HashMap<[u8; 32], ShareGrant>,
+}
+
+impl ShareRegistry {
+    /// Crée un registre vide
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Émet un jeton donnant accès à une entrée pendant `ttl`, pour `uses` consultations
+    pub fn mint(&mut self, entry_id: EntryId, ttl: Duration, uses: u32) -> Result<String, ShareError> {
+        self.mint_with_rng(entry_id, ttl, uses, &mut rand::thread_rng())
+    }
+    
+    /// Émet un jeton en tirant sa partie aléatoire du générateur fourni
+    pub fn mint_with_rng<R: RngCore + CryptoRng>(&mut self, entry_id: EntryId, ttl: Duration, uses: u32, rng: &mut R) -> Result<String, ShareError> {
+        if uses == 0 {
+            return Err(ShareError::NoUses);
+        }
+        self.purge_expired();
+        
+        let mut bytes = [0u8; TOKEN_LEN];
+        rng.fill_bytes(&mut bytes);
+        let token = general_purpose::URL_SAFE_NO_PAD.encode(bytes);
+        
+        self.grants.insert(fingerprint(&token), ShareGrant {
+            entry_id,
+            expires_at: clock::now() + ttl,
+            remaining_uses: uses,
+        });
+        Ok(token)
+    }
+    
+    /// Consomme une utilisation du jeton et retourne l'entrée à laquelle il donne accès.
+    ///
+    /// Un jeton épuisé ou expiré est retiré du registre.
+    pub fn redeem(&mut self, token: &str) -> Result<EntryId, ShareError> {
+        let key = fingerprint(token);
+        let grant = self.grants.get_mut(&key).ok_or(ShareError::UnknownToken)?;
+        
+        if grant.expires_at <= clock::now() {
+            self.grants.remove(&key);
+            return Err(ShareError::Expired);
+        }
+        
+        let entry_id = grant.entry_id;
+        grant.remaining_uses -= 1;
+        if grant.remaining_uses == 0 {
+            self.grants.remove(&key);
+        }
+        Ok(entry_id)
+    }
+    
+    /// Révoque un jeton avant son expiration; retourne false s'il n'existait pas
+    pub fn revoke(&mut self, token: &str) -> bool {
+        self.grants.remove(&fingerprint(token)).is_some()
+    }
+    
+    /// Révoque tous les jetons donnant accès à une entrée (ex: après sa suppression)
+    pub fn revoke_entry(&mut self, entry_id: &EntryId) {
+        self.grants.retain(|_, grant| grant.entry_id != *entry_id);
+    }
+    
+    /// Retire les jetons expirés
+    pub fn purge_expired(&mut self) {
+        let now = clock::now();
+        self.grants.retain(|_, grant| grant.expires_at > now);
+    }
+    
+    /// Nombre de jetons actifs
+    pub fn len(&self) -> usize {
+        self.grants.len()
+    }
+    
+    /// Indique si aucun jeton n'est actif
+    pub fn is_empty(&self) -> bool {
+        self.grants.is_empty()
+    }
+}
+
+/// Empreinte sous laquelle un jeton est conservé
+fn fingerprint(token: &str) -> [u8; 32] {
+    Sha256::digest(token.as_bytes()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_token_is_single_use_and_expires() {
+        let mut registry = ShareRegistry::new();
+        let entry_id = EntryId::new();
+        
+        let token = registry.mint(entry_id, Duration::minutes(DEFAULT_SHARE_TTL_MINUTES as i64), DEFAULT_SHARE_USES).unwrap();
+        assert_eq!(registry.redeem(&token), Ok(entry_id));
+        assert_eq!(registry.redeem(&token), Err(ShareError::UnknownToken));
+        
+        let expired = registry.mint(entry_id, Duration::zero(), 3).unwrap();
+        assert_eq!(registry.redeem(&expired), Err(ShareError::Expired));
+        assert!(registry.is_empty());
+        
+        assert_eq!(registry.mint(entry_id, Duration::minutes(1), 0), Err(ShareError::NoUses));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,159 @@
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::crypto::signing;
+use crate::events;
+use crate::utils::paths;
+
+/// Nom du fichier des clés de signature, dans le répertoire de configuration
+const SIGNING_FILE: &str = "signing.toml";
+
+/// Conseil affiché lorsqu'une signature est refusée
+const LEGITIMATE_CHANGE_HINT: &str = "Si le fichier vient d'un appareil dont vous n'avez pas accepté la clé, utilisez 'keeprust signing trust'; \
+sinon retirez la base de signed_vaults dans signing.toml après avoir vérifié sa provenance.";
+
+/// Clés de signature de cet appareil et bases dont la signature est exigée :
+///
+/// ```toml
+/// secret_key = "..."                          # clé privée de cet appareil (hexadécimal)
+/// trusted_keys = ["..."]                      # clés publiques des autres appareils autorisés
+/// signed_vaults = ["/home/bob/coffre.krs"]    # bases vérifiées avant chaque ouverture
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SigningConfig {
+    pub secret_key: Option<String>,
+    pub trusted_keys: Vec<String>,
+    pub signed_vaults: Vec<PathBuf>,
+}
+
+impl SigningConfig {
+    /// Clé privée de cet appareil
+    pub fn secret(&self) -> Result<SigningKey, String> {
+        let hex = self.secret_key.as_deref().ok_or("Aucune clé de signature: utilisez 'keeprust signing init'.")?;
+        signing::decode_secret(hex).map_err(|e| format!("Clé de signature de {}: {}", SIGNING_FILE, e))
+    }
+    
+    /// Clés acceptées pour les signatures : celle de cet appareil et celles des autres appareils
+    pub fn trusted(&self) -> Result<Vec<VerifyingKey>, String> {
+        let mut trusted = vec![self.secret()?.verifying_key()];
+        for key in &self.trusted_keys {
+            trusted.push(signing::decode_public(key).map_err(|e| format!("Clé publique '{}' de {}: {}", key, SIGNING_FILE, e))?);
+        }
+        Ok(trusted)
+    }
+    
+    /// Indique si la signature de la base est exigée
+    pub fn is_signed(&self, path: &Path) -> bool {
+        self.signed_vaults.contains(&absolute(path))
+    }
+}
+
+/// Emplacement du fichier des clés de signature
+pub fn signing_path() -> Option<PathBuf> {
+    Some(events::config_dir()?.join(SIGNING_FILE))
+}
+
+/// Chemin absolu d'une base, pour la reconnaître quel que soit le répertoire courant
+pub fn absolute(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Clés de signature configurées; aucune si le fichier n'existe pas
+pub fn load() -> Result<SigningConfig, String> {
+    let Some(path) = signing_path() else {
+        return Ok(SigningConfig::default());
+    };
+    match fs::read_to_string(&path) {
+        Ok(content) => toml::from_str(&content).map_err(|e| format!("Fichier {} invalide: {}", path.display(), e)),
+        Err(_) => Ok(SigningConfig::default()),
+    }
+}
+
+/// Enregistre le fichier des clés de signature, lisible par son seul propriétaire
+pub fn write(config: &SigningConfig) -> Result<(), String> {
+    let path = signing_path().ok_or("Aucun répertoire de configuration pour les clés de signature.")?;
+    if let Some(parent) = path.parent() {
+        paths::create_private_dir(parent).map_err(|e| e.to_string())?;
+    }
+    let content = toml::to_string(config).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| format!("Impossible d'enregistrer {}: {}", path.display(), e))?;
+    
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Vérifie la signature détachée d'une base avec les clés fournies
+pub fn check(path: &Path, trusted: &[VerifyingKey]) -> Result<(), String> {
+    let signature_path = signing::signature_path(path);
+    let detached = fs::read_to_string(&signature_path).map_err(|_| format!(
+        "Signature absente ({}): la base {} a peut-être été remplacée. Elle n'a pas été ouverte.\n{}",
+        signature_path.display(), path.display(), LEGITIMATE_CHANGE_HINT
+    ))?;
+    let content = fs::read(path).map_err(|e| format!("Impossible de lire {}: {}", path.display(), e))?;
+    
+    signing::verify(trusted, &content, &detached).map(|_| ()).map_err(|e| format!(
+        "Signature de {} refusée: {}. Le fichier a peut-être été remplacé; il n'a pas été ouvert.\n{}",
+        path.display(), e, LEGITIMATE_CHANGE_HINT
+    ))
+}
+
+/// Écrit la signature détachée d'une base
+pub fn write_signature(path: &Path, key: &SigningKey) -> Result<(), String> {
+    let content = fs::read(path).map_err(|e| format!("Impossible de lire {}: {}", path.display(), e))?;
+    let signature_path = signing::signature_path(path);
+    fs::write(&signature_path, signing::sign(key, &content))
+        .map_err(|e| format!("Impossible d'enregistrer la signature {}: {}", signature_path.display(), e))
+}
+
+/// Vérifie, avant de demander le mot de passe, la signature d'une base dont la signature est
+/// exigée; sans effet pour les autres bases
+pub fn verify_vault(path: &Path) -> Result<(), String> {
+    let config = load()?;
+    if !config.is_signed(path) {
+        return Ok(());
+    }
+    check(path, &config.trusted()?)
+}
+
+/// Vérifie la signature d'une autre version d'une base (copie à fusionner), si la signature
+/// de la base est exigée
+pub fn verify_copy(path: &Path, copy: &Path) -> Result<(), String> {
+    let config = load()?;
+    if !config.is_signed(path) {
+        return Ok(());
+    }
+    check(copy, &config.trusted()?)
+}
+
+/// Signe une base venant d'être enregistrée, si sa signature est exigée
+pub fn sign_vault(path: &Path) -> Result<(), String> {
+    let config = load()?;
+    if !config.is_signed(path) {
+        return Ok(());
+    }
+    write_signature(path, &config.secret()?)
+}
+
+/// Vérifie qu'une base pourra être signée après son enregistrement : sans la clé de cet appareil,
+/// une base accompagnée d'une signature détachée n'est pas réécrite (les appareils qui exigent sa
+/// signature refuseraient ensuite de l'ouvrir)
+pub fn ensure_signable(path: &Path) -> Result<(), String> {
+    let config = load()?;
+    if config.is_signed(path) {
+        return config.secret().map(|_| ());
+    }
+    if signing::signature_path(path).exists() {
+        return Err(format!(
+            "La base {} est signée par un autre appareil: enregistrez-la depuis un appareil dont la clé de signature est acceptée.",
+            path.display()
+        ));
+    }
+    Ok(())
+}
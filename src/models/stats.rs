@@ -0,0 +1,90 @@
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use super::clock;
+use super::group::Group;
+use super::id::GroupId;
+
+/// Nombre de jours utilisé pour considérer qu'une entrée expire bientôt
+pub const EXPIRING_SOON_DAYS: i64 = 30;
+
+/// Statistiques d'un groupe (entrées comptées récursivement)
+#[derive(Debug, Clone)]
+pub struct GroupStats {
+    /// Nom du groupe
+    pub name: String,
+    
+    /// Profondeur du groupe dans l'arborescence (0 pour le groupe racine)
+    pub depth: usize,
+    
+    /// Nombre d'entrées du groupe et de tous ses sous-groupes
+    pub total_entries: usize,
+}
+
+/// Statistiques globales de la base de données
+#[derive(Debug, Clone)]
+pub struct DatabaseStats {
+    /// Nombre total d'entrées dans toute l'arborescence
+    pub total_entries: usize,
+    
+    /// Nombre d'entrées déjà expirées
+    pub expired: usize,
+    
+    /// Nombre d'entrées expirant dans les prochains jours
+    pub expiring_soon: usize,
+    
+    /// Statistiques par groupe, dans l'ordre d'affichage de l'arborescence
+    pub groups: Vec<GroupStats>,
+}
+
+impl DatabaseStats {
+    /// Calcule les statistiques de l'arborescence du groupe racine à partir de ses entrées,
+    /// données par leur groupe et leur date d'expiration
+    pub fn compute(root: &Group, entries: impl IntoIterator<Item = (GroupId, Option<DateTime<Utc>>)>) -> Self {
+        let mut stats = Self {
+            total_entries: 0,
+            expired: 0,
+            expiring_soon: 0,
+            groups: Vec::new(),
+        };
+        
+        let now = clock::now();
+        let soon = now + Duration::days(EXPIRING_SOON_DAYS);
+        let mut per_group: HashMap<GroupId, usize> = HashMap::new();
+        for (group, expires_at) in entries {
+            stats.total_entries += 1;
+            *per_group.entry(group).or_default() += 1;
+            match expires_at {
+                Some(expires_at) if expires_at <= now => stats.expired += 1,
+                Some(expires_at) if expires_at <= soon => stats.expiring_soon += 1,
+                _ => {}
+            }
+        }
+        
+        stats.visit(root, 0, &per_group);
+        stats
+    }
+    
+    /// Parcourt récursivement les sous-groupes d'un groupe et retourne son nombre total d'entrées
+    fn visit(&mut self, group: &Group, depth: usize, per_group: &HashMap<GroupId, usize>) -> usize {
+        // Réserver la place du groupe pour conserver l'ordre parent -> enfants
+        let index = self.groups.len();
+        self.groups.push(GroupStats {
+            name: group.name.clone(),
+            depth,
+            total_entries: 0,
+        });
+        
+        let mut total = per_group.get(&group.id).copied().unwrap_or(0);
+        
+        // Trier les sous-groupes par nom pour un affichage stable
+        let mut subgroups: Vec<&Group> = group.subgroups.values().collect();
+        subgroups.sort_by(|a, b| a.name.cmp(&b.name));
+        
+        for subgroup in subgroups {
+            total += self.visit(subgroup, depth + 1, per_group);
+        }
+        
+        self.groups[index].total_entries = total;
+        total
+    }
+}
@@ -0,0 +1,148 @@
+use data_encoding::HEXLOWER;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+use crate::utils::password_analyzer::PasswordAnalyzer;
+
+use super::database::Database;
+use super::entry::Entry;
+use super::id::EntryId;
+
+/// Score mémorisé pour une entrée, avec l'empreinte du mot de passe analysé
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedScore {
+    pub score: u8,
+    
+    /// SHA-256 de l'identifiant de l'entrée et du mot de passe (l'identifiant évite que deux
+    /// entrées au même mot de passe aient la même empreinte)
+    pub password_hash: String,
+}
+
+/// Scores de force des mots de passe lors du dernier audit, enregistrés dans les métadonnées :
+/// un audit suivant ne réanalyse que les mots de passe modifiés depuis.
+///
+/// Tous les scores sont écartés lorsque l'analyseur change (version ou strength.toml).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StrengthCache {
+    /// Empreinte de l'analyseur ayant calculé les scores
+    pub analyzer: String,
+    pub scores: HashMap<EntryId, CachedScore>,
+}
+
+/// Résultat d'une évaluation de la force des mots de passe
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StrengthScores {
+    pub scores: HashMap<EntryId, u8>,
+    
+    /// Nombre de scores repris du cache sans nouvelle analyse
+    pub reused: usize,
+}
+
+/// Empreinte du mot de passe d'une entrée
+fn password_hash(entry: &Entry) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(entry.id.to_string());
+    hasher.update([0]);
+    hasher.update(&entry.password);
+    HEXLOWER.encode(&hasher.finalize())
+}
+
+impl Database {
+    /// Scores de force des entrées retenues par `filter`, repris du cache pour les mots de passe
+    /// inchangés depuis la dernière évaluation; le cache est mis à jour (sans changer la date de
+    /// dernière modification) et oublie les entrées supprimées
+    pub fn strength_scores(&mut self, analyzer: &PasswordAnalyzer, filter: impl Fn(&Entry) -> bool) -> StrengthScores {
+        let fingerprint = analyzer.fingerprint();
+        let mut cache = std::mem::take(&mut self.metadata.strength_cache);
+        if cache.analyzer != fingerprint {
+            cache = StrengthCache { analyzer: fingerprint, scores: HashMap::new() };
+        }
+        
+        let mut result = StrengthScores::default();
+        let mut known = HashMap::new();
+        for entry in self.iter_entries() {
+            let hash = password_hash(entry);
+            let cached = cache.scores.remove(&entry.id).filter(|cached| cached.password_hash == hash);
+            if !filter(entry) {
+                if let Some(cached) = cached {
+                    known.insert(entry.id, cached);
+                }
+                continue;
+            }
+            
+            let score = match cached {
+                Some(cached) => {
+                    result.reused += 1;
+                    cached.score
+                }
+                None => analyzer.analyze(&entry.password).score,
+            };
+            result.scores.insert(entry.id, score);
+            known.insert(entry.id, CachedScore { score, password_hash: hash });
+        }
+        
+        cache.scores = known;
+        self.metadata.strength_cache = cache;
+        self.is_modified = true;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::key_derivation::KdfParams;
+    use crate::models::repository::{Repository, Timings};
+    use crate::utils::password_analyzer::AnalyzerConfig;
+    
+    #[test]
+    fn test_unchanged_passwords_are_not_reanalyzed() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let mail = db.add_entry(None, Entry::builder().title("Mail").password("azerty").build().unwrap()).unwrap();
+        let bank = db.add_entry(None, Entry::builder().title("Banque").password("k7#Qm2!vX9@pL4$w").build().unwrap()).unwrap();
+        let analyzer = PasswordAnalyzer::new();
+        
+        let first = db.strength_scores(&analyzer, |_| true);
+        assert_eq!(first.reused, 0);
+        assert_eq!(first.scores[&mail], analyzer.analyze("azerty").score);
+        
+        db.update_entry(&mail, |entry| entry.password = "Tr0ub4dor&3-cheval".to_string()).unwrap();
+        let second = db.strength_scores(&analyzer, |_| true);
+        assert_eq!(second.reused, 1);
+        assert_eq!(second.scores[&mail], analyzer.analyze("Tr0ub4dor&3-cheval").score);
+        
+        // Les entrées écartées par le filtre gardent leur score en cache
+        let only_mail = db.strength_scores(&analyzer, |entry| entry.id == mail);
+        assert_eq!((only_mail.scores.len(), only_mail.reused), (1, 1));
+        assert_eq!(db.strength_scores(&analyzer, |_| true).reused, 2);
+        
+        let mut config = AnalyzerConfig::default();
+        config.thresholds.strong = 90;
+        let tuned = PasswordAnalyzer::with_config(config).unwrap();
+        assert_eq!(db.strength_scores(&tuned, |_| true).reused, 0);
+        
+        db.remove_entry(&bank).unwrap();
+        db.strength_scores(&tuned, |_| true);
+        assert_eq!(db.metadata.strength_cache.scores.len(), 1);
+    }
+    
+    #[test]
+    fn test_cache_survives_save_and_reload() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        db.add_entry(None, Entry::builder().title("Mail").password("azerty").build().unwrap()).unwrap();
+        db.mark_as_saved();
+        let analyzer = PasswordAnalyzer::new();
+        
+        let updated_at = db.metadata.updated_at;
+        db.strength_scores(&analyzer, |_| true);
+        assert!(db.is_dirty());
+        assert_eq!(db.metadata.updated_at, updated_at);
+        
+        let kdf = KdfParams::Pbkdf2 { iterations: 10 };
+        let bytes = Repository::encode_with_kdf(&db, "pw", kdf, &mut Timings::disabled()).unwrap();
+        let mut reloaded = Repository::decode(&bytes, "pw").unwrap();
+        assert_eq!(reloaded.metadata.strength_cache, db.metadata.strength_cache);
+        assert_eq!(reloaded.strength_scores(&analyzer, |_| true).reused, 1);
+    }
+}
\ No newline at end of file
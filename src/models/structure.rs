@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::database::{Database, DatabaseError};
+use super::group::Group;
+use super::id::GroupId;
+
+/// Valeur du champ `format` d'un fichier de structure
+const STRUCTURE_FORMAT: &str = "keeprust-structure";
+
+/// Version du format de structure produite par cette version de keeprust
+const STRUCTURE_VERSION: u32 = 1;
+
+/// Erreurs lors de la lecture ou de l'application d'une structure
+#[derive(Error, Debug)]
+pub enum StructureError {
+    #[error("Fichier de structure invalide: {0}")]
+    Json(#[from] serde_json::Error),
+    
+    #[error("Ce fichier n'est pas une structure de groupes keeprust (format '{0}')")]
+    Format(String),
+    
+    #[error("Version de structure non prise en charge: {0} (au plus {STRUCTURE_VERSION})")]
+    Version(u32),
+    
+    #[error("Groupe sans nom dans la structure, sous '{0}'")]
+    EmptyName(String),
+    
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+}
+
+/// Groupe d'une arborescence exportée, sans ses entrées
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupNode {
+    pub name: String,
+    
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<GroupNode>,
+}
+
+impl GroupNode {
+    /// Arborescence d'un groupe, sous-groupes par ordre alphabétique (corbeille exclue)
+    fn from_group(group: &Group, trash: Option<GroupId>) -> Self {
+        let mut subgroups: Vec<&Group> = group.subgroups.values().filter(|subgroup| Some(subgroup.id) != trash).collect();
+        subgroups.sort_by(|a, b| a.name.cmp(&b.name));
+        Self {
+            name: group.name.clone(),
+            groups: subgroups.into_iter().map(|subgroup| GroupNode::from_group(subgroup, trash)).collect(),
+        }
+    }
+    
+    /// Nombre de groupes de l'arborescence, celui-ci inclus
+    fn count(&self) -> usize {
+        1 + self.groups.iter().map(GroupNode::count).sum::<usize>()
+    }
+}
+
+/// Arborescence des groupes d'une base, sans entrées, pour reproduire le même classement dans
+/// d'autres bases (travail, famille, équipe).
+///
+/// Les groupes keeprust n'ont ni icône ni réglage propre : seuls leurs noms et leur imbrication
+/// sont exportés. La corbeille n'en fait jamais partie.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Structure {
+    pub format: String,
+    pub version: u32,
+    
+    /// Sous-groupes du groupe exporté (son propre nom n'est pas repris)
+    pub groups: Vec<GroupNode>,
+}
+
+impl Structure {
+    /// Nombre total de groupes décrits
+    pub fn group_count(&self) -> usize {
+        self.groups.iter().map(GroupNode::count).sum()
+    }
+    
+    /// Document JSON indenté
+    pub fn to_json(&self) -> Result<String, StructureError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+    
+    /// Lit et vérifie un document JSON produit par `export-structure`
+    pub fn from_json(text: &str) -> Result<Self, StructureError> {
+        let structure: Structure = serde_json::from_str(text)?;
+        if structure.format != STRUCTURE_FORMAT {
+            return Err(StructureError::Format(structure.format));
+        }
+        if structure.version > STRUCTURE_VERSION {
+            return Err(StructureError::Version(structure.version));
+        }
+        check_names(&structure.groups, "racine")?;
+        Ok(structure)
+    }
+}
+
+/// Refuse les groupes sans nom, qui ne pourraient pas être retrouvés lors d'un nouvel import
+fn check_names(nodes: &[GroupNode], parent: &str) -> Result<(), StructureError> {
+    for node in nodes {
+        if node.name.trim().is_empty() {
+            return Err(StructureError::EmptyName(parent.to_string()));
+        }
+        check_names(&node.groups, &node.name)?;
+    }
+    Ok(())
+}
+
+/// Bilan de l'application d'une structure
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StructureImport {
+    /// Groupes créés
+    pub created: usize,
+    
+    /// Groupes déjà présents sous le même parent, conservés tels quels
+    pub existing: usize,
+}
+
+impl Database {
+    /// Arborescence des sous-groupes de `group_id` (groupe racine si None), sans entrées
+    pub fn export_structure(&self, group_id: Option<&GroupId>) -> Result<Structure, DatabaseError> {
+        let group = match group_id {
+            Some(id) => self.find_group(id).ok_or(DatabaseError::GroupNotFound(*id))?,
+            None => &self.root_group,
+        };
+        let node = GroupNode::from_group(group, self.metadata.trash_group_id);
+        Ok(Structure { format: STRUCTURE_FORMAT.to_string(), version: STRUCTURE_VERSION, groups: node.groups })
+    }
+    
+    /// Crée sous `parent_id` (groupe racine si None) les groupes de la structure qui n'existent
+    /// pas encore; un groupe de même nom sous le même parent est réutilisé, sans doublon
+    pub fn import_structure(&mut self, structure: &Structure, parent_id: Option<&GroupId>) -> Result<StructureImport, DatabaseError> {
+        let parent = match parent_id {
+            Some(id) => self.find_group(id).ok_or(DatabaseError::GroupNotFound(*id))?.id,
+            None => self.root_group.id,
+        };
+        let mut report = StructureImport::default();
+        self.import_nodes(&structure.groups, parent, &mut report)?;
+        Ok(report)
+    }
+    
+    fn import_nodes(&mut self, nodes: &[GroupNode], parent: GroupId, report: &mut StructureImport) -> Result<(), DatabaseError> {
+        let trash = self.metadata.trash_group_id;
+        for node in nodes {
+            // Parmi des groupes de même nom, le plus ancien est retenu
+            let existing = self.find_group(&parent).and_then(|group| {
+                group.subgroups.values()
+                    .filter(|subgroup| subgroup.name == node.name && Some(subgroup.id) != trash)
+                    .min_by_key(|subgroup| subgroup.created_at)
+                    .map(|subgroup| subgroup.id)
+            });
+            let id = match existing {
+                Some(id) => {
+                    report.existing += 1;
+                    id
+                }
+                None => {
+                    report.created += 1;
+                    self.add_group(Some(&parent), node.name.clone())?
+                }
+            };
+            self.import_nodes(&node.groups, id, report)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_structure_round_trip_without_duplicates() {
+        let mut source = Database::new("Travail".to_string(), String::new());
+        let clients = source.add_group(None, "Clients".to_string()).unwrap();
+        source.add_group(Some(&clients), "Europe".to_string()).unwrap();
+        source.add_group(None, "Admin".to_string()).unwrap();
+        source.add_entry(Some(&clients), crate::models::entry::Entry::builder().title("Portail").build().unwrap()).unwrap();
+        
+        let json = source.export_structure(None).unwrap().to_json().unwrap();
+        assert!(!json.contains("Portail"));
+        let structure = Structure::from_json(&json).unwrap();
+        assert_eq!(structure.groups.iter().map(|node| node.name.as_str()).collect::<Vec<_>>(), vec!["Admin", "Clients"]);
+        assert_eq!(structure.group_count(), 3);
+        
+        let mut target = Database::new("Famille".to_string(), String::new());
+        target.add_group(None, "Clients".to_string()).unwrap();
+        let report = target.import_structure(&structure, None).unwrap();
+        assert_eq!(report, StructureImport { created: 2, existing: 1 });
+        assert!(target.find_group_by_path("Clients/Europe").is_some());
+        
+        // Un second import ne crée rien
+        assert_eq!(target.import_structure(&structure, None).unwrap(), StructureImport { created: 0, existing: 3 });
+        
+        assert!(matches!(Structure::from_json(r#"{"format":"autre","version":1,"groups":[]}"#), Err(StructureError::Format(_))));
+        assert!(matches!(Structure::from_json(r#"{"format":"keeprust-structure","version":1,"groups":[{"name":" "}]}"#), Err(StructureError::EmptyName(_))));
+    }
+}
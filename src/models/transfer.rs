@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use super::database::{Database, DatabaseError};
+use super::group::Group;
+use super::id::{EntryId, GroupId};
+
+/// Renouvelle les IDs d'une arborescence copiée; les liens internes (parents, copies de conflit)
+/// suivent les nouveaux IDs
+fn regenerate_ids(group: &mut Group, parent_id: Option<GroupId>, entry_ids: &HashMap<EntryId, EntryId>) {
+    group.id = GroupId::new();
+    group.parent_id = parent_id;
+    
+    group.entries = std::mem::take(&mut group.entries).into_values()
+        .map(|mut entry| {
+            entry.id = entry_ids[&entry.id];
+            entry.conflict_of = entry.conflict_of.and_then(|original| entry_ids.get(&original).copied());
+            (entry.id, entry)
+        })
+        .collect();
+    
+    let group_id = group.id;
+    group.subgroups = std::mem::take(&mut group.subgroups).into_values()
+        .map(|mut subgroup| {
+            regenerate_ids(&mut subgroup, Some(group_id), entry_ids);
+            (subgroup.id, subgroup)
+        })
+        .collect();
+}
+
+impl Database {
+    /// Copie d'un groupe et de toute sa descendance, destinée à une autre base.
+    ///
+    /// Sans `keep_ids`, groupes et entrées reçoivent de nouveaux IDs pour éviter toute collision
+    /// (la même arborescence peut alors être copiée plusieurs fois).
+    pub fn clone_subtree(&self, group_id: &GroupId, keep_ids: bool) -> Result<Group, DatabaseError> {
+        let mut subtree = self.find_group(group_id).ok_or(DatabaseError::GroupNotFound(*group_id))?.clone();
+        
+        if !keep_ids {
+            let entry_ids: HashMap<EntryId, EntryId> = subtree.iter_groups()
+                .flat_map(|group| group.entries.keys())
+                .map(|id| (*id, EntryId::new()))
+                .collect();
+            let parent_id = subtree.parent_id;
+            regenerate_ids(&mut subtree, parent_id, &entry_ids);
+        }
+        
+        // L'origine d'une entrée de la corbeille n'a pas de sens dans une autre base
+        let mut stack = vec![&mut subtree];
+        while let Some(group) = stack.pop() {
+            for entry in group.entries.values_mut() {
+                entry.deleted_from = None;
+                entry.deleted_from_parents.clear();
+            }
+            stack.extend(group.subgroups.values_mut());
+        }
+        Ok(subtree)
+    }
+    
+    /// Ajoute une arborescence copiée d'une autre base sous un groupe (le groupe racine si aucun
+    /// ID n'est fourni); échoue si l'un de ses IDs existe déjà dans cette base
+    pub fn insert_subtree(&mut self, parent_id: Option<&GroupId>, mut subtree: Group) -> Result<GroupId, DatabaseError> {
+        let parent_id = parent_id.copied().unwrap_or(self.root_group.id);
+        if self.find_group(&parent_id).is_none() {
+            return Err(DatabaseError::GroupNotFound(parent_id));
+        }
+        
+        for group in subtree.iter_groups() {
+            if self.find_group(&group.id).is_some() {
+                return Err(DatabaseError::DuplicateId(group.id.to_string()));
+            }
+            if let Some(id) = group.entries.keys().find(|id| self.find_entry(id).is_some()) {
+                return Err(DatabaseError::DuplicateId(id.to_string()));
+            }
+        }
+        
+        subtree.parent_id = Some(parent_id);
+        let group_id = subtree.id;
+        self.find_group_mut(&parent_id)
+            .ok_or(DatabaseError::GroupNotFound(parent_id))?
+            .add_subgroup(subtree);
+        self.mark_as_modified();
+        Ok(group_id)
+    }
+    
+    /// Supprime définitivement un groupe et toute sa descendance; le groupe racine ne peut pas
+    /// être supprimé
+    pub fn remove_group(&mut self, group_id: &GroupId) -> Result<Group, DatabaseError> {
+        let parent_id = self.find_group(group_id)
+            .ok_or(DatabaseError::GroupNotFound(*group_id))?
+            .parent_id
+            .ok_or(DatabaseError::RootGroup)?;
+        
+        let group = self.find_group_mut(&parent_id)
+            .and_then(|parent| parent.remove_subgroup(group_id))
+            .ok_or(DatabaseError::GroupNotFound(*group_id))?;
+        
+        if self.metadata.trash_group_id.is_some_and(|trash_id| group.iter_groups().any(|group| group.id == trash_id)) {
+            self.metadata.trash_group_id = None;
+        }
+        self.mark_as_modified();
+        Ok(group)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    
+    #[test]
+    fn test_subtree_copy_between_databases() {
+        let mut source = Database::new("Source".to_string(), String::new());
+        let work = source.add_group(None, "Work".to_string()).unwrap();
+        let servers = source.add_group(Some(&work), "Servers".to_string()).unwrap();
+        let ssh = source.add_entry(Some(&servers), Entry::new("ssh".to_string(), "root".to_string(), "pw".to_string(), String::new(), String::new())).unwrap();
+        
+        let mut target = Database::new("Cible".to_string(), String::new());
+        
+        // Avec les IDs d'origine : une seconde copie entre en collision
+        let copied = target.insert_subtree(None, source.clone_subtree(&work, true).unwrap()).unwrap();
+        assert_eq!(copied, work);
+        assert_eq!(target.find_entry(&ssh).unwrap().title, "ssh");
+        assert_eq!(target.insert_subtree(None, source.clone_subtree(&work, true).unwrap()).unwrap_err(),
+            DatabaseError::DuplicateId(work.to_string()));
+        
+        // Avec de nouveaux IDs : copie indépendante, liens de parenté cohérents
+        let fresh = target.insert_subtree(None, source.clone_subtree(&work, false).unwrap()).unwrap();
+        assert_ne!(fresh, work);
+        assert_eq!(target.count_entries(), 2);
+        let fresh_group = target.find_group(&fresh).unwrap();
+        assert!(fresh_group.subgroups.values().all(|group| group.parent_id == Some(fresh)));
+        
+        // Déplacement : suppression de l'arborescence d'origine
+        assert_eq!(source.remove_group(&work).unwrap().name, "Work");
+        assert_eq!(source.count_entries(), 0);
+        let root = source.root_group.id;
+        assert_eq!(source.remove_group(&root).unwrap_err(), DatabaseError::RootGroup);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,236 @@
+use std::collections::HashSet;
+
+use super::database::{Database, DatabaseError};
+use super::entry::Entry;
+use super::group::Group;
+use super::id::{EntryId, GroupId};
+
+/// Nom du groupe servant de corbeille
+pub const TRASH_GROUP_NAME: &str = "Corbeille";
+
+/// Étape du cycle de vie d'une entrée
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryState {
+    /// Entrée en usage
+    Active,
+    
+    /// Entrée archivée (compte inactif), masquée par défaut dans les listes
+    Archived,
+    
+    /// Entrée placée dans la corbeille (qu'elle soit archivée ou non)
+    Trashed,
+}
+
+impl Database {
+    /// Groupe servant de corbeille, s'il a déjà été créé
+    pub fn trash_group(&self) -> Option<&Group> {
+        self.find_group(&self.metadata.trash_group_id?)
+    }
+    
+    /// Indique si un groupe est la corbeille ou l'un de ses sous-groupes
+    pub fn is_trash_group(&self, group_id: &GroupId) -> bool {
+        self.trash_group().is_some_and(|trash| trash.iter_groups().any(|group| group.id == *group_id))
+    }
+    
+    /// Indique si une entrée se trouve dans la corbeille
+    pub fn is_in_trash(&self, entry_id: &EntryId) -> bool {
+        self.trash_group().is_some_and(|trash| trash.iter_groups().any(|group| group.entries.contains_key(entry_id)))
+    }
+    
+    /// IDs de la corbeille et de ses sous-groupes (aucun si elle n'existe pas encore)
+    pub fn trash_group_ids(&self) -> HashSet<GroupId> {
+        self.trash_group().map(|trash| trash.iter_groups().map(|group| group.id).collect()).unwrap_or_default()
+    }
+    
+    /// État d'une entrée, ou None si elle n'existe pas
+    pub fn entry_state(&self, entry_id: &EntryId) -> Option<EntryState> {
+        let entry = self.find_entry(entry_id)?;
+        Some(if self.is_in_trash(entry_id) {
+            EntryState::Trashed
+        } else if entry.archived {
+            EntryState::Archived
+        } else {
+            EntryState::Active
+        })
+    }
+    
+    /// Entrée désignée par son ID, refusée si elle est dans la corbeille (sauf avec `include_trash`) :
+    /// une commande ne doit pas agir sans le dire sur une entrée supprimée
+    pub fn find_live_entry(&self, entry_id: &EntryId, include_trash: bool) -> Result<&Entry, DatabaseError> {
+        let entry = self.find_entry(entry_id).ok_or(DatabaseError::EntryNotFound(*entry_id))?;
+        if !include_trash && self.is_in_trash(entry_id) {
+            return Err(DatabaseError::InTrash(*entry_id));
+        }
+        Ok(entry)
+    }
+    
+    /// Groupes parents d'un groupe, du plus proche au groupe racine
+    fn parent_groups(&self, group_id: &GroupId) -> Vec<GroupId> {
+        let mut parents = Vec::new();
+        let mut current = self.find_group(group_id).and_then(|group| group.parent_id);
+        while let Some(parent_id) = current
+            && !parents.contains(&parent_id)
+        {
+            parents.push(parent_id);
+            current = self.find_group(&parent_id).and_then(|group| group.parent_id);
+        }
+        parents
+    }
+    
+    /// Retourne l'ID de la corbeille, en la créant sous le groupe racine si nécessaire
+    fn ensure_trash_group(&mut self) -> GroupId {
+        if let Some(trash_id) = self.metadata.trash_group_id
+            && self.find_group(&trash_id).is_some()
+        {
+            return trash_id;
+        }
+        
+        let trash_id = self.add_group(None, TRASH_GROUP_NAME.to_string())
+            .expect("le groupe racine existe toujours");
+        self.metadata.trash_group_id = Some(trash_id);
+        trash_id
+    }
+    
+    /// Déplace une entrée dans un autre groupe
+    pub fn move_entry(&mut self, entry_id: &EntryId, target_id: &GroupId) -> Result<(), DatabaseError> {
+        if self.find_group(target_id).is_none() {
+            return Err(DatabaseError::GroupNotFound(*target_id));
+        }
+        
+        let entry = self.find_entry_group_mut(entry_id)
+            .and_then(|group| group.remove_entry(entry_id))
+            .ok_or(DatabaseError::EntryNotFound(*entry_id))?;
+        self.find_group_mut(target_id)
+            .ok_or(DatabaseError::GroupNotFound(*target_id))?
+            .add_entry(entry);
+        
+        self.mark_as_modified();
+        Ok(())
+    }
+    
+    /// Place une entrée dans la corbeille en mémorisant son groupe d'origine et les parents de
+    /// celui-ci; retourne false si elle y était déjà. Une entrée verrouillée est refusée
+    pub fn trash_entry(&mut self, entry_id: &EntryId) -> Result<bool, DatabaseError> {
+        self.trash_entry_with(entry_id, false)
+    }
+    
+    /// Comme `trash_entry`, y compris pour une entrée verrouillée (--unlock-entry)
+    pub fn trash_entry_unlocked(&mut self, entry_id: &EntryId) -> Result<bool, DatabaseError> {
+        self.trash_entry_with(entry_id, true)
+    }
+    
+    pub(super) fn trash_entry_with(&mut self, entry_id: &EntryId, unlock: bool) -> Result<bool, DatabaseError> {
+        self.check_unlocked(entry_id, unlock)?;
+        let origin = self.iter_groups()
+            .find(|group| group.entries.contains_key(entry_id))
+            .ok_or(DatabaseError::EntryNotFound(*entry_id))?
+            .id;
+        if self.is_trash_group(&origin) {
+            return Ok(false);
+        }
+        
+        let parents = self.parent_groups(&origin);
+        let trash_id = self.ensure_trash_group();
+        self.move_entry(entry_id, &trash_id)?;
+        self.update_entry_metadata(entry_id, |entry| {
+            entry.deleted_from = Some(origin);
+            entry.deleted_from_parents = parents;
+        })?;
+        Ok(true)
+    }
+    
+    /// Restaure une entrée de la corbeille dans son groupe d'origine; si celui-ci a été supprimé
+    /// (ou mis à la corbeille), dans le plus proche de ses parents encore présent, sinon dans le
+    /// groupe racine. Retourne le groupe de destination
+    pub fn restore_entry(&mut self, entry_id: &EntryId) -> Result<GroupId, DatabaseError> {
+        let entry = self.find_entry(entry_id).ok_or(DatabaseError::EntryNotFound(*entry_id))?;
+        if !self.is_in_trash(entry_id) {
+            return Err(DatabaseError::NotInTrash(*entry_id));
+        }
+        
+        let target_id = entry.deleted_from.iter()
+            .chain(&entry.deleted_from_parents)
+            .find(|group_id| self.find_group(group_id).is_some() && !self.is_trash_group(group_id))
+            .copied()
+            .unwrap_or(self.root_group.id);
+        self.move_entry(entry_id, &target_id)?;
+        self.update_entry_metadata(entry_id, |entry| {
+            entry.deleted_from = None;
+            entry.deleted_from_parents.clear();
+        })?;
+        Ok(target_id)
+    }
+    
+    /// Vide la corbeille; retourne le nombre d'entrées supprimées définitivement
+    pub fn empty_trash(&mut self) -> usize {
+        let Some(trash_id) = self.metadata.trash_group_id else {
+            return 0;
+        };
+        let Some(trash) = self.find_group_mut(&trash_id) else {
+            return 0;
+        };
+        
+        let count: usize = trash.iter_groups().map(|group| group.entries.len()).sum();
+        if count == 0 && trash.subgroups.is_empty() {
+            return 0;
+        }
+        
+        // Les mots de passe sont effacés de la mémoire à la destruction des entrées
+        trash.entries.clear();
+        trash.subgroups.clear();
+        self.mark_as_modified();
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entry::Entry;
+    
+    #[test]
+    fn test_trash_restore_and_empty() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let work = db.add_group(None, "Work".to_string()).unwrap();
+        let vpn = db.add_entry(Some(&work), Entry::new("VPN".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new())).unwrap();
+        let mail = db.add_entry(None, Entry::new("Mail".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new())).unwrap();
+        
+        assert!(db.trash_entry(&vpn).unwrap());
+        assert!(!db.trash_entry(&vpn).unwrap());
+        assert!(db.is_in_trash(&vpn));
+        assert_eq!(db.trash_group().unwrap().name, TRASH_GROUP_NAME);
+        assert_eq!(db.restore_entry(&vpn).unwrap(), work);
+        assert_eq!(db.restore_entry(&vpn).unwrap_err(), DatabaseError::NotInTrash(vpn));
+        
+        // Groupe d'origine disparu : restauration dans son parent, puis dans le groupe racine
+        let vpns = db.add_group(Some(&work), "VPN".to_string()).unwrap();
+        db.move_entry(&vpn, &vpns).unwrap();
+        db.trash_entry(&vpn).unwrap();
+        assert_eq!(db.entry_state(&vpn), Some(EntryState::Trashed));
+        assert_eq!(db.find_live_entry(&vpn, false).unwrap_err(), DatabaseError::InTrash(vpn));
+        assert!(db.find_live_entry(&vpn, true).is_ok());
+        db.find_group_mut(&work).unwrap().remove_subgroup(&vpns);
+        assert_eq!(db.restore_entry(&vpn).unwrap(), work);
+        assert_eq!(db.entry_state(&vpn), Some(EntryState::Active));
+        
+        db.trash_entry(&vpn).unwrap();
+        db.root_group.remove_subgroup(&work);
+        assert_eq!(db.restore_entry(&vpn).unwrap(), db.root_group.id);
+        
+        db.trash_entry(&mail).unwrap();
+        assert_eq!(db.empty_trash(), 1);
+        assert!(db.find_entry(&mail).is_none());
+        assert_eq!(db.count_entries(), 1);
+    }    
+    #[test]
+    fn test_locked_entry_stays_out_of_trash_without_override() {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let vpn = db.add_entry(None, Entry::new("VPN".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new())).unwrap();
+        db.set_locked(&vpn, true).unwrap();
+        
+        assert_eq!(db.trash_entry(&vpn).unwrap_err(), DatabaseError::EntryLocked("VPN".to_string()));
+        assert!(!db.is_in_trash(&vpn));
+        assert!(db.trash_entry_unlocked(&vpn).unwrap());
+        assert!(db.is_in_trash(&vpn));
+    }
+}
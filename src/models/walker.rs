@@ -0,0 +1,284 @@
+use super::database::Database;
+use super::entry::Entry;
+use super::group::Group;
+use super::id::{EntryId, GroupId};
+
+/// Décision retournée par le callback de parcours
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalkControl {
+    /// Continue le parcours normalement
+    Continue,
+    
+    /// Ignore les entrées et sous-groupes du groupe visité (sans effet sur une entrée)
+    #[allow(dead_code)]
+    SkipChildren,
+    
+    /// Interrompt complètement le parcours
+    Stop,
+}
+
+/// Options du parcours de l'arborescence
+#[derive(Debug, Clone, Default)]
+pub struct WalkOptions {
+    /// Profondeur maximale visitée (0 = groupe racine uniquement, None = illimitée)
+    pub max_depth: Option<usize>,
+}
+
+impl WalkOptions {
+    /// Limite le parcours à une profondeur maximale
+    #[allow(dead_code)]
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+}
+
+impl Database {
+    /// Parcourt toute l'arborescence en profondeur.
+    ///
+    /// Le callback reçoit le chemin (noms des groupes depuis la racine, groupe courant inclus),
+    /// le groupe courant et `None` lors de la visite d'un groupe, ou `Some(entrée)` pour
+    /// chacune de ses entrées. Les sous-groupes et les entrées sont visités par ordre alphabétique.
+    pub fn walk<'a, F>(&'a self, callback: F)
+    where
+        F: FnMut(&[String], &'a Group, Option<&'a Entry>) -> WalkControl,
+    {
+        self.walk_with(&WalkOptions::default(), callback);
+    }
+    
+    /// Parcourt l'arborescence avec des options (profondeur maximale)
+    pub fn walk_with<'a, F>(&'a self, options: &WalkOptions, mut callback: F)
+    where
+        F: FnMut(&[String], &'a Group, Option<&'a Entry>) -> WalkControl,
+    {
+        let mut path = Vec::new();
+        walk_group(&self.root_group, options, &mut path, &mut callback);
+    }
+    
+    /// Version mutable de `walk`.
+    ///
+    /// Pendant la visite d'une entrée, la table `entries` du groupe passé au callback est
+    /// temporairement vide : les entrées ajoutées à ce moment sont conservées à la fin de la visite.
+    /// La base de données est marquée comme modifiée à l'issue du parcours.
+    #[allow(dead_code)]
+    pub fn walk_mut<F>(&mut self, callback: F)
+    where
+        F: FnMut(&[String], &mut Group, Option<&mut Entry>) -> WalkControl,
+    {
+        self.walk_mut_with(&WalkOptions::default(), callback);
+    }
+    
+    /// Version mutable de `walk_with`
+    #[allow(dead_code)]
+    pub fn walk_mut_with<F>(&mut self, options: &WalkOptions, mut callback: F)
+    where
+        F: FnMut(&[String], &mut Group, Option<&mut Entry>) -> WalkControl,
+    {
+        let mut path = Vec::new();
+        walk_group_mut(&mut self.root_group, options, &mut path, &mut callback);
+        self.mark_as_modified();
+    }
+    
+    /// Chemin (noms des groupes depuis la racine) du groupe contenant une entrée
+    pub fn entry_path(&self, id: &EntryId) -> Option<Vec<String>> {
+        let mut found = None;
+        self.walk(|path, _, entry| {
+            if entry.is_some_and(|entry| entry.id == *id) {
+                found = Some(path.to_vec());
+                return WalkControl::Stop;
+            }
+            WalkControl::Continue
+        });
+        found
+    }
+    
+    /// Chemin (noms des groupes depuis la racine, groupe inclus) d'un groupe
+    pub fn group_path(&self, id: &GroupId) -> Option<Vec<String>> {
+        let mut found = None;
+        self.walk(|path, group, entry| {
+            if entry.is_none() && group.id == *id {
+                found = Some(path.to_vec());
+                return WalkControl::Stop;
+            }
+            WalkControl::Continue
+        });
+        found
+    }
+}
+
+/// Visite récursivement un groupe; retourne false si le parcours doit s'arrêter
+fn walk_group<'a, F>(group: &'a Group, options: &WalkOptions, path: &mut Vec<String>, callback: &mut F) -> bool
+where
+    F: FnMut(&[String], &'a Group, Option<&'a Entry>) -> WalkControl,
+{
+    if options.max_depth.is_some_and(|max| path.len() > max) {
+        return true;
+    }
+    
+    path.push(group.name.clone());
+    
+    let result = match callback(path, group, None) {
+        WalkControl::Stop => false,
+        WalkControl::SkipChildren => true,
+        WalkControl::Continue => walk_children(group, options, path, callback),
+    };
+    
+    path.pop();
+    result
+}
+
+/// Visite les entrées puis les sous-groupes d'un groupe
+fn walk_children<'a, F>(group: &'a Group, options: &WalkOptions, path: &mut Vec<String>, callback: &mut F) -> bool
+where
+    F: FnMut(&[String], &'a Group, Option<&'a Entry>) -> WalkControl,
+{
+    let mut entries: Vec<&'a Entry> = group.entries.values().collect();
+    entries.sort_by(|a, b| a.title.cmp(&b.title));
+    
+    for entry in entries {
+        if callback(path, group, Some(entry)) == WalkControl::Stop {
+            return false;
+        }
+    }
+    
+    let mut subgroups: Vec<&'a Group> = group.subgroups.values().collect();
+    subgroups.sort_by(|a, b| a.name.cmp(&b.name));
+    
+    subgroups.into_iter().all(|subgroup| walk_group(subgroup, options, path, callback))
+}
+
+/// Visite récursivement un groupe en mode mutable; retourne false si le parcours doit s'arrêter
+fn walk_group_mut<F>(group: &mut Group, options: &WalkOptions, path: &mut Vec<String>, callback: &mut F) -> bool
+where
+    F: FnMut(&[String], &mut Group, Option<&mut Entry>) -> WalkControl,
+{
+    if options.max_depth.is_some_and(|max| path.len() > max) {
+        return true;
+    }
+    
+    path.push(group.name.clone());
+    
+    let result = match callback(path, group, None) {
+        WalkControl::Stop => false,
+        WalkControl::SkipChildren => true,
+        WalkControl::Continue => walk_children_mut(group, options, path, callback),
+    };
+    
+    path.pop();
+    result
+}
+
+/// Visite les entrées puis les sous-groupes d'un groupe en mode mutable
+fn walk_children_mut<F>(group: &mut Group, options: &WalkOptions, path: &mut Vec<String>, callback: &mut F) -> bool
+where
+    F: FnMut(&[String], &mut Group, Option<&mut Entry>) -> WalkControl,
+{
+    // Sortir les entrées du groupe pour pouvoir prêter le groupe et l'entrée en même temps
+    let mut entries = std::mem::take(&mut group.entries);
+    let mut ids: Vec<EntryId> = entries.keys().copied().collect();
+    ids.sort_by(|a, b| entries[a].title.cmp(&entries[b].title));
+    
+    let mut keep_going = true;
+    for id in ids {
+        if let Some(entry) = entries.get_mut(&id)
+            && callback(path, group, Some(entry)) == WalkControl::Stop
+        {
+            keep_going = false;
+            break;
+        }
+    }
+    
+    // Réintégrer les entrées, y compris celles ajoutées pendant la visite
+    entries.extend(group.entries.drain());
+    group.entries = entries;
+    
+    if !keep_going {
+        return false;
+    }
+    
+    let mut ids: Vec<GroupId> = group.subgroups.keys().copied().collect();
+    ids.sort_by(|a, b| group.subgroups[a].name.cmp(&group.subgroups[b].name));
+    
+    for id in ids {
+        if let Some(subgroup) = group.subgroups.get_mut(&id)
+            && !walk_group_mut(subgroup, options, path, callback)
+        {
+            return false;
+        }
+    }
+    
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn sample_database() -> Database {
+        let mut db = Database::new("Test".to_string(), String::new());
+        let mut work = Group::new("Work".to_string(), Some(db.root_group.id));
+        let mut servers = Group::new("Servers".to_string(), Some(work.id));
+        servers.add_entry(Entry::new("ssh".to_string(), "root".to_string(), "pw".to_string(), String::new(), String::new()));
+        work.add_entry(Entry::new("VPN".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new()));
+        work.add_subgroup(servers);
+        db.root_group.add_entry(Entry::new("Mail".to_string(), "bob".to_string(), "pw".to_string(), String::new(), String::new()));
+        db.root_group.add_subgroup(work);
+        db
+    }
+    
+    fn visited_entries(db: &Database, options: &WalkOptions, prune: &str) -> Vec<String> {
+        let mut visited = Vec::new();
+        db.walk_with(options, |path, group, entry| {
+            match entry {
+                Some(entry) => visited.push(format!("{}/{}", path.join("/"), entry.title)),
+                None if group.name == prune => return WalkControl::SkipChildren,
+                None => {},
+            }
+            WalkControl::Continue
+        });
+        visited
+    }
+    
+    #[test]
+    fn test_walk_visits_paths_in_order() {
+        let db = sample_database();
+        let visited = visited_entries(&db, &WalkOptions::default(), "");
+        
+        assert_eq!(visited, vec!["Root/Mail", "Root/Work/VPN", "Root/Work/Servers/ssh"]);
+        
+        let ssh = db.iter_entries().find(|entry| entry.title == "ssh").unwrap().id;
+        assert_eq!(db.entry_path(&ssh), Some(vec!["Root".to_string(), "Work".to_string(), "Servers".to_string()]));
+        assert_eq!(db.group_path(&db.root_group.id), Some(vec!["Root".to_string()]));
+        assert_eq!(db.entry_path(&EntryId::new()), None);
+    }
+    
+    #[test]
+    fn test_walk_depth_limit_and_pruning() {
+        let db = sample_database();
+        
+        let visited = visited_entries(&db, &WalkOptions::default().max_depth(1), "");
+        assert_eq!(visited, vec!["Root/Mail", "Root/Work/VPN"]);
+        
+        let visited = visited_entries(&db, &WalkOptions::default(), "Work");
+        assert_eq!(visited, vec!["Root/Mail"]);
+    }
+    
+    #[test]
+    fn test_walk_mut_updates_entries_and_stops() {
+        let mut db = sample_database();
+        let mut count = 0;
+        db.walk_mut(|_, _, entry| {
+            if let Some(entry) = entry {
+                entry.username = "alice".to_string();
+                count += 1;
+                if count == 2 {
+                    return WalkControl::Stop;
+                }
+            }
+            WalkControl::Continue
+        });
+        
+        assert_eq!(db.count_entries(), 3);
+        assert_eq!(db.iter_entries().filter(|e| e.username == "alice").count(), 2);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,111 @@
+use data_encoding::HEXUPPER;
+use sha1::{Digest, Sha1};
+use std::collections::HashMap;
+#[cfg(feature = "hibp")]
+use std::process::Command;
+use thiserror::Error;
+
+/// API « range » de Have I Been Pwned : seuls les 5 premiers caractères de l'empreinte sont envoyés
+#[cfg(feature = "hibp")]
+const RANGE_API: &str = "https://api.pwnedpasswords.com/range/";
+
+/// Longueur du préfixe d'empreinte transmis à l'API
+const PREFIX_LEN: usize = 5;
+
+/// Délai maximal d'une requête, en secondes
+#[cfg(feature = "hibp")]
+const REQUEST_TIMEOUT_SECS: u32 = 10;
+
+/// Erreurs lors de la vérification d'un mot de passe auprès de Have I Been Pwned
+#[derive(Error, Debug)]
+pub enum BreachError {
+    #[error("impossible de lancer curl: {0}")]
+    Spawn(String),
+    
+    #[error("échec de la requête vers {0}")]
+    Request(String),
+    
+    #[error("cette version de keeprust a été compilée sans la vérification des fuites de données (fonctionnalité 'hibp')")]
+    Disabled,
+}
+
+/// Empreinte SHA-1 d'un mot de passe, en hexadécimal majuscule (format de l'API)
+pub fn password_hash(password: &str) -> String {
+    HEXUPPER.encode(&Sha1::digest(password.as_bytes()))
+}
+
+/// Nombre d'apparitions d'un suffixe d'empreinte dans une réponse de l'API (lignes `SUFFIXE:NOMBRE`).
+///
+/// Les lignes de remplissage (nombre nul) ne comptent pas.
+pub fn parse_range(body: &str, suffix: &str) -> u64 {
+    body.lines()
+        .filter_map(|line| line.trim().split_once(':'))
+        .find(|(candidate, _)| candidate.eq_ignore_ascii_case(suffix))
+        .and_then(|(_, count)| count.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Interroge l'API pour un préfixe d'empreinte (délégué à curl pour ne pas embarquer de client HTTP)
+#[cfg(feature = "hibp")]
+fn fetch_range(prefix: &str) -> Result<String, BreachError> {
+    let url = format!("{}{}", RANGE_API, prefix);
+    let output = Command::new("curl")
+        .args(["-fsS", "-H", "Add-Padding: true", "-A", concat!("keeprust/", env!("CARGO_PKG_VERSION"))])
+        .arg("-m").arg(REQUEST_TIMEOUT_SECS.to_string())
+        .arg(&url)
+        .output()
+        .map_err(|e| BreachError::Spawn(e.to_string()))?;
+    
+    if !output.status.success() {
+        return Err(BreachError::Request(url));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(not(feature = "hibp"))]
+fn fetch_range(_prefix: &str) -> Result<String, BreachError> {
+    Err(BreachError::Disabled)
+}
+
+/// Vérifie des mots de passe auprès de Have I Been Pwned, selon le principe du k-anonymat.
+///
+/// Les réponses sont conservées par préfixe pour ne pas répéter une requête pendant une vérification.
+#[derive(Debug, Default)]
+pub struct BreachChecker {
+    ranges: HashMap<String, String>,
+}
+
+impl BreachChecker {
+    /// Crée un vérificateur sans réponse en cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Nombre d'apparitions du mot de passe dans les fuites de données connues
+    pub fn occurrences(&mut self, password: &str) -> Result<u64, BreachError> {
+        let hash = password_hash(password);
+        let (prefix, suffix) = hash.split_at(PREFIX_LEN);
+        
+        if !self.ranges.contains_key(prefix) {
+            let body = fetch_range(prefix)?;
+            self.ranges.insert(prefix.to_string(), body);
+        }
+        Ok(parse_range(&self.ranges[prefix], suffix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_hash_and_range_parsing() {
+        let hash = password_hash("password");
+        assert_eq!(hash, "5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8");
+        
+        let body = "003D68EB55068C33ACE09247EE4C639306B:3\r\n1E4C9B93F3F0682250B6CF8331B7EE68FD8:9659365\r\n1E4C9B93F3F0682250B6CF8331B7EE68FD9:0\r\n";
+        assert_eq!(parse_range(body, &hash[PREFIX_LEN..]), 9_659_365);
+        assert_eq!(parse_range(body, "1E4C9B93F3F0682250B6CF8331B7EE68FD9"), 0);
+        assert_eq!(parse_range(body, "FFFF"), 0);
+    }
+}
\ No newline at end of file
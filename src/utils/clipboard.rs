@@ -1,7 +1,11 @@
-use clipboard::{ClipboardContext, ClipboardProvider};
+use std::io::{self, IsTerminal, Write};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use thiserror::Error;
+use zeroize::Zeroizing;
+
+use backend::ClipboardContext;
+use super::termux;
 
 #[derive(Error, Debug)]
 pub enum ClipboardError {
@@ -9,88 +13,326 @@ pub enum ClipboardError {
     AccessError(String),
     
     #[error("Presse-papiers non disponible")]
-    #[allow(dead_code)]
+    #[cfg_attr(feature = "clipboard", allow(dead_code))]
     NotAvailable,
 }
 
+/// Accès au presse-papiers système (fonctionnalité `clipboard`)
+#[cfg(feature = "clipboard")]
+mod backend {
+    use clipboard::ClipboardProvider;
+    use super::ClipboardError;
+    
+    pub use clipboard::ClipboardContext;
+    
+    /// Ouvre le presse-papiers système
+    pub fn open() -> Result<ClipboardContext, ClipboardError> {
+        ClipboardProvider::new().map_err(|e| ClipboardError::AccessError(e.to_string()))
+    }
+    
+    /// Remplace le contenu du presse-papiers
+    pub fn set(context: &mut ClipboardContext, text: String) -> Result<(), ClipboardError> {
+        context.set_contents(text).map_err(|e| ClipboardError::AccessError(e.to_string()))
+    }
+    
+    /// Lit le contenu du presse-papiers
+    pub fn get(context: &mut ClipboardContext) -> Result<String, ClipboardError> {
+        context.get_contents().map_err(|e| ClipboardError::AccessError(e.to_string()))
+    }
+}
+
+/// Remplaçant utilisé lorsque keeprust est compilé sans la fonctionnalité `clipboard`
+#[cfg(not(feature = "clipboard"))]
+mod backend {
+    use super::ClipboardError;
+    
+    pub struct ClipboardContext;
+    
+    pub fn open() -> Result<ClipboardContext, ClipboardError> {
+        Err(ClipboardError::NotAvailable)
+    }
+    
+    pub fn set(_context: &mut ClipboardContext, _text: String) -> Result<(), ClipboardError> {
+        Err(ClipboardError::NotAvailable)
+    }
+    
+    pub fn get(_context: &mut ClipboardContext) -> Result<String, ClipboardError> {
+        Err(ClipboardError::NotAvailable)
+    }
+}
+
+/// Presse-papiers utilisé : celui du système, ou celui d'Android sous Termux
+enum Context {
+    System(ClipboardContext),
+    
+    /// Presse-papiers d'Android, via Termux:API (termux-clipboard-get/set)
+    Termux,
+    
+    /// Presse-papiers en mémoire, partagé avec le test qui l'examine
+    #[cfg(test)]
+    Memory(std::sync::Arc<std::sync::Mutex<String>>),
+}
+
+impl Context {
+    fn open() -> Result<Self, ClipboardError> {
+        if termux::is_termux() {
+            return Ok(Self::Termux);
+        }
+        backend::open().map(Self::System)
+    }
+    
+    fn set(&mut self, text: String) -> Result<(), ClipboardError> {
+        match self {
+            Self::System(context) => backend::set(context, text),
+            Self::Termux => termux::set_clipboard(&text).map_err(ClipboardError::AccessError),
+            #[cfg(test)]
+            Self::Memory(content) => {
+                *content.lock().unwrap() = text;
+                Ok(())
+            }
+        }
+    }
+    
+    fn get(&mut self) -> Result<String, ClipboardError> {
+        match self {
+            Self::System(context) => backend::get(context),
+            Self::Termux => termux::clipboard().map_err(ClipboardError::AccessError),
+            #[cfg(test)]
+            Self::Memory(content) => Ok(content.lock().unwrap().clone()),
+        }
+    }
+}
+
 /// Gestionnaire du presse-papiers avec effacement automatique
 pub struct SecureClipboard {
-    context: ClipboardContext,
+    context: Context,
+    
+    /// Contenu du presse-papiers avant la première copie, restauré après le délai d'effacement
+    previous: Option<String>,
 }
 
 impl SecureClipboard {
     /// Crée un nouveau gestionnaire de presse-papiers
     pub fn new() -> Result<Self, ClipboardError> {
-        let context = ClipboardProvider::new()
-            .map_err(|e| ClipboardError::AccessError(e.to_string()))?;
+        let context = Context::open()?;
         
-        Ok(Self { context })
+        Ok(Self { context, previous: None })
     }
     
     /// Copie un texte dans le presse-papiers
     pub fn copy(&mut self, text: &str) -> Result<(), ClipboardError> {
-        self.context.set_contents(text.to_owned())
-            .map_err(|e| ClipboardError::AccessError(e.to_string()))?;
-        
-        Ok(())
+        if self.previous.is_none() {
+            // Un presse-papiers vide ou illisible sera simplement effacé
+            self.previous = Some(self.context.get().unwrap_or_default());
+        }
+        self.context.set(text.to_owned())
     }
     
-    /// Copie un texte dans le presse-papiers et l'efface après un délai
-    pub fn copy_with_timeout(&mut self, text: &str, timeout_seconds: u64) -> Result<(), ClipboardError> {
+    /// Copie un texte dans le presse-papiers; le contenu précédent est rétabli par `wait` sur la
+    /// restauration retournée, après `timeout_seconds` secondes (0 = jamais) si le presse-papiers
+    /// contient toujours ce texte
+    pub fn copy_with_timeout(mut self, text: &str, timeout_seconds: u64) -> Result<PendingRestore, ClipboardError> {
         // Copier le texte
         self.copy(text)?;
         
         println!("Texte copié dans le presse-papiers.");
         if timeout_seconds > 0 {
-            println!("Le presse-papiers sera effacé dans {} secondes.", timeout_seconds);
-            
-            // Lancer un thread pour effacer le presse-papiers après le délai
-            thread::spawn(move || {
-                thread::sleep(Duration::from_secs(timeout_seconds));
-                
-                // Créer un nouveau contexte pour le thread
-                let mut context: ClipboardContext = match ClipboardProvider::new() {
-                    Ok(ctx) => ctx,
-                    Err(_) => {
-                        eprintln!("Impossible d'accéder au presse-papiers pour l'effacement");
-                        return;
-                    }
-                };
-                
-                // Effacer le presse-papiers
-                if let Err(e) = context.set_contents(String::new()) {
-                    eprintln!("Erreur lors de l'effacement du presse-papiers: {}", e);
-                } else {
-                    println!("Presse-papiers effacé automatiquement.");
-                }
-            });
+            println!("Le presse-papiers sera restauré dans {} secondes.", timeout_seconds);
+            if self.is_termux() {
+                termux::notify("keeprust", &format!("Copié dans le presse-papiers, restauré dans {} s", timeout_seconds));
+            }
         }
         
-        Ok(())
+        Ok(PendingRestore {
+            clipboard: self,
+            copied: Zeroizing::new(text.to_owned()),
+            timeout_seconds,
+            pending: timeout_seconds > 0,
+        })
+    }
+    
+    /// Lance un thread qui rétablit le contenu précédent après le délai, sauf si le
+    /// presse-papiers a été modifié entre-temps (processus de longue durée seulement : le thread
+    /// s'arrête avec le processus)
+    fn restore_after(&mut self, text: &str, timeout_seconds: u64) {
+        let copied = Zeroizing::new(text.to_owned());
+        let previous = self.previous.take();
+        let termux = self.is_termux();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_secs(timeout_seconds));
+            
+            // Créer un nouveau contexte pour le thread
+            let result = Context::open().and_then(|context| SecureClipboard { context, previous }.restore(&copied));
+            report_restore(&result, termux, false);
+        });
+    }
+    
+    /// Rétablit le contenu précédent si le presse-papiers contient toujours `copied`; retourne
+    /// false s'il a été modifié entre-temps
+    fn restore(&mut self, copied: &str) -> Result<bool, ClipboardError> {
+        let previous = Zeroizing::new(self.previous.take().unwrap_or_default());
+        let current = Zeroizing::new(self.context.get().unwrap_or_default());
+        match content_after_timeout(&current, copied, &previous) {
+            Some(content) => self.context.set(content).map(|_| true),
+            None => Ok(false),
+        }
+    }
+    
+    fn is_termux(&self) -> bool {
+        matches!(self.context, Context::Termux)
     }
     
     /// Lit le contenu du presse-papiers
     #[allow(dead_code)]
     pub fn read(&mut self) -> Result<String, ClipboardError> {
-        self.context.get_contents()
-            .map_err(|e| ClipboardError::AccessError(e.to_string()))
+        self.context.get()
     }
     
     /// Efface le presse-papiers
     #[allow(dead_code)]
     pub fn clear(&mut self) -> Result<(), ClipboardError> {
-        self.context.set_contents(String::new())
-            .map_err(|e| ClipboardError::AccessError(e.to_string()))?;
+        self.context.set(String::new())?;
         
         println!("Presse-papiers effacé.");
         Ok(())
     }
 }
 
-/// Copie un mot de passe dans le presse-papiers de manière sécurisée
-pub fn copy_password_securely(password: &str, timeout_seconds: u64) -> Result<(), ClipboardError> {
+/// Rétablissement du presse-papiers à la fin du délai d'effacement.
+///
+/// Une commande ponctuelle appelle `wait` une fois son travail terminé : le processus reste actif
+/// jusqu'à la fin du délai, que Ctrl+C abrège (sous X11, le contenu copié disparaît d'ailleurs
+/// avec le processus qui l'a copié). Abandonnée sans `wait`, la restauration a lieu aussitôt.
+#[must_use = "le presse-papiers n'est rétabli qu'à la fin de wait()"]
+pub struct PendingRestore {
+    clipboard: SecureClipboard,
+    copied: Zeroizing<String>,
+    timeout_seconds: u64,
+    
+    /// Restauration encore à faire
+    pending: bool,
+}
+
+impl PendingRestore {
+    /// Attend la fin du délai en affichant le temps restant, puis rétablit le contenu précédent;
+    /// Ctrl+C le rétablit aussitôt
+    pub fn wait(self) {
+        interrupt::catching(|interrupted| self.wait_with(interrupted));
+    }
+    
+    fn wait_with(mut self, interrupted: &dyn Fn() -> bool) {
+        if self.pending {
+            countdown(self.timeout_seconds, interrupted);
+            self.finish(true);
+        }
+    }
+    
+    fn finish(&mut self, report: bool) {
+        if std::mem::take(&mut self.pending) {
+            let result = self.clipboard.restore(&self.copied);
+            report_restore(&result, self.clipboard.is_termux(), report);
+        }
+    }
+}
+
+impl Drop for PendingRestore {
+    fn drop(&mut self) {
+        self.finish(false);
+    }
+}
+
+/// Signale le résultat d'une restauration (sous Android par une notification, l'utilisateur
+/// étant le plus souvent dans une autre application)
+fn report_restore(result: &Result<bool, ClipboardError>, termux: bool, report: bool) {
+    if termux && matches!(result, Ok(true)) {
+        termux::notify("keeprust", "Presse-papiers restauré");
+    }
+    match result {
+        Ok(true) if report => println!("Presse-papiers restauré."),
+        Ok(false) if report => println!("Presse-papiers modifié entre-temps: laissé tel quel."),
+        Err(e) if report => eprintln!("Erreur lors de la restauration du presse-papiers: {}", e),
+        _ => {}
+    }
+}
+
+/// Attend `seconds` secondes en affichant le temps restant dans un terminal; retourne false si
+/// `interrupted` a abrégé l'attente
+fn countdown(seconds: u64, interrupted: &dyn Fn() -> bool) -> bool {
+    let terminal = io::stdout().is_terminal();
+    let deadline = Instant::now() + Duration::from_secs(seconds);
+    let mut shown = None;
+    let completed = loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break true;
+        }
+        if interrupted() {
+            break false;
+        }
+        let seconds_left = (remaining.as_millis() as u64).div_ceil(1000);
+        if terminal && shown != Some(seconds_left) {
+            print!("\rRestauration du presse-papiers dans {} s (Ctrl+C pour restaurer maintenant)  ", seconds_left);
+            let _ = io::stdout().flush();
+            shown = Some(seconds_left);
+        }
+        thread::sleep(remaining.min(COUNTDOWN_TICK));
+    };
+    if shown.is_some() {
+        println!();
+    }
+    completed
+}
+
+/// Intervalle de vérification de Ctrl+C pendant le compte à rebours
+const COUNTDOWN_TICK: Duration = Duration::from_millis(100);
+
+/// Ctrl+C pendant le compte à rebours : noté au lieu d'arrêter le processus
+#[cfg(unix)]
+mod interrupt {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    
+    static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+    
+    extern "C" fn on_interrupt(_signal: libc::c_int) {
+        INTERRUPTED.store(true, Ordering::SeqCst);
+    }
+    
+    /// Exécute `f` en interceptant Ctrl+C (SIGINT); `f` reçoit une fonction indiquant s'il a été
+    /// pressé. Le comportement précédent est rétabli ensuite.
+    pub(super) fn catching<T>(f: impl FnOnce(&dyn Fn() -> bool) -> T) -> T {
+        INTERRUPTED.store(false, Ordering::SeqCst);
+        let handler = on_interrupt as extern "C" fn(libc::c_int);
+        // SAFETY : le gestionnaire se contente d'écrire dans un atomique (sûr dans un signal)
+        let previous = unsafe { libc::signal(libc::SIGINT, handler as libc::sighandler_t) };
+        let result = f(&|| INTERRUPTED.load(Ordering::SeqCst));
+        // SAFETY : rétablit le gestionnaire retourné par l'appel précédent
+        unsafe { libc::signal(libc::SIGINT, previous) };
+        result
+    }
+}
+
+/// Sans signaux Unix, Ctrl+C interrompt le processus comme d'habitude
+#[cfg(not(unix))]
+mod interrupt {
+    pub(super) fn catching<T>(f: impl FnOnce(&dyn Fn() -> bool) -> T) -> T {
+        f(&|| false)
+    }
+}
+
+/// Copie un mot de passe dans le presse-papiers de manière sécurisée; l'appelant termine son
+/// travail puis appelle `wait` sur la restauration retournée
+pub fn copy_password_securely(password: &str, timeout_seconds: u64) -> Result<PendingRestore, ClipboardError> {
+    SecureClipboard::new()?.copy_with_timeout(password, timeout_seconds)
+}
+
+/// Copie un texte sans rien écrire sur la sortie standard (interface plein écran) et rétablit
+/// silencieusement le contenu précédent après `timeout_seconds` secondes (0 = jamais)
+pub fn copy_quietly(text: &str, timeout_seconds: u64) -> Result<(), ClipboardError> {
     let mut clipboard = SecureClipboard::new()?;
-    clipboard.copy_with_timeout(password, timeout_seconds)?;
+    clipboard.copy(text)?;
+    if timeout_seconds > 0 {
+        clipboard.restore_after(text, timeout_seconds);
+    }
     Ok(())
 }
 
@@ -100,4 +342,81 @@ pub fn copy_to_clipboard(text: &str) -> Result<(), ClipboardError> {
     clipboard.copy(text)?;
     println!("Texte copié dans le presse-papiers.");
     Ok(())
+}
+
+/// Contenu à remettre dans le presse-papiers à la fin du délai : le contenu précédent si le
+/// presse-papiers contient toujours le texte copié, rien s'il a été modifié entre-temps
+fn content_after_timeout(current: &str, copied: &str, previous: &str) -> Option<String> {
+    (current == copied).then(|| previous.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    
+    /// Presse-papiers en mémoire contenant `content`, et son contenu partagé
+    fn memory(content: &str) -> (SecureClipboard, Arc<Mutex<String>>) {
+        let shared = Arc::new(Mutex::new(content.to_string()));
+        (SecureClipboard { context: Context::Memory(shared.clone()), previous: None }, shared)
+    }
+    
+    #[test]
+    fn test_content_after_timeout() {
+        assert_eq!(content_after_timeout("secret", "secret", "avant"), Some("avant".to_string()));
+        assert_eq!(content_after_timeout("secret", "secret", ""), Some(String::new()));
+        assert_eq!(content_after_timeout("autre chose", "secret", "avant"), None);
+    }
+    
+    #[test]
+    fn test_wait_restores_previous_content() {
+        let (clipboard, shared) = memory("avant");
+        let pending = clipboard.copy_with_timeout("secret", 1).unwrap();
+        assert_eq!(*shared.lock().unwrap(), "secret");
+        
+        let started = Instant::now();
+        pending.wait_with(&|| false);
+        assert!(started.elapsed() >= Duration::from_secs(1));
+        assert_eq!(*shared.lock().unwrap(), "avant");
+    }
+    
+    #[test]
+    fn test_interrupted_wait_restores_at_once() {
+        let (clipboard, shared) = memory("avant");
+        let pending = clipboard.copy_with_timeout("secret", 600).unwrap();
+        
+        let started = Instant::now();
+        pending.wait_with(&|| true);
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(*shared.lock().unwrap(), "avant");
+    }
+    
+    #[test]
+    fn test_clipboard_changed_meanwhile_is_left_alone() {
+        let (clipboard, shared) = memory("avant");
+        let pending = clipboard.copy_with_timeout("secret", 600).unwrap();
+        *shared.lock().unwrap() = "copié ailleurs".to_string();
+        
+        pending.wait_with(&|| true);
+        assert_eq!(*shared.lock().unwrap(), "copié ailleurs");
+    }
+    
+    #[test]
+    fn test_abandoned_restore_happens_on_drop() {
+        let (clipboard, shared) = memory("avant");
+        drop(clipboard.copy_with_timeout("secret", 600).unwrap());
+        assert_eq!(*shared.lock().unwrap(), "avant");
+        
+        // Sans délai, le texte copié reste dans le presse-papiers
+        let (clipboard, shared) = memory("avant");
+        clipboard.copy_with_timeout("nom", 0).unwrap().wait();
+        assert_eq!(*shared.lock().unwrap(), "nom");
+    }    
+    #[cfg(unix)]
+    #[test]
+    fn test_ctrl_c_is_caught_during_the_countdown() {
+        // SAFETY : SIGINT est intercepté par `catching` le temps de l'appel
+        let caught = interrupt::catching(|interrupted| unsafe { libc::raise(libc::SIGINT) } == 0 && interrupted());
+        assert!(caught);
+    }
 }
\ No newline at end of file
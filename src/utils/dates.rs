@@ -0,0 +1,152 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use thiserror::Error;
+
+use crate::models::clock;
+
+/// Enumération des erreurs possibles lors de l'analyse d'une date ou d'une durée
+#[derive(Error, Debug, PartialEq)]
+pub enum DateError {
+    #[error("Date invalide '{0}' (formats acceptés: JJ-MM-AAAA, AAAA-MM-JJ, une durée comme 90d, 2w, 6m ou 1y, ou une expression comme hier, le mois dernier, il y a 3 jours, dans 2 semaines, last month).")]
+    InvalidDate(String),
+    
+    #[error("Durée invalide '{0}' (formats acceptés: 12h, 90d ou 90j, 2w, 6m, 1y ou 1a, ou en toutes lettres: 3 jours, 2 weeks, 1 an).")]
+    InvalidDuration(String),
+    
+    #[error("'{0}' désigne une date passée alors qu'une date à venir est attendue (ex: 90d, dans 3 mois, next year).")]
+    PastDate(String),
+    
+    #[error("'{0}' désigne une date à venir alors qu'une date passée est attendue (ex: 2w, hier, il y a 3 mois, last month).")]
+    FutureDate(String),
+}
+
+/// Sens attendu d'une date relative; une durée seule ("90d") est comptée dans ce sens
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    /// Date passée (ex: --as-of, --updated-since)
+    Past,
+    /// Date à venir (ex: --expires)
+    Future,
+}
+
+/// Unité de durée, en anglais ou en français (un mois compte 30 jours, une année 365)
+fn parse_unit(unit: &str) -> Option<Duration> {
+    match unit {
+        "h" | "hour" | "hours" | "heure" | "heures" => Some(Duration::hours(1)),
+        "d" | "j" | "day" | "days" | "jour" | "jours" => Some(Duration::days(1)),
+        "w" | "week" | "weeks" | "semaine" | "semaines" => Some(Duration::weeks(1)),
+        "m" | "month" | "months" | "mois" => Some(Duration::days(30)),
+        "y" | "a" | "year" | "years" | "an" | "ans" | "année" | "années" | "annee" | "annees" => Some(Duration::days(365)),
+        _ => None,
+    }
+}
+
+/// Analyse une durée positive : "90d", "2w", "6m", "1y", ou "3 jours", "2 weeks", "1 an"
+pub fn parse_duration(value: &str) -> Result<Duration, DateError> {
+    amount_and_unit(&value.trim().to_lowercase()).ok_or_else(|| DateError::InvalidDuration(value.to_string()))
+}
+
+fn amount_and_unit(value: &str) -> Option<Duration> {
+    let split = value.find(|c: char| !c.is_ascii_digit())?;
+    let amount: i32 = value[..split].parse().ok()?;
+    parse_unit(value[split..].trim_start())?.checked_mul(amount)
+}
+
+/// Analyse une date absolue (JJ-MM-AAAA ou AAAA-MM-JJ, à minuit UTC) ou relative à maintenant
+/// ("90d", "hier", "le mois dernier", "il y a 3 jours", "dans 2 semaines", "last month"...).
+///
+/// Une durée seule est comptée dans le sens attendu; une expression explicitement dans l'autre
+/// sens est refusée plutôt que d'être interprétée silencieusement.
+pub fn parse_date(value: &str, direction: Direction) -> Result<DateTime<Utc>, DateError> {
+    parse_date_at(value, direction, clock::now())
+}
+
+fn parse_date_at(value: &str, direction: Direction, now: DateTime<Utc>) -> Result<DateTime<Utc>, DateError> {
+    let trimmed = value.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(trimmed, "%d-%m-%Y").or_else(|_| NaiveDate::parse_from_str(trimmed, "%Y-%m-%d")) {
+        return Ok(date.and_time(NaiveTime::MIN).and_utc());
+    }
+    
+    let offset = relative_offset(&trimmed.to_lowercase().replace('’', "'"), direction)
+        .ok_or_else(|| DateError::InvalidDate(value.to_string()))?;
+    match direction {
+        Direction::Future if offset < Duration::zero() => Err(DateError::PastDate(value.to_string())),
+        Direction::Past if offset > Duration::zero() => Err(DateError::FutureDate(value.to_string())),
+        _ => now.checked_add_signed(offset).ok_or_else(|| DateError::InvalidDate(value.to_string())),
+    }
+}
+
+/// Décalage par rapport à maintenant exprimé par une date relative (négatif dans le passé)
+fn relative_offset(value: &str, direction: Direction) -> Option<Duration> {
+    match value {
+        "now" | "today" | "maintenant" | "aujourd'hui" => return Some(Duration::zero()),
+        "yesterday" | "hier" => return Some(-Duration::days(1)),
+        "tomorrow" | "demain" => return Some(Duration::days(1)),
+        _ => {},
+    }
+    
+    if let Some(unit) = value.strip_prefix("last ") {
+        return parse_unit(unit).map(|unit| -unit);
+    }
+    if let Some(unit) = value.strip_prefix("next ") {
+        return parse_unit(unit);
+    }
+    // "le mois dernier", "la semaine prochaine", "l'année dernière"
+    let unit = value.strip_prefix("le ").or_else(|| value.strip_prefix("la ")).or_else(|| value.strip_prefix("l'")).unwrap_or(value);
+    for (suffix, past) in [(" dernier", true), (" dernière", true), (" derniere", true), (" prochain", false), (" prochaine", false)] {
+        if let Some(unit) = unit.strip_suffix(suffix) {
+            return parse_unit(unit).map(|unit| if past { -unit } else { unit });
+        }
+    }
+    
+    if let Some(amount) = value.strip_suffix(" ago").or_else(|| value.strip_prefix("il y a ")) {
+        return amount_and_unit(amount.trim()).map(|duration| -duration);
+    }
+    if let Some(amount) = value.strip_prefix("in ").or_else(|| value.strip_prefix("dans ")) {
+        return amount_and_unit(amount.trim());
+    }
+    if let Some(amount) = value.strip_prefix('-') {
+        return amount_and_unit(amount).map(|duration| -duration);
+    }
+    if let Some(amount) = value.strip_prefix('+') {
+        return amount_and_unit(amount);
+    }
+    
+    let duration = amount_and_unit(value)?;
+    Some(match direction {
+        Direction::Past => -duration,
+        Direction::Future => duration,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_dates_and_durations() {
+        let now = NaiveDate::from_ymd_opt(2024, 6, 15).unwrap().and_time(NaiveTime::MIN).and_utc();
+        let parse = |value: &str, direction| parse_date_at(value, direction, now);
+        
+        let midnight = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap().and_time(NaiveTime::MIN).and_utc();
+        assert_eq!(parse("01-03-2024", Direction::Future), Ok(midnight));
+        assert_eq!(parse("2024-03-01", Direction::Past), Ok(midnight));
+        
+        assert_eq!(parse("90d", Direction::Future), Ok(now + Duration::days(90)));
+        assert_eq!(parse("2w", Direction::Past), Ok(now - Duration::weeks(2)));
+        assert_eq!(parse("Last Month", Direction::Past), Ok(now - Duration::days(30)));
+        assert_eq!(parse("l’année dernière", Direction::Past), Ok(now - Duration::days(365)));
+        assert_eq!(parse("il y a 3 jours", Direction::Past), Ok(now - Duration::days(3)));
+        assert_eq!(parse("dans 6 mois", Direction::Future), Ok(now + Duration::days(180)));
+        assert_eq!(parse("hier", Direction::Past), Ok(now - Duration::days(1)));
+        assert_eq!(parse("today", Direction::Future), Ok(now));
+        
+        assert_eq!(parse("last month", Direction::Future), Err(DateError::PastDate("last month".to_string())));
+        assert_eq!(parse("in 2 weeks", Direction::Past), Err(DateError::FutureDate("in 2 weeks".to_string())));
+        assert_eq!(parse("31-02-2024", Direction::Past), Err(DateError::InvalidDate("31-02-2024".to_string())));
+        assert!(parse("90x", Direction::Future).is_err());
+        
+        assert_eq!(parse_duration("2y"), Ok(Duration::days(730)));
+        assert_eq!(parse_duration(" 3 semaines "), Ok(Duration::weeks(3)));
+        assert_eq!(parse_duration("jours"), Err(DateError::InvalidDuration("jours".to_string())));
+    }
+}
@@ -0,0 +1,162 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::models::database::Database;
+use crate::models::entry::Entry;
+use crate::models::group::Group;
+use crate::models::id::{EntryId, GroupId};
+use crate::utils::password_generator::PasswordGenerator;
+
+/// Services fictifs utilisés pour les titres et les URL
+const SERVICES: &[&str] = &[
+    "Messagerie", "Forge", "Boutique", "Streaming", "Banque", "Opérateur", "Train", "Impôts",
+    "Réseau pro", "Musique", "Stockage", "Assurance", "Courrier", "Annonces", "Santé", "Énergie",
+    "Chat", "Cloud", "Forum", "Mutuelle",
+];
+
+/// Prénoms fictifs utilisés pour les noms d'utilisateur
+const FIRST_NAMES: &[&str] = &[
+    "alice", "bob", "camille", "david", "emma", "farid", "gabriel", "hugo", "ines", "jules",
+    "lea", "manon", "nathan", "oceane", "paul", "sarah",
+];
+
+/// Noms de groupes fictifs
+const GROUP_NAMES: &[&str] = &[
+    "Personnel", "Travail", "Banque", "Réseaux sociaux", "Serveurs", "Achats", "Administration",
+    "Loisirs", "Projets", "Archives",
+];
+
+/// Étiquettes fictives
+const TAGS: &[&str] = &["perso", "travail", "banque", "2fa", "partagé", "important"];
+
+/// Mots de passe faibles, pour que les audits aient quelque chose à signaler
+const WEAK_PASSWORDS: &[&str] = &["123456", "azerty", "motdepasse", "soleil2020", "password1"];
+
+/// Nombre maximal de sous-groupes par groupe
+const MAX_SUBGROUPS: usize = 3;
+
+/// Paramètres de génération d'une base de test
+#[derive(Debug, Clone)]
+pub struct FixtureOptions {
+    /// Nombre total d'entrées
+    pub entries: usize,
+    
+    /// Profondeur maximale des groupes sous la racine
+    pub depth: usize,
+    
+    /// Graine du générateur aléatoire
+    pub seed: u64,
+}
+
+/// Date de référence des bases de test (toutes les dates en sont dérivées)
+pub fn fixture_epoch() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+}
+
+/// Génère une base de données synthétique.
+///
+/// Le contenu (identifiants, dates, mots de passe...) ne dépend que des options :
+/// la même graine produit toujours la même base.
+pub fn generate(options: &FixtureOptions) -> Database {
+    let mut rng = StdRng::seed_from_u64(options.seed);
+    let epoch = fixture_epoch();
+    
+    let mut db = Database::new(
+        format!("Fixture {}", options.seed),
+        format!("{} entrées synthétiques, profondeur {}", options.entries, options.depth),
+    );
+    db.metadata.created_at = epoch;
+    db.metadata.updated_at = epoch;
+    
+    let mut counter = 0;
+    let mut root = Group::new("Root".to_string(), None);
+    root.id = GroupId::from_rng(&mut rng);
+    fill_group(&mut root, options.entries, options.depth, &mut counter, &mut rng);
+    db.root_group = root;
+    
+    db
+}
+
+/// Remplit récursivement un groupe avec ses entrées et ses sous-groupes
+fn fill_group(group: &mut Group, entries: usize, depth: usize, counter: &mut usize, rng: &mut StdRng) {
+    let subgroup_count = if depth == 0 { 0 } else { rng.gen_range(1..=MAX_SUBGROUPS) };
+    
+    // Répartir les entrées entre le groupe (case 0) et ses sous-groupes
+    let mut shares = vec![0; subgroup_count + 1];
+    for _ in 0..entries {
+        shares[rng.gen_range(0..=subgroup_count)] += 1;
+    }
+    
+    for _ in 0..shares[0] {
+        *counter += 1;
+        group.add_entry(fake_entry(*counter, rng));
+    }
+    
+    let names: Vec<&str> = GROUP_NAMES.choose_multiple(rng, subgroup_count).copied().collect();
+    for (name, share) in names.into_iter().zip(&shares[1..]) {
+        let mut subgroup = Group::new(name.to_string(), Some(group.id));
+        subgroup.id = GroupId::from_rng(rng);
+        fill_group(&mut subgroup, *share, depth - 1, counter, rng);
+        group.add_subgroup(subgroup);
+    }
+    
+    group.created_at = fixture_epoch();
+    group.updated_at = fixture_epoch();
+}
+
+/// Crée une entrée fictive
+fn fake_entry(index: usize, rng: &mut StdRng) -> Entry {
+    let service = SERVICES.choose(rng).unwrap();
+    let first_name = FIRST_NAMES.choose(rng).unwrap();
+    let slug: String = service.to_lowercase().chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+    
+    let password = if rng.gen_bool(0.1) {
+        WEAK_PASSWORDS.choose(rng).unwrap().to_string()
+    } else {
+        PasswordGenerator::new()
+            .length(rng.gen_range(10..=24))
+            .generate_with_rng(rng)
+            .unwrap()
+    };
+    
+    let tag_count = rng.gen_range(0..=2);
+    let mut builder = Entry::builder()
+        .title(format!("{} {}", service, index))
+        .username(format!("{}{}@example.org", first_name, rng.gen_range(1..100)))
+        .password(password)
+        .url(format!("https://{}.example.com", if slug.is_empty() { "site" } else { &slug }))
+        .tags(TAGS.choose_multiple(rng, tag_count).map(|tag| tag.to_string()));
+    
+    if rng.gen_bool(0.2) {
+        builder = builder.notes(format!("Note de test n°{}", index));
+    }
+    if rng.gen_bool(0.1) {
+        builder = builder.expires_at(Some(fixture_epoch() + Duration::days(rng.gen_range(-30..180))));
+    }
+    
+    let mut entry = builder.build().unwrap();
+    entry.id = EntryId::from_rng(rng);
+    entry.updated_at = fixture_epoch() - Duration::days(rng.gen_range(0..730));
+    entry.created_at = entry.updated_at - Duration::days(rng.gen_range(0..365));
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_same_seed_gives_same_database() {
+        let options = FixtureOptions { entries: 200, depth: 3, seed: 42 };
+        let first = generate(&options);
+        let second = generate(&options);
+        
+        assert_eq!(first.count_entries(), 200);
+        assert_eq!(serde_json::to_value(&first).unwrap(), serde_json::to_value(&second).unwrap());
+        
+        let other = generate(&FixtureOptions { seed: 43, ..options });
+        assert_ne!(serde_json::to_value(&first).unwrap(), serde_json::to_value(&other).unwrap());
+    }
+}
\ No newline at end of file
@@ -1,3 +1,12 @@
 pub mod password_generator;
 pub mod clipboard;
-pub mod password_analyzer;
\ No newline at end of file
+pub mod password_analyzer;
+pub mod fixture;
+pub mod breach;
+pub mod dates;
+pub mod paths;
+pub mod preview;
+pub mod redact;
+pub mod symbols;
+pub mod termux;
+pub mod webdav;
\ No newline at end of file
@@ -1,15 +1,125 @@
+use data_encoding::HEXLOWER;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashSet;
 use std::fmt;
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+use super::symbols::Symbol;
+
+/// Version de l'algorithme d'analyse, à incrémenter lorsque le calcul du score change
+/// (invalide les scores mémorisés dans les bases)
+pub const ANALYZER_VERSION: u32 = 1;
+
+#[derive(Error, Debug, PartialEq)]
 pub enum PasswordAnalysisError {
-    #[error("Erreur d'analyse: {0}")]
-    #[allow(dead_code)]
-    AnalysisError(String),
+    #[error("Configuration de l'analyseur invalide: {0}")]
+    InvalidConfig(String),
+}
+
+/// Points gagnés ou perdus par un mot de passe selon ses caractéristiques (score sur 100)
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StrengthWeights {
+    /// Au moins 8 caractères
+    pub min_length: u8,
+    
+    /// Au moins 12 caractères (en plus de `min_length`)
+    pub length_12: u8,
+    
+    /// Au moins 16 caractères (en plus de `length_12`)
+    pub length_16: u8,
+    
+    /// Deux types de caractères (minuscules, majuscules, chiffres, symboles)
+    pub two_char_types: u8,
+    
+    /// Trois types de caractères
+    pub three_char_types: u8,
+    
+    /// Les quatre types de caractères
+    pub four_char_types: u8,
+    
+    /// Pénalité pour un caractère répété trois fois ("aaa")
+    pub repetition_penalty: u8,
+    
+    /// Pénalité pour une séquence ("abc", "321")
+    pub sequence_penalty: u8,
+}
+
+impl Default for StrengthWeights {
+    fn default() -> Self {
+        Self {
+            min_length: 20,
+            length_12: 10,
+            length_16: 10,
+            two_char_types: 15,
+            three_char_types: 25,
+            four_char_types: 35,
+            repetition_penalty: 15,
+            sequence_penalty: 10,
+        }
+    }
+}
+
+/// Score maximal de chaque niveau de force; au-delà de `strong`, le mot de passe est très fort
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StrengthThresholds {
+    pub very_weak: u8,
+    pub weak: u8,
+    pub medium: u8,
+    pub strong: u8,
+}
+
+impl Default for StrengthThresholds {
+    fn default() -> Self {
+        Self { very_weak: 20, weak: 40, medium: 60, strong: 80 }
+    }
+}
+
+impl StrengthThresholds {
+    /// Niveau de force correspondant à un score
+    fn strength(&self, score: u8) -> PasswordStrength {
+        if score <= self.very_weak {
+            PasswordStrength::VeryWeak
+        } else if score <= self.weak {
+            PasswordStrength::Weak
+        } else if score <= self.medium {
+            PasswordStrength::Medium
+        } else if score <= self.strong {
+            PasswordStrength::Strong
+        } else {
+            PasswordStrength::VeryStrong
+        }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Réglages de l'analyseur, pour calibrer ce qui compte comme faible ou fort
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AnalyzerConfig {
+    pub weights: StrengthWeights,
+    pub thresholds: StrengthThresholds,
+}
+
+impl AnalyzerConfig {
+    /// Vérifie que les seuils sont croissants et ne dépassent pas 100
+    pub fn validate(&self) -> Result<(), PasswordAnalysisError> {
+        let StrengthThresholds { very_weak, weak, medium, strong } = self.thresholds;
+        if !(very_weak < weak && weak < medium && medium < strong) {
+            return Err(PasswordAnalysisError::InvalidConfig(
+                "les seuils doivent être strictement croissants (very_weak < weak < medium < strong)".to_string()
+            ));
+        }
+        if strong > 100 {
+            return Err(PasswordAnalysisError::InvalidConfig("le seuil strong ne peut pas dépasser 100".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PasswordStrength {
     VeryWeak,
     Weak,
@@ -26,8 +136,16 @@ pub struct PasswordAnalysis {
     pub suggestions: Vec<String>,
 }
 
+#[derive(Debug, Clone)]
 pub struct PasswordAnalyzer {
     common_passwords: HashSet<String>,
+    config: AnalyzerConfig,
+}
+
+impl Default for PasswordAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PasswordAnalyzer {
@@ -47,11 +165,35 @@ impl PasswordAnalyzer {
             common_passwords.insert(pwd.to_lowercase());
         }
         
-        Self { common_passwords }
+        Self { common_passwords, config: AnalyzerConfig::default() }
+    }
+    
+    /// Analyseur utilisant des poids et des seuils personnalisés
+    pub fn with_config(config: AnalyzerConfig) -> Result<Self, PasswordAnalysisError> {
+        config.validate()?;
+        Ok(Self { config, ..Self::new() })
+    }
+    
+    /// Réglages de l'analyseur
+    pub fn config(&self) -> &AnalyzerConfig {
+        &self.config
+    }
+    
+    /// Empreinte de la version de l'algorithme et des réglages : deux analyseurs de même
+    /// empreinte donnent le même score à un mot de passe
+    pub fn fingerprint(&self) -> String {
+        let digest = Sha256::digest(format!("{}:{:?}", ANALYZER_VERSION, self.config));
+        format!("v{}-{}", ANALYZER_VERSION, &HEXLOWER.encode(&digest)[..16])
+    }
+    
+    /// Niveau de force correspondant à un score, d'après les seuils de l'analyseur
+    pub fn strength(&self, score: u8) -> PasswordStrength {
+        self.config.thresholds.strength(score)
     }
     
     pub fn analyze(&self, password: &str) -> PasswordAnalysis {
-        let mut score = 0u8;
+        let weights = &self.config.weights;
+        let mut score = 0u32;
         let mut issues = Vec::new();
         let mut suggestions = Vec::new();
         
@@ -60,15 +202,15 @@ impl PasswordAnalyzer {
             issues.push("Mot de passe trop court (moins de 8 caractères)".to_string());
             suggestions.push("Utilisez au moins 8 caractères".to_string());
         } else if password.len() >= 8 {
-            score += 20;
+            score += u32::from(weights.min_length);
         }
         
         if password.len() >= 12 {
-            score += 10;
+            score += u32::from(weights.length_12);
         }
         
         if password.len() >= 16 {
-            score += 10;
+            score += u32::from(weights.length_16);
         }
         
         // Vérifier la présence de différents types de caractères
@@ -88,19 +230,22 @@ impl PasswordAnalyzer {
                 suggestions.push("Mélangez majuscules, minuscules, chiffres et symboles".to_string());
             },
             2 => {
-                score += 15;
+                score += u32::from(weights.two_char_types);
                 suggestions.push("Ajoutez des chiffres et/ou des symboles".to_string());
             },
             3 => {
-                score += 25;
+                score += u32::from(weights.three_char_types);
                 suggestions.push("Ajoutez des symboles pour plus de sécurité".to_string());
             },
             4 => {
-                score += 35;
+                score += u32::from(weights.four_char_types);
             },
             _ => {}
         }
         
+        // Le score est ramené sur 100 avant les pénalités
+        let mut score = score.min(100) as u8;
+        
         // Vérifier les mots de passe communs
         if self.common_passwords.contains(&password.to_lowercase()) {
             score = 0; // Score à zéro pour les mots de passe communs
@@ -110,26 +255,20 @@ impl PasswordAnalyzer {
         
         // Vérifier les répétitions
         if self.has_repetitions(password) {
-            score = score.saturating_sub(15);
+            score = score.saturating_sub(weights.repetition_penalty);
             issues.push("Contient des répétitions de caractères".to_string());
             suggestions.push("Évitez les répétitions comme 'aaa' ou '111'".to_string());
         }
         
         // Vérifier les séquences
         if self.has_sequences(password) {
-            score = score.saturating_sub(10);
+            score = score.saturating_sub(weights.sequence_penalty);
             issues.push("Contient des séquences prévisibles".to_string());
             suggestions.push("Évitez les séquences comme 'abc' ou '123'".to_string());
         }
         
-        // Déterminer la force (utiliser _ pour couvrir tous les cas restants)
-        let strength = match score {
-            0..=20 => PasswordStrength::VeryWeak,
-            21..=40 => PasswordStrength::Weak,
-            41..=60 => PasswordStrength::Medium,
-            61..=80 => PasswordStrength::Strong,
-            _ => PasswordStrength::VeryStrong, // Couvre 81 et plus
-        };
+        // Déterminer la force selon les seuils configurés
+        let strength = self.config.thresholds.strength(score);
         
         PasswordAnalysis {
             strength,
@@ -165,6 +304,28 @@ impl PasswordAnalyzer {
     }
 }
 
+/// Estimation de l'entropie d'un mot de passe en bits : longueur multipliée par le logarithme
+/// de la taille de l'alphabet utilisé (minuscules, majuscules, chiffres, symboles ASCII et espace,
+/// autres caractères).
+///
+/// C'est une borne haute : un mot de passe construit à partir de mots ou de motifs est plus
+/// faible que son estimation.
+pub fn estimate_entropy_bits(password: &str) -> f64 {
+    let has = |matches: fn(&char) -> bool| password.chars().any(|c| matches(&c));
+    let pool = [
+        (has(char::is_ascii_lowercase), 26),
+        (has(char::is_ascii_uppercase), 26),
+        (has(char::is_ascii_digit), 10),
+        (has(|c| c.is_ascii_punctuation() || *c == ' '), 33),
+        (has(|c| !c.is_ascii()), 100),
+    ];
+    let pool: u32 = pool.iter().filter(|(present, _)| *present).map(|(_, size)| size).sum();
+    if pool == 0 {
+        return 0.0;
+    }
+    password.chars().count() as f64 * f64::from(pool).log2()
+}
+
 impl fmt::Display for PasswordStrength {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -184,17 +345,47 @@ impl fmt::Display for PasswordAnalysis {
         if !self.issues.is_empty() {
             writeln!(f, "\nProblèmes détectés:")?;
             for issue in &self.issues {
-                writeln!(f, "  ⚠️  {}", issue)?;
+                writeln!(f, "  {}{}", Symbol::Warning, issue)?;
             }
         }
         
         if !self.suggestions.is_empty() {
             writeln!(f, "\nSuggestions:")?;
             for suggestion in &self.suggestions {
-                writeln!(f, "  💡 {}", suggestion)?;
+                writeln!(f, "  {}{}", Symbol::Tip, suggestion)?;
             }
         }
         
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_configured_thresholds_and_weights() {
+        let default = PasswordAnalyzer::new();
+        assert_eq!(default.analyze("Tr0ub4dor&3").score, 55);
+        assert_eq!(default.analyze("Tr0ub4dor&3").strength, PasswordStrength::Medium);
+        
+        let config: AnalyzerConfig = toml::from_str("
+            [thresholds]
+            very_weak = 30
+            weak = 60
+            medium = 75
+            strong = 90
+            
+            [weights]
+            four_char_types = 60
+        ").unwrap();
+        let strict = PasswordAnalyzer::with_config(config).unwrap();
+        let analysis = strict.analyze("Tr0ub4dor&3");
+        assert_eq!((analysis.score, analysis.strength), (80, PasswordStrength::Strong));
+        assert_eq!(strict.analyze("password").score, 0);
+        
+        let unordered = AnalyzerConfig { thresholds: StrengthThresholds { very_weak: 50, weak: 40, medium: 60, strong: 80 }, ..Default::default() };
+        assert!(matches!(PasswordAnalyzer::with_config(unordered), Err(PasswordAnalysisError::InvalidConfig(_))));
+    }
 }
\ No newline at end of file
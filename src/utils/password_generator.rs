@@ -1,6 +1,15 @@
 use rand::{Rng, thread_rng};
+use rand::seq::SliceRandom;
+use std::sync::OnceLock;
 use thiserror::Error;
 
+/// Mots des phrases de passe : liste longue de l'EFF (7776 mots courants de 3 à 9 lettres, un par
+/// combinaison de cinq dés, précédé de cette combinaison), soit environ 12,9 bits d'entropie par mot
+const WORDLIST: &str = include_str!("wordlist.txt");
+
+/// Nombre de mots d'une phrase de passe par défaut (77 bits d'entropie)
+pub const DEFAULT_PASSPHRASE_WORDS: usize = 6;
+
 /// Enumération des erreurs possibles lors de la génération de mots de passe
 #[derive(Error, Debug)]
 pub enum PasswordGenError {
@@ -9,6 +18,15 @@ pub enum PasswordGenError {
     
     #[error("Aucun jeu de caractères n'est activé")]
     NoCharsetEnabled,
+    
+    #[error("Nombre de mots invalide: {0} (au moins 1)")]
+    InvalidWordCount(usize),
+}
+
+/// Mots utilisables dans une phrase de passe
+pub fn passphrase_words() -> &'static [&'static str] {
+    static WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+    WORDS.get_or_init(|| WORDLIST.lines().filter_map(|line| line.split_whitespace().last()).collect())
 }
 
 /// Configuration pour le générateur de mots de passe
@@ -100,6 +118,11 @@ impl PasswordGenerator {
     
     /// Génère un mot de passe avec la configuration actuelle
     pub fn generate(&self) -> Result<String, PasswordGenError> {
+        self.generate_with_rng(&mut thread_rng())
+    }
+    
+    /// Génère un mot de passe avec le générateur aléatoire fourni (ex: générateur initialisé par une graine)
+    pub fn generate_with_rng<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<String, PasswordGenError> {
         // Vérifier la longueur minimale
         if self.length == 0 {
             return Err(PasswordGenError::InvalidLength("La longueur doit être supérieure à 0".to_string()));
@@ -153,7 +176,6 @@ impl PasswordGenerator {
         let charset: Vec<char> = charset.chars().collect();
         
         // Générer le mot de passe
-        let mut rng = thread_rng();
         let password: String = (0..self.length)
             .map(|_| {
                 let idx = rng.gen_range(0..charset.len());
@@ -164,6 +186,43 @@ impl PasswordGenerator {
         Ok(password)
     }
     
+    /// Génère une phrase de passe de `words` mots tirés au hasard, séparés par `separator`,
+    /// plus facile à saisir sur un téléphone qu'une suite de caractères.
+    ///
+    /// Avec `capitalize`, chaque mot commence par une majuscule; avec `add_digit`, un chiffre est
+    /// ajouté à la fin d'un des mots (pour les sites exigeant un chiffre).
+    pub fn generate_passphrase(words: usize, separator: &str, capitalize: bool, add_digit: bool) -> Result<String, PasswordGenError> {
+        Self::generate_passphrase_with_rng(words, separator, capitalize, add_digit, &mut thread_rng())
+    }
+    
+    /// Version de `generate_passphrase` utilisant le générateur aléatoire fourni
+    pub fn generate_passphrase_with_rng<R: Rng + ?Sized>(words: usize, separator: &str, capitalize: bool, add_digit: bool, rng: &mut R) -> Result<String, PasswordGenError> {
+        if words == 0 {
+            return Err(PasswordGenError::InvalidWordCount(words));
+        }
+        
+        let wordlist = passphrase_words();
+        let mut chosen: Vec<String> = (0..words)
+            .map(|_| {
+                let word = wordlist.choose(rng).expect("liste de mots non vide");
+                if capitalize {
+                    let mut chars = word.chars();
+                    chars.next().map(|first| first.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+                } else {
+                    word.to_string()
+                }
+            })
+            .collect();
+        
+        if add_digit {
+            let index = rng.gen_range(0..chosen.len());
+            let digit = rng.gen_range(0..10);
+            chosen[index].push_str(&digit.to_string());
+        }
+        
+        Ok(chosen.join(separator))
+    }
+    
     /// Génère un mot de passe respectant certaines règles de complexité
     pub fn generate_complex(&self) -> Result<String, PasswordGenError> {
         // Vérifier que les options minimales sont activées
@@ -249,6 +308,38 @@ mod tests {
         assert!(password.chars().any(|c| !c.is_alphanumeric()));
     }
     
+    #[test]
+    fn test_wordlist() {
+        let words = passphrase_words();
+        assert_eq!(words.len(), 7776);
+        assert_eq!((words[0], words[7775]), ("abacus", "zoom"));
+        assert!(words.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(words.iter().all(|word| (3..=9).contains(&word.len()) && word.chars().all(|c| c.is_ascii_lowercase() || c == '-')));
+        
+        // Chaque mot suit sa combinaison de dés, de 11111 à 66666 dans l'ordre
+        for (index, line) in WORDLIST.lines().enumerate() {
+            let dice: String = (0..5).rev().map(|position| char::from(b'1' + (index / 6usize.pow(position) % 6) as u8)).collect();
+            assert_eq!(line.split_whitespace().next(), Some(dice.as_str()));
+        }
+        assert!(((words.len() as f64).log2() - 12.925).abs() < 0.001);
+    }
+    
+    #[test]
+    fn test_passphrase() {
+        // Séparateur absent des mots ("t-shirt", "yo-yo" contiennent des tirets)
+        let passphrase = PasswordGenerator::generate_passphrase(6, " ", false, false).unwrap();
+        let words: Vec<&str> = passphrase.split(' ').collect();
+        assert_eq!(words.len(), 6);
+        assert!(words.iter().all(|word| passphrase_words().contains(word)));
+        
+        let passphrase = PasswordGenerator::generate_passphrase(4, " ", true, true).unwrap();
+        assert_eq!(passphrase.split(' ').count(), 4);
+        assert!(passphrase.split(' ').all(|word| word.starts_with(|c: char| c.is_ascii_uppercase())));
+        assert_eq!(passphrase.chars().filter(char::is_ascii_digit).count(), 1);
+        
+        assert!(matches!(PasswordGenerator::generate_passphrase(0, "-", false, false), Err(PasswordGenError::InvalidWordCount(0))));
+    }
+    
     #[test]
     fn test_exclude_similar() {
         let generator = PasswordGenerator::new().exclude_similar(true);
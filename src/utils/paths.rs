@@ -0,0 +1,101 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Préfixe des chemins Windows étendus, qui lèvent la limite de 260 caractères (MAX_PATH)
+const EXTENDED_PREFIX: &str = r"\\?\";
+
+/// Chemin utilisable pour les accès au disque : sous Windows, chemin absolu étendu (`\\?\C:\...`
+/// ou `\\?\UNC\serveur\partage\...`) pour les chemins longs et les partages réseau; ailleurs,
+/// le chemin tel quel
+pub fn extended_length(path: &Path) -> PathBuf {
+    #[cfg(windows)]
+    {
+        if let Ok(absolute) = std::path::absolute(path)
+            && let Some(extended) = absolute.to_str().and_then(extend)
+        {
+            return PathBuf::from(extended);
+        }
+    }
+    path.to_path_buf()
+}
+
+/// Forme étendue d'un chemin Windows absolu et normalisé; None s'il l'est déjà ou n'est pas absolu
+#[cfg_attr(not(windows), allow(dead_code))]
+fn extend(path: &str) -> Option<String> {
+    if path.starts_with(EXTENDED_PREFIX) || path.starts_with(r"\\.\") {
+        return None;
+    }
+    if let Some(share) = path.strip_prefix(r"\\") {
+        return Some(format!(r"{}UNC\{}", EXTENDED_PREFIX, share));
+    }
+    let bytes = path.as_bytes();
+    let is_drive = bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'\\';
+    is_drive.then(|| format!("{}{}", EXTENDED_PREFIX, path))
+}
+
+/// Crée un répertoire (et ses parents) accessible à son seul propriétaire : mode 0700 sous Unix,
+/// droits hérités remplacés par l'utilisateur courant et SYSTEM sous Windows. Un répertoire
+/// existant est laissé tel quel.
+pub fn create_private_dir(path: &Path) -> io::Result<()> {
+    if path.is_dir() {
+        return Ok(());
+    }
+    fs::create_dir_all(path)?;
+    
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o700))?;
+    }
+    #[cfg(windows)]
+    restrict_acl(path)?;
+    Ok(())
+}
+
+/// Retire les droits hérités d'un répertoire Windows et n'accorde l'accès qu'à l'utilisateur
+/// courant et à SYSTEM, pour lui et tout ce qu'il contiendra
+#[cfg(windows)]
+fn restrict_acl(path: &Path) -> io::Result<()> {
+    let user = std::env::var("USERNAME").map_err(|_| io::Error::other("Variable USERNAME absente"))?;
+    let user = match std::env::var("USERDOMAIN") {
+        Ok(domain) if !domain.is_empty() => format!(r"{}\{}", domain, user),
+        _ => user,
+    };
+    let status = std::process::Command::new("icacls")
+        .arg(path)
+        .args(["/inheritance:r", "/grant:r"])
+        .arg(format!("{}:(OI)(CI)F", user))
+        .args(["/grant:r", "*S-1-5-18:(OI)(CI)F"])
+        .stdout(std::process::Stdio::null())
+        .status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("icacls n'a pas pu restreindre les droits de {}", path.display())));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_extended_length_paths() {
+        assert_eq!(extend(r"C:\Users\Jean Dupont\AppData\Roaming\keeprust\keeprust.krs").as_deref(),
+            Some(r"\\?\C:\Users\Jean Dupont\AppData\Roaming\keeprust\keeprust.krs"));
+        assert_eq!(extend(r"\\nas\coffres partagés\équipe.krs").as_deref(), Some(r"\\?\UNC\nas\coffres partagés\équipe.krs"));
+        assert_eq!(extend(r"\\?\C:\déjà\étendu.krs"), None);
+        assert_eq!(extend(r"keeprust.krs"), None);
+        assert_eq!(extend(r"C:relatif.krs"), None);
+        
+        let directory = std::env::temp_dir().join(format!("keeprust-paths-{}", std::process::id())).join("privé");
+        create_private_dir(&directory).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&directory).unwrap().permissions().mode() & 0o777, 0o700);
+        }
+        create_private_dir(&directory).unwrap();
+        fs::remove_dir_all(directory.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,181 @@
+use base64::{Engine as _, engine::general_purpose};
+use std::env;
+use std::io::{self, Write};
+use thiserror::Error;
+
+/// Nombre maximal de lignes de texte affichées
+pub const MAX_PREVIEW_LINES: usize = 200;
+
+/// Taille des blocs base64 envoyés au terminal (protocole graphique de kitty)
+const KITTY_CHUNK_LEN: usize = 4096;
+
+/// Erreurs lors de l'aperçu d'un contenu
+#[derive(Error, Debug)]
+pub enum PreviewError {
+    #[error("aperçu impossible: contenu binaire ({0} octets)")]
+    Binary(usize),
+    
+    #[error("aperçu impossible: ce terminal n'affiche pas les images ({0}); utilisez iTerm2, WezTerm ou kitty")]
+    ImageUnsupported(ImageFormat),
+    
+    #[error("erreur d'écriture: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Format d'image reconnu à sa signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+}
+
+impl std::fmt::Display for ImageFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageFormat::Png => write!(f, "PNG"),
+            ImageFormat::Jpeg => write!(f, "JPEG"),
+            ImageFormat::Gif => write!(f, "GIF"),
+            ImageFormat::Webp => write!(f, "WebP"),
+        }
+    }
+}
+
+/// Nature d'un contenu à prévisualiser
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentKind {
+    Text,
+    Image(ImageFormat),
+    Binary,
+}
+
+/// Protocole d'affichage d'images du terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageProtocol {
+    /// Images en ligne d'iTerm2 (aussi reconnu par WezTerm)
+    Iterm,
+    
+    /// Protocole graphique de kitty
+    Kitty,
+    
+    /// Aucun affichage d'images
+    None,
+}
+
+impl ImageProtocol {
+    /// Protocole du terminal courant, d'après ses variables d'environnement
+    pub fn detect() -> Self {
+        let term_program = env::var("TERM_PROGRAM").unwrap_or_default();
+        if matches!(term_program.as_str(), "iTerm.app" | "WezTerm") {
+            return ImageProtocol::Iterm;
+        }
+        if env::var_os("KITTY_WINDOW_ID").is_some() || env::var("TERM").is_ok_and(|term| term.contains("kitty")) {
+            return ImageProtocol::Kitty;
+        }
+        ImageProtocol::None
+    }
+}
+
+/// Détermine la nature d'un contenu : image d'après sa signature, texte s'il est en UTF-8
+/// sans caractères de contrôle autres que les espacements, binaire sinon
+pub fn detect(bytes: &[u8]) -> ContentKind {
+    let image = if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some(ImageFormat::Png)
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some(ImageFormat::Jpeg)
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some(ImageFormat::Gif)
+    } else if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        Some(ImageFormat::Webp)
+    } else {
+        None
+    };
+    if let Some(format) = image {
+        return ContentKind::Image(format);
+    }
+    
+    match std::str::from_utf8(bytes) {
+        Ok(text) if !text.chars().any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t')) => ContentKind::Text,
+        _ => ContentKind::Binary,
+    }
+}
+
+/// Écrit un texte ligne par ligne (au plus `MAX_PREVIEW_LINES` lignes)
+fn render_text(text: &str, out: &mut impl Write) -> io::Result<()> {
+    let mut lines = text.lines();
+    for line in lines.by_ref().take(MAX_PREVIEW_LINES) {
+        writeln!(out, "{}", line.trim_end_matches('\r'))?;
+    }
+    
+    let remaining = lines.count();
+    if remaining > 0 {
+        writeln!(out, "... ({} ligne(s) de plus)", remaining)?;
+    }
+    Ok(())
+}
+
+/// Écrit une image avec le protocole du terminal
+fn render_image(name: &str, bytes: &[u8], protocol: ImageProtocol, out: &mut impl Write) -> io::Result<()> {
+    let data = general_purpose::STANDARD.encode(bytes);
+    match protocol {
+        ImageProtocol::Iterm => {
+            let name = general_purpose::STANDARD.encode(name);
+            writeln!(out, "\x1b]1337;File=name={};size={};inline=1:{}\x07", name, bytes.len(), data)
+        }
+        ImageProtocol::Kitty => {
+            let chunks: Vec<_> = data.as_bytes().chunks(KITTY_CHUNK_LEN).collect();
+            for (index, chunk) in chunks.iter().enumerate() {
+                let more = u8::from(index + 1 < chunks.len());
+                let chunk = std::str::from_utf8(chunk).expect("base64 est en ASCII");
+                if index == 0 {
+                    write!(out, "\x1b_Gf=100,a=T,m={};{}\x1b\\", more, chunk)?;
+                } else {
+                    write!(out, "\x1b_Gm={};{}\x1b\\", more, chunk)?;
+                }
+            }
+            writeln!(out)
+        }
+        ImageProtocol::None => Ok(()),
+    }
+}
+
+/// Affiche un contenu déchiffré sans jamais l'écrire sur le disque : texte en ligne, image avec
+/// le protocole du terminal s'il en dispose.
+///
+/// Les caractères de contrôle sont refusés pour le texte : un fichier ne peut pas envoyer de
+/// séquences d'échappement au terminal.
+pub fn render(name: &str, bytes: &[u8], protocol: ImageProtocol, out: &mut impl Write) -> Result<ContentKind, PreviewError> {
+    let kind = detect(bytes);
+    match kind {
+        ContentKind::Text => render_text(std::str::from_utf8(bytes).expect("texte détecté"), out)?,
+        ContentKind::Image(format) if protocol == ImageProtocol::None => return Err(PreviewError::ImageUnsupported(format)),
+        ContentKind::Image(_) => render_image(name, bytes, protocol, out)?,
+        ContentKind::Binary => return Err(PreviewError::Binary(bytes.len())),
+    }
+    Ok(kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_detect_and_render() {
+        assert_eq!(detect(b"\x89PNG\r\n\x1a\n\0\0"), ContentKind::Image(ImageFormat::Png));
+        assert_eq!(detect("Code PIN: 1234\r\n".as_bytes()), ContentKind::Text);
+        assert_eq!(detect(b"titre\x1b]0;pirate\x07"), ContentKind::Binary);
+        assert_eq!(detect(&[0xC3, 0x28]), ContentKind::Binary);
+        
+        let mut out = Vec::new();
+        render("a.txt", b"un\r\ndeux\n", ImageProtocol::None, &mut out).unwrap();
+        assert_eq!(out, b"un\ndeux\n");
+        
+        let png = b"\x89PNG\r\n\x1a\n";
+        assert!(matches!(render("a.png", png, ImageProtocol::None, &mut Vec::new()), Err(PreviewError::ImageUnsupported(ImageFormat::Png))));
+        
+        let mut out = Vec::new();
+        render("a.png", png, ImageProtocol::Iterm, &mut out).unwrap();
+        assert!(out.starts_with(b"\x1b]1337;File=name=YS5wbmc=;size=8;inline=1:"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,85 @@
+use std::fmt;
+
+/// Texte affiché à la place d'un secret masqué (longueur fixe : ne révèle pas celle du secret)
+pub const MASK: &str = "********";
+
+/// Nombre de caractères visibles avec `Redactor::LastFour`
+const VISIBLE_SUFFIX: usize = 4;
+
+/// Longueur minimale d'un secret pour en montrer la fin; les secrets plus courts sont masqués
+/// entièrement
+const MIN_PARTIAL_LEN: usize = 8;
+
+/// Manière d'afficher un secret (mot de passe, jeton, numéro de carte, champ protégé).
+///
+/// Tous les affichages passent par un `Redactor` : `show`, les listes, les formats de
+/// débogage (journaux, messages d'erreur) et les sorties structurées.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Redactor {
+    /// Masqué entièrement
+    #[default]
+    Mask,
+    
+    /// Seuls les 4 derniers caractères sont visibles (ex: ••••••1234)
+    LastFour,
+    
+    /// Affiché en clair
+    Reveal,
+}
+
+impl Redactor {
+    /// Texte à afficher pour un secret
+    pub fn redact(&self, secret: &str) -> String {
+        match self {
+            Redactor::Mask => MASK.to_string(),
+            Redactor::LastFour => {
+                let len = secret.chars().count();
+                if len < MIN_PARTIAL_LEN {
+                    return MASK.to_string();
+                }
+                let suffix: String = secret.chars().skip(len - VISIBLE_SUFFIX).collect();
+                format!("{}{}", "•".repeat(len - VISIBLE_SUFFIX), suffix)
+            }
+            Redactor::Reveal => secret.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Redactor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Redactor::Mask => write!(f, "mask"),
+            Redactor::LastFour => write!(f, "last4"),
+            Redactor::Reveal => write!(f, "reveal"),
+        }
+    }
+}
+
+impl std::str::FromStr for Redactor {
+    type Err = String;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "mask" | "masque" => Ok(Redactor::Mask),
+            "last4" | "fin" => Ok(Redactor::LastFour),
+            "reveal" | "clair" => Ok(Redactor::Reveal),
+            other => Err(format!("Affichage inconnu: '{}' (mask, last4 ou reveal)", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_redact_modes() {
+        assert_eq!(Redactor::Mask.redact("4970101234567890"), MASK);
+        assert_eq!(Redactor::LastFour.redact("4970101234567890"), "••••••••••••7890");
+        assert_eq!(Redactor::LastFour.redact("pin1234"), MASK);
+        assert_eq!(Redactor::LastFour.redact("clé-été-2024"), "••••••••2024");
+        assert_eq!(Redactor::Reveal.redact("secret"), "secret");
+        assert_eq!("last4".parse::<Redactor>(), Ok(Redactor::LastFour));
+        assert!("partiel".parse::<Redactor>().is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Active la sortie accessible (--plain) : libellés explicites à la place des symboles, sans
+/// couleur ni animation
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+/// Indique si la sortie accessible est demandée
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// Symbole en tête d'un message, remplacé par un libellé explicite en sortie accessible (les
+/// lecteurs d'écran lisent mal les emojis, les terminaux simples ne les affichent pas)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Symbol {
+    Warning,
+    Success,
+    Failure,
+    Alert,
+    Tip,
+
+    /// Symbole décoratif d'un titre, simplement omis en sortie accessible
+    Heading(&'static str),
+}
+
+impl Symbol {
+    fn plain(self) -> &'static str {
+        match self {
+            Symbol::Warning => "Attention: ",
+            Symbol::Success => "OK: ",
+            Symbol::Failure => "Échec: ",
+            Symbol::Alert => "Alerte: ",
+            Symbol::Tip => "Conseil: ",
+            Symbol::Heading(_) => "",
+        }
+    }
+
+    fn emoji(self) -> &'static str {
+        match self {
+            Symbol::Warning => "⚠️",
+            Symbol::Success => "✅",
+            Symbol::Failure => "❌",
+            Symbol::Alert => "🚨",
+            Symbol::Tip => "💡",
+            Symbol::Heading(emoji) => emoji,
+        }
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            _ if is_plain() => f.write_str(self.plain()),
+            // Le symbole d'avertissement déborde sur la colonne suivante dans la plupart des terminaux
+            Symbol::Warning => write!(f, "{}  ", self.emoji()),
+            _ => write!(f, "{} ", self.emoji()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbols_and_plain_labels() {
+        assert_eq!(Symbol::Heading("🔍").emoji(), "🔍");
+        assert_eq!(Symbol::Warning.plain(), "Attention: ");
+        assert_eq!(Symbol::Heading("🔍").plain(), "");
+        assert_eq!(format!("{}Analyse", Symbol::Heading("🔍")), if is_plain() { "Analyse" } else { "🔍 Analyse" });
+    }
+}
@@ -0,0 +1,77 @@
+use std::env;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Identifiant des notifications de keeprust : chaque notification remplace la précédente
+const NOTIFICATION_ID: &str = "keeprust";
+
+/// Indique si keeprust tourne dans Termux (Android)
+pub fn is_termux() -> bool {
+    env::var_os("TERMUX_VERSION").is_some() || env::var("PREFIX").is_ok_and(|prefix| is_termux_prefix(&prefix))
+}
+
+/// Préfixe d'installation des paquets Termux (/data/data/com.termux/files/usr)
+fn is_termux_prefix(prefix: &str) -> bool {
+    prefix.contains("/com.termux/")
+}
+
+/// Stockage partagé d'Android (~/storage/shared, créé par termux-setup-storage), accessible aux
+/// autres applications : fichiers échangés, bases synchronisées (Syncthing...)
+pub fn shared_storage() -> Option<PathBuf> {
+    let shared = PathBuf::from(env::var_os("HOME")?).join("storage").join("shared");
+    shared.is_dir().then_some(shared)
+}
+
+/// Message d'une commande Termux:API introuvable
+fn missing(command: &str, error: std::io::Error) -> String {
+    format!("{} introuvable ({}): installez le paquet termux-api et l'application Termux:API", command, error)
+}
+
+/// Remplace le contenu du presse-papiers d'Android (termux-clipboard-set)
+pub fn set_clipboard(text: &str) -> Result<(), String> {
+    let mut child = Command::new("termux-clipboard-set")
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| missing("termux-clipboard-set", e))?;
+    // Le texte passe par l'entrée standard, jamais par les arguments (visibles des autres processus)
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes()).map_err(|e| e.to_string())?;
+    }
+    let status = child.wait().map_err(|e| e.to_string())?;
+    if !status.success() {
+        return Err("termux-clipboard-set a échoué".to_string());
+    }
+    Ok(())
+}
+
+/// Lit le contenu du presse-papiers d'Android (termux-clipboard-get)
+pub fn clipboard() -> Result<String, String> {
+    let output = Command::new("termux-clipboard-get").output().map_err(|e| missing("termux-clipboard-get", e))?;
+    if !output.status.success() {
+        return Err("termux-clipboard-get a échoué".to_string());
+    }
+    String::from_utf8(output.stdout).map_err(|_| "Presse-papiers illisible (texte non UTF-8)".to_string())
+}
+
+/// Affiche une notification Android (termux-notification), visible hors du terminal; sans effet
+/// si Termux:API n'est pas installé
+pub fn notify(title: &str, content: &str) {
+    let _ = Command::new("termux-notification")
+        .args(["--id", NOTIFICATION_ID, "--title", title, "--content", content])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_termux_prefix() {
+        assert!(is_termux_prefix("/data/data/com.termux/files/usr"));
+        assert!(!is_termux_prefix("/usr/local"));
+        assert!(!is_termux_prefix("/opt/com.termux.backup"));
+    }
+}